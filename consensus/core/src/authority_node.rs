@@ -2,13 +2,14 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{sync::Arc, time::Instant};
+use std::{path::Path, sync::Arc, time::Instant};
 
 use consensus_config::{AuthorityIndex, Committee, NetworkKeyPair, Parameters, ProtocolKeyPair};
 use iota_protocol_config::{ConsensusNetwork, ProtocolConfig};
 use parking_lot::RwLock;
 use prometheus::Registry;
 use tracing::{info, warn};
+use typed_store::TypedStoreError;
 
 use crate::{
     CommitConsumer,
@@ -93,6 +94,14 @@ impl ConsensusAuthority {
         }
     }
 
+    /// Creates a consistent checkpoint of the consensus store at `path`,
+    /// without stopping the authority.
+    pub fn checkpoint_store(&self, path: &Path) -> Result<(), TypedStoreError> {
+        match self {
+            Self::WithTonic(authority) => authority.store.checkpoint(path),
+        }
+    }
+
     #[cfg(test)]
     fn context(&self) -> &Arc<Context> {
         match self {
@@ -124,6 +133,7 @@ where
     broadcaster: Option<Broadcaster>,
     subscriber: Option<Subscriber<N::Client, AuthorityService<ChannelCoreThreadDispatcher>>>,
     network_manager: N,
+    store: Arc<RocksDBStore>,
     #[cfg(test)]
     sync_last_known_own_block: bool,
 }
@@ -269,7 +279,7 @@ where
             core_dispatcher,
             signals_receivers.block_broadcast_receiver(),
             dag_state.clone(),
-            store,
+            store.clone(),
         ));
 
         let subscriber = if N::Client::SUPPORT_STREAMING {
@@ -307,6 +317,7 @@ where
             broadcaster,
             subscriber,
             network_manager,
+            store,
             #[cfg(test)]
             sync_last_known_own_block,
         }
@@ -2,13 +2,13 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{ops::Bound::Included, time::Duration};
+use std::{ops::Bound::Included, path::Path, time::Duration};
 
 use bytes::Bytes;
 use consensus_config::AuthorityIndex;
 use iota_macros::fail_point;
 use typed_store::{
-    Map as _,
+    Map as _, TypedStoreError,
     metrics::SamplingInterval,
     reopen,
     rocks::{DBMap, MetricConf, ReadWriteOptions, default_db_options, open_cf_opts},
@@ -90,6 +90,12 @@ impl RocksDBStore {
             commit_info,
         }
     }
+
+    /// Creates a consistent RocksDB checkpoint of the store at `path`, while
+    /// the store keeps being written to.
+    pub(crate) fn checkpoint(&self, path: &Path) -> Result<(), TypedStoreError> {
+        self.blocks.checkpoint_db(path)
+    }
 }
 
 impl Store for RocksDBStore {
@@ -13,6 +13,7 @@ pub struct AnalyticsMetrics {
     pub total_received: IntCounterVec,
     pub last_uploaded_checkpoint: IntGaugeVec,
     pub max_checkpoint_on_store: IntGaugeVec,
+    pub lake_compacted_files: IntCounterVec,
 }
 
 impl AnalyticsMetrics {
@@ -39,6 +40,13 @@ impl AnalyticsMetrics {
                 registry,
             )
             .unwrap(),
+            lake_compacted_files: register_int_counter_vec_with_registry!(
+                "lake_compacted_files",
+                "Number of local lake files merged by compaction.",
+                &["data_type"],
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
@@ -23,13 +23,17 @@ use tracing::{error, info};
 
 use crate::{
     AnalyticsIndexerConfig, EPOCH_DIR_PREFIX, FileMetadata, MaxCheckpointReader, ParquetSchema,
-    analytics_metrics::AnalyticsMetrics, handlers::AnalyticsHandler, join_paths,
+    analytics_metrics::AnalyticsMetrics,
+    handlers::AnalyticsHandler,
+    join_paths,
+    lake::{Compactor, LocalLake},
     writers::AnalyticsWriter,
 };
 
 struct State<S: Serialize + ParquetSchema> {
     current_epoch: u64,
     current_checkpoint_range: Range<u64>,
+    current_range_start_timestamp_ms: u64,
     last_commit_instant: Instant,
     num_checkpoint_iterations: u64,
     writer: Box<dyn AnalyticsWriter<S>>,
@@ -85,6 +89,9 @@ impl<S: Serialize + ParquetSchema + 'static> Worker for AnalyticsProcessor<S> {
             .total_received
             .with_label_values(&[self.name()])
             .inc();
+        if state.current_checkpoint_range.is_empty() {
+            state.current_range_start_timestamp_ms = timestamp;
+        }
         self.handler.process_checkpoint(checkpoint_data).await?;
         let rows = self.handler.read().await?;
         state.writer.write(&rows)?;
@@ -113,22 +120,50 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
             ..Default::default()
         };
         let local_object_store = local_store_config.make()?;
-        let remote_object_store = config.remote_store_config.make()?;
         let (kill_sender, kill_receiver) = oneshot::channel::<()>();
         let (sender, receiver) = mpsc::channel::<FileMetadata>(100);
         let name: String = handler.name().parse()?;
         let checkpoint_dir = config.checkpoint_dir.clone();
         let cloned_metrics = metrics.clone();
-        tokio::task::spawn(Self::start_syncing_with_remote(
-            remote_object_store,
-            local_object_store.clone(),
-            checkpoint_dir,
-            config.remote_store_path_prefix.clone(),
-            receiver,
-            kill_receiver,
-            cloned_metrics,
-            name.clone(),
-        ));
+        if let Some(lake_dir) = &config.local_lake_dir {
+            let lake = LocalLake::new(lake_dir, config.file_type, config.file_format)?;
+            let schema_version = lake.register_schema(S::schema(), next_checkpoint_seq_num)?;
+            info!(
+                "Writing {name} to local lake {} with schema version {}",
+                lake_dir.display(),
+                schema_version.version
+            );
+            if let Some(compactor) =
+                lake.compactor(config.lake_compaction_min_file_size_mb * 1024 * 1024)
+            {
+                tokio::task::spawn(Self::start_lake_compaction(
+                    compactor,
+                    Duration::from_secs(config.lake_compaction_interval_s),
+                    metrics.clone(),
+                    name.clone(),
+                ));
+            }
+            tokio::task::spawn(Self::start_syncing_with_lake(
+                lake,
+                checkpoint_dir,
+                receiver,
+                kill_receiver,
+                cloned_metrics,
+                name.clone(),
+            ));
+        } else {
+            let remote_object_store = config.remote_store_config.make()?;
+            tokio::task::spawn(Self::start_syncing_with_remote(
+                remote_object_store,
+                local_object_store.clone(),
+                checkpoint_dir,
+                config.remote_store_path_prefix.clone(),
+                receiver,
+                kill_receiver,
+                cloned_metrics,
+                name.clone(),
+            ));
+        }
         let (max_checkpoint_sender, max_checkpoint_receiver) = oneshot::channel::<()>();
         tokio::task::spawn(Self::setup_max_checkpoint_metrics_updates(
             max_checkpoint_reader,
@@ -139,6 +174,7 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
         let state = State {
             current_epoch: 0,
             current_checkpoint_range: next_checkpoint_seq_num..next_checkpoint_seq_num,
+            current_range_start_timestamp_ms: 0,
             last_commit_instant: Instant::now(),
             num_checkpoint_iterations: 0,
             writer,
@@ -167,6 +203,7 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
                 self.config.file_format,
                 state.current_epoch,
                 state.current_checkpoint_range.clone(),
+                state.current_range_start_timestamp_ms,
             );
             self.sender.send(file_metadata).await?;
             tokio::task::yield_now().await;
@@ -251,6 +288,56 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
         Ok(())
     }
 
+    async fn start_syncing_with_lake(
+        lake: LocalLake,
+        local_staging_root_dir: PathBuf,
+        mut file_recv: mpsc::Receiver<FileMetadata>,
+        mut recv: oneshot::Receiver<()>,
+        metrics: AnalyticsMetrics,
+        name: String,
+    ) -> Result<()> {
+        loop {
+            tokio::select! {
+                _ = &mut recv => break,
+                file = file_recv.recv() => {
+                    if let Some(file_metadata) = file {
+                        info!("Received {name} file with checkpoints: {:?}", &file_metadata.checkpoint_seq_range);
+                        let checkpoint_seq_num = file_metadata.checkpoint_seq_range.end;
+                        let staging_file = path_to_filesystem(local_staging_root_dir.clone(), &file_metadata.file_path())?;
+                        lake.install(&staging_file, &file_metadata)
+                            .expect("Installing file into local lake should not fail");
+                        metrics.last_uploaded_checkpoint.with_label_values(&[&name]).set(checkpoint_seq_num as i64);
+                    } else {
+                        info!("Terminating local lake sync loop");
+                        break;
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
+
+    async fn start_lake_compaction(
+        compactor: Compactor,
+        interval: Duration,
+        metrics: AnalyticsMetrics,
+        name: String,
+    ) -> Result<()> {
+        let compactor = Arc::new(compactor);
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            interval.tick().await;
+            let cloned_compactor = compactor.clone();
+            match tokio::task::spawn_blocking(move || cloned_compactor.compact()).await? {
+                Ok(num_compacted) => metrics
+                    .lake_compacted_files
+                    .with_label_values(&[&name])
+                    .inc_by(num_compacted as u64),
+                Err(err) => error!("Failed to compact local lake for {name} with err: {err}"),
+            }
+        }
+    }
+
     async fn setup_max_checkpoint_metrics_updates(
         max_checkpoint_reader: Box<dyn MaxCheckpointReader>,
         analytics_metrics: AnalyticsMetrics,
@@ -0,0 +1,182 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    fs::{self, File},
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use arrow_array::RecordBatch;
+use parquet::{
+    arrow::{ArrowWriter, arrow_reader::ParquetRecordBatchReaderBuilder},
+    basic::Compression,
+    file::properties::WriterProperties,
+};
+use tracing::info;
+
+use crate::{
+    FileFormat,
+    lake::partition::{data_file_name, list_partition_dirs, parse_data_file_name},
+};
+
+struct DataFile {
+    path: PathBuf,
+    checkpoint_range: Range<u64>,
+    size: u64,
+}
+
+/// Merges small parquet files of the same partition into larger ones.
+///
+/// Only runs of files with contiguous checkpoint ranges and identical arrow
+/// schemas are merged, so the merged file is named after the union of their
+/// ranges and the lake can still be used to resume indexing.
+pub(crate) struct Compactor {
+    table_dir: PathBuf,
+    min_file_size_bytes: u64,
+}
+
+impl Compactor {
+    pub(crate) fn new(table_dir: PathBuf, min_file_size_bytes: u64) -> Self {
+        Self {
+            table_dir,
+            min_file_size_bytes,
+        }
+    }
+
+    /// Compacts all partitions of the table, returning the number of files
+    /// that were merged away.
+    pub(crate) fn compact(&self) -> Result<usize> {
+        let mut num_removed = 0;
+        for partition_dir in list_partition_dirs(&self.table_dir)? {
+            num_removed += self.compact_partition(&partition_dir)?;
+        }
+        Ok(num_removed)
+    }
+
+    fn compact_partition(&self, dir: &Path) -> Result<usize> {
+        let mut files = vec![];
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let name = entry.file_name();
+            let Some(checkpoint_range) = name
+                .to_str()
+                .and_then(|name| parse_data_file_name(FileFormat::PARQUET, name))
+            else {
+                continue;
+            };
+            files.push(DataFile {
+                path: entry.path(),
+                checkpoint_range,
+                size: entry.metadata()?.len(),
+            });
+        }
+        files.sort_by_key(|file| {
+            (
+                file.checkpoint_range.start,
+                u64::MAX - file.checkpoint_range.end,
+            )
+        });
+
+        let mut num_removed = remove_covered_files(&mut files)?;
+        let mut run: Vec<DataFile> = vec![];
+        for file in files {
+            let extends_run = run
+                .last()
+                .is_some_and(|last| last.checkpoint_range.end == file.checkpoint_range.start);
+            if file.size >= self.min_file_size_bytes || !extends_run {
+                num_removed += merge_run(dir, std::mem::take(&mut run))?;
+            }
+            if file.size < self.min_file_size_bytes {
+                run.push(file);
+            }
+        }
+        num_removed += merge_run(dir, run)?;
+        Ok(num_removed)
+    }
+}
+
+/// Removes files whose checkpoint range is covered by another file, which
+/// happens if the indexer stopped after a merged file was written but before
+/// its inputs were removed. Expects files sorted by range start and then by
+/// descending range end.
+fn remove_covered_files(files: &mut Vec<DataFile>) -> Result<usize> {
+    let mut kept: Vec<DataFile> = Vec::with_capacity(files.len());
+    let mut num_removed = 0;
+    for file in files.drain(..) {
+        if kept
+            .last()
+            .is_some_and(|last| last.checkpoint_range.end >= file.checkpoint_range.end)
+        {
+            fs::remove_file(&file.path)?;
+            num_removed += 1;
+        } else {
+            kept.push(file);
+        }
+    }
+    *files = kept;
+    Ok(num_removed)
+}
+
+/// Merges a run of contiguous files, splitting it wherever the schema changes.
+fn merge_run(dir: &Path, run: Vec<DataFile>) -> Result<usize> {
+    let mut num_removed = 0;
+    let mut batches: Vec<RecordBatch> = vec![];
+    let mut inputs: Vec<DataFile> = vec![];
+    for file in run {
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&file.path)?)?.build()?;
+        let file_batches = reader.collect::<Result<Vec<_>, _>>()?;
+        let schema_changed = match (batches.first(), file_batches.first()) {
+            (Some(current), Some(next)) => current.schema() != next.schema(),
+            _ => false,
+        };
+        if schema_changed {
+            num_removed += write_merged(
+                dir,
+                std::mem::take(&mut inputs),
+                std::mem::take(&mut batches),
+            )?;
+        }
+        batches.extend(file_batches);
+        inputs.push(file);
+    }
+    num_removed += write_merged(dir, inputs, batches)?;
+    Ok(num_removed)
+}
+
+fn write_merged(dir: &Path, inputs: Vec<DataFile>, batches: Vec<RecordBatch>) -> Result<usize> {
+    let (Some(first), Some(last)) = (inputs.first(), inputs.last()) else {
+        return Ok(0);
+    };
+    if inputs.len() < 2 {
+        return Ok(0);
+    }
+    let checkpoint_range = first.checkpoint_range.start..last.checkpoint_range.end;
+    let file_name = data_file_name(FileFormat::PARQUET, checkpoint_range);
+    let tmp_path = dir.join(format!(".{file_name}.tmp"));
+    if let Some(schema) = batches.first().map(|batch| batch.schema()) {
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        let mut writer = ArrowWriter::try_new(File::create(&tmp_path)?, schema, Some(properties))?;
+        for batch in &batches {
+            writer.write(batch)?;
+        }
+        writer.close()?;
+    } else {
+        // All inputs are empty, keep a single one of them as placeholder so the
+        // checkpoint range stays covered.
+        fs::copy(&first.path, &tmp_path)?;
+    }
+    fs::rename(&tmp_path, dir.join(&file_name))?;
+    for input in &inputs {
+        fs::remove_file(&input.path)?;
+    }
+    info!(
+        "Compacted {} files into {}",
+        inputs.len(),
+        dir.join(file_name).display()
+    );
+    Ok(inputs.len())
+}
@@ -0,0 +1,150 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Result, anyhow};
+use iota_types::messages_checkpoint::CheckpointSequenceNumber;
+
+use crate::{
+    FileFormat, FileMetadata, FileType,
+    lake::{
+        compaction::Compactor,
+        partition::{HivePartition, list_partition_dirs, parse_data_file_name},
+        schema_registry::{SchemaRegistry, SchemaVersion},
+    },
+};
+
+/// Local data lake holding the files of a single table as Hive-style
+/// partitions, which can be queried directly with DuckDB or Polars, e.g.
+/// `read_parquet('<lake>/objects/**/*.parquet', hive_partitioning = true)`.
+pub struct LocalLake {
+    root: PathBuf,
+    file_type: FileType,
+    file_format: FileFormat,
+}
+
+impl LocalLake {
+    pub fn new(root: &Path, file_type: FileType, file_format: FileFormat) -> Result<Self> {
+        fs::create_dir_all(root)?;
+        Ok(Self {
+            root: root.to_path_buf(),
+            file_type,
+            file_format,
+        })
+    }
+
+    fn table_name(&self) -> String {
+        self.file_type.dir_prefix().to_string()
+    }
+
+    fn table_dir(&self) -> PathBuf {
+        self.root.join(self.table_name())
+    }
+
+    /// Registers the columns of the table in the schema registry of the lake.
+    pub fn register_schema(
+        &self,
+        columns: Vec<String>,
+        first_checkpoint: CheckpointSequenceNumber,
+    ) -> Result<SchemaVersion> {
+        SchemaRegistry::new(&self.root).register(&self.table_name(), columns, first_checkpoint)
+    }
+
+    /// Moves a flushed staging file into its partition of the lake. The file
+    /// only becomes visible under its final name once it was fully copied.
+    pub fn install(&self, staging_file: &Path, file_metadata: &FileMetadata) -> Result<PathBuf> {
+        let partition = HivePartition::new(file_metadata.epoch_num, file_metadata.timestamp_ms)?;
+        let dest = self.root.join(partition.file_path(
+            self.file_type,
+            self.file_format,
+            file_metadata.checkpoint_seq_range.clone(),
+        ));
+        let dir = dest.parent().ok_or(anyhow!("Bad directory path"))?;
+        fs::create_dir_all(dir)?;
+        let file_name = dest
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or(anyhow!("Bad file name"))?;
+        let tmp_path = dir.join(format!(".{file_name}.tmp"));
+        fs::copy(staging_file, &tmp_path)?;
+        fs::rename(&tmp_path, &dest)?;
+        fs::remove_file(staging_file)?;
+        Ok(dest)
+    }
+
+    /// Returns the next checkpoint to index, based on the files already in the
+    /// lake.
+    pub fn next_checkpoint_seq_num(&self) -> Result<CheckpointSequenceNumber> {
+        let mut next_checkpoint_seq_num = 0;
+        for partition_dir in list_partition_dirs(&self.table_dir())? {
+            for entry in fs::read_dir(partition_dir)? {
+                let name = entry?.file_name();
+                if let Some(range) = name
+                    .to_str()
+                    .and_then(|name| parse_data_file_name(self.file_format, name))
+                {
+                    next_checkpoint_seq_num = next_checkpoint_seq_num.max(range.end);
+                }
+            }
+        }
+        Ok(next_checkpoint_seq_num)
+    }
+
+    /// Returns the compactor of the table if its file format supports
+    /// compaction.
+    pub(crate) fn compactor(&self, min_file_size_bytes: u64) -> Option<Compactor> {
+        match self.file_format {
+            FileFormat::PARQUET => Some(Compactor::new(self.table_dir(), min_file_size_bytes)),
+            // CSV files carry no schema, so files of different schema versions
+            // can't be told apart.
+            FileFormat::CSV => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::{FileFormat, FileMetadata, FileType, lake::LocalLake};
+
+    #[test]
+    fn install_and_resume() {
+        let staging = tempfile::tempdir().unwrap();
+        let lake_dir = tempfile::tempdir().unwrap();
+        let lake = LocalLake::new(lake_dir.path(), FileType::Checkpoint, FileFormat::CSV).unwrap();
+        assert_eq!(lake.next_checkpoint_seq_num().unwrap(), 0);
+
+        for (range, timestamp_ms) in [(0..10, 1_727_784_000_000), (10..25, 1_727_870_400_000)] {
+            let staging_file = staging.path().join("staged.csv");
+            fs::write(&staging_file, b"row").unwrap();
+            let file_metadata = FileMetadata {
+                file_type: FileType::Checkpoint,
+                file_format: FileFormat::CSV,
+                epoch_num: 3,
+                checkpoint_seq_range: range,
+                timestamp_ms,
+            };
+            lake.install(&staging_file, &file_metadata).unwrap();
+            assert!(!staging_file.exists());
+        }
+
+        assert!(
+            lake_dir
+                .path()
+                .join("checkpoints/epoch=3/date=2024-10-01/0_10.csv")
+                .exists()
+        );
+        assert!(
+            lake_dir
+                .path()
+                .join("checkpoints/epoch=3/date=2024-10-02/10_25.csv")
+                .exists()
+        );
+        assert_eq!(lake.next_checkpoint_seq_num().unwrap(), 25);
+    }
+}
@@ -0,0 +1,12 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+mod compaction;
+mod local_lake;
+mod partition;
+mod schema_registry;
+
+pub(crate) use compaction::Compactor;
+pub use local_lake::LocalLake;
+pub use partition::HivePartition;
+pub use schema_registry::{SchemaRegistry, SchemaVersion, TableSchemaHistory};
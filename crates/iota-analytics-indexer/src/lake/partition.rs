@@ -0,0 +1,156 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, NaiveDate};
+use iota_types::base_types::EpochId;
+
+use crate::{FileFormat, FileType};
+
+const EPOCH_PARTITION_KEY: &str = "epoch";
+const DATE_PARTITION_KEY: &str = "date";
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Hive-style partition of a table in the local lake, i.e.
+/// `<table>/epoch=<epoch>/date=<yyyy-mm-dd>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HivePartition {
+    pub epoch: EpochId,
+    pub date: NaiveDate,
+}
+
+impl HivePartition {
+    /// Builds the partition of a file from its epoch and the timestamp of the
+    /// first checkpoint it contains.
+    pub fn new(epoch: EpochId, timestamp_ms: u64) -> Result<Self> {
+        let date = DateTime::from_timestamp_millis(timestamp_ms as i64)
+            .ok_or(anyhow!("Invalid checkpoint timestamp: {timestamp_ms}"))?
+            .date_naive();
+        Ok(Self { epoch, date })
+    }
+
+    /// Directory of the partition relative to the lake root.
+    pub fn dir(&self, file_type: FileType) -> PathBuf {
+        PathBuf::from(file_type.dir_prefix().to_string())
+            .join(format!("{EPOCH_PARTITION_KEY}={}", self.epoch))
+            .join(format!(
+                "{DATE_PARTITION_KEY}={}",
+                self.date.format(DATE_FORMAT)
+            ))
+    }
+
+    /// Path of a data file relative to the lake root.
+    pub fn file_path(
+        &self,
+        file_type: FileType,
+        file_format: FileFormat,
+        checkpoint_range: Range<u64>,
+    ) -> PathBuf {
+        self.dir(file_type)
+            .join(data_file_name(file_format, checkpoint_range))
+    }
+
+    /// Parses the partition from the two innermost directories of `dir`.
+    pub fn from_dir(dir: &Path) -> Option<Self> {
+        let date = dir.file_name()?.to_str()?;
+        let epoch = dir.parent()?.file_name()?.to_str()?;
+        let epoch = epoch
+            .strip_prefix(EPOCH_PARTITION_KEY)?
+            .strip_prefix('=')?
+            .parse()
+            .ok()?;
+        let date = date.strip_prefix(DATE_PARTITION_KEY)?.strip_prefix('=')?;
+        let date = NaiveDate::parse_from_str(date, DATE_FORMAT).ok()?;
+        Some(Self { epoch, date })
+    }
+}
+
+/// Name of a data file holding the given checkpoint range.
+pub(crate) fn data_file_name(file_format: FileFormat, checkpoint_range: Range<u64>) -> String {
+    format!(
+        "{}_{}.{}",
+        checkpoint_range.start,
+        checkpoint_range.end,
+        file_format.file_suffix()
+    )
+}
+
+/// Parses the checkpoint range out of a data file name, ignoring files of
+/// other formats and temporary files.
+pub(crate) fn parse_data_file_name(file_format: FileFormat, name: &str) -> Option<Range<u64>> {
+    let stem = name.strip_suffix(&format!(".{}", file_format.file_suffix()))?;
+    let (start, end) = stem.split_once('_')?;
+    Some(start.parse().ok()?..end.parse().ok()?)
+}
+
+/// Lists all partition directories of a table in the lake.
+pub(crate) fn list_partition_dirs(table_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut dirs = vec![];
+    if !table_dir.exists() {
+        return Ok(dirs);
+    }
+    for epoch_dir in std::fs::read_dir(table_dir)? {
+        let epoch_dir = epoch_dir?.path();
+        if !epoch_dir.is_dir() {
+            continue;
+        }
+        for date_dir in std::fs::read_dir(&epoch_dir)? {
+            let date_dir = date_dir?.path();
+            if date_dir.is_dir() && HivePartition::from_dir(&date_dir).is_some() {
+                dirs.push(date_dir);
+            }
+        }
+    }
+    dirs.sort();
+    Ok(dirs)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use crate::{
+        FileFormat, FileType,
+        lake::partition::{HivePartition, parse_data_file_name},
+    };
+
+    #[test]
+    fn partition_path_roundtrip() {
+        // 2024-10-01T12:00:00Z
+        let partition = HivePartition::new(12, 1_727_784_000_000).unwrap();
+        let path = partition.file_path(FileType::Object, FileFormat::PARQUET, 100..200);
+        assert_eq!(
+            path,
+            PathBuf::from("objects/epoch=12/date=2024-10-01/100_200.parquet")
+        );
+        assert_eq!(
+            HivePartition::from_dir(path.parent().unwrap()),
+            Some(partition)
+        );
+        assert_eq!(
+            HivePartition::from_dir(Path::new("objects/epoch_12/date=2024-10-01")),
+            None
+        );
+    }
+
+    #[test]
+    fn data_file_names() {
+        assert_eq!(
+            parse_data_file_name(FileFormat::PARQUET, "100_200.parquet"),
+            Some(100..200)
+        );
+        assert_eq!(
+            parse_data_file_name(FileFormat::PARQUET, ".100_200.parquet.tmp"),
+            None
+        );
+        assert_eq!(
+            parse_data_file_name(FileFormat::CSV, "100_200.parquet"),
+            None
+        );
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+const SCHEMA_REGISTRY_DIR: &str = "_schemas";
+
+/// A single version of a table schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaVersion {
+    pub version: u32,
+    pub columns: Vec<String>,
+    /// First checkpoint written with this version of the schema.
+    pub first_checkpoint: u64,
+}
+
+/// All schema versions of a table, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableSchemaHistory {
+    pub table: String,
+    pub versions: Vec<SchemaVersion>,
+}
+
+impl TableSchemaHistory {
+    pub fn latest(&self) -> Option<&SchemaVersion> {
+        self.versions.last()
+    }
+}
+
+/// Versioned JSON schema registry of the tables in a local lake.
+///
+/// Every table has a `_schemas/<table>.json` file next to its data. Columns
+/// may only be appended, so that readers which merge files by column name
+/// (e.g. DuckDB's `union_by_name`) keep working across versions.
+pub struct SchemaRegistry {
+    root: PathBuf,
+}
+
+impl SchemaRegistry {
+    pub fn new(lake_dir: &Path) -> Self {
+        Self {
+            root: lake_dir.join(SCHEMA_REGISTRY_DIR),
+        }
+    }
+
+    fn table_path(&self, table: &str) -> PathBuf {
+        self.root.join(format!("{table}.json"))
+    }
+
+    /// Loads the schema history of a table, empty if the table is unknown.
+    pub fn load(&self, table: &str) -> Result<TableSchemaHistory> {
+        let path = self.table_path(table);
+        if !path.exists() {
+            return Ok(TableSchemaHistory {
+                table: table.to_string(),
+                versions: vec![],
+            });
+        }
+        let bytes = fs::read(&path)?;
+        serde_json::from_slice(&bytes)
+            .with_context(|| format!("Failed to parse schema registry file {}", path.display()))
+    }
+
+    /// Registers the current columns of a table and returns the matching
+    /// schema version, creating a new one if columns were appended.
+    pub fn register(
+        &self,
+        table: &str,
+        columns: Vec<String>,
+        first_checkpoint: u64,
+    ) -> Result<SchemaVersion> {
+        let mut history = self.load(table)?;
+        let version = match history.latest() {
+            Some(latest) if latest.columns == columns => return Ok(latest.clone()),
+            Some(latest) => {
+                if !columns.starts_with(&latest.columns) {
+                    bail!(
+                        "Incompatible schema change for table {table}: columns may only be appended"
+                    );
+                }
+                latest.version + 1
+            }
+            None => 1,
+        };
+        let schema_version = SchemaVersion {
+            version,
+            columns,
+            first_checkpoint,
+        };
+        history.versions.push(schema_version.clone());
+        self.store(&history)?;
+        Ok(schema_version)
+    }
+
+    fn store(&self, history: &TableSchemaHistory) -> Result<()> {
+        fs::create_dir_all(&self.root)?;
+        let path = self.table_path(&history.table);
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, serde_json::to_vec_pretty(history)?)?;
+        fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lake::schema_registry::SchemaRegistry;
+
+    fn columns(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn appending_columns_creates_new_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = SchemaRegistry::new(dir.path());
+
+        let first = registry
+            .register("objects", columns(&["a", "b"]), 0)
+            .unwrap();
+        assert_eq!(first.version, 1);
+
+        let same = registry
+            .register("objects", columns(&["a", "b"]), 10)
+            .unwrap();
+        assert_eq!(same, first);
+
+        let second = registry
+            .register("objects", columns(&["a", "b", "c"]), 20)
+            .unwrap();
+        assert_eq!(second.version, 2);
+        assert_eq!(second.first_checkpoint, 20);
+
+        let history = registry.load("objects").unwrap();
+        assert_eq!(history.versions, vec![first, second]);
+    }
+
+    #[test]
+    fn removing_columns_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = SchemaRegistry::new(dir.path());
+        registry
+            .register("objects", columns(&["a", "b"]), 0)
+            .unwrap();
+        assert!(registry.register("objects", columns(&["b"]), 10).is_err());
+        assert!(
+            registry
+                .register("objects", columns(&["b", "a"]), 10)
+                .is_err()
+        );
+    }
+}
@@ -36,6 +36,7 @@ use crate::{
         transaction_objects_handler::TransactionObjectsHandler,
         wrapped_object_handler::WrappedObjectHandler,
     },
+    lake::LocalLake,
     tables::{
         CheckpointEntry, DynamicFieldEntry, EventEntry, InputObjectKind, MoveCallEntry,
         MovePackageEntry, ObjectEntry, ObjectStatus, OwnerType, TransactionEntry,
@@ -48,6 +49,7 @@ pub mod analytics_metrics;
 pub mod analytics_processor;
 pub mod errors;
 mod handlers;
+pub mod lake;
 mod package_store;
 pub mod tables;
 mod writers;
@@ -154,6 +156,18 @@ pub struct AnalyticsIndexerConfig {
     pub sf_checkpoint_col_id: Option<String>,
     #[clap(long, global = true)]
     pub report_sf_max_table_checkpoint: bool,
+    /// Local directory to write Hive-style partitioned files
+    /// (`epoch=<n>/date=<yyyy-mm-dd>`) to, instead of syncing them to the
+    /// remote object store.
+    #[clap(long, default_value = None, global = true)]
+    pub local_lake_dir: Option<PathBuf>,
+    /// Time in seconds between compactions of the local lake partitions.
+    #[clap(long, default_value = "300", global = true)]
+    pub lake_compaction_interval_s: u64,
+    /// Files in the local lake smaller than this size in mb are merged
+    /// during compaction.
+    #[clap(long, default_value = "64", global = true)]
+    pub lake_compaction_min_file_size_mb: u64,
 }
 
 #[async_trait::async_trait]
@@ -456,6 +470,8 @@ pub struct FileMetadata {
     pub file_format: FileFormat,
     pub epoch_num: u64,
     pub checkpoint_seq_range: Range<u64>,
+    /// Timestamp of the first checkpoint in the file.
+    pub timestamp_ms: u64,
 }
 
 impl FileMetadata {
@@ -464,12 +480,14 @@ impl FileMetadata {
         file_format: FileFormat,
         epoch_num: u64,
         checkpoint_seq_range: Range<u64>,
+        timestamp_ms: u64,
     ) -> FileMetadata {
         FileMetadata {
             file_type,
             file_format,
             epoch_num,
             checkpoint_seq_range,
+            timestamp_ms,
         }
     }
 
@@ -878,6 +896,8 @@ pub async fn get_starting_checkpoint_seq_num(
 ) -> Result<u64> {
     let checkpoint = if let Some(starting_checkpoint_seq_num) = config.starting_checkpoint_seq_num {
         starting_checkpoint_seq_num
+    } else if let Some(lake_dir) = &config.local_lake_dir {
+        LocalLake::new(lake_dir, file_type, config.file_format)?.next_checkpoint_seq_num()?
     } else {
         read_store_for_checkpoint(
             config.remote_store_config.clone(),
@@ -509,6 +509,7 @@ mod test {
                         txn_count_limit,
                     );
                 }
+                PerObjectCongestionControlMode::ExecutionTimeEstimate => unreachable!(),
             }
             config.set_max_deferral_rounds_for_congestion_control_for_testing(max_deferral_rounds);
             config
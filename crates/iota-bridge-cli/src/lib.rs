@@ -123,6 +123,18 @@ pub enum BridgeCommand {
         #[clap(long, default_value = "false")]
         ping: bool,
     },
+    /// View the processing history of bridge actions recorded by a bridge
+    /// node running the bridge client
+    #[clap(name = "view-action-history")]
+    ViewActionHistory {
+        /// Base URL of the bridge node server
+        #[clap(long = "bridge-node-url")]
+        bridge_node_url: String,
+        /// Digest of the action, in hex (`0x`-prefixed) or base58. If not
+        /// set, the history of all pending actions is shown
+        #[clap(long = "action-digest")]
+        action_digest: Option<String>,
+    },
     /// Client to facilitate and execute Bridge actions
     #[clap(name = "client")]
     Client {
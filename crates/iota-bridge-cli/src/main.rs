@@ -18,7 +18,7 @@ use iota_bridge::{
     eth_transaction_builder::build_eth_transaction,
     iota_client::IotaClient,
     iota_transaction_builder::build_iota_transaction,
-    types::BridgeActionType,
+    types::{BridgeActionHistoryResponse, BridgeActionType},
     utils::{
         EthBridgeContracts, examine_key, generate_bridge_authority_key_and_write_to_file,
         generate_bridge_client_key_and_write_to_file,
@@ -502,6 +502,30 @@ async fn main() -> anyhow::Result<()> {
             output_wrapper.inner = output;
             println!("{}", serde_json::to_string_pretty(&output_wrapper).unwrap());
         }
+        BridgeCommand::ViewActionHistory {
+            bridge_node_url,
+            action_digest,
+        } => {
+            let url = format!("{}/action_history", bridge_node_url.trim_end_matches('/'));
+            let client = reqwest::Client::builder()
+                .connect_timeout(Duration::from_secs(10))
+                .timeout(Duration::from_secs(10))
+                .build()?;
+            let histories = if let Some(action_digest) = action_digest {
+                let resp = client.get(format!("{url}/{action_digest}")).send().await?;
+                if !resp.status().is_success() {
+                    anyhow::bail!("Failed to get action history: {}", resp.text().await?);
+                }
+                vec![resp.json::<BridgeActionHistoryResponse>().await?]
+            } else {
+                let resp = client.get(url).send().await?;
+                if !resp.status().is_success() {
+                    anyhow::bail!("Failed to get action history: {}", resp.text().await?);
+                }
+                resp.json::<Vec<BridgeActionHistoryResponse>>().await?
+            };
+            println!("{}", serde_json::to_string_pretty(&histories).unwrap());
+        }
         BridgeCommand::Client { config_path, cmd } => {
             let config = BridgeCliConfig::load(config_path).expect("Couldn't load BridgeCliConfig");
             let config = LoadedBridgeCliConfig::load(config).await?;
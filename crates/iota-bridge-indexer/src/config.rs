@@ -2,7 +2,7 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::env;
+use std::{env, net::SocketAddr};
 
 use serde::{Deserialize, Serialize};
 
@@ -24,6 +24,9 @@ pub struct IndexerConfig {
     pub iota_rpc_url: Option<String>,
     pub back_fill_lot_size: u64,
     pub resume_from_checkpoint: Option<u64>,
+    /// Address to serve the transfer query API on, disabled if not set.
+    #[serde(default)]
+    pub api_listen_address: Option<SocketAddr>,
 }
 
 impl iota_config::Config for IndexerConfig {}
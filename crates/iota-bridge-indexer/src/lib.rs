@@ -11,6 +11,7 @@ use crate::models::{
     TokenTransferData as DBTokenTransferData,
 };
 
+pub mod api;
 pub mod config;
 pub mod iota_transaction_handler;
 pub mod iota_transaction_queries;
@@ -9,6 +9,7 @@ use clap::*;
 use ethers::providers::{Http, Middleware, Provider};
 use iota_bridge::metrics::BridgeMetrics;
 use iota_bridge_indexer::{
+    api::run_server,
     config::IndexerConfig,
     eth_bridge_indexer::{EthDataMapper, EthSubscriptionDatasource, EthSyncDatasource},
     iota_bridge_indexer::{IotaBridgeDataMapper, PgBridgePersistent},
@@ -76,6 +77,10 @@ async fn main() -> Result<()> {
     let db_url = config.db_url.clone();
     let datastore = PgBridgePersistent::new(get_connection_pool(db_url.clone()));
 
+    if let Some(api_listen_address) = config.api_listen_address {
+        run_server(api_listen_address, get_connection_pool(db_url.clone()));
+    }
+
     let provider = Arc::new(
         Provider::<Http>::try_from(config.eth_rpc_url.clone())?
             .interval(std::time::Duration::from_millis(2000)),
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS token_transfer_data_recipient_address;
+DROP INDEX IF EXISTS token_transfer_txn_hash;
@@ -0,0 +1,2 @@
+CREATE INDEX token_transfer_data_recipient_address ON token_transfer_data (recipient_address);
+CREATE INDEX token_transfer_txn_hash ON token_transfer (txn_hash);
@@ -13,7 +13,10 @@ use iota_types::digests::TransactionDigest;
 
 use crate::{
     ProcessedTxnData,
-    models::{IotaProgressStore, TokenTransfer as DBTokenTransfer},
+    models::{
+        IotaProgressStore, TokenTransfer as DBTokenTransfer,
+        TokenTransferData as DBTokenTransferData,
+    },
     schema,
     schema::{
         iota_error_transactions, iota_progress_store::txn_digest, token_transfer,
@@ -126,3 +129,64 @@ pub fn get_latest_eth_token_transfer(
             .optional()
     }
 }
+
+/// Get all the recorded steps of the transfer identified by `chain_id` and
+/// `nonce`, together with its data if the deposit was indexed.
+pub fn get_token_transfer(
+    pool: &PgPool,
+    chain_id: i32,
+    nonce: i64,
+) -> Result<(Vec<DBTokenTransfer>, Option<DBTokenTransferData>), anyhow::Error> {
+    let connection = &mut pool.get()?;
+    let transfers = token_transfer::table
+        .filter(
+            token_transfer::chain_id
+                .eq(chain_id)
+                .and(token_transfer::nonce.eq(nonce)),
+        )
+        .order(token_transfer::block_height.asc())
+        .load::<DBTokenTransfer>(connection)?;
+    let data = token_transfer_data::table
+        .filter(
+            token_transfer_data::chain_id
+                .eq(chain_id)
+                .and(token_transfer_data::nonce.eq(nonce)),
+        )
+        .first::<DBTokenTransferData>(connection)
+        .optional()?;
+    Ok((transfers, data))
+}
+
+/// Get the `(chain_id, nonce)` keys of the transfers sent from or to
+/// `address`, most recent first.
+pub fn get_token_transfer_keys_by_address(
+    pool: &PgPool,
+    address: &[u8],
+    limit: i64,
+) -> Result<Vec<(i32, i64)>, anyhow::Error> {
+    let connection = &mut pool.get()?;
+    Ok(token_transfer_data::table
+        .filter(
+            token_transfer_data::sender_address
+                .eq(address)
+                .or(token_transfer_data::recipient_address.eq(address)),
+        )
+        .order(token_transfer_data::timestamp_ms.desc())
+        .limit(limit)
+        .select((token_transfer_data::chain_id, token_transfer_data::nonce))
+        .load(connection)?)
+}
+
+/// Get the `(chain_id, nonce)` keys of the transfers that have a step recorded
+/// in the transaction `txn_hash`.
+pub fn get_token_transfer_keys_by_txn_hash(
+    pool: &PgPool,
+    txn_hash: &[u8],
+) -> Result<Vec<(i32, i64)>, anyhow::Error> {
+    let connection = &mut pool.get()?;
+    Ok(token_transfer::table
+        .filter(token_transfer::txn_hash.eq(txn_hash))
+        .select((token_transfer::chain_id, token_transfer::nonce))
+        .distinct()
+        .load(connection)?)
+}
@@ -37,7 +37,10 @@ use crate::{
     metrics::BridgeMetrics,
     retry_with_max_elapsed_time,
     storage::BridgeOrchestratorTables,
-    types::{BridgeAction, BridgeActionStatus, IsBridgePaused, VerifiedCertifiedBridgeAction},
+    types::{
+        BridgeAction, BridgeActionHistoryEvent, BridgeActionStatus, IsBridgePaused,
+        VerifiedCertifiedBridgeAction,
+    },
 };
 
 pub const CHANNEL_SIZE: usize = 1000;
@@ -54,6 +57,18 @@ async fn delay(attempt_times: u64) {
     tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
 }
 
+// The action history is informational only, so failing to record it must not
+// interrupt the processing of the action.
+fn record_action_event(
+    store: &BridgeOrchestratorTables,
+    action: &BridgeAction,
+    event: BridgeActionHistoryEvent,
+) {
+    if let Err(e) = store.record_action_event(action, event) {
+        warn!("Failed to record action history: {:?}", e);
+    }
+}
+
 #[derive(Debug)]
 pub struct BridgeActionExecutionWrapper(pub BridgeAction, pub u64);
 
@@ -257,6 +272,9 @@ where
         if !should_proceed {
             metrics.action_executor_signing_queue_skipped_actions.inc();
             warn!("skipping signing task: {:?}", action_key);
+            record_action_event(store, &action.0, BridgeActionHistoryEvent::Failed {
+                reason: "Bridge is paused, skipped signing".to_string(),
+            });
             return;
         }
 
@@ -305,6 +323,7 @@ where
                     action
                 );
                 metrics.action_executor_already_processed_actions.inc();
+                record_action_event(store, action, BridgeActionHistoryEvent::AlreadyProcessed);
                 store
                     .remove_pending_actions(&[action.digest()])
                     .unwrap_or_else(|e| {
@@ -356,12 +375,20 @@ where
         {
             return;
         }
+        record_action_event(
+            &store,
+            &action,
+            BridgeActionHistoryEvent::SignatureRequested {
+                attempt: attempt_times,
+            },
+        );
         match auth_agg
             .load()
             .request_committee_signatures(action.clone())
             .await
         {
             Ok(certificate) => {
+                record_action_event(&store, &action, BridgeActionHistoryEvent::QuorumReached);
                 info!("Sending certificate to execution");
                 execution_queue_sender
                     .send(CertifiedBridgeActionExecutionWrapper(certificate, 0))
@@ -373,6 +400,11 @@ where
             Err(e) => {
                 warn!("Failed to collect sigs for bridge action: {:?}", e);
                 metrics.err_signature_aggregation.inc();
+                record_action_event(&store, &action, BridgeActionHistoryEvent::Failed {
+                    reason: format!(
+                        "Failed to collect signatures (attempt {attempt_times}/{MAX_SIGNING_ATTEMPTS}): {e:?}"
+                    ),
+                });
 
                 // TODO: spawn a task for this
                 if attempt_times >= MAX_SIGNING_ATTEMPTS {
@@ -419,6 +451,13 @@ where
             // if bridge is unpaused.
             if *bridge_pause_rx.borrow() {
                 warn!("Bridge is paused, skipping execution");
+                record_action_event(
+                    &store,
+                    certificate_wrapper.0.data(),
+                    BridgeActionHistoryEvent::Failed {
+                        reason: "Bridge is paused, skipped execution".to_string(),
+                    },
+                );
                 metrics
                     .action_executor_execution_queue_skipped_actions_due_to_pausing
                     .inc();
@@ -498,6 +537,9 @@ where
             Ok(tx_data) => tx_data,
             Err(err) => {
                 metrics.err_build_iota_transaction.inc();
+                record_action_event(store, action, BridgeActionHistoryEvent::Failed {
+                    reason: format!("Failed to build transaction: {err:?}"),
+                });
                 error!(
                     "Manual intervention is required. Failed to build transaction for action {:?}: {:?}",
                     action, err
@@ -528,6 +570,10 @@ where
         }
 
         info!(?tx_digest, ?gas_object_ref, "Sending transaction to Iota");
+        record_action_event(store, action, BridgeActionHistoryEvent::Submitted {
+            tx_digest,
+            attempt: attempt_times,
+        });
         match iota_client
             .execute_transaction_block_with_effects(signed_tx)
             .await
@@ -544,6 +590,11 @@ where
                     "Iota transaction failed at signing: {err:?}"
                 );
                 metrics.err_iota_transaction_submission.inc();
+                record_action_event(store, action, BridgeActionHistoryEvent::Failed {
+                    reason: format!(
+                        "Transaction {tx_digest} failed at signing (attempt {attempt_times}/{MAX_EXECUTION_ATTEMPTS}): {err:?}"
+                    ),
+                });
                 let metrics_clone = metrics.clone();
                 // Do this in a separate task so we won't deadlock here
                 let sender_clone = execution_queue_sender.clone();
@@ -600,6 +651,9 @@ where
                     events,
                 );
                 info!(?tx_digest, "Iota transaction executed successfully");
+                record_action_event(store, action, BridgeActionHistoryEvent::Executed {
+                    tx_digest,
+                });
                 store
                     .remove_pending_actions(&[action.digest()])
                     .unwrap_or_else(|e| {
@@ -615,6 +669,9 @@ where
                 // WAL.
 
                 metrics.err_iota_transaction_execution.inc();
+                record_action_event(store, action, BridgeActionHistoryEvent::Failed {
+                    reason: format!("Transaction {tx_digest} executed and failed: {error:?}"),
+                });
                 error!(
                     ?tx_digest,
                     "Manual intervention is needed. Iota transaction executed and failed with error: {error:?}"
@@ -765,6 +822,25 @@ mod tests {
         // removed from WAL
         tx_subscription.recv().await.unwrap();
         assert!(store.get_all_pending_actions().is_empty());
+        // The history records the whole lifecycle of the action
+        let history = store.get_action_history(&action.digest()).unwrap().unwrap();
+        assert_eq!(
+            history
+                .entries
+                .into_iter()
+                .map(|e| e.event)
+                .collect::<Vec<_>>(),
+            vec![
+                BridgeActionHistoryEvent::Observed,
+                BridgeActionHistoryEvent::SignatureRequested { attempt: 0 },
+                BridgeActionHistoryEvent::QuorumReached,
+                BridgeActionHistoryEvent::Submitted {
+                    tx_digest,
+                    attempt: 0
+                },
+                BridgeActionHistoryEvent::Executed { tx_digest },
+            ]
+        );
 
         /////////////////////////////////////////////////////////////////////////////////////////////////
         ////////////////////////////////////// Test execution failure
@@ -821,6 +897,12 @@ mod tests {
             store.get_all_pending_actions()[&action.digest()],
             action.clone()
         );
+        // The failure reason is recorded in the history
+        let history = store.get_action_history(&action.digest()).unwrap().unwrap();
+        assert!(matches!(
+            &history.entries.last().unwrap().event,
+            BridgeActionHistoryEvent::Failed { reason } if reason.contains("failure is mother of success")
+        ));
 
         /////////////////////////////////////////////////////////////////////////////////////////////////
         //////////////////////////// Test transaction failed at signing stage
@@ -47,11 +47,15 @@ pub async fn run_bridge_node(
     let (server_config, client_config) = config.validate(metrics.clone()).await?;
 
     // Start Client
-    let _handles = if let Some(client_config) = client_config {
-        start_client_components(client_config, metrics.clone()).await
+    let (_handles, store) = if let Some(client_config) = client_config {
+        let store = BridgeOrchestratorTables::new(&client_config.db_path.join("client"));
+        (
+            start_client_components(client_config, store.clone(), metrics.clone()).await?,
+            Some(store),
+        )
     } else {
-        Ok(vec![])
-    }?;
+        (vec![], None)
+    };
 
     // Start Server
     let socket_address = SocketAddr::new(
@@ -69,16 +73,16 @@ pub async fn run_bridge_node(
         ),
         metrics,
         Arc::new(metadata),
+        store,
     ))
 }
 
 // TODO: is there a way to clean up the overrides after it's stored in DB?
 async fn start_client_components(
     client_config: BridgeClientConfig,
+    store: Arc<BridgeOrchestratorTables>,
     metrics: Arc<BridgeMetrics>,
 ) -> anyhow::Result<Vec<JoinHandle<()>>> {
-    let store: std::sync::Arc<BridgeOrchestratorTables> =
-        BridgeOrchestratorTables::new(&client_config.db_path.join("client"));
     let iota_modules_to_watch = get_iota_modules_to_watch(
         &store,
         client_config.iota_bridge_module_last_processed_event_id_override,
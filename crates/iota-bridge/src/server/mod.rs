@@ -13,7 +13,7 @@ use axum::{
 };
 use ethers::types::Address as EthAddress;
 use fastcrypto::{
-    encoding::{Encoding, Hex},
+    encoding::{Base58, Encoding, Hex},
     traits::ToFromBytes,
 };
 use iota_types::{TypeTag, bridge::BridgeChainId};
@@ -24,10 +24,12 @@ use crate::{
     error::BridgeError,
     metrics::BridgeMetrics,
     server::handler::{BridgeRequestHandler, BridgeRequestHandlerTrait},
+    storage::BridgeOrchestratorTables,
     types::{
         AddTokensOnEvmAction, AddTokensOnIotaAction, AssetPriceUpdateAction,
-        BlocklistCommitteeAction, BlocklistType, BridgeAction, EmergencyAction,
-        EmergencyActionType, EvmContractUpgradeAction, LimitUpdateAction, SignedBridgeAction,
+        BlocklistCommitteeAction, BlocklistType, BridgeAction, BridgeActionDigest,
+        BridgeActionHistoryResponse, EmergencyAction, EmergencyActionType,
+        EvmContractUpgradeAction, LimitUpdateAction, SignedBridgeAction,
     },
     with_metrics,
 };
@@ -59,6 +61,8 @@ pub const EVM_CONTRACT_UPGRADE_PATH: &str =
     "/sign/upgrade_evm_contract/:chain_id/:nonce/:proxy_address/:new_impl_address";
 pub const ADD_TOKENS_ON_IOTA_PATH: &str =
     "/sign/add_tokens_on_iota/:chain_id/:nonce/:native/:token_ids/:token_type_names/:token_prices";
+pub const PENDING_ACTIONS_HISTORY_PATH: &str = "/action_history";
+pub const ACTION_HISTORY_PATH: &str = "/action_history/:action_digest";
 pub const ADD_TOKENS_ON_EVM_PATH: &str = "/sign/add_tokens_on_evm/:chain_id/:nonce/:native/:token_ids/:token_addresses/:token_iota_decimals/:token_prices";
 
 /// BridgeNode's public metadata that is acceesible via the `/ping` endpoint.
@@ -80,18 +84,21 @@ impl BridgeNodePublicMetadata {
     }
 }
 
+/// `store` is only available when the node runs the bridge client, without it
+/// the action history endpoints return an error.
 pub fn run_server(
     socket_address: &SocketAddr,
     handler: BridgeRequestHandler,
     metrics: Arc<BridgeMetrics>,
     metadata: Arc<BridgeNodePublicMetadata>,
+    store: Option<Arc<BridgeOrchestratorTables>>,
 ) -> tokio::task::JoinHandle<()> {
     let socket_address = *socket_address;
     tokio::spawn(async move {
         let listener = tokio::net::TcpListener::bind(socket_address).await.unwrap();
         axum::serve(
             listener,
-            make_router(Arc::new(handler), metrics, metadata).into_make_service(),
+            make_router(Arc::new(handler), metrics, metadata, store).into_make_service(),
         )
         .await
         .unwrap();
@@ -102,7 +109,15 @@ pub(crate) fn make_router(
     handler: Arc<impl BridgeRequestHandlerTrait + Sync + Send + 'static>,
     metrics: Arc<BridgeMetrics>,
     metadata: Arc<BridgeNodePublicMetadata>,
+    store: Option<Arc<BridgeOrchestratorTables>>,
 ) -> Router {
+    let action_history_router = Router::new()
+        .route(
+            PENDING_ACTIONS_HISTORY_PATH,
+            get(handle_pending_actions_history),
+        )
+        .route(ACTION_HISTORY_PATH, get(handle_action_history))
+        .with_state(store);
     Router::new()
         .route("/", get(health_check))
         .route(PING_PATH, get(ping))
@@ -126,6 +141,7 @@ pub(crate) fn make_router(
         .route(ADD_TOKENS_ON_IOTA_PATH, get(handle_add_tokens_on_iota))
         .route(ADD_TOKENS_ON_EVM_PATH, get(handle_add_tokens_on_evm))
         .with_state((handler, metrics, metadata))
+        .merge(action_history_router)
 }
 
 impl axum::response::IntoResponse for BridgeError {
@@ -162,6 +178,62 @@ async fn ping(
     Ok(Json(metadata.as_ref().clone()))
 }
 
+fn action_history_store(
+    store: &Option<Arc<BridgeOrchestratorTables>>,
+) -> Result<&BridgeOrchestratorTables, BridgeError> {
+    store.as_deref().ok_or_else(|| {
+        BridgeError::InvalidBridgeClientRequest(
+            "Action history is only available on nodes running the bridge client".to_string(),
+        )
+    })
+}
+
+async fn handle_pending_actions_history(
+    State(store): State<Option<Arc<BridgeOrchestratorTables>>>,
+) -> Result<Json<Vec<BridgeActionHistoryResponse>>, BridgeError> {
+    let store = action_history_store(&store)?;
+    let mut responses = vec![];
+    for digest in store.get_all_pending_actions().into_keys() {
+        if let Some(history) = store.get_action_history(&digest)? {
+            responses.push(BridgeActionHistoryResponse {
+                digest,
+                pending: true,
+                history,
+            });
+        }
+    }
+    Ok(Json(responses))
+}
+
+#[instrument(level = "error", skip_all, fields(action_digest=action_digest))]
+async fn handle_action_history(
+    Path(action_digest): Path<String>,
+    State(store): State<Option<Arc<BridgeOrchestratorTables>>>,
+) -> Result<Json<BridgeActionHistoryResponse>, BridgeError> {
+    let store = action_history_store(&store)?;
+    // Accept both the hex and the base58 encoding of the digest.
+    let bytes = if action_digest.starts_with("0x") {
+        Hex::decode(&action_digest).map_err(|e| e.to_string())
+    } else {
+        Base58::decode(&action_digest).map_err(|e| e.to_string())
+    }
+    .and_then(|bytes| {
+        <[u8; 32]>::try_from(bytes).map_err(|_| "Digest must be 32 bytes".to_string())
+    })
+    .map_err(|e| BridgeError::InvalidBridgeClientRequest(format!("Invalid action digest: {e}")))?;
+    let digest = BridgeActionDigest::new(bytes);
+    let history = store.get_action_history(&digest)?.ok_or_else(|| {
+        BridgeError::InvalidBridgeClientRequest(format!(
+            "No history for action digest {action_digest}"
+        ))
+    })?;
+    Ok(Json(BridgeActionHistoryResponse {
+        digest,
+        pending: store.is_pending_action(&digest)?,
+        history,
+    }))
+}
+
 #[instrument(level = "error", skip_all, fields(tx_hash_hex=tx_hash_hex, event_idx=event_idx))]
 async fn handle_eth_tx_hash(
     Path((tx_hash_hex, event_idx)): Path<(String, u16)>,
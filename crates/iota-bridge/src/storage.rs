@@ -2,7 +2,12 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::HashMap, path::Path, sync::Arc};
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use iota_types::{Identifier, event::EventID};
 use typed_store::{
@@ -13,7 +18,10 @@ use typed_store::{
 
 use crate::{
     error::{BridgeError, BridgeResult},
-    types::{BridgeAction, BridgeActionDigest},
+    types::{
+        BridgeAction, BridgeActionDigest, BridgeActionHistory, BridgeActionHistoryEntry,
+        BridgeActionHistoryEvent,
+    },
 };
 
 #[derive(DBMapUtils)]
@@ -24,6 +32,9 @@ pub struct BridgeOrchestratorTables {
     pub(crate) iota_syncer_cursors: DBMap<Identifier, EventID>,
     /// contract address to the last processed block
     pub(crate) eth_syncer_cursors: DBMap<ethers::types::Address, u64>,
+    /// audit trail of every BridgeAction the orchestrator received, kept after
+    /// the action is executed
+    pub(crate) action_history: DBMap<BridgeActionDigest, BridgeActionHistory>,
 }
 
 impl BridgeOrchestratorTables {
@@ -46,6 +57,15 @@ impl BridgeOrchestratorTables {
             .map_err(|e| {
                 BridgeError::Storage(format!("Couldn't insert into pending_actions: {:?}", e))
             })?;
+        let histories = actions
+            .iter()
+            .map(|a| self.append_action_history(a, BridgeActionHistoryEvent::Observed))
+            .collect::<BridgeResult<Vec<_>>>()?;
+        batch
+            .insert_batch(&self.action_history, histories)
+            .map_err(|e| {
+                BridgeError::Storage(format!("Couldn't insert into action_history: {:?}", e))
+            })?;
         batch
             .write()
             .map_err(|e| BridgeError::Storage(format!("Couldn't write batch: {:?}", e)))
@@ -66,6 +86,40 @@ impl BridgeOrchestratorTables {
             .map_err(|e| BridgeError::Storage(format!("Couldn't write batch: {:?}", e)))
     }
 
+    /// Append `event` to the audit trail of `action`.
+    pub(crate) fn record_action_event(
+        &self,
+        action: &BridgeAction,
+        event: BridgeActionHistoryEvent,
+    ) -> BridgeResult<()> {
+        let (digest, history) = self.append_action_history(action, event)?;
+        self.action_history.insert(&digest, &history).map_err(|e| {
+            BridgeError::Storage(format!("Couldn't insert into action_history: {:?}", e))
+        })
+    }
+
+    fn append_action_history(
+        &self,
+        action: &BridgeAction,
+        event: BridgeActionHistoryEvent,
+    ) -> BridgeResult<(BridgeActionDigest, BridgeActionHistory)> {
+        let digest = action.digest();
+        let mut history =
+            self.get_action_history(&digest)?
+                .unwrap_or_else(|| BridgeActionHistory {
+                    action: action.clone(),
+                    entries: vec![],
+                });
+        history.entries.push(BridgeActionHistoryEntry {
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Time went backwards")
+                .as_millis() as u64,
+            event,
+        });
+        Ok((digest, history))
+    }
+
     pub(crate) fn update_iota_event_cursor(
         &self,
         module: Identifier,
@@ -104,6 +158,21 @@ impl BridgeOrchestratorTables {
         self.pending_actions.unbounded_iter().collect()
     }
 
+    pub fn is_pending_action(&self, digest: &BridgeActionDigest) -> BridgeResult<bool> {
+        self.pending_actions
+            .contains_key(digest)
+            .map_err(|e| BridgeError::Storage(format!("Couldn't get pending_actions: {:?}", e)))
+    }
+
+    pub fn get_action_history(
+        &self,
+        digest: &BridgeActionDigest,
+    ) -> BridgeResult<Option<BridgeActionHistory>> {
+        self.action_history
+            .get(digest)
+            .map_err(|e| BridgeError::Storage(format!("Couldn't get action_history: {:?}", e)))
+    }
+
     pub fn get_iota_event_cursors(
         &self,
         identifiers: &[Identifier],
@@ -203,6 +272,42 @@ mod tests {
         let actions = store.get_all_pending_actions();
         assert!(actions.is_empty());
 
+        // the history of removed actions is kept
+        assert!(!store.is_pending_action(&action1.digest()).unwrap());
+        let history = store
+            .get_action_history(&action1.digest())
+            .unwrap()
+            .unwrap();
+        assert_eq!(history.action, action1);
+        assert_eq!(
+            history
+                .entries
+                .iter()
+                .map(|e| e.event.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                BridgeActionHistoryEvent::Observed,
+                BridgeActionHistoryEvent::Observed
+            ]
+        );
+        store
+            .record_action_event(&action1, BridgeActionHistoryEvent::AlreadyProcessed)
+            .unwrap();
+        let history = store
+            .get_action_history(&action1.digest())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            history.entries.last().unwrap().event,
+            BridgeActionHistoryEvent::AlreadyProcessed
+        );
+        assert!(
+            store
+                .get_action_history(&action2.digest())
+                .unwrap()
+                .is_some()
+        );
+
         // update eth event cursor
         let eth_contract_address = ethers::types::Address::random();
         let eth_block_num = 199999u64;
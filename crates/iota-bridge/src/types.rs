@@ -456,6 +456,52 @@ impl BridgeActionDigest {
     }
 }
 
+/// A step in the processing of a [`BridgeAction`] by the bridge client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BridgeActionHistoryEvent {
+    /// The action was observed on its source chain and added to the pending
+    /// actions.
+    Observed,
+    /// Signatures were requested from the bridge committee.
+    SignatureRequested { attempt: u64 },
+    /// Enough signatures were collected to certify the action.
+    QuorumReached,
+    /// A transaction executing the certified action was submitted.
+    Submitted {
+        tx_digest: TransactionDigest,
+        attempt: u64,
+    },
+    /// The transaction executing the action succeeded.
+    Executed { tx_digest: TransactionDigest },
+    /// The action was found to be already approved or claimed on chain.
+    AlreadyProcessed,
+    /// Processing the action failed. Depending on the reason it is retried or
+    /// requires manual intervention.
+    Failed { reason: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BridgeActionHistoryEntry {
+    pub timestamp_ms: u64,
+    pub event: BridgeActionHistoryEvent,
+}
+
+/// The audit trail of a [`BridgeAction`] processed by the bridge client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BridgeActionHistory {
+    pub action: BridgeAction,
+    pub entries: Vec<BridgeActionHistoryEntry>,
+}
+
+/// The history of an action together with whether it is still pending, as
+/// served by the bridge node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BridgeActionHistoryResponse {
+    pub digest: BridgeActionDigest,
+    pub pending: bool,
+    pub history: BridgeActionHistory,
+}
+
 #[derive(Debug, Clone)]
 pub struct BridgeCommitteeValiditySignInfo {
     pub signatures: BTreeMap<BridgeAuthorityPublicKeyBytes, BridgeAuthorityRecoverableSignature>,
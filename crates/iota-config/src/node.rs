@@ -15,7 +15,7 @@ use anyhow::Result;
 use consensus_config::Parameters as ConsensusParameters;
 use iota_keys::keypair_file::{read_authority_keypair_from_file, read_keypair_from_file};
 use iota_types::{
-    base_types::IotaAddress,
+    base_types::{IotaAddress, ObjectID},
     committee::EpochId,
     crypto::{
         AccountKeyPair, AuthorityKeyPair, AuthorityPublicKeyBytes, IotaKeyPair, KeypairTraits,
@@ -164,6 +164,11 @@ pub struct NodeConfig {
     #[serde(default)]
     pub db_checkpoint_config: DBCheckpointConfig,
 
+    /// Configuration of backups of the databases of the node to a local path,
+    /// taken on demand through the admin interface or at a fixed interval.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub db_backup_config: Option<DBBackupConfig>,
+
     /// Defines a threshold for an object size above which object
     /// is stored separately as `IndirectObject`. Used in `AuthorityStore`.
     #[serde(default)]
@@ -408,6 +413,16 @@ impl NodeConfig {
         self.db_path.join("archive")
     }
 
+    /// Path of the deny list which can be updated through the admin interface.
+    pub fn dynamic_deny_list_path(&self) -> PathBuf {
+        self.db_path.join("deny_list.yaml")
+    }
+
+    /// Path of the table of discovered peers and their reputation.
+    pub fn peer_table_path(&self) -> PathBuf {
+        self.db_path.join("peers")
+    }
+
     pub fn snapshot_path(&self) -> PathBuf {
         self.db_path.join("snapshot")
     }
@@ -700,6 +715,11 @@ pub struct AuthorityStorePruningConfig {
     pub num_epochs_to_retain_for_checkpoints: Option<u64>,
     #[serde(default = "default_smoothing", skip_serializing_if = "is_true")]
     pub smooth: bool,
+    /// policies retaining old versions of matching objects beyond
+    /// `num_epochs_to_retain`. If several policies match an object version,
+    /// the longest retention applies.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub object_retention_policies: Vec<ObjectRetentionPolicy>,
 }
 
 fn default_num_latest_epoch_dbs_to_retain() -> usize {
@@ -734,6 +754,7 @@ impl Default for AuthorityStorePruningConfig {
             periodic_compaction_threshold_days: None,
             num_epochs_to_retain_for_checkpoints: if cfg!(msim) { Some(2) } else { None },
             smooth: true,
+            object_retention_policies: vec![],
         }
     }
 }
@@ -757,6 +778,32 @@ impl AuthorityStorePruningConfig {
     }
 }
 
+/// Retains old versions of the objects matching `filter` when they are pruned
+/// by the objects pruner.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ObjectRetentionPolicy {
+    #[serde(flatten)]
+    pub filter: ObjectRetentionFilter,
+    /// number of epochs to retain old versions of matching objects for,
+    /// counted from the epoch in which a version was superseded. Matching
+    /// versions are retained indefinitely if not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_epochs_to_retain: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ObjectRetentionFilter {
+    /// Objects of the given type, written as `package::module::struct`. Type
+    /// parameters are only compared if they are given.
+    ObjectType(String),
+    /// Objects whose type is defined in the given package.
+    Package(ObjectID),
+    /// Object versions owned by the given address or object.
+    Owner(IotaAddress),
+}
+
 #[derive(Default, Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct DBCheckpointConfig {
@@ -772,6 +819,27 @@ pub struct DBCheckpointConfig {
     pub prune_and_compact_before_upload: Option<bool>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DBBackupConfig {
+    /// Local directory that backups are written to, one directory per backup.
+    pub backup_path: PathBuf,
+    /// Interval between two scheduled backups. Backups are only taken on
+    /// demand if not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval_s: Option<u64>,
+    /// Number of most recent backups to keep.
+    #[serde(default = "default_db_backup_retention")]
+    pub retention: usize,
+    /// Whether backups include the indexes of the node.
+    #[serde(default)]
+    pub include_indexes: bool,
+}
+
+fn default_db_backup_retention() -> usize {
+    3
+}
+
 #[derive(Debug, Clone)]
 pub struct ArchiveReaderConfig {
     pub remote_store_config: ObjectStoreConfig,
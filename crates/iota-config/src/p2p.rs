@@ -197,6 +197,23 @@ pub struct StateSyncConfig {
     /// content from. If unspecified, this will set to default value
     #[serde(skip_serializing_if = "Option::is_none")]
     pub wait_interval_when_no_peer_to_sync_content_ms: Option<u64>,
+
+    /// Reputation score at or below which a peer is temporarily banned. A
+    /// banned peer is neither asked for checkpoint data nor dialed by
+    /// discovery. Peers lose score on timeouts, checkpoints with invalid
+    /// signatures and contents not matching their digest, and gain score
+    /// with every valid response.
+    ///
+    /// If unspecified, this will default to `-100`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peer_ban_score_threshold: Option<i64>,
+
+    /// How long a peer stays banned once its reputation score dropped to
+    /// `peer_ban_score_threshold`.
+    ///
+    /// If unspecified, this will default to `600,000` milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peer_ban_duration_ms: Option<u64>,
 }
 
 impl StateSyncConfig {
@@ -262,6 +279,21 @@ impl StateSyncConfig {
             .unwrap_or(self.default_wait_interval_when_no_peer_to_sync_content())
     }
 
+    pub fn peer_ban_score_threshold(&self) -> i64 {
+        const PEER_BAN_SCORE_THRESHOLD: i64 = -100;
+
+        self.peer_ban_score_threshold
+            .unwrap_or(PEER_BAN_SCORE_THRESHOLD)
+    }
+
+    pub fn peer_ban_duration(&self) -> Duration {
+        const DEFAULT_PEER_BAN_DURATION: Duration = Duration::from_secs(600);
+
+        self.peer_ban_duration_ms
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_PEER_BAN_DURATION)
+    }
+
     fn default_wait_interval_when_no_peer_to_sync_content(&self) -> Duration {
         if cfg!(msim) {
             Duration::from_secs(5)
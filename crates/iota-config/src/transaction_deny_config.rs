@@ -24,15 +24,21 @@ pub struct TransactionDenyConfig {
     /// dependencies. Note that this does not apply to type arguments.
     /// Also since we only compare the deny list against the upgraded package ID
     /// of each dependency in the used package, when a package ID is denied,
-    /// newer versions of that package are still allowed. If we want to deny
-    /// the entire upgrade family of a package, we need to explicitly
-    /// specify all the package IDs in the deny list. TODO: We could
-    /// consider making this more flexible, e.g. whether to check in type args,
-    /// whether to block entire upgrade family, whether to allow upgrade and
-    /// etc.
+    /// newer versions of that package are still allowed. To deny the entire
+    /// upgrade family of a package, use `package_family_deny_list` instead.
+    /// TODO: We could consider making this more flexible, e.g. whether to
+    /// check in type args, whether to allow upgrade and etc.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     package_deny_list: Vec<ObjectID>,
 
+    /// A list of original package IDs whose entire upgrade family is not
+    /// allowed to be called into in transactions, either directly or
+    /// indirectly through transitive dependencies. Unlike
+    /// `package_deny_list`, this also denies all past and future versions of
+    /// the packages.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    package_family_deny_list: Vec<ObjectID>,
+
     /// A list of iota addresses that are not allowed to be used as the sender
     /// or sponsor.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -63,6 +69,9 @@ pub struct TransactionDenyConfig {
     #[serde(skip)]
     package_deny_set: OnceCell<HashSet<ObjectID>>,
 
+    #[serde(skip)]
+    package_family_deny_set: OnceCell<HashSet<ObjectID>>,
+
     #[serde(skip)]
     address_deny_set: OnceCell<HashSet<IotaAddress>>,
 
@@ -93,6 +102,11 @@ impl TransactionDenyConfig {
             .get_or_init(|| self.package_deny_list.iter().cloned().collect())
     }
 
+    pub fn get_package_family_deny_set(&self) -> &HashSet<ObjectID> {
+        self.package_family_deny_set
+            .get_or_init(|| self.package_family_deny_list.iter().cloned().collect())
+    }
+
     pub fn get_address_deny_set(&self) -> &HashSet<IotaAddress> {
         self.address_deny_set
             .get_or_init(|| self.address_deny_list.iter().cloned().collect())
@@ -181,6 +195,11 @@ impl TransactionDenyConfigBuilder {
         self
     }
 
+    pub fn add_denied_package_family(mut self, original_id: ObjectID) -> Self {
+        self.config.package_family_deny_list.push(original_id);
+        self
+    }
+
     pub fn disable_zklogin_sig(mut self) -> Self {
         self.config.zklogin_sig_disabled = true;
         self
@@ -49,6 +49,7 @@ use iota_storage::{
     key_value_store::{TransactionKeyValueStore, TransactionKeyValueStoreTrait},
     key_value_store_metrics::KeyValueStoreMetrics,
 };
+use iota_transaction_checks::deny::DynamicDenyList;
 #[cfg(msim)]
 use iota_types::committee::CommitteeTrait;
 use iota_types::{
@@ -67,6 +68,7 @@ use iota_types::{
     error::{ExecutionError, IotaError, IotaResult, UserInputError},
     event::{Event, EventID, SystemEpochInfoEventV1},
     executable_transaction::VerifiedExecutableTransaction,
+    execution::ExecutionInstrumentation,
     execution_config_utils::to_binary_config,
     execution_status::ExecutionStatus,
     fp_ensure,
@@ -96,6 +98,7 @@ use iota_types::{
     },
     metrics::{BytecodeVerifierMetrics, LimitsMetrics},
     object::{MoveObject, OBJECT_START_VERSION, Object, ObjectRead, Owner, PastObjectRead},
+    state_override::{ResolvedStateOverrides, StateOverrides},
     storage::{
         BackingPackageStore, BackingStore, ObjectKey, ObjectOrTombstone, ObjectStore, WriteKind,
     },
@@ -200,6 +203,7 @@ pub mod authority_store_pruner;
 pub mod authority_store_tables;
 pub mod authority_store_types;
 pub mod epoch_start_configuration;
+pub mod execution_time_estimator;
 pub mod shared_object_congestion_tracker;
 pub mod shared_object_version_manager;
 #[cfg(any(test, feature = "test-utils"))]
@@ -796,6 +800,10 @@ pub struct AuthorityState {
 
     pub config: NodeConfig,
 
+    /// Deny list entries added at runtime on top of the
+    /// `TransactionDenyConfig` of the node config.
+    dynamic_deny_list: DynamicDenyList,
+
     /// Current overload status in this authority. Updated periodically.
     pub overload_info: AuthorityOverloadInfo,
 
@@ -831,6 +839,10 @@ impl AuthorityState {
         &self.config.authority_overload_config
     }
 
+    pub fn dynamic_deny_list(&self) -> &DynamicDenyList {
+        &self.dynamic_deny_list
+    }
+
     pub fn get_epoch_state_commitments(
         &self,
         epoch: EpochId,
@@ -862,6 +874,7 @@ impl AuthorityState {
             &input_object_kinds,
             &receiving_objects_refs,
             &self.config.transaction_deny_config,
+            &self.dynamic_deny_list.entries(),
             self.get_backing_package_store().as_ref(),
         )?;
 
@@ -1593,6 +1606,7 @@ impl AuthorityState {
         let transaction_data = &certificate.data().intent_message().value;
         let (kind, signer, gas) = transaction_data.execution_parts();
 
+        let execution_start_time = tokio::time::Instant::now();
         #[expect(unused_mut)]
         let (inner_temp_store, _, mut effects, execution_error_opt) =
             epoch_store.executor().execute_transaction_to_effects(
@@ -1617,6 +1631,7 @@ impl AuthorityState {
                 signer,
                 tx_digest,
             );
+        epoch_store.record_local_execution_time(tx_data, execution_start_time.elapsed());
 
         fail_point_if!("cp_execution_nondeterminism", || {
             #[cfg(msim)]
@@ -1658,6 +1673,45 @@ impl AuthorityState {
         BTreeMap<ObjectID, (ObjectRef, Object, WriteKind)>,
         TransactionEffects,
         Option<ObjectID>,
+    )> {
+        self.checked_dry_exec_transaction(transaction, transaction_digest, None)
+            .await
+            .map(|(response, written, effects, mock_gas, _)| (response, written, effects, mock_gas))
+    }
+
+    /// Like [`Self::dry_exec_transaction`], but executes the transaction
+    /// against the on-chain state with the given overrides applied. Also
+    /// returns the objects which replaced on-chain objects, so that the
+    /// changes of the transaction can be computed against them.
+    pub async fn dry_exec_transaction_with_overrides(
+        &self,
+        transaction: TransactionData,
+        transaction_digest: TransactionDigest,
+        state_overrides: StateOverrides,
+    ) -> IotaResult<(
+        DryRunTransactionBlockResponse,
+        BTreeMap<ObjectID, (ObjectRef, Object, WriteKind)>,
+        TransactionEffects,
+        Option<ObjectID>,
+        Vec<Object>,
+    )> {
+        self.checked_dry_exec_transaction(transaction, transaction_digest, Some(state_overrides))
+            .await
+    }
+
+    /// Checks that the transaction may be dry run on this node before dry
+    /// running it, with the state overrides applied if any are given.
+    async fn checked_dry_exec_transaction(
+        &self,
+        transaction: TransactionData,
+        transaction_digest: TransactionDigest,
+        state_overrides: Option<StateOverrides>,
+    ) -> IotaResult<(
+        DryRunTransactionBlockResponse,
+        BTreeMap<ObjectID, (ObjectRef, Object, WriteKind)>,
+        TransactionEffects,
+        Option<ObjectID>,
+        Vec<Object>,
     )> {
         let epoch_store = self.load_epoch_store_one_call_per_task();
         if !self.is_fullnode(&epoch_store) {
@@ -1672,8 +1726,13 @@ impl AuthorityState {
             });
         }
 
-        self.dry_exec_transaction_impl(&epoch_store, transaction, transaction_digest)
-            .await
+        self.dry_exec_transaction_impl(
+            &epoch_store,
+            transaction,
+            transaction_digest,
+            state_overrides,
+        )
+        .await
     }
 
     pub async fn dry_exec_transaction_for_benchmark(
@@ -1687,8 +1746,9 @@ impl AuthorityState {
         Option<ObjectID>,
     )> {
         let epoch_store = self.load_epoch_store_one_call_per_task();
-        self.dry_exec_transaction_impl(&epoch_store, transaction, transaction_digest)
+        self.dry_exec_transaction_impl(&epoch_store, transaction, transaction_digest, None)
             .await
+            .map(|(response, written, effects, mock_gas, _)| (response, written, effects, mock_gas))
     }
 
     async fn dry_exec_transaction_impl(
@@ -1696,15 +1756,19 @@ impl AuthorityState {
         epoch_store: &AuthorityPerEpochStore,
         transaction: TransactionData,
         transaction_digest: TransactionDigest,
+        state_overrides: Option<StateOverrides>,
     ) -> IotaResult<(
         DryRunTransactionBlockResponse,
         BTreeMap<ObjectID, (ObjectRef, Object, WriteKind)>,
         TransactionEffects,
         Option<ObjectID>,
+        Vec<Object>,
     )> {
         // Cheap validity checks for a transaction, including input size limits.
         transaction.validity_check_no_gas_check(epoch_store.protocol_config())?;
 
+        let state_overrides = self.resolve_state_overrides(epoch_store, state_overrides)?;
+
         let input_object_kinds = transaction.input_objects()?;
         let receiving_object_refs = transaction.receiving_objects();
 
@@ -1714,16 +1778,18 @@ impl AuthorityState {
             &input_object_kinds,
             &receiving_object_refs,
             &self.config.transaction_deny_config,
+            &self.dynamic_deny_list.entries(),
             self.get_backing_package_store().as_ref(),
         )?;
 
-        let (input_objects, receiving_objects) = self.input_loader.read_objects_for_signing(
+        let (mut input_objects, receiving_objects) = self.input_loader.read_objects_for_signing(
             // We don't want to cache this transaction since it's a dry run.
             None,
             &input_object_kinds,
             &receiving_object_refs,
             epoch_store.epoch(),
         )?;
+        state_overrides.apply_to_inputs(&mut input_objects);
 
         // make a gas object if one was not provided
         let mut gas_object_refs = transaction.gas().to_vec();
@@ -1775,25 +1841,29 @@ impl AuthorityState {
             .expect("Creating an executor should not fail here");
 
         let expensive_checks = false;
-        let (inner_temp_store, _, effects, _execution_error) = executor
-            .execute_transaction_to_effects(
-                self.get_backing_store().as_ref(),
-                protocol_config,
-                self.metrics.limits_metrics.clone(),
-                expensive_checks,
-                self.config.certificate_deny_config.certificate_deny_set(),
-                &epoch_store.epoch_start_config().epoch_data().epoch_id(),
-                epoch_store
-                    .epoch_start_config()
-                    .epoch_data()
-                    .epoch_start_timestamp(),
-                checked_input_objects,
-                gas_object_refs,
-                gas_status,
-                kind,
-                signer,
-                transaction_digest,
-            );
+        let backing_store = state_overrides.overlay(self.get_backing_store().as_ref());
+        let track_command_gas = true;
+        let trace_execution = false;
+        let mut instrumentation = ExecutionInstrumentation::new(track_command_gas, trace_execution);
+        let (inner_temp_store, _, effects, _execution_error) = executor.dry_run_transaction(
+            &backing_store,
+            protocol_config,
+            self.metrics.limits_metrics.clone(),
+            expensive_checks,
+            self.config.certificate_deny_config.certificate_deny_set(),
+            &state_overrides.epoch_or(epoch_store.epoch_start_config().epoch_data().epoch_id()),
+            epoch_store
+                .epoch_start_config()
+                .epoch_data()
+                .epoch_start_timestamp(),
+            checked_input_objects,
+            gas_object_refs,
+            gas_status,
+            kind,
+            signer,
+            transaction_digest,
+            &mut instrumentation,
+        );
         let tx_digest = *effects.transaction_digest();
 
         let module_cache =
@@ -1804,7 +1874,7 @@ impl AuthorityState {
                 .executor()
                 .type_layout_resolver(Box::new(PackageStoreWithFallback::new(
                     &inner_temp_store,
-                    self.get_backing_package_store(),
+                    &backing_store,
                 )));
         // Returning empty vector here because we recalculate changes in the rpc layer.
         let object_changes = Vec::new();
@@ -1852,13 +1922,31 @@ impl AuthorityState {
                 )?,
                 object_changes,
                 balance_changes,
+                gas_breakdown: instrumentation.gas_breakdown(),
             },
             written_with_kind,
             effects,
             mock_gas,
+            state_overrides.objects().values().cloned().collect(),
         ))
     }
 
+    /// Resolves the given state overrides against the latest on-chain state.
+    fn resolve_state_overrides(
+        &self,
+        epoch_store: &AuthorityPerEpochStore,
+        state_overrides: Option<StateOverrides>,
+    ) -> IotaResult<ResolvedStateOverrides> {
+        let Some(state_overrides) = state_overrides.filter(|overrides| !overrides.is_empty())
+        else {
+            return Ok(ResolvedStateOverrides::default());
+        };
+        state_overrides.resolve(
+            self.get_backing_store().as_ref(),
+            epoch_store.protocol_config(),
+        )
+    }
+
     /// The object ID for gas can be any object ID, even for an uncreated object
     pub async fn dev_inspect_transaction_block(
         &self,
@@ -1870,6 +1958,37 @@ impl AuthorityState {
         gas_objects: Option<Vec<ObjectRef>>,
         show_raw_txn_data_and_effects: Option<bool>,
         skip_checks: Option<bool>,
+    ) -> IotaResult<DevInspectResults> {
+        self.dev_inspect_transaction_block_with_overrides(
+            sender,
+            transaction_kind,
+            gas_price,
+            gas_budget,
+            gas_sponsor,
+            gas_objects,
+            show_raw_txn_data_and_effects,
+            skip_checks,
+            None,
+            None,
+        )
+        .await
+    }
+
+    /// Like [`Self::dev_inspect_transaction_block`], but executes the
+    /// transaction against the on-chain state with the given overrides
+    /// applied.
+    pub async fn dev_inspect_transaction_block_with_overrides(
+        &self,
+        sender: IotaAddress,
+        transaction_kind: TransactionKind,
+        gas_price: Option<u64>,
+        gas_budget: Option<u64>,
+        gas_sponsor: Option<IotaAddress>,
+        gas_objects: Option<Vec<ObjectRef>>,
+        show_raw_txn_data_and_effects: Option<bool>,
+        skip_checks: Option<bool>,
+        state_overrides: Option<StateOverrides>,
+        trace: Option<bool>,
     ) -> IotaResult<DevInspectResults> {
         let epoch_store = self.load_epoch_store_one_call_per_task();
 
@@ -1919,6 +2038,8 @@ impl AuthorityState {
 
         transaction.validity_check_no_gas_check(protocol_config)?;
 
+        let state_overrides = self.resolve_state_overrides(&epoch_store, state_overrides)?;
+
         let input_object_kinds = transaction.input_objects()?;
         let receiving_object_refs = transaction.receiving_objects();
 
@@ -1928,6 +2049,7 @@ impl AuthorityState {
             &input_object_kinds,
             &receiving_object_refs,
             &self.config.transaction_deny_config,
+            &self.dynamic_deny_list.entries(),
             self.get_backing_package_store().as_ref(),
         )?;
 
@@ -1938,6 +2060,7 @@ impl AuthorityState {
             &receiving_object_refs,
             epoch_store.epoch(),
         )?;
+        state_overrides.apply_to_inputs(&mut input_objects);
 
         // Create and use a dummy gas object if there is no gas object provided.
         let dummy_gas_object = Object::new_gas_with_balance_and_owner_for_testing(
@@ -2014,14 +2137,18 @@ impl AuthorityState {
             transaction,
         );
         let transaction_digest = TransactionDigest::new(default_hash(&intent_msg.value));
+        let backing_store = state_overrides.overlay(self.get_backing_store().as_ref());
+        let track_command_gas = true;
+        let mut instrumentation =
+            ExecutionInstrumentation::new(track_command_gas, trace.unwrap_or(false));
         let (inner_temp_store, _, effects, execution_result) = executor.dev_inspect_transaction(
-            self.get_backing_store().as_ref(),
+            &backing_store,
             protocol_config,
             self.metrics.limits_metrics.clone(),
             // expensive checks
             false,
             self.config.certificate_deny_config.certificate_deny_set(),
-            &epoch_store.epoch_start_config().epoch_data().epoch_id(),
+            &state_overrides.epoch_or(epoch_store.epoch_start_config().epoch_data().epoch_id()),
             epoch_store
                 .epoch_start_config()
                 .epoch_data()
@@ -2033,6 +2160,7 @@ impl AuthorityState {
             sender,
             transaction_digest,
             skip_checks,
+            &mut instrumentation,
         );
 
         let raw_effects = if show_raw_txn_data_and_effects {
@@ -2048,7 +2176,7 @@ impl AuthorityState {
                 .executor()
                 .type_layout_resolver(Box::new(PackageStoreWithFallback::new(
                     &inner_temp_store,
-                    self.get_backing_package_store(),
+                    &backing_store,
                 )));
 
         DevInspectResults::new(
@@ -2057,6 +2185,8 @@ impl AuthorityState {
             execution_result,
             raw_txn_data,
             raw_effects,
+            instrumentation.gas_breakdown(),
+            instrumentation.execution_trace,
             layout_resolver.as_mut(),
         )
     }
@@ -2662,6 +2792,8 @@ impl AuthorityState {
         );
         let input_loader =
             TransactionInputLoader::new(execution_cache_trait_pointers.object_cache_reader.clone());
+        let dynamic_deny_list = DynamicDenyList::load(config.dynamic_deny_list_path())
+            .expect("Failed to load the dynamic deny list");
         let epoch = epoch_store.epoch();
         let state = Arc::new(AuthorityState {
             name,
@@ -2682,6 +2814,7 @@ impl AuthorityState {
             _authority_per_epoch_pruner,
             db_checkpoint_config: db_checkpoint_config.clone(),
             config,
+            dynamic_deny_list,
             overload_info: AuthorityOverloadInfo::default(),
             validator_tx_finalizer,
         });
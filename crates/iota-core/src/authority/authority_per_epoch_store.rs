@@ -7,6 +7,7 @@ use std::{
     future::Future,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use arc_swap::ArcSwapOption;
@@ -26,7 +27,9 @@ use iota_config::node::ExpensiveSafetyCheckConfig;
 use iota_execution::{self, Executor};
 use iota_macros::{fail_point, fail_point_arg};
 use iota_metrics::monitored_scope;
-use iota_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
+use iota_protocol_config::{
+    Chain, PerObjectCongestionControlMode, ProtocolConfig, ProtocolVersion,
+};
 use iota_storage::mutex_table::{MutexGuard, MutexTable};
 use iota_types::{
     accumulator::Accumulator,
@@ -50,14 +53,16 @@ use iota_types::{
     },
     messages_consensus::{
         AuthorityCapabilitiesV1, ConsensusTransaction, ConsensusTransactionKey,
-        ConsensusTransactionKind, VersionedDkgConfirmation, check_total_jwk_size,
+        ConsensusTransactionKind, ExecutionTimeObservation, ExecutionTimeObservationKey,
+        MAX_EXECUTION_TIME_OBSERVATIONS_PER_MESSAGE, VersionedDkgConfirmation,
+        check_total_jwk_size,
     },
     signature::GenericSignature,
     storage::{BackingPackageStore, GetSharedLocks, InputKey, ObjectStore},
     transaction::{
         AuthenticatorStateUpdateV1, CertifiedTransaction, InputObjectKind, SenderSignedData,
-        Transaction, TransactionDataAPI, TransactionKey, TransactionKind, VerifiedCertificate,
-        VerifiedSignedTransaction, VerifiedTransaction,
+        Transaction, TransactionData, TransactionDataAPI, TransactionKey, TransactionKind,
+        VerifiedCertificate, VerifiedSignedTransaction, VerifiedTransaction,
     },
 };
 use itertools::{Itertools, izip};
@@ -81,6 +86,7 @@ use typed_store::{
 use super::{
     authority_store_tables::ENV_VAR_LOCKS_BLOCK_CACHE_SIZE,
     epoch_start_configuration::EpochStartConfigTrait,
+    execution_time_estimator::{ExecutionTimeEstimator, ExecutionTimeObserver},
     shared_object_congestion_tracker::SharedObjectCongestionTracker,
     transaction_deferral::{DeferralKey, DeferralReason, transaction_deferral_within_limit},
 };
@@ -386,6 +392,13 @@ pub struct AuthorityPerEpochStore {
     /// aggregator for JWK votes
     jwk_aggregator: Mutex<JwkAggregator>,
 
+    /// Execution times of Move entry functions measured locally, to be shared
+    /// through consensus.
+    execution_time_observer: Mutex<ExecutionTimeObserver>,
+    /// Committee-agreed execution time estimates, combined from the
+    /// execution time observations received through consensus.
+    execution_time_estimator: Mutex<ExecutionTimeEstimator>,
+
     /// State machine managing randomness DKG and generation.
     randomness_manager: OnceCell<tokio::sync::Mutex<RandomnessManager>>,
     randomness_reporter: OnceCell<RandomnessReporter>,
@@ -550,6 +563,12 @@ pub struct AuthorityEpochTables {
     /// Record of the capabilities advertised by each authority.
     authority_capabilities_v1: DBMap<AuthorityName, AuthorityCapabilitiesV1>,
 
+    /// Latest execution time estimate received from each authority for each
+    /// entry function, along with the generation of the observation it was
+    /// received in.
+    execution_time_observations:
+        DBMap<(AuthorityName, ExecutionTimeObservationKey), (u64, Duration)>,
+
     /// Contains a single key, which overrides the value of
     /// ProtocolConfig::buffer_stake_for_protocol_upgrade_bps
     override_protocol_upgrade_buffer_stake: DBMap<u64, u64>,
@@ -862,6 +881,14 @@ impl AuthorityPerEpochStore {
 
         let jwk_aggregator = Mutex::new(jwk_aggregator);
 
+        let execution_time_estimator = Mutex::new(ExecutionTimeEstimator::new_from_persisted(
+            committee.clone(),
+            tables
+                .execution_time_observations
+                .unbounded_iter()
+                .seek_to_first(),
+        ));
+
         let s = Arc::new(Self {
             name,
             committee,
@@ -888,6 +915,8 @@ impl AuthorityPerEpochStore {
             execution_component,
             chain_identifier,
             jwk_aggregator,
+            execution_time_observer: Mutex::new(ExecutionTimeObserver::new(name)),
+            execution_time_estimator,
             randomness_manager: OnceCell::new(),
             randomness_reporter: OnceCell::new(),
         });
@@ -903,6 +932,16 @@ impl AuthorityPerEpochStore {
         }
     }
 
+    /// Creates a consistent RocksDB checkpoint of the tables of this epoch at
+    /// `path`.
+    pub fn checkpoint_db(&self, path: &Path) -> IotaResult {
+        // This checkpoints the entire db and not just the signed transactions table
+        self.tables()?
+            .signed_transactions
+            .checkpoint_db(path)
+            .map_err(Into::into)
+    }
+
     // Ideally the epoch tables handle should have the same lifetime as the outer
     // AuthorityPerEpochStore, and this function should be unnecessary. But
     // unfortunately, Arc<AuthorityPerEpochStore> outlives the
@@ -2081,6 +2120,51 @@ impl AuthorityPerEpochStore {
         Ok(())
     }
 
+    fn record_execution_time_observation(
+        &self,
+        output: &mut ConsensusCommitOutput,
+        observation: &ExecutionTimeObservation,
+    ) {
+        // Observations are processed in consensus order, so all authorities
+        // agree on which ones are stale. The updated estimates take effect
+        // from the next consensus commit.
+        if let Some(estimates) = self
+            .execution_time_estimator
+            .lock()
+            .process_observation(observation)
+        {
+            output.insert_execution_time_observation(ExecutionTimeObservation {
+                authority: observation.authority,
+                generation: observation.generation,
+                estimates,
+            });
+        }
+    }
+
+    /// Records the locally measured execution time of a transaction, if this
+    /// is a validator and execution time estimates are used for shared
+    /// object congestion control.
+    pub fn record_local_execution_time(&self, tx_data: &TransactionData, duration: Duration) {
+        if self.protocol_config().per_object_congestion_control_mode()
+            != PerObjectCongestionControlMode::ExecutionTimeEstimate
+            || !self.committee.authority_exists(&self.name)
+        {
+            return;
+        }
+        self.execution_time_observer
+            .lock()
+            .record_local_observation(tx_data, duration);
+    }
+
+    /// Returns the local execution time estimates that should be shared
+    /// through consensus, if any.
+    pub fn take_pending_execution_time_observation(&self) -> Option<ConsensusTransaction> {
+        self.execution_time_observer
+            .lock()
+            .take_pending_observation()
+            .map(ConsensusTransaction::new_execution_time_observation)
+    }
+
     pub fn get_capabilities_v1(&self) -> IotaResult<Vec<AuthorityCapabilitiesV1>> {
         let result: Result<Vec<AuthorityCapabilitiesV1>, TypedStoreError> = self
             .tables()?
@@ -2410,6 +2494,34 @@ impl AuthorityPerEpochStore {
                     return None;
                 }
             }
+            SequencedConsensusTransactionKind::External(ConsensusTransaction {
+                kind: ConsensusTransactionKind::ExecutionTimeObservation(observation),
+                ..
+            }) => {
+                if transaction.sender_authority() != observation.authority {
+                    warn!(
+                        "ExecutionTimeObservation authority {} does not match its author from consensus {}",
+                        observation.authority, transaction.certificate_author_index
+                    );
+                    return None;
+                }
+                if self.protocol_config().per_object_congestion_control_mode()
+                    != PerObjectCongestionControlMode::ExecutionTimeEstimate
+                {
+                    warn!(
+                        "{:?} sent execution time observation while execution time estimates are disabled",
+                        transaction.sender_authority().concise()
+                    );
+                    return None;
+                }
+                if observation.estimates.len() > MAX_EXECUTION_TIME_OBSERVATIONS_PER_MESSAGE {
+                    warn!(
+                        "{:?} sent execution time observation with too many estimates",
+                        transaction.sender_authority().concise()
+                    );
+                    return None;
+                }
+            }
             SequencedConsensusTransactionKind::System(_) => {}
         }
         Some(VerifiedSequencedConsensusTransaction(transaction))
@@ -2948,13 +3060,16 @@ impl AuthorityPerEpochStore {
         // We track transaction execution cost separately for regular transactions and
         // transactions using randomness, since they will be in different
         // checkpoints.
+        let execution_time_estimates = self.execution_time_estimator.lock().estimates();
         let mut shared_object_congestion_tracker = SharedObjectCongestionTracker::new(
             self.protocol_config().per_object_congestion_control_mode(),
-        );
+        )
+        .with_execution_time_estimates(execution_time_estimates.clone());
         let mut shared_object_using_randomness_congestion_tracker =
             SharedObjectCongestionTracker::new(
                 self.protocol_config().per_object_congestion_control_mode(),
-            );
+            )
+            .with_execution_time_estimates(execution_time_estimates);
 
         fail_point_arg!(
             "initial_congestion_tracker",
@@ -3403,6 +3518,27 @@ impl AuthorityPerEpochStore {
                 }
                 Ok(ConsensusCertificateResult::ConsensusMessage)
             }
+            SequencedConsensusTransactionKind::External(ConsensusTransaction {
+                kind: ConsensusTransactionKind::ExecutionTimeObservation(observation),
+                ..
+            }) => {
+                if self
+                    .get_reconfig_state_read_lock_guard()
+                    .should_accept_consensus_certs()
+                {
+                    debug!(
+                        "Received ExecutionTimeObservation from {:?}",
+                        observation.authority.concise()
+                    );
+                    self.record_execution_time_observation(output, observation);
+                } else {
+                    debug!(
+                        "Ignoring ExecutionTimeObservation from {:?} because of end of epoch",
+                        observation.authority.concise()
+                    );
+                }
+                Ok(ConsensusCertificateResult::ConsensusMessage)
+            }
             SequencedConsensusTransactionKind::External(ConsensusTransaction {
                 kind: ConsensusTransactionKind::NewJWKFetched(authority, jwk_id, jwk),
                 ..
@@ -3842,6 +3978,9 @@ pub(crate) struct ConsensusCommitOutput {
     // jwk state
     pending_jwks: BTreeSet<(AuthorityName, JwkId, JWK)>,
     active_jwks: BTreeSet<(u64, (JwkId, JWK))>,
+
+    // congestion control state
+    execution_time_observations: Vec<ExecutionTimeObservation>,
 }
 
 impl ConsensusCommitOutput {
@@ -3941,6 +4080,10 @@ impl ConsensusCommitOutput {
         self.active_jwks.insert((round, key));
     }
 
+    fn insert_execution_time_observation(&mut self, observation: ExecutionTimeObservation) {
+        self.execution_time_observations.push(observation);
+    }
+
     pub fn write_to_batch(
         self,
         epoch_store: &AuthorityPerEpochStore,
@@ -4038,6 +4181,23 @@ impl ConsensusCommitOutput {
             self.active_jwks.into_iter().map(|j| (j, ())),
         )?;
 
+        batch.insert_batch(
+            &tables.execution_time_observations,
+            self.execution_time_observations
+                .into_iter()
+                .flat_map(|observation| {
+                    observation
+                        .estimates
+                        .into_iter()
+                        .map(move |(key, duration)| {
+                            (
+                                (observation.authority, key),
+                                (observation.generation, duration),
+                            )
+                        })
+                }),
+        )?;
+
         Ok(())
     }
 }
@@ -4,24 +4,29 @@
 
 use std::{
     cmp::{max, min},
-    collections::{BTreeSet, HashMap},
+    collections::{BTreeSet, HashMap, HashSet},
     sync::{Arc, Mutex},
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use anyhow::anyhow;
 use iota_archival::reader::ArchiveReaderBalancer;
-use iota_config::node::AuthorityStorePruningConfig;
+use iota_config::node::{
+    AuthorityStorePruningConfig, ObjectRetentionFilter, ObjectRetentionPolicy,
+};
 use iota_metrics::{monitored_scope, spawn_monitored_task};
 use iota_storage::mutex_table::RwLockTable;
 use iota_types::{
-    base_types::{ObjectID, SequenceNumber, VersionNumber},
+    base_types::{IotaAddress, ObjectID, SequenceNumber, VersionNumber},
     committee::EpochId,
     effects::{TransactionEffects, TransactionEffectsAPI},
     message_envelope::Message,
     messages_checkpoint::{CheckpointContents, CheckpointDigest, CheckpointSequenceNumber},
+    object::Object,
+    parse_iota_struct_tag,
     storage::ObjectKey,
 };
+use move_core_types::language_storage::StructTag;
 use once_cell::sync::Lazy;
 use prometheus::{
     IntCounter, IntGauge, Registry, register_int_counter_with_registry,
@@ -136,8 +141,90 @@ pub enum PruningMode {
     Checkpoints,
 }
 
+/// How long an object version matched by an `ObjectRetentionPolicy` is
+/// retained.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Retention {
+    /// The version may be pruned from the given epoch on.
+    Until(EpochId),
+    Forever,
+}
+
+#[derive(Debug)]
+enum RetentionFilter {
+    ObjectType(StructTag),
+    Package(ObjectID),
+    Owner(IotaAddress),
+}
+
+impl RetentionFilter {
+    fn matches(&self, object: &Object) -> bool {
+        match self {
+            Self::ObjectType(struct_tag) => object.type_().is_some_and(|type_| {
+                type_.address() == struct_tag.address
+                    && type_.module() == struct_tag.module.as_ident_str()
+                    && type_.name() == struct_tag.name.as_ident_str()
+                    && (struct_tag.type_params.is_empty()
+                        || type_.type_params() == struct_tag.type_params)
+            }),
+            Self::Package(package) => object
+                .type_()
+                .is_some_and(|type_| ObjectID::from(type_.address()) == *package),
+            Self::Owner(address) => object
+                .owner
+                .get_owner_address()
+                .is_ok_and(|owner| owner == *address),
+        }
+    }
+}
+
+/// The `ObjectRetentionPolicy`s of the pruning config, parsed for matching
+/// against object versions.
+#[derive(Debug, Default)]
+pub struct ObjectRetentionPolicies(Vec<(RetentionFilter, Option<u64>)>);
+
+impl ObjectRetentionPolicies {
+    pub fn new(policies: &[ObjectRetentionPolicy]) -> anyhow::Result<Self> {
+        policies
+            .iter()
+            .map(|policy| {
+                let filter = match &policy.filter {
+                    ObjectRetentionFilter::ObjectType(type_) => {
+                        RetentionFilter::ObjectType(parse_iota_struct_tag(type_).map_err(|e| {
+                            anyhow!("invalid object type {type_} in object retention policy: {e}")
+                        })?)
+                    }
+                    ObjectRetentionFilter::Package(package) => RetentionFilter::Package(*package),
+                    ObjectRetentionFilter::Owner(owner) => RetentionFilter::Owner(*owner),
+                };
+                Ok((filter, policy.num_epochs_to_retain))
+            })
+            .collect::<anyhow::Result<_>>()
+            .map(Self)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the longest retention of all policies matching `object`, a
+    /// version that was superseded in `superseded_epoch`.
+    fn retention(&self, object: &Object, superseded_epoch: EpochId) -> Option<Retention> {
+        self.0
+            .iter()
+            .filter(|(filter, _)| filter.matches(object))
+            .map(|(_, num_epochs_to_retain)| match num_epochs_to_retain {
+                Some(num_epochs) => Retention::Until(superseded_epoch.saturating_add(*num_epochs)),
+                None => Retention::Forever,
+            })
+            .max()
+    }
+}
+
 impl AuthorityStorePruner {
-    /// prunes old versions of objects based on transaction effects
+    /// prunes old versions of objects based on transaction effects. Versions
+    /// matched by one of the `retention_policies` are kept, and recorded in
+    /// `retained_object_versions` if they may be pruned in a later epoch.
     async fn prune_objects(
         transaction_effects: Vec<TransactionEffects>,
         perpetual_db: &Arc<AuthorityPerpetualTables>,
@@ -145,36 +232,68 @@ impl AuthorityStorePruner {
         checkpoint_number: CheckpointSequenceNumber,
         metrics: Arc<AuthorityStorePruningMetrics>,
         indirect_objects_threshold: usize,
+        retention_policies: &ObjectRetentionPolicies,
+        current_epoch: EpochId,
     ) -> anyhow::Result<()> {
         let _scope = monitored_scope("ObjectsLivePruner");
         let mut wb = perpetual_db.objects.batch();
 
-        // Collect objects keys that need to be deleted from `transaction_effects`.
+        // Collect objects keys that need to be deleted from `transaction_effects`,
+        // along with the epoch in which they were superseded.
         let mut live_object_keys_to_prune = vec![];
         let mut object_tombstones_to_prune = vec![];
         for effects in &transaction_effects {
+            let epoch = effects.executed_epoch();
             for (object_id, seq_number) in effects.modified_at_versions() {
-                live_object_keys_to_prune.push(ObjectKey(object_id, seq_number));
+                live_object_keys_to_prune.push((ObjectKey(object_id, seq_number), epoch));
             }
 
             for deleted_object_key in effects.all_tombstones() {
                 object_tombstones_to_prune
-                    .push(ObjectKey(deleted_object_key.0, deleted_object_key.1));
+                    .push((ObjectKey(deleted_object_key.0, deleted_object_key.1), epoch));
             }
         }
 
-        metrics
-            .num_pruned_objects
-            .inc_by(live_object_keys_to_prune.len() as u64);
-        metrics
-            .num_pruned_tombstones
-            .inc_by(object_tombstones_to_prune.len() as u64);
+        // Object versions kept by a retention policy, and the subset of them that
+        // can be pruned in a later epoch.
+        let mut retained_object_keys = HashSet::new();
+        let mut retained_object_versions = vec![];
+        let mut retained_object_ids: HashMap<ObjectID, Retention> = HashMap::new();
+        if !retention_policies.is_empty() {
+            let objects = perpetual_db
+                .objects
+                .multi_get(live_object_keys_to_prune.iter().map(|(key, _)| key))?;
+            for ((object_key, epoch), store_object) in live_object_keys_to_prune.iter().zip(objects)
+            {
+                let Some(store_object) = store_object else {
+                    continue;
+                };
+                let Some(object) = perpetual_db.object(object_key, store_object)? else {
+                    continue;
+                };
+                let retention = match retention_policies.retention(&object, *epoch) {
+                    None => continue,
+                    Some(Retention::Until(until)) if until <= current_epoch => continue,
+                    Some(retention @ Retention::Until(until)) => {
+                        retained_object_versions.push((until, *object_key));
+                        retention
+                    }
+                    Some(Retention::Forever) => Retention::Forever,
+                };
+                retained_object_keys.insert(*object_key);
+                retained_object_ids
+                    .entry(object_key.0)
+                    .and_modify(|max_retention| *max_retention = max(*max_retention, retention))
+                    .or_insert(retention);
+            }
+            live_object_keys_to_prune.retain(|(key, _)| !retained_object_keys.contains(key));
+        }
 
         let mut indirect_objects: HashMap<_, i64> = HashMap::new();
         if indirect_objects_threshold > 0 && indirect_objects_threshold < usize::MAX {
             for object in perpetual_db
                 .objects
-                .multi_get(live_object_keys_to_prune.iter())?
+                .multi_get(live_object_keys_to_prune.iter().map(|(key, _)| key))?
                 .into_iter()
                 .flatten()
             {
@@ -186,8 +305,16 @@ impl AuthorityStorePruner {
             }
         }
 
+        // Range deletes would also remove the retained versions in between, so
+        // objects with retained versions are pruned with point deletes.
         let mut updates: HashMap<ObjectID, (VersionNumber, VersionNumber)> = HashMap::new();
-        for ObjectKey(object_id, seq_number) in live_object_keys_to_prune {
+        let mut object_keys_to_delete = vec![];
+        for (object_key, _) in &live_object_keys_to_prune {
+            let ObjectKey(object_id, seq_number) = *object_key;
+            if retained_object_ids.contains_key(&object_id) {
+                object_keys_to_delete.push(*object_key);
+                continue;
+            }
             updates
                 .entry(object_id)
                 .and_modify(|range| *range = (min(range.0, seq_number), max(range.1, seq_number)))
@@ -212,21 +339,60 @@ impl AuthorityStorePruner {
         // (imagine a tombstone is compacted away, but earlier version is still not).
         // Using point deletes guarantees that all earlier versions are deleted
         // in the database.
-        if !object_tombstones_to_prune.is_empty() {
-            let mut object_keys_to_delete = vec![];
-            for ObjectKey(object_id, seq_number) in object_tombstones_to_prune {
-                for result in perpetual_db.objects.safe_iter_with_bounds(
-                    Some(ObjectKey(object_id, VersionNumber::MIN)),
-                    Some(ObjectKey(object_id, seq_number.next())),
-                ) {
-                    let (object_key, _) = result?;
-                    assert_eq!(object_key.0, object_id);
-                    object_keys_to_delete.push(object_key);
+        //
+        // Versions of deleted objects kept by a retention policy are not deleted.
+        // The tombstone is then kept as long as any of them, so that the object
+        // history can still be resolved.
+        let mut num_pruned_tombstones = 0;
+        for (ObjectKey(object_id, seq_number), epoch) in object_tombstones_to_prune {
+            let mut object_keys = vec![];
+            let mut tombstone_retention = retained_object_ids.get(&object_id).copied();
+            for result in perpetual_db.objects.safe_iter_with_bounds(
+                Some(ObjectKey(object_id, VersionNumber::MIN)),
+                Some(ObjectKey(object_id, seq_number.next())),
+            ) {
+                let (object_key, store_object) = result?;
+                assert_eq!(object_key.0, object_id);
+                if retained_object_keys.contains(&object_key) {
+                    continue;
+                }
+                let retention = if retention_policies.is_empty() {
+                    None
+                } else {
+                    perpetual_db
+                        .object(&object_key, store_object)?
+                        .and_then(|object| retention_policies.retention(&object, epoch))
+                        .filter(|retention| *retention > Retention::Until(current_epoch))
+                };
+                match retention {
+                    // Versions kept by an earlier pruning run.
+                    Some(retention) => {
+                        tombstone_retention = max(tombstone_retention, Some(retention))
+                    }
+                    None => object_keys.push(object_key),
                 }
             }
-
-            wb.delete_batch(&perpetual_db.objects, object_keys_to_delete)?;
+            if let Some(retention) = tombstone_retention {
+                let tombstone_key = ObjectKey(object_id, seq_number);
+                object_keys.retain(|key| *key != tombstone_key);
+                if let Retention::Until(until) = retention {
+                    retained_object_versions.push((until, tombstone_key));
+                }
+            } else {
+                num_pruned_tombstones += 1;
+            }
+            object_keys_to_delete.extend(object_keys);
         }
+        wb.delete_batch(&perpetual_db.objects, object_keys_to_delete)?;
+        wb.insert_batch(
+            &perpetual_db.retained_object_versions,
+            retained_object_versions.into_iter().map(|key| (key, ())),
+        )?;
+
+        metrics
+            .num_pruned_objects
+            .inc_by(live_object_keys_to_prune.len() as u64);
+        metrics.num_pruned_tombstones.inc_by(num_pruned_tombstones);
 
         if !indirect_objects.is_empty() {
             let ref_count_update = indirect_objects
@@ -244,6 +410,78 @@ impl AuthorityStorePruner {
         Ok(())
     }
 
+    /// Prunes the object versions kept by a retention policy whose retention
+    /// has expired by `current_epoch`.
+    async fn prune_expired_retained_objects(
+        perpetual_db: &Arc<AuthorityPerpetualTables>,
+        objects_lock_table: &Arc<RwLockTable<ObjectContentDigest>>,
+        metrics: Arc<AuthorityStorePruningMetrics>,
+        indirect_objects_threshold: usize,
+        current_epoch: EpochId,
+    ) -> anyhow::Result<()> {
+        let _scope = monitored_scope("RetainedObjectsPruner");
+        let expired = perpetual_db
+            .retained_object_versions
+            .safe_iter_with_bounds(None, Some((current_epoch + 1, ObjectKey::ZERO)))
+            .map(|result| result.map(|(key, _)| key))
+            .collect::<Result<Vec<_>, _>>()?;
+        if expired.is_empty() {
+            return Ok(());
+        }
+        let mut object_keys: Vec<_> = expired.iter().map(|(_, object_key)| *object_key).collect();
+
+        let mut indirect_objects: HashMap<_, i64> = HashMap::new();
+        let mut object_tombstones = vec![];
+        for (object_key, object) in object_keys
+            .iter()
+            .zip(perpetual_db.objects.multi_get(object_keys.iter())?)
+        {
+            match object.map(|object| object.into_inner()) {
+                Some(StoreObject::Value(obj)) => {
+                    if indirect_objects_threshold > 0 && indirect_objects_threshold < usize::MAX {
+                        if let StoreData::IndirectObject(indirect_object) = obj.data {
+                            *indirect_objects.entry(indirect_object.digest).or_default() -= 1;
+                        }
+                    }
+                }
+                Some(_) => object_tombstones.push(*object_key),
+                None => {}
+            }
+        }
+        // A tombstone is retained as long as the versions of the object before
+        // it. Prune any version still left with it, so that the object can not
+        // appear live again.
+        for ObjectKey(object_id, seq_number) in object_tombstones {
+            for result in perpetual_db.objects.safe_iter_with_bounds(
+                Some(ObjectKey(object_id, VersionNumber::MIN)),
+                Some(ObjectKey(object_id, seq_number)),
+            ) {
+                let (object_key, _) = result?;
+                object_keys.push(object_key);
+            }
+        }
+
+        info!(
+            "Pruning {} object versions with expired retention",
+            object_keys.len()
+        );
+        metrics.num_pruned_objects.inc_by(object_keys.len() as u64);
+        let mut wb = perpetual_db.objects.batch();
+        wb.delete_batch(&perpetual_db.objects, object_keys)?;
+        wb.delete_batch(&perpetual_db.retained_object_versions, expired)?;
+        if !indirect_objects.is_empty() {
+            let ref_count_update = indirect_objects
+                .iter()
+                .map(|(digest, delta)| (digest, delta.to_le_bytes()));
+            wb.partial_merge_batch(&perpetual_db.indirect_move_objects, ref_count_update)?;
+        }
+        let _locks = objects_lock_table
+            .acquire_locks(indirect_objects.into_keys())
+            .await;
+        wb.write()?;
+        Ok(())
+    }
+
     /// Prunes checkpoint-related data from the `AuthorityStore`, including
     /// transaction effects, executed transactions, and checkpoint contents,
     /// based on the specified checkpoint number and list of checkpoints to
@@ -342,6 +580,16 @@ impl AuthorityStorePruner {
             .map(|c| (*c.sequence_number(), c.epoch))
             .unwrap_or_default();
         let pruned_checkpoint_number = perpetual_db.get_highest_pruned_checkpoint()?;
+        if !config.object_retention_policies.is_empty() {
+            Self::prune_expired_retained_objects(
+                perpetual_db,
+                objects_lock_table,
+                metrics.clone(),
+                indirect_objects_threshold,
+                epoch_id,
+            )
+            .await?;
+        }
         if config.smooth && config.num_epochs_to_retain > 0 {
             max_eligible_checkpoint_number = Self::smoothed_max_eligible_checkpoint_number(
                 checkpoint_store,
@@ -458,6 +706,12 @@ impl AuthorityStorePruner {
             .get_highest_executed_checkpoint()?
             .map(|c| c.epoch())
             .unwrap_or_default();
+        let retention_policies = match mode {
+            PruningMode::Objects => {
+                ObjectRetentionPolicies::new(&config.object_retention_policies)?
+            }
+            PruningMode::Checkpoints => ObjectRetentionPolicies::default(),
+        };
 
         let mut checkpoints_to_prune = vec![];
         let mut checkpoint_content_to_prune = vec![];
@@ -512,6 +766,8 @@ impl AuthorityStorePruner {
                             checkpoint_number,
                             metrics.clone(),
                             indirect_objects_threshold,
+                            &retention_policies,
+                            current_epoch,
                         )
                         .await?
                     }
@@ -544,6 +800,8 @@ impl AuthorityStorePruner {
                         checkpoint_number,
                         metrics.clone(),
                         indirect_objects_threshold,
+                        &retention_policies,
+                        current_epoch,
                     )
                     .await?
                 }
@@ -793,11 +1051,12 @@ impl AuthorityStorePruner {
 mod tests {
     use std::{collections::HashSet, path::Path, sync::Arc, time::Duration};
 
+    use iota_config::node::{ObjectRetentionFilter, ObjectRetentionPolicy};
     use iota_storage::mutex_table::RwLockTable;
     use iota_types::{
-        base_types::{ObjectDigest, ObjectID, SequenceNumber},
+        base_types::{IotaAddress, ObjectDigest, ObjectID, SequenceNumber},
         effects::{TransactionEffects, TransactionEffectsAPI},
-        object::Object,
+        object::{Object, PastObjectRead},
         storage::ObjectKey,
     };
     use more_asserts as ma;
@@ -808,14 +1067,16 @@ mod tests {
         rocks::{DBMap, MetricConf, ReadWriteOptions, util::reference_count_merge_operator},
     };
 
-    use super::AuthorityStorePruner;
+    use super::{AuthorityStorePruner, ObjectRetentionPolicies};
     use crate::authority::{
+        AuthorityState,
         authority_store_pruner::AuthorityStorePruningMetrics,
         authority_store_tables::AuthorityPerpetualTables,
         authority_store_types::{
             ObjectContentDigest, StoreData, StoreObject, StoreObjectPair, StoreObjectWrapper,
             get_store_object_pair,
         },
+        test_authority_builder::TestAuthorityBuilder,
     };
 
     fn get_keys_after_pruning(path: &Path) -> anyhow::Result<HashSet<ObjectKey>> {
@@ -966,6 +1227,8 @@ mod tests {
                 0,
                 metrics,
                 indirect_object_threshold,
+                &ObjectRetentionPolicies::default(),
+                0,
             )
             .await
             .unwrap();
@@ -1030,6 +1293,219 @@ mod tests {
         }
     }
 
+    // Writes 3 versions of an object owned by `retained_owner` and of an object
+    // owned by another address, and prunes the 2 oldest versions of both.
+    async fn run_pruner_with_retention_policy(
+        path: &Path,
+        retained_owner: IotaAddress,
+        num_epochs_to_retain: Option<u64>,
+    ) -> (ObjectID, ObjectID) {
+        let db = Arc::new(AuthorityPerpetualTables::open(path, None));
+        let retained_id = ObjectID::random();
+        let pruned_id = ObjectID::random();
+        let mut effects = TransactionEffects::default();
+        for (id, owner) in [
+            (retained_id, retained_owner),
+            (pruned_id, IotaAddress::random_for_testing_only()),
+        ] {
+            for seq in 0..3 {
+                let obj = get_store_object_pair(Object::with_id_owner_for_testing(id, owner), 0).0;
+                db.objects
+                    .insert(&ObjectKey(id, SequenceNumber::from(seq)), &obj)
+                    .unwrap();
+                if seq < 2 {
+                    effects.unsafe_add_deleted_live_object_for_testing((
+                        id,
+                        SequenceNumber::from(seq),
+                        ObjectDigest::MIN,
+                    ));
+                }
+            }
+        }
+        let retention_policies = ObjectRetentionPolicies::new(&[ObjectRetentionPolicy {
+            filter: ObjectRetentionFilter::Owner(retained_owner),
+            num_epochs_to_retain,
+        }])
+        .unwrap();
+        AuthorityStorePruner::prune_objects(
+            vec![effects],
+            &db,
+            &lock_table(),
+            0,
+            AuthorityStorePruningMetrics::new_for_test(),
+            0,
+            &retention_policies,
+            0,
+        )
+        .await
+        .unwrap();
+        (retained_id, pruned_id)
+    }
+
+    fn object_keys(id: ObjectID, versions: &[u64]) -> Vec<ObjectKey> {
+        versions
+            .iter()
+            .map(|seq| ObjectKey(id, SequenceNumber::from(*seq)))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_pruning_with_retention_policy() {
+        let path = tempfile::tempdir().unwrap().into_path();
+        let (retained_id, pruned_id) =
+            run_pruner_with_retention_policy(&path, IotaAddress::random_for_testing_only(), None)
+                .await;
+        let mut expected = object_keys(retained_id, &[0, 1, 2]);
+        expected.extend(object_keys(pruned_id, &[2]));
+        assert_eq!(
+            HashSet::from_iter(expected),
+            get_keys_after_pruning(&path).unwrap()
+        );
+        let db = AuthorityPerpetualTables::open(&path, None);
+        assert_eq!(db.retained_object_versions.unbounded_iter().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_pruning_expired_retained_objects() {
+        let path = tempfile::tempdir().unwrap().into_path();
+        let (retained_id, pruned_id) = run_pruner_with_retention_policy(
+            &path,
+            IotaAddress::random_for_testing_only(),
+            Some(2),
+        )
+        .await;
+        {
+            let db = Arc::new(AuthorityPerpetualTables::open(&path, None));
+            assert_eq!(db.retained_object_versions.unbounded_iter().count(), 2);
+
+            // Retained versions are kept until their retention expires.
+            for current_epoch in [1, 2] {
+                AuthorityStorePruner::prune_expired_retained_objects(
+                    &db,
+                    &lock_table(),
+                    AuthorityStorePruningMetrics::new_for_test(),
+                    0,
+                    current_epoch,
+                )
+                .await
+                .unwrap();
+                let expected_retained = if current_epoch < 2 { 2 } else { 0 };
+                assert_eq!(
+                    db.retained_object_versions.unbounded_iter().count(),
+                    expected_retained
+                );
+            }
+        }
+        let mut expected = object_keys(retained_id, &[2]);
+        expected.extend(object_keys(pruned_id, &[2]));
+        assert_eq!(
+            HashSet::from_iter(expected),
+            get_keys_after_pruning(&path).unwrap()
+        );
+    }
+
+    // Writes 2 versions of an object owned by a retained owner followed by its
+    // tombstone, as left behind by a transaction deleting the object, and
+    // prunes them with a retention policy for that owner.
+    async fn delete_and_prune_retained_object(
+        state: &AuthorityState,
+        num_epochs_to_retain: Option<u64>,
+    ) -> ObjectID {
+        let db = state.database_for_testing().perpetual_tables.clone();
+        let owner = IotaAddress::random_for_testing_only();
+        let id = ObjectID::random();
+        let mut effects = TransactionEffects::default();
+        for seq in 1..3 {
+            let version = SequenceNumber::from(seq);
+            let obj = get_store_object_pair(
+                Object::with_id_owner_version_for_testing(id, version, owner),
+                0,
+            )
+            .0;
+            db.objects.insert(&ObjectKey(id, version), &obj).unwrap();
+            effects.unsafe_add_deleted_live_object_for_testing((id, version, ObjectDigest::MIN));
+        }
+        let tombstone_version = SequenceNumber::from(3);
+        db.objects
+            .insert(
+                &ObjectKey(id, tombstone_version),
+                &StoreObjectWrapper::V1(StoreObject::Deleted),
+            )
+            .unwrap();
+        effects.unsafe_add_object_tombstone_for_testing((id, tombstone_version, ObjectDigest::MIN));
+
+        let retention_policies = ObjectRetentionPolicies::new(&[ObjectRetentionPolicy {
+            filter: ObjectRetentionFilter::Owner(owner),
+            num_epochs_to_retain,
+        }])
+        .unwrap();
+        AuthorityStorePruner::prune_objects(
+            vec![effects],
+            &db,
+            &lock_table(),
+            0,
+            AuthorityStorePruningMetrics::new_for_test(),
+            0,
+            &retention_policies,
+            0,
+        )
+        .await
+        .unwrap();
+        id
+    }
+
+    #[tokio::test]
+    async fn test_past_object_read_of_retained_deleted_object() {
+        let state = TestAuthorityBuilder::new().build().await;
+        let id = delete_and_prune_retained_object(&state, None).await;
+
+        let retained_version = SequenceNumber::from(2);
+        match state.get_past_object_read(&id, retained_version).unwrap() {
+            PastObjectRead::VersionFound((object_id, version, _), object, _) => {
+                assert_eq!(object_id, id);
+                assert_eq!(version, retained_version);
+                assert_eq!(object.version(), retained_version);
+            }
+            other => panic!("unexpected past object read {other:?}"),
+        }
+        match state
+            .get_past_object_read(&id, SequenceNumber::from(3))
+            .unwrap()
+        {
+            PastObjectRead::ObjectDeleted((object_id, version, digest)) => {
+                assert_eq!(object_id, id);
+                assert_eq!(version, SequenceNumber::from(3));
+                assert!(!digest.is_alive());
+            }
+            other => panic!("unexpected past object read {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_past_object_read_after_retention_expired() {
+        let state = TestAuthorityBuilder::new().build().await;
+        let id = delete_and_prune_retained_object(&state, Some(1)).await;
+
+        AuthorityStorePruner::prune_expired_retained_objects(
+            &state.database_for_testing().perpetual_tables,
+            &lock_table(),
+            AuthorityStorePruningMetrics::new_for_test(),
+            0,
+            1,
+        )
+        .await
+        .unwrap();
+
+        for version in [2, 3] {
+            assert!(matches!(
+                state
+                    .get_past_object_read(&id, SequenceNumber::from(version))
+                    .unwrap(),
+                PastObjectRead::ObjectNotExists(object_id) if object_id == id
+            ));
+        }
+    }
+
     #[cfg(not(target_env = "msvc"))]
     #[tokio::test]
     async fn test_db_size_after_compaction() -> Result<(), anyhow::Error> {
@@ -1091,6 +1567,8 @@ mod tests {
             0,
             metrics,
             0,
+            &ObjectRetentionPolicies::default(),
+            0,
         )
         .await;
         info!("Total pruned keys = {:?}", total_pruned);
@@ -1125,7 +1603,7 @@ mod pprof_tests {
     use tracing::log::{error, info};
     use typed_store::{Map, rocks::DBMap};
 
-    use super::AuthorityStorePruner;
+    use super::{AuthorityStorePruner, ObjectRetentionPolicies};
     use crate::authority::{
         authority_store_pruner::{AuthorityStorePruningMetrics, tests, tests::lock_table},
         authority_store_tables::AuthorityPerpetualTables,
@@ -1206,6 +1684,8 @@ mod pprof_tests {
             0,
             metrics,
             1,
+            &ObjectRetentionPolicies::default(),
+            0,
         )
         .await?;
         let guard = pprof::ProfilerGuardBuilder::default()
@@ -1243,6 +1723,8 @@ mod pprof_tests {
             0,
             metrics,
             1,
+            &ObjectRetentionPolicies::default(),
+            0,
         )
         .await?;
         if let Ok(()) = perpetual_db.objects.flush() {
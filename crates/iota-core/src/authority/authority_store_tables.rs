@@ -140,6 +140,11 @@ pub struct AuthorityPerpetualTables {
     /// per-epoch, and all previous epochs other than the current epoch may
     /// be pruned safely.
     pub(crate) object_per_epoch_marker_table: DBMap<(EpochId, ObjectKey), MarkerValue>,
+
+    /// Object versions kept by an object retention policy of the objects
+    /// pruner, keyed by the epoch from which on they may be pruned. Versions
+    /// retained indefinitely are not recorded here.
+    pub(crate) retained_object_versions: DBMap<(EpochId, ObjectKey), ()>,
 }
 
 /// The total IOTA supply used during conservation checks.
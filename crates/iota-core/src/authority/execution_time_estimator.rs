@@ -0,0 +1,540 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::Duration,
+};
+
+use iota_types::{
+    base_types::AuthorityName,
+    committee::{Committee, CommitteeTrait},
+    messages_consensus::{
+        ExecutionTimeObservation, ExecutionTimeObservationKey,
+        MAX_EXECUTION_TIME_OBSERVATIONS_PER_MESSAGE,
+    },
+    transaction::{TransactionData, TransactionDataAPI},
+};
+use tracing::debug;
+
+// The execution time estimate, in microseconds, used for Move calls that have
+// no committee-agreed estimate yet.
+pub const DEFAULT_MOVE_CALL_EXECUTION_TIME_ESTIMATE_US: u64 = 1_000;
+
+// Weight of a new local measurement in the exponential moving average of the
+// execution time of an entry function.
+const LOCAL_OBSERVATION_SMOOTHING_FACTOR: f64 = 0.1;
+
+// A local estimate is shared again through consensus only once it differs from
+// the last shared estimate by more than this ratio.
+const LOCAL_OBSERVATION_CHANGE_THRESHOLD: f64 = 0.2;
+
+// The maximum number of distinct entry functions tracked per authority, both
+// for local observations and for the observations received through consensus.
+// Estimates for further entry functions are ignored.
+pub const MAX_EXECUTION_TIME_OBSERVATION_KEYS_PER_AUTHORITY: usize = 1024;
+
+// Returns the keys of all Move calls of a transaction, one per call.
+fn move_call_keys(tx_data: &TransactionData) -> Vec<ExecutionTimeObservationKey> {
+    tx_data
+        .move_calls()
+        .into_iter()
+        .map(|(package, module, function)| ExecutionTimeObservationKey {
+            package: *package,
+            module: module.to_string(),
+            function: function.to_string(),
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+struct LocalObservation {
+    moving_average: Duration,
+    last_shared: Option<Duration>,
+}
+
+impl LocalObservation {
+    fn should_share(&self) -> bool {
+        let Some(last_shared) = self.last_shared else {
+            return true;
+        };
+        let last_shared = last_shared.as_secs_f64();
+        let diff = (self.moving_average.as_secs_f64() - last_shared).abs();
+        diff > last_shared * LOCAL_OBSERVATION_CHANGE_THRESHOLD
+    }
+}
+
+// ExecutionTimeObserver keeps the smoothed execution times of Move entry
+// functions measured locally by this authority, and produces the
+// `ExecutionTimeObservation`s to be shared through consensus.
+//
+// The execution time of a transaction is split evenly between its Move calls.
+// Local observations are not persisted and start from scratch at every epoch.
+#[derive(Debug)]
+pub struct ExecutionTimeObserver {
+    authority: AuthorityName,
+    local_observations: HashMap<ExecutionTimeObservationKey, LocalObservation>,
+}
+
+impl ExecutionTimeObserver {
+    pub fn new(authority: AuthorityName) -> Self {
+        Self {
+            authority,
+            local_observations: HashMap::new(),
+        }
+    }
+
+    // Records the measured execution time of a transaction.
+    pub fn record_local_observation(&mut self, tx_data: &TransactionData, duration: Duration) {
+        let keys = move_call_keys(tx_data);
+        if keys.is_empty() {
+            return;
+        }
+        let per_call_duration = duration / keys.len() as u32;
+        for key in keys {
+            if self.local_observations.len() >= MAX_EXECUTION_TIME_OBSERVATION_KEYS_PER_AUTHORITY
+                && !self.local_observations.contains_key(&key)
+            {
+                continue;
+            }
+            self.local_observations
+                .entry(key)
+                .and_modify(|observation| {
+                    observation.moving_average = observation
+                        .moving_average
+                        .mul_f64(1.0 - LOCAL_OBSERVATION_SMOOTHING_FACTOR)
+                        + per_call_duration.mul_f64(LOCAL_OBSERVATION_SMOOTHING_FACTOR);
+                })
+                .or_insert(LocalObservation {
+                    moving_average: per_call_duration,
+                    last_shared: None,
+                });
+        }
+    }
+
+    // Returns an observation containing the local estimates that changed
+    // significantly since they were last shared, or None if there are none.
+    // The returned estimates are considered shared.
+    pub fn take_pending_observation(&mut self) -> Option<ExecutionTimeObservation> {
+        let mut pending: Vec<_> = self
+            .local_observations
+            .iter_mut()
+            .filter(|(_, observation)| observation.should_share())
+            .collect();
+        if pending.is_empty() {
+            return None;
+        }
+        // Share the most costly estimates first if not all of them fit in one
+        // message; the remaining ones are picked up by the next call.
+        pending.sort_by(|(_, a), (_, b)| b.moving_average.cmp(&a.moving_average));
+        pending.truncate(MAX_EXECUTION_TIME_OBSERVATIONS_PER_MESSAGE);
+
+        let estimates = pending
+            .into_iter()
+            .map(|(key, observation)| {
+                observation.last_shared = Some(observation.moving_average);
+                (key.clone(), observation.moving_average)
+            })
+            .collect();
+        Some(ExecutionTimeObservation::new(self.authority, estimates))
+    }
+}
+
+// The latest estimate of an authority for an entry function as persisted,
+// keyed by authority and entry function, along with the generation of the
+// observation it was received in.
+pub type PersistedExecutionTimeEstimate = (
+    (AuthorityName, ExecutionTimeObservationKey),
+    (u64, Duration),
+);
+
+// ExecutionTimeEstimator combines the observations shared through consensus by
+// all authorities into the committee-agreed execution time estimates.
+//
+// The estimate of an entry function is the stake-weighted median of the latest
+// estimates of all authorities that observed it. An estimate is only produced
+// once authorities with at least validity threshold stake have observed the
+// function, so that a minority of authorities cannot determine it.
+//
+// Since observations are processed in consensus order, all authorities arrive
+// at the same estimates. Only the latest estimate of each authority for each
+// entry function is kept, for at most
+// `MAX_EXECUTION_TIME_OBSERVATION_KEYS_PER_AUTHORITY` entry functions per
+// authority.
+#[derive(Debug)]
+pub struct ExecutionTimeEstimator {
+    committee: Arc<Committee>,
+    generations: HashMap<AuthorityName, u64>,
+    tracked_keys: HashMap<AuthorityName, usize>,
+    observations: HashMap<ExecutionTimeObservationKey, BTreeMap<AuthorityName, Duration>>,
+    estimates: Arc<ExecutionTimeEstimates>,
+}
+
+impl ExecutionTimeEstimator {
+    pub fn new(committee: Arc<Committee>) -> Self {
+        Self {
+            committee,
+            generations: HashMap::new(),
+            tracked_keys: HashMap::new(),
+            observations: HashMap::new(),
+            estimates: Default::default(),
+        }
+    }
+
+    // Restores the estimator from the estimates previously accepted by
+    // `process_observation`.
+    pub fn new_from_persisted(
+        committee: Arc<Committee>,
+        persisted: impl IntoIterator<Item = PersistedExecutionTimeEstimate>,
+    ) -> Self {
+        let mut estimator = Self::new(committee);
+        for ((authority, key), (generation, duration)) in persisted {
+            let last_generation = estimator.generations.entry(authority).or_default();
+            *last_generation = (*last_generation).max(generation);
+            *estimator.tracked_keys.entry(authority).or_default() += 1;
+            estimator
+                .observations
+                .entry(key)
+                .or_default()
+                .insert(authority, duration);
+        }
+        let estimates = estimator
+            .observations
+            .iter()
+            .filter_map(|(key, observations)| {
+                stake_weighted_median(&estimator.committee, observations)
+                    .map(|estimate| (key.clone(), estimate))
+            })
+            .collect();
+        estimator.estimates = Arc::new(ExecutionTimeEstimates(estimates));
+        estimator
+    }
+
+    // Processes an observation received through consensus. Returns the
+    // estimates that were accepted, to be persisted, or None if the
+    // observation was ignored because a newer one from the same authority was
+    // already processed. Estimates for entry functions beyond the limit of
+    // tracked entry functions of the authority are dropped.
+    pub fn process_observation(
+        &mut self,
+        observation: &ExecutionTimeObservation,
+    ) -> Option<Vec<(ExecutionTimeObservationKey, Duration)>> {
+        let authority = observation.authority;
+        if self
+            .generations
+            .get(&authority)
+            .is_some_and(|generation| *generation >= observation.generation)
+        {
+            debug!(
+                "ignoring stale execution time observation {:?}",
+                observation
+            );
+            return None;
+        }
+        self.generations.insert(authority, observation.generation);
+
+        let mut accepted = Vec::with_capacity(observation.estimates.len());
+        let mut estimates = (*self.estimates).clone();
+        for (key, duration) in &observation.estimates {
+            let observations = self.observations.entry(key.clone()).or_default();
+            if !observations.contains_key(&authority) {
+                let tracked_keys = self.tracked_keys.entry(authority).or_default();
+                if *tracked_keys >= MAX_EXECUTION_TIME_OBSERVATION_KEYS_PER_AUTHORITY {
+                    debug!(
+                        "ignoring execution time estimate of {:?} from {:?}: too many entry functions tracked",
+                        key,
+                        authority.concise()
+                    );
+                    if observations.is_empty() {
+                        self.observations.remove(key);
+                    }
+                    continue;
+                }
+                *tracked_keys += 1;
+            }
+            observations.insert(authority, *duration);
+            accepted.push((key.clone(), *duration));
+            match stake_weighted_median(&self.committee, observations) {
+                Some(estimate) => {
+                    estimates.0.insert(key.clone(), estimate);
+                }
+                None => {
+                    estimates.0.remove(key);
+                }
+            }
+        }
+        self.estimates = Arc::new(estimates);
+        Some(accepted)
+    }
+
+    // Returns a snapshot of the current committee-agreed estimates.
+    pub fn estimates(&self) -> Arc<ExecutionTimeEstimates> {
+        self.estimates.clone()
+    }
+}
+
+// Returns the stake-weighted median of the given observations in microseconds,
+// or None if the observing authorities have less than validity threshold stake.
+fn stake_weighted_median(
+    committee: &Committee,
+    observations: &BTreeMap<AuthorityName, Duration>,
+) -> Option<u64> {
+    let mut weighted: Vec<_> = observations
+        .iter()
+        .map(|(authority, duration)| (*duration, committee.weight(authority)))
+        .filter(|(_, stake)| *stake > 0)
+        .collect();
+    let total_stake: u64 = weighted.iter().map(|(_, stake)| stake).sum();
+    if total_stake < committee.validity_threshold() {
+        return None;
+    }
+    weighted.sort();
+    let mut accumulated_stake = 0;
+    for (duration, stake) in weighted {
+        accumulated_stake += stake;
+        if accumulated_stake * 2 >= total_stake {
+            return Some(duration.as_micros().try_into().unwrap_or(u64::MAX));
+        }
+    }
+    unreachable!("the accumulated stake must reach the total stake");
+}
+
+// Committee-agreed execution time estimates of Move entry functions, in
+// microseconds.
+#[derive(Default, Clone, PartialEq, Eq, Debug)]
+pub struct ExecutionTimeEstimates(HashMap<ExecutionTimeObservationKey, u64>);
+
+impl ExecutionTimeEstimates {
+    pub fn new_for_test(estimates: HashMap<ExecutionTimeObservationKey, u64>) -> Self {
+        Self(estimates)
+    }
+
+    pub fn get(&self, key: &ExecutionTimeObservationKey) -> Option<u64> {
+        self.0.get(key).copied()
+    }
+
+    // Returns the estimated execution time of a transaction as the sum of the
+    // estimates of its Move calls. Transactions without Move calls are
+    // estimated as a single call.
+    pub fn get_tx_cost(&self, tx_data: &TransactionData) -> u64 {
+        let keys = move_call_keys(tx_data);
+        if keys.is_empty() {
+            return DEFAULT_MOVE_CALL_EXECUTION_TIME_ESTIMATE_US;
+        }
+        keys.iter()
+            .map(|key| {
+                self.get(key)
+                    .unwrap_or(DEFAULT_MOVE_CALL_EXECUTION_TIME_ESTIMATE_US)
+            })
+            .fold(0u64, u64::saturating_add)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iota_types::{
+        Identifier,
+        base_types::{IotaAddress, ObjectID, random_object_ref},
+        committee::Committee,
+    };
+
+    use super::*;
+
+    fn key(function: &str) -> ExecutionTimeObservationKey {
+        ExecutionTimeObservationKey {
+            package: ObjectID::ZERO,
+            module: "module".to_string(),
+            function: function.to_string(),
+        }
+    }
+
+    fn observation(
+        authority: AuthorityName,
+        generation: u64,
+        estimates: &[(&str, u64)],
+    ) -> ExecutionTimeObservation {
+        ExecutionTimeObservation {
+            authority,
+            generation,
+            estimates: estimates
+                .iter()
+                .map(|(function, micros)| (key(function), Duration::from_micros(*micros)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_estimate_requires_validity_threshold() {
+        // 4 authorities with equal stake, validity threshold is 2 authorities.
+        let (committee, _) = Committee::new_simple_test_committee_of_size(4);
+        let authorities: Vec<_> = committee.names().copied().collect();
+        let mut estimator = ExecutionTimeEstimator::new(Arc::new(committee));
+
+        assert!(
+            estimator
+                .process_observation(&observation(authorities[0], 1, &[("f", 100)]))
+                .is_some()
+        );
+        assert_eq!(estimator.estimates().get(&key("f")), None);
+
+        assert!(
+            estimator
+                .process_observation(&observation(authorities[1], 1, &[("f", 300)]))
+                .is_some()
+        );
+        assert_eq!(estimator.estimates().get(&key("f")), Some(100));
+    }
+
+    #[test]
+    fn test_estimate_is_stake_weighted_median() {
+        let (committee, _) = Committee::new_simple_test_committee_of_size(4);
+        let authorities: Vec<_> = committee.names().copied().collect();
+        let mut estimator = ExecutionTimeEstimator::new(Arc::new(committee));
+
+        for (authority, micros) in authorities.iter().zip([100, 5_000_000, 200, 300]) {
+            estimator.process_observation(&observation(*authority, 1, &[("f", micros)]));
+        }
+        // A single outlier does not move the estimate.
+        assert_eq!(estimator.estimates().get(&key("f")), Some(200));
+
+        // Newer observations replace older ones from the same authority.
+        estimator.process_observation(&observation(authorities[0], 2, &[("f", 400)]));
+        assert_eq!(estimator.estimates().get(&key("f")), Some(300));
+
+        // Stale observations are ignored.
+        assert!(
+            estimator
+                .process_observation(&observation(authorities[0], 2, &[("f", 1)]))
+                .is_none()
+        );
+        assert_eq!(estimator.estimates().get(&key("f")), Some(300));
+    }
+
+    #[test]
+    fn test_tracked_keys_per_authority_are_capped() {
+        let (committee, _) = Committee::new_simple_test_committee_of_size(4);
+        let authorities: Vec<_> = committee.names().copied().collect();
+        let mut estimator = ExecutionTimeEstimator::new(Arc::new(committee));
+
+        let functions: Vec<_> = (0..=MAX_EXECUTION_TIME_OBSERVATION_KEYS_PER_AUTHORITY)
+            .map(|i| format!("f{i}"))
+            .collect();
+        let (tracked, untracked) =
+            functions.split_at(MAX_EXECUTION_TIME_OBSERVATION_KEYS_PER_AUTHORITY);
+        for (generation, chunk) in tracked
+            .chunks(MAX_EXECUTION_TIME_OBSERVATIONS_PER_MESSAGE)
+            .enumerate()
+        {
+            let estimates: Vec<_> = chunk.iter().map(|f| (f.as_str(), 100)).collect();
+            let accepted = estimator
+                .process_observation(&observation(authorities[0], generation as u64, &estimates))
+                .unwrap();
+            assert_eq!(accepted.len(), chunk.len());
+        }
+
+        // A new entry function is ignored once the limit is reached, while the
+        // tracked ones can still be updated.
+        let generation = functions.len() as u64;
+        let accepted = estimator
+            .process_observation(&observation(authorities[0], generation, &[
+                (untracked[0].as_str(), 100),
+                (tracked[0].as_str(), 200),
+            ]))
+            .unwrap();
+        assert_eq!(accepted, vec![(
+            key(&tracked[0]),
+            Duration::from_micros(200)
+        )]);
+
+        // Other authorities have their own limit.
+        let accepted = estimator
+            .process_observation(&observation(authorities[1], 1, &[(
+                untracked[0].as_str(),
+                100,
+            )]))
+            .unwrap();
+        assert_eq!(accepted.len(), 1);
+        assert_eq!(estimator.estimates().get(&key(&untracked[0])), None);
+    }
+
+    #[test]
+    fn test_restore_from_persisted() {
+        let (committee, _) = Committee::new_simple_test_committee_of_size(4);
+        let committee = Arc::new(committee);
+        let authorities: Vec<_> = committee.names().copied().collect();
+        let mut estimator = ExecutionTimeEstimator::new(committee.clone());
+
+        let mut persisted = BTreeMap::new();
+        for (authority, generation, estimates) in [
+            (authorities[0], 1, vec![("f", 100), ("g", 1_000)]),
+            (authorities[1], 1, vec![("f", 300)]),
+            (authorities[0], 2, vec![("f", 200)]),
+            (authorities[2], 1, vec![("g", 2_000)]),
+        ] {
+            let accepted = estimator
+                .process_observation(&observation(authority, generation, &estimates))
+                .unwrap();
+            // Only the latest estimate per authority and entry function is
+            // persisted.
+            for (key, duration) in accepted {
+                persisted.insert((authority, key), (generation, duration));
+            }
+        }
+        assert_eq!(persisted.len(), 4);
+
+        let restored = ExecutionTimeEstimator::new_from_persisted(committee, persisted);
+        assert_eq!(restored.estimates(), estimator.estimates());
+        assert_eq!(restored.estimates().get(&key("f")), Some(200));
+        assert_eq!(restored.estimates().get(&key("g")), Some(1_000));
+
+        // Generations are restored, so stale observations are still ignored.
+        let mut restored = restored;
+        assert!(
+            restored
+                .process_observation(&observation(authorities[0], 2, &[("f", 1)]))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_observer_shares_significant_changes_only() {
+        let (committee, _) = Committee::new_simple_test_committee_of_size(1);
+        let authority = *committee.names().next().unwrap();
+        let mut observer = ExecutionTimeObserver::new(authority);
+        let tx_data = TransactionData::new_move_call(
+            IotaAddress::random_for_testing_only(),
+            ObjectID::ZERO,
+            Identifier::new("module").unwrap(),
+            Identifier::new("f").unwrap(),
+            vec![],
+            random_object_ref(),
+            vec![],
+            1_000_000,
+            1_000,
+        )
+        .unwrap();
+
+        assert!(observer.take_pending_observation().is_none());
+
+        observer.record_local_observation(&tx_data, Duration::from_micros(1_000));
+        let shared = observer.take_pending_observation().unwrap();
+        assert_eq!(shared.estimates, vec![(
+            key("f"),
+            Duration::from_micros(1_000)
+        )]);
+        assert!(observer.take_pending_observation().is_none());
+
+        // A small change in the moving average is not shared.
+        observer.record_local_observation(&tx_data, Duration::from_micros(1_500));
+        assert!(observer.take_pending_observation().is_none());
+
+        // A large change is.
+        for _ in 0..10 {
+            observer.record_local_observation(&tx_data, Duration::from_micros(5_000));
+        }
+        let shared = observer.take_pending_observation().unwrap();
+        assert_eq!(shared.estimates.len(), 1);
+        assert!(shared.estimates[0].1 > Duration::from_micros(1_200));
+    }
+}
@@ -2,7 +2,7 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use iota_protocol_config::PerObjectCongestionControlMode;
 use iota_types::{
@@ -11,7 +11,9 @@ use iota_types::{
     transaction::SharedInputObject,
 };
 
-use crate::authority::transaction_deferral::DeferralKey;
+use crate::authority::{
+    execution_time_estimator::ExecutionTimeEstimates, transaction_deferral::DeferralKey,
+};
 
 // SharedObjectCongestionTracker stores the accumulated cost of executing
 // transactions on an object, for all transactions in a consensus commit.
@@ -24,12 +26,13 @@ use crate::authority::transaction_deferral::DeferralKey;
 // transaction execution latency on each objects.
 //
 // The mode field determines how the cost is calculated. The cost can be
-// calculated based on the total gas budget, or total number of transaction
-// count.
+// calculated based on the total gas budget, total number of transaction
+// count, or the committee-agreed execution time estimates.
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub struct SharedObjectCongestionTracker {
     object_execution_cost: HashMap<ObjectID, u64>,
     mode: PerObjectCongestionControlMode,
+    execution_time_estimates: Arc<ExecutionTimeEstimates>,
 }
 
 impl SharedObjectCongestionTracker {
@@ -37,9 +40,20 @@ impl SharedObjectCongestionTracker {
         Self {
             object_execution_cost: HashMap::new(),
             mode,
+            execution_time_estimates: Default::default(),
         }
     }
 
+    // Sets the execution time estimates used as transaction cost in
+    // `PerObjectCongestionControlMode::ExecutionTimeEstimate` mode.
+    pub fn with_execution_time_estimates(
+        mut self,
+        execution_time_estimates: Arc<ExecutionTimeEstimates>,
+    ) -> Self {
+        self.execution_time_estimates = execution_time_estimates;
+        self
+    }
+
     pub fn new_with_initial_value_for_test(
         init_values: &[(ObjectID, u64)],
         mode: PerObjectCongestionControlMode,
@@ -51,6 +65,7 @@ impl SharedObjectCongestionTracker {
         Self {
             object_execution_cost,
             mode,
+            execution_time_estimates: Default::default(),
         }
     }
 
@@ -72,6 +87,10 @@ impl SharedObjectCongestionTracker {
             PerObjectCongestionControlMode::None => None,
             PerObjectCongestionControlMode::TotalGasBudget => Some(cert.gas_budget()),
             PerObjectCongestionControlMode::TotalTxCount => Some(1),
+            PerObjectCongestionControlMode::ExecutionTimeEstimate => Some(
+                self.execution_time_estimates
+                    .get_tx_cost(cert.transaction_data()),
+            ),
         }
     }
 
@@ -174,11 +193,13 @@ mod object_cost_tests {
     use iota_types::{
         base_types::{SequenceNumber, random_object_ref},
         crypto::{AccountKeyPair, get_key_pair},
-        transaction::{CallArg, ObjectArg, VerifiedTransaction},
+        messages_consensus::ExecutionTimeObservationKey,
+        transaction::{CallArg, ObjectArg, TransactionDataAPI, VerifiedTransaction},
     };
     use rstest::rstest;
 
     use super::*;
+    use crate::authority::execution_time_estimator::DEFAULT_MOVE_CALL_EXECUTION_TIME_ESTIMATE_US;
 
     fn construct_shared_input_objects(objects: &[(ObjectID, bool)]) -> Vec<SharedInputObject> {
         objects
@@ -276,7 +297,8 @@ mod object_cost_tests {
     fn test_should_defer_return_correct_congested_objects(
         #[values(
             PerObjectCongestionControlMode::TotalGasBudget,
-            PerObjectCongestionControlMode::TotalTxCount
+            PerObjectCongestionControlMode::TotalTxCount,
+            PerObjectCongestionControlMode::ExecutionTimeEstimate
         )]
         mode: PerObjectCongestionControlMode,
     ) {
@@ -293,6 +315,9 @@ mod object_cost_tests {
             PerObjectCongestionControlMode::None => unreachable!(),
             PerObjectCongestionControlMode::TotalGasBudget => tx_gas_budget + 1,
             PerObjectCongestionControlMode::TotalTxCount => 2,
+            PerObjectCongestionControlMode::ExecutionTimeEstimate => {
+                DEFAULT_MOVE_CALL_EXECUTION_TIME_ESTIMATE_US + 1
+            }
         };
 
         let shared_object_congestion_tracker = match mode {
@@ -317,6 +342,16 @@ mod object_cost_tests {
                     mode,
                 )
             }
+            PerObjectCongestionControlMode::ExecutionTimeEstimate => {
+                // Construct object execution cost as following
+                //                1     10
+                // object 0:            |
+                // object 1:      |
+                SharedObjectCongestionTracker::new_with_initial_value_for_test(
+                    &[(shared_obj_0, 10), (shared_obj_1, 1)],
+                    mode,
+                )
+            }
         };
 
         // Read/write to object 0 should be deferred.
@@ -381,7 +416,8 @@ mod object_cost_tests {
     fn test_should_defer_return_correct_deferral_key(
         #[values(
             PerObjectCongestionControlMode::TotalGasBudget,
-            PerObjectCongestionControlMode::TotalTxCount
+            PerObjectCongestionControlMode::TotalTxCount,
+            PerObjectCongestionControlMode::ExecutionTimeEstimate
         )]
         mode: PerObjectCongestionControlMode,
     ) {
@@ -476,7 +512,8 @@ mod object_cost_tests {
     fn test_bump_object_execution_cost(
         #[values(
             PerObjectCongestionControlMode::TotalGasBudget,
-            PerObjectCongestionControlMode::TotalTxCount
+            PerObjectCongestionControlMode::TotalTxCount,
+            PerObjectCongestionControlMode::ExecutionTimeEstimate
         )]
         mode: PerObjectCongestionControlMode,
     ) {
@@ -511,6 +548,9 @@ mod object_cost_tests {
             PerObjectCongestionControlMode::None => unreachable!(),
             PerObjectCongestionControlMode::TotalGasBudget => 20,
             PerObjectCongestionControlMode::TotalTxCount => 11,
+            PerObjectCongestionControlMode::ExecutionTimeEstimate => {
+                10 + DEFAULT_MOVE_CALL_EXECUTION_TIME_ESTIMATE_US
+            }
         };
         assert_eq!(
             shared_object_congestion_tracker,
@@ -538,6 +578,9 @@ mod object_cost_tests {
             PerObjectCongestionControlMode::None => unreachable!(),
             PerObjectCongestionControlMode::TotalGasBudget => 30,
             PerObjectCongestionControlMode::TotalTxCount => 12,
+            PerObjectCongestionControlMode::ExecutionTimeEstimate => {
+                10 + 2 * DEFAULT_MOVE_CALL_EXECUTION_TIME_ESTIMATE_US
+            }
         };
         shared_object_congestion_tracker.bump_object_execution_cost(&cert);
         assert_eq!(
@@ -556,4 +599,32 @@ mod object_cost_tests {
             expected_object_cost
         );
     }
+
+    #[test]
+    fn test_execution_time_estimate_cost() {
+        let object_id = ObjectID::random();
+        let cert = build_transaction(&[(object_id, true)], 10);
+        let (package, module, function) = cert.transaction_data().move_calls()[0];
+        let key = ExecutionTimeObservationKey {
+            package: *package,
+            module: module.to_string(),
+            function: function.to_string(),
+        };
+
+        // Without an agreed estimate, the default estimate is used.
+        let tracker = SharedObjectCongestionTracker::new(
+            PerObjectCongestionControlMode::ExecutionTimeEstimate,
+        );
+        assert_eq!(
+            tracker.get_tx_cost(&cert),
+            Some(DEFAULT_MOVE_CALL_EXECUTION_TIME_ESTIMATE_US)
+        );
+
+        let mut tracker = tracker.with_execution_time_estimates(Arc::new(
+            ExecutionTimeEstimates::new_for_test(HashMap::from([(key, 42)])),
+        ));
+        assert_eq!(tracker.get_tx_cost(&cert), Some(42));
+        tracker.bump_object_execution_cost(&cert);
+        assert_eq!(tracker.max_cost(), 42);
+    }
 }
@@ -19,6 +19,8 @@ use iota_network::{
     tonic,
 };
 use iota_types::{
+    base_types::ObjectID,
+    digests::TransactionDigest,
     effects::TransactionEffectsAPI,
     error::*,
     fp_ensure,
@@ -56,7 +58,9 @@ use crate::{
     },
     mysticeti_adapter::LazyMysticetiClient,
     traffic_controller::{
-        TrafficController, metrics::TrafficControllerMetrics, policies::TrafficTally,
+        TrafficController,
+        metrics::TrafficControllerMetrics,
+        policies::{TrafficTally, TransactionTally},
     },
 };
 
@@ -356,6 +360,11 @@ impl ValidatorService {
         &self.state
     }
 
+    /// Returns the traffic controller, if traffic control is configured.
+    pub fn traffic_controller(&self) -> Option<Arc<TrafficController>> {
+        self.traffic_controller.clone()
+    }
+
     /// Executes a `CertifiedTransaction` for testing.
     pub async fn execute_certificate_for_testing(
         &self,
@@ -383,7 +392,7 @@ impl ValidatorService {
             state,
             consensus_adapter,
             metrics,
-            traffic_controller: _,
+            traffic_controller,
             client_id_source: _,
         } = self.clone();
         let transaction = request.into_inner();
@@ -427,6 +436,22 @@ impl ValidatorService {
         })?;
         drop(tx_verif_metrics_guard);
 
+        // Only now that the signature is verified can the sender and gas coins be
+        // held responsible for the transaction
+        let sender = transaction.data().transaction_data().sender();
+        let gas_coins = transaction
+            .data()
+            .transaction_data()
+            .gas()
+            .iter()
+            .map(|(id, _, _)| *id)
+            .collect::<Vec<_>>();
+        if let Some(traffic_controller) = &traffic_controller {
+            if !traffic_controller.check_transaction(&sender, &gas_coins) {
+                return Err(IotaError::TooManyRequests.into());
+            }
+        }
+
         let tx_digest = transaction.digest();
 
         // Enable Trace Propagation across spans/processes using tx_digest
@@ -440,8 +465,22 @@ impl ValidatorService {
                 if let IotaError::ValidatorHaltedAtEpochEnd = e {
                     metrics.num_rejected_tx_in_epoch_boundary.inc();
                 }
+                if let Some(traffic_controller) = &traffic_controller {
+                    if let Some(tally) = transaction_error_tally(
+                        &epoch_store,
+                        *tx_digest,
+                        transaction.data().transaction_data(),
+                        e,
+                    ) {
+                        traffic_controller.tally_transaction(tally);
+                    }
+                }
             })?;
 
+        if let Some(traffic_controller) = &traffic_controller {
+            traffic_controller.record_signed_transaction(&sender, &gas_coins);
+        }
+
         if let Some(error) = validator_pushback_error {
             // TODO: right now, we still sign the txn, but just don't return it. We can also
             // skip signing to save more CPU.
@@ -625,6 +664,17 @@ impl ValidatorService {
                     .state
                     .execute_certificate(&certificate, epoch_store)
                     .await?;
+                if effects.status().is_err() {
+                    if let Some(traffic_controller) = &self.traffic_controller {
+                        let data = certificate.data().transaction_data();
+                        traffic_controller.tally_transaction(TransactionTally::new(
+                            *certificate.digest(),
+                            data.sender(),
+                            tallied_gas_coins(data),
+                            Weight::one(),
+                        ));
+                    }
+                }
                 let events = if include_events {
                     if let Some(digest) = effects.events_digest() {
                         Some(self.state.get_transaction_events(digest)?)
@@ -1059,6 +1109,49 @@ fn normalize(err: IotaError) -> Weight {
     }
 }
 
+/// Returns the gas coins of a transaction that are held responsible for its
+/// failure. The gas coins of sponsored transactions belong to the sponsor,
+/// who is not to blame for the failures of the sender.
+fn tallied_gas_coins(data: &TransactionData) -> Vec<ObjectID> {
+    if data.is_sponsored_tx() {
+        return vec![];
+    }
+    data.gas().iter().map(|(id, _, _)| *id).collect()
+}
+
+/// Returns the tally of the transaction `digest` that failed to be signed
+/// with `err`, if its sender is to blame for it. Lock conflicts are only
+/// tallied if the transaction holding the lock is of the same sender, as
+/// otherwise the conflict may have been caused by someone else, e.g. the
+/// sponsor of the transaction.
+fn transaction_error_tally(
+    epoch_store: &AuthorityPerEpochStore,
+    digest: TransactionDigest,
+    data: &TransactionData,
+    err: &IotaError,
+) -> Option<TransactionTally> {
+    let sender = data.sender();
+    match err {
+        IotaError::ObjectLockConflict {
+            pending_transaction,
+            ..
+        } => {
+            let pending_sender = epoch_store
+                .get_signed_transaction(pending_transaction)
+                .ok()
+                .flatten()
+                .map(|pending| pending.data().transaction_data().sender());
+            (pending_sender == Some(sender)).then(|| {
+                TransactionTally::new(digest, sender, tallied_gas_coins(data), Weight::one())
+            })
+        }
+        IotaError::UserInput { .. } => {
+            Some(TransactionTally::new(digest, sender, vec![], Weight::one()))
+        }
+        _ => None,
+    }
+}
+
 /// Implements generic pre- and post-processing. Since this is on the critical
 /// path, any heavy lifting should be done in a separate non-blocking task
 /// unless it is necessary to override the return value.
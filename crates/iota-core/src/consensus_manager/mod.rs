@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::{
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -52,6 +52,11 @@ pub trait ConsensusManagerTrait {
     async fn shutdown(&self);
 
     async fn is_running(&self) -> bool;
+
+    /// Creates a consistent checkpoint of the consensus store of the running
+    /// epoch at `path`. Returns the epoch of the store, or `None` if consensus
+    /// is not running.
+    async fn checkpoint_db(&self, path: &Path) -> IotaResult<Option<EpochId>>;
 }
 
 // Wraps the underlying consensus protocol managers to make calling
@@ -166,6 +171,10 @@ impl ConsensusManagerTrait for ConsensusManager {
         let active = self.active.lock();
         *active
     }
+
+    async fn checkpoint_db(&self, path: &Path) -> IotaResult<Option<EpochId>> {
+        self.mysticeti_manager.checkpoint_db(path).await
+    }
 }
 
 #[derive(Default)]
@@ -2,7 +2,11 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{path::PathBuf, sync::Arc, time::Duration};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 
 use arc_swap::ArcSwapOption;
 use async_trait::async_trait;
@@ -13,7 +17,7 @@ use iota_config::NodeConfig;
 use iota_metrics::{RegistryID, RegistryService, monitored_mpsc::unbounded_channel};
 use iota_protocol_config::ConsensusNetwork;
 use iota_types::{
-    committee::EpochId,
+    committee::EpochId, error::IotaResult,
     iota_system_state::epoch_start_iota_system_state::EpochStartSystemStateTrait,
 };
 use prometheus::Registry;
@@ -227,4 +231,19 @@ impl ConsensusManagerTrait for MysticetiManager {
     async fn is_running(&self) -> bool {
         Running::False != *self.running.lock().await
     }
+
+    async fn checkpoint_db(&self, path: &Path) -> IotaResult<Option<EpochId>> {
+        // Holding the running lock prevents consensus from being shut down, and
+        // the authority from being unwrapped, while the checkpoint is created.
+        let running = self.running.lock().await;
+        let Running::True(epoch, _) = *running else {
+            return Ok(None);
+        };
+        let Some(authority) = self.authority.load_full() else {
+            return Ok(None);
+        };
+
+        authority.0.checkpoint_store(path)?;
+        Ok(Some(epoch))
+    }
 }
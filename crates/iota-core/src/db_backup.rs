@@ -0,0 +1,626 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Consistent backups of the databases of a running node to a local path.
+//!
+//! A backup is a directory laid out like the database directory of a node:
+//! RocksDB checkpoints of the checkpoint store, the perpetual and per-epoch
+//! tables, the committee store and optionally the indexes, plus the consensus
+//! store of the running epoch for validators. A manifest lists every file of
+//! the backup with its size and hash, so that a backup can be verified before
+//! it is restored.
+
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+use fastcrypto::{
+    encoding::{Encoding, Hex},
+    hash::{HashFunction, Sha3_256},
+};
+use iota_config::node::DBBackupConfig;
+use iota_types::committee::EpochId;
+use prometheus::{IntGauge, Registry, register_int_gauge_with_registry};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, OwnedMutexGuard};
+use tracing::{info, warn};
+
+use crate::{
+    authority::{AuthorityState, authority_per_epoch_store::AuthorityPerEpochStore},
+    consensus_manager::{ConsensusManager, ConsensusManagerTrait},
+};
+
+/// Name of the manifest of a backup. A backup directory without a manifest
+/// is incomplete.
+pub const DB_BACKUP_MANIFEST: &str = "MANIFEST.json";
+
+const DB_BACKUP_PREFIX: &str = "backup_";
+const DB_BACKUP_STAGING_SUFFIX: &str = ".staging";
+
+/// Directory of a backup that holds the consensus store, by epoch.
+const CONSENSUS_DIR: &str = "consensus";
+
+/// Directories of a backup that are restored into the database directory of
+/// the node.
+const NODE_DB_DIRS: &[&str] = &["checkpoints", "store", "epochs", "indexes"];
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DBBackupManifest {
+    /// The epoch the node was in when the backup was taken.
+    pub epoch: EpochId,
+    pub timestamp_ms: u64,
+    /// The epoch of the consensus store in the backup, if it has one.
+    pub consensus_epoch: Option<EpochId>,
+    pub files: Vec<DBBackupFile>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DBBackupFile {
+    /// Path of the file, relative to the backup directory.
+    pub path: PathBuf,
+    pub size: u64,
+    /// Hex encoded SHA3-256 digest of the file.
+    pub sha3_256: String,
+}
+
+pub struct DBBackupMetrics {
+    pub last_db_backup_epoch: IntGauge,
+    pub last_db_backup_duration_ms: IntGauge,
+    pub num_local_db_backups: IntGauge,
+}
+
+/// Takes backups of the databases of the node to a local directory, on demand
+/// or at a fixed interval, and keeps a configured number of the most recent
+/// ones.
+pub struct DBBackupHandler {
+    backup_path: PathBuf,
+    interval: Option<Duration>,
+    retention: usize,
+    include_indexes: bool,
+    /// Serializes backups, so that a scheduled and an on-demand backup never
+    /// run at once.
+    lock: Arc<Mutex<()>>,
+    metrics: DBBackupMetrics,
+}
+
+/// A backup whose databases have been checkpointed to its staging directory,
+/// except for the consensus store, which is owned by the consensus manager.
+pub struct PendingDBBackup {
+    staging_path: PathBuf,
+    epoch: EpochId,
+    timestamp_ms: u64,
+    consensus_epoch: Option<EpochId>,
+    started: Instant,
+    _guard: OwnedMutexGuard<()>,
+}
+
+impl DBBackupMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            last_db_backup_epoch: register_int_gauge_with_registry!(
+                "last_db_backup_epoch",
+                "Epoch the node was in when the last db backup was taken",
+                registry
+            )
+            .unwrap(),
+            last_db_backup_duration_ms: register_int_gauge_with_registry!(
+                "last_db_backup_duration_ms",
+                "Time it took to take the last db backup, including hashing its files",
+                registry
+            )
+            .unwrap(),
+            num_local_db_backups: register_int_gauge_with_registry!(
+                "num_local_db_backups",
+                "Number of complete db backups currently residing on local disk",
+                registry
+            )
+            .unwrap(),
+        }
+    }
+}
+
+impl DBBackupHandler {
+    pub fn new(config: &DBBackupConfig, registry: &Registry) -> Result<Arc<Self>> {
+        fs::create_dir_all(&config.backup_path).with_context(|| {
+            format!(
+                "Failed to create db backup directory {}",
+                config.backup_path.display()
+            )
+        })?;
+
+        Ok(Arc::new(Self {
+            backup_path: config.backup_path.clone(),
+            interval: config.interval_s.map(Duration::from_secs),
+            retention: config.retention.max(1),
+            include_indexes: config.include_indexes,
+            lock: Default::default(),
+            metrics: DBBackupMetrics::new(registry),
+        }))
+    }
+
+    /// The interval between scheduled backups, if backups are scheduled.
+    pub fn interval(&self) -> Option<Duration> {
+        self.interval
+    }
+
+    /// Checkpoints the checkpoint store, the perpetual tables, the committee
+    /// store, the indexes if configured and the tables of the current epoch to
+    /// the staging directory of a new backup.
+    ///
+    /// The databases are checkpointed one after the other, in the order in
+    /// which a running node persists data to them, so that databases
+    /// checkpointed later may only be ahead of ones checkpointed earlier. The
+    /// node recovers from this on startup as it does after a crash.
+    pub async fn begin(&self, state: Arc<AuthorityState>) -> Result<PendingDBBackup> {
+        let guard = self.lock.clone().lock_owned().await;
+        self.remove_staging_dirs()?;
+
+        let started = Instant::now();
+        let timestamp_ms = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64;
+        let epoch_store = state.load_epoch_store_one_call_per_task().clone();
+        let epoch = epoch_store.epoch();
+        let staging_path = self.backup_path.join(format!(
+            "{DB_BACKUP_PREFIX}{epoch}_{timestamp_ms}{DB_BACKUP_STAGING_SUFFIX}"
+        ));
+        info!(
+            "Starting db backup for epoch {epoch} in {}",
+            staging_path.display()
+        );
+
+        let include_indexes = self.include_indexes;
+        let path = staging_path.clone();
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            state.checkpoint_all_dbs(&path, &epoch_store, include_indexes)?;
+            epoch_store.checkpoint_db(&AuthorityPerEpochStore::path(epoch, &path.join("store")))?;
+            Ok(())
+        })
+        .await??;
+
+        Ok(PendingDBBackup {
+            staging_path,
+            epoch,
+            timestamp_ms,
+            consensus_epoch: None,
+            started,
+            _guard: guard,
+        })
+    }
+
+    /// Hashes the files of the backup, writes its manifest and moves it into
+    /// place, then deletes the oldest backups beyond the retention. Returns the
+    /// path of the backup.
+    ///
+    /// The backup is discarded if the node reconfigured before its consensus
+    /// store was checkpointed, as the consensus store would not belong to the
+    /// epoch tables of the backup.
+    pub async fn finish(&self, backup: PendingDBBackup) -> Result<PathBuf> {
+        let PendingDBBackup {
+            staging_path,
+            epoch,
+            timestamp_ms,
+            consensus_epoch,
+            started,
+            _guard,
+        } = backup;
+
+        if let Some(consensus_epoch) =
+            consensus_epoch.filter(|consensus_epoch| *consensus_epoch != epoch)
+        {
+            fs::remove_dir_all(&staging_path)?;
+            bail!(
+                "The node reconfigured during the db backup of epoch {epoch}, which would include the consensus store of epoch {consensus_epoch}"
+            );
+        }
+
+        let path = staging_path.clone();
+        let files = tokio::task::spawn_blocking(move || hash_files(&path)).await??;
+        let manifest = DBBackupManifest {
+            epoch,
+            timestamp_ms,
+            consensus_epoch,
+            files,
+        };
+        fs::write(
+            staging_path.join(DB_BACKUP_MANIFEST),
+            serde_json::to_vec_pretty(&manifest)?,
+        )?;
+
+        let backup_path = self
+            .backup_path
+            .join(format!("{DB_BACKUP_PREFIX}{epoch}_{timestamp_ms}"));
+        fs::rename(&staging_path, &backup_path)?;
+
+        let deleted = self.apply_retention()?;
+        if !deleted.is_empty() {
+            info!("Deleted db backups beyond retention: {deleted:?}");
+        }
+
+        let duration = started.elapsed();
+        self.metrics.last_db_backup_epoch.set(epoch as i64);
+        self.metrics
+            .last_db_backup_duration_ms
+            .set(duration.as_millis() as i64);
+        info!(
+            "Finished db backup for epoch {epoch} in {} after {duration:?}",
+            backup_path.display()
+        );
+        Ok(backup_path)
+    }
+
+    /// The complete backups in the backup directory, ordered from the oldest
+    /// to the most recent.
+    pub fn list_backups(&self) -> Result<Vec<PathBuf>> {
+        let mut backups = vec![];
+        for entry in fs::read_dir(&self.backup_path)? {
+            let path = entry?.path();
+            if let Some(timestamp_ms) = backup_timestamp_ms(&path) {
+                if path.join(DB_BACKUP_MANIFEST).exists() {
+                    backups.push((timestamp_ms, path));
+                }
+            }
+        }
+        backups.sort();
+        Ok(backups.into_iter().map(|(_, path)| path).collect())
+    }
+
+    fn apply_retention(&self) -> Result<Vec<PathBuf>> {
+        let backups = self.list_backups()?;
+        let excess = backups.len().saturating_sub(self.retention);
+        let deleted: Vec<_> = backups.into_iter().take(excess).collect();
+        for path in &deleted {
+            fs::remove_dir_all(path)?;
+        }
+
+        self.metrics
+            .num_local_db_backups
+            .set(self.list_backups()?.len() as i64);
+        Ok(deleted)
+    }
+
+    /// Removes the staging directories of backups that were interrupted.
+    fn remove_staging_dirs(&self) -> Result<()> {
+        for entry in fs::read_dir(&self.backup_path)? {
+            let path = entry?.path();
+            let name = path.file_name().and_then(|name| name.to_str());
+            if name.is_some_and(|name| {
+                name.starts_with(DB_BACKUP_PREFIX) && !name.ends_with(|c: char| c.is_ascii_digit())
+            }) {
+                warn!("Removing incomplete db backup {}", path.display());
+                fs::remove_dir_all(&path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PendingDBBackup {
+    /// Checkpoints the consensus store of the running epoch into the backup.
+    /// Consensus is checkpointed last, as its output is processed into the
+    /// tables of the epoch.
+    pub async fn checkpoint_consensus(
+        &mut self,
+        consensus_manager: &ConsensusManager,
+    ) -> Result<()> {
+        let path = self.staging_path.join(CONSENSUS_DIR);
+        fs::create_dir_all(&path)?;
+
+        // The consensus store is checkpointed to a temporary directory first, as
+        // its epoch is only known once it is checkpointed.
+        let tmp_path = path.join("tmp");
+        match consensus_manager.checkpoint_db(&tmp_path).await? {
+            Some(epoch) => {
+                fs::rename(&tmp_path, path.join(epoch.to_string()))?;
+                self.consensus_epoch = Some(epoch);
+            }
+            None => {
+                warn!("Consensus is not running, db backup does not include the consensus store");
+                fs::remove_dir(&path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads the manifest of the backup at `backup_path` and checks that the
+/// backup holds exactly the files it lists, with matching sizes and hashes.
+pub fn verify_backup(backup_path: &Path) -> Result<DBBackupManifest> {
+    let manifest: DBBackupManifest = serde_json::from_slice(
+        &fs::read(backup_path.join(DB_BACKUP_MANIFEST))
+            .with_context(|| format!("{} is not a complete db backup", backup_path.display()))?,
+    )?;
+
+    let files = hash_files(backup_path)?;
+    for expected in &manifest.files {
+        let Some(actual) = files.iter().find(|file| file.path == expected.path) else {
+            bail!("File {} of the backup is missing", expected.path.display());
+        };
+        if actual != expected {
+            bail!(
+                "File {} of the backup is corrupted: expected {} bytes with hash {}, found {} bytes with hash {}",
+                expected.path.display(),
+                expected.size,
+                expected.sha3_256,
+                actual.size,
+                actual.sha3_256
+            );
+        }
+    }
+
+    if let Some(extra) = files.iter().find(|file| {
+        !manifest
+            .files
+            .iter()
+            .any(|expected| expected.path == file.path)
+    }) {
+        bail!(
+            "File {} is not listed in the manifest of the backup",
+            extra.path.display()
+        );
+    }
+
+    Ok(manifest)
+}
+
+/// Verifies the backup at `backup_path` and installs it into the database
+/// directory of a node, and the consensus store into `consensus_db_path` for
+/// validators. The databases of the node must not be in use.
+///
+/// Existing databases are only replaced if `force` is set. All existing
+/// consensus stores are removed in that case, even if the backup has none, as
+/// they would be ahead of the restored epoch tables.
+pub fn restore_backup(
+    backup_path: &Path,
+    db_path: &Path,
+    consensus_db_path: Option<&Path>,
+    force: bool,
+) -> Result<DBBackupManifest> {
+    let manifest = verify_backup(backup_path)?;
+
+    if manifest.consensus_epoch.is_some() && consensus_db_path.is_none() {
+        bail!(
+            "The backup includes a consensus store, but the node has no consensus config to restore it to"
+        );
+    }
+
+    let mut targets: Vec<_> = NODE_DB_DIRS
+        .iter()
+        .filter(|dir| backup_path.join(dir).exists())
+        .map(|dir| db_path.join(dir))
+        .collect();
+    targets.extend(consensus_db_path.map(Path::to_path_buf));
+
+    for target in &targets {
+        if target.exists() && fs::read_dir(target)?.next().is_some() {
+            if !force {
+                bail!(
+                    "{} already exists, pass --force to replace it with the backup",
+                    target.display()
+                );
+            }
+            warn!("Removing {} to restore the backup", target.display());
+            fs::remove_dir_all(target)?;
+        }
+    }
+
+    for file in &manifest.files {
+        let target = match file.path.strip_prefix(CONSENSUS_DIR) {
+            Ok(path) => consensus_db_path
+                .ok_or_else(|| anyhow!("Unexpected consensus file {}", file.path.display()))?
+                .join(path),
+            Err(_) => db_path.join(&file.path),
+        };
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(backup_path.join(&file.path), &target)?;
+    }
+
+    info!(
+        "Restored db backup of epoch {} from {} to {}",
+        manifest.epoch,
+        backup_path.display(),
+        db_path.display()
+    );
+    Ok(manifest)
+}
+
+/// Lists and hashes all files under `root`, except for the manifest, ordered
+/// by path.
+fn hash_files(root: &Path) -> Result<Vec<DBBackupFile>> {
+    let mut files = vec![];
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if entry.file_type()?.is_dir() {
+                dirs.push(path);
+                continue;
+            }
+
+            let relative = path.strip_prefix(root)?.to_path_buf();
+            if relative == Path::new(DB_BACKUP_MANIFEST) {
+                continue;
+            }
+
+            let (size, digest) = hash_file(&path)?;
+            files.push(DBBackupFile {
+                path: relative,
+                size,
+                sha3_256: Hex::encode(digest),
+            });
+        }
+    }
+
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+fn hash_file(path: &Path) -> io::Result<(u64, [u8; 32])> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha3_256::default();
+    let mut buf = vec![0u8; 1 << 20];
+    let mut size = 0;
+    loop {
+        let read = file.read(&mut buf)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buf[..read]);
+        size += read as u64;
+    }
+    Ok((size, hasher.finalize().digest))
+}
+
+/// The timestamp of the backup at `path`, if it is the directory of a backup.
+fn backup_timestamp_ms(path: &Path) -> Option<u64> {
+    let name = path.file_name()?.to_str()?;
+    let (_epoch, timestamp_ms) = name.strip_prefix(DB_BACKUP_PREFIX)?.split_once('_')?;
+    timestamp_ms.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_backup(root: &Path) -> DBBackupManifest {
+        write_backup_with_consensus(root, true)
+    }
+
+    fn write_backup_with_consensus(root: &Path, with_consensus: bool) -> DBBackupManifest {
+        fs::create_dir_all(root.join("store/perpetual")).unwrap();
+        fs::create_dir_all(root.join("checkpoints")).unwrap();
+        fs::write(root.join("store/perpetual/000001.sst"), b"perpetual").unwrap();
+        fs::write(root.join("checkpoints/000002.sst"), b"checkpoints").unwrap();
+        if with_consensus {
+            fs::create_dir_all(root.join("consensus/3")).unwrap();
+            fs::write(root.join("consensus/3/000003.sst"), b"consensus").unwrap();
+        }
+
+        let manifest = DBBackupManifest {
+            epoch: 3,
+            timestamp_ms: 1000,
+            consensus_epoch: with_consensus.then_some(3),
+            files: hash_files(root).unwrap(),
+        };
+        fs::write(
+            root.join(DB_BACKUP_MANIFEST),
+            serde_json::to_vec(&manifest).unwrap(),
+        )
+        .unwrap();
+        manifest
+    }
+
+    #[test]
+    fn test_verify_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = write_backup(dir.path());
+        assert_eq!(manifest.files.len(), 3);
+        assert_eq!(verify_backup(dir.path()).unwrap(), manifest);
+
+        fs::write(dir.path().join("checkpoints/000002.sst"), b"corrupted").unwrap();
+        assert!(verify_backup(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_verify_backup_with_unlisted_file() {
+        let dir = tempfile::tempdir().unwrap();
+        write_backup(dir.path());
+        fs::write(dir.path().join("store/perpetual/000004.sst"), b"extra").unwrap();
+        assert!(verify_backup(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_restore_backup() {
+        let backup = tempfile::tempdir().unwrap();
+        let node = tempfile::tempdir().unwrap();
+        write_backup(backup.path());
+
+        let db_path = node.path().join("db");
+        let consensus_db_path = node.path().join("consensus_db");
+
+        // The consensus store cannot be dropped.
+        assert!(restore_backup(backup.path(), &db_path, None, false).is_err());
+
+        restore_backup(backup.path(), &db_path, Some(&consensus_db_path), false).unwrap();
+        assert_eq!(
+            fs::read(db_path.join("store/perpetual/000001.sst")).unwrap(),
+            b"perpetual"
+        );
+        assert_eq!(
+            fs::read(consensus_db_path.join("3/000003.sst")).unwrap(),
+            b"consensus"
+        );
+
+        // Existing databases are only replaced when forced.
+        assert!(restore_backup(backup.path(), &db_path, Some(&consensus_db_path), false).is_err());
+        fs::create_dir_all(consensus_db_path.join("4")).unwrap();
+        restore_backup(backup.path(), &db_path, Some(&consensus_db_path), true).unwrap();
+        assert!(!consensus_db_path.join("4").exists());
+    }
+
+    #[test]
+    fn test_restore_backup_without_consensus_store() {
+        let backup = tempfile::tempdir().unwrap();
+        let node = tempfile::tempdir().unwrap();
+        write_backup_with_consensus(backup.path(), false);
+
+        let db_path = node.path().join("db");
+        let consensus_db_path = node.path().join("consensus_db");
+        fs::create_dir_all(consensus_db_path.join("4")).unwrap();
+
+        // Existing consensus stores are ahead of the restored epoch tables.
+        assert!(restore_backup(backup.path(), &db_path, Some(&consensus_db_path), false).is_err());
+        restore_backup(backup.path(), &db_path, Some(&consensus_db_path), true).unwrap();
+        assert!(!consensus_db_path.exists());
+        assert_eq!(
+            fs::read(db_path.join("checkpoints/000002.sst")).unwrap(),
+            b"checkpoints"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_finish_discards_backup_with_consensus_store_of_other_epoch() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = DBBackupConfig {
+            backup_path: dir.path().to_path_buf(),
+            interval_s: None,
+            retention: 3,
+            include_indexes: false,
+        };
+        let handler = DBBackupHandler::new(&config, &Registry::new()).unwrap();
+
+        let staging_path = dir.path().join("backup_3_1000.staging");
+        write_backup_with_consensus(&staging_path, false);
+        let backup = PendingDBBackup {
+            staging_path: staging_path.clone(),
+            epoch: 3,
+            timestamp_ms: 1000,
+            consensus_epoch: Some(4),
+            started: Instant::now(),
+            _guard: handler.lock.clone().lock_owned().await,
+        };
+
+        assert!(handler.finish(backup).await.is_err());
+        assert!(!staging_path.exists());
+        assert!(handler.list_backups().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_backup_timestamp() {
+        assert_eq!(
+            backup_timestamp_ms(Path::new("/backups/backup_3_1000")),
+            Some(1000)
+        );
+        assert_eq!(
+            backup_timestamp_ms(Path::new("/backups/backup_3_1000.staging")),
+            None
+        );
+        assert_eq!(backup_timestamp_ms(Path::new("/backups/other")), None);
+    }
+}
@@ -16,10 +16,12 @@ pub mod consensus_handler;
 pub mod consensus_manager;
 pub(crate) mod consensus_types;
 pub mod consensus_validator;
+pub mod db_backup;
 pub mod db_checkpoint_handler;
 pub mod epoch;
 pub mod execution_cache;
 mod execution_driver;
+pub mod live_object_set_audit;
 pub mod metrics;
 #[cfg(any(test, feature = "test-utils"))]
 pub mod mock_consensus;
@@ -0,0 +1,631 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Online audit of the live object set of a node against a trusted reference,
+//! such as a peer or a formal snapshot.
+//!
+//! The object ID space is walked in ranges. For every range, the ECMH
+//! accumulator of the local live objects in the range is compared to the one of
+//! the reference. Ranges whose digests differ are bisected until they hold few
+//! enough objects to compare them object by object, so that the audit reports
+//! the individual objects that diverge, without reading the whole live object
+//! set of the reference.
+
+use std::{
+    fmt,
+    future::Future,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use fastcrypto::hash::MultisetHash;
+use iota_types::{
+    accumulator::Accumulator,
+    base_types::{ObjectID, ObjectRef},
+    digests::TransactionDigest,
+    messages_checkpoint::{CheckpointSequenceNumber, ECMHLiveObjectSetDigest},
+};
+use move_core_types::u256::U256;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::authority::authority_store_tables::{AuthorityPerpetualTables, LiveObject};
+
+/// An inclusive range of object IDs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObjectIdRange {
+    pub start: ObjectID,
+    pub end: ObjectID,
+}
+
+/// The number of live objects in a range of object IDs, and the digest of
+/// their accumulator.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RangeDigest {
+    pub count: u64,
+    pub digest: ECMHLiveObjectSetDigest,
+}
+
+/// The maximum number of object references served for a range at once.
+pub const MAX_OBJECT_REFS_PER_PAGE: usize = 10_000;
+
+/// A page of the references of the live objects in a range of object IDs.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObjectRefsPage {
+    pub object_refs: Vec<ObjectRef>,
+    /// The object ID to continue the range from, if it holds more objects than
+    /// fit in the page.
+    pub next_cursor: Option<ObjectID>,
+}
+
+/// A live object set that the local one is audited against.
+#[async_trait]
+pub trait LiveObjectSetReference: Send + Sync {
+    /// The number and accumulated digest of the live objects in `range`.
+    async fn range_digest(&self, range: ObjectIdRange) -> anyhow::Result<RangeDigest>;
+
+    /// The references of the live objects in `range`, ordered by object ID.
+    async fn range_object_refs(&self, range: ObjectIdRange) -> anyhow::Result<Vec<ObjectRef>>;
+}
+
+/// The live object set of the local store.
+pub struct LocalLiveObjectSet {
+    tables: Arc<AuthorityPerpetualTables>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LiveObjectSetAuditConfig {
+    /// Ranges whose digests differ are bisected until they hold at most this
+    /// many objects, locally and in the reference, before their objects are
+    /// compared.
+    pub max_objects_per_range: u64,
+    /// Pause between two ranges, to limit the load the audit puts on the node
+    /// and the reference.
+    pub throttle: Duration,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LiveObjectSetAuditStatus {
+    #[default]
+    NotStarted,
+    LoadingReference,
+    Running,
+    Completed,
+    Stopped,
+    Failed(String),
+}
+
+/// An object whose live version differs between the local store and the
+/// reference.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectDivergence {
+    /// The object is live in the reference, but not in the local store.
+    Missing { reference: ObjectRef },
+    /// The object is live in the local store, but not in the reference.
+    Unexpected { local: ObjectRef },
+    /// The object is live in both, but with different references.
+    Mismatch {
+        local: ObjectRef,
+        reference: ObjectRef,
+    },
+}
+
+/// A range of object IDs whose digest differs between the local store and the
+/// reference, and holds few enough objects to be compared object by object.
+#[derive(Clone, Debug, Serialize)]
+pub struct DivergingRange {
+    pub range: ObjectIdRange,
+    pub local: RangeDigest,
+    pub reference: RangeDigest,
+    pub objects: Vec<ObjectDivergence>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct LiveObjectSetAuditReport {
+    pub status: LiveObjectSetAuditStatus,
+    /// Description of the reference the live object set is audited against.
+    pub reference: String,
+    /// The checkpoint the reference was taken at, if known. Local objects
+    /// modified after it are not reported as divergences.
+    pub reference_checkpoint: Option<CheckpointSequenceNumber>,
+    pub ranges_compared: u64,
+    pub objects_compared: u64,
+    /// Objects that differ from the reference only because they were modified
+    /// locally after the reference was taken.
+    pub objects_changed_since_reference: u64,
+    pub diverging_ranges: Vec<DivergingRange>,
+}
+
+/// Runs at most one audit of the live object set at a time in the background,
+/// and keeps the report of the latest one.
+pub struct LiveObjectSetAudit {
+    local: Arc<LocalLiveObjectSet>,
+    report: Arc<Mutex<LiveObjectSetAuditReport>>,
+    stop: Mutex<Arc<AtomicBool>>,
+}
+
+enum Comparison {
+    Equal,
+    ChangedSinceReference,
+    Diverges(ObjectDivergence),
+}
+
+struct Auditor {
+    local: Arc<LocalLiveObjectSet>,
+    reference: Arc<dyn LiveObjectSetReference>,
+    reference_checkpoint: Option<CheckpointSequenceNumber>,
+    config: LiveObjectSetAuditConfig,
+    report: Arc<Mutex<LiveObjectSetAuditReport>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl ObjectIdRange {
+    pub fn new(start: ObjectID, end: ObjectID) -> Self {
+        Self { start, end }
+    }
+
+    /// The range of all object IDs.
+    pub fn full() -> Self {
+        Self::new(ObjectID::ZERO, ObjectID::MAX)
+    }
+
+    pub fn contains(&self, id: &ObjectID) -> bool {
+        self.start <= *id && *id <= self.end
+    }
+
+    /// Splits the range in two halves, or returns `None` if it holds a single
+    /// object ID.
+    pub fn split(&self) -> Option<(Self, Self)> {
+        let start = id_to_u256(&self.start);
+        let end = id_to_u256(&self.end);
+        if start >= end {
+            return None;
+        }
+
+        let mid = start + (end - start) / U256::from(2u8);
+        Some((
+            Self::new(self.start, u256_to_id(mid)),
+            Self::new(u256_to_id(mid + U256::one()), self.end),
+        ))
+    }
+}
+
+impl fmt::Display for ObjectIdRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {}]", self.start, self.end)
+    }
+}
+
+impl RangeDigest {
+    /// Accumulates the references of live objects the same way as the state
+    /// accumulator.
+    pub fn from_object_refs(refs: impl IntoIterator<Item = ObjectRef>) -> Self {
+        let mut acc = Accumulator::default();
+        let mut count = 0;
+        for object_ref in refs {
+            acc.insert(object_ref.2);
+            count += 1;
+        }
+
+        Self {
+            count,
+            digest: acc.digest().into(),
+        }
+    }
+}
+
+impl LocalLiveObjectSet {
+    pub fn new(tables: Arc<AuthorityPerpetualTables>) -> Self {
+        Self { tables }
+    }
+
+    pub fn range_digest(&self, range: ObjectIdRange) -> RangeDigest {
+        RangeDigest::from_object_refs(
+            self.iter_range(range)
+                .map(|live_object| live_object.object_reference()),
+        )
+    }
+
+    /// The references of at most `limit` live objects in `range`, starting
+    /// from its start.
+    pub fn range_object_refs(&self, range: ObjectIdRange, limit: usize) -> ObjectRefsPage {
+        let mut object_refs: Vec<_> = self
+            .iter_range(range)
+            .map(|live_object| live_object.object_reference())
+            .take(limit.saturating_add(1))
+            .collect();
+        let next_cursor = if object_refs.len() > limit {
+            object_refs.pop().map(|object_ref| object_ref.0)
+        } else {
+            None
+        };
+
+        ObjectRefsPage {
+            object_refs,
+            next_cursor,
+        }
+    }
+
+    /// The last checkpoint of `epoch`, if the node has accumulated the live
+    /// object set at the end of it.
+    pub fn epoch_last_checkpoint(
+        &self,
+        epoch: u64,
+    ) -> anyhow::Result<Option<CheckpointSequenceNumber>> {
+        Ok(self
+            .tables
+            .get_root_state_hash(epoch)?
+            .map(|(checkpoint, _)| checkpoint))
+    }
+
+    /// The live objects in `range` along with the transaction that last
+    /// modified them.
+    fn range_objects(&self, range: ObjectIdRange) -> Vec<(ObjectRef, TransactionDigest)> {
+        self.iter_range(range)
+            .filter_map(|live_object| match live_object {
+                LiveObject::Normal(object) => Some((
+                    object.compute_object_reference(),
+                    object.previous_transaction,
+                )),
+                LiveObject::Wrapped(_) => None,
+            })
+            .collect()
+    }
+
+    fn iter_range(&self, range: ObjectIdRange) -> impl Iterator<Item = LiveObject> + '_ {
+        self.tables
+            .range_iter_live_object_set(Some(range.start), Some(range.end))
+            .filter(move |live_object| range.contains(&live_object.object_id()))
+    }
+
+    /// Whether the local object was modified after `checkpoint`, or by a
+    /// transaction that is not part of a checkpoint yet.
+    fn modified_after(
+        &self,
+        transaction: &TransactionDigest,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> anyhow::Result<bool> {
+        Ok(self
+            .tables
+            .get_checkpoint_sequence_number(transaction)?
+            .map_or(true, |(_, executed_in)| executed_in > checkpoint))
+    }
+
+    /// The latest version of the object in the local store, including
+    /// versions at which it was deleted or wrapped.
+    fn latest_version(&self, id: ObjectID) -> anyhow::Result<Option<ObjectRef>> {
+        Ok(self.tables.get_latest_object_ref_or_tombstone(id)?)
+    }
+}
+
+impl Default for LiveObjectSetAuditConfig {
+    fn default() -> Self {
+        Self {
+            max_objects_per_range: 1_000,
+            throttle: Duration::from_millis(10),
+        }
+    }
+}
+
+impl LiveObjectSetAudit {
+    pub fn new(tables: Arc<AuthorityPerpetualTables>) -> Self {
+        Self {
+            local: Arc::new(LocalLiveObjectSet::new(tables)),
+            report: Default::default(),
+            stop: Default::default(),
+        }
+    }
+
+    pub fn local(&self) -> Arc<LocalLiveObjectSet> {
+        self.local.clone()
+    }
+
+    /// The report of the running audit, or of the latest one if none is
+    /// running.
+    pub fn report(&self) -> LiveObjectSetAuditReport {
+        self.report.lock().clone()
+    }
+
+    /// Starts an audit against the reference that `reference` resolves to, in
+    /// the background. Fails if an audit is already running.
+    pub fn start(
+        &self,
+        description: String,
+        reference_checkpoint: Option<CheckpointSequenceNumber>,
+        config: LiveObjectSetAuditConfig,
+        reference: impl Future<Output = anyhow::Result<Arc<dyn LiveObjectSetReference>>>
+        + Send
+        + 'static,
+    ) -> anyhow::Result<()> {
+        let mut stop = self.stop.lock();
+        {
+            let mut report = self.report.lock();
+            if matches!(
+                report.status,
+                LiveObjectSetAuditStatus::LoadingReference | LiveObjectSetAuditStatus::Running
+            ) {
+                return Err(anyhow!(
+                    "An audit of the live object set is already running"
+                ));
+            }
+
+            *report = LiveObjectSetAuditReport {
+                status: LiveObjectSetAuditStatus::LoadingReference,
+                reference: description,
+                reference_checkpoint,
+                ..Default::default()
+            };
+        }
+
+        *stop = Arc::new(AtomicBool::new(false));
+        let local = self.local.clone();
+        let report = self.report.clone();
+        let stop = stop.clone();
+        tokio::spawn(async move {
+            let result = match reference.await {
+                Ok(reference) => {
+                    let auditor = Auditor {
+                        local,
+                        reference,
+                        reference_checkpoint,
+                        config,
+                        report: report.clone(),
+                        stop,
+                    };
+                    auditor.run().await
+                }
+                Err(e) => Err(e.context("Failed to load the reference live object set")),
+            };
+
+            let mut report = report.lock();
+            match result {
+                Ok(status) => report.status = status,
+                Err(e) => {
+                    warn!("Live object set audit failed: {e:#}");
+                    report.status = LiveObjectSetAuditStatus::Failed(format!("{e:#}"));
+                }
+            }
+            info!(
+                status = ?report.status,
+                diverging_ranges = report.diverging_ranges.len(),
+                "Live object set audit finished"
+            );
+        });
+
+        Ok(())
+    }
+
+    /// Stops the running audit after the range it is comparing. Returns
+    /// whether an audit was running.
+    pub fn stop(&self) -> bool {
+        let running = matches!(
+            self.report.lock().status,
+            LiveObjectSetAuditStatus::LoadingReference | LiveObjectSetAuditStatus::Running
+        );
+        self.stop.lock().store(true, Ordering::Relaxed);
+        running
+    }
+}
+
+impl Auditor {
+    async fn run(self) -> anyhow::Result<LiveObjectSetAuditStatus> {
+        self.report.lock().status = LiveObjectSetAuditStatus::Running;
+        info!(
+            reference = %self.report.lock().reference,
+            "Starting live object set audit"
+        );
+
+        // Ranges are popped in ascending order of object IDs.
+        let mut pending = vec![ObjectIdRange::full()];
+        while let Some(range) = pending.pop() {
+            if self.stop.load(Ordering::Relaxed) {
+                return Ok(LiveObjectSetAuditStatus::Stopped);
+            }
+
+            let local = self
+                .blocking(move |local| local.range_digest(range))
+                .await?;
+            let reference = self.reference.range_digest(range).await?;
+            self.report.lock().ranges_compared += 1;
+
+            if local != reference {
+                let max_count = local.count.max(reference.count);
+                match range.split() {
+                    Some((lower, upper)) if max_count > self.config.max_objects_per_range => {
+                        pending.push(upper);
+                        pending.push(lower);
+                    }
+                    _ => self.compare_objects(range, local, reference).await?,
+                }
+            }
+
+            tokio::time::sleep(self.config.throttle).await;
+        }
+
+        Ok(LiveObjectSetAuditStatus::Completed)
+    }
+
+    async fn compare_objects(
+        &self,
+        range: ObjectIdRange,
+        local_digest: RangeDigest,
+        reference_digest: RangeDigest,
+    ) -> anyhow::Result<()> {
+        let local = self
+            .blocking(move |local| local.range_objects(range))
+            .await?;
+        let reference = self.reference.range_object_refs(range).await?;
+
+        let mut objects = vec![];
+        let mut changed_since_reference = 0;
+        let mut compared = 0;
+
+        let mut local = local.into_iter().peekable();
+        let mut reference = reference.into_iter().peekable();
+        loop {
+            let (local_object, reference_ref) = match (local.peek(), reference.peek()) {
+                (None, None) => break,
+                (Some((l, _)), Some(r)) if l.0 == r.0 => (local.next(), reference.next()),
+                (Some((l, _)), Some(r)) if l.0 < r.0 => (local.next(), None),
+                (Some(_), None) => (local.next(), None),
+                _ => (None, reference.next()),
+            };
+            compared += 1;
+
+            match self.classify(local_object, reference_ref)? {
+                Comparison::Equal => {}
+                Comparison::ChangedSinceReference => changed_since_reference += 1,
+                Comparison::Diverges(divergence) => {
+                    warn!(%range, ?divergence, "Live object diverges from the reference");
+                    objects.push(divergence);
+                }
+            }
+        }
+
+        let mut report = self.report.lock();
+        report.objects_compared += compared;
+        report.objects_changed_since_reference += changed_since_reference;
+        if !objects.is_empty() {
+            report.diverging_ranges.push(DivergingRange {
+                range,
+                local: local_digest,
+                reference: reference_digest,
+                objects,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Compares the local and reference versions of an object. Differences
+    /// that are explained by the object having been modified locally after
+    /// the reference was taken are not divergences.
+    fn classify(
+        &self,
+        local: Option<(ObjectRef, TransactionDigest)>,
+        reference: Option<ObjectRef>,
+    ) -> anyhow::Result<Comparison> {
+        let divergence = match (local, reference) {
+            (Some((local, _)), Some(reference)) if local == reference => {
+                return Ok(Comparison::Equal);
+            }
+            (Some((local, _)), Some(reference)) => {
+                if local.1 > reference.1 {
+                    return Ok(Comparison::ChangedSinceReference);
+                }
+                ObjectDivergence::Mismatch { local, reference }
+            }
+            (Some((local, transaction)), None) => {
+                if let Some(checkpoint) = self.reference_checkpoint {
+                    if self.local.modified_after(&transaction, checkpoint)? {
+                        return Ok(Comparison::ChangedSinceReference);
+                    }
+                }
+                ObjectDivergence::Unexpected { local }
+            }
+            (None, Some(reference)) => {
+                // Deleted or wrapped locally after the version in the reference.
+                if let Some(latest) = self.local.latest_version(reference.0)? {
+                    if latest.1 > reference.1 {
+                        return Ok(Comparison::ChangedSinceReference);
+                    }
+                }
+                ObjectDivergence::Missing { reference }
+            }
+            (None, None) => return Ok(Comparison::Equal),
+        };
+
+        Ok(Comparison::Diverges(divergence))
+    }
+
+    /// Runs `f` against the local live object set on a blocking thread, as it
+    /// iterates over the database.
+    async fn blocking<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&LocalLiveObjectSet) -> T + Send + 'static,
+    ) -> anyhow::Result<T> {
+        let local = self.local.clone();
+        Ok(tokio::task::spawn_blocking(move || f(&local)).await?)
+    }
+}
+
+fn id_to_u256(id: &ObjectID) -> U256 {
+    let mut bytes = id.into_bytes();
+    bytes.reverse();
+    U256::from_le_bytes(&bytes)
+}
+
+fn u256_to_id(value: U256) -> ObjectID {
+    let mut bytes = value.to_le_bytes();
+    bytes.reverse();
+    ObjectID::new(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use iota_types::object::Object;
+
+    use super::*;
+
+    #[test]
+    fn test_split_range() {
+        let (lower, upper) = ObjectIdRange::full().split().unwrap();
+        assert_eq!(lower.start, ObjectID::ZERO);
+        assert_eq!(upper.end, ObjectID::MAX);
+        assert_eq!(
+            u256_to_id(id_to_u256(&lower.end) + U256::one()),
+            upper.start
+        );
+        assert_eq!(upper.start.into_bytes()[0], 0x80);
+        assert!(upper.start.into_bytes()[1..].iter().all(|b| *b == 0));
+
+        let single = ObjectIdRange::new(ObjectID::ZERO, ObjectID::ZERO);
+        assert!(single.split().is_none());
+
+        let pair = ObjectIdRange::new(ObjectID::ZERO, u256_to_id(U256::one()));
+        let (lower, upper) = pair.split().unwrap();
+        assert_eq!(lower.start, lower.end);
+        assert_eq!(upper.start, upper.end);
+    }
+
+    #[test]
+    fn test_range_object_refs_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        let tables = Arc::new(AuthorityPerpetualTables::open(dir.path(), None));
+        let mut expected = vec![];
+        for _ in 0..5 {
+            let object = Object::with_id_owner_for_testing(ObjectID::random(), Default::default());
+            expected.push(object.compute_object_reference());
+            tables.insert_object_test_only(object).unwrap();
+        }
+        expected.sort();
+
+        let local = LocalLiveObjectSet::new(tables);
+        let mut range = ObjectIdRange::full();
+        let mut pages = vec![];
+        loop {
+            let page = local.range_object_refs(range, 2);
+            pages.push(page.object_refs);
+            match page.next_cursor {
+                Some(cursor) => range.start = cursor,
+                None => break,
+            }
+        }
+
+        let page_sizes: Vec<_> = pages.iter().map(Vec::len).collect();
+        assert_eq!(page_sizes, [2, 2, 1]);
+        assert_eq!(pages.concat(), expected);
+
+        let page = local.range_object_refs(ObjectIdRange::full(), 5);
+        assert_eq!(page.object_refs, expected);
+        assert_eq!(page.next_cursor, None);
+    }
+}
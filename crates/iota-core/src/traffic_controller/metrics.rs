@@ -24,6 +24,11 @@ pub struct TrafficControllerMetrics {
     pub highest_proxied_spam_rate: IntGauge,
     pub highest_direct_error_rate: IntGauge,
     pub highest_proxied_error_rate: IntGauge,
+    pub transaction_tallies: IntCounter,
+    pub transactions_rejected_by_reputation: IntCounter,
+    pub reputation_throttles: IntCounter,
+    pub reputation_blocks: IntCounter,
+    pub reputation_table_len: IntGauge,
 }
 
 impl TrafficControllerMetrics {
@@ -122,6 +127,37 @@ impl TrafficControllerMetrics {
                 registry
             )
             .unwrap(),
+            transaction_tallies: register_int_counter_with_registry!(
+                "traffic_control_transaction_tallies",
+                "Number of tallies of failed transactions",
+                registry
+            )
+            .unwrap(),
+            transactions_rejected_by_reputation: register_int_counter_with_registry!(
+                "traffic_control_transactions_rejected_by_reputation",
+                "Number of transactions not signed because their sender or gas coins \
+                    are throttled or blocked",
+                registry
+            )
+            .unwrap(),
+            reputation_throttles: register_int_counter_with_registry!(
+                "traffic_control_reputation_throttles",
+                "Number of times a sender or gas coin started to be throttled",
+                registry
+            )
+            .unwrap(),
+            reputation_blocks: register_int_counter_with_registry!(
+                "traffic_control_reputation_blocks",
+                "Number of times a sender or gas coin was added to the blocklist",
+                registry
+            )
+            .unwrap(),
+            reputation_table_len: register_int_gauge_with_registry!(
+                "traffic_control_reputation_table_len",
+                "Number of senders and gas coins with a reputation score",
+                registry
+            )
+            .unwrap(),
         }
     }
 
@@ -19,7 +19,10 @@ use std::{
 use dashmap::DashMap;
 use fs::File;
 use iota_metrics::spawn_monitored_task;
-use iota_types::traffic_control::{PolicyConfig, RemoteFirewallConfig, Weight};
+use iota_types::{
+    base_types::{IotaAddress, ObjectID},
+    traffic_control::{PolicyConfig, RemoteFirewallConfig, Weight},
+};
 use prometheus::IntGauge;
 use rand::Rng;
 use tokio::{
@@ -31,7 +34,10 @@ use tracing::{debug, error, info, trace, warn};
 use self::metrics::TrafficControllerMetrics;
 use crate::traffic_controller::{
     nodefw_client::{BlockAddress, BlockAddresses, NodeFWClient},
-    policies::{Policy, PolicyResponse, TrafficControlPolicy, TrafficTally},
+    policies::{
+        Policy, PolicyResponse, Restriction, TrafficControlPolicy, TrafficTally,
+        TransactionReputation, TransactionTally,
+    },
 };
 
 pub const METRICS_INTERVAL_SECS: u64 = 2;
@@ -49,6 +55,8 @@ struct Blocklists {
 pub struct TrafficController {
     tally_channel: mpsc::Sender<TrafficTally>,
     blocklists: Blocklists,
+    transaction_tally_channel: mpsc::Sender<TransactionTally>,
+    reputation: Arc<TransactionReputation>,
     metrics: Arc<TrafficControllerMetrics>,
     dry_run_mode: bool,
 }
@@ -81,6 +89,7 @@ impl TrafficController {
     ) -> Self {
         let metrics = Arc::new(metrics);
         let (tx, rx) = mpsc::channel(policy_config.channel_capacity);
+        let (transaction_tx, transaction_rx) = mpsc::channel(policy_config.channel_capacity);
         // Memoized drainfile existence state. This is passed into delegation
         // functions to prevent them from continuing to populate blocklists
         // if drain is set, as otherwise it will grow without bounds
@@ -99,9 +108,18 @@ impl TrafficController {
                 clients: Arc::new(DashMap::new()),
                 proxied_clients: Arc::new(DashMap::new()),
             },
+            transaction_tally_channel: transaction_tx,
+            reputation: Arc::new(TransactionReputation::from_config(&policy_config)),
             metrics: metrics.clone(),
             dry_run_mode: policy_config.dry_run,
         };
+        if ret.reputation.is_enabled() {
+            spawn_monitored_task!(run_reputation_tally_loop(
+                transaction_rx,
+                ret.reputation.clone(),
+                metrics.clone(),
+            ));
+        }
         let tally_loop_blocklists = ret.blocklists.clone();
         let clear_loop_blocklists = ret.blocklists.clone();
         let tally_loop_metrics = metrics.clone();
@@ -197,6 +215,53 @@ impl TrafficController {
         self.dry_run_mode
     }
 
+    /// Tallies a transaction that failed to be signed or executed towards the
+    /// reputation of its sender and gas coins.
+    pub fn tally_transaction(&self, tally: TransactionTally) {
+        if !self.reputation.is_enabled() || !tally.weight.is_sampled() {
+            return;
+        }
+        // As for `tally`, drop the tally rather than creating backpressure
+        match self.transaction_tally_channel.try_send(tally) {
+            Err(TrySendError::Full(_)) => {
+                warn!("TrafficController transaction tally channel full, dropping tally");
+                self.metrics.tally_channel_overflow.inc();
+            }
+            Err(TrySendError::Closed(_)) => {
+                panic!("TrafficController transaction tally channel closed unexpectedly");
+            }
+            Ok(_) => {}
+        }
+    }
+
+    /// Returns true if a transaction of `sender` that pays with `gas_coins`
+    /// may be signed, with dry-run mode considered
+    pub fn check_transaction(&self, sender: &IotaAddress, gas_coins: &[ObjectID]) -> bool {
+        if self.reputation.check(sender, gas_coins, SystemTime::now()) {
+            return true;
+        }
+        if self.dry_run_mode() {
+            debug!(
+                "Dry run mode: Rejected transaction of sender {sender} with gas coins {gas_coins:?}"
+            );
+            self.metrics.num_dry_run_blocked_requests.inc();
+            return true;
+        }
+        self.metrics.transactions_rejected_by_reputation.inc();
+        false
+    }
+
+    /// Records that a transaction of `sender` that pays with `gas_coins` was
+    /// signed, which counts towards throttling them.
+    pub fn record_signed_transaction(&self, sender: &IotaAddress, gas_coins: &[ObjectID]) {
+        self.reputation
+            .record_signed(sender, gas_coins, SystemTime::now());
+    }
+
+    pub fn transaction_reputation(&self) -> &TransactionReputation {
+        &self.reputation
+    }
+
     async fn check_and_clear_blocklist(
         &self,
         client: &Option<IpAddr>,
@@ -248,6 +313,39 @@ async fn run_clear_blocklists_loop(blocklists: Blocklists, metrics: Arc<TrafficC
     }
 }
 
+async fn run_reputation_tally_loop(
+    mut receiver: mpsc::Receiver<TransactionTally>,
+    reputation: Arc<TransactionReputation>,
+    metrics: Arc<TrafficControllerMetrics>,
+) {
+    let mut prune_interval = time::interval(Duration::from_secs(METRICS_INTERVAL_SECS));
+    loop {
+        tokio::select! {
+            received = receiver.recv() => {
+                let Some(tally) = received else {
+                    info!("TrafficController transaction tally channel closed by all senders");
+                    return;
+                };
+                metrics.transaction_tallies.inc();
+                for restriction in reputation.handle_tally(&tally) {
+                    debug!(
+                        "Restricting sender {} or gas coins {:?}: {:?}",
+                        tally.sender, tally.gas_coins, restriction
+                    );
+                    match restriction {
+                        Restriction::Throttled { .. } => metrics.reputation_throttles.inc(),
+                        Restriction::Blocked { .. } => metrics.reputation_blocks.inc(),
+                    }
+                }
+            }
+            _ = prune_interval.tick() => {
+                reputation.prune(SystemTime::now());
+                metrics.reputation_table_len.set(reputation.len() as i64);
+            }
+        }
+    }
+}
+
 async fn run_tally_loop(
     mut receiver: mpsc::Receiver<TrafficTally>,
     policy_config: PolicyConfig,
@@ -14,13 +14,21 @@ use std::{
 };
 
 use count_min_sketch::CountMinSketch32;
+use dashmap::{DashMap, mapref::entry::Entry};
 use iota_metrics::spawn_monitored_task;
-use iota_types::traffic_control::{FreqThresholdConfig, PolicyConfig, PolicyType, Weight};
+use iota_types::{
+    base_types::{IotaAddress, ObjectID},
+    digests::TransactionDigest,
+    traffic_control::{FreqThresholdConfig, PolicyConfig, PolicyType, ReputationConfig, Weight},
+};
 use parking_lot::RwLock;
-use tracing::info;
+use tracing::{info, warn};
 
 const HIGHEST_RATES_CAPACITY: usize = 20;
 
+/// Scores below this are forgotten once they are no longer restricted.
+const MIN_REPUTATION_SCORE: f64 = 0.01;
+
 /// The type of request client.
 #[derive(Hash, Eq, PartialEq, Debug)]
 enum ClientType {
@@ -316,6 +324,13 @@ impl TrafficControlPolicy {
             PolicyType::TestPanicOnInvocation => {
                 Self::TestPanicOnInvocation(TestPanicOnInvocationPolicy::new(policy_config))
             }
+            PolicyType::SenderReputation(_) | PolicyType::GasCoinReputation(_) => {
+                warn!(
+                    "Reputation policy {policy_type:?} only applies to transactions, \
+                    configure it in reputation-policy-types instead"
+                );
+                Self::NoOp(NoOpPolicy::new(policy_config))
+            }
         }
     }
 }
@@ -400,6 +415,363 @@ impl FreqThresholdPolicy {
     }
 }
 
+/// Tally of a transaction that failed to be signed or executed. Unlike a
+/// [`TrafficTally`], it is attributed to the sender and gas coins of the
+/// transaction rather than to the client that submitted it.
+#[derive(Clone, Debug)]
+pub struct TransactionTally {
+    pub digest: TransactionDigest,
+    pub sender: IotaAddress,
+    pub gas_coins: Vec<ObjectID>,
+    pub weight: Weight,
+    pub timestamp: SystemTime,
+}
+
+impl TransactionTally {
+    pub fn new(
+        digest: TransactionDigest,
+        sender: IotaAddress,
+        gas_coins: Vec<ObjectID>,
+        weight: Weight,
+    ) -> Self {
+        Self {
+            digest,
+            sender,
+            gas_coins,
+            weight,
+            timestamp: SystemTime::now(),
+        }
+    }
+}
+
+/// A restriction on signing transactions that involve a sender or gas coin.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Restriction {
+    /// At most one transaction is signed every `interval`.
+    Throttled {
+        expiration: SystemTime,
+        interval: Duration,
+    },
+    /// No transactions are signed.
+    Blocked { expiration: SystemTime },
+}
+
+impl Restriction {
+    pub fn expiration(&self) -> SystemTime {
+        match self {
+            Restriction::Throttled { expiration, .. } | Restriction::Blocked { expiration } => {
+                *expiration
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Reputation {
+    /// Score as of `updated_at`, which halves every `score_half_life_secs`.
+    pub score: f64,
+    pub updated_at: SystemTime,
+    pub restriction: Option<Restriction>,
+    pub last_signed: Option<SystemTime>,
+}
+
+impl Reputation {
+    fn new(now: SystemTime) -> Self {
+        Self {
+            score: 0.0,
+            updated_at: now,
+            restriction: None,
+            last_signed: None,
+        }
+    }
+
+    fn score_at(&self, now: SystemTime, half_life: Duration) -> f64 {
+        if half_life.is_zero() {
+            return 0.0;
+        }
+        let elapsed = now.duration_since(self.updated_at).unwrap_or_default();
+        self.score * 0.5f64.powf(elapsed.as_secs_f64() / half_life.as_secs_f64())
+    }
+
+    fn restriction_at(&self, now: SystemTime) -> Option<Restriction> {
+        self.restriction
+            .filter(|restriction| now < restriction.expiration())
+    }
+}
+
+/// Scores of the keys, e.g. senders, of failed transactions, and the
+/// restrictions on signing transactions that involve them.
+pub struct ReputationTable<K> {
+    config: ReputationConfig,
+    entries: DashMap<K, Reputation>,
+    /// The transactions tallied within the last half-life of scores, and when.
+    tallied: DashMap<TransactionDigest, SystemTime>,
+}
+
+impl<K: Copy + Eq + Hash> ReputationTable<K> {
+    pub fn new(config: ReputationConfig) -> Self {
+        Self {
+            config,
+            entries: DashMap::new(),
+            tallied: DashMap::new(),
+        }
+    }
+
+    fn half_life(&self) -> Duration {
+        Duration::from_secs(self.config.score_half_life_secs)
+    }
+
+    /// Records that the transaction `digest` is tallied. Returns false if it
+    /// was already tallied within the last half-life of scores, so that a
+    /// failing transaction resubmitted by anyone only counts once towards the
+    /// scores while they decay.
+    pub fn record_tallied(&self, digest: TransactionDigest, now: SystemTime) -> bool {
+        match self.tallied.entry(digest) {
+            Entry::Occupied(mut entry) => {
+                if now
+                    .duration_since(*entry.get())
+                    .map_or(true, |elapsed| elapsed < self.half_life())
+                {
+                    return false;
+                }
+                entry.insert(now);
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(now);
+            }
+        }
+        true
+    }
+
+    /// Adds `weight` to the decayed score of `key`, and escalates its
+    /// restriction if the score crosses a threshold. Returns the restriction
+    /// if it was newly imposed or escalated.
+    pub fn handle_tally(&self, key: K, weight: &Weight, now: SystemTime) -> Option<Restriction> {
+        let half_life = self.half_life();
+        let mut entry = self
+            .entries
+            .entry(key)
+            .or_insert_with(|| Reputation::new(now));
+        let score = entry.score_at(now, half_life) + weight.value() as f64;
+        entry.score = score;
+        entry.updated_at = now;
+
+        let current = entry.restriction_at(now);
+        if score >= self.config.blocklist_threshold {
+            let restriction = Restriction::Blocked {
+                expiration: now + Duration::from_secs(self.config.blocklist_ttl_secs),
+            };
+            entry.restriction = Some(restriction);
+            (!matches!(current, Some(Restriction::Blocked { .. }))).then_some(restriction)
+        } else if score >= self.config.throttle_threshold {
+            if matches!(current, Some(Restriction::Blocked { .. })) {
+                return None;
+            }
+            // throttle until the score has decayed to half the threshold, so that
+            // a single failure right after does not throttle again
+            let restriction = Restriction::Throttled {
+                expiration: now
+                    + half_life.mul_f64((score / self.config.throttle_threshold).log2() + 1.0),
+                interval: Duration::from_millis(self.config.throttle_interval_ms),
+            };
+            entry.restriction = Some(restriction);
+            current.is_none().then_some(restriction)
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if a transaction that involves `key` may be signed.
+    pub fn check(&self, key: &K, now: SystemTime) -> bool {
+        let Some(entry) = self.entries.get(key) else {
+            return true;
+        };
+        match entry.restriction_at(now) {
+            None => true,
+            Some(Restriction::Blocked { .. }) => false,
+            Some(Restriction::Throttled { interval, .. }) => entry.last_signed.is_none_or(|t| {
+                now.duration_since(t)
+                    .is_ok_and(|elapsed| elapsed >= interval)
+            }),
+        }
+    }
+
+    /// Records that a transaction that involves `key` was signed, which
+    /// counts towards its throttling.
+    pub fn record_signed(&self, key: &K, now: SystemTime) {
+        if let Some(mut entry) = self.entries.get_mut(key) {
+            entry.last_signed = Some(now);
+        }
+    }
+
+    /// Returns the reputation of `key` as of `now`.
+    pub fn get(&self, key: &K, now: SystemTime) -> Option<Reputation> {
+        self.entries
+            .get(key)
+            .map(|entry| self.reputation_at(&entry, now))
+    }
+
+    /// Returns the reputations of all keys as of `now`.
+    pub fn entries(&self, now: SystemTime) -> Vec<(K, Reputation)> {
+        self.entries
+            .iter()
+            .map(|entry| (*entry.key(), self.reputation_at(entry.value(), now)))
+            .collect()
+    }
+
+    /// Forgets the score and lifts the restriction of `key`. Returns false if
+    /// the key was not known.
+    pub fn reset(&self, key: &K) -> bool {
+        self.entries.remove(key).is_some()
+    }
+
+    pub fn clear(&self) {
+        self.entries.clear();
+    }
+
+    /// Forgets keys that are no longer restricted and whose score has
+    /// decayed, and transactions tallied more than a half-life ago.
+    pub fn prune(&self, now: SystemTime) {
+        let half_life = self.half_life();
+        self.entries.retain(|_, entry| {
+            entry.restriction_at(now).is_some()
+                || entry.score_at(now, half_life) >= MIN_REPUTATION_SCORE
+        });
+        self.tallied.retain(|_, tallied_at| {
+            now.duration_since(*tallied_at)
+                .map_or(true, |elapsed| elapsed < half_life)
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn reputation_at(&self, reputation: &Reputation, now: SystemTime) -> Reputation {
+        Reputation {
+            score: reputation.score_at(now, self.half_life()),
+            updated_at: now,
+            restriction: reputation.restriction_at(now),
+            last_signed: reputation.last_signed,
+        }
+    }
+}
+
+/// The reputation tables of transaction senders and gas coins, for the
+/// policies configured in `reputation_policy_types`.
+#[derive(Default)]
+pub struct TransactionReputation {
+    pub senders: Option<ReputationTable<IotaAddress>>,
+    pub gas_coins: Option<ReputationTable<ObjectID>>,
+}
+
+impl TransactionReputation {
+    /// Panics if a reputation policy is misconfigured, so that the node does
+    /// not start with thresholds that would fail when scores are tallied.
+    pub fn from_config(policy_config: &PolicyConfig) -> Self {
+        let mut reputation = Self::default();
+        for policy_type in &policy_config.reputation_policy_types {
+            if let PolicyType::SenderReputation(config) | PolicyType::GasCoinReputation(config) =
+                policy_type
+            {
+                if let Err(e) = config.validate() {
+                    panic!("Invalid reputation policy {policy_type:?}: {e}");
+                }
+            }
+            match policy_type {
+                PolicyType::SenderReputation(config) if reputation.senders.is_none() => {
+                    reputation.senders = Some(ReputationTable::new(config.clone()));
+                }
+                PolicyType::GasCoinReputation(config) if reputation.gas_coins.is_none() => {
+                    reputation.gas_coins = Some(ReputationTable::new(config.clone()));
+                }
+                _ => warn!("Ignoring reputation policy {policy_type:?}"),
+            }
+        }
+        reputation
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.senders.is_some() || self.gas_coins.is_some()
+    }
+
+    /// Returns the restrictions that were newly imposed or escalated by
+    /// `tally`. A transaction is tallied at most once per half-life of the
+    /// scores of each table.
+    pub fn handle_tally(&self, tally: &TransactionTally) -> Vec<Restriction> {
+        let mut restrictions = vec![];
+        if let Some(senders) = &self.senders {
+            if senders.record_tallied(tally.digest, tally.timestamp) {
+                restrictions.extend(senders.handle_tally(
+                    tally.sender,
+                    &tally.weight,
+                    tally.timestamp,
+                ));
+            }
+        }
+        if let Some(gas_coins) = &self.gas_coins {
+            if !tally.gas_coins.is_empty()
+                && gas_coins.record_tallied(tally.digest, tally.timestamp)
+            {
+                for gas_coin in &tally.gas_coins {
+                    restrictions.extend(gas_coins.handle_tally(
+                        *gas_coin,
+                        &tally.weight,
+                        tally.timestamp,
+                    ));
+                }
+            }
+        }
+        restrictions
+    }
+
+    /// Returns true if a transaction of `sender` that pays with `gas_coins`
+    /// may be signed.
+    pub fn check(&self, sender: &IotaAddress, gas_coins: &[ObjectID], now: SystemTime) -> bool {
+        self.senders
+            .as_ref()
+            .is_none_or(|senders| senders.check(sender, now))
+            && self
+                .gas_coins
+                .as_ref()
+                .is_none_or(|table| gas_coins.iter().all(|gas_coin| table.check(gas_coin, now)))
+    }
+
+    pub fn record_signed(&self, sender: &IotaAddress, gas_coins: &[ObjectID], now: SystemTime) {
+        if let Some(senders) = &self.senders {
+            senders.record_signed(sender, now);
+        }
+        if let Some(table) = &self.gas_coins {
+            for gas_coin in gas_coins {
+                table.record_signed(gas_coin, now);
+            }
+        }
+    }
+
+    pub fn prune(&self, now: SystemTime) {
+        if let Some(senders) = &self.senders {
+            senders.prune(now);
+        }
+        if let Some(gas_coins) = &self.gas_coins {
+            gas_coins.prune(now);
+        }
+    }
+
+    /// The number of senders and gas coins that are tracked.
+    pub fn len(&self) -> usize {
+        self.senders.as_ref().map_or(0, ReputationTable::len)
+            + self.gas_coins.as_ref().map_or(0, ReputationTable::len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
 ////////////// *** Test policies below this point *** //////////////
 
 #[derive(Clone)]
@@ -657,6 +1029,163 @@ mod tests {
         assert_eq!(proxied_rate, 1);
     }
 
+    #[sim_test]
+    async fn test_reputation_table() {
+        let table = ReputationTable::new(ReputationConfig {
+            score_half_life_secs: 10,
+            throttle_threshold: 2.0,
+            throttle_interval_ms: 1_000,
+            blocklist_threshold: 4.0,
+            blocklist_ttl_secs: 60,
+        });
+        let alice = IotaAddress::random_for_testing_only();
+        let bob = IotaAddress::random_for_testing_only();
+        let start = SystemTime::now();
+        let after = |secs: f64| start + Duration::from_secs_f64(secs);
+
+        // a single failure does not restrict alice
+        assert_eq!(table.handle_tally(alice, &Weight::one(), start), None);
+        assert!(table.check(&alice, start));
+
+        // the second one throttles her until her score has halved
+        assert_eq!(
+            table.handle_tally(alice, &Weight::one(), start),
+            Some(Restriction::Throttled {
+                expiration: after(10.0),
+                interval: Duration::from_secs(1),
+            })
+        );
+        assert!(table.check(&alice, start));
+        table.record_signed(&alice, start);
+        assert!(!table.check(&alice, after(0.5)));
+        assert!(table.check(&alice, after(1.0)));
+        assert!(table.check(&bob, after(0.5)));
+
+        // further failures escalate to blocking her
+        assert_eq!(table.handle_tally(alice, &Weight::one(), start), None);
+        assert_eq!(
+            table.handle_tally(alice, &Weight::one(), start),
+            Some(Restriction::Blocked {
+                expiration: after(60.0),
+            })
+        );
+        assert_eq!(table.handle_tally(alice, &Weight::one(), start), None);
+        assert!(!table.check(&alice, after(30.0)));
+
+        // the block expires, while her score keeps decaying
+        assert!(table.check(&alice, after(60.0)));
+        let reputation = table.get(&alice, after(60.0)).unwrap();
+        assert_eq!(reputation.restriction, None);
+        assert!((reputation.score - 5.0 / 64.0).abs() < 1e-9);
+
+        // decayed scores are pruned
+        table.prune(after(60.0));
+        assert_eq!(table.len(), 1);
+        table.prune(after(120.0));
+        assert!(table.is_empty());
+
+        // resetting lifts the restriction
+        for _ in 0..4 {
+            table.handle_tally(bob, &Weight::one(), start);
+        }
+        assert!(!table.check(&bob, start));
+        assert!(table.reset(&bob));
+        assert!(table.check(&bob, start));
+        assert!(!table.reset(&bob));
+    }
+
+    #[test]
+    fn test_reputation_config_validation() {
+        assert!(ReputationConfig::default().validate().is_ok());
+        let invalid = [
+            ReputationConfig {
+                throttle_threshold: 0.0,
+                ..Default::default()
+            },
+            ReputationConfig {
+                blocklist_threshold: f64::NAN,
+                ..Default::default()
+            },
+            ReputationConfig {
+                score_half_life_secs: u64::MAX,
+                ..Default::default()
+            },
+            ReputationConfig {
+                blocklist_ttl_secs: u64::MAX,
+                ..Default::default()
+            },
+        ];
+        for config in invalid {
+            assert!(config.validate().is_err(), "{config:?}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid reputation policy")]
+    fn test_invalid_reputation_policy_panics() {
+        TransactionReputation::from_config(&PolicyConfig {
+            reputation_policy_types: vec![PolicyType::SenderReputation(ReputationConfig {
+                throttle_threshold: 0.0,
+                ..Default::default()
+            })],
+            ..Default::default()
+        });
+    }
+
+    #[sim_test]
+    async fn test_transaction_tallied_once_per_half_life() {
+        let config = ReputationConfig {
+            score_half_life_secs: 10,
+            ..Default::default()
+        };
+        let reputation = TransactionReputation::from_config(&PolicyConfig {
+            reputation_policy_types: vec![
+                PolicyType::SenderReputation(config.clone()),
+                PolicyType::GasCoinReputation(config),
+            ],
+            ..Default::default()
+        });
+        let senders = reputation.senders.as_ref().unwrap();
+        let gas_coins = reputation.gas_coins.as_ref().unwrap();
+        let alice = IotaAddress::random_for_testing_only();
+        let gas_coin = ObjectID::random();
+        let start = SystemTime::now();
+        let after = |secs| start + Duration::from_secs(secs);
+        let tally = |digest, gas_coins: Vec<ObjectID>, secs| TransactionTally {
+            digest,
+            sender: alice,
+            gas_coins,
+            weight: Weight::one(),
+            timestamp: after(secs),
+        };
+        fn score<K: Copy + Eq + Hash>(table: &ReputationTable<K>, key: &K, now: SystemTime) -> f64 {
+            table
+                .get(key, now)
+                .map_or(0.0, |reputation| reputation.score)
+        }
+
+        // resubmitting a failing transaction does not add to the scores
+        let digest = TransactionDigest::random();
+        reputation.handle_tally(&tally(digest, vec![gas_coin], 0));
+        reputation.handle_tally(&tally(digest, vec![gas_coin], 5));
+        assert_eq!(score(senders, &alice, after(0)), 1.0);
+        assert_eq!(score(gas_coins, &gas_coin, after(0)), 1.0);
+
+        // other transactions do
+        reputation.handle_tally(&tally(TransactionDigest::random(), vec![], 0));
+        assert_eq!(score(senders, &alice, after(0)), 2.0);
+        assert_eq!(score(gas_coins, &gas_coin, after(0)), 1.0);
+
+        // the transaction counts again once the scores have halved
+        reputation.handle_tally(&tally(digest, vec![gas_coin], 10));
+        assert_eq!(score(senders, &alice, after(10)), 2.0);
+        assert_eq!(score(gas_coins, &gas_coin, after(10)), 1.5);
+
+        // and is forgotten by then
+        reputation.prune(after(20));
+        assert!(senders.tallied.is_empty());
+    }
+
     #[sim_test]
     async fn test_traffic_sketch_mem_estimate() {
         // Test for getting a rough estimate of memory usage for the traffic sketch
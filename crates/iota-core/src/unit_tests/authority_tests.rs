@@ -17,8 +17,9 @@ use iota_protocol_config::{
     Chain, PerObjectCongestionControlMode, ProtocolConfig, ProtocolVersion,
 };
 use iota_types::{
-    IOTA_AUTHENTICATOR_STATE_OBJECT_ID, IOTA_CLOCK_OBJECT_ID, IOTA_FRAMEWORK_PACKAGE_ID,
-    IOTA_RANDOMNESS_STATE_OBJECT_ID, IOTA_SYSTEM_STATE_OBJECT_ID, MOVE_STDLIB_PACKAGE_ID,
+    IOTA_AUTHENTICATOR_STATE_OBJECT_ID, IOTA_CLOCK_OBJECT_ID, IOTA_CLOCK_OBJECT_SHARED_VERSION,
+    IOTA_FRAMEWORK_PACKAGE_ID, IOTA_RANDOMNESS_STATE_OBJECT_ID, IOTA_SYSTEM_STATE_OBJECT_ID,
+    MOVE_STDLIB_PACKAGE_ID,
     base_types::dbg_addr,
     crypto::{AccountKeyPair, AuthorityKeyPair, Signature, get_key_pair},
     digests::Digest,
@@ -26,14 +27,16 @@ use iota_types::{
     effects::TransactionEffects,
     epoch_data::EpochData,
     error::UserInputError,
-    execution::SharedInput,
+    execution::{SharedInput, TraceStep},
     execution_status::{ExecutionFailureStatus, ExecutionStatus},
+    gas::GasBreakdown,
     gas_coin::GasCoin,
     iota_system_state::IotaSystemStateWrapper,
     messages_consensus::{AuthorityCapabilitiesV1, ConsensusDeterminedVersionAssignments},
     object::{Data, GAS_VALUE_FOR_TESTING, OBJECT_START_VERSION, Owner},
     programmable_transaction_builder::ProgrammableTransactionBuilder,
     randomness_state::get_randomness_state_obj_initial_shared_version,
+    state_override::{ObjectOverride, StateOverrides},
     storage::GetSharedLocks,
     supported_protocol_versions::SupportedProtocolVersions,
     utils::{to_sender_signed_transaction, to_sender_signed_transaction_with_multi_signers},
@@ -851,6 +854,93 @@ async fn test_dev_inspect_gas_coin_argument() {
     assert!(return_values.is_empty());
 }
 
+#[tokio::test]
+async fn test_dev_inspect_trace() {
+    let (sender, _sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (_validator, fullnode, object_basics) =
+        init_state_with_ids_and_object_basics_with_fullnode(vec![(sender, gas_object_id)]).await;
+
+    // `object_basics::Object` values, passed as pure bytes
+    let object1_id = ObjectID::random();
+    let object2_id = ObjectID::random();
+    let test_object1_bytes = bcs::to_bytes(&(object1_id, 16_u64)).unwrap();
+    let test_object2_bytes = bcs::to_bytes(&(object2_id, 17_u64)).unwrap();
+    let move_call = |function: &str| {
+        Command::MoveCall(Box::new(ProgrammableMoveCall {
+            package: object_basics.0,
+            module: Identifier::new("object_basics").unwrap(),
+            function: Identifier::new(function).unwrap(),
+            type_arguments: vec![],
+            arguments: vec![Argument::Input(0), Argument::Input(1)],
+        }))
+    };
+    let pt = ProgrammableTransaction {
+        inputs: vec![
+            CallArg::Pure(test_object1_bytes),
+            CallArg::Pure(test_object2_bytes),
+        ],
+        commands: vec![move_call("update"), move_call("add_ofield")],
+    };
+    let kind = TransactionKind::programmable(pt);
+
+    // tracing is opt-in
+    let DevInspectResults { trace, .. } = fullnode
+        .dev_inspect_transaction_block(sender, kind.clone(), None, None, None, None, None, None)
+        .await
+        .unwrap();
+    assert!(trace.is_none());
+
+    let DevInspectResults { error, trace, .. } = fullnode
+        .dev_inspect_transaction_block_with_overrides(
+            sender,
+            kind,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(true),
+        )
+        .await
+        .unwrap();
+    assert!(error.is_none(), "unexpected error: {error:?}");
+    let trace = trace.unwrap();
+    assert_eq!(trace.commands.len(), 2);
+
+    // update
+    let update = &trace.commands[0];
+    assert_eq!(update.command_index, 0);
+    assert!(update.error.is_none());
+    assert!(update.object_changes.is_empty());
+    assert!(matches!(
+        update.steps.first(),
+        Some(TraceStep::Call { depth: 0, function, arguments, .. })
+            if *function == format!("{}::object_basics::update", object_basics.0)
+                && arguments.len() == 2
+    ));
+    assert!(update.steps.iter().any(|step| matches!(
+        step,
+        TraceStep::Event { depth: 1, event_type, contents }
+            if event_type.ends_with("::object_basics::NewValueEvent") && contents == "{ 17 }"
+    )));
+    assert!(matches!(
+        update.steps.last(),
+        Some(TraceStep::Return { depth: 0, .. })
+    ));
+
+    // add_ofield
+    let add_ofield = &trace.commands[1];
+    assert_eq!(add_ofield.command_index, 1);
+    assert!(add_ofield.error.is_none());
+    assert!(add_ofield.steps.iter().any(|step| matches!(
+        step,
+        TraceStep::DynamicFieldWrite { parent, .. } if *parent == object1_id
+    )));
+}
+
 #[tokio::test]
 async fn test_dev_inspect_gas_price() {
     let (_, fullnode, _object_basics) =
@@ -902,6 +992,99 @@ async fn test_dev_inspect_gas_price() {
     );
 }
 
+#[tokio::test]
+async fn test_dev_inspect_state_overrides() {
+    let (_, fullnode, _object_basics) =
+        init_state_with_ids_and_object_basics_with_fullnode(vec![]).await;
+
+    let sender = IotaAddress::random_for_testing_only();
+    let pt = {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let clock = builder
+            .obj(ObjectArg::SharedObject {
+                id: IOTA_CLOCK_OBJECT_ID,
+                initial_shared_version: IOTA_CLOCK_OBJECT_SHARED_VERSION,
+                mutable: false,
+            })
+            .unwrap();
+        builder.programmable_move_call(
+            IOTA_FRAMEWORK_PACKAGE_ID,
+            ident_str!("clock").to_owned(),
+            ident_str!("timestamp_ms").to_owned(),
+            vec![],
+            vec![clock],
+        );
+        builder.finish()
+    };
+    let kind = TransactionKind::programmable(pt);
+    let timestamp_ms = 1_700_000_000_000;
+    let current_epoch = fullnode.epoch_store_for_testing().epoch();
+    let DevInspectResults {
+        effects, results, ..
+    } = fullnode
+        .dev_inspect_transaction_block_with_overrides(
+            sender,
+            kind.clone(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(StateOverrides {
+                clock_timestamp_ms: Some(timestamp_ms),
+                epoch: Some(current_epoch + 10),
+                ..Default::default()
+            }),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(effects.executed_epoch(), current_epoch + 10);
+    let (return_value, _) = &results.unwrap()[0].return_values[0];
+    assert_eq!(bcs::from_bytes::<u64>(return_value).unwrap(), timestamp_ms);
+
+    // Overrides must not be persisted.
+    let DevInspectResults { results, .. } = fullnode
+        .dev_inspect_transaction_block(sender, kind, None, None, None, None, None, None)
+        .await
+        .unwrap();
+    let (return_value, _) = &results.unwrap()[0].return_values[0];
+    assert_ne!(bcs::from_bytes::<u64>(return_value).unwrap(), timestamp_ms);
+
+    // Overriding an object with contents of another object is rejected.
+    let pt = ProgrammableTransactionBuilder::new().finish();
+    let error = fullnode
+        .dev_inspect_transaction_block_with_overrides(
+            sender,
+            TransactionKind::programmable(pt),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(StateOverrides {
+                objects: vec![ObjectOverride::Contents {
+                    object_id: IOTA_CLOCK_OBJECT_ID,
+                    contents: bcs::to_bytes(&(IOTA_SYSTEM_STATE_OBJECT_ID, 0u64)).unwrap(),
+                }],
+                ..Default::default()
+            }),
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            UserInputError::try_from(error.clone()).unwrap(),
+            UserInputError::InvalidStateOverride { .. }
+        ),
+        "{}",
+        error
+    );
+}
+
 fn check_coin_value(actual_value: &[u8], actual_type: &IotaTypeTag, expected_value: u64) {
     let actual_type: TypeTag = actual_type.clone().try_into().unwrap();
     assert_eq!(actual_type, TypeTag::Struct(Box::new(GasCoin::type_())));
@@ -1157,6 +1340,78 @@ async fn test_dry_run_dev_inspect_max_gas_version() {
     assert_eq!(effects.status(), &IotaExecutionStatus::Success);
 }
 
+#[tokio::test]
+async fn test_dry_run_dev_inspect_gas_breakdown() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (validator, fullnode) = init_state_validator_with_fullnode().await;
+    let (validator, object_basics) = publish_object_basics(validator).await;
+    let (fullnode, _object_basics) = publish_object_basics(fullnode).await;
+    let gas_object = Object::with_id_owner_for_testing(gas_object_id, sender);
+    let gas_object_ref = gas_object.compute_object_reference();
+    validator.insert_genesis_object(gas_object.clone()).await;
+    fullnode.insert_genesis_object(gas_object).await;
+    let rgp = fullnode.reference_gas_price_for_testing().unwrap();
+    let create = Command::MoveCall(Box::new(ProgrammableMoveCall {
+        package: object_basics.0,
+        module: Identifier::new("object_basics").unwrap(),
+        function: Identifier::new("create").unwrap(),
+        type_arguments: vec![],
+        arguments: vec![Argument::Input(0), Argument::Input(1)],
+    }));
+    let pt = ProgrammableTransaction {
+        inputs: vec![
+            CallArg::Pure(bcs::to_bytes(&(32_u64)).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&sender).unwrap()),
+        ],
+        commands: vec![create.clone(), create],
+    };
+    let function = format!("{}::object_basics::create", object_basics.0);
+    let check_breakdown = |gas_breakdown: Option<GasBreakdown>| {
+        let gas_breakdown = gas_breakdown.unwrap();
+        assert_eq!(gas_breakdown.commands.len(), 2);
+        for (i, command) in gas_breakdown.commands.iter().enumerate() {
+            assert_eq!(command.command_index as usize, i);
+            assert_eq!(command.command, "MoveCall");
+            assert_eq!(command.function.as_ref(), Some(&function));
+            assert!(command.computation_cost > 0);
+        }
+        assert_eq!(gas_breakdown.move_functions.len(), 1);
+        assert_eq!(gas_breakdown.move_functions[0].function, function);
+        assert_eq!(gas_breakdown.move_functions[0].calls, 2);
+        assert_eq!(
+            gas_breakdown.move_functions[0].computation_cost,
+            gas_breakdown
+                .commands
+                .iter()
+                .map(|c| c.computation_cost)
+                .sum::<u64>()
+        );
+    };
+
+    // dev inspect
+    let kind = TransactionKind::programmable(pt.clone());
+    let DevInspectResults { gas_breakdown, .. } = fullnode
+        .dev_inspect_transaction_block(sender, kind, Some(rgp), None, None, None, None, None)
+        .await
+        .unwrap();
+    check_breakdown(gas_breakdown);
+
+    // dry run
+    let data = TransactionData::new_programmable(
+        sender,
+        vec![gas_object_ref],
+        pt,
+        rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS,
+        rgp,
+    );
+    let transaction = to_sender_signed_transaction(data.clone(), &sender_key);
+    let digest = *transaction.digest();
+    let DryRunTransactionBlockResponse { gas_breakdown, .. } =
+        fullnode.dry_exec_transaction(data, digest).await.unwrap().0;
+    check_breakdown(gas_breakdown);
+}
+
 #[tokio::test]
 async fn test_handle_transfer_transaction_bad_signature() {
     let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
@@ -5807,6 +6062,7 @@ async fn test_consensus_handler_per_object_congestion_control(
         PerObjectCongestionControlMode::None => unreachable!(),
         PerObjectCongestionControlMode::TotalGasBudget => 5,
         PerObjectCongestionControlMode::TotalTxCount => 2,
+        PerObjectCongestionControlMode::ExecutionTimeEstimate => unreachable!(),
     };
     let gas_objects_commit_1 = create_gas_objects(5 + non_congested_tx_count, sender);
     let gas_objects_commit_2 = create_gas_objects(non_congested_tx_count, sender);
@@ -5828,6 +6084,7 @@ async fn test_consensus_handler_per_object_congestion_control(
             protocol_config
                 .set_max_accumulated_txn_cost_per_object_in_mysticeti_commit_for_testing(2);
         }
+        PerObjectCongestionControlMode::ExecutionTimeEstimate => unreachable!(),
     }
     protocol_config.set_max_deferral_rounds_for_congestion_control_for_testing(1000); // Set to a large number so that we don't hit this limit.
     let authority = TestAuthorityBuilder::new()
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use iota_types::{
-    base_types::{dbg_addr, dbg_object_id},
+    base_types::{dbg_addr, dbg_object_id, random_object_ref},
     messages_grpc::LayoutGenerationOption,
 };
 
@@ -35,3 +35,15 @@ async fn test_simple_request() {
 
     client.handle_object_info_request(req).await.unwrap();
 }
+
+#[test]
+fn test_sponsored_gas_coins_are_not_tallied() {
+    let sender = dbg_addr(1);
+    let gas = random_object_ref();
+    let data = TransactionData::new_transfer_iota(dbg_addr(2), sender, None, gas, 1_000_000, 1_000);
+    assert_eq!(tallied_gas_coins(&data), vec![gas.0]);
+
+    let mut sponsored = data;
+    sponsored.gas_data_mut().owner = dbg_addr(3);
+    assert!(tallied_gas_coins(&sponsored).is_empty());
+}
@@ -14,6 +14,7 @@ use iota_swarm_config::{
     network_config::NetworkConfig,
 };
 use iota_test_transaction_builder::TestTransactionBuilder;
+use iota_transaction_checks::deny::DenyListEntry;
 use iota_types::{
     base_types::{IotaAddress, ObjectID, ObjectRef},
     effects::TransactionEffectsAPI,
@@ -447,6 +448,143 @@ async fn test_package_denied() {
     assert!(result.is_ok());
 }
 
+#[tokio::test]
+async fn test_dynamic_deny_list() {
+    let (network_config, state) = setup_test(TransactionDenyConfigBuilder::new().build()).await;
+    let accounts = get_accounts_and_coins(&network_config, &state);
+    let deny_list = state.dynamic_deny_list();
+
+    // Deny the gas coin of accounts[0] and the address of accounts[1] at runtime.
+    let obj_ref = accounts[0].2[0];
+    assert!(deny_list.add(DenyListEntry::Object(obj_ref.0)).unwrap());
+    assert!(!deny_list.add(DenyListEntry::Object(obj_ref.0)).unwrap());
+    assert!(
+        deny_list
+            .add(DenyListEntry::Address(accounts[1].0))
+            .unwrap()
+    );
+    assert_denied(&transfer_with_account(&accounts[0], &accounts[0], &state).await);
+    assert_denied(&transfer_with_account(&accounts[2], &accounts[1], &state).await);
+
+    // The entries are persisted across restarts.
+    let state = reload_state_with_new_deny_config(
+        &network_config,
+        state,
+        TransactionDenyConfigBuilder::new().build(),
+    )
+    .await;
+    assert_denied(&transfer_with_account(&accounts[0], &accounts[0], &state).await);
+    assert_denied(&transfer_with_account(&accounts[2], &accounts[1], &state).await);
+
+    // Removed entries are no longer denied.
+    let deny_list = state.dynamic_deny_list();
+    assert!(deny_list.remove(DenyListEntry::Object(obj_ref.0)).unwrap());
+    assert!(!deny_list.remove(DenyListEntry::Object(obj_ref.0)).unwrap());
+    assert!(
+        deny_list
+            .remove(DenyListEntry::Address(accounts[1].0))
+            .unwrap()
+    );
+    assert!(deny_list.entries().objects.is_empty());
+    assert!(
+        transfer_with_account(&accounts[0], &accounts[0], &state)
+            .await
+            .is_ok()
+    );
+    assert!(
+        transfer_with_account(&accounts[2], &accounts[1], &state)
+            .await
+            .is_ok()
+    );
+}
+
+#[tokio::test]
+async fn test_package_family_denied() {
+    let (network_config, state) = setup_test(TransactionDenyConfigBuilder::new().build()).await;
+    let accounts = get_accounts_and_coins(&network_config, &state);
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // Publish c and b, where b depends on c, and upgrade c to c'.
+    let (package_c, cap_c) = publish_package_on_single_authority(
+        &path.join("src/unit_tests/data/package_deny/c"),
+        accounts[0].0,
+        &accounts[0].1,
+        accounts[0].2[0],
+        [("c", ObjectID::ZERO)],
+        vec![],
+        &state,
+    )
+    .await
+    .unwrap();
+    let (package_b, _) = publish_package_on_single_authority(
+        &path.join("src/unit_tests/data/package_deny/b"),
+        accounts[0].0,
+        &accounts[0].1,
+        accounts[0].2[1],
+        [("b", ObjectID::ZERO), ("c", package_c)],
+        vec![package_c],
+        &state,
+    )
+    .await
+    .unwrap();
+    let package_c_prime = upgrade_package_on_single_authority(
+        &path.join("src/unit_tests/data/package_deny/c"),
+        accounts[0].0,
+        &accounts[0].1,
+        accounts[0].2[2],
+        package_c,
+        cap_c,
+        [("c", ObjectID::ZERO)],
+        vec![],
+        &state,
+    )
+    .await
+    .unwrap();
+
+    // Deny the upgrade family of c through the node config.
+    let state = reload_state_with_new_deny_config(
+        &network_config,
+        state,
+        TransactionDenyConfigBuilder::new()
+            .add_denied_package_family(package_c)
+            .build(),
+    )
+    .await;
+
+    // Both c and c' are denied, unlike when only the package ID of c is denied.
+    let result =
+        handle_move_call_transaction(&state, package_c, "c", "c", vec![], &accounts[0], 3).await;
+    assert_denied(&result);
+    let result =
+        handle_move_call_transaction(&state, package_c_prime, "c", "c", vec![], &accounts[0], 4)
+            .await;
+    assert_denied(&result);
+
+    // Calling modules in package b fails too as it depends on c.
+    let result =
+        handle_move_call_transaction(&state, package_b, "b", "b", vec![], &accounts[0], 5).await;
+    assert_denied(&result);
+
+    // The same applies to package families denied at runtime.
+    let state = reload_state_with_new_deny_config(
+        &network_config,
+        state,
+        TransactionDenyConfigBuilder::new().build(),
+    )
+    .await;
+    let result =
+        handle_move_call_transaction(&state, package_c_prime, "c", "c", vec![], &accounts[0], 6)
+            .await;
+    assert!(result.is_ok());
+    state
+        .dynamic_deny_list()
+        .add(DenyListEntry::PackageFamily(package_c))
+        .unwrap();
+    let result =
+        handle_move_call_transaction(&state, package_c_prime, "c", "c", vec![], &accounts[0], 7)
+            .await;
+    assert_denied(&result);
+}
+
 #[tokio::test]
 async fn test_certificate_deny() {
     let (network_config, state) = setup_test(TransactionDenyConfig::default()).await;
@@ -3,6 +3,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 module iota::ed25519 {
+    /// The number of signatures, public keys and messages in a batch differ.
+    const ELengthMismatch: u64 = 0;
+
+    #[allow(unused_const)]
+    const ENotSupported: u64 = 1;
+
     /// @param signature: 32-byte signature that is a point on the Ed25519 elliptic curve.
     /// @param public_key: 32-byte signature that is a point on the Ed25519 elliptic curve.
     /// @param msg: The message that we test the signature against.
@@ -10,4 +16,29 @@ module iota::ed25519 {
     /// If the signature is a valid Ed25519 signature of the message and public key, return true.
     /// Otherwise, return false.
     public native fun ed25519_verify(signature: &vector<u8>, public_key: &vector<u8>, msg: &vector<u8>): bool;
+
+    /// @param signatures: Ed25519 signatures, where `signatures[i]` signs `msgs[i]` under `public_keys[i]`.
+    /// @param public_keys: 32-byte Ed25519 public keys.
+    /// @param msgs: The messages that we test the signatures against.
+    ///
+    /// If all signatures are valid in the sense of `ed25519_verify`, return true. Otherwise, or if the batch is
+    /// empty, return false. Aborts with `ELengthMismatch` if the three vectors have different lengths.
+    ///
+    /// The signatures are verified together, which costs less per signature than calling `ed25519_verify` for each.
+    public fun ed25519_batch_verify(
+        signatures: &vector<vector<u8>>,
+        public_keys: &vector<vector<u8>>,
+        msgs: &vector<vector<u8>>,
+    ): bool {
+        let len = signatures.length();
+        assert!(public_keys.length() == len && msgs.length() == len, ELengthMismatch);
+        ed25519_batch_verify_internal(signatures, public_keys, msgs)
+    }
+
+    /// The internal function for `ed25519_batch_verify`.
+    native fun ed25519_batch_verify_internal(
+        signatures: &vector<vector<u8>>,
+        public_keys: &vector<vector<u8>>,
+        msgs: &vector<vector<u8>>,
+    ): bool;
 }
@@ -0,0 +1,16 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+module iota::schnorr {
+    #[allow(unused_const)]
+    const ENotSupported: u64 = 0;
+
+    /// @param signature: 64-byte BIP-340 Schnorr signature over secp256k1.
+    /// @param public_key: 32-byte x-only public key, as used in Bitcoin Taproot outputs.
+    /// @param msg: The message that we test the signature against. For Taproot spends this is the
+    /// 32-byte signature hash of the transaction.
+    ///
+    /// If the signature is a valid BIP-340 signature of the message and public key, return true.
+    /// Otherwise, return false.
+    public native fun bip340_verify(signature: &vector<u8>, public_key: &vector<u8>, msg: &vector<u8>): bool;
+}
@@ -43,4 +43,34 @@ module iota::ed25519_tests {
         let verify = ed25519::ed25519_verify(&sig, &pk, &msg);
         assert!(verify == false)
     }
+
+    #[test]
+    fun test_ed25519_batch_verify() {
+        let msg = x"315f5bdb76d078c43b8ac0064e4a0164612b1fce77c869345bfc94c75894edd3";
+        let pk = x"cc62332e34bb2d5cd69f60efbb2a36cb916c7eb458301ea36636c4dbb012bd88";
+        let sig = x"cce72947906dbae4c166fc01fd096432784032be43db540909bc901dbc057992b4d655ca4f4355cf0868e1266baacf6919902969f063e74162f8f04bc4056105";
+
+        let verify = ed25519::ed25519_batch_verify(&vector[sig, sig], &vector[pk, pk], &vector[msg, msg]);
+        assert!(verify == true);
+
+        let other_msg = x"415f5bdb76d078c43b8ac0064e4a0164612b1fce77c869345bfc94c75894edd3";
+        let verify = ed25519::ed25519_batch_verify(&vector[sig, sig], &vector[pk, pk], &vector[msg, other_msg]);
+        assert!(verify == false);
+
+        let verify = ed25519::ed25519_batch_verify(&vector[sig, sig], &vector[pk, x""], &vector[msg, msg]);
+        assert!(verify == false);
+
+        let verify = ed25519::ed25519_batch_verify(&vector[], &vector[], &vector[]);
+        assert!(verify == false);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = ed25519::ELengthMismatch)]
+    fun test_ed25519_batch_verify_length_mismatch() {
+        let msg = x"315f5bdb76d078c43b8ac0064e4a0164612b1fce77c869345bfc94c75894edd3";
+        let pk = x"cc62332e34bb2d5cd69f60efbb2a36cb916c7eb458301ea36636c4dbb012bd88";
+        let sig = x"cce72947906dbae4c166fc01fd096432784032be43db540909bc901dbc057992b4d655ca4f4355cf0868e1266baacf6919902969f063e74162f8f04bc4056105";
+
+        ed25519::ed25519_batch_verify(&vector[sig, sig], &vector[pk], &vector[msg, msg]);
+    }
 }
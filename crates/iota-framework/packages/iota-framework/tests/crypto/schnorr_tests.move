@@ -0,0 +1,44 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+#[test_only]
+module iota::schnorr_tests {
+    use iota::schnorr;
+
+    #[test]
+    fun test_bip340_valid_sig() {
+        // Test vector 0 from https://github.com/bitcoin/bips/blob/master/bip-0340/test-vectors.csv
+        let msg = x"0000000000000000000000000000000000000000000000000000000000000000";
+        let pk = x"f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9";
+        let sig = x"e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca821525f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0";
+
+        let verify = schnorr::bip340_verify(&sig, &pk, &msg);
+        assert!(verify == true);
+    }
+
+    #[test]
+    fun test_bip340_invalid_sig() {
+        let msg = x"0000000000000000000000000000000000000000000000000000000000000000";
+        let pk = x"f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9";
+        let invalid_sig = x"e807831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca821525f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0";
+
+        let verify = schnorr::bip340_verify(&invalid_sig, &pk, &msg);
+        assert!(verify == false);
+
+        let sig = x"e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca821525f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0";
+        let other_msg = x"0100000000000000000000000000000000000000000000000000000000000000";
+
+        let verify = schnorr::bip340_verify(&sig, &pk, &other_msg);
+        assert!(verify == false);
+    }
+
+    #[test]
+    fun test_bip340_invalid_pubkey() {
+        let msg = x"0000000000000000000000000000000000000000000000000000000000000000";
+        let pk = x"";
+        let sig = x"e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca821525f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0";
+
+        let verify = schnorr::bip340_verify(&sig, &pk, &msg);
+        assert!(verify == false)
+    }
+}
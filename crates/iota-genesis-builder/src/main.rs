@@ -4,13 +4,18 @@
 //! Creating a stardust objects snapshot out of a Hornet snapshot.
 //! TIP that defines the Hornet snapshot file format:
 //! https://github.com/iotaledger/tips/blob/main/tips/TIP-0035/tip-0035.md
-use std::{collections::BTreeMap, fs::File, io::BufWriter};
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{BufReader, BufWriter},
+};
 
 use anyhow::{Result, anyhow};
 use clap::{Parser, Subcommand};
 use iota_genesis_builder::{
     OBJECT_SNAPSHOT_FILE_PATH,
     stardust::{
+        inspect::{OutputObjectsDiff, OutputObjectsMap, SnapshotStatistics},
         migration::{Migration, MigrationTargetNetwork},
         parse::HornetSnapshotParser,
         types::{address_swap_map::AddressSwapMap, output_header::OutputHeader},
@@ -24,7 +29,7 @@ use iota_sdk::types::block::{
     },
 };
 use iota_types::{stardust::coin_type::CoinType, timelock::timelock::is_vested_reward};
-use tracing::Level;
+use tracing::{Level, info};
 use tracing_subscriber::FmtSubscriber;
 
 #[derive(Parser, Debug)]
@@ -49,6 +54,29 @@ enum Snapshot {
         address_swap_map_path: Option<String>,
         #[clap(long, value_parser = clap::value_parser!(MigrationTargetNetwork), help = "Target network for migration")]
         target_network: MigrationTargetNetwork,
+        #[clap(
+            long,
+            help = "Path of a JSON file where the objects created for every migrated output are written"
+        )]
+        output_objects_map_path: Option<String>,
+    },
+    #[clap(about = "Report statistics about the outputs of a Hornet full-snapshot file")]
+    Inspect {
+        #[clap(long, help = "Path to the Hornet full-snapshot file")]
+        snapshot_path: String,
+        #[clap(
+            long,
+            default_value_t = 20,
+            help = "Number of addresses holding the largest amounts to report"
+        )]
+        top_addresses: usize,
+    },
+    #[clap(about = "Compare the output objects maps of two migration runs")]
+    Diff {
+        #[clap(long, help = "Path to the output objects map of the old migration run")]
+        old: String,
+        #[clap(long, help = "Path to the output objects map of the new migration run")]
+        new: String,
     },
 }
 
@@ -61,18 +89,32 @@ fn main() -> Result<()> {
 
     // Parse the CLI arguments
     let cli = Cli::parse();
-    let (snapshot_path, address_swap_map_path, target_network, coin_type) = match cli.snapshot {
-        Snapshot::Iota {
-            snapshot_path,
-            address_swap_map_path,
-            target_network,
-        } => (
-            snapshot_path,
-            address_swap_map_path,
-            target_network,
-            CoinType::Iota,
-        ),
-    };
+    let (snapshot_path, address_swap_map_path, target_network, output_objects_map_path, coin_type) =
+        match cli.snapshot {
+            Snapshot::Iota {
+                snapshot_path,
+                address_swap_map_path,
+                target_network,
+                output_objects_map_path,
+            } => (
+                snapshot_path,
+                address_swap_map_path,
+                target_network,
+                output_objects_map_path,
+                CoinType::Iota,
+            ),
+            Snapshot::Inspect {
+                snapshot_path,
+                top_addresses,
+            } => {
+                return inspect_snapshot(
+                    snapshot_path,
+                    top_addresses,
+                    cli.disable_global_snapshot_verification,
+                );
+            }
+            Snapshot::Diff { old, new } => return diff_output_objects_maps(old, new),
+        };
 
     // Start the Hornet snapshot parser
     let mut snapshot_parser = if cli.disable_global_snapshot_verification {
@@ -200,7 +242,15 @@ fn main() -> Result<()> {
                 Ok::<_, anyhow::Error>((header, output))
             });
             itertools::process_results(merged_outputs, |outputs| {
-                migration.run(outputs, object_snapshot_writer)
+                if let Some(path) = output_objects_map_path {
+                    migration.run_with_output_objects_map(
+                        outputs,
+                        object_snapshot_writer,
+                        BufWriter::new(File::create(path)?),
+                    )
+                } else {
+                    migration.run(outputs, object_snapshot_writer)
+                }
             })??;
         }
     }
@@ -208,6 +258,41 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Stream the outputs of a snapshot and print statistics about them to stdout.
+fn inspect_snapshot(
+    snapshot_path: String,
+    top_addresses: usize,
+    disable_global_snapshot_verification: bool,
+) -> Result<()> {
+    let mut snapshot_parser = if disable_global_snapshot_verification {
+        HornetSnapshotParser::new::<false>(File::open(snapshot_path)?)?
+    } else {
+        HornetSnapshotParser::new::<true>(File::open(snapshot_path)?)?
+    };
+    let mut statistics = SnapshotStatistics::new(snapshot_parser.target_milestone_timestamp());
+    for res in snapshot_parser.outputs() {
+        let (header, output) = res?;
+        statistics.add_output(&header, &output)?;
+    }
+    serde_json::to_writer_pretty(std::io::stdout(), &statistics.finish(top_addresses))?;
+    Ok(())
+}
+
+/// Compare two output objects maps and print the differences to stdout.
+fn diff_output_objects_maps(old: String, new: String) -> Result<()> {
+    let old = OutputObjectsMap::from_reader(BufReader::new(File::open(old)?))?;
+    let new = OutputObjectsMap::from_reader(BufReader::new(File::open(new)?))?;
+    let diff = OutputObjectsDiff::new(old, new);
+    info!(
+        "{} outputs unchanged, {} removed, {} added, {} changed",
+        diff.unchanged_count,
+        diff.removed.len(),
+        diff.added.len(),
+        diff.changed.len()
+    );
+    diff.write(std::io::stdout())
+}
+
 struct OutputHeaderWithBalance {
     output_header: OutputHeader,
     balance: u64,
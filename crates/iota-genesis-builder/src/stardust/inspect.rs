@@ -0,0 +1,387 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tools to inspect a Stardust snapshot and the outcome of its migration.
+//!
+//! * [`SnapshotStatistics`] aggregates the outputs of a snapshot by output
+//!   type, address, native token and unlock condition.
+//! * [`OutputObjectsMap`] records, for every migrated output, the objects that
+//!   were created for it.
+//! * [`OutputObjectsDiff`] compares two [`OutputObjectsMap`]s produced by
+//!   different migration runs.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::{Read, Write},
+};
+
+use anyhow::Result;
+use iota_sdk::{
+    U256,
+    types::block::{
+        address::Address,
+        output::{Output, OutputId, TokenId, unlock_condition::UnlockCondition},
+    },
+};
+use iota_types::{
+    base_types::{IotaAddress, ObjectID},
+    digests::ObjectDigest,
+    in_memory_storage::InMemoryStorage,
+    object::Owner,
+    stardust::stardust_to_iota_address,
+    timelock::timelock::is_vested_reward,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
+
+use crate::stardust::{
+    migration::verification::created_objects::CreatedObjects, types::output_header::OutputHeader,
+};
+
+/// Number of outputs and the amount of base tokens they hold.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputCount {
+    pub count: u64,
+    pub amount: u64,
+}
+
+impl OutputCount {
+    fn add(&mut self, amount: u64) {
+        self.count += 1;
+        self.amount = self.amount.saturating_add(amount);
+    }
+}
+
+/// Number of outputs holding a native token and the total amount they hold.
+#[serde_as]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NativeTokenCount {
+    pub count: u64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub amount: U256,
+}
+
+/// Unlock condition occurrences across the outputs of a snapshot.
+///
+/// The `timelocked` and `expired` counters are evaluated against the target
+/// milestone timestamp of the snapshot.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnlockConditionCount {
+    pub address: u64,
+    pub storage_deposit_return: u64,
+    pub timelock: u64,
+    pub expiration: u64,
+    pub state_controller_address: u64,
+    pub governor_address: u64,
+    pub immutable_alias_address: u64,
+    pub timelocked: u64,
+    pub expired: u64,
+}
+
+/// Statistics over the outputs of a Stardust snapshot.
+///
+/// Outputs are fed one by one through [`SnapshotStatistics::add_output`], so
+/// a snapshot can be inspected while streaming it from the parser. Amounts
+/// are reported as found in the snapshot, without any scaling.
+#[serde_as]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotStatistics {
+    pub target_milestone_timestamp: u32,
+    pub total: OutputCount,
+    pub by_output_type: BTreeMap<String, OutputCount>,
+    pub vested_rewards: OutputCount,
+    pub unlock_conditions: UnlockConditionCount,
+    #[serde_as(as = "BTreeMap<DisplayFromStr, _>")]
+    pub native_tokens: BTreeMap<TokenId, NativeTokenCount>,
+    /// Number of distinct addresses owning at least one output.
+    pub address_count: u64,
+    /// The addresses holding the largest amounts, in descending order.
+    pub top_addresses: Vec<AddressCount>,
+    #[serde(skip)]
+    addresses: HashMap<IotaAddress, OutputCount>,
+}
+
+/// Outputs owned by an address, see [`SnapshotStatistics::top_addresses`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressCount {
+    pub address: IotaAddress,
+    #[serde(flatten)]
+    pub outputs: OutputCount,
+}
+
+impl SnapshotStatistics {
+    pub fn new(target_milestone_timestamp: u32) -> Self {
+        Self {
+            target_milestone_timestamp,
+            ..Default::default()
+        }
+    }
+
+    /// Account for a single output of the snapshot.
+    pub fn add_output(&mut self, header: &OutputHeader, output: &Output) -> Result<()> {
+        let amount = output.amount();
+        self.total.add(amount);
+        self.by_output_type
+            .entry(output_type(output).to_owned())
+            .or_default()
+            .add(amount);
+        if let Output::Basic(basic) = output {
+            if is_vested_reward(header.output_id(), basic) {
+                self.vested_rewards.add(amount);
+            }
+        }
+
+        if let Some(unlock_conditions) = output.unlock_conditions() {
+            let count = &mut self.unlock_conditions;
+            for unlock_condition in unlock_conditions.iter() {
+                match unlock_condition {
+                    UnlockCondition::Address(_) => count.address += 1,
+                    UnlockCondition::StorageDepositReturn(_) => count.storage_deposit_return += 1,
+                    UnlockCondition::Timelock(timelock) => {
+                        count.timelock += 1;
+                        if timelock.timestamp() > self.target_milestone_timestamp {
+                            count.timelocked += 1;
+                        }
+                    }
+                    UnlockCondition::Expiration(expiration) => {
+                        count.expiration += 1;
+                        if expiration.timestamp() <= self.target_milestone_timestamp {
+                            count.expired += 1;
+                        }
+                    }
+                    UnlockCondition::StateControllerAddress(_) => {
+                        count.state_controller_address += 1
+                    }
+                    UnlockCondition::GovernorAddress(_) => count.governor_address += 1,
+                    UnlockCondition::ImmutableAliasAddress(_) => count.immutable_alias_address += 1,
+                }
+            }
+        }
+
+        if let Some(native_tokens) = output.native_tokens() {
+            for native_token in native_tokens.iter() {
+                let entry = self
+                    .native_tokens
+                    .entry(*native_token.token_id())
+                    .or_default();
+                entry.count += 1;
+                entry.amount = entry.amount.saturating_add(native_token.amount());
+            }
+        }
+
+        if let Some(address) = owning_address(output) {
+            self.addresses
+                .entry(stardust_to_iota_address(address)?)
+                .or_default()
+                .add(amount);
+        }
+        Ok(())
+    }
+
+    /// Finalize the statistics, keeping the `top` addresses holding the
+    /// largest amounts.
+    pub fn finish(mut self, top: usize) -> Self {
+        let mut addresses = std::mem::take(&mut self.addresses)
+            .into_iter()
+            .map(|(address, outputs)| AddressCount { address, outputs })
+            .collect::<Vec<_>>();
+        self.address_count = addresses.len() as u64;
+        addresses.sort_by(|a, b| {
+            b.outputs
+                .amount
+                .cmp(&a.outputs.amount)
+                .then(a.address.cmp(&b.address))
+        });
+        addresses.truncate(top);
+        self.top_addresses = addresses;
+        self
+    }
+}
+
+fn output_type(output: &Output) -> &'static str {
+    match output {
+        Output::Treasury(_) => "treasury",
+        Output::Basic(_) => "basic",
+        Output::Alias(_) => "alias",
+        Output::Foundry(_) => "foundry",
+        Output::Nft(_) => "nft",
+    }
+}
+
+/// The address the migrated objects of an output will belong to, before any
+/// address swap takes place.
+fn owning_address(output: &Output) -> Option<Address> {
+    match output {
+        Output::Basic(basic) => Some(*basic.address()),
+        Output::Nft(nft) => Some(*nft.address()),
+        Output::Alias(alias) => Some(*alias.governor_address()),
+        Output::Foundry(foundry) => Some(Address::from(*foundry.alias_address())),
+        Output::Treasury(_) => None,
+    }
+}
+
+/// An object created by the migration of an output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigratedObject {
+    pub id: ObjectID,
+    pub digest: ObjectDigest,
+    pub owner: Owner,
+    /// The type of the object, or `None` for packages.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_: Option<String>,
+}
+
+/// The objects created for every migrated output, keyed by [`OutputId`].
+#[serde_as]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct OutputObjectsMap(
+    #[serde_as(as = "BTreeMap<DisplayFromStr, _>")] pub BTreeMap<OutputId, Vec<MigratedObject>>,
+);
+
+impl OutputObjectsMap {
+    /// Resolve the created objects of every output against the migration
+    /// store.
+    pub(crate) fn new(
+        output_objects_map: &HashMap<OutputId, CreatedObjects>,
+        store: &InMemoryStorage,
+    ) -> Result<Self> {
+        output_objects_map
+            .iter()
+            .map(|(output_id, created_objects)| {
+                let objects = created_objects
+                    .object_ids()
+                    .map(|id| {
+                        let object = store
+                            .get_object(id)
+                            .ok_or_else(|| anyhow::anyhow!("missing object {id}"))?;
+                        Ok(MigratedObject {
+                            id: *id,
+                            digest: object.digest(),
+                            owner: object.owner,
+                            type_: object.struct_tag().map(|tag| tag.to_canonical_string(true)),
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok((*output_id, objects))
+            })
+            .collect::<Result<_>>()
+            .map(Self)
+    }
+
+    pub fn from_reader(reader: impl Read) -> Result<Self> {
+        Ok(serde_json::from_reader(reader)?)
+    }
+
+    pub fn write(&self, writer: impl Write) -> Result<()> {
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+}
+
+/// The objects created for an output in two different migration runs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangedOutput {
+    pub old: Vec<MigratedObject>,
+    pub new: Vec<MigratedObject>,
+}
+
+/// Differences between two [`OutputObjectsMap`]s.
+#[serde_as]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputObjectsDiff {
+    pub unchanged_count: u64,
+    #[serde_as(as = "BTreeMap<DisplayFromStr, _>")]
+    pub removed: BTreeMap<OutputId, Vec<MigratedObject>>,
+    #[serde_as(as = "BTreeMap<DisplayFromStr, _>")]
+    pub added: BTreeMap<OutputId, Vec<MigratedObject>>,
+    #[serde_as(as = "BTreeMap<DisplayFromStr, _>")]
+    pub changed: BTreeMap<OutputId, ChangedOutput>,
+}
+
+impl OutputObjectsDiff {
+    pub fn new(old: OutputObjectsMap, mut new: OutputObjectsMap) -> Self {
+        let mut diff = Self::default();
+        for (output_id, old_objects) in old.0 {
+            match new.0.remove(&output_id) {
+                None => {
+                    diff.removed.insert(output_id, old_objects);
+                }
+                Some(new_objects) if new_objects == old_objects => diff.unchanged_count += 1,
+                Some(new_objects) => {
+                    diff.changed.insert(output_id, ChangedOutput {
+                        old: old_objects,
+                        new: new_objects,
+                    });
+                }
+            }
+        }
+        diff.added = new.0;
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty() && self.added.is_empty() && self.changed.is_empty()
+    }
+
+    pub fn write(&self, writer: impl Write) -> Result<()> {
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iota_sdk::types::block::payload::transaction::TransactionId;
+
+    use super::*;
+
+    fn object(id: ObjectID, owner: IotaAddress) -> MigratedObject {
+        MigratedObject {
+            id,
+            digest: ObjectDigest::MIN,
+            owner: Owner::AddressOwner(owner),
+            type_: None,
+        }
+    }
+
+    #[test]
+    fn output_objects_diff() {
+        let [unchanged, changed, removed, added] =
+            std::array::from_fn(|i| OutputId::new(TransactionId::new([i as u8; 32]), 0).unwrap());
+        let id = ObjectID::random();
+        let owner = IotaAddress::random_for_testing_only();
+        let swapped = IotaAddress::random_for_testing_only();
+
+        let old = OutputObjectsMap(BTreeMap::from([
+            (unchanged, vec![object(id, owner)]),
+            (changed, vec![object(id, owner)]),
+            (removed, vec![object(id, owner)]),
+        ]));
+        let new = OutputObjectsMap(BTreeMap::from([
+            (unchanged, vec![object(id, owner)]),
+            (changed, vec![object(id, swapped)]),
+            (added, vec![object(id, owner)]),
+        ]));
+
+        let diff = OutputObjectsDiff::new(old.clone(), new);
+        assert!(!diff.is_empty());
+        assert_eq!(diff.unchanged_count, 1);
+        assert_eq!(diff.removed.keys().collect::<Vec<_>>(), [&removed]);
+        assert_eq!(diff.added.keys().collect::<Vec<_>>(), [&added]);
+        assert_eq!(diff.changed[&changed].new, vec![object(id, swapped)]);
+
+        let mut bytes = vec![];
+        old.write(&mut bytes).unwrap();
+        let roundtrip = OutputObjectsMap::from_reader(bytes.as_slice()).unwrap();
+        assert!(OutputObjectsDiff::new(old, roundtrip).is_empty());
+    }
+}
@@ -26,6 +26,7 @@ use move_binary_format::file_format_common::VERSION_MAX;
 use tracing::info;
 
 use crate::stardust::{
+    inspect::OutputObjectsMap,
     migration::{
         MigrationTargetNetwork,
         executor::Executor,
@@ -150,13 +151,40 @@ impl Migration {
     /// * Validate the resulting object-based ledger state.
     /// * Create the snapshot file.
     pub fn run(
+        self,
+        outputs: impl IntoIterator<Item = (OutputHeader, Output)>,
+        writer: impl Write,
+    ) -> Result<()> {
+        self.run_inner(outputs, writer, None)
+    }
+
+    /// Run all stages of the migration, also writing the
+    /// [`OutputObjectsMap`] of the migrated outputs.
+    ///
+    /// See also `Self::run`.
+    pub fn run_with_output_objects_map(
+        self,
+        outputs: impl IntoIterator<Item = (OutputHeader, Output)>,
+        writer: impl Write,
+        mut output_objects_map_writer: impl Write,
+    ) -> Result<()> {
+        self.run_inner(outputs, writer, Some(&mut output_objects_map_writer))
+    }
+
+    fn run_inner(
         mut self,
         outputs: impl IntoIterator<Item = (OutputHeader, Output)>,
         writer: impl Write,
+        output_objects_map_writer: Option<&mut dyn Write>,
     ) -> Result<()> {
         info!("Starting the migration...");
         self.run_migration(outputs)?;
         info!("Migration ended.");
+        if let Some(output_objects_map_writer) = output_objects_map_writer {
+            info!("Writing output objects map...");
+            OutputObjectsMap::new(&self.output_objects_map, self.executor.store())?
+                .write(output_objects_map_writer)?;
+        }
         info!("Writing snapshot file...");
         create_snapshot(self.into_objects(), writer)?;
         info!("Snapshot file written.");
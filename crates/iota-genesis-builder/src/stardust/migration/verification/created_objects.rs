@@ -18,6 +18,18 @@ pub struct CreatedObjects {
 }
 
 impl CreatedObjects {
+    /// All the object ids recorded for the output.
+    pub fn object_ids(&self) -> impl Iterator<Item = &ObjectID> {
+        self.output
+            .iter()
+            .chain(&self.package)
+            .chain(&self.coin)
+            .chain(&self.native_token_coin)
+            .chain(self.native_tokens.iter().flatten())
+            .chain(&self.coin_manager)
+            .chain(&self.coin_manager_treasury_cap)
+    }
+
     pub fn output(&self) -> Result<&ObjectID> {
         self.output
             .as_ref()
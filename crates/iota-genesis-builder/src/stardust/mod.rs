@@ -5,6 +5,7 @@
 //! parsing Stardust UTXOs from a full-snapshot file, and converting
 //! them to the appropriate genesis objects.
 
+pub mod inspect;
 pub mod migration;
 pub mod native_token;
 pub mod parse;
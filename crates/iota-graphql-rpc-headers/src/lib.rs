@@ -6,3 +6,4 @@ use axum::http::HeaderName;
 
 pub static VERSION_HEADER: HeaderName = HeaderName::from_static("x-iota-rpc-version");
 pub static LIMITS_HEADER: HeaderName = HeaderName::from_static("x-iota-rpc-show-usage");
+pub static API_KEY_HEADER: HeaderName = HeaderName::from_static("x-iota-rpc-api-key");
@@ -2,7 +2,7 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::BTreeSet, fmt::Display, time::Duration};
+use std::{collections::BTreeSet, fmt::Display, path::PathBuf, time::Duration};
 
 use async_graphql::*;
 use fastcrypto_zkp::bn254::zk_login_api::ZkLoginEnv;
@@ -54,6 +54,9 @@ pub struct ServiceConfig {
     pub(crate) experiments: Experiments,
     pub(crate) background_tasks: BackgroundTasksConfig,
     pub(crate) zklogin: ZkLoginConfig,
+    pub(crate) api_keys: ApiKeysConfig,
+    pub(crate) persisted_queries: PersistedQueriesConfig,
+    pub(crate) response_cache: ResponseCacheConfig,
 }
 
 #[GraphQLConfig]
@@ -113,6 +116,59 @@ pub struct BackgroundTasksConfig {
     pub watermark_update_ms: u64,
 }
 
+/// Configuration for identifying clients by the API key they send in the
+/// `x-iota-rpc-api-key` header, and for budgeting the cost of their queries.
+/// Budgets are only enforced if at least one key or an anonymous budget is
+/// configured, and if the query limits checker is enabled.
+#[GraphQLConfig]
+#[derive(Default)]
+pub struct ApiKeysConfig {
+    /// The API keys accepted by the service. Requests with a key that is not
+    /// listed here are rejected.
+    pub(crate) keys: Vec<ApiKeyConfig>,
+    /// Budget of query cost per minute shared by all requests without an API
+    /// key. Such requests are not limited if this is not set.
+    pub(crate) anonymous_budget: Option<u64>,
+    /// File that the usage of the budgets is persisted to, so that it survives
+    /// restarts of the service. Usage is only tracked in memory if this is
+    /// not set.
+    pub(crate) usage_path: Option<PathBuf>,
+}
+
+/// An API key accepted by the service, and its budget.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ApiKeyConfig {
+    /// Name the key is tracked and reported under in metrics, so that the key
+    /// itself is not exposed.
+    pub(crate) name: String,
+    /// The key, as sent by clients in the `x-iota-rpc-api-key` header.
+    pub(crate) key: String,
+    /// Budget of query cost per minute. The cost of a query is its estimated
+    /// number of output nodes, as checked against `max_output_nodes`.
+    pub(crate) budget: u64,
+}
+
+#[GraphQLConfig]
+#[derive(Copy)]
+pub struct PersistedQueriesConfig {
+    /// Maximum number of query documents kept for automatic persisted queries.
+    /// The least recently used documents are evicted first, after which
+    /// clients have to send the full document again.
+    pub cache_size: u32,
+}
+
+#[GraphQLConfig]
+#[derive(Copy)]
+pub struct ResponseCacheConfig {
+    /// Maximum number of responses kept in the response cache. The least
+    /// recently used responses are evicted first.
+    pub max_entries: u32,
+    /// Maximum size (in bytes) of the data of a response, serialized as JSON,
+    /// for it to be cached.
+    pub max_entry_size: u32,
+}
+
 /// The Version of the service. `year.month` represents the major release.
 /// New `patch` versions represent backwards compatible fixes for their major
 /// release. The `full` version is `year.month.patch-sha`.
@@ -179,6 +235,8 @@ pub struct InternalFeatureConfig {
     pub(crate) tracing: bool,
     pub(crate) apollo_tracing: bool,
     pub(crate) open_telemetry: bool,
+    pub(crate) persisted_queries: bool,
+    pub(crate) response_cache: bool,
 }
 
 #[GraphQLConfig]
@@ -504,6 +562,8 @@ impl Default for InternalFeatureConfig {
             tracing: false,
             apollo_tracing: false,
             open_telemetry: false,
+            persisted_queries: true,
+            response_cache: false,
         }
     }
 }
@@ -516,6 +576,21 @@ impl Default for BackgroundTasksConfig {
     }
 }
 
+impl Default for PersistedQueriesConfig {
+    fn default() -> Self {
+        Self { cache_size: 1_000 }
+    }
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: 1_000,
+            max_entry_size: 100_000,
+        }
+    }
+}
+
 impl Display for Version {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.full)
@@ -616,6 +691,41 @@ mod tests {
         assert_eq!(actual, expect)
     }
 
+    #[test]
+    fn test_read_api_keys_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [api-keys]
+                anonymous-budget = 1000
+                usage-path = "/var/lib/iota-graphql-rpc/usage.json"
+
+                [[api-keys.keys]]
+                name = "wallet"
+                key = "secret"
+                budget = 50000
+
+                [persisted-queries]
+                cache-size = 42
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            api_keys: ApiKeysConfig {
+                keys: vec![ApiKeyConfig {
+                    name: "wallet".to_string(),
+                    key: "secret".to_string(),
+                    budget: 50_000,
+                }],
+                anonymous_budget: Some(1000),
+                usage_path: Some("/var/lib/iota-graphql-rpc/usage.json".into()),
+            },
+            persisted_queries: PersistedQueriesConfig { cache_size: 42 },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect)
+    }
+
     #[test]
     fn test_read_everything_in_service_config() {
         let actual = ServiceConfig::read(
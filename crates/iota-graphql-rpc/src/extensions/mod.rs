@@ -6,4 +6,6 @@ pub(crate) mod directive_checker;
 pub(crate) mod feature_gate;
 pub(crate) mod logger;
 pub(crate) mod query_limits_checker;
+pub(crate) mod query_quotas;
+pub(crate) mod response_cache;
 pub(crate) mod timeout;
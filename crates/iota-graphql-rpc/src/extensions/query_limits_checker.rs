@@ -30,6 +30,7 @@ use uuid::Uuid;
 use crate::{
     config::{Limits, ServiceConfig},
     error::{code, graphql_error, graphql_error_at_pos},
+    extensions::query_quotas::{ApiKey, QueryQuotas},
     metrics::Metrics,
 };
 
@@ -415,7 +416,9 @@ impl Extension for QueryLimitsCheckerExt {
     }
 
     /// Validates the query against the limits set in the service config
-    /// If the limits are hit, the operation terminates early
+    /// If the limits are hit, the operation terminates early. Otherwise, the
+    /// estimated output nodes of the query are charged to the budget of the
+    /// request's API key, if budgets are configured.
     async fn parse_query(
         &self,
         ctx: &ExtensionContext<'_>,
@@ -473,12 +476,18 @@ impl Extension for QueryLimitsCheckerExt {
         metrics.query_validation_latency(instant.elapsed());
         usage.report(metrics);
 
-        res.map(|()| {
-            if ctx.data_opt::<ShowUsage>().is_some() {
-                *self.usage.lock().unwrap() = Some(usage);
-            }
+        res?;
 
-            doc
-        })
+        if let Some(quotas) = ctx.data_opt::<QueryQuotas>() {
+            quotas
+                .charge(ctx.data_opt::<ApiKey>(), usage.output_nodes as u64, metrics)
+                .await?;
+        }
+
+        if ctx.data_opt::<ShowUsage>().is_some() {
+            *self.usage.lock().unwrap() = Some(usage);
+        }
+
+        Ok(doc)
     }
 }
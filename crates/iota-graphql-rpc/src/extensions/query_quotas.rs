@@ -0,0 +1,315 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use async_graphql::{ServerError, ServerResult};
+use async_trait::async_trait;
+use axum::http::HeaderName;
+use iota_graphql_rpc_headers::API_KEY_HEADER;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::{
+    config::ApiKeysConfig,
+    error::{Error, code, graphql_error},
+    metrics::Metrics,
+};
+
+/// Name that requests without an API key are tracked and reported under.
+const ANONYMOUS: &str = "anonymous";
+
+/// Length of the windows that budgets of query cost are defined over.
+const QUOTA_WINDOW: Duration = Duration::from_secs(60);
+
+/// Minimum time between two writes of the usage to a `FileQuotaStore`.
+const PERSIST_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The API key a request was sent with, if the request had the API key header.
+pub(crate) struct ApiKey(pub String);
+
+/// Budgets of query cost per minute, by API key. The cost of a query is its
+/// estimated number of output nodes, which is charged by the
+/// `QueryLimitsChecker` after checking the query against the service's limits.
+pub(crate) struct QueryQuotas {
+    /// The name and budget of each accepted API key, by key.
+    keys: HashMap<String, (String, u64)>,
+    /// Budget shared by all requests without an API key, if any.
+    anonymous_budget: Option<u64>,
+    store: Box<dyn QuotaStore>,
+}
+
+/// Storage for the usage of budgets within the current window.
+#[async_trait]
+pub(crate) trait QuotaStore: Send + Sync {
+    /// Spend `cost` out of the `budget` of the key tracked under `name`, in
+    /// the given `window`. Returns the remaining budget after spending, or
+    /// the remaining budget as an error if it does not cover `cost`, in which
+    /// case nothing is spent.
+    async fn spend(&self, name: &str, window: u64, cost: u64, budget: u64) -> Result<u64, u64>;
+}
+
+/// Tracks usage in memory only, so that it is reset when the service restarts.
+#[derive(Default)]
+pub(crate) struct InMemoryQuotaStore {
+    usage: Mutex<HashMap<String, WindowUsage>>,
+}
+
+/// Tracks usage in memory, and persists it to a file at most once per
+/// `PERSIST_INTERVAL`, so that it survives restarts of the service.
+pub(crate) struct FileQuotaStore {
+    memory: InMemoryQuotaStore,
+    path: PathBuf,
+    last_persisted: Mutex<Instant>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+struct WindowUsage {
+    window: u64,
+    spent: u64,
+}
+
+impl ApiKey {
+    pub(crate) fn name() -> &'static HeaderName {
+        &API_KEY_HEADER
+    }
+}
+
+impl QueryQuotas {
+    /// Set up the budgets configured in `config`, or return `None` if no API
+    /// keys and no anonymous budget are configured.
+    pub(crate) fn new(config: &ApiKeysConfig) -> Result<Option<Self>, Error> {
+        if config.keys.is_empty() && config.anonymous_budget.is_none() {
+            return Ok(None);
+        }
+
+        let mut keys = HashMap::new();
+        for key in &config.keys {
+            if key.name == ANONYMOUS {
+                return Err(Error::Internal(format!(
+                    "API key name '{ANONYMOUS}' is reserved for requests without an API key"
+                )));
+            }
+
+            if keys
+                .insert(key.key.clone(), (key.name.clone(), key.budget))
+                .is_some()
+            {
+                return Err(Error::Internal(format!(
+                    "API key '{}' is configured more than once",
+                    key.name
+                )));
+            }
+        }
+
+        let store: Box<dyn QuotaStore> = match &config.usage_path {
+            Some(path) => Box::new(FileQuotaStore::new(path.clone())?),
+            None => Box::<InMemoryQuotaStore>::default(),
+        };
+
+        Ok(Some(Self {
+            keys,
+            anonymous_budget: config.anonymous_budget,
+            store,
+        }))
+    }
+
+    /// Charge a query of the given `cost` to the budget of `key`, or to the
+    /// anonymous budget if the request had no API key. Fails if the key is not
+    /// accepted, or if its remaining budget for the current minute does not
+    /// cover `cost`.
+    pub(crate) async fn charge(
+        &self,
+        key: Option<&ApiKey>,
+        cost: u64,
+        metrics: &Metrics,
+    ) -> ServerResult<()> {
+        let (name, budget) = match key {
+            Some(ApiKey(key)) => {
+                let Some((name, budget)) = self.keys.get(key) else {
+                    return Err(graphql_error(code::BAD_REQUEST, "Unknown API key"));
+                };
+                (name.as_str(), Some(*budget))
+            }
+            None => (ANONYMOUS, self.anonymous_budget),
+        };
+
+        let request_metrics = &metrics.request_metrics;
+        request_metrics
+            .api_key_queries
+            .with_label_values(&[name])
+            .inc();
+
+        if let Some(budget) = budget {
+            if let Err(remaining) = self.store.spend(name, current_window(), cost, budget).await {
+                request_metrics
+                    .api_key_queries_rejected
+                    .with_label_values(&[name])
+                    .inc();
+                info!(
+                    api_key = name,
+                    error_code = code::TOO_MANY_REQUESTS,
+                    "Query cost {cost} exceeds remaining budget {remaining}"
+                );
+
+                return Err(quota_exceeded_error(cost, remaining));
+            }
+        }
+
+        request_metrics
+            .api_key_query_cost
+            .with_label_values(&[name])
+            .inc_by(cost);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl QuotaStore for InMemoryQuotaStore {
+    async fn spend(&self, name: &str, window: u64, cost: u64, budget: u64) -> Result<u64, u64> {
+        let mut usage = self.usage.lock().unwrap();
+        let usage = usage.entry(name.to_string()).or_default();
+        if usage.window != window {
+            *usage = WindowUsage { window, spent: 0 };
+        }
+
+        let remaining = budget.saturating_sub(usage.spent);
+        if cost > remaining {
+            return Err(remaining);
+        }
+
+        usage.spent += cost;
+        Ok(remaining - cost)
+    }
+}
+
+impl FileQuotaStore {
+    /// Create a store persisting to `path`, restoring the usage previously
+    /// persisted there, if any.
+    fn new(path: PathBuf) -> Result<Self, Error> {
+        let usage = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+                Error::Internal(format!(
+                    "Failed to parse API key usage from {}: {e}",
+                    path.display()
+                ))
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => {
+                return Err(Error::Internal(format!(
+                    "Failed to read API key usage from {}: {e}",
+                    path.display()
+                )));
+            }
+        };
+
+        Ok(Self {
+            memory: InMemoryQuotaStore {
+                usage: Mutex::new(usage),
+            },
+            path,
+            last_persisted: Mutex::new(Instant::now()),
+        })
+    }
+
+    /// Write the usage to a temporary file next to `path`, and move it into
+    /// place, so that a crash never leaves a partially written file behind.
+    async fn persist(&self, usage: HashMap<String, WindowUsage>) -> std::io::Result<()> {
+        let bytes = serde_json::to_vec(&usage)?;
+        let tmp = self.path.with_extension("tmp");
+        tokio::fs::write(&tmp, bytes).await?;
+        tokio::fs::rename(&tmp, &self.path).await
+    }
+}
+
+#[async_trait]
+impl QuotaStore for FileQuotaStore {
+    async fn spend(&self, name: &str, window: u64, cost: u64, budget: u64) -> Result<u64, u64> {
+        let remaining = self.memory.spend(name, window, cost, budget).await?;
+
+        let snapshot = {
+            let mut last_persisted = self.last_persisted.lock().unwrap();
+            if last_persisted.elapsed() < PERSIST_INTERVAL {
+                None
+            } else {
+                *last_persisted = Instant::now();
+                Some(self.memory.usage.lock().unwrap().clone())
+            }
+        };
+
+        if let Some(usage) = snapshot {
+            if let Err(e) = self.persist(usage).await {
+                warn!(
+                    "Failed to persist API key usage to {}: {e}",
+                    self.path.display()
+                );
+            }
+        }
+
+        Ok(remaining)
+    }
+}
+
+/// The index of the window of `QUOTA_WINDOW` that the current time falls in.
+fn current_window() -> u64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    now.as_secs() / QUOTA_WINDOW.as_secs()
+}
+
+fn quota_exceeded_error(cost: u64, remaining: u64) -> ServerError {
+    graphql_error(
+        code::TOO_MANY_REQUESTS,
+        format!("Query cost of {cost} exceeds the remaining budget of {remaining} for this minute"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_spend_within_window() {
+        let store = InMemoryQuotaStore::default();
+        assert_eq!(store.spend("a", 0, 40, 100).await, Ok(60));
+        assert_eq!(store.spend("a", 0, 60, 100).await, Ok(0));
+        assert_eq!(store.spend("a", 0, 1, 100).await, Err(0));
+
+        // Keys are tracked separately.
+        assert_eq!(store.spend("b", 0, 10, 100).await, Ok(90));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_spend_is_not_charged() {
+        let store = InMemoryQuotaStore::default();
+        assert_eq!(store.spend("a", 0, 70, 100).await, Ok(30));
+        assert_eq!(store.spend("a", 0, 50, 100).await, Err(30));
+        assert_eq!(store.spend("a", 0, 30, 100).await, Ok(0));
+    }
+
+    #[tokio::test]
+    async fn test_budget_resets_in_next_window() {
+        let store = InMemoryQuotaStore::default();
+        assert_eq!(store.spend("a", 0, 100, 100).await, Ok(0));
+        assert_eq!(store.spend("a", 1, 100, 100).await, Ok(0));
+    }
+
+    #[tokio::test]
+    async fn test_file_store_restores_usage() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("usage.json");
+
+        let store = FileQuotaStore::new(path.clone()).unwrap();
+        *store.last_persisted.lock().unwrap() -= PERSIST_INTERVAL;
+        assert_eq!(store.spend("a", 0, 40, 100).await, Ok(60));
+
+        let store = FileQuotaStore::new(path).unwrap();
+        assert_eq!(store.spend("a", 0, 40, 100).await, Ok(20));
+    }
+}
@@ -0,0 +1,377 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+};
+
+use async_graphql::{
+    Response, ServerResult, Value,
+    extensions::{
+        Extension, ExtensionContext, ExtensionFactory, NextExecute, NextParseQuery, NextResolve,
+        ResolveInfo,
+    },
+    parser::types::{ExecutableDocument, OperationType},
+};
+use async_graphql_value::Variables;
+use async_trait::async_trait;
+use lru::LruCache;
+
+use crate::{config::ResponseCacheConfig, metrics::Metrics, server::watermark_task::Watermark};
+
+/// Fields whose values are not determined by the checkpoint a query is viewed
+/// at, because they are not served from the database. Responses that resolve
+/// any of these fields are never cached.
+const UNCACHEABLE_FIELDS: &[(&str, &str)] = &[("Query", "dryRunTransactionBlock")];
+
+/// Fields whose values do not change as the watermark advances, once they are
+/// not null, because they are read from data that is immutable once indexed,
+/// like executed transactions. Responses that only resolve these fields are
+/// kept across watermark advances.
+const IMMUTABLE_FIELDS: &[(&str, &str)] = &[
+    ("Query", "chainIdentifier"),
+    ("Query", "transactionBlock"),
+    ("TransactionBlock", "digest"),
+    ("TransactionBlock", "signatures"),
+    ("TransactionBlock", "effects"),
+    ("TransactionBlock", "bcs"),
+    ("TransactionBlockEffects", "status"),
+    ("TransactionBlockEffects", "lamportVersion"),
+    ("TransactionBlockEffects", "errors"),
+    ("TransactionBlockEffects", "gasEffects"),
+    ("TransactionBlockEffects", "timestamp"),
+    ("TransactionBlockEffects", "bcs"),
+    ("GasEffects", "gasSummary"),
+    ("GasCostSummary", "computationCost"),
+    ("GasCostSummary", "computationCostBurned"),
+    ("GasCostSummary", "storageCost"),
+    ("GasCostSummary", "storageRebate"),
+    ("GasCostSummary", "nonRefundableStorageFee"),
+];
+
+/// Extension factory for caching the data of responses to queries. Responses
+/// are cached per checkpoint that the watermark was at when they were served,
+/// as all data read from the database is consistent with that checkpoint.
+/// Entries for earlier checkpoints are invalidated whenever the watermark
+/// advances, so the cache serves repeated queries within one watermark update
+/// interval. Responses that only resolve [`IMMUTABLE_FIELDS`] are cached
+/// independently of the checkpoint, and are only evicted by the LRU policy.
+#[derive(Clone)]
+pub(crate) struct ResponseCache(Arc<ResponseCacheInner>);
+
+struct ResponseCacheInner {
+    entries: Mutex<LruCache<CacheKey, Value>>,
+    /// The checkpoint the cache was last invalidated for. Responses viewed at
+    /// earlier checkpoints are no longer inserted.
+    checkpoint: AtomicU64,
+    /// Responses whose data is larger than this many bytes, once serialized,
+    /// are not cached.
+    max_entry_size: usize,
+    metrics: Metrics,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    /// The checkpoint the response was viewed at, or `None` if it is the same
+    /// at every checkpoint.
+    checkpoint: Option<u64>,
+    operation_name: Option<String>,
+    /// The normalized document of the request.
+    document: String,
+    /// The variables of the request, serialized as JSON.
+    variables: String,
+}
+
+struct ResponseCacheExt {
+    cache: ResponseCache,
+    /// The normalized document and variables of the request, if it is a
+    /// query.
+    document: Mutex<Option<(String, String)>>,
+    /// Whether every field resolved so far can be cached.
+    cacheable: AtomicBool,
+    /// Whether every field resolved so far is immutable.
+    immutable: AtomicBool,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(config: &ResponseCacheConfig, metrics: Metrics) -> Self {
+        let max_entries =
+            NonZeroUsize::new(config.max_entries as usize).unwrap_or(NonZeroUsize::MIN);
+        Self(Arc::new(ResponseCacheInner {
+            entries: Mutex::new(LruCache::new(max_entries)),
+            checkpoint: AtomicU64::new(0),
+            max_entry_size: config.max_entry_size as usize,
+            metrics,
+        }))
+    }
+
+    /// Evict all entries for checkpoints before `checkpoint`. These can no
+    /// longer be hit, because requests are viewed at the latest watermark.
+    /// Entries which are the same at every checkpoint are kept.
+    pub(crate) fn invalidate(&self, checkpoint: u64) {
+        self.0.checkpoint.fetch_max(checkpoint, Ordering::Relaxed);
+
+        let mut entries = self.0.entries.lock().unwrap();
+        let stale: Vec<_> = entries
+            .iter()
+            .filter(|(key, _)| {
+                key.checkpoint
+                    .is_some_and(|viewed_at| viewed_at < checkpoint)
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        for key in &stale {
+            entries.pop(key);
+        }
+
+        self.0
+            .metrics
+            .request_metrics
+            .response_cache_entries
+            .set(entries.len() as f64);
+    }
+
+    /// Looks up the response for `key`, either viewed at its checkpoint or the
+    /// same at every checkpoint.
+    fn get(&self, key: &CacheKey) -> Option<Value> {
+        let mut entries = self.0.entries.lock().unwrap();
+        if let Some(data) = entries.get(key) {
+            return Some(data.clone());
+        }
+
+        let immutable = CacheKey {
+            checkpoint: None,
+            ..key.clone()
+        };
+        entries.get(&immutable).cloned()
+    }
+
+    fn insert(&self, key: CacheKey, data: &Value) {
+        if key
+            .checkpoint
+            .is_some_and(|viewed_at| viewed_at < self.0.checkpoint.load(Ordering::Relaxed))
+        {
+            return;
+        }
+
+        let size = serde_json::to_vec(data).map_or(usize::MAX, |bytes| bytes.len());
+        if size > self.0.max_entry_size {
+            return;
+        }
+
+        let mut entries = self.0.entries.lock().unwrap();
+        entries.put(key, data.clone());
+        self.0
+            .metrics
+            .request_metrics
+            .response_cache_entries
+            .set(entries.len() as f64);
+    }
+}
+
+impl ExtensionFactory for ResponseCache {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(ResponseCacheExt {
+            cache: self.clone(),
+            document: Mutex::new(None),
+            cacheable: AtomicBool::new(true),
+            immutable: AtomicBool::new(true),
+        })
+    }
+}
+
+#[async_trait]
+impl Extension for ResponseCacheExt {
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let document = next.run(ctx, query, variables).await?;
+
+        let is_mutation = document
+            .operations
+            .iter()
+            .any(|(_, operation)| operation.node.ty == OperationType::Mutation);
+
+        if !is_mutation {
+            if let Ok(serialized) = serde_json::to_string(variables) {
+                *self.document.lock().unwrap() =
+                    Some((ctx.stringify_execute_doc(&document, variables), serialized));
+            }
+        }
+
+        Ok(document)
+    }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let document = self.document.lock().unwrap().take();
+        let (Some((document, variables)), Some(watermark)) =
+            (document, ctx.data_opt::<Watermark>())
+        else {
+            return next.run(ctx, operation_name).await;
+        };
+
+        let mut key = CacheKey {
+            checkpoint: Some(watermark.checkpoint),
+            operation_name: operation_name.map(str::to_owned),
+            document,
+            variables,
+        };
+
+        let request_metrics = &self.cache.0.metrics.request_metrics;
+        if let Some(data) = self.cache.get(&key) {
+            request_metrics.response_cache_hits.inc();
+            return Response::new(data);
+        }
+
+        request_metrics.response_cache_misses.inc();
+        let response = next.run(ctx, operation_name).await;
+        if response.errors.is_empty() && self.cacheable.load(Ordering::Relaxed) {
+            if self.immutable.load(Ordering::Relaxed) {
+                key.checkpoint = None;
+            }
+            self.cache.insert(key, &response.data);
+        }
+
+        response
+    }
+
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        let field = (info.parent_type, info.name);
+        if UNCACHEABLE_FIELDS.contains(&field) {
+            self.cacheable.store(false, Ordering::Relaxed);
+        }
+
+        let value = next.run(ctx, info).await;
+        // A null value can become set at a later checkpoint, e.g. when the
+        // transaction it is read from gets indexed.
+        let is_set = matches!(&value, Ok(Some(value)) if *value != Value::Null);
+        if !is_set || !IMMUTABLE_FIELDS.contains(&field) {
+            self.immutable.store(false, Ordering::Relaxed);
+        }
+
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_graphql::{EmptyMutation, EmptySubscription, Object, Request, Schema, SimpleObject};
+    use prometheus::Registry;
+
+    use super::*;
+
+    struct Query;
+
+    #[derive(SimpleObject)]
+    struct TransactionBlock {
+        digest: String,
+    }
+
+    #[Object]
+    impl Query {
+        async fn chain_identifier(&self) -> &str {
+            "6364aad5"
+        }
+
+        async fn transaction_block(&self, digest: String) -> Option<TransactionBlock> {
+            (digest != "unknown").then_some(TransactionBlock { digest })
+        }
+
+        async fn checkpoint(&self, ctx: &async_graphql::Context<'_>) -> u64 {
+            ctx.data_unchecked::<Watermark>().checkpoint
+        }
+    }
+
+    fn schema() -> (
+        Schema<Query, EmptyMutation, EmptySubscription>,
+        ResponseCache,
+    ) {
+        let metrics = Metrics::new(&Registry::new());
+        let cache = ResponseCache::new(&ResponseCacheConfig::default(), metrics);
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(cache.clone())
+            .finish();
+        (schema, cache)
+    }
+
+    async fn execute(
+        schema: &Schema<Query, EmptyMutation, EmptySubscription>,
+        query: &str,
+        checkpoint: u64,
+    ) -> Value {
+        let watermark = Watermark {
+            checkpoint,
+            ..Default::default()
+        };
+        let response = schema.execute(Request::new(query).data(watermark)).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        response.data
+    }
+
+    fn hits(cache: &ResponseCache) -> u64 {
+        cache.0.metrics.request_metrics.response_cache_hits.get()
+    }
+
+    fn entries(cache: &ResponseCache) -> usize {
+        cache.0.entries.lock().unwrap().len()
+    }
+
+    #[tokio::test]
+    async fn test_immutable_response_is_kept() {
+        let (schema, cache) = schema();
+        let query = r#"{ chainIdentifier transactionBlock(digest: "a") { digest } }"#;
+
+        let data = execute(&schema, query, 1).await;
+        assert_eq!(hits(&cache), 0);
+
+        cache.invalidate(2);
+        assert_eq!(entries(&cache), 1);
+        assert_eq!(execute(&schema, query, 2).await, data);
+        assert_eq!(hits(&cache), 1);
+    }
+
+    #[tokio::test]
+    async fn test_mutable_response_is_evicted() {
+        let (schema, cache) = schema();
+        let queries = [
+            "{ checkpoint }",
+            "{ chainIdentifier checkpoint }",
+            // The transaction may be indexed at a later checkpoint.
+            r#"{ transactionBlock(digest: "unknown") { digest } }"#,
+        ];
+
+        for query in queries {
+            execute(&schema, query, 1).await;
+            execute(&schema, query, 1).await;
+        }
+        assert_eq!(hits(&cache), queries.len() as u64);
+        assert_eq!(entries(&cache), queries.len());
+
+        cache.invalidate(2);
+        assert_eq!(entries(&cache), 0);
+        assert_eq!(
+            execute(&schema, "{ checkpoint }", 2).await,
+            Value::from_json(serde_json::json!({ "checkpoint": 2 })).unwrap()
+        );
+        assert_eq!(hits(&cache), queries.len() as u64);
+    }
+}
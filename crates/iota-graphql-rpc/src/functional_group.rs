@@ -25,6 +25,9 @@ pub(crate) enum FunctionalGroup {
     /// Querying an object's dynamic fields.
     DynamicFields,
 
+    /// Outputs migrated from the Stardust ledger and the assets they hold.
+    Stardust,
+
     /// Transaction and Event subscriptions.
     Subscriptions,
 
@@ -48,6 +51,7 @@ impl FunctionalGroup {
             G::Analytics,
             G::Coins,
             G::DynamicFields,
+            G::Stardust,
             G::Subscriptions,
             G::SystemState,
         ];
@@ -69,6 +73,7 @@ fn functional_groups() -> &'static BTreeMap<(&'static str, &'static str), Functi
             (("Address", "balance"), G::Coins),
             (("Address", "balances"), G::Coins),
             (("Address", "coins"), G::Coins),
+            (("Address", "stardustOutputs"), G::Stardust),
             (("Checkpoint", "addressMetrics"), G::Analytics),
             (("Checkpoint", "networkTotalTransactions"), G::Analytics),
             (("Epoch", "protocolConfigs"), G::SystemState),
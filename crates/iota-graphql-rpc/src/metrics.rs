@@ -83,6 +83,18 @@ pub(crate) struct RequestMetrics {
     pub num_queries_top_level: IntCounterVec,
     /// Total inflight requests
     pub inflight_requests: Gauge,
+    /// Number of queries by API key
+    pub api_key_queries: IntCounterVec,
+    /// Query cost charged by API key
+    pub api_key_query_cost: IntCounterVec,
+    /// Number of queries rejected for exceeding the budget of their API key
+    pub api_key_queries_rejected: IntCounterVec,
+    /// Number of queries served from the response cache
+    pub response_cache_hits: IntCounter,
+    /// Number of queries looked up in the response cache, but not found
+    pub response_cache_misses: IntCounter,
+    /// Number of responses in the response cache
+    pub response_cache_entries: Gauge,
 }
 
 impl Metrics {
@@ -261,6 +273,45 @@ impl RequestMetrics {
                 registry
             )
             .unwrap(),
+            api_key_queries: register_int_counter_vec_with_registry!(
+                "api_key_queries",
+                "Number of queries by the name of their API key",
+                &["key"],
+                registry
+            )
+            .unwrap(),
+            api_key_query_cost: register_int_counter_vec_with_registry!(
+                "api_key_query_cost",
+                "Query cost (estimated output nodes) charged by the name of the API key",
+                &["key"],
+                registry
+            )
+            .unwrap(),
+            api_key_queries_rejected: register_int_counter_vec_with_registry!(
+                "api_key_queries_rejected",
+                "Number of queries rejected for exceeding the budget of their API key, by the name of the key",
+                &["key"],
+                registry
+            )
+            .unwrap(),
+            response_cache_hits: register_int_counter_with_registry!(
+                "response_cache_hits",
+                "Number of queries served from the response cache",
+                registry
+            )
+            .unwrap(),
+            response_cache_misses: register_int_counter_with_registry!(
+                "response_cache_misses",
+                "Number of queries looked up in the response cache, but not found",
+                registry
+            )
+            .unwrap(),
+            response_cache_entries: register_gauge_with_registry!(
+                "response_cache_entries",
+                "Number of responses in the response cache",
+                registry
+            )
+            .unwrap(),
         }
     }
 }
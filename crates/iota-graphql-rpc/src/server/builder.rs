@@ -12,7 +12,10 @@ use std::{
 
 use async_graphql::{
     EmptySubscription, Schema, SchemaBuilder,
-    extensions::{ApolloTracing, ExtensionFactory, Tracing},
+    extensions::{
+        ApolloTracing, ExtensionFactory, Tracing,
+        apollo_persisted_queries::{ApolloPersistedQueries, LruCacheStorage},
+    },
 };
 use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
 use axum::{
@@ -26,7 +29,7 @@ use axum::{
 };
 use chrono::Utc;
 use http::{HeaderValue, Method, Request};
-use iota_graphql_rpc_headers::LIMITS_HEADER;
+use iota_graphql_rpc_headers::{API_KEY_HEADER, LIMITS_HEADER};
 use iota_metrics::spawn_monitored_task;
 use iota_network_stack::callback::{CallbackLayer, MakeCallbackHandler, ResponseHandler};
 use iota_package_resolver::{PackageStoreWithLruCache, Resolver};
@@ -54,6 +57,8 @@ use crate::{
         feature_gate::FeatureGate,
         logger::Logger,
         query_limits_checker::{QueryLimitsChecker, ShowUsage},
+        query_quotas::{ApiKey, QueryQuotas},
+        response_cache::ResponseCache,
         timeout::Timeout,
     },
     metrics::Metrics,
@@ -61,6 +66,7 @@ use crate::{
     server::{
         compatibility_check::check_all_tables,
         exchange_rates_task::TriggerExchangeRatesTask,
+        response_cache_task::ResponseCacheTask,
         system_package_task::SystemPackageTask,
         version::{check_version_middleware, set_version_middleware},
         watermark_task::{Watermark, WatermarkLock, WatermarkTask},
@@ -84,6 +90,7 @@ pub(crate) struct Server {
     watermark_task: WatermarkTask,
     system_package_task: SystemPackageTask,
     trigger_exchange_rates_task: TriggerExchangeRatesTask,
+    response_cache_task: Option<ResponseCacheTask>,
     state: AppState,
     db_reader: Db,
 }
@@ -126,6 +133,18 @@ impl Server {
             })
         };
 
+        // A handle that spawns a background task to evict responses from the response
+        // cache as the watermark advances, if the cache is enabled.
+        let response_cache_task = {
+            info!("Starting response cache task");
+            let response_cache_task = self.response_cache_task.take();
+            spawn_monitored_task!(async move {
+                if let Some(mut task) = response_cache_task {
+                    task.run().await;
+                }
+            })
+        };
+
         let server_task = {
             info!("Starting graphql service");
             let cancellation_token = self.state.cancellation_token.clone();
@@ -154,6 +173,7 @@ impl Server {
             watermark_task,
             system_package_task,
             trigger_exchange_rates_task,
+            response_cache_task,
             server_task
         );
 
@@ -167,6 +187,7 @@ pub(crate) struct ServerBuilder {
     router: Option<Router>,
     db_reader: Option<Db>,
     resolver: Option<PackageResolver>,
+    response_cache: Option<ResponseCache>,
 }
 
 #[derive(Clone)]
@@ -216,6 +237,7 @@ impl ServerBuilder {
             router: None,
             db_reader: None,
             resolver: None,
+            response_cache: None,
         }
     }
 
@@ -249,6 +271,7 @@ impl ServerBuilder {
         Schema<Query, Mutation, EmptySubscription>,
         Db,
         PackageResolver,
+        Option<ResponseCache>,
         Router,
     ) {
         let address = self.address();
@@ -257,6 +280,7 @@ impl ServerBuilder {
             schema,
             db_reader,
             resolver,
+            response_cache,
             router,
         } = self;
         (
@@ -264,6 +288,7 @@ impl ServerBuilder {
             schema.finish(),
             db_reader.expect("DB reader not initialized"),
             resolver.expect("Package resolver not initialized"),
+            response_cache,
             router.expect("Router not initialized"),
         )
     }
@@ -328,14 +353,19 @@ impl ServerBuilder {
             .allow_methods([Method::POST])
             // Allow requests from any origin
             .allow_origin(acl)
-            .allow_headers([hyper::header::CONTENT_TYPE, LIMITS_HEADER.clone()]);
+            .allow_headers([
+                hyper::header::CONTENT_TYPE,
+                LIMITS_HEADER.clone(),
+                API_KEY_HEADER.clone(),
+            ]);
         Ok(cors)
     }
 
     /// Consumes the `ServerBuilder` to create a `Server` that can be run.
     pub fn build(self) -> Result<Server, Error> {
         let state = self.state.clone();
-        let (address, schema, db_reader, resolver, router) = self.build_components();
+        let (address, schema, db_reader, resolver, response_cache, router) =
+            self.build_components();
 
         // Initialize the watermark background task struct.
         let watermark_task = WatermarkTask::new(
@@ -357,6 +387,14 @@ impl ServerBuilder {
             state.cancellation_token.clone(),
         );
 
+        let response_cache_task = response_cache.map(|cache| {
+            ResponseCacheTask::new(
+                cache,
+                watermark_task.checkpoint_receiver(),
+                state.cancellation_token.clone(),
+            )
+        });
+
         let router = router
             .route_layer(middleware::from_fn_with_state(
                 state.version,
@@ -378,6 +416,7 @@ impl ServerBuilder {
             watermark_task,
             system_package_task,
             trigger_exchange_rates_task,
+            response_cache_task,
             state,
             db_reader,
         })
@@ -486,6 +525,10 @@ impl ServerBuilder {
             .context_data(metrics.clone())
             .context_data(config.clone());
 
+        if let Some(quotas) = QueryQuotas::new(&config.service.api_keys)? {
+            builder = builder.context_data(quotas);
+        }
+
         if config.internal_features.feature_gate {
             builder = builder.extension(FeatureGate);
         }
@@ -514,6 +557,18 @@ impl ServerBuilder {
             builder = builder.extension(ApolloTracing);
         }
 
+        if config.internal_features.persisted_queries {
+            builder = builder.extension(ApolloPersistedQueries::new(LruCacheStorage::new(
+                config.service.persisted_queries.cache_size as usize,
+            )));
+        }
+
+        if config.internal_features.response_cache {
+            let response_cache = ResponseCache::new(&config.service.response_cache, metrics);
+            builder.response_cache = Some(response_cache.clone());
+            builder = builder.extension(response_cache);
+        }
+
         // TODO: uncomment once impl
         // if config.internal_features.open_telemetry { }
 
@@ -535,8 +590,8 @@ pub fn export_schema() -> String {
 }
 
 /// Entry point for graphql requests. Each request is stamped with a unique ID,
-/// a `ShowUsage` flag if set in the request headers, and the watermark as set
-/// by the background task.
+/// a `ShowUsage` flag if set in the request headers, the `ApiKey` if set in
+/// the request headers, and the watermark as set by the background task.
 async fn graphql_handler(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     schema: Extension<IotaGraphQLSchema>,
@@ -549,6 +604,12 @@ async fn graphql_handler(
     if headers.contains_key(ShowUsage::name()) {
         req.data.insert(ShowUsage)
     }
+    if let Some(key) = headers.get(ApiKey::name()) {
+        // Keys that are not valid strings can never match a configured key, and
+        // are rejected as unknown.
+        req.data
+            .insert(ApiKey(key.to_str().unwrap_or_default().to_string()));
+    }
     // Capture the IP address of the client
     // Note: if a load balancer is used it must be configured to forward the client
     // IP address
@@ -701,7 +762,10 @@ pub mod tests {
 
     use super::*;
     use crate::{
-        config::{ConnectionConfig, Limits, ServiceConfig, Version},
+        config::{
+            ApiKeyConfig, ApiKeysConfig, ConnectionConfig, Limits, ResponseCacheConfig,
+            ServiceConfig, Version,
+        },
         context_data::db_data_provider::PgManager,
         extensions::{query_limits_checker::QueryLimitsChecker, timeout::Timeout},
     };
@@ -1091,6 +1155,123 @@ pub mod tests {
         assert_eq!(req_metrics.query_depth.get_sample_sum(), 1. + 3.);
     }
 
+    pub async fn test_query_quotas_impl() {
+        let service_config = ServiceConfig {
+            api_keys: ApiKeysConfig {
+                keys: vec![ApiKeyConfig {
+                    name: "test".to_string(),
+                    key: "secret".to_string(),
+                    budget: 3,
+                }],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let quotas = QueryQuotas::new(&service_config.api_keys)
+            .unwrap()
+            .expect("Quotas should be configured");
+        let server_builder = prep_schema(None, Some(service_config));
+        let metrics = server_builder.state.metrics.clone();
+        let schema = server_builder
+            .context_data(quotas)
+            .extension(QueryLimitsChecker)
+            .build_schema();
+
+        let request = |key: &str| {
+            async_graphql::Request::new("{ chainIdentifier }").data(ApiKey(key.to_string()))
+        };
+
+        // Each query has one output node, so the budget covers three of them.
+        for _ in 0..3 {
+            schema
+                .execute(request("secret"))
+                .await
+                .into_result()
+                .expect("Should complete successfully");
+        }
+
+        // Should fail
+        let errs: Vec<_> = schema
+            .execute(request("secret"))
+            .await
+            .into_result()
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(errs, vec![
+            "Query cost of 1 exceeds the remaining budget of 0 for this minute".to_string()
+        ]);
+
+        let errs: Vec<_> = schema
+            .execute(request("unknown"))
+            .await
+            .into_result()
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(errs, vec!["Unknown API key".to_string()]);
+
+        // Requests without an API key are not limited without an anonymous budget.
+        schema
+            .execute("{ chainIdentifier }")
+            .await
+            .into_result()
+            .expect("Should complete successfully");
+
+        let req_metrics = metrics.request_metrics;
+        let queries = req_metrics.api_key_queries.with_label_values(&["test"]);
+        let cost = req_metrics.api_key_query_cost.with_label_values(&["test"]);
+        let rejected = req_metrics
+            .api_key_queries_rejected
+            .with_label_values(&["test"]);
+        assert_eq!(queries.get(), 4);
+        assert_eq!(cost.get(), 3);
+        assert_eq!(rejected.get(), 1);
+    }
+
+    pub async fn test_response_cache_impl() {
+        let server_builder = prep_schema(None, None);
+        let metrics = server_builder.state.metrics.clone();
+        let cache = ResponseCache::new(&ResponseCacheConfig::default(), metrics.clone());
+        let schema = server_builder.extension(cache.clone()).build_schema();
+
+        // The schema is viewed at checkpoint 1, so the second query is served from
+        // the cache, even if its document is formatted differently.
+        for query in [
+            "{ serviceConfig { maxQueryDepth } }",
+            "{\n  serviceConfig {\n    maxQueryDepth\n  }\n}",
+        ] {
+            schema
+                .execute(query)
+                .await
+                .into_result()
+                .expect("Should complete successfully");
+        }
+
+        let req_metrics = &metrics.request_metrics;
+        assert_eq!(req_metrics.response_cache_misses.get(), 1);
+        assert_eq!(req_metrics.response_cache_hits.get(), 1);
+        assert_eq!(req_metrics.response_cache_entries.get(), 1.);
+
+        // Once the watermark moves on, responses viewed at earlier checkpoints are
+        // evicted, and no longer inserted.
+        cache.invalidate(2);
+        assert_eq!(req_metrics.response_cache_entries.get(), 0.);
+
+        schema
+            .execute("{ serviceConfig { maxQueryDepth } }")
+            .await
+            .into_result()
+            .expect("Should complete successfully");
+
+        assert_eq!(req_metrics.response_cache_misses.get(), 2);
+        assert_eq!(req_metrics.response_cache_hits.get(), 1);
+        assert_eq!(req_metrics.response_cache_entries.get(), 0.);
+    }
+
     pub async fn test_health_check_impl() {
         let server_builder = prep_schema(None, None);
         let url = format!(
@@ -7,6 +7,7 @@ pub mod graphiql_server;
 pub mod builder;
 pub(crate) mod compatibility_check;
 pub(crate) mod exchange_rates_task;
+pub(crate) mod response_cache_task;
 pub(crate) mod system_package_task;
 pub mod version;
 pub(crate) mod watermark_task;
@@ -0,0 +1,46 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+use crate::extensions::response_cache::ResponseCache;
+
+/// Background task responsible for evicting responses from the response cache
+/// once the watermark has moved past the checkpoint they were served at.
+pub(crate) struct ResponseCacheTask {
+    cache: ResponseCache,
+    checkpoint_rx: watch::Receiver<u64>,
+    cancel: CancellationToken,
+}
+
+impl ResponseCacheTask {
+    pub(crate) fn new(
+        cache: ResponseCache,
+        checkpoint_rx: watch::Receiver<u64>,
+        cancel: CancellationToken,
+    ) -> Self {
+        Self {
+            cache,
+            checkpoint_rx,
+            cancel,
+        }
+    }
+
+    pub(crate) async fn run(&mut self) {
+        loop {
+            tokio::select! {
+                _ = self.cancel.cancelled() => {
+                    info!("Shutdown signal received, terminating response cache eviction task");
+                    return;
+                }
+
+                _ = self.checkpoint_rx.changed() => {
+                    let checkpoint = *self.checkpoint_rx.borrow_and_update();
+                    self.cache.invalidate(checkpoint);
+                }
+            }
+        }
+    }
+}
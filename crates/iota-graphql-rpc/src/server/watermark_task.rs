@@ -28,6 +28,8 @@ pub(crate) struct WatermarkTask {
     cancel: CancellationToken,
     sender: watch::Sender<u64>,
     receiver: watch::Receiver<u64>,
+    checkpoint_sender: watch::Sender<u64>,
+    checkpoint_receiver: watch::Receiver<u64>,
 }
 
 pub(crate) type WatermarkLock = Arc<RwLock<Watermark>>;
@@ -54,6 +56,7 @@ impl WatermarkTask {
         cancel: CancellationToken,
     ) -> Self {
         let (sender, receiver) = watch::channel(0);
+        let (checkpoint_sender, checkpoint_receiver) = watch::channel(0);
 
         Self {
             watermark: Default::default(),
@@ -63,6 +66,8 @@ impl WatermarkTask {
             cancel,
             sender,
             receiver,
+            checkpoint_sender,
+            checkpoint_receiver,
         }
     }
 
@@ -85,13 +90,19 @@ impl WatermarkTask {
                     };
 
                     // Write the watermark as follows to limit how long we hold the lock
-                    let prev_epoch = {
+                    let (prev_checkpoint, prev_epoch) = {
                         let mut w = self.watermark.write().await;
-                        w.checkpoint = checkpoint;
                         w.checkpoint_timestamp_ms = checkpoint_timestamp_ms;
-                        mem::replace(&mut w.epoch, epoch)
+                        (
+                            mem::replace(&mut w.checkpoint, checkpoint),
+                            mem::replace(&mut w.epoch, epoch),
+                        )
                     };
 
+                    if checkpoint > prev_checkpoint {
+                        self.checkpoint_sender.send(checkpoint).unwrap();
+                    }
+
                     if epoch > prev_epoch {
                         self.sender.send(epoch).unwrap();
                     }
@@ -108,6 +119,11 @@ impl WatermarkTask {
     pub(crate) fn epoch_receiver(&self) -> watch::Receiver<u64> {
         self.receiver.clone()
     }
+
+    /// Receiver for subscribing to checkpoint watermark changes.
+    pub(crate) fn checkpoint_receiver(&self) -> watch::Receiver<u64> {
+        self.checkpoint_receiver.clone()
+    }
 }
 
 impl Watermark {
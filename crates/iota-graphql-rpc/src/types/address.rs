@@ -10,11 +10,14 @@ use crate::{
         balance::{self, Balance},
         coin::Coin,
         cursor::Page,
+        date_time::DateTime,
         iota_address::IotaAddress,
+        kiosk::Kiosk,
         move_object::MoveObject,
         object::{self, ObjectFilter},
         owner::OwnerImpl,
         stake::StakedIota,
+        stardust::StardustOutput,
         transaction_block::{self, TransactionBlock, TransactionBlockFilter},
         type_filter::ExactTypeFilter,
     },
@@ -116,6 +119,55 @@ impl Address {
             .await
     }
 
+    /// The kiosks whose `0x2::kiosk::KioskOwnerCap` is owned by this address.
+    /// The page is defined in terms of the caps, skipping caps whose kiosk
+    /// was deleted.
+    pub(crate) async fn kiosks(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<object::Cursor>,
+        last: Option<u64>,
+        before: Option<object::Cursor>,
+    ) -> Result<Connection<String, Kiosk>> {
+        let page = Page::from_params(ctx.data_unchecked(), first, after, last, before)?;
+        Kiosk::paginate(ctx, page, self.address, self.checkpoint_viewed_at)
+            .await
+            .extend()
+    }
+
+    /// The outputs migrated from the Stardust ledger which belong to this
+    /// address: the outputs it owns, and the outputs with an expiration unlock
+    /// condition, which are shared objects, that name it as their owner or
+    /// return address.
+    ///
+    /// `claimableAt` restricts the outputs to those whose unlock conditions
+    /// allow this address to claim them at the given time. Outputs are
+    /// filtered after they are read, so a page may contain fewer outputs than
+    /// requested if too many objects had to be scanned to fill it, while
+    /// `pageInfo` still allows to continue paginating.
+    pub(crate) async fn stardust_outputs(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<object::Cursor>,
+        last: Option<u64>,
+        before: Option<object::Cursor>,
+        claimable_at: Option<DateTime>,
+    ) -> Result<ScanConnection<String, StardustOutput>> {
+        let page = Page::from_params(ctx.data_unchecked(), first, after, last, before)?;
+        let claimable_at = claimable_at.map(|time| time.timestamp_ms().max(0) as u64 / 1000);
+        StardustOutput::paginate(
+            ctx.data_unchecked(),
+            page,
+            self.address,
+            claimable_at,
+            self.checkpoint_viewed_at,
+        )
+        .await
+        .extend()
+    }
+
     /// Similar behavior to the `transactionBlocks` in Query but supporting the
     /// additional `AddressTransactionBlockRelationship` filter, which
     /// defaults to `SIGN`.
@@ -214,6 +214,29 @@ impl<C> Page<C> {
     pub(crate) fn end(&self) -> End {
         self.end
     }
+
+    /// The page that continues this page's range past `cursor`, the last entry
+    /// read from it: after the cursor when entries are taken from the front,
+    /// and before it when they are taken from the back.
+    pub(crate) fn continue_from(&self, cursor: C) -> Self
+    where
+        C: Clone,
+    {
+        match self.end {
+            End::Front => Page {
+                after: Some(cursor),
+                before: self.before.clone(),
+                limit: self.limit,
+                end: self.end,
+            },
+            End::Back => Page {
+                after: self.after.clone(),
+                before: Some(cursor),
+                limit: self.limit,
+                end: self.end,
+            },
+        }
+    }
 }
 
 impl<C> Page<C>
@@ -25,6 +25,10 @@ impl DateTime {
             .ok_or_else(|| Error::Internal("Cannot convert timestamp into DateTime".to_string()))
             .map(Self)
     }
+
+    pub fn timestamp_ms(&self) -> i64 {
+        self.0.timestamp_millis()
+    }
 }
 
 /// The DateTime in UTC format. The milliseconds part is optional,
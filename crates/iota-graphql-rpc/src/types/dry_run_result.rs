@@ -6,6 +6,7 @@ use async_graphql::*;
 use iota_json_rpc_types::{DevInspectResults, IotaExecutionResult};
 use iota_types::{
     effects::TransactionEffects as NativeTransactionEffects,
+    gas::{CommandGasUsage, GasBreakdown, MoveFunctionGasUsage},
     transaction::TransactionData as NativeTransactionData,
 };
 
@@ -13,9 +14,11 @@ use crate::{
     error::Error,
     types::{
         base64::Base64,
+        big_int::BigInt,
         move_type::MoveType,
         transaction_block::{TransactionBlock, TransactionBlockInner},
         transaction_block_kind::programmable::TransactionArgument,
+        uint53::UInt53,
     },
 };
 
@@ -28,6 +31,9 @@ pub(crate) struct DryRunResult {
     pub results: Option<Vec<DryRunEffect>>,
     /// The transaction block representing the dry run execution.
     pub transaction: Option<TransactionBlock>,
+    /// The gas paid for executing each command of the dry run execution, and
+    /// for each Move function called by a command.
+    pub gas_breakdown: Option<DryRunGasBreakdown>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, SimpleObject)]
@@ -55,6 +61,76 @@ pub(crate) struct DryRunReturn {
 
     pub bcs: Base64,
 }
+
+/// Breakdown of the computation cost of a dry run execution. Computation
+/// charged outside of commands, like for loading input objects, and storage
+/// costs are not attributed to any command.
+#[derive(Clone, Debug, PartialEq, Eq, SimpleObject)]
+pub(crate) struct DryRunGasBreakdown {
+    /// The gas paid for each command, in execution order.
+    pub commands: Vec<DryRunCommandGas>,
+    /// The gas paid for each Move function called by a command, most expensive
+    /// first.
+    pub move_functions: Vec<DryRunMoveFunctionGas>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, SimpleObject)]
+pub(crate) struct DryRunCommandGas {
+    /// Index of the command in the transaction.
+    pub command_index: u16,
+    /// The kind of the command, e.g. `MoveCall` or `SplitCoins`.
+    pub command: String,
+    /// The Move function called by a `MoveCall` command, as
+    /// `package::module::function`.
+    pub function: Option<String>,
+    /// Gas paid for executing this command (in NANOS), before the
+    /// computation cost of the whole transaction is rounded.
+    pub computation_cost: BigInt,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, SimpleObject)]
+pub(crate) struct DryRunMoveFunctionGas {
+    /// The Move function, as `package::module::function`.
+    pub function: String,
+    /// The number of commands calling this function.
+    pub calls: UInt53,
+    /// Gas paid for executing all calls of this function (in NANOS).
+    pub computation_cost: BigInt,
+}
+
+impl From<GasBreakdown> for DryRunGasBreakdown {
+    fn from(breakdown: GasBreakdown) -> Self {
+        Self {
+            commands: breakdown.commands.into_iter().map(Into::into).collect(),
+            move_functions: breakdown
+                .move_functions
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        }
+    }
+}
+
+impl From<CommandGasUsage> for DryRunCommandGas {
+    fn from(usage: CommandGasUsage) -> Self {
+        Self {
+            command_index: usage.command_index,
+            command: usage.command,
+            function: usage.function,
+            computation_cost: BigInt::from(usage.computation_cost),
+        }
+    }
+}
+
+impl From<MoveFunctionGasUsage> for DryRunMoveFunctionGas {
+    fn from(usage: MoveFunctionGasUsage) -> Self {
+        Self {
+            function: usage.function,
+            calls: usage.calls.into(),
+            computation_cost: BigInt::from(usage.computation_cost),
+        }
+    }
+}
 impl TryFrom<IotaExecutionResult> for DryRunEffect {
     type Error = crate::error::Error;
 
@@ -139,6 +215,7 @@ impl TryFrom<DevInspectResults> for DryRunResult {
             error: dev_inspect_results.error,
             results,
             transaction,
+            gas_breakdown: dev_inspect_results.gas_breakdown.map(Into::into),
         })
     }
 }
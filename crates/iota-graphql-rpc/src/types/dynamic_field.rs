@@ -107,7 +107,7 @@ impl DynamicField {
     /// `MoveObject`, in which case it is also accessible off-chain via its
     /// address. Its contents will be from the latest version that is at
     /// most equal to its parent object's version.
-    async fn value(&self, ctx: &Context<'_>) -> Result<Option<DynamicFieldValue>> {
+    pub(crate) async fn value(&self, ctx: &Context<'_>) -> Result<Option<DynamicFieldValue>> {
         if self.df_kind == DynamicFieldType::DynamicObject {
             // If `df_kind` is a DynamicObject, the object we are currently on is the field
             // object, and we must resolve one more level down to the value
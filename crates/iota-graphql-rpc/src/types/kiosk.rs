@@ -0,0 +1,571 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use async_graphql::{
+    connection::{Connection, Edge},
+    *,
+};
+use iota_types::{
+    dynamic_field::{DynamicFieldInfo, DynamicFieldType, Field},
+    id::ID,
+    kiosk::{
+        Kiosk as NativeKiosk, KioskItem as NativeKioskItem, KioskListing as NativeKioskListing,
+        KioskLock as NativeKioskLock, KioskOwnerCap as NativeKioskOwnerCap,
+        TransferPolicy as NativeTransferPolicy, TransferPolicyRuleKey,
+    },
+};
+use move_core_types::language_storage::{StructTag, TypeTag};
+use serde::Serialize;
+
+use crate::{
+    config::ServiceConfig,
+    connection::ScanConnection,
+    data::Db,
+    error::Error,
+    types::{
+        base64::Base64,
+        big_int::BigInt,
+        cursor::Page,
+        dynamic_field::{DynamicField, DynamicFieldName, DynamicFieldValue},
+        iota_address::IotaAddress,
+        move_object::MoveObject,
+        move_type::MoveType,
+        move_value::MoveValue,
+        object::{self, Object, ObjectFilter, ObjectImpl, ObjectOwner},
+        owner::OwnerImpl,
+        type_filter::ExactTypeFilter,
+        uint53::UInt53,
+    },
+};
+
+pub(crate) enum KioskDowncastError {
+    NotAKioskObject,
+    Bcs(bcs::Error),
+}
+
+#[derive(Clone)]
+pub(crate) struct Kiosk {
+    /// Representation of this kiosk as a generic Move Object.
+    pub super_: MoveObject,
+
+    /// Deserialized representation of the Move Object's contents.
+    pub native: NativeKiosk,
+
+    /// The owner cap of the kiosk, if it was already read while looking up
+    /// the kiosk.
+    pub owner_cap: Option<KioskOwnerCap>,
+}
+
+#[derive(Clone)]
+pub(crate) struct KioskOwnerCap {
+    /// Representation of this cap as a generic Move Object.
+    pub super_: MoveObject,
+
+    /// Deserialized representation of the Move Object's contents.
+    pub native: NativeKioskOwnerCap,
+}
+
+#[derive(Clone)]
+pub(crate) struct KioskItem {
+    /// The kiosk the item is placed in.
+    pub kiosk: MoveObject,
+
+    /// The ID of the item.
+    pub address: IotaAddress,
+
+    /// The version of the dynamic object field holding the item, bounding the
+    /// version of the item.
+    pub field_version: u64,
+}
+
+/// The price an item is listed for in a kiosk.
+#[derive(SimpleObject, Clone)]
+pub(crate) struct KioskListing {
+    /// The price of the item, in NANOS. For exclusive listings, this is the
+    /// minimum price.
+    price: BigInt,
+    /// Whether the item is listed with a `PurchaseCap`, and can only be
+    /// purchased by its holder.
+    is_exclusive: bool,
+}
+
+#[derive(Clone)]
+pub(crate) struct TransferPolicy {
+    /// Representation of this policy as a generic Move Object.
+    pub super_: MoveObject,
+
+    /// Deserialized representation of the Move Object's contents.
+    pub native: NativeTransferPolicy,
+}
+
+#[derive(Clone)]
+pub(crate) struct TransferPolicyRule {
+    /// The policy the rule is attached to.
+    pub policy: MoveObject,
+
+    /// The witness type of the rule.
+    pub rule_type: TypeTag,
+}
+
+/// A `0x2::kiosk::Kiosk` object, in which items can be placed, listed and
+/// purchased.
+#[Object]
+impl Kiosk {
+    pub(crate) async fn address(&self) -> IotaAddress {
+        OwnerImpl::from(&self.super_.super_).address().await
+    }
+
+    pub(crate) async fn version(&self) -> UInt53 {
+        ObjectImpl(&self.super_.super_).version().await
+    }
+
+    /// 32-byte hash that identifies the object's contents, encoded as a Base58
+    /// string.
+    pub(crate) async fn digest(&self) -> Option<String> {
+        ObjectImpl(&self.super_.super_).digest().await
+    }
+
+    /// The owner type of this object: Immutable, Shared, Parent, Address
+    pub(crate) async fn owner(&self, ctx: &Context<'_>) -> Option<ObjectOwner> {
+        ObjectImpl(&self.super_.super_).owner(ctx).await
+    }
+
+    /// The kiosk as a generic Move object.
+    async fn as_move_object(&self) -> MoveObject {
+        self.super_.clone()
+    }
+
+    /// The address set as the owner of the kiosk by its `KioskOwnerCap`,
+    /// which is the address that created the kiosk by default.
+    async fn kiosk_owner(&self) -> IotaAddress {
+        self.native.owner.into()
+    }
+
+    /// The profits of the sales in the kiosk, in NANOS.
+    async fn profits(&self) -> BigInt {
+        BigInt::from(self.native.profits.value())
+    }
+
+    /// The number of items placed in the kiosk.
+    async fn item_count(&self) -> u32 {
+        self.native.item_count
+    }
+
+    /// The items placed in the kiosk.
+    ///
+    /// Items are stored as dynamic object fields of the kiosk, next to the
+    /// dynamic fields of listings, locks and extensions, which are skipped. A
+    /// page may hence contain fewer items than requested while `pageInfo`
+    /// still allows to continue paginating.
+    async fn items(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<object::Cursor>,
+        last: Option<u64>,
+        before: Option<object::Cursor>,
+    ) -> Result<ScanConnection<String, KioskItem>> {
+        let page = Page::from_params(ctx.data_unchecked(), first, after, last, before)?;
+        let fields = DynamicField::paginate(
+            ctx.data_unchecked(),
+            page,
+            self.super_.super_.address,
+            Some(self.super_.root_version()),
+            self.super_.super_.checkpoint_viewed_at,
+        )
+        .await
+        .extend()?;
+
+        let item_type = TypeTag::Struct(Box::new(NativeKioskItem::type_()));
+        let mut conn = ScanConnection::new(fields.has_previous_page, fields.has_next_page);
+        conn.start_cursor = fields.edges.first().map(|edge| edge.cursor.clone());
+        conn.end_cursor = fields.edges.last().map(|edge| edge.cursor.clone());
+        for edge in fields.edges {
+            let field = edge.node;
+            let tag = StructTag::from(field.super_.native.type_().clone());
+            if DynamicFieldInfo::try_extract_field_name(&tag, &field.df_kind)
+                .is_ok_and(|name_type| name_type == item_type)
+            {
+                conn.edges.push(Edge::new(edge.cursor, KioskItem {
+                    kiosk: self.super_.clone(),
+                    address: field.df_object_id,
+                    field_version: field.root_version(),
+                }));
+            }
+        }
+
+        Ok(conn)
+    }
+
+    /// The `KioskOwnerCap` giving access to the kiosk, if it is owned by the
+    /// `kioskOwner`. Only the first `maxPageSize` caps owned by the
+    /// `kioskOwner` are searched.
+    async fn owner_cap(&self, ctx: &Context<'_>) -> Result<Option<KioskOwnerCap>> {
+        if let Some(owner_cap) = &self.owner_cap {
+            return Ok(Some(owner_cap.clone()));
+        }
+
+        let limits = &ctx.data_unchecked::<ServiceConfig>().limits;
+        let caps = KioskOwnerCap::paginate(
+            ctx.data_unchecked(),
+            Page::bounded(limits.max_page_size as u64),
+            self.native.owner.into(),
+            self.super_.super_.checkpoint_viewed_at,
+        )
+        .await
+        .extend()?;
+
+        let kiosk_id = self.native.id.object_id();
+        Ok(caps
+            .edges
+            .into_iter()
+            .map(|edge| edge.node)
+            .find(|cap| cap.native.kiosk_id() == *kiosk_id))
+    }
+}
+
+/// A `0x2::kiosk::KioskOwnerCap` object, giving access to a kiosk.
+#[Object]
+impl KioskOwnerCap {
+    pub(crate) async fn address(&self) -> IotaAddress {
+        OwnerImpl::from(&self.super_.super_).address().await
+    }
+
+    pub(crate) async fn version(&self) -> UInt53 {
+        ObjectImpl(&self.super_.super_).version().await
+    }
+
+    /// 32-byte hash that identifies the object's contents, encoded as a Base58
+    /// string.
+    pub(crate) async fn digest(&self) -> Option<String> {
+        ObjectImpl(&self.super_.super_).digest().await
+    }
+
+    /// The owner type of this object: Immutable, Shared, Parent, Address
+    pub(crate) async fn owner(&self, ctx: &Context<'_>) -> Option<ObjectOwner> {
+        ObjectImpl(&self.super_.super_).owner(ctx).await
+    }
+
+    /// The cap as a generic Move object.
+    async fn as_move_object(&self) -> MoveObject {
+        self.super_.clone()
+    }
+
+    /// The kiosk the cap gives access to.
+    async fn kiosk(&self, ctx: &Context<'_>) -> Result<Option<Kiosk>> {
+        self.kiosk_impl(ctx).await.extend()
+    }
+}
+
+/// An item placed in a kiosk.
+#[Object]
+impl KioskItem {
+    /// The ID of the item.
+    async fn address(&self) -> IotaAddress {
+        self.address
+    }
+
+    /// The item object, at its latest version while placed in the kiosk.
+    async fn object(&self, ctx: &Context<'_>) -> Result<Option<MoveObject>> {
+        MoveObject::query(
+            ctx,
+            self.address,
+            Object::under_parent(self.field_version, self.kiosk.super_.checkpoint_viewed_at),
+        )
+        .await
+        .extend()
+    }
+
+    /// The listing of the item, if it is listed for sale.
+    async fn listing(&self, ctx: &Context<'_>) -> Result<Option<KioskListing>> {
+        for is_exclusive in [false, true] {
+            let name = NativeKioskListing {
+                id: ID::new(self.address.into()),
+                is_exclusive,
+            };
+            let Some(field) = kiosk_field(ctx, &self.kiosk, NativeKioskListing::type_(), &name)
+                .await
+                .extend()?
+            else {
+                continue;
+            };
+            let listing: Field<NativeKioskListing, u64> =
+                bcs::from_bytes(field.super_.native.contents())
+                    .map_err(|e| Error::Internal(format!("Failed to deserialize listing: {e}")))
+                    .extend()?;
+            return Ok(Some(KioskListing {
+                price: BigInt::from(listing.value),
+                is_exclusive,
+            }));
+        }
+        Ok(None)
+    }
+
+    /// Whether the item is locked in the kiosk, so that it can only leave the
+    /// kiosk by being purchased.
+    async fn is_locked(&self, ctx: &Context<'_>) -> Result<bool> {
+        let name = NativeKioskLock {
+            id: ID::new(self.address.into()),
+        };
+        Ok(
+            kiosk_field(ctx, &self.kiosk, NativeKioskLock::type_(), &name)
+                .await
+                .extend()?
+                .is_some(),
+        )
+    }
+}
+
+/// A `0x2::transfer_policy::TransferPolicy` object, whose rules must be
+/// satisfied for items of its type to be transferred out of a kiosk.
+#[Object]
+impl TransferPolicy {
+    pub(crate) async fn address(&self) -> IotaAddress {
+        OwnerImpl::from(&self.super_.super_).address().await
+    }
+
+    pub(crate) async fn version(&self) -> UInt53 {
+        ObjectImpl(&self.super_.super_).version().await
+    }
+
+    /// 32-byte hash that identifies the object's contents, encoded as a Base58
+    /// string.
+    pub(crate) async fn digest(&self) -> Option<String> {
+        ObjectImpl(&self.super_.super_).digest().await
+    }
+
+    /// The owner type of this object: Immutable, Shared, Parent, Address
+    pub(crate) async fn owner(&self, ctx: &Context<'_>) -> Option<ObjectOwner> {
+        ObjectImpl(&self.super_.super_).owner(ctx).await
+    }
+
+    /// The policy as a generic Move object.
+    async fn as_move_object(&self) -> MoveObject {
+        self.super_.clone()
+    }
+
+    /// The type of the items the policy applies to.
+    async fn item_type(&self) -> Option<MoveType> {
+        let tag = StructTag::from(self.super_.native.type_().clone());
+        NativeTransferPolicy::item_type(&tag)
+            .cloned()
+            .map(MoveType::new)
+    }
+
+    /// The balance collected by the rules of the policy, in NANOS.
+    async fn balance(&self) -> BigInt {
+        BigInt::from(self.native.balance.value())
+    }
+
+    /// The rules which must be satisfied to transfer an item.
+    async fn rules(&self) -> Result<Vec<TransferPolicyRule>> {
+        self.native
+            .rules
+            .contents
+            .iter()
+            .map(|rule| -> Result<TransferPolicyRule, Error> {
+                // Type names are written without `0x` prefix.
+                let rule_type = TypeTag::from_str(&format!("0x{}", rule.name)).map_err(|e| {
+                    Error::Internal(format!("Failed to parse rule type {}: {e}", rule.name))
+                })?;
+                Ok(TransferPolicyRule {
+                    policy: self.super_.clone(),
+                    rule_type,
+                })
+            })
+            .collect::<Result<_, _>>()
+            .extend()
+    }
+}
+
+/// A rule of a transfer policy.
+#[Object]
+impl TransferPolicyRule {
+    /// The witness type of the rule.
+    async fn rule_type(&self) -> MoveType {
+        MoveType::new(self.rule_type.clone())
+    }
+
+    /// The configuration of the rule, stored as a dynamic field of the policy.
+    async fn config(&self, ctx: &Context<'_>) -> Result<Option<MoveValue>> {
+        let Some(field) = kiosk_field(
+            ctx,
+            &self.policy,
+            TransferPolicyRuleKey::type_(self.rule_type.clone()),
+            &TransferPolicyRuleKey::default(),
+        )
+        .await
+        .extend()?
+        else {
+            return Ok(None);
+        };
+        Ok(match field.value(ctx).await? {
+            Some(DynamicFieldValue::MoveValue(value)) => Some(value),
+            Some(DynamicFieldValue::MoveObject(_)) | None => None,
+        })
+    }
+}
+
+impl Kiosk {
+    /// Query the database for a `page` of the kiosks whose `KioskOwnerCap` is
+    /// owned by `owner`. The page uses the same cursor type as is used for
+    /// `Object`, and is defined in terms of the caps. A cap whose kiosk can
+    /// not be found is skipped.
+    ///
+    /// `checkpoint_viewed_at` represents the checkpoint sequence number at
+    /// which this page was queried for. Each entity returned in the
+    /// connection will inherit this checkpoint, so that when viewing that
+    /// entity's state, it will be as if it was read at the same checkpoint.
+    pub(crate) async fn paginate(
+        ctx: &Context<'_>,
+        page: Page<object::Cursor>,
+        owner: IotaAddress,
+        checkpoint_viewed_at: u64,
+    ) -> Result<Connection<String, Kiosk>, Error> {
+        let caps = KioskOwnerCap::paginate(ctx.data_unchecked(), page, owner, checkpoint_viewed_at)
+            .await?;
+
+        let mut conn = Connection::new(caps.has_previous_page, caps.has_next_page);
+        for edge in caps.edges {
+            if let Some(kiosk) = edge.node.kiosk_impl(ctx).await? {
+                conn.edges.push(Edge::new(edge.cursor, kiosk));
+            }
+        }
+
+        Ok(conn)
+    }
+}
+
+impl KioskOwnerCap {
+    /// Query the database for a `page` of `KioskOwnerCap`s owned by `owner`.
+    pub(crate) async fn paginate(
+        db: &Db,
+        page: Page<object::Cursor>,
+        owner: IotaAddress,
+        checkpoint_viewed_at: u64,
+    ) -> Result<Connection<String, KioskOwnerCap>, Error> {
+        let filter = ObjectFilter {
+            type_: Some(NativeKioskOwnerCap::type_().into()),
+            owner: Some(owner),
+            ..Default::default()
+        };
+
+        Object::paginate_subtype(db, page, filter, checkpoint_viewed_at, |object| {
+            let address = object.address;
+            let move_object = MoveObject::try_from(&object).map_err(|_| {
+                Error::Internal(format!(
+                    "Expected {address} to be a KioskOwnerCap, but it's not a Move Object.",
+                ))
+            })?;
+
+            KioskOwnerCap::try_from(&move_object).map_err(|_| {
+                Error::Internal(format!(
+                    "Expected {address} to be a KioskOwnerCap, but it is not."
+                ))
+            })
+        })
+        .await
+    }
+
+    async fn kiosk_impl(&self, ctx: &Context<'_>) -> Result<Option<Kiosk>, Error> {
+        let Some(object) = MoveObject::query(
+            ctx,
+            IotaAddress::from(self.native.kiosk_id()),
+            Object::latest_at(self.super_.super_.checkpoint_viewed_at),
+        )
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        match Kiosk::try_from(&object) {
+            Ok(kiosk) => Ok(Some(Kiosk {
+                owner_cap: Some(self.clone()),
+                ..kiosk
+            })),
+            Err(KioskDowncastError::NotAKioskObject) => Ok(None),
+            Err(KioskDowncastError::Bcs(e)) => {
+                Err(Error::Internal(format!("Failed to deserialize Kiosk: {e}")))
+            }
+        }
+    }
+}
+
+/// Loads the dynamic field of `parent` (a kiosk or transfer policy) named
+/// `name` of type `name_type`, as of the version of `parent`.
+async fn kiosk_field(
+    ctx: &Context<'_>,
+    parent: &MoveObject,
+    name_type: StructTag,
+    name: &impl Serialize,
+) -> Result<Option<DynamicField>, Error> {
+    let name = DynamicFieldName {
+        type_: ExactTypeFilter(TypeTag::Struct(Box::new(name_type))),
+        bcs: Base64::from(bcs::to_bytes(name).map_err(|e| {
+            Error::Internal(format!("Failed to serialize dynamic field name: {e}"))
+        })?),
+    };
+    DynamicField::query(
+        ctx,
+        parent.super_.address,
+        Some(parent.root_version()),
+        name,
+        DynamicFieldType::DynamicField,
+        parent.super_.checkpoint_viewed_at,
+    )
+    .await
+}
+
+impl TryFrom<&MoveObject> for Kiosk {
+    type Error = KioskDowncastError;
+
+    fn try_from(move_object: &MoveObject) -> Result<Self, Self::Error> {
+        let tag = StructTag::from(move_object.native.type_().clone());
+        if !NativeKiosk::is_kiosk(&tag) {
+            return Err(KioskDowncastError::NotAKioskObject);
+        }
+
+        Ok(Self {
+            super_: move_object.clone(),
+            native: bcs::from_bytes(move_object.native.contents())
+                .map_err(KioskDowncastError::Bcs)?,
+            owner_cap: None,
+        })
+    }
+}
+
+impl TryFrom<&MoveObject> for KioskOwnerCap {
+    type Error = KioskDowncastError;
+
+    fn try_from(move_object: &MoveObject) -> Result<Self, Self::Error> {
+        let tag = StructTag::from(move_object.native.type_().clone());
+        if !NativeKioskOwnerCap::is_kiosk_owner_cap(&tag) {
+            return Err(KioskDowncastError::NotAKioskObject);
+        }
+
+        Ok(Self {
+            super_: move_object.clone(),
+            native: bcs::from_bytes(move_object.native.contents())
+                .map_err(KioskDowncastError::Bcs)?,
+        })
+    }
+}
+
+impl TryFrom<&MoveObject> for TransferPolicy {
+    type Error = KioskDowncastError;
+
+    fn try_from(move_object: &MoveObject) -> Result<Self, Self::Error> {
+        let tag = StructTag::from(move_object.native.type_().clone());
+        if !NativeTransferPolicy::is_transfer_policy(&tag) {
+            return Err(KioskDowncastError::NotAKioskObject);
+        }
+
+        Ok(Self {
+            super_: move_object.clone(),
+            native: bcs::from_bytes(move_object.native.contents())
+                .map_err(KioskDowncastError::Bcs)?,
+        })
+    }
+}
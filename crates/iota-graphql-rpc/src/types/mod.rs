@@ -26,6 +26,7 @@ pub(crate) mod gas;
 pub(crate) mod intersect;
 pub(crate) mod iota_address;
 pub(crate) mod json;
+pub(crate) mod kiosk;
 pub(crate) mod move_enum;
 pub(crate) mod move_function;
 pub(crate) mod move_module;
@@ -43,6 +44,8 @@ pub(crate) mod protocol_config;
 pub(crate) mod query;
 pub(crate) mod safe_mode;
 pub(crate) mod stake;
+pub(crate) mod stardust;
+pub(crate) mod state_override;
 pub(crate) mod storage_fund;
 pub(crate) mod string_input;
 pub(crate) mod system_parameters;
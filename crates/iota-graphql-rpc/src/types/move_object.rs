@@ -22,6 +22,7 @@ use crate::{
         display::DisplayEntry,
         dynamic_field::{DynamicField, DynamicFieldName},
         iota_address::IotaAddress,
+        kiosk::{Kiosk, KioskDowncastError, TransferPolicy},
         move_value::MoveValue,
         object::{self, Object, ObjectFilter, ObjectImpl, ObjectLookup, ObjectOwner, ObjectStatus},
         owner::OwnerImpl,
@@ -381,6 +382,30 @@ impl MoveObject {
             .extend(),
         }
     }
+
+    /// Attempts to convert the Move object into a `0x2::kiosk::Kiosk`.
+    async fn as_kiosk(&self) -> Result<Option<Kiosk>> {
+        match Kiosk::try_from(self) {
+            Ok(kiosk) => Ok(Some(kiosk)),
+            Err(KioskDowncastError::NotAKioskObject) => Ok(None),
+            Err(KioskDowncastError::Bcs(e)) => {
+                Err(Error::Internal(format!("Failed to deserialize Kiosk: {e}"))).extend()
+            }
+        }
+    }
+
+    /// Attempts to convert the Move object into a
+    /// `0x2::transfer_policy::TransferPolicy`.
+    async fn as_transfer_policy(&self) -> Result<Option<TransferPolicy>> {
+        match TransferPolicy::try_from(self) {
+            Ok(policy) => Ok(Some(policy)),
+            Err(KioskDowncastError::NotAKioskObject) => Ok(None),
+            Err(KioskDowncastError::Bcs(e)) => Err(Error::Internal(format!(
+                "Failed to deserialize TransferPolicy: {e}"
+            )))
+            .extend(),
+        }
+    }
 }
 
 impl MoveObjectImpl<'_> {
@@ -117,6 +117,25 @@ struct Linkage {
     version: UInt53,
 }
 
+/// A package among the transitive dependencies of another package, at the
+/// version that the other package links to according to its linkage table.
+#[derive(SimpleObject)]
+struct PackageDependency {
+    /// The ID on-chain of the first version of the dependency.
+    original_id: IotaAddress,
+
+    /// The ID on-chain of the version of the dependency that is linked to.
+    upgraded_id: IotaAddress,
+
+    /// The version of the dependency that is linked to.
+    version: UInt53,
+
+    /// The original IDs of the packages that this dependency refers to
+    /// directly. These are also among the transitive dependencies of the
+    /// package whose dependency graph this is part of.
+    dependencies: Vec<IotaAddress>,
+}
+
 /// Information about which previous versions of a package introduced its types.
 #[derive(SimpleObject)]
 struct TypeOrigin {
@@ -523,6 +542,78 @@ impl MovePackage {
         Some(linkage)
     }
 
+    /// The original IDs of the packages that this package's modules refer to
+    /// directly. The versions of these packages that this package links to
+    /// are recorded in its `linkage`.
+    async fn direct_dependencies(&self) -> Result<Option<Vec<IotaAddress>>> {
+        let dependencies = self.immediate_dependencies_impl().extend()?;
+        Ok(Some(dependencies))
+    }
+
+    /// The dependency graph of this package: Its transitive dependencies, at
+    /// the versions that this package links to according to its `linkage`,
+    /// each with the packages it refers to directly.
+    async fn dependency_graph(&self, ctx: &Context<'_>) -> Result<Option<Vec<PackageDependency>>> {
+        let checkpoint_viewed_at = self.checkpoint_viewed_at_impl();
+        let linkage = self.native.linkage_table();
+        let packages = futures::future::try_join_all(linkage.values().map(|upgrade_info| {
+            MovePackage::query(
+                ctx,
+                upgrade_info.upgraded_id.into(),
+                MovePackage::by_id_at(checkpoint_viewed_at),
+            )
+        }))
+        .await
+        .extend()?;
+
+        let mut graph = Vec::with_capacity(linkage.len());
+        for ((&original_id, upgrade_info), package) in linkage.iter().zip(packages) {
+            let Some(package) = package else {
+                return Err(Error::Internal(format!(
+                    "Dependency {} of package {} not found",
+                    upgrade_info.upgraded_id,
+                    self.native.id(),
+                ))
+                .extend());
+            };
+
+            graph.push(PackageDependency {
+                original_id: original_id.into(),
+                upgraded_id: upgrade_info.upgraded_id.into(),
+                version: upgrade_info.upgraded_version.value().into(),
+                dependencies: package.immediate_dependencies_impl().extend()?,
+            });
+        }
+
+        Ok(Some(graph))
+    }
+
+    /// Paginate through the packages that depend on any version of this
+    /// package, directly or transitively, according to their linkage tables.
+    /// Their publication can optionally be bounded from below with
+    /// `afterCheckpoint`, or from above with `beforeCheckpoint`.
+    async fn dependents(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<Cursor>,
+        last: Option<u64>,
+        before: Option<Cursor>,
+        filter: Option<MovePackageCheckpointFilter>,
+    ) -> Result<Connection<String, MovePackage>> {
+        let page = Page::from_params(ctx.data_unchecked(), first, after, last, before)?;
+
+        MovePackage::paginate_dependents(
+            ctx.data_unchecked(),
+            page,
+            self.native.original_package_id().into(),
+            filter,
+            self.checkpoint_viewed_at_impl(),
+        )
+        .await
+        .extend()
+    }
+
     /// The (previous) versions of this package that introduced its types.
     async fn type_origins(&self) -> Option<Vec<TypeOrigin>> {
         let type_origins = self
@@ -559,6 +650,15 @@ impl MovePackage {
             .map_err(|e| Error::Internal(format!("Error reading package: {e}")))
     }
 
+    fn immediate_dependencies_impl(&self) -> Result<Vec<IotaAddress>, Error> {
+        let dependencies = self
+            .native
+            .immediate_dependencies()
+            .map_err(|e| Error::Internal(format!("Error reading dependencies of package: {e}")))?;
+
+        Ok(dependencies.into_iter().map(IotaAddress::from).collect())
+    }
+
     /// This package was viewed at a snapshot of the chain state at this
     /// checkpoint (identified by its sequence number).
     fn checkpoint_viewed_at_impl(&self) -> u64 {
@@ -804,6 +904,90 @@ impl MovePackage {
         Ok(conn)
     }
 
+    /// Query the database for a `page` of Move packages that depend on the
+    /// package with original ID `original_id`, at any of its versions,
+    /// according to the dependencies that the indexer records from their
+    /// linkage tables. The Page uses the same cursor as
+    /// `paginate_by_checkpoint`, and the query can optionally be filtered by a
+    /// bound on the checkpoints the packages were created in.
+    pub(crate) async fn paginate_dependents(
+        db: &Db,
+        page: Page<Cursor>,
+        original_id: IotaAddress,
+        filter: Option<MovePackageCheckpointFilter>,
+        checkpoint_viewed_at: u64,
+    ) -> Result<Connection<String, MovePackage>, Error> {
+        let cursor_viewed_at = page.validate_cursor_consistency()?;
+        let checkpoint_viewed_at = cursor_viewed_at.unwrap_or(checkpoint_viewed_at);
+
+        let after_checkpoint: Option<u64> = filter
+            .as_ref()
+            .and_then(|f| f.after_checkpoint)
+            .map(|v| v.into());
+
+        // Clamp the "before checkpoint" bound by "checkpoint viewed at".
+        let before_checkpoint = filter
+            .as_ref()
+            .and_then(|f| f.before_checkpoint)
+            .map(|v| v.into())
+            .unwrap_or(u64::MAX)
+            .min(checkpoint_viewed_at + 1);
+
+        let (prev, next, results) = db
+            .execute(move |conn| {
+                let mut q = query!(
+                    r#"
+                    SELECT
+                            p.original_id,
+                            o.*
+                    FROM
+                            package_dependencies d
+                    INNER JOIN
+                            packages p
+                    ON
+                            d.package_id = p.package_id
+                    INNER JOIN
+                            objects_history o
+                    ON
+                            p.package_id = o.object_id
+                    AND     p.package_version = o.object_version
+                    AND     p.checkpoint_sequence_number = o.checkpoint_sequence_number
+                "#
+                );
+
+                q = filter!(
+                    q,
+                    format!(
+                        "d.dependency_original_id = '\\x{}'::bytea",
+                        hex::encode(original_id.into_vec())
+                    )
+                );
+                q = filter!(
+                    q,
+                    format!("o.checkpoint_sequence_number < {before_checkpoint}")
+                );
+                if let Some(after) = after_checkpoint {
+                    q = filter!(q, format!("{after} < o.checkpoint_sequence_number"));
+                }
+
+                page.paginate_raw_query::<StoredHistoryPackage>(conn, checkpoint_viewed_at, q)
+            })
+            .await?;
+
+        let mut conn = Connection::new(prev, next);
+
+        // The "checkpoint viewed at" sets a consistent upper bound for the nested
+        // queries.
+        for stored in results {
+            let cursor = stored.cursor(checkpoint_viewed_at).encode_cursor();
+            let package =
+                MovePackage::try_from_stored_history_object(stored.object, checkpoint_viewed_at)?;
+            conn.edges.push(Edge::new(cursor, package));
+        }
+
+        Ok(conn)
+    }
+
     /// `checkpoint_viewed_at` points to the checkpoint snapshot that this
     /// `MovePackage` came from. This is stored in the `MovePackage` so that
     /// related fields from the package are read from the same checkpoint
@@ -815,6 +815,46 @@ impl Object {
         filter: ObjectFilter,
         checkpoint_viewed_at: u64,
         downcast: impl Fn(Object) -> Result<T, Error>,
+    ) -> Result<Connection<String, T>, Error> {
+        Self::paginate_objects_query(
+            db,
+            page,
+            checkpoint_viewed_at,
+            move |range, page| objects_query(&filter, range, page),
+            downcast,
+        )
+        .await
+    }
+
+    /// Query the database for a `page` of some sub-type of live Objects, like
+    /// [`Object::paginate_subtype`], but with `filter_fn` applied to the raw
+    /// queries instead of an [`ObjectFilter`]. This allows for conditions that
+    /// can not be expressed through the filter, e.g. disjunctions of owners.
+    pub(crate) async fn paginate_subtype_raw<T: OutputType>(
+        db: &Db,
+        page: Page<Cursor>,
+        filter_fn: impl Fn(RawQuery) -> RawQuery + Send + 'static,
+        checkpoint_viewed_at: u64,
+        downcast: impl Fn(Object) -> Result<T, Error>,
+    ) -> Result<Connection<String, T>, Error> {
+        Self::paginate_objects_query(
+            db,
+            page,
+            checkpoint_viewed_at,
+            move |range, page| {
+                build_objects_query(View::Consistent, range, page, filter_fn, |newer| newer)
+            },
+            downcast,
+        )
+        .await
+    }
+
+    async fn paginate_objects_query<T: OutputType>(
+        db: &Db,
+        page: Page<Cursor>,
+        checkpoint_viewed_at: u64,
+        query: impl FnOnce(AvailableRange, &Page<Cursor>) -> RawQuery + Send + 'static,
+        downcast: impl Fn(Object) -> Result<T, Error>,
     ) -> Result<Connection<String, T>, Error> {
         // If cursors are provided, defer to the `checkpoint_viewed_at` in the cursor if
         // they are consistent. Otherwise, use the value from the parameter, or
@@ -829,10 +869,11 @@ impl Object {
                     return Ok::<_, diesel::result::Error>(None);
                 };
 
+                let query = query(range, &page);
                 Ok(Some(page.paginate_raw_query::<StoredHistoryObject>(
                     conn,
                     checkpoint_viewed_at,
-                    objects_query(&filter, range, &page),
+                    query,
                 )?))
             })
             .await?
@@ -41,6 +41,7 @@ use crate::{
         object::{self, Object, ObjectFilter},
         owner::Owner,
         protocol_config::ProtocolConfigs,
+        state_override::StateOverrides,
         transaction_block::{self, TransactionBlock, TransactionBlockFilter},
         transaction_metadata::TransactionMetadata,
         type_filter::ExactTypeFilter,
@@ -99,14 +100,23 @@ impl Query {
     ///     checks that prevent access to objects that are owned by
     ///     addresses other than the sender, and calling non-public,
     ///     non-entry functions, and some other checks.  Defaults to false.
+    ///
+    /// `stateOverrides` optional objects, packages, clock timestamp and
+    ///     epoch to simulate the transaction against instead of the
+    ///     on-chain state.
     async fn dry_run_transaction_block(
         &self,
         ctx: &Context<'_>,
         tx_bytes: String,
         tx_meta: Option<TransactionMetadata>,
         skip_checks: Option<bool>,
+        state_overrides: Option<StateOverrides>,
     ) -> Result<DryRunResult> {
         let skip_checks = skip_checks.unwrap_or(false);
+        let state_overrides = state_overrides
+            .map(TryInto::try_into)
+            .transpose()
+            .extend()?;
 
         let iota_sdk_client: &Option<IotaClient> = ctx
             .data()
@@ -168,6 +178,8 @@ impl Query {
             gas_objects,
             show_raw_txn_data_and_effects: Some(true),
             skip_checks: Some(skip_checks),
+            state_overrides,
+            trace: None,
         };
 
         let res = iota_sdk_client
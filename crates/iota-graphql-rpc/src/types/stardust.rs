@@ -0,0 +1,1180 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use async_graphql::{
+    connection::{Connection, CursorType, Edge},
+    *,
+};
+use iota_indexer::types::OwnerType;
+use iota_types::{
+    STARDUST_PACKAGE_ID,
+    base_types::{IotaAddress as NativeIotaAddress, ObjectID},
+    collection_types::VecMap,
+    dynamic_field::DynamicFieldType,
+    object::Owner as NativeOwner,
+    stardust::output::{
+        ALIAS_DYNAMIC_OBJECT_FIELD_KEY, ALIAS_MODULE_NAME, ALIAS_OUTPUT_MODULE_NAME,
+        ALIAS_OUTPUT_STRUCT_NAME, ALIAS_STRUCT_NAME, Alias as NativeAlias,
+        AliasOutput as NativeAliasOutput, BASIC_OUTPUT_MODULE_NAME, BASIC_OUTPUT_STRUCT_NAME,
+        BasicOutput as NativeBasicOutput, Irc27Metadata as NativeIrc27Metadata,
+        NFT_DYNAMIC_OBJECT_FIELD_KEY, NFT_MODULE_NAME, NFT_OUTPUT_MODULE_NAME,
+        NFT_OUTPUT_STRUCT_NAME, NFT_STRUCT_NAME, Nft as NativeNft, NftOutput as NativeNftOutput,
+        unlock_conditions::{
+            ExpirationUnlockCondition as NativeExpirationUnlockCondition,
+            StorageDepositReturnUnlockCondition as NativeStorageDepositReturnUnlockCondition,
+            TimelockUnlockCondition as NativeTimelockUnlockCondition,
+        },
+    },
+};
+use move_core_types::{
+    identifier::IdentStr,
+    language_storage::{StructTag, TypeTag},
+};
+
+use crate::{
+    connection::ScanConnection,
+    data::Db,
+    error::Error,
+    filter,
+    raw_query::RawQuery,
+    types::{
+        base64::Base64,
+        big_int::BigInt,
+        cursor::Page,
+        dynamic_field::{DynamicField, DynamicFieldName},
+        iota_address::IotaAddress,
+        move_object::MoveObject,
+        move_type::MoveType,
+        object::{self, Object, ObjectImpl, ObjectOwner},
+        owner::OwnerImpl,
+        type_filter::ExactTypeFilter,
+        uint53::UInt53,
+    },
+};
+
+/// The maximum number of pages of objects scanned to fill a page of outputs.
+const MAX_SCANNED_PAGES: usize = 10;
+
+pub(crate) enum StardustDowncastError {
+    NotAStardustObject,
+    Bcs(bcs::Error),
+}
+
+/// An output migrated from the Stardust ledger at genesis, holding coins,
+/// native tokens and possibly an NFT or alias until it is claimed.
+#[derive(Union, Clone)]
+pub(crate) enum StardustOutput {
+    Basic(BasicOutput),
+    Nft(NftOutput),
+    Alias(AliasOutput),
+}
+
+#[derive(Clone)]
+pub(crate) struct BasicOutput {
+    /// Representation of this output as a generic Move Object.
+    pub super_: MoveObject,
+
+    /// Deserialized representation of the Move Object's contents.
+    pub native: NativeBasicOutput,
+}
+
+#[derive(Clone)]
+pub(crate) struct NftOutput {
+    /// Representation of this output as a generic Move Object.
+    pub super_: MoveObject,
+
+    /// Deserialized representation of the Move Object's contents.
+    pub native: NativeNftOutput,
+}
+
+#[derive(Clone)]
+pub(crate) struct AliasOutput {
+    /// Representation of this output as a generic Move Object.
+    pub super_: MoveObject,
+
+    /// Deserialized representation of the Move Object's contents.
+    pub native: NativeAliasOutput,
+}
+
+#[derive(Clone)]
+pub(crate) struct Nft {
+    /// Representation of this NFT as a generic Move Object.
+    pub super_: MoveObject,
+
+    /// Deserialized representation of the Move Object's contents.
+    pub native: NativeNft,
+}
+
+#[derive(Clone)]
+pub(crate) struct Alias {
+    /// Representation of this alias as a generic Move Object.
+    pub super_: MoveObject,
+
+    /// Deserialized representation of the Move Object's contents.
+    pub native: NativeAlias,
+}
+
+/// Only the `owner` can claim the output before `unixTime` (in seconds), and
+/// only the `returnAddress` from then on.
+#[derive(SimpleObject, Clone)]
+pub(crate) struct ExpirationUnlockCondition {
+    owner: IotaAddress,
+    return_address: IotaAddress,
+    unix_time: UInt53,
+}
+
+/// Claiming the output sends `returnAmount` of its coins back to
+/// `returnAddress`.
+#[derive(SimpleObject, Clone)]
+pub(crate) struct StorageDepositReturnUnlockCondition {
+    return_address: IotaAddress,
+    return_amount: BigInt,
+}
+
+/// The output can not be claimed before `unixTime` (in seconds).
+#[derive(SimpleObject, Clone)]
+pub(crate) struct TimelockUnlockCondition {
+    unix_time: UInt53,
+}
+
+/// The immutable IRC-27 metadata of a Stardust NFT.
+#[derive(SimpleObject, Clone)]
+pub(crate) struct Irc27Metadata {
+    /// Version of the metadata standard.
+    version: String,
+    /// The media type (MIME) of the asset.
+    media_type: String,
+    /// URL pointing to the NFT file location.
+    uri: String,
+    /// Human identifiable name of the NFT.
+    name: String,
+    /// Human-readable collection name of the NFT.
+    collection_name: Option<String>,
+    /// Legacy royalty payment addresses and their share of the royalties.
+    royalties: Vec<Irc27Royalty>,
+    /// Human-readable name of the NFT creator.
+    issuer_name: Option<String>,
+    /// Human-readable description of the NFT.
+    description: Option<String>,
+    /// Additional attributes of the NFT.
+    attributes: Vec<Irc27Entry>,
+    /// Legacy non-standard metadata fields.
+    non_standard_fields: Vec<Irc27Entry>,
+}
+
+/// A royalty payment address and its share of the royalties, between 0 and 1.
+#[derive(SimpleObject, Clone)]
+pub(crate) struct Irc27Royalty {
+    address: IotaAddress,
+    share: f64,
+}
+
+#[derive(SimpleObject, Clone)]
+pub(crate) struct Irc27Entry {
+    key: String,
+    value: String,
+}
+
+/// A `0x107a::basic_output::BasicOutput` object: coins and native tokens
+/// guarded by unlock conditions.
+#[Object]
+impl BasicOutput {
+    pub(crate) async fn address(&self) -> IotaAddress {
+        OwnerImpl::from(&self.super_.super_).address().await
+    }
+
+    pub(crate) async fn version(&self) -> UInt53 {
+        ObjectImpl(&self.super_.super_).version().await
+    }
+
+    /// 32-byte hash that identifies the object's contents, encoded as a Base58
+    /// string.
+    pub(crate) async fn digest(&self) -> Option<String> {
+        ObjectImpl(&self.super_.super_).digest().await
+    }
+
+    /// The owner type of this object: Immutable, Shared, Parent, Address
+    pub(crate) async fn owner(&self, ctx: &Context<'_>) -> Option<ObjectOwner> {
+        ObjectImpl(&self.super_.super_).owner(ctx).await
+    }
+
+    /// The output as a generic Move object.
+    async fn as_move_object(&self) -> MoveObject {
+        self.super_.clone()
+    }
+
+    /// The type of the coins held by the output.
+    async fn coin_type(&self) -> Option<MoveType> {
+        coin_type(&self.super_)
+    }
+
+    /// The amount of coins held by the output.
+    async fn balance(&self) -> BigInt {
+        BigInt::from(self.native.balance.value())
+    }
+
+    /// The native token balances held by the output, keyed by the type of
+    /// the token.
+    async fn native_tokens(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<object::Cursor>,
+        last: Option<u64>,
+        before: Option<object::Cursor>,
+    ) -> Result<Connection<String, DynamicField>> {
+        native_tokens(
+            ctx,
+            &self.super_,
+            self.native.native_tokens.id.object_id(),
+            first,
+            after,
+            last,
+            before,
+        )
+        .await
+    }
+
+    async fn storage_deposit_return(&self) -> Option<StorageDepositReturnUnlockCondition> {
+        self.native.storage_deposit_return.as_ref().map(Into::into)
+    }
+
+    async fn timelock(&self) -> Option<TimelockUnlockCondition> {
+        self.native.timelock.as_ref().map(Into::into)
+    }
+
+    async fn expiration(&self) -> Option<ExpirationUnlockCondition> {
+        self.native.expiration.as_ref().map(Into::into)
+    }
+
+    /// The metadata of the output.
+    async fn metadata(&self) -> Option<Base64> {
+        self.native.metadata.clone().map(Base64::from)
+    }
+
+    /// The indexation tag of the output.
+    async fn tag(&self) -> Option<Base64> {
+        self.native.tag.clone().map(Base64::from)
+    }
+
+    /// The sender of the output.
+    async fn sender(&self) -> Option<IotaAddress> {
+        self.native.sender.map(IotaAddress::from)
+    }
+}
+
+/// A `0x107a::nft_output::NftOutput` object: an NFT, coins and native tokens
+/// guarded by unlock conditions.
+#[Object]
+impl NftOutput {
+    pub(crate) async fn address(&self) -> IotaAddress {
+        OwnerImpl::from(&self.super_.super_).address().await
+    }
+
+    pub(crate) async fn version(&self) -> UInt53 {
+        ObjectImpl(&self.super_.super_).version().await
+    }
+
+    /// 32-byte hash that identifies the object's contents, encoded as a Base58
+    /// string.
+    pub(crate) async fn digest(&self) -> Option<String> {
+        ObjectImpl(&self.super_.super_).digest().await
+    }
+
+    /// The owner type of this object: Immutable, Shared, Parent, Address
+    pub(crate) async fn owner(&self, ctx: &Context<'_>) -> Option<ObjectOwner> {
+        ObjectImpl(&self.super_.super_).owner(ctx).await
+    }
+
+    /// The output as a generic Move object.
+    async fn as_move_object(&self) -> MoveObject {
+        self.super_.clone()
+    }
+
+    /// The type of the coins held by the output.
+    async fn coin_type(&self) -> Option<MoveType> {
+        coin_type(&self.super_)
+    }
+
+    /// The amount of coins held by the output.
+    async fn balance(&self) -> BigInt {
+        BigInt::from(self.native.balance.value())
+    }
+
+    /// The native token balances held by the output, keyed by the type of
+    /// the token.
+    async fn native_tokens(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<object::Cursor>,
+        last: Option<u64>,
+        before: Option<object::Cursor>,
+    ) -> Result<Connection<String, DynamicField>> {
+        native_tokens(
+            ctx,
+            &self.super_,
+            self.native.native_tokens.id.object_id(),
+            first,
+            after,
+            last,
+            before,
+        )
+        .await
+    }
+
+    async fn storage_deposit_return(&self) -> Option<StorageDepositReturnUnlockCondition> {
+        self.native.storage_deposit_return.as_ref().map(Into::into)
+    }
+
+    async fn timelock(&self) -> Option<TimelockUnlockCondition> {
+        self.native.timelock.as_ref().map(Into::into)
+    }
+
+    async fn expiration(&self) -> Option<ExpirationUnlockCondition> {
+        self.native.expiration.as_ref().map(Into::into)
+    }
+
+    /// The NFT held by the output, stored as a dynamic object field.
+    async fn nft(&self, ctx: &Context<'_>) -> Result<Option<Nft>> {
+        let Some(object) = held_object(ctx, &self.super_, NFT_DYNAMIC_OBJECT_FIELD_KEY).await?
+        else {
+            return Ok(None);
+        };
+        match Nft::try_from(&object) {
+            Ok(nft) => Ok(Some(nft)),
+            Err(StardustDowncastError::NotAStardustObject) => Ok(None),
+            Err(StardustDowncastError::Bcs(e)) => {
+                Err(Error::Internal(format!("Failed to deserialize Nft: {e}"))).extend()
+            }
+        }
+    }
+}
+
+/// A `0x107a::alias_output::AliasOutput` object: an alias, coins and native
+/// tokens.
+#[Object]
+impl AliasOutput {
+    pub(crate) async fn address(&self) -> IotaAddress {
+        OwnerImpl::from(&self.super_.super_).address().await
+    }
+
+    pub(crate) async fn version(&self) -> UInt53 {
+        ObjectImpl(&self.super_.super_).version().await
+    }
+
+    /// 32-byte hash that identifies the object's contents, encoded as a Base58
+    /// string.
+    pub(crate) async fn digest(&self) -> Option<String> {
+        ObjectImpl(&self.super_.super_).digest().await
+    }
+
+    /// The owner type of this object: Immutable, Shared, Parent, Address
+    pub(crate) async fn owner(&self, ctx: &Context<'_>) -> Option<ObjectOwner> {
+        ObjectImpl(&self.super_.super_).owner(ctx).await
+    }
+
+    /// The output as a generic Move object.
+    async fn as_move_object(&self) -> MoveObject {
+        self.super_.clone()
+    }
+
+    /// The type of the coins held by the output.
+    async fn coin_type(&self) -> Option<MoveType> {
+        coin_type(&self.super_)
+    }
+
+    /// The amount of coins held by the output.
+    async fn balance(&self) -> BigInt {
+        BigInt::from(self.native.balance.value())
+    }
+
+    /// The native token balances held by the output, keyed by the type of
+    /// the token.
+    async fn native_tokens(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<object::Cursor>,
+        last: Option<u64>,
+        before: Option<object::Cursor>,
+    ) -> Result<Connection<String, DynamicField>> {
+        native_tokens(
+            ctx,
+            &self.super_,
+            self.native.native_tokens.id.object_id(),
+            first,
+            after,
+            last,
+            before,
+        )
+        .await
+    }
+
+    /// The alias held by the output, stored as a dynamic object field.
+    async fn alias(&self, ctx: &Context<'_>) -> Result<Option<Alias>> {
+        let Some(object) = held_object(ctx, &self.super_, ALIAS_DYNAMIC_OBJECT_FIELD_KEY).await?
+        else {
+            return Ok(None);
+        };
+        match Alias::try_from(&object) {
+            Ok(alias) => Ok(Some(alias)),
+            Err(StardustDowncastError::NotAStardustObject) => Ok(None),
+            Err(StardustDowncastError::Bcs(e)) => {
+                Err(Error::Internal(format!("Failed to deserialize Alias: {e}"))).extend()
+            }
+        }
+    }
+}
+
+/// A `0x107a::nft::Nft` object, either held by an `NftOutput` or claimed from
+/// it.
+#[Object]
+impl Nft {
+    pub(crate) async fn address(&self) -> IotaAddress {
+        OwnerImpl::from(&self.super_.super_).address().await
+    }
+
+    pub(crate) async fn version(&self) -> UInt53 {
+        ObjectImpl(&self.super_.super_).version().await
+    }
+
+    /// 32-byte hash that identifies the object's contents, encoded as a Base58
+    /// string.
+    pub(crate) async fn digest(&self) -> Option<String> {
+        ObjectImpl(&self.super_.super_).digest().await
+    }
+
+    /// The owner type of this object: Immutable, Shared, Parent, Address
+    pub(crate) async fn owner(&self, ctx: &Context<'_>) -> Option<ObjectOwner> {
+        ObjectImpl(&self.super_.super_).owner(ctx).await
+    }
+
+    /// The NFT as a generic Move object.
+    async fn as_move_object(&self) -> MoveObject {
+        self.super_.clone()
+    }
+
+    /// The Stardust sender of the NFT.
+    async fn legacy_sender(&self) -> Option<IotaAddress> {
+        self.native.legacy_sender.map(IotaAddress::from)
+    }
+
+    /// The mutable metadata of the NFT.
+    async fn metadata(&self) -> Option<Base64> {
+        self.native.metadata.clone().map(Base64::from)
+    }
+
+    /// The indexation tag of the NFT.
+    async fn tag(&self) -> Option<Base64> {
+        self.native.tag.clone().map(Base64::from)
+    }
+
+    /// The issuer of the NFT.
+    async fn immutable_issuer(&self) -> Option<IotaAddress> {
+        self.native.immutable_issuer.map(IotaAddress::from)
+    }
+
+    async fn immutable_metadata(&self) -> Irc27Metadata {
+        Irc27Metadata::from(&self.native.immutable_metadata)
+    }
+}
+
+/// A `0x107a::alias::Alias` object, either held by an `AliasOutput` or
+/// claimed from it.
+#[Object]
+impl Alias {
+    pub(crate) async fn address(&self) -> IotaAddress {
+        OwnerImpl::from(&self.super_.super_).address().await
+    }
+
+    pub(crate) async fn version(&self) -> UInt53 {
+        ObjectImpl(&self.super_.super_).version().await
+    }
+
+    /// 32-byte hash that identifies the object's contents, encoded as a Base58
+    /// string.
+    pub(crate) async fn digest(&self) -> Option<String> {
+        ObjectImpl(&self.super_.super_).digest().await
+    }
+
+    /// The owner type of this object: Immutable, Shared, Parent, Address
+    pub(crate) async fn owner(&self, ctx: &Context<'_>) -> Option<ObjectOwner> {
+        ObjectImpl(&self.super_.super_).owner(ctx).await
+    }
+
+    /// The alias as a generic Move object.
+    async fn as_move_object(&self) -> MoveObject {
+        self.super_.clone()
+    }
+
+    /// The Stardust state controller of the alias.
+    async fn legacy_state_controller(&self) -> IotaAddress {
+        self.native.legacy_state_controller.into()
+    }
+
+    /// A counter increased by 1 every time the alias was state transitioned.
+    async fn state_index(&self) -> u32 {
+        self.native.state_index
+    }
+
+    /// State metadata that can be used to store additional information.
+    async fn state_metadata(&self) -> Option<Base64> {
+        self.native.state_metadata.clone().map(Base64::from)
+    }
+
+    /// The sender of the alias.
+    async fn sender(&self) -> Option<IotaAddress> {
+        self.native.sender.map(IotaAddress::from)
+    }
+
+    /// The metadata of the alias.
+    async fn metadata(&self) -> Option<Base64> {
+        self.native.metadata.clone().map(Base64::from)
+    }
+
+    /// The issuer of the alias.
+    async fn immutable_issuer(&self) -> Option<IotaAddress> {
+        self.native.immutable_issuer.map(IotaAddress::from)
+    }
+
+    /// The immutable metadata of the alias.
+    async fn immutable_metadata(&self) -> Option<Base64> {
+        self.native.immutable_metadata.clone().map(Base64::from)
+    }
+}
+
+impl StardustOutput {
+    /// Query the database for a `page` of the Stardust outputs of `owner`: the
+    /// outputs it owns, and the outputs with an expiration unlock condition,
+    /// which are shared objects, that name it as their owner or return
+    /// address. The page uses the same cursor type as is used for `Object`.
+    /// If `claimable_at` (a unix timestamp in seconds) is provided, only the
+    /// outputs `owner` can claim at that time are returned.
+    ///
+    /// Outputs are filtered after they are read, so further pages of objects
+    /// are scanned until the page is full, up to `MAX_SCANNED_PAGES` of them.
+    /// A page may therefore contain fewer outputs than requested while its
+    /// cursors still cover all objects that were scanned.
+    ///
+    /// `checkpoint_viewed_at` represents the checkpoint sequence number at
+    /// which this page was queried for. Each entity returned in the
+    /// connection will inherit this checkpoint, so that when viewing that
+    /// entity's state, it will be as if it was read at the same checkpoint.
+    pub(crate) async fn paginate(
+        db: &Db,
+        page: Page<object::Cursor>,
+        owner: IotaAddress,
+        claimable_at: Option<u64>,
+        checkpoint_viewed_at: u64,
+    ) -> Result<ScanConnection<String, StardustOutput>, Error> {
+        let limit = page.limit();
+        let from_front = page.is_from_front();
+        let native_owner = NativeIotaAddress::from(owner);
+
+        let mut edges = vec![];
+        let mut first_scanned = None;
+        let mut last_scanned: Option<String> = None;
+        let mut has_more_before = false;
+        let mut has_more_after = false;
+        let mut scan_page = page.clone();
+        'scan: for scanned_pages in 0..MAX_SCANNED_PAGES {
+            // The package also defines the `Nft` and `Alias` objects claimed from
+            // outputs, which are skipped.
+            let objects = Object::paginate_subtype_raw(
+                db,
+                scan_page,
+                outputs_of(owner),
+                checkpoint_viewed_at,
+                |object| {
+                    let address = object.address;
+                    let move_object = MoveObject::try_from(&object).map_err(|_| {
+                        Error::Internal(format!(
+                            "Expected {address} to be a Stardust object, but it's not a Move Object.",
+                        ))
+                    })?;
+                    match StardustOutput::try_from(&move_object) {
+                        Ok(output) => Ok(Some(output)),
+                        Err(StardustDowncastError::NotAStardustObject) => Ok(None),
+                        Err(StardustDowncastError::Bcs(e)) => Err(Error::Internal(format!(
+                            "Failed to deserialize Stardust output {address}: {e}"
+                        ))),
+                    }
+                },
+            )
+            .await?;
+
+            let (has_more, has_more_behind) = if from_front {
+                (objects.has_next_page, objects.has_previous_page)
+            } else {
+                (objects.has_previous_page, objects.has_next_page)
+            };
+            if scanned_pages == 0 {
+                has_more_before = has_more_behind;
+            }
+            has_more_after = has_more;
+
+            let mut scanned = objects.edges;
+            if !from_front {
+                scanned.reverse();
+            }
+            let num_scanned = scanned.len();
+            for (i, edge) in scanned.into_iter().enumerate() {
+                first_scanned.get_or_insert_with(|| edge.cursor.clone());
+                last_scanned = Some(edge.cursor.clone());
+                let Some(output) = edge.node else {
+                    continue;
+                };
+                if output.is_listed_for(native_owner)
+                    && claimable_at.map_or(true, |unix_time| {
+                        output.is_claimable_by(native_owner, unix_time)
+                    })
+                {
+                    edges.push(Edge::new(edge.cursor, output));
+                }
+                if edges.len() == limit {
+                    has_more_after |= i + 1 < num_scanned;
+                    break 'scan;
+                }
+            }
+
+            let Some(cursor) = last_scanned.as_deref().filter(|_| has_more) else {
+                break;
+            };
+            let cursor = object::Cursor::decode_cursor(cursor)
+                .map_err(|e| Error::Internal(format!("Failed to decode object cursor: {e}")))?;
+            scan_page = page.continue_from(cursor);
+        }
+
+        if !from_front {
+            edges.reverse();
+            std::mem::swap(&mut first_scanned, &mut last_scanned);
+            std::mem::swap(&mut has_more_before, &mut has_more_after);
+        }
+        let mut conn = ScanConnection::new(has_more_before, has_more_after);
+        conn.start_cursor = first_scanned;
+        conn.end_cursor = last_scanned;
+        conn.edges = edges;
+        Ok(conn)
+    }
+
+    /// Whether the output is listed among the outputs of `address`. Outputs
+    /// with an expiration unlock condition are listed for both their owner and
+    /// their return address, all others for the address that owns them.
+    fn is_listed_for(&self, address: NativeIotaAddress) -> bool {
+        let expiration = match self {
+            StardustOutput::Basic(output) => &output.native.expiration,
+            StardustOutput::Nft(output) => &output.native.expiration,
+            StardustOutput::Alias(_) => &None,
+        };
+        match expiration {
+            Some(expiration) => expiration.owner == address || expiration.return_address == address,
+            None => self.owner() == Some(&NativeOwner::AddressOwner(address)),
+        }
+    }
+
+    fn owner(&self) -> Option<&NativeOwner> {
+        let super_ = match self {
+            StardustOutput::Basic(output) => &output.super_,
+            StardustOutput::Nft(output) => &output.super_,
+            StardustOutput::Alias(output) => &output.super_,
+        };
+        super_.super_.native_impl().map(|native| &native.owner)
+    }
+
+    /// Whether the unlock conditions of the output allow `claimer` to claim it
+    /// at `unix_time` (in seconds).
+    fn is_claimable_by(&self, claimer: NativeIotaAddress, unix_time: u64) -> bool {
+        let (timelock, expiration) = match self {
+            StardustOutput::Basic(output) => (&output.native.timelock, &output.native.expiration),
+            StardustOutput::Nft(output) => (&output.native.timelock, &output.native.expiration),
+            StardustOutput::Alias(_) => return true,
+        };
+        if timelock
+            .as_ref()
+            .is_some_and(|timelock| u64::from(timelock.unix_time) > unix_time)
+        {
+            return false;
+        }
+        match expiration {
+            Some(expiration) if u64::from(expiration.unix_time) <= unix_time => {
+                expiration.return_address == claimer
+            }
+            Some(expiration) => expiration.owner == claimer,
+            None => true,
+        }
+    }
+}
+
+/// Restricts a raw objects query to the Stardust package's objects owned by
+/// `owner`, and its shared basic and NFT outputs that contain the bytes of
+/// `owner`, a superset of the outputs with an expiration unlock condition
+/// naming `owner`. The outputs are checked exactly once they are read.
+fn outputs_of(owner: IotaAddress) -> impl Fn(RawQuery) -> RawQuery + Send + 'static {
+    let package = hex::encode(STARDUST_PACKAGE_ID.into_bytes());
+    let owner = hex::encode(owner.into_vec());
+    move |query| {
+        filter!(
+            query,
+            format!(
+                "object_type_package = '\\x{package}'::bytea AND \
+                 ((owner_id = '\\x{owner}'::bytea AND owner_type = {address_owner}) OR \
+                 (owner_type = {shared} AND object_type_module IN ('{basic}', '{nft}') AND \
+                 position('\\x{owner}'::bytea IN serialized_object) > 0))",
+                address_owner = OwnerType::Address as i16,
+                shared = OwnerType::Shared as i16,
+                basic = BASIC_OUTPUT_MODULE_NAME,
+                nft = NFT_OUTPUT_MODULE_NAME,
+            )
+        )
+    }
+}
+
+/// The coin type of a Stardust output, which is the type parameter of the
+/// output's type.
+fn coin_type(move_object: &MoveObject) -> Option<MoveType> {
+    let tag = StructTag::from(move_object.native.type_().clone());
+    tag.type_params.into_iter().next().map(MoveType::new)
+}
+
+/// The native tokens of an output are stored in a `Bag`, which is wrapped in
+/// the output, so its dynamic fields are bound by the version of the output.
+async fn native_tokens(
+    ctx: &Context<'_>,
+    output: &MoveObject,
+    bag_id: &ObjectID,
+    first: Option<u64>,
+    after: Option<object::Cursor>,
+    last: Option<u64>,
+    before: Option<object::Cursor>,
+) -> Result<Connection<String, DynamicField>> {
+    OwnerImpl {
+        address: IotaAddress::from(*bag_id),
+        checkpoint_viewed_at: output.super_.checkpoint_viewed_at,
+    }
+    .dynamic_fields(ctx, first, after, last, before, Some(output.root_version()))
+    .await
+}
+
+/// Loads the object an output holds as a dynamic object field named `key`.
+async fn held_object(
+    ctx: &Context<'_>,
+    output: &MoveObject,
+    key: &[u8],
+) -> Result<Option<MoveObject>> {
+    let name = DynamicFieldName {
+        type_: ExactTypeFilter(TypeTag::Vector(Box::new(TypeTag::U8))),
+        bcs: Base64::from(
+            bcs::to_bytes(key)
+                .map_err(|e| {
+                    Error::Internal(format!("Failed to serialize dynamic field name: {e}"))
+                })
+                .extend()?,
+        ),
+    };
+    let checkpoint_viewed_at = output.super_.checkpoint_viewed_at;
+    let Some(field) = DynamicField::query(
+        ctx,
+        output.super_.address,
+        Some(output.root_version()),
+        name,
+        DynamicFieldType::DynamicObject,
+        checkpoint_viewed_at,
+    )
+    .await
+    .extend()?
+    else {
+        return Ok(None);
+    };
+
+    MoveObject::query(
+        ctx,
+        field.df_object_id,
+        Object::under_parent(field.super_.root_version(), checkpoint_viewed_at),
+    )
+    .await
+    .extend()
+}
+
+fn is_stardust_type(tag: &StructTag, module: &IdentStr, name: &IdentStr) -> bool {
+    tag.address == STARDUST_PACKAGE_ID.into()
+        && tag.module.as_ident_str() == module
+        && tag.name.as_ident_str() == name
+}
+
+impl TryFrom<&MoveObject> for StardustOutput {
+    type Error = StardustDowncastError;
+
+    fn try_from(move_object: &MoveObject) -> Result<Self, Self::Error> {
+        let tag = StructTag::from(move_object.native.type_().clone());
+        let contents = move_object.native.contents();
+        let super_ = move_object.clone();
+        Ok(
+            if is_stardust_type(&tag, BASIC_OUTPUT_MODULE_NAME, BASIC_OUTPUT_STRUCT_NAME) {
+                StardustOutput::Basic(BasicOutput {
+                    super_,
+                    native: bcs::from_bytes(contents).map_err(StardustDowncastError::Bcs)?,
+                })
+            } else if is_stardust_type(&tag, NFT_OUTPUT_MODULE_NAME, NFT_OUTPUT_STRUCT_NAME) {
+                StardustOutput::Nft(NftOutput {
+                    super_,
+                    native: bcs::from_bytes(contents).map_err(StardustDowncastError::Bcs)?,
+                })
+            } else if is_stardust_type(&tag, ALIAS_OUTPUT_MODULE_NAME, ALIAS_OUTPUT_STRUCT_NAME) {
+                StardustOutput::Alias(AliasOutput {
+                    super_,
+                    native: bcs::from_bytes(contents).map_err(StardustDowncastError::Bcs)?,
+                })
+            } else {
+                return Err(StardustDowncastError::NotAStardustObject);
+            },
+        )
+    }
+}
+
+impl TryFrom<&MoveObject> for Nft {
+    type Error = StardustDowncastError;
+
+    fn try_from(move_object: &MoveObject) -> Result<Self, Self::Error> {
+        let tag = StructTag::from(move_object.native.type_().clone());
+        if !is_stardust_type(&tag, NFT_MODULE_NAME, NFT_STRUCT_NAME) {
+            return Err(StardustDowncastError::NotAStardustObject);
+        }
+
+        Ok(Self {
+            super_: move_object.clone(),
+            native: bcs::from_bytes(move_object.native.contents())
+                .map_err(StardustDowncastError::Bcs)?,
+        })
+    }
+}
+
+impl TryFrom<&MoveObject> for Alias {
+    type Error = StardustDowncastError;
+
+    fn try_from(move_object: &MoveObject) -> Result<Self, Self::Error> {
+        let tag = StructTag::from(move_object.native.type_().clone());
+        if !is_stardust_type(&tag, ALIAS_MODULE_NAME, ALIAS_STRUCT_NAME) {
+            return Err(StardustDowncastError::NotAStardustObject);
+        }
+
+        Ok(Self {
+            super_: move_object.clone(),
+            native: bcs::from_bytes(move_object.native.contents())
+                .map_err(StardustDowncastError::Bcs)?,
+        })
+    }
+}
+
+impl From<&NativeExpirationUnlockCondition> for ExpirationUnlockCondition {
+    fn from(expiration: &NativeExpirationUnlockCondition) -> Self {
+        Self {
+            owner: expiration.owner.into(),
+            return_address: expiration.return_address.into(),
+            unix_time: u64::from(expiration.unix_time).into(),
+        }
+    }
+}
+
+impl From<&NativeStorageDepositReturnUnlockCondition> for StorageDepositReturnUnlockCondition {
+    fn from(storage_deposit_return: &NativeStorageDepositReturnUnlockCondition) -> Self {
+        Self {
+            return_address: storage_deposit_return.return_address.into(),
+            return_amount: BigInt::from(storage_deposit_return.return_amount),
+        }
+    }
+}
+
+impl From<&NativeTimelockUnlockCondition> for TimelockUnlockCondition {
+    fn from(timelock: &NativeTimelockUnlockCondition) -> Self {
+        Self {
+            unix_time: u64::from(timelock.unix_time).into(),
+        }
+    }
+}
+
+impl From<&NativeIrc27Metadata> for Irc27Metadata {
+    fn from(metadata: &NativeIrc27Metadata) -> Self {
+        let entries = |map: &VecMap<String, String>| {
+            map.contents
+                .iter()
+                .map(|entry| Irc27Entry {
+                    key: entry.key.clone(),
+                    value: entry.value.clone(),
+                })
+                .collect()
+        };
+        Self {
+            version: metadata.version.clone(),
+            media_type: metadata.media_type.clone(),
+            uri: metadata.uri.url().to_string(),
+            name: metadata.name.clone(),
+            collection_name: metadata.collection_name.clone(),
+            royalties: metadata
+                .royalties
+                .contents
+                .iter()
+                .map(|entry| Irc27Royalty {
+                    address: entry.key.into(),
+                    // `FixedPoint32` values are fractions with 32 fractional bits.
+                    share: entry.value.value as f64 / (1u64 << 32) as f64,
+                })
+                .collect(),
+            issuer_name: metadata.issuer_name.clone(),
+            description: metadata.description.clone(),
+            attributes: entries(&metadata.attributes),
+            non_standard_fields: entries(&metadata.non_standard_fields),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iota_protocol_config::ProtocolConfig;
+    use iota_types::{
+        balance::Balance,
+        collection_types::Bag,
+        digests::TransactionDigest,
+        gas_coin::{GAS, GasCoin},
+        id::UID,
+        object::{
+            MoveObject as NativeMoveObject, OBJECT_START_VERSION, Object as NativeObject,
+            Owner as NativeOwner,
+        },
+    };
+
+    use super::*;
+
+    const NOW: u32 = 1_700_000_000;
+
+    fn random_address() -> NativeIotaAddress {
+        ObjectID::random().into()
+    }
+
+    fn move_object(tag: StructTag, contents: Vec<u8>, owner: NativeIotaAddress) -> MoveObject {
+        let native = NativeMoveObject::new_from_execution(
+            tag.into(),
+            OBJECT_START_VERSION,
+            contents,
+            &ProtocolConfig::get_for_max_version_UNSAFE(),
+        )
+        .unwrap();
+        let native = NativeObject::new_move(
+            native,
+            NativeOwner::AddressOwner(owner),
+            TransactionDigest::genesis_marker(),
+        );
+        let object = Object::from_native(IotaAddress::from(native.id()), native, 0, None);
+        let Ok(move_object) = MoveObject::try_from(&object) else {
+            panic!("Expected a Move object");
+        };
+        move_object
+    }
+
+    fn basic_output(
+        owner: NativeIotaAddress,
+        timelock: Option<NativeTimelockUnlockCondition>,
+        expiration: Option<NativeExpirationUnlockCondition>,
+    ) -> StardustOutput {
+        let output = NativeBasicOutput {
+            id: UID::new(ObjectID::random()),
+            balance: Balance::new(1_000),
+            native_tokens: Bag::default(),
+            storage_deposit_return: None,
+            timelock,
+            expiration,
+            metadata: None,
+            tag: None,
+            sender: None,
+        };
+        let object = move_object(
+            NativeBasicOutput::tag(GAS::type_tag()),
+            bcs::to_bytes(&output).unwrap(),
+            owner,
+        );
+        let Ok(output) = StardustOutput::try_from(&object) else {
+            panic!("Expected a Stardust output");
+        };
+        output
+    }
+
+    #[test]
+    fn test_decode_basic_output() {
+        let owner = random_address();
+        let return_address = random_address();
+        let bag_id = ObjectID::random();
+        let output = NativeBasicOutput {
+            id: UID::new(ObjectID::random()),
+            balance: Balance::new(1_000),
+            native_tokens: Bag {
+                id: UID::new(bag_id),
+                size: 2,
+            },
+            storage_deposit_return: Some(NativeStorageDepositReturnUnlockCondition {
+                return_address,
+                return_amount: 400,
+            }),
+            timelock: Some(NativeTimelockUnlockCondition { unix_time: NOW }),
+            expiration: Some(NativeExpirationUnlockCondition {
+                owner,
+                return_address,
+                unix_time: NOW + 60,
+            }),
+            metadata: Some(b"metadata".to_vec()),
+            tag: Some(b"tag".to_vec()),
+            sender: Some(return_address),
+        };
+        let object = move_object(
+            NativeBasicOutput::tag(GAS::type_tag()),
+            bcs::to_bytes(&output).unwrap(),
+            owner,
+        );
+
+        let Ok(StardustOutput::Basic(decoded)) = StardustOutput::try_from(&object) else {
+            panic!("Expected a basic output");
+        };
+        assert_eq!(decoded.native, output);
+        assert_eq!(*decoded.native.native_tokens.id.object_id(), bag_id);
+        assert_eq!(decoded.native.native_tokens.size, 2);
+        assert_eq!(coin_type(&object), Some(MoveType::new(GAS::type_tag())));
+
+        let storage_deposit_return = StorageDepositReturnUnlockCondition::from(
+            decoded.native.storage_deposit_return.as_ref().unwrap(),
+        );
+        assert_eq!(
+            storage_deposit_return.return_address,
+            IotaAddress::from(return_address)
+        );
+        assert_eq!(storage_deposit_return.return_amount, BigInt::from(400u64));
+
+        let timelock = TimelockUnlockCondition::from(decoded.native.timelock.as_ref().unwrap());
+        assert_eq!(u64::from(timelock.unix_time), u64::from(NOW));
+
+        let expiration =
+            ExpirationUnlockCondition::from(decoded.native.expiration.as_ref().unwrap());
+        assert_eq!(expiration.owner, IotaAddress::from(owner));
+        assert_eq!(expiration.return_address, IotaAddress::from(return_address));
+        assert_eq!(u64::from(expiration.unix_time), u64::from(NOW + 60));
+    }
+
+    #[test]
+    fn test_decode_nft_output() {
+        let owner = random_address();
+        let output = NativeNftOutput {
+            id: UID::new(ObjectID::random()),
+            balance: Balance::new(1_000),
+            native_tokens: Bag::default(),
+            storage_deposit_return: None,
+            timelock: None,
+            expiration: None,
+        };
+        let object = move_object(
+            NativeNftOutput::tag(GAS::type_tag()),
+            bcs::to_bytes(&output).unwrap(),
+            owner,
+        );
+
+        let Ok(StardustOutput::Nft(decoded)) = StardustOutput::try_from(&object) else {
+            panic!("Expected an NFT output");
+        };
+        assert_eq!(decoded.native, output);
+        assert_eq!(decoded.native.native_tokens.size, 0);
+    }
+
+    #[test]
+    fn test_decode_other_objects() {
+        let owner = random_address();
+        let coin = GasCoin::new(ObjectID::random(), 1_000);
+        let object = move_object(GasCoin::type_(), bcs::to_bytes(&coin).unwrap(), owner);
+        assert!(matches!(
+            StardustOutput::try_from(&object),
+            Err(StardustDowncastError::NotAStardustObject)
+        ));
+
+        // A Stardust output type with contents that do not match it.
+        let object = move_object(
+            NativeBasicOutput::tag(GAS::type_tag()),
+            bcs::to_bytes(&coin).unwrap(),
+            owner,
+        );
+        assert!(matches!(
+            StardustOutput::try_from(&object),
+            Err(StardustDowncastError::Bcs(_))
+        ));
+    }
+
+    #[test]
+    fn test_claimable_by_timelock() {
+        let owner = random_address();
+        let output = basic_output(owner, None, None);
+        assert!(output.is_claimable_by(owner, NOW.into()));
+
+        let output = basic_output(
+            owner,
+            Some(NativeTimelockUnlockCondition { unix_time: NOW }),
+            None,
+        );
+        assert!(!output.is_claimable_by(owner, u64::from(NOW) - 1));
+        assert!(output.is_claimable_by(owner, NOW.into()));
+    }
+
+    #[test]
+    fn test_claimable_by_expiration() {
+        let owner = random_address();
+        let return_address = random_address();
+        let output = basic_output(
+            owner,
+            None,
+            Some(NativeExpirationUnlockCondition {
+                owner,
+                return_address,
+                unix_time: NOW,
+            }),
+        );
+
+        // Only the owner can claim the output before it expires, and only the
+        // return address afterwards.
+        let before = u64::from(NOW) - 1;
+        assert!(output.is_claimable_by(owner, before));
+        assert!(!output.is_claimable_by(return_address, before));
+        assert!(!output.is_claimable_by(owner, NOW.into()));
+        assert!(output.is_claimable_by(return_address, NOW.into()));
+
+        // A timelock still applies to the return address.
+        let output = basic_output(
+            owner,
+            Some(NativeTimelockUnlockCondition {
+                unix_time: NOW + 60,
+            }),
+            Some(NativeExpirationUnlockCondition {
+                owner,
+                return_address,
+                unix_time: NOW,
+            }),
+        );
+        assert!(!output.is_claimable_by(return_address, NOW.into()));
+        assert!(output.is_claimable_by(return_address, u64::from(NOW) + 60));
+    }
+    #[test]
+    fn test_listed_for() {
+        let owner = random_address();
+        let return_address = random_address();
+        let output = basic_output(owner, None, None);
+        assert!(output.is_listed_for(owner));
+        assert!(!output.is_listed_for(return_address));
+
+        // Outputs with an expiration are listed for both addresses it names.
+        let output = basic_output(
+            owner,
+            None,
+            Some(NativeExpirationUnlockCondition {
+                owner,
+                return_address,
+                unix_time: NOW,
+            }),
+        );
+        assert!(output.is_listed_for(owner));
+        assert!(output.is_listed_for(return_address));
+        assert!(!output.is_listed_for(random_address()));
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use async_graphql::*;
+use iota_json_rpc_types::{IotaObjectOverride, IotaStateOverrides};
+
+use crate::{
+    error::Error,
+    types::{base64::Base64, iota_address::IotaAddress, uint53::UInt53},
+};
+
+/// Overrides of on-chain state a transaction dry run is simulated against.
+/// Effects and events are reported as if the overridden state were real.
+/// `clockTimestampMs` sets the timestamp of the `Clock` object and `epoch`
+/// the epoch the transaction is executed in.
+#[derive(Clone, Debug, PartialEq, Eq, InputObject)]
+pub(crate) struct StateOverrides {
+    pub objects: Option<Vec<ObjectOverride>>,
+    pub clock_timestamp_ms: Option<UInt53>,
+    pub epoch: Option<UInt53>,
+}
+
+/// Replaces the object or package at `address`. Exactly one of the other
+/// fields must be set: `bcs` replaces the whole object with a BCS-encoded
+/// `Object`, `contents` replaces the BCS contents of a Move object, `version`
+/// uses a past version of the object and `package` substitutes a package with
+/// locally compiled modules.
+#[derive(Clone, Debug, PartialEq, Eq, InputObject)]
+pub(crate) struct ObjectOverride {
+    pub address: IotaAddress,
+    pub bcs: Option<Base64>,
+    pub contents: Option<Base64>,
+    pub version: Option<UInt53>,
+    pub package: Option<PackageOverride>,
+}
+
+/// Modules of a package override and the addresses of all their transitive
+/// dependencies.
+#[derive(Clone, Debug, PartialEq, Eq, InputObject)]
+pub(crate) struct PackageOverride {
+    pub modules: Vec<Base64>,
+    pub dependencies: Vec<IotaAddress>,
+}
+
+impl TryFrom<ObjectOverride> for IotaObjectOverride {
+    type Error = Error;
+
+    fn try_from(object_override: ObjectOverride) -> Result<Self, Self::Error> {
+        let ObjectOverride {
+            address,
+            bcs,
+            contents,
+            version,
+            package,
+        } = object_override;
+        let object_id = address.into();
+        Ok(match (bcs, contents, version, package) {
+            (Some(bcs), None, None, None) => Self::Object { bcs: bcs.0 },
+            (None, Some(contents), None, None) => Self::Contents {
+                object_id,
+                contents: contents.0,
+            },
+            (None, None, Some(version), None) => Self::Version {
+                object_id,
+                version: version.into(),
+            },
+            (None, None, None, Some(package)) => Self::Package {
+                package_id: object_id,
+                modules: package.modules.into_iter().map(|m| m.0).collect(),
+                dependencies: package.dependencies.into_iter().map(Into::into).collect(),
+            },
+            _ => {
+                return Err(Error::Client(format!(
+                    "Override of {address} must set exactly one of 'bcs', 'contents', \
+                     'version' or 'package'"
+                )));
+            }
+        })
+    }
+}
+
+impl TryFrom<StateOverrides> for IotaStateOverrides {
+    type Error = Error;
+
+    fn try_from(overrides: StateOverrides) -> Result<Self, Self::Error> {
+        Ok(Self {
+            objects: overrides
+                .objects
+                .unwrap_or_default()
+                .into_iter()
+                .map(IotaObjectOverride::try_from)
+                .collect::<Result<_, _>>()?,
+            clock_timestamp_ms: overrides.clock_timestamp_ms.map(Into::into),
+            epoch: overrides.epoch.map(Into::into),
+        })
+    }
+}
@@ -4,7 +4,7 @@
 
 #[cfg(feature = "pg_integration")]
 mod tests {
-    use std::{sync::Arc, time::Duration};
+    use std::{num::NonZeroUsize, sync::Arc, time::Duration};
 
     use fastcrypto::encoding::{Base64, Encoding};
     use iota_graphql_rpc::{
@@ -12,16 +12,31 @@ mod tests {
         config::ConnectionConfig,
         test_infra::cluster::{DEFAULT_INTERNAL_DATA_SOURCE_PORT, ExecutorCluster},
     };
+    use iota_protocol_config::ProtocolConfig;
+    use iota_swarm_config::network_config_builder::ConfigBuilder;
     use iota_types::{
-        IOTA_FRAMEWORK_ADDRESS, IOTA_FRAMEWORK_PACKAGE_ID, STARDUST_ADDRESS,
-        digests::ChainIdentifier,
-        gas_coin::GAS,
+        IOTA_FRAMEWORK_ADDRESS, IOTA_FRAMEWORK_PACKAGE_ID, STARDUST_ADDRESS, TypeTag,
+        balance::Balance,
+        base_types::{IotaAddress, ObjectID},
+        collection_types::Bag,
+        digests::{ChainIdentifier, TransactionDigest},
+        dynamic_field::{DOFWrapper, DynamicFieldInfo, Field, derive_dynamic_field_id},
+        gas_coin::{GAS, GasCoin},
+        id::{ID, UID},
+        kiosk::{Kiosk, KioskItem, KioskListing, KioskLock, KioskOwnerCap},
+        object::{MoveObject, OBJECT_START_VERSION, Object, Owner},
+        stardust::output::{
+            BasicOutput,
+            unlock_conditions::{ExpirationUnlockCondition, TimelockUnlockCondition},
+        },
         transaction::{CallArg, ObjectArg, TransactionDataAPI},
     };
+    use move_core_types::language_storage::StructTag;
     use rand::{SeedableRng, rngs::StdRng};
+    use serde::Serialize;
     use serde_json::json;
     use serial_test::serial;
-    use simulacrum::Simulacrum;
+    use simulacrum::{InMemoryStore, Simulacrum};
     use tempfile::tempdir;
     use tokio::time::sleep;
 
@@ -834,6 +849,374 @@ mod tests {
         cluster.cleanup_resources().await
     }
 
+    /// Creates a Move object of type `type_` to be included in the genesis.
+    fn genesis_object(type_: StructTag, owner: Owner, contents: &impl Serialize) -> Object {
+        let move_object = MoveObject::new_from_execution(
+            type_.into(),
+            OBJECT_START_VERSION,
+            bcs::to_bytes(contents).unwrap(),
+            &ProtocolConfig::get_for_max_version_UNSAFE(),
+        )
+        .unwrap();
+        Object::new_move(move_object, owner, TransactionDigest::genesis_marker())
+    }
+
+    /// Serves a network whose genesis includes `objects`.
+    async fn serve_genesis_objects(objects: Vec<Object>) -> ExecutorCluster {
+        let mut rng = StdRng::from_seed([12; 32]);
+        let config = ConfigBuilder::new_with_temp_dir()
+            .rng(&mut rng)
+            .with_chain_start_timestamp_ms(1)
+            .deterministic_committee_size(NonZeroUsize::new(1).unwrap())
+            .with_objects(objects)
+            .build();
+        let store = InMemoryStore::new(&config.genesis);
+        let mut sim = Simulacrum::new_with_network_config_store(&config, rng, store);
+        let data_ingestion_path = tempdir().unwrap().into_path();
+        sim.set_data_ingestion_path(data_ingestion_path.clone());
+        sim.create_checkpoint();
+        sim.create_checkpoint();
+
+        let cluster = iota_graphql_rpc::test_infra::cluster::serve_executor(
+            ConnectionConfig::ci_integration_test_cfg(),
+            DEFAULT_INTERNAL_DATA_SOURCE_PORT,
+            Arc::new(sim),
+            None,
+            data_ingestion_path,
+        )
+        .await;
+        cluster
+            .wait_for_checkpoint_catchup(1, Duration::from_secs(10))
+            .await;
+        cluster
+    }
+
+    /// Creates a basic output owned by `owner`. Outputs with an expiration
+    /// are shared, as in the migration.
+    fn basic_output(
+        owner: IotaAddress,
+        timelock: Option<u32>,
+        expiration: Option<ExpirationUnlockCondition>,
+    ) -> Object {
+        let object_owner = if expiration.is_some() {
+            Owner::Shared {
+                initial_shared_version: OBJECT_START_VERSION,
+            }
+        } else {
+            Owner::AddressOwner(owner)
+        };
+        let output = BasicOutput {
+            id: UID::new(ObjectID::random()),
+            balance: Balance::new(1_000),
+            native_tokens: Bag::default(),
+            storage_deposit_return: None,
+            timelock: timelock.map(|unix_time| TimelockUnlockCondition { unix_time }),
+            expiration,
+            metadata: None,
+            tag: None,
+            sender: None,
+        };
+        genesis_object(BasicOutput::tag(GAS::type_tag()), object_owner, &output)
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_stardust_outputs_claimable_at() {
+        let owner = IotaAddress::from(ObjectID::random());
+        let other = IotaAddress::from(ObjectID::random());
+        let unlocked = basic_output(owner, None, None);
+        let timelocked = basic_output(owner, Some(2_000_000_000), None);
+        // Claimable by `owner` until 2036, and by `other` afterwards.
+        let expiring = basic_output(
+            owner,
+            None,
+            Some(ExpirationUnlockCondition {
+                owner,
+                return_address: other,
+                unix_time: 2_100_000_000,
+            }),
+        );
+        let unrelated = basic_output(
+            other,
+            None,
+            Some(ExpirationUnlockCondition {
+                owner: other,
+                return_address: other,
+                unix_time: 2_100_000_000,
+            }),
+        );
+        let cluster = serve_genesis_objects(vec![
+            unlocked.clone(),
+            timelocked.clone(),
+            expiring.clone(),
+            unrelated,
+        ])
+        .await;
+
+        let query = r#"
+            {
+                address(address: $owner) {
+                    all: stardustOutputs {
+                        nodes { ... on BasicOutput { address timelock { unixTime } } }
+                    }
+                    beforeTimelock: stardustOutputs(claimableAt: "2033-05-18T03:33:19Z") {
+                        nodes { ... on BasicOutput { address } }
+                    }
+                    atTimelock: stardustOutputs(claimableAt: "2033-05-18T03:33:20Z") {
+                        nodes { ... on BasicOutput { address } }
+                    }
+                    afterExpiration: stardustOutputs(claimableAt: "2036-07-18T22:40:00Z") {
+                        nodes { ... on BasicOutput { address } }
+                    }
+                    firstBeforeTimelock: stardustOutputs(
+                        first: 1,
+                        claimableAt: "2033-05-18T03:33:19Z"
+                    ) {
+                        nodes { ... on BasicOutput { address } }
+                    }
+                }
+            }
+        "#;
+        let variables = vec![GraphqlQueryVariable {
+            name: "owner".to_string(),
+            ty: "IotaAddress!".to_string(),
+            value: json!(owner.to_string()),
+        }];
+        let res = cluster
+            .graphql_client
+            .execute_to_graphql(query.to_string(), true, variables, vec![])
+            .await
+            .unwrap();
+        assert!(res.errors().is_empty(), "{:?}", res.errors());
+
+        let data = res.response_body().data.clone().into_json().unwrap();
+        let addresses = |field: &str| {
+            let mut addresses: Vec<_> = data["address"][field]["nodes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|node| node["address"].as_str().unwrap().to_string())
+                .collect();
+            addresses.sort();
+            addresses
+        };
+        let sorted = |objects: &[&Object]| {
+            let mut addresses: Vec<_> = objects
+                .iter()
+                .map(|object| object.id().to_string())
+                .collect();
+            addresses.sort();
+            addresses
+        };
+
+        assert_eq!(
+            addresses("all"),
+            sorted(&[&unlocked, &timelocked, &expiring])
+        );
+        assert_eq!(addresses("beforeTimelock"), sorted(&[&unlocked, &expiring]));
+        assert_eq!(
+            addresses("atTimelock"),
+            sorted(&[&unlocked, &timelocked, &expiring])
+        );
+        assert_eq!(
+            addresses("afterExpiration"),
+            sorted(&[&unlocked, &timelocked])
+        );
+        // Objects are scanned until the page is full, whatever their order.
+        assert_eq!(addresses("firstBeforeTimelock").len(), 1);
+    }
+
+    /// Creates the objects of a kiosk owned by `owner`, holding a listed
+    /// item, a locked item and an item which is neither. Returns the objects
+    /// and the IDs of the kiosk and the items, in this order.
+    fn kiosk_objects(owner: IotaAddress) -> (Vec<Object>, ObjectID, [ObjectID; 3]) {
+        let kiosk_id = ObjectID::random();
+        let item_ids = [ObjectID::random(), ObjectID::random(), ObjectID::random()];
+        let [listed, locked, _] = item_ids;
+        let kiosk_owner = Owner::ObjectOwner(kiosk_id.into());
+
+        let mut objects = vec![
+            genesis_object(
+                Kiosk::type_(),
+                Owner::Shared {
+                    initial_shared_version: OBJECT_START_VERSION,
+                },
+                &Kiosk {
+                    id: UID::new(kiosk_id),
+                    profits: Balance::new(0),
+                    owner,
+                    item_count: item_ids.len() as u32,
+                },
+            ),
+            genesis_object(
+                KioskOwnerCap::type_(),
+                Owner::AddressOwner(owner),
+                &KioskOwnerCap {
+                    id: UID::new(ObjectID::random()),
+                    for_: ID::new(kiosk_id),
+                },
+            ),
+        ];
+
+        // Items are dynamic object fields of the kiosk.
+        for item_id in item_ids {
+            let name = KioskItem {
+                id: ID::new(item_id),
+            };
+            let name_type = TypeTag::Struct(Box::new(KioskItem::type_()));
+            let wrapper_type = TypeTag::Struct(Box::new(
+                DynamicFieldInfo::dynamic_object_field_wrapper(name_type),
+            ));
+            let field_id = derive_dynamic_field_id(
+                kiosk_id,
+                &wrapper_type,
+                &bcs::to_bytes(&DOFWrapper { name }).unwrap(),
+            )
+            .unwrap();
+            objects.push(genesis_object(
+                DynamicFieldInfo::dynamic_field_type(
+                    wrapper_type,
+                    TypeTag::Struct(Box::new(ID::type_())),
+                ),
+                kiosk_owner,
+                &Field {
+                    id: UID::new(field_id),
+                    name: DOFWrapper { name },
+                    value: ID::new(item_id),
+                },
+            ));
+            objects.push(genesis_object(
+                GasCoin::type_(),
+                Owner::ObjectOwner(field_id.into()),
+                &GasCoin::new(item_id, 0),
+            ));
+        }
+
+        // Listings and locks are dynamic fields of the kiosk.
+        let name = KioskListing {
+            id: ID::new(listed),
+            is_exclusive: false,
+        };
+        objects.push(kiosk_field(
+            kiosk_id,
+            KioskListing::type_(),
+            name,
+            TypeTag::U64,
+            1_000u64,
+        ));
+        let name = KioskLock {
+            id: ID::new(locked),
+        };
+        objects.push(kiosk_field(
+            kiosk_id,
+            KioskLock::type_(),
+            name,
+            TypeTag::Bool,
+            true,
+        ));
+
+        (objects, kiosk_id, item_ids)
+    }
+
+    fn kiosk_field(
+        kiosk_id: ObjectID,
+        name_type: StructTag,
+        name: impl Serialize,
+        value_type: TypeTag,
+        value: impl Serialize,
+    ) -> Object {
+        let name_type = TypeTag::Struct(Box::new(name_type));
+        let name_bytes = bcs::to_bytes(&name).unwrap();
+        genesis_object(
+            DynamicFieldInfo::dynamic_field_type(name_type.clone(), value_type),
+            Owner::ObjectOwner(kiosk_id.into()),
+            &Field {
+                id: UID::new(derive_dynamic_field_id(kiosk_id, &name_type, &name_bytes).unwrap()),
+                name,
+                value,
+            },
+        )
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_address_kiosks() {
+        let owner = IotaAddress::from(ObjectID::random());
+        let (objects, kiosk_id, [listed, locked, placed]) = kiosk_objects(owner);
+        let cluster = serve_genesis_objects(objects).await;
+
+        let query = r#"
+            {
+                address(address: $owner) {
+                    kiosks {
+                        nodes {
+                            address
+                            kioskOwner
+                            itemCount
+                            ownerCap { kiosk { address } }
+                            items {
+                                nodes {
+                                    address
+                                    object { address }
+                                    listing { price isExclusive }
+                                    isLocked
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        "#;
+        let variables = vec![GraphqlQueryVariable {
+            name: "owner".to_string(),
+            ty: "IotaAddress!".to_string(),
+            value: json!(owner.to_string()),
+        }];
+        let res = cluster
+            .graphql_client
+            .execute_to_graphql(query.to_string(), true, variables, vec![])
+            .await
+            .unwrap();
+        assert!(res.errors().is_empty(), "{:?}", res.errors());
+
+        let data = res.response_body().data.clone().into_json().unwrap();
+        let kiosks = data["address"]["kiosks"]["nodes"].as_array().unwrap();
+        assert_eq!(kiosks.len(), 1);
+        let kiosk = &kiosks[0];
+        assert_eq!(kiosk["address"], json!(kiosk_id.to_string()));
+        assert_eq!(kiosk["kioskOwner"], json!(owner.to_string()));
+        assert_eq!(kiosk["itemCount"], json!(3));
+        assert_eq!(
+            kiosk["ownerCap"]["kiosk"]["address"],
+            json!(kiosk_id.to_string())
+        );
+
+        let mut items = kiosk["items"]["nodes"].as_array().unwrap().clone();
+        items.sort_by_key(|item| item["address"].as_str().unwrap().to_string());
+        let mut expected = vec![
+            json!({
+                "address": listed.to_string(),
+                "object": { "address": listed.to_string() },
+                "listing": { "price": "1000", "isExclusive": false },
+                "isLocked": false,
+            }),
+            json!({
+                "address": locked.to_string(),
+                "object": { "address": locked.to_string() },
+                "listing": null,
+                "isLocked": true,
+            }),
+            json!({
+                "address": placed.to_string(),
+                "object": { "address": placed.to_string() },
+                "listing": null,
+                "isLocked": false,
+            }),
+        ];
+        expected.sort_by_key(|item| item["address"].as_str().unwrap().to_string());
+        assert_eq!(items, expected);
+    }
+
     use iota_graphql_rpc::server::builder::tests::*;
 
     #[tokio::test]
@@ -887,6 +1270,18 @@ mod tests {
         test_query_complexity_metrics_impl().await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_query_quotas() {
+        test_query_quotas_impl().await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_response_cache() {
+        test_response_cache_impl().await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_health_check() {
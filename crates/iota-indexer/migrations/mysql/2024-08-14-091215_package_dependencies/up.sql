@@ -0,0 +1,17 @@
+-- The dependencies of each package, as recorded in its linkage table, to look
+-- up the packages that depend on a given package.
+-- The dependencies of the packages indexed before this table existed are
+-- backfilled from the `packages` table when the indexer writer starts.
+CREATE TABLE package_dependencies
+(
+    package_id                   BLOB          NOT NULL,
+    -- original ID of the dependency
+    dependency_original_id       BLOB          NOT NULL,
+    -- storage ID of the version of the dependency that the package links to
+    dependency_package_id        BLOB          NOT NULL,
+    dependency_version           BIGINT        NOT NULL,
+    checkpoint_sequence_number   BIGINT        NOT NULL,
+    CONSTRAINT package_dependencies_pk PRIMARY KEY (package_id(32), dependency_original_id(32))
+);
+
+CREATE INDEX package_dependencies_dependency_cp ON package_dependencies (dependency_original_id(32), checkpoint_sequence_number);
@@ -0,0 +1,17 @@
+-- The dependencies of each package, as recorded in its linkage table, to look
+-- up the packages that depend on a given package.
+-- The dependencies of the packages indexed before this table existed are
+-- backfilled from the `packages` table when the indexer writer starts.
+CREATE TABLE package_dependencies
+(
+    package_id                   bytea          NOT NULL,
+    -- original ID of the dependency
+    dependency_original_id       bytea          NOT NULL,
+    -- storage ID of the version of the dependency that the package links to
+    dependency_package_id        bytea          NOT NULL,
+    dependency_version           bigint         NOT NULL,
+    checkpoint_sequence_number   bigint         NOT NULL,
+    CONSTRAINT package_dependencies_pkey PRIMARY KEY (package_id, dependency_original_id)
+);
+
+CREATE INDEX package_dependencies_dependency_cp ON package_dependencies (dependency_original_id, checkpoint_sequence_number);
@@ -7,7 +7,7 @@ use fastcrypto::encoding::Base64;
 use iota_json_rpc::IotaRpcModule;
 use iota_json_rpc_api::{WriteApiClient, WriteApiServer, error_object_from_rpc};
 use iota_json_rpc_types::{
-    DevInspectArgs, DevInspectResults, DryRunTransactionBlockResponse,
+    DevInspectArgs, DevInspectResults, DryRunTransactionBlockResponse, IotaStateOverrides,
     IotaTransactionBlockResponse, IotaTransactionBlockResponseOptions,
 };
 use iota_open_rpc::Module;
@@ -74,9 +74,10 @@ impl WriteApiServer for WriteApi {
     async fn dry_run_transaction_block(
         &self,
         tx_bytes: Base64,
+        state_overrides: Option<IotaStateOverrides>,
     ) -> RpcResult<DryRunTransactionBlockResponse> {
         self.fullnode
-            .dry_run_transaction_block(tx_bytes)
+            .dry_run_transaction_block(tx_bytes, state_overrides)
             .await
             .map_err(error_object_from_rpc)
     }
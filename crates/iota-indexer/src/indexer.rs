@@ -132,6 +132,10 @@ impl Indexer {
             store.persist_protocol_configs_and_feature_flags(chain_id)?;
         }
 
+        // Packages indexed before their dependencies were recorded have none in
+        // the db, so the dependencies are derived from their linkage tables.
+        store.backfill_package_dependencies()?;
+
         let cancel_clone = cancel.clone();
         let (exit_sender, exit_receiver) = oneshot::channel();
         // Spawn a task that links the cancellation token to the exit sender
@@ -4,7 +4,10 @@
 
 use diesel::prelude::*;
 
-use crate::{schema::packages, types::IndexedPackage};
+use crate::{
+    schema::{package_dependencies, packages},
+    types::IndexedPackage,
+};
 
 #[derive(Queryable, Insertable, Selectable, Clone, Debug, Identifiable)]
 #[diesel(table_name = packages, primary_key(package_id))]
@@ -27,3 +30,32 @@ impl From<IndexedPackage> for StoredPackage {
         }
     }
 }
+
+/// A dependency of a package on (a version of) another package, as recorded in
+/// the linkage table of the package.
+#[derive(Queryable, Insertable, Selectable, Clone, Debug)]
+#[diesel(table_name = package_dependencies)]
+pub struct StoredPackageDependency {
+    pub package_id: Vec<u8>,
+    pub dependency_original_id: Vec<u8>,
+    pub dependency_package_id: Vec<u8>,
+    pub dependency_version: i64,
+    pub checkpoint_sequence_number: i64,
+}
+
+impl StoredPackageDependency {
+    /// The dependencies in the linkage table of package `p`.
+    pub fn from_package(p: &IndexedPackage) -> Vec<Self> {
+        p.move_package
+            .linkage_table()
+            .iter()
+            .map(|(original_id, upgrade_info)| Self {
+                package_id: p.package_id.to_vec(),
+                dependency_original_id: original_id.to_vec(),
+                dependency_package_id: upgrade_info.upgraded_id.to_vec(),
+                dependency_version: upgrade_info.upgraded_version.value() as i64,
+                checkpoint_sequence_number: p.checkpoint_sequence_number as i64,
+            })
+            .collect()
+    }
+}
@@ -18,9 +18,10 @@ mod inner {
         epoch_peak_tps, epochs, event_emit_module, event_emit_package, event_senders,
         event_struct_instantiation, event_struct_module, event_struct_name, event_struct_package,
         events, feature_flags, move_call_metrics, move_calls, objects, objects_history,
-        objects_snapshot, objects_version, packages, protocol_configs, pruner_cp_watermark,
-        transactions, tx_calls_fun, tx_calls_mod, tx_calls_pkg, tx_changed_objects,
-        tx_count_metrics, tx_digests, tx_input_objects, tx_kinds, tx_recipients, tx_senders,
+        objects_snapshot, objects_version, package_dependencies, packages, protocol_configs,
+        pruner_cp_watermark, transactions, tx_calls_fun, tx_calls_mod, tx_calls_pkg,
+        tx_changed_objects, tx_count_metrics, tx_digests, tx_input_objects, tx_kinds,
+        tx_recipients, tx_senders,
     };
 }
 
@@ -31,9 +32,9 @@ mod inner {
         chain_identifier, checkpoints, display, epochs, event_emit_module, event_emit_package,
         event_senders, event_struct_instantiation, event_struct_module, event_struct_name,
         event_struct_package, events, feature_flags, objects, objects_history, objects_snapshot,
-        objects_version, packages, protocol_configs, pruner_cp_watermark, transactions,
-        tx_calls_fun, tx_calls_mod, tx_calls_pkg, tx_changed_objects, tx_digests, tx_input_objects,
-        tx_kinds, tx_recipients, tx_senders,
+        objects_version, package_dependencies, packages, protocol_configs, pruner_cp_watermark,
+        transactions, tx_calls_fun, tx_calls_mod, tx_calls_pkg, tx_changed_objects, tx_digests,
+        tx_input_objects, tx_kinds, tx_recipients, tx_senders,
     };
 }
 
@@ -46,9 +47,9 @@ pub use inner::{
     chain_identifier, checkpoints, display, epochs, event_emit_module, event_emit_package,
     event_senders, event_struct_instantiation, event_struct_module, event_struct_name,
     event_struct_package, events, feature_flags, objects, objects_history, objects_snapshot,
-    objects_version, packages, protocol_configs, pruner_cp_watermark, transactions, tx_calls_fun,
-    tx_calls_mod, tx_calls_pkg, tx_changed_objects, tx_digests, tx_input_objects, tx_kinds,
-    tx_recipients, tx_senders,
+    objects_version, package_dependencies, packages, protocol_configs, pruner_cp_watermark,
+    transactions, tx_calls_fun, tx_calls_mod, tx_calls_pkg, tx_changed_objects, tx_digests,
+    tx_input_objects, tx_kinds, tx_recipients, tx_senders,
 };
 
 #[cfg(feature = "postgres-feature")]
@@ -235,6 +235,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    package_dependencies (package_id, dependency_original_id) {
+        package_id -> Blob,
+        dependency_original_id -> Blob,
+        dependency_package_id -> Blob,
+        dependency_version -> Bigint,
+        checkpoint_sequence_number -> Bigint,
+    }
+}
+
 diesel::table! {
     packages (package_id) {
         package_id -> Blob,
@@ -375,6 +385,7 @@ macro_rules! for_all_tables {
             objects_history,
             objects_snapshot,
             objects_version,
+            package_dependencies,
             packages,
             transactions,
             tx_calls_fun,
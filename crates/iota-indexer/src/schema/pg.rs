@@ -2377,6 +2377,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    package_dependencies (package_id, dependency_original_id) {
+        package_id -> Bytea,
+        dependency_original_id -> Bytea,
+        dependency_package_id -> Bytea,
+        dependency_version -> Int8,
+        checkpoint_sequence_number -> Int8,
+    }
+}
+
 diesel::table! {
     packages (package_id, original_id, package_version) {
         package_id -> Bytea,
@@ -2545,6 +2555,7 @@ macro_rules! for_all_tables {
             objects_history,
             objects_snapshot,
             objects_version,
+            package_dependencies,
             packages,
             protocol_configs,
             pruner_cp_watermark,
@@ -43,6 +43,9 @@ pub trait IndexerStore: Any + Clone + Sync + Send + 'static {
         chain_id: Vec<u8>,
     ) -> Result<(), IndexerError>;
 
+    // write the dependencies of packages indexed before they were recorded
+    fn backfill_package_dependencies(&self) -> Result<(), IndexerError>;
+
     async fn persist_objects(
         &self,
         object_changes: Vec<TransactionObjectChangesToCommit>,
@@ -14,7 +14,7 @@ use async_trait::async_trait;
 use diesel::upsert::excluded;
 use diesel::{
     ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl,
-    dsl::{max, min},
+    dsl::{exists, max, min, not},
     r2d2::R2D2Connection,
 };
 use downcast::Any;
@@ -47,7 +47,7 @@ use crate::{
             StoredDeletedHistoryObject, StoredDeletedObject, StoredHistoryObject, StoredObject,
             StoredObjectSnapshot,
         },
-        packages::StoredPackage,
+        packages::{StoredPackage, StoredPackageDependency},
         transactions::StoredTransaction,
     },
     on_conflict_do_update, persist_chunk_into_table, read_only_blocking,
@@ -55,9 +55,9 @@ use crate::{
         chain_identifier, checkpoints, display, epochs, event_emit_module, event_emit_package,
         event_senders, event_struct_instantiation, event_struct_module, event_struct_name,
         event_struct_package, events, feature_flags, objects, objects_history, objects_snapshot,
-        objects_version, packages, protocol_configs, pruner_cp_watermark, transactions,
-        tx_calls_fun, tx_calls_mod, tx_calls_pkg, tx_changed_objects, tx_digests, tx_input_objects,
-        tx_kinds, tx_recipients, tx_senders,
+        objects_version, package_dependencies, packages, protocol_configs, pruner_cp_watermark,
+        transactions, tx_calls_fun, tx_calls_mod, tx_calls_pkg, tx_changed_objects, tx_digests,
+        tx_input_objects, tx_kinds, tx_recipients, tx_senders,
     },
     transactional_blocking_with_retry,
     types::{
@@ -835,6 +835,10 @@ impl<T: R2D2Connection + 'static> PgIndexerStore<T> {
             .metrics
             .checkpoint_db_commit_latency_packages
             .start_timer();
+        let dependencies = packages
+            .iter()
+            .flat_map(StoredPackageDependency::from_package)
+            .collect::<Vec<_>>();
         let packages = packages
             .into_iter()
             .map(StoredPackage::from)
@@ -858,6 +862,11 @@ impl<T: R2D2Connection + 'static> PgIndexerStore<T> {
                         conn
                     );
                 }
+                // The linkage table of a package never changes, so its dependencies only
+                // need to be written once.
+                for dependencies_chunk in dependencies.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
+                    insert_or_ignore_into!(package_dependencies::table, dependencies_chunk, conn);
+                }
                 Ok::<(), IndexerError>(())
             },
             PG_DB_COMMIT_SLEEP_DURATION
@@ -871,6 +880,58 @@ impl<T: R2D2Connection + 'static> PgIndexerStore<T> {
         })
     }
 
+    /// Write the dependencies of the packages that have none recorded, which
+    /// were indexed before dependencies were. Packages without dependencies
+    /// are read again on every call, but there are few of them.
+    fn backfill_package_dependencies(&self) -> Result<(), IndexerError> {
+        let stored_packages: Vec<StoredPackage> = read_only_blocking!(&self.blocking_cp, |conn| {
+            packages::table
+                .filter(not(exists(package_dependencies::table.filter(
+                    package_dependencies::package_id.eq(packages::package_id),
+                ))))
+                .load::<StoredPackage>(conn)
+        })
+        .context("Failed reading packages without dependencies from PostgresDB")?;
+
+        let mut dependencies = vec![];
+        for stored in stored_packages {
+            let package = IndexedPackage {
+                package_id: ObjectID::from_bytes(&stored.package_id).map_err(|e| {
+                    IndexerError::PersistentStorageDataCorruption(format!(
+                        "Failed to parse package ID: {e}"
+                    ))
+                })?,
+                move_package: bcs::from_bytes(&stored.move_package).map_err(|e| {
+                    IndexerError::PersistentStorageDataCorruption(format!(
+                        "Failed to deserialize package: {e}"
+                    ))
+                })?,
+                checkpoint_sequence_number: stored.checkpoint_sequence_number as u64,
+            };
+            dependencies.extend(StoredPackageDependency::from_package(&package));
+        }
+        if dependencies.is_empty() {
+            return Ok(());
+        }
+
+        transactional_blocking_with_retry!(
+            &self.blocking_cp,
+            |conn| {
+                for dependencies_chunk in dependencies.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
+                    insert_or_ignore_into!(package_dependencies::table, dependencies_chunk, conn);
+                }
+                Ok::<(), IndexerError>(())
+            },
+            PG_DB_COMMIT_SLEEP_DURATION
+        )
+        .tap_ok(|_| {
+            info!("Backfilled {} package dependencies", dependencies.len());
+        })
+        .tap_err(|e| {
+            tracing::error!("Failed to backfill package dependencies with error: {}", e);
+        })
+    }
+
     async fn persist_event_indices_chunk(
         &self,
         indices: Vec<EventIndex>,
@@ -2161,6 +2222,10 @@ impl<T: R2D2Connection> IndexerStore for PgIndexerStore<T> {
 
     /// Persist protocol configs and feature flags until the protocol version
     /// for the latest epoch we have stored in the db, inclusive.
+    fn backfill_package_dependencies(&self) -> Result<(), IndexerError> {
+        self.backfill_package_dependencies()
+    }
+
     fn persist_protocol_configs_and_feature_flags(
         &self,
         chain_id: Vec<u8>,
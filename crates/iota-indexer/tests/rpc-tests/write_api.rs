@@ -83,7 +83,7 @@ fn dry_run_transaction_block() {
             prepare_and_sign_tx(sender, receiver, cluster, client, objects[0], gas).await;
 
         let dry_run_tx_block_resp = client
-            .dry_run_transaction_block(tx_bytes.clone())
+            .dry_run_transaction_block(tx_bytes.clone(), None)
             .await
             .unwrap();
 
@@ -4,7 +4,7 @@
 
 use fastcrypto::encoding::Base64;
 use iota_json_rpc_types::{
-    DevInspectArgs, DevInspectResults, DryRunTransactionBlockResponse,
+    DevInspectArgs, DevInspectResults, DryRunTransactionBlockResponse, IotaStateOverrides,
     IotaTransactionBlockResponse, IotaTransactionBlockResponseOptions,
 };
 use iota_open_rpc_macros::open_rpc;
@@ -61,9 +61,12 @@ pub trait WriteApi {
 
     /// Return transaction execution effects including the gas cost summary,
     /// while the effects are not committed to the chain.
+    #[rustfmt::skip]
     #[method(name = "dryRunTransactionBlock")]
     async fn dry_run_transaction_block(
         &self,
         tx_bytes: Base64,
+        /// Overrides of on-chain objects, packages, clock and epoch to run the transaction against
+        state_overrides: Option<IotaStateOverrides>,
     ) -> RpcResult<DryRunTransactionBlockResponse>;
 }
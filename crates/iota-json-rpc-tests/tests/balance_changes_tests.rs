@@ -41,7 +41,7 @@ async fn test_dry_run_publish_with_mocked_coin() -> Result<(), anyhow::Error> {
 
     let result = client
         .read_api()
-        .dry_run_transaction_block(transaction_bytes)
+        .dry_run_transaction_block(transaction_bytes, None)
         .await;
 
     // Dry run balance change should not fail because of mocked coin
@@ -371,7 +371,7 @@ async fn staking_multiple_coins() -> Result<(), anyhow::Error> {
     let (tx_bytes, signatures) = tx.to_tx_bytes_and_signatures();
 
     let dryrun_response = http_client
-        .dry_run_transaction_block(tx_bytes.clone())
+        .dry_run_transaction_block(tx_bytes.clone(), None)
         .await?;
 
     let executed_response = http_client
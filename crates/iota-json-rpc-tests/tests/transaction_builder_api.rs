@@ -87,7 +87,9 @@ async fn test_public_transfer_object() -> Result<(), anyhow::Error> {
         .sign_transaction(&transaction_bytes.clone().to_data()?);
     let (tx_bytes, _signatures) = tx.to_tx_bytes_and_signatures();
 
-    let dryrun_response = http_client.dry_run_transaction_block(tx_bytes).await?;
+    let dryrun_response = http_client
+        .dry_run_transaction_block(tx_bytes, None)
+        .await?;
 
     let tx_response = execute_tx(&cluster, http_client, transaction_bytes)
         .await
@@ -0,0 +1,121 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use fastcrypto::encoding::Base64;
+use iota_types::{
+    base_types::{EpochId, ObjectID, SequenceNumber},
+    iota_serde::{BigInt, SequenceNumber as AsSequenceNumber},
+    object::Object,
+    state_override::{ObjectOverride, StateOverrides},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// Overrides of on-chain state to simulate a transaction against. Effects and
+/// events are reported as if the overridden state were real.
+#[serde_as]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "StateOverrides", rename_all = "camelCase")]
+pub struct IotaStateOverrides {
+    /// Objects and packages to use instead of their on-chain counterparts.
+    #[serde(default)]
+    pub objects: Vec<IotaObjectOverride>,
+    /// Timestamp of the `Clock` object seen by the transaction.
+    #[schemars(with = "Option<BigInt<u64>>")]
+    #[serde_as(as = "Option<BigInt<u64>>")]
+    #[serde(default)]
+    pub clock_timestamp_ms: Option<u64>,
+    /// Epoch the transaction is executed in.
+    #[schemars(with = "Option<BigInt<u64>>")]
+    #[serde_as(as = "Option<BigInt<u64>>")]
+    #[serde(default)]
+    pub epoch: Option<EpochId>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "ObjectOverride", rename_all = "camelCase", tag = "type")]
+pub enum IotaObjectOverride {
+    /// Replaces an object with the given BCS encoded object, including its
+    /// version, owner and contents.
+    #[serde(rename_all = "camelCase")]
+    Object {
+        #[serde_as(as = "Base64")]
+        #[schemars(with = "Base64")]
+        bcs: Vec<u8>,
+    },
+    /// Replaces the BCS contents of a Move object, keeping its type, owner and
+    /// version.
+    #[serde(rename_all = "camelCase")]
+    Contents {
+        object_id: ObjectID,
+        #[serde_as(as = "Base64")]
+        #[schemars(with = "Base64")]
+        contents: Vec<u8>,
+    },
+    /// Uses a past version of an object instead of the latest one.
+    #[serde(rename_all = "camelCase")]
+    Version {
+        object_id: ObjectID,
+        #[schemars(with = "AsSequenceNumber")]
+        #[serde_as(as = "AsSequenceNumber")]
+        version: SequenceNumber,
+    },
+    /// Replaces a package with locally compiled modules, as if it had been
+    /// upgraded in place.
+    #[serde(rename_all = "camelCase")]
+    Package {
+        package_id: ObjectID,
+        #[serde_as(as = "Vec<Base64>")]
+        #[schemars(with = "Vec<Base64>")]
+        modules: Vec<Vec<u8>>,
+        /// IDs of all transitive dependencies of the modules.
+        dependencies: Vec<ObjectID>,
+    },
+}
+
+impl TryFrom<IotaObjectOverride> for ObjectOverride {
+    type Error = bcs::Error;
+
+    fn try_from(object_override: IotaObjectOverride) -> Result<Self, Self::Error> {
+        Ok(match object_override {
+            IotaObjectOverride::Object { bcs } => Self::Object(bcs::from_bytes::<Object>(&bcs)?),
+            IotaObjectOverride::Contents {
+                object_id,
+                contents,
+            } => Self::Contents {
+                object_id,
+                contents,
+            },
+            IotaObjectOverride::Version { object_id, version } => {
+                Self::Version { object_id, version }
+            }
+            IotaObjectOverride::Package {
+                package_id,
+                modules,
+                dependencies,
+            } => Self::Package {
+                package_id,
+                modules,
+                dependencies,
+            },
+        })
+    }
+}
+
+impl TryFrom<IotaStateOverrides> for StateOverrides {
+    type Error = bcs::Error;
+
+    fn try_from(overrides: IotaStateOverrides) -> Result<Self, Self::Error> {
+        Ok(Self {
+            objects: overrides
+                .objects
+                .into_iter()
+                .map(ObjectOverride::try_from)
+                .collect::<Result<_, _>>()?,
+            clock_timestamp_ms: overrides.clock_timestamp_ms,
+            epoch: overrides.epoch,
+        })
+    }
+}
@@ -21,8 +21,9 @@ use iota_types::{
     effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents},
     error::{ExecutionError, IotaError, IotaResult},
     event::EventID,
+    execution::ExecutionTrace,
     execution_status::ExecutionStatus,
-    gas::GasCostSummary,
+    gas::{GasBreakdown, GasCostSummary},
     iota_serde::{
         BigInt, IotaTypeTag as AsIotaTypeTag, Readable, SequenceNumber as AsSequenceNumber,
     },
@@ -56,7 +57,7 @@ use tabled::{
 };
 
 use crate::{
-    Filter, IotaEvent, IotaObjectRef, Page, balance_changes::BalanceChange,
+    Filter, IotaEvent, IotaObjectRef, IotaStateOverrides, Page, balance_changes::BalanceChange,
     iota_transaction::GenericSignature::Signature, object_changes::ObjectChange,
 };
 
@@ -1061,6 +1062,10 @@ pub struct DryRunTransactionBlockResponse {
     pub object_changes: Vec<ObjectChange>,
     pub balance_changes: Vec<BalanceChange>,
     pub input: IotaTransactionBlockData,
+    /// Computation cost per command and per top-level Move function of a
+    /// programmable transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_breakdown: Option<GasBreakdown>,
 }
 
 #[derive(Eq, PartialEq, Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
@@ -1152,6 +1157,12 @@ pub struct DevInspectArgs {
     pub skip_checks: Option<bool>,
     /// Whether to return the raw transaction data and effects.
     pub show_raw_txn_data_and_effects: Option<bool>,
+    /// Overrides of on-chain state to run the transaction against.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<IotaStateOverrides>,
+    /// Whether to return a trace of the Move execution of each command.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<bool>,
 }
 
 /// The response from processing a dev inspect transaction
@@ -1179,6 +1190,13 @@ pub struct DevInspectResults {
     /// The raw effects of the transaction that was dev inspected.
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub raw_effects: Vec<u8>,
+    /// Computation cost per command and per top-level Move function of a
+    /// programmable transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_breakdown: Option<GasBreakdown>,
+    /// Trace of the Move execution of each command, if requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<ExecutionTrace>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -1207,6 +1225,8 @@ impl DevInspectResults {
         return_values: Result<Vec<ExecutionResult>, ExecutionError>,
         raw_txn_data: Vec<u8>,
         raw_effects: Vec<u8>,
+        gas_breakdown: Option<GasBreakdown>,
+        trace: Option<ExecutionTrace>,
         resolver: &mut dyn LayoutResolver,
     ) -> IotaResult<Self> {
         let tx_digest = *effects.transaction_digest();
@@ -1243,6 +1263,8 @@ impl DevInspectResults {
             error,
             raw_txn_data,
             raw_effects,
+            gas_breakdown,
+            trace,
         })
     }
 }
@@ -11,6 +11,7 @@ pub use iota_governance::*;
 pub use iota_move::*;
 pub use iota_object::*;
 pub use iota_protocol::*;
+pub use iota_state_override::*;
 pub use iota_transaction::*;
 use iota_types::{base_types::ObjectID, dynamic_field::DynamicFieldInfo};
 pub use object_changes::*;
@@ -31,6 +32,7 @@ mod iota_governance;
 mod iota_move;
 mod iota_object;
 mod iota_protocol;
+mod iota_state_override;
 mod iota_transaction;
 mod object_changes;
 
@@ -43,6 +43,7 @@ use iota_types::{
         VerifiedCheckpoint,
     },
     object::{Object, ObjectRead, PastObjectRead},
+    state_override::StateOverrides,
     storage::{BackingPackageStore, ObjectStore, WriteKind},
     timelock::timelocked_staked_iota::TimelockedStakedIota,
     transaction::{Transaction, TransactionData, TransactionKind},
@@ -124,11 +125,13 @@ pub trait StateRead: Send + Sync {
         &self,
         transaction: TransactionData,
         transaction_digest: TransactionDigest,
+        state_overrides: StateOverrides,
     ) -> StateReadResult<(
         DryRunTransactionBlockResponse,
         BTreeMap<ObjectID, (ObjectRef, Object, WriteKind)>,
         TransactionEffects,
         Option<ObjectID>,
+        Vec<Object>,
     )>;
 
     async fn dev_inspect_transaction_block(
@@ -141,6 +144,8 @@ pub trait StateRead: Send + Sync {
         gas_objects: Option<Vec<ObjectRef>>,
         show_raw_txn_data_and_effects: Option<bool>,
         skip_checks: Option<bool>,
+        state_overrides: Option<StateOverrides>,
+        trace: Option<bool>,
     ) -> StateReadResult<DevInspectResults>;
 
     // indexer_api
@@ -358,14 +363,16 @@ impl StateRead for AuthorityState {
         &self,
         transaction: TransactionData,
         transaction_digest: TransactionDigest,
+        state_overrides: StateOverrides,
     ) -> StateReadResult<(
         DryRunTransactionBlockResponse,
         BTreeMap<ObjectID, (ObjectRef, Object, WriteKind)>,
         TransactionEffects,
         Option<ObjectID>,
+        Vec<Object>,
     )> {
         Ok(self
-            .dry_exec_transaction(transaction, transaction_digest)
+            .dry_exec_transaction_with_overrides(transaction, transaction_digest, state_overrides)
             .await?)
     }
 
@@ -379,9 +386,11 @@ impl StateRead for AuthorityState {
         gas_objects: Option<Vec<ObjectRef>>,
         show_raw_txn_data_and_effects: Option<bool>,
         skip_checks: Option<bool>,
+        state_overrides: Option<StateOverrides>,
+        trace: Option<bool>,
     ) -> StateReadResult<DevInspectResults> {
         Ok(self
-            .dev_inspect_transaction_block(
+            .dev_inspect_transaction_block_with_overrides(
                 sender,
                 transaction_kind,
                 gas_price,
@@ -390,6 +399,8 @@ impl StateRead for AuthorityState {
                 gas_objects,
                 show_raw_txn_data_and_effects,
                 skip_checks,
+                state_overrides,
+                trace,
             )
             .await?)
     }
@@ -18,6 +18,7 @@ use iota_open_rpc::Module;
 use iota_transaction_builder::{DataReader, TransactionBuilder};
 use iota_types::{
     base_types::{IotaAddress, ObjectID, ObjectInfo},
+    dynamic_field::DynamicFieldInfo,
     iota_serde::BigInt,
 };
 use jsonrpsee::{RpcModule, core::RpcResult};
@@ -76,6 +77,19 @@ impl DataReader for AuthorityStateDataReader {
         let epoch_store = self.0.load_epoch_store_one_call_per_task();
         Ok(epoch_store.reference_gas_price())
     }
+
+    async fn get_dynamic_fields(
+        &self,
+        object_id: ObjectID,
+    ) -> Result<Vec<DynamicFieldInfo>, anyhow::Error> {
+        Ok(self
+            .0
+            // DataReader is used internally, don't need a limit
+            .get_dynamic_fields(object_id, None, usize::MAX)?
+            .into_iter()
+            .map(|(_, info)| info)
+            .collect())
+    }
 }
 
 #[async_trait]
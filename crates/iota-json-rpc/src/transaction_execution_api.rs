@@ -12,8 +12,9 @@ use iota_core::{
 };
 use iota_json_rpc_api::{JsonRpcMetrics, WriteApiOpenRpc, WriteApiServer};
 use iota_json_rpc_types::{
-    DevInspectArgs, DevInspectResults, DryRunTransactionBlockResponse, IotaTransactionBlock,
-    IotaTransactionBlockEvents, IotaTransactionBlockResponse, IotaTransactionBlockResponseOptions,
+    DevInspectArgs, DevInspectResults, DryRunTransactionBlockResponse, IotaStateOverrides,
+    IotaTransactionBlock, IotaTransactionBlockEvents, IotaTransactionBlockResponse,
+    IotaTransactionBlockResponseOptions,
 };
 use iota_metrics::spawn_monitored_task;
 use iota_open_rpc::Module;
@@ -27,6 +28,7 @@ use iota_types::{
         ExecuteTransactionRequestType, ExecuteTransactionRequestV1, ExecuteTransactionResponseV1,
     },
     signature::GenericSignature,
+    state_override::StateOverrides,
     storage::PostExecutionPackageResolver,
     transaction::{
         InputObjectKind, Transaction, TransactionData, TransactionDataAPI, TransactionKind,
@@ -281,15 +283,30 @@ impl TransactionExecutionApi {
     async fn dry_run_transaction_block(
         &self,
         tx_bytes: Base64,
+        state_overrides: Option<IotaStateOverrides>,
     ) -> Result<DryRunTransactionBlockResponse, Error> {
         let (txn_data, txn_digest, input_objs) =
             self.prepare_dry_run_transaction_block(tx_bytes)?;
+        let state_overrides = state_overrides
+            .map(StateOverrides::try_from)
+            .transpose()
+            .map_err(IotaRpcInputError::from)?
+            .unwrap_or_default();
         let sender = txn_data.sender();
-        let (resp, written_objects, transaction_effects, mock_gas) = self
+        let (resp, written_objects, transaction_effects, mock_gas, override_objects) = self
             .state
-            .dry_exec_transaction(txn_data.clone(), txn_digest)
+            .dry_exec_transaction(txn_data.clone(), txn_digest, state_overrides)
             .await?;
-        let object_cache = ObjectProviderCache::new_with_cache(self.state.clone(), written_objects);
+        // Overridden objects are not in the store, so balance and object changes
+        // have to be computed against them as well.
+        let object_cache = ObjectProviderCache::new_with_output_objects(
+            self.state.clone(),
+            written_objects
+                .into_values()
+                .map(|(_, object, _)| object)
+                .chain(override_objects)
+                .collect(),
+        );
         let balance_changes = get_balance_changes_from_effect(
             &object_cache,
             &transaction_effects,
@@ -312,6 +329,7 @@ impl TransactionExecutionApi {
             object_changes,
             balance_changes,
             input: resp.input,
+            gas_breakdown: resp.gas_breakdown,
         })
     }
 }
@@ -337,7 +355,7 @@ impl WriteApiServer for TransactionExecutionApi {
         sender_address: IotaAddress,
         tx_bytes: Base64,
         gas_price: Option<BigInt<u64>>,
-        _epoch: Option<BigInt<u64>>,
+        epoch: Option<BigInt<u64>>,
         additional_args: Option<DevInspectArgs>,
     ) -> RpcResult<DevInspectResults> {
         async move {
@@ -347,8 +365,20 @@ impl WriteApiServer for TransactionExecutionApi {
                 gas_objects,
                 show_raw_txn_data_and_effects,
                 skip_checks,
+                state_overrides,
+                trace,
             } = additional_args.unwrap_or_default();
             let tx_kind: TransactionKind = self.convert_bytes(tx_bytes)?;
+            let mut state_overrides = state_overrides
+                .map(StateOverrides::try_from)
+                .transpose()
+                .map_err(IotaRpcInputError::from)?;
+            if let Some(epoch) = epoch {
+                state_overrides
+                    .get_or_insert_default()
+                    .epoch
+                    .get_or_insert(*epoch);
+            }
             self.state
                 .dev_inspect_transaction_block(
                     sender_address,
@@ -359,6 +389,8 @@ impl WriteApiServer for TransactionExecutionApi {
                     gas_objects,
                     show_raw_txn_data_and_effects,
                     skip_checks,
+                    state_overrides,
+                    trace,
                 )
                 .await
                 .map_err(Error::from)
@@ -371,8 +403,11 @@ impl WriteApiServer for TransactionExecutionApi {
     async fn dry_run_transaction_block(
         &self,
         tx_bytes: Base64,
+        state_overrides: Option<IotaStateOverrides>,
     ) -> RpcResult<DryRunTransactionBlockResponse> {
-        self.dry_run_transaction_block(tx_bytes).trace().await
+        self.dry_run_transaction_block(tx_bytes, state_overrides)
+            .trace()
+            .await
     }
 }
 
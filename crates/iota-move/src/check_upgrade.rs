@@ -0,0 +1,32 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::Parser;
+use iota_types::base_types::ObjectID;
+
+/// Check whether the package at the given path could be published as an
+/// upgrade of a package on chain, under each upgrade policy, without
+/// publishing it.
+///
+/// This command requires a network connection, and is executed by the `iota`
+/// binary.
+#[derive(Parser)]
+#[group(id = "iota-move-check-upgrade")]
+pub struct CheckUpgrade {
+    /// The ID of the version of the package on chain to check the upgrade
+    /// against. Defaults to the address the package is published at,
+    /// according to its `Move.lock` or `Move.toml`.
+    #[clap(long = "package-id", value_parser = ObjectID::from_hex_literal)]
+    pub package_id: Option<ObjectID>,
+    /// Also include the modules of dependencies that have not been published
+    /// yet, as `iota client upgrade --with-unpublished-dependencies` would.
+    #[clap(long)]
+    pub with_unpublished_dependencies: bool,
+    /// Do not check that the sources of dependencies match their bytecode on
+    /// chain.
+    #[clap(long)]
+    pub skip_dependency_verification: bool,
+    /// Return command outputs in json format.
+    #[clap(long)]
+    pub json: bool,
+}
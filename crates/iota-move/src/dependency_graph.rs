@@ -0,0 +1,26 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::Parser;
+use iota_types::base_types::ObjectID;
+
+/// Show the dependency graph of a package published on chain: The versions of
+/// its transitive dependencies that it links to, which of them each package
+/// refers to directly, and the packages that depend on it.
+///
+/// This command requires a network connection, and is executed by the `iota`
+/// binary.
+#[derive(Parser)]
+#[group(id = "iota-move-dependency-graph")]
+pub struct DependencyGraph {
+    /// The ID of the package on chain.
+    #[clap(long = "package-id", value_parser = ObjectID::from_hex_literal)]
+    pub package_id: ObjectID,
+    /// The URL of a GraphQL service to look up the packages that depend on
+    /// the package from. Dependents are not listed if this is not set.
+    #[clap(long)]
+    pub graphql: Option<String>,
+    /// Return command outputs in json format.
+    #[clap(long)]
+    pub json: bool,
+}
@@ -12,8 +12,10 @@ use move_package::BuildConfig;
 
 #[cfg(feature = "build")]
 pub mod build;
+pub mod check_upgrade;
 #[cfg(feature = "coverage")]
 pub mod coverage;
+pub mod dependency_graph;
 #[cfg(feature = "disassemble")]
 pub mod disassemble;
 pub mod manage_package;
@@ -26,8 +28,10 @@ pub mod unit_test;
 pub enum Command {
     #[cfg(feature = "build")]
     Build(build::Build),
+    CheckUpgrade(check_upgrade::CheckUpgrade),
     #[cfg(feature = "coverage")]
     Coverage(coverage::Coverage),
+    DependencyGraph(dependency_graph::DependencyGraph),
     #[cfg(feature = "disassemble")]
     Disassemble(disassemble::Disassemble),
     ManagePackage(manage_package::ManagePackage),
@@ -55,6 +59,11 @@ pub fn execute_move_command(
     match command {
         #[cfg(feature = "build")]
         Command::Build(c) => c.execute(package_path, build_config),
+        Command::CheckUpgrade(_) | Command::DependencyGraph(_) => {
+            anyhow::bail!(
+                "This command requires a network connection, and can only be run as `iota move`"
+            )
+        }
         #[cfg(feature = "coverage")]
         Command::Coverage(c) => c.execute(package_path, build_config),
         #[cfg(feature = "disassemble")]
@@ -19,12 +19,13 @@ use tokio::{
 use super::{
     Discovery, DiscoveryEventLoop, DiscoveryServer, State, metrics::Metrics, server::Server,
 };
-use crate::discovery::TrustedPeerChangeEvent;
+use crate::{discovery::TrustedPeerChangeEvent, peer_table::PeerTable};
 
 /// Discovery Service Builder.
 pub struct Builder {
     config: Option<P2pConfig>,
     metrics: Option<Metrics>,
+    peer_table: Option<Arc<PeerTable>>,
     trusted_peer_change_rx: watch::Receiver<TrustedPeerChangeEvent>,
 }
 
@@ -33,6 +34,7 @@ impl Builder {
         Self {
             config: None,
             metrics: None,
+            peer_table: None,
             trusted_peer_change_rx,
         }
     }
@@ -47,6 +49,13 @@ impl Builder {
         self
     }
 
+    /// Sets the table peers are persisted to. If unset, discovered peers are
+    /// only kept in memory.
+    pub fn peer_table(mut self, peer_table: Arc<PeerTable>) -> Self {
+        self.peer_table = Some(peer_table);
+        self
+    }
+
     pub fn build(self) -> (UnstartedDiscovery, DiscoveryServer<impl Discovery>) {
         let discovery_config = self
             .config
@@ -72,10 +81,16 @@ impl Builder {
         let Builder {
             config,
             metrics,
+            peer_table,
             trusted_peer_change_rx,
         } = self;
         let config = config.unwrap();
         let metrics = metrics.unwrap_or_else(Metrics::disabled);
+        let peer_table = peer_table.unwrap_or_else(|| {
+            Arc::new(PeerTable::new_in_memory(
+                &config.state_sync.clone().unwrap_or_default(),
+            ))
+        });
         let (sender, receiver) = oneshot::channel();
 
         let handle = Handle {
@@ -102,6 +117,7 @@ impl Builder {
                 state,
                 trusted_peer_change_rx,
                 metrics,
+                peer_table,
             },
             server,
         )
@@ -116,6 +132,7 @@ pub struct UnstartedDiscovery {
     pub(super) state: Arc<RwLock<State>>,
     pub(super) trusted_peer_change_rx: watch::Receiver<TrustedPeerChangeEvent>,
     pub(super) metrics: Metrics,
+    pub(super) peer_table: Arc<PeerTable>,
 }
 
 impl UnstartedDiscovery {
@@ -127,6 +144,7 @@ impl UnstartedDiscovery {
             state,
             trusted_peer_change_rx,
             metrics,
+            peer_table,
         } = self;
 
         let discovery_config = config.discovery.clone().unwrap_or_default();
@@ -156,6 +174,7 @@ impl UnstartedDiscovery {
                 state,
                 trusted_peer_change_rx,
                 metrics,
+                peer_table,
             },
             handle,
         )
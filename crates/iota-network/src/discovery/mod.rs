@@ -44,6 +44,7 @@ pub use generated::{
 pub use server::GetKnownPeersResponse;
 
 use self::metrics::Metrics;
+use crate::peer_table::PeerTable;
 
 /// The internal discovery state shared between the main event loop and the
 /// request handler
@@ -89,6 +90,7 @@ struct DiscoveryEventLoop {
     state: Arc<RwLock<State>>,
     trusted_peer_change_rx: watch::Receiver<TrustedPeerChangeEvent>,
     metrics: Metrics,
+    peer_table: Arc<PeerTable>,
 }
 
 impl DiscoveryEventLoop {
@@ -98,6 +100,7 @@ impl DiscoveryEventLoop {
 
         self.construct_our_info();
         self.configure_preferred_peers();
+        self.load_persisted_peers();
 
         let mut interval = tokio::time::interval(self.discovery_config.interval_period());
         let mut peer_events = {
@@ -206,6 +209,19 @@ impl DiscoveryEventLoop {
         }
     }
 
+    /// Adds the peers persisted in the peer table by previous runs to the
+    /// known peers, so that they can be dialed without first querying the
+    /// seed peers.
+    fn load_persisted_peers(&mut self) {
+        update_known_peers(
+            self.state.clone(),
+            self.metrics.clone(),
+            self.peer_table.known_node_infos(),
+            self.allowlisted_peers.clone(),
+            self.peer_table.clone(),
+        );
+    }
+
     fn update_our_info_timestamp(&mut self, now_unix: u64) {
         if let Some(our_info) = &mut self.state.write().unwrap().our_info {
             our_info.timestamp_ms = now_unix;
@@ -249,6 +265,7 @@ impl DiscoveryEventLoop {
                         self.state.clone(),
                         self.metrics.clone(),
                         self.allowlisted_peers.clone(),
+                        self.peer_table.clone(),
                     ));
                 }
             }
@@ -273,9 +290,11 @@ impl DiscoveryEventLoop {
     /// 3. Culls old known peers older than a day.
     /// 4. Cleans out the pending_dials, dial_seed_peers_task if it's done.
     /// 5. Selects a subset of known peers to dial if we're not connected to
-    ///    enough peers.
+    ///    enough peers, preferring peers with a good reputation and skipping
+    ///    banned ones.
     /// 6. If we have no neighbors and we aren't presently trying to connect to
     ///    anyone we need to try the seed peers.
+    /// 7. Records the connected peers in the peer table and persists it.
     fn handle_tick(&mut self, _now: std::time::Instant, now_unix: u64) {
         self.update_our_info_timestamp(now_unix);
 
@@ -286,6 +305,7 @@ impl DiscoveryEventLoop {
                 self.state.clone(),
                 self.metrics.clone(),
                 self.allowlisted_peers.clone(),
+                self.peer_table.clone(),
             ));
 
         // Culls old known peers older than a day.
@@ -307,7 +327,7 @@ impl DiscoveryEventLoop {
         // Selects a subset of known peers to dial if we're not connected to enough
         // peers.
         let state = self.state.read().unwrap();
-        let eligible = state
+        let mut eligible = state
             .known_peers
             .clone()
             .into_iter()
@@ -318,6 +338,7 @@ impl DiscoveryEventLoop {
                 && !self.pending_dials.contains_key(peer_id) // There is no
                 // pending dial to
                 // this node
+                && !self.peer_table.is_banned(peer_id) // Peer is not banned
             })
             .collect::<Vec<_>>();
 
@@ -330,12 +351,12 @@ impl DiscoveryEventLoop {
                 .saturating_sub(number_of_connections),
         );
 
-        // Randomly selects the number_to_dial of peers to connect to.
-        for (peer_id, info) in rand::seq::SliceRandom::choose_multiple(
-            eligible.as_slice(),
-            &mut rand::thread_rng(),
-            number_to_dial,
-        ) {
+        // Selects the number_to_dial of peers with the best reputation to connect to,
+        // randomly among peers with the same score.
+        rand::seq::SliceRandom::shuffle(eligible.as_mut_slice(), &mut rand::thread_rng());
+        eligible
+            .sort_by_cached_key(|(peer_id, _)| std::cmp::Reverse(self.peer_table.score(peer_id)));
+        for (peer_id, info) in eligible.iter().take(number_to_dial) {
             let abort_handle = self.tasks.spawn(try_to_connect_to_peer(
                 self.network.clone(),
                 info.to_owned(),
@@ -358,6 +379,17 @@ impl DiscoveryEventLoop {
 
             self.dial_seed_peers_task = Some(abort_handle);
         }
+
+        // Records the connected peers in the peer table, forgets the peers that are
+        // culled from the known peers above, and persists it.
+        for peer_id in state.connected_peers.keys() {
+            if let Some(peer) = self.network.peer(*peer_id) {
+                self.peer_table.record_seen(*peer_id, peer.connection_rtt());
+            }
+        }
+        self.peer_table
+            .prune(Duration::from_millis(ONE_DAY_MILLISECONDS));
+        self.peer_table.flush();
     }
 }
 
@@ -418,6 +450,7 @@ async fn query_peer_for_their_known_peers(
     state: Arc<RwLock<State>>,
     metrics: Metrics,
     allowlisted_peers: Arc<HashMap<PeerId, Option<Multiaddr>>>,
+    peer_table: Arc<PeerTable>,
 ) {
     let mut client = DiscoveryClient::new(peer);
 
@@ -439,7 +472,7 @@ async fn query_peer_for_their_known_peers(
             },
         )
     {
-        update_known_peers(state, metrics, found_peers, allowlisted_peers);
+        update_known_peers(state, metrics, found_peers, allowlisted_peers, peer_table);
     }
 }
 
@@ -450,6 +483,7 @@ async fn query_connected_peers_for_their_known_peers(
     state: Arc<RwLock<State>>,
     metrics: Metrics,
     allowlisted_peers: Arc<HashMap<PeerId, Option<Multiaddr>>>,
+    peer_table: Arc<PeerTable>,
 ) {
     use rand::seq::IteratorRandom;
 
@@ -488,18 +522,19 @@ async fn query_connected_peers_for_their_known_peers(
         .collect::<Vec<_>>()
         .await;
 
-    update_known_peers(state, metrics, found_peers, allowlisted_peers);
+    update_known_peers(state, metrics, found_peers, allowlisted_peers, peer_table);
 }
 
 /// Updates the known peers list with the found peers. The found peer is ignored
 /// if it is too old or too far in the future from our clock.
 /// If a peer is already known, the NodeInfo is updated, otherwise the peer is
-/// inserted.
+/// inserted. Accepted peers are also recorded in the peer table.
 fn update_known_peers(
     state: Arc<RwLock<State>>,
     metrics: Metrics,
     found_peers: Vec<NodeInfo>,
     allowlisted_peers: Arc<HashMap<PeerId, Option<Multiaddr>>>,
+    peer_table: Arc<PeerTable>,
 ) {
     use std::collections::hash_map::Entry;
 
@@ -525,6 +560,8 @@ fn update_known_peers(
             continue;
         }
 
+        peer_table.record_node_info(&peer);
+
         match known_peers.entry(peer.peer_id) {
             // Updates the NodeInfo of the peer if it exists.
             Entry::Occupied(mut o) => {
@@ -549,7 +586,7 @@ fn update_known_peers(
     }
 }
 
-fn now_unix() -> u64 {
+pub(crate) fn now_unix() -> u64 {
     use std::time::{SystemTime, UNIX_EPOCH};
 
     SystemTime::now()
@@ -11,7 +11,10 @@ use iota_config::p2p::AllowlistedPeer;
 use tokio::time::timeout;
 
 use super::*;
-use crate::utils::{build_network, build_network_with_anemo_config};
+use crate::{
+    peer_table::PeerOutcome,
+    utils::{build_network, build_network_with_anemo_config},
+};
 
 #[tokio::test]
 async fn get_known_peers() -> Result<()> {
@@ -251,6 +254,56 @@ async fn peers_are_added_from_reocnfig_channel() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn persisted_peers_are_dialed_unless_banned() -> Result<()> {
+    let peer_table = Arc::new(PeerTable::new_in_memory(&Default::default()));
+    let (builder, server) = Builder::new(create_test_channel().1)
+        .config(P2pConfig::default())
+        .peer_table(peer_table.clone())
+        .build();
+    let network = build_network(|router| router.add_rpc_service(server));
+    let (builder_2, server_2) = Builder::new(create_test_channel().1)
+        .config(P2pConfig::default())
+        .build();
+    let network_2 = build_network(|router| router.add_rpc_service(server_2));
+    let (_event_loop_2, _handle_2) = builder_2.build(network_2.clone());
+
+    // A peer discovered in a previous run.
+    let persisted = NodeInfo {
+        peer_id: network_2.peer_id(),
+        addresses: vec![format!("/dns/localhost/udp/{}", network_2.local_addr().port()).parse()?],
+        timestamp_ms: now_unix(),
+        access_type: AccessType::Public,
+    };
+    peer_table.record_node_info(&persisted);
+
+    let (mut event_loop, _handle) = builder.build(network.clone());
+    event_loop.construct_our_info();
+    event_loop.load_persisted_peers();
+    assert_eq!(
+        event_loop
+            .state
+            .read()
+            .unwrap()
+            .known_peers
+            .get(&persisted.peer_id),
+        Some(&persisted)
+    );
+
+    // Banned peers are not dialed.
+    peer_table.record_outcome(persisted.peer_id, PeerOutcome::BadSignature);
+    peer_table.record_outcome(persisted.peer_id, PeerOutcome::BadSignature);
+    assert!(peer_table.is_banned(&persisted.peer_id));
+    event_loop.handle_tick(std::time::Instant::now(), now_unix());
+    assert!(!event_loop.pending_dials.contains_key(&persisted.peer_id));
+
+    peer_table.unban(&persisted.peer_id);
+    event_loop.handle_tick(std::time::Instant::now(), now_unix());
+    assert!(event_loop.pending_dials.contains_key(&persisted.peer_id));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_access_types() {
     // This test case constructs a mesh graph of 11 nodes, with the following
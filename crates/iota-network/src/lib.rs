@@ -8,6 +8,7 @@ use iota_network_stack::config::Config;
 
 pub mod api;
 pub mod discovery;
+pub mod peer_table;
 pub mod randomness;
 pub mod state_sync;
 pub mod utils;
@@ -0,0 +1,485 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::RwLock,
+    time::Duration,
+};
+
+use anemo::PeerId;
+use iota_config::p2p::StateSyncConfig;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use typed_store::{
+    DBMapUtils, Map,
+    rocks::{DBMap, MetricConf},
+    traits::{TableSummary, TypedStoreDebug},
+};
+
+use crate::discovery::{NodeInfo, now_unix};
+
+/// Highest reputation score a peer can reach.
+const MAX_SCORE: i64 = 100;
+/// Weight of the newest sample in the request latency moving average.
+const LATENCY_EWMA_WEIGHT: f64 = 0.2;
+/// Maximum number of peers remembered. Peers advertise arbitrary other peers
+/// through discovery, so the table must not grow without bound.
+const MAX_PEER_RECORDS: usize = 10_000;
+
+/// Outcome of a state-sync request sent to a peer, used to update the
+/// reputation of that peer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PeerOutcome {
+    /// The peer served valid data after the given latency.
+    Success(Duration),
+    /// The request to the peer timed out.
+    Timeout,
+    /// The peer served a checkpoint summary that failed signature
+    /// verification.
+    BadSignature,
+    /// The peer served data that does not match the requested digest.
+    DigestMismatch,
+}
+
+impl PeerOutcome {
+    fn score_delta(&self) -> i64 {
+        match self {
+            PeerOutcome::Success(_) => 1,
+            PeerOutcome::Timeout => -5,
+            PeerOutcome::BadSignature => -50,
+            PeerOutcome::DigestMismatch => -25,
+        }
+    }
+}
+
+/// Everything this node remembers about a peer.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PeerRecord {
+    /// The latest information advertised by the peer through discovery.
+    pub node_info: Option<NodeInfo>,
+    /// Last time (unix ms) we were connected to the peer.
+    pub last_seen_ms: u64,
+    /// Round-trip time of the connection when the peer was last seen.
+    pub rtt_ms: Option<u64>,
+    /// Moving average of the latency of successful state-sync requests.
+    pub latency_ms: Option<f64>,
+    /// Reputation score, driven by the outcomes of state-sync requests.
+    pub score: i64,
+    pub successes: u64,
+    pub timeouts: u64,
+    pub bad_signatures: u64,
+    pub digest_mismatches: u64,
+    /// The peer is neither dialed by discovery nor asked for checkpoint data
+    /// until this time (unix ms).
+    pub banned_until_ms: Option<u64>,
+}
+
+impl PeerRecord {
+    pub fn is_banned(&self, now_ms: u64) -> bool {
+        self.banned_until_ms.is_some_and(|until| until > now_ms)
+    }
+
+    /// Last time (unix ms) the peer was seen or advertised itself.
+    fn last_active_ms(&self) -> u64 {
+        self.node_info
+            .as_ref()
+            .map_or(0, |info| info.timestamp_ms)
+            .max(self.last_seen_ms)
+    }
+}
+
+#[derive(DBMapUtils)]
+struct PeerTableStore {
+    peers: DBMap<PeerId, PeerRecord>,
+}
+
+/// Table of discovered peers and their reputation, shared between discovery
+/// and state sync.
+///
+/// Updates are applied in memory and written to the node DB on
+/// [`PeerTable::flush`], which discovery calls on every tick, so that known
+/// peers and their reputation survive restarts.
+pub struct PeerTable {
+    store: Option<PeerTableStore>,
+    inner: RwLock<Inner>,
+    ban_score_threshold: i64,
+    ban_duration: Duration,
+}
+
+#[derive(Default)]
+struct Inner {
+    records: HashMap<PeerId, PeerRecord>,
+    dirty: HashSet<PeerId>,
+    removed: HashSet<PeerId>,
+}
+
+impl Inner {
+    fn remove(&mut self, peer_id: &PeerId) {
+        self.records.remove(peer_id);
+        self.dirty.remove(peer_id);
+        self.removed.insert(*peer_id);
+    }
+
+    /// Makes room for a new peer by evicting the least recently active peer
+    /// that is not banned. Banned peers are kept, so that they cannot escape
+    /// their ban by flooding the table.
+    fn make_room(&mut self, now_ms: u64) {
+        if self.records.len() < MAX_PEER_RECORDS {
+            return;
+        }
+        let evicted = self
+            .records
+            .iter()
+            .filter(|(_, record)| !record.is_banned(now_ms))
+            .min_by_key(|(peer_id, record)| (record.last_active_ms(), **peer_id))
+            .map(|(peer_id, _)| *peer_id);
+        if let Some(peer_id) = evicted {
+            self.remove(&peer_id);
+        }
+    }
+}
+
+impl PeerTable {
+    /// Opens the peer table stored at `path` and loads all persisted peers.
+    pub fn open(path: PathBuf, config: &StateSyncConfig) -> Self {
+        let store =
+            PeerTableStore::open_tables_read_write(path, MetricConf::new("peers"), None, None);
+        let records = store
+            .peers
+            .safe_iter()
+            .filter_map(|item| {
+                item.inspect_err(|e| warn!("skipping unreadable peer record: {e}"))
+                    .ok()
+            })
+            .collect();
+        Self {
+            store: Some(store),
+            inner: RwLock::new(Inner {
+                records,
+                ..Default::default()
+            }),
+            ban_score_threshold: config.peer_ban_score_threshold(),
+            ban_duration: config.peer_ban_duration(),
+        }
+    }
+
+    /// Creates a peer table which is not persisted.
+    pub fn new_in_memory(config: &StateSyncConfig) -> Self {
+        Self {
+            store: None,
+            inner: RwLock::new(Inner::default()),
+            ban_score_threshold: config.peer_ban_score_threshold(),
+            ban_duration: config.peer_ban_duration(),
+        }
+    }
+
+    /// Returns all peers, ordered from the best to the worst reputation.
+    pub fn peers(&self) -> Vec<(PeerId, PeerRecord)> {
+        let mut peers: Vec<_> = self
+            .inner
+            .read()
+            .unwrap()
+            .records
+            .iter()
+            .map(|(peer_id, record)| (*peer_id, record.clone()))
+            .collect();
+        peers.sort_by_key(|(peer_id, record)| (std::cmp::Reverse(record.score), *peer_id));
+        peers
+    }
+
+    pub fn get(&self, peer_id: &PeerId) -> Option<PeerRecord> {
+        self.inner.read().unwrap().records.get(peer_id).cloned()
+    }
+
+    /// Returns the [`NodeInfo`] of all peers ever discovered.
+    pub fn known_node_infos(&self) -> Vec<NodeInfo> {
+        self.inner
+            .read()
+            .unwrap()
+            .records
+            .values()
+            .filter_map(|record| record.node_info.clone())
+            .collect()
+    }
+
+    pub fn score(&self, peer_id: &PeerId) -> i64 {
+        self.inner
+            .read()
+            .unwrap()
+            .records
+            .get(peer_id)
+            .map_or(0, |record| record.score)
+    }
+
+    pub fn is_banned(&self, peer_id: &PeerId) -> bool {
+        let now_ms = now_unix();
+        self.inner
+            .read()
+            .unwrap()
+            .records
+            .get(peer_id)
+            .is_some_and(|record| record.is_banned(now_ms))
+    }
+
+    /// Stores the information a peer advertises about itself, unless a more
+    /// recent one is already known.
+    pub fn record_node_info(&self, info: &NodeInfo) {
+        self.update(info.peer_id, |record| {
+            if record
+                .node_info
+                .as_ref()
+                .map_or(true, |known| known.timestamp_ms < info.timestamp_ms)
+            {
+                record.node_info = Some(info.clone());
+            }
+        });
+    }
+
+    /// Records that we are connected to a peer.
+    pub fn record_seen(&self, peer_id: PeerId, rtt: Duration) {
+        let now_ms = now_unix();
+        self.update(peer_id, |record| {
+            record.last_seen_ms = now_ms;
+            record.rtt_ms = Some(rtt.as_millis() as u64);
+        });
+    }
+
+    /// Updates the reputation of a peer with the outcome of a request. A peer
+    /// whose score drops to the ban threshold is banned for the configured
+    /// duration and gets half of the threshold as its new score, so that it is
+    /// banned again quickly if it keeps misbehaving.
+    pub fn record_outcome(&self, peer_id: PeerId, outcome: PeerOutcome) {
+        let now_ms = now_unix();
+        let ban_score_threshold = self.ban_score_threshold;
+        let ban_duration = self.ban_duration;
+        self.update(peer_id, |record| {
+            match outcome {
+                PeerOutcome::Success(latency) => {
+                    record.successes += 1;
+                    let latency_ms = latency.as_secs_f64() * 1_000.0;
+                    record.latency_ms = Some(record.latency_ms.map_or(latency_ms, |average| {
+                        average + LATENCY_EWMA_WEIGHT * (latency_ms - average)
+                    }));
+                }
+                PeerOutcome::Timeout => record.timeouts += 1,
+                PeerOutcome::BadSignature => record.bad_signatures += 1,
+                PeerOutcome::DigestMismatch => record.digest_mismatches += 1,
+            }
+            record.score = (record.score + outcome.score_delta()).min(MAX_SCORE);
+            if record.score <= ban_score_threshold && !record.is_banned(now_ms) {
+                info!(
+                    "banning peer {peer_id} for {ban_duration:?} after {outcome:?}, score {}",
+                    record.score
+                );
+                record.banned_until_ms =
+                    Some(now_ms.saturating_add(ban_duration.as_millis() as u64));
+                record.score = ban_score_threshold / 2;
+            }
+        });
+    }
+
+    /// Lifts the ban of a peer and resets its score.
+    pub fn unban(&self, peer_id: &PeerId) -> bool {
+        let mut inner = self.inner.write().unwrap();
+        let Some(record) = inner.records.get_mut(peer_id) else {
+            return false;
+        };
+        record.banned_until_ms = None;
+        record.score = record.score.max(0);
+        inner.dirty.insert(*peer_id);
+        true
+    }
+
+    /// Forgets peers that are not banned and were neither seen nor
+    /// advertised within `max_age`, and the least recently active peers
+    /// beyond the maximum size of the table.
+    pub fn prune(&self, max_age: Duration) {
+        let now_ms = now_unix();
+        let cutoff_ms = now_ms.saturating_sub(max_age.as_millis() as u64);
+        let mut inner = self.inner.write().unwrap();
+        let expired: Vec<_> = inner
+            .records
+            .iter()
+            .filter(|(_, record)| !record.is_banned(now_ms) && record.last_active_ms() < cutoff_ms)
+            .map(|(peer_id, _)| *peer_id)
+            .collect();
+        for peer_id in &expired {
+            inner.remove(peer_id);
+        }
+        while inner.records.len() > MAX_PEER_RECORDS {
+            let len = inner.records.len();
+            inner.make_room(now_ms);
+            if inner.records.len() == len {
+                // only banned peers are left
+                break;
+            }
+        }
+    }
+
+    /// Writes all peers updated since the last flush to the node DB, and
+    /// deletes the peers forgotten since then.
+    pub fn flush(&self) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        let (updated, removed): (Vec<_>, Vec<_>) = {
+            let mut inner = self.inner.write().unwrap();
+            let dirty = std::mem::take(&mut inner.dirty);
+            let removed = std::mem::take(&mut inner.removed);
+            let updated = dirty
+                .into_iter()
+                .filter_map(|peer_id| {
+                    inner
+                        .records
+                        .get(&peer_id)
+                        .map(|record| (peer_id, record.clone()))
+                })
+                .collect();
+            (updated, removed.into_iter().collect())
+        };
+        if !removed.is_empty() {
+            if let Err(e) = store.peers.multi_remove(removed) {
+                warn!("failed to delete peers from peer table: {e}");
+            }
+        }
+        if updated.is_empty() {
+            return;
+        }
+        if let Err(e) = store.peers.multi_insert(updated) {
+            warn!("failed to persist peer table: {e}");
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.read().unwrap().records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn update(&self, peer_id: PeerId, f: impl FnOnce(&mut PeerRecord)) {
+        let mut inner = self.inner.write().unwrap();
+        if !inner.records.contains_key(&peer_id) {
+            inner.make_room(now_unix());
+        }
+        f(inner.records.entry(peer_id).or_default());
+        inner.removed.remove(&peer_id);
+        inner.dirty.insert(peer_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iota_config::p2p::AccessType;
+
+    use super::*;
+
+    #[test]
+    fn bad_peers_are_banned() {
+        let table = PeerTable::new_in_memory(&StateSyncConfig::default());
+        let good = PeerId([1; 32]);
+        let bad = PeerId([2; 32]);
+
+        table.record_outcome(good, PeerOutcome::Success(Duration::from_millis(100)));
+        table.record_outcome(good, PeerOutcome::Success(Duration::from_millis(200)));
+        table.record_outcome(good, PeerOutcome::Timeout);
+        let record = table.get(&good).unwrap();
+        assert_eq!(record.score, -3);
+        assert_eq!(record.latency_ms, Some(120.0));
+        assert!(!table.is_banned(&good));
+
+        table.record_outcome(bad, PeerOutcome::BadSignature);
+        assert!(!table.is_banned(&bad));
+        table.record_outcome(bad, PeerOutcome::BadSignature);
+        assert!(table.is_banned(&bad));
+        assert_eq!(table.score(&bad), -50);
+        assert_eq!(table.peers()[0].0, good);
+
+        assert!(table.unban(&bad));
+        assert!(!table.is_banned(&bad));
+        assert_eq!(table.score(&bad), 0);
+    }
+
+    #[test]
+    fn inactive_peers_are_pruned() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = StateSyncConfig::default();
+        let node_info = |id: u8, timestamp_ms: u64| NodeInfo {
+            peer_id: PeerId([id; 32]),
+            addresses: vec![],
+            timestamp_ms,
+            access_type: AccessType::Public,
+        };
+        let max_age = Duration::from_secs(60);
+        let now_ms = now_unix();
+        let recent = node_info(1, now_ms);
+        let stale = node_info(2, now_ms - 120_000);
+        let banned = node_info(3, now_ms - 120_000);
+
+        let table = PeerTable::open(dir.path().join("peers"), &config);
+        for info in [&recent, &stale, &banned] {
+            table.record_node_info(info);
+        }
+        table.record_outcome(banned.peer_id, PeerOutcome::BadSignature);
+        table.record_outcome(banned.peer_id, PeerOutcome::BadSignature);
+        table.flush();
+
+        table.prune(max_age);
+        table.flush();
+        assert!(table.get(&recent.peer_id).is_some());
+        assert!(table.get(&stale.peer_id).is_none());
+        assert!(table.get(&banned.peer_id).is_some());
+        drop(table);
+
+        // the pruned peer is deleted from the store too
+        let table = PeerTable::open(dir.path().join("peers"), &config);
+        assert_eq!(table.len(), 2);
+        assert!(table.get(&stale.peer_id).is_none());
+    }
+
+    #[test]
+    fn table_size_is_capped() {
+        let table = PeerTable::new_in_memory(&StateSyncConfig::default());
+        let now_ms = now_unix();
+        for i in 0..=MAX_PEER_RECORDS as u64 {
+            let mut id = [0; 32];
+            id[..8].copy_from_slice(&i.to_le_bytes());
+            table.record_node_info(&NodeInfo {
+                peer_id: PeerId(id),
+                addresses: vec![],
+                timestamp_ms: now_ms - MAX_PEER_RECORDS as u64 + i,
+                access_type: AccessType::Public,
+            });
+        }
+        assert_eq!(table.len(), MAX_PEER_RECORDS);
+        // the least recently active peer was evicted
+        assert!(table.get(&PeerId([0; 32])).is_none());
+    }
+
+    #[test]
+    fn peers_are_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = StateSyncConfig::default();
+        let peer_id = PeerId([3; 32]);
+        let info = NodeInfo {
+            peer_id,
+            addresses: vec!["/dns/localhost/udp/8080".parse().unwrap()],
+            timestamp_ms: now_unix(),
+            access_type: AccessType::Public,
+        };
+
+        let table = PeerTable::open(dir.path().join("peers"), &config);
+        table.record_node_info(&info);
+        table.record_outcome(peer_id, PeerOutcome::DigestMismatch);
+        table.flush();
+        let expected = table.get(&peer_id).unwrap();
+        drop(table);
+
+        let table = PeerTable::open(dir.path().join("peers"), &config);
+        assert_eq!(table.get(&peer_id), Some(expected));
+        assert_eq!(table.known_node_infos(), vec![info]);
+    }
+}
@@ -23,12 +23,14 @@ use super::{
     metrics::Metrics,
     server::{CheckpointContentsDownloadLimitLayer, Server},
 };
+use crate::peer_table::PeerTable;
 
 pub struct Builder<S> {
     store: Option<S>,
     config: Option<StateSyncConfig>,
     metrics: Option<Metrics>,
     archive_readers: Option<ArchiveReaderBalancer>,
+    peer_table: Option<Arc<PeerTable>>,
 }
 
 impl Builder<()> {
@@ -39,6 +41,7 @@ impl Builder<()> {
             config: None,
             metrics: None,
             archive_readers: None,
+            peer_table: None,
         }
     }
 }
@@ -50,6 +53,7 @@ impl<S> Builder<S> {
             config: self.config,
             metrics: self.metrics,
             archive_readers: self.archive_readers,
+            peer_table: self.peer_table,
         }
     }
 
@@ -67,6 +71,13 @@ impl<S> Builder<S> {
         self.archive_readers = Some(archive_readers);
         self
     }
+
+    /// Sets the table the outcomes of requests to peers are recorded in. If
+    /// unset, peer reputation is only kept in memory.
+    pub fn peer_table(mut self, peer_table: Arc<PeerTable>) -> Self {
+        self.peer_table = Some(peer_table);
+        self
+    }
 }
 
 impl<S> Builder<S>
@@ -127,11 +138,13 @@ where
             config,
             metrics,
             archive_readers,
+            peer_table,
         } = self;
         let store = store.unwrap();
         let config = config.unwrap_or_default();
         let metrics = metrics.unwrap_or_else(Metrics::disabled);
         let archive_readers = archive_readers.unwrap_or_default();
+        let peer_table = peer_table.unwrap_or_else(|| Arc::new(PeerTable::new_in_memory(&config)));
 
         let (sender, mailbox) = mpsc::channel(config.mailbox_capacity());
         let (checkpoint_event_sender, _receiver) =
@@ -168,6 +181,7 @@ where
                 checkpoint_event_sender,
                 metrics,
                 archive_readers,
+                peer_table,
             },
             server,
         )
@@ -184,6 +198,7 @@ pub struct UnstartedStateSync<S> {
     pub(super) checkpoint_event_sender: broadcast::Sender<VerifiedCheckpoint>,
     pub(super) metrics: Metrics,
     pub(super) archive_readers: ArchiveReaderBalancer,
+    pub(super) peer_table: Arc<PeerTable>,
 }
 
 impl<S> UnstartedStateSync<S>
@@ -201,6 +216,7 @@ where
             checkpoint_event_sender,
             metrics,
             archive_readers,
+            peer_table,
         } = self;
 
         (
@@ -219,6 +235,7 @@ where
                 metrics,
                 archive_readers,
                 sync_checkpoint_from_archive_task: None,
+                peer_table,
             },
             handle,
         )
@@ -57,6 +57,12 @@
 //! downloaded. Notifications on this broadcast channel will always be made in
 //! order. StateSync will also send out a notification to its peers of the newly
 //! synchronized checkpoint so that it can help other peers synchronize.
+//!
+//! The outcome of every checkpoint request is recorded in the shared
+//! [`PeerTable`]. Peers which time out or serve checkpoints with invalid
+//! signatures or mismatching digests lose reputation and are temporarily banned
+//! once it drops too low, while peers with a good reputation are preferred when
+//! selecting whom to request data from.
 
 use std::{
     collections::{HashMap, VecDeque},
@@ -106,6 +112,7 @@ use iota_storage::verify_checkpoint;
 pub use server::{GetCheckpointAvailabilityResponse, GetCheckpointSummaryRequest};
 
 use self::{metrics::Metrics, server::CheckpointContentsDownloadLimitLayer};
+use crate::peer_table::{PeerOutcome, PeerTable};
 
 /// A handle to the StateSync subsystem.
 ///
@@ -288,13 +295,14 @@ impl PeerHeights {
     }
 }
 
-// PeerBalancer is an Iterator that selects peers based on RTT with some added
-// randomness.
+// PeerBalancer is an Iterator that selects peers based on reputation and RTT
+// with some added randomness. Banned peers are never selected.
 #[derive(Clone)]
 struct PeerBalancer {
     peers: VecDeque<(anemo::Peer, PeerStateSyncInfo)>,
     requested_checkpoint: Option<CheckpointSequenceNumber>,
     request_type: PeerCheckpointRequestType,
+    peer_table: Arc<PeerTable>,
 }
 
 #[derive(Clone)]
@@ -307,20 +315,26 @@ impl PeerBalancer {
     pub fn new(
         network: &anemo::Network,
         peer_heights: Arc<RwLock<PeerHeights>>,
+        peer_table: Arc<PeerTable>,
         request_type: PeerCheckpointRequestType,
     ) -> Self {
         let mut peers: Vec<_> = peer_heights
             .read()
             .unwrap()
             .peers_on_same_chain()
+            // Filter out any peers who are banned.
+            .filter(|(peer_id, _info)| !peer_table.is_banned(peer_id))
             // Filter out any peers who we aren't connected with.
             .filter_map(|(peer_id, info)| {
-                network
-                    .peer(*peer_id)
-                    .map(|peer| (peer.connection_rtt(), peer, *info))
+                network.peer(*peer_id).map(|peer| {
+                    // Peers in good standing are only ordered by RTT, peers which lost
+                    // reputation come last.
+                    let penalty = std::cmp::Reverse(peer_table.score(peer_id).min(0));
+                    ((penalty, peer.connection_rtt()), peer, *info)
+                })
             })
             .collect();
-        peers.sort_by(|(rtt_a, _, _), (rtt_b, _, _)| rtt_a.cmp(rtt_b));
+        peers.sort_by(|(key_a, _, _), (key_b, _, _)| key_a.cmp(key_b));
         Self {
             peers: peers
                 .into_iter()
@@ -328,6 +342,7 @@ impl PeerBalancer {
                 .collect(),
             requested_checkpoint: None,
             request_type,
+            peer_table,
         }
     }
 
@@ -400,6 +415,7 @@ struct StateSyncEventLoop<S> {
 
     archive_readers: ArchiveReaderBalancer,
     sync_checkpoint_from_archive_task: Option<AbortHandle>,
+    peer_table: Arc<PeerTable>,
 }
 
 impl<S> StateSyncEventLoop<S>
@@ -442,6 +458,7 @@ where
             self.network.clone(),
             self.store.clone(),
             self.peer_heights.clone(),
+            self.peer_table.clone(),
             self.weak_sender.clone(),
             self.checkpoint_event_sender.clone(),
             self.config.checkpoint_content_download_concurrency(),
@@ -730,6 +747,7 @@ where
                 self.network.clone(),
                 self.store.clone(),
                 self.peer_heights.clone(),
+                self.peer_table.clone(),
                 self.metrics.clone(),
                 self.config.pinned_checkpoints.clone(),
                 self.config.checkpoint_header_download_concurrency(),
@@ -1015,6 +1033,7 @@ async fn sync_to_checkpoint<S>(
     network: anemo::Network,
     store: S,
     peer_heights: Arc<RwLock<PeerHeights>>,
+    peer_table: Arc<PeerTable>,
     metrics: Metrics,
     pinned_checkpoints: Vec<(CheckpointSequenceNumber, CheckpointDigest)>,
     checkpoint_header_download_concurrency: usize,
@@ -1040,6 +1059,7 @@ where
     let peer_balancer = PeerBalancer::new(
         &network,
         peer_heights.clone(),
+        peer_table.clone(),
         PeerCheckpointRequestType::Summary,
     );
     // Range of the next sequence_numbers to fetch
@@ -1048,6 +1068,7 @@ where
         .map(|next| {
             let peers = peer_balancer.clone().with_checkpoint(next);
             let peer_heights = peer_heights.clone();
+            let peer_table = peer_table.clone();
             let pinned_checkpoints = &pinned_checkpoints;
             async move {
                 if let Some(checkpoint) = peer_heights
@@ -1061,12 +1082,17 @@ where
                 // Iterate through peers trying each one in turn until we're able to
                 // successfully get the target checkpoint
                 for mut peer in peers {
+                    let peer_id = peer.inner().peer_id();
                     let request = Request::new(GetCheckpointSummaryRequest::BySequenceNumber(next))
                         .with_timeout(timeout);
+                    let start = tokio::time::Instant::now();
                     if let Some(checkpoint) = peer
                         .get_checkpoint_summary(request)
                         .await
-                        .tap_err(|e| trace!("{e:?}"))
+                        .tap_err(|e| {
+                            trace!("{e:?}");
+                            record_request_failure(&peer_table, peer_id, e);
+                        })
                         .ok()
                         .and_then(Response::into_inner)
                         .tap_none(|| trace!("peer unable to help sync"))
@@ -1092,6 +1118,7 @@ where
                                     pinned_checkpoints[pinned_digest_index].1,
                                     checkpoint_digest
                                 );
+                                peer_table.record_outcome(peer_id, PeerOutcome::DigestMismatch);
                                 continue;
                             }
                         }
@@ -1101,7 +1128,7 @@ where
                             .write()
                             .unwrap()
                             .insert_checkpoint(checkpoint.clone());
-                        return (Some(checkpoint), next, Some(peer.inner().peer_id()));
+                        return (Some(checkpoint), next, Some((peer_id, start.elapsed())));
                     }
                 }
                 (None, next, None)
@@ -1110,7 +1137,7 @@ where
         .pipe(futures::stream::iter)
         .buffered(checkpoint_header_download_concurrency);
 
-    while let Some((maybe_checkpoint, next, maybe_peer_id)) = request_stream.next().await {
+    while let Some((maybe_checkpoint, next, maybe_peer)) = request_stream.next().await {
         assert_eq!(
             current
                 .sequence_number()
@@ -1129,19 +1156,35 @@ where
                 .binary_search_by_key(checkpoint.sequence_number(), |(seq_num, _digest)| *seq_num)
                 .is_ok()
             {
+                if let Some((peer_id, latency)) = maybe_peer {
+                    peer_table.record_outcome(peer_id, PeerOutcome::Success(latency));
+                }
                 break 'cp VerifiedCheckpoint::new_unchecked(checkpoint);
             }
             match verify_checkpoint(&current, &store, checkpoint) {
-                Ok(verified_checkpoint) => verified_checkpoint,
+                Ok(verified_checkpoint) => {
+                    if let Some((peer_id, latency)) = maybe_peer {
+                        peer_table.record_outcome(peer_id, PeerOutcome::Success(latency));
+                    }
+                    verified_checkpoint
+                }
                 Err(checkpoint) => {
                     let mut peer_heights = peer_heights.write().unwrap();
                     // Remove the checkpoint from our temporary store so that we can try querying
                     // another peer for a different one
                     peer_heights.remove_checkpoint(checkpoint.digest());
 
-                    // Mark peer as not on the same chain as us
-                    if let Some(peer_id) = maybe_peer_id {
+                    // Mark peer as not on the same chain as us and penalize it. A checkpoint
+                    // which does not extend our chain is reported as a digest mismatch, any
+                    // other verification failure is due to an invalid signature.
+                    if let Some((peer_id, _)) = maybe_peer {
                         peer_heights.mark_peer_as_not_on_same_chain(peer_id);
+                        let outcome = if checkpoint.previous_digest != Some(*current.digest()) {
+                            PeerOutcome::DigestMismatch
+                        } else {
+                            PeerOutcome::BadSignature
+                        };
+                        peer_table.record_outcome(peer_id, outcome);
                     }
 
                     return Err(anyhow::anyhow!(
@@ -1256,6 +1299,7 @@ async fn sync_checkpoint_contents<S>(
     network: anemo::Network,
     store: S,
     peer_heights: Arc<RwLock<PeerHeights>>,
+    peer_table: Arc<PeerTable>,
     sender: mpsc::WeakSender<StateSyncMessage>,
     checkpoint_event_sender: broadcast::Sender<VerifiedCheckpoint>,
     checkpoint_content_download_concurrency: usize,
@@ -1319,6 +1363,7 @@ async fn sync_checkpoint_contents<S>(
                             network.clone(),
                             &store,
                             peer_heights.clone(),
+                            peer_table.clone(),
                             timeout,
                             checkpoint,
                         ));
@@ -1352,6 +1397,7 @@ async fn sync_checkpoint_contents<S>(
                 network.clone(),
                 &store,
                 peer_heights.clone(),
+                peer_table.clone(),
                 timeout,
                 next_checkpoint,
             ));
@@ -1377,6 +1423,7 @@ async fn sync_one_checkpoint_contents<S>(
     network: anemo::Network,
     store: S,
     peer_heights: Arc<RwLock<PeerHeights>>,
+    peer_table: Arc<PeerTable>,
     timeout: Duration,
     checkpoint: VerifiedCheckpoint,
 ) -> Result<VerifiedCheckpoint, VerifiedCheckpoint>
@@ -1401,6 +1448,7 @@ where
     let peers = PeerBalancer::new(
         &network,
         peer_heights.clone(),
+        peer_table,
         PeerCheckpointRequestType::Content,
     )
     .with_checkpoint(*checkpoint.sequence_number());
@@ -1453,34 +1501,47 @@ where
 
     // Iterate through our selected peers trying each one in turn until we're able
     // to successfully get the target checkpoint
+    let peer_table = peers.peer_table.clone();
     for mut peer in peers {
-        debug!(
-            ?timeout,
-            "requesting checkpoint contents from {}",
-            peer.inner().peer_id(),
-        );
+        let peer_id = peer.inner().peer_id();
+        debug!(?timeout, "requesting checkpoint contents from {peer_id}");
         let request = Request::new(digest).with_timeout(timeout);
+        let start = tokio::time::Instant::now();
         if let Some(contents) = peer
             .get_checkpoint_contents(request)
             .await
-            .tap_err(|e| trace!("{e:?}"))
+            .tap_err(|e| {
+                trace!("{e:?}");
+                record_request_failure(&peer_table, peer_id, e);
+            })
             .ok()
             .and_then(Response::into_inner)
             .tap_none(|| trace!("peer unable to help sync"))
         {
             if contents.verify_digests(digest).is_ok() {
+                peer_table.record_outcome(peer_id, PeerOutcome::Success(start.elapsed()));
                 let verified_contents = VerifiedCheckpointContents::new_unchecked(contents.clone());
                 store
                     .insert_checkpoint_contents(checkpoint, verified_contents)
                     .expect("store operation should not fail");
                 return Some(contents);
             }
+            debug!("peer {peer_id} returned checkpoint contents not matching digest {digest}");
+            peer_table.record_outcome(peer_id, PeerOutcome::DigestMismatch);
         }
     }
     debug!("no peers had checkpoint contents");
     None
 }
 
+/// Penalizes a peer whose request timed out. Other failures, like the peer
+/// being overloaded, don't affect its reputation.
+fn record_request_failure(peer_table: &PeerTable, peer_id: PeerId, status: &anemo::rpc::Status) {
+    if status.status() == anemo::types::response::StatusCode::RequestTimeout {
+        peer_table.record_outcome(peer_id, PeerOutcome::Timeout);
+    }
+}
+
 async fn update_checkpoint_watermark_metrics<S>(
     mut recv: oneshot::Receiver<()>,
     store: S,
@@ -3,11 +3,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::{
+    hash::Hash,
     net::{IpAddr, Ipv4Addr, SocketAddr},
+    num::NonZeroUsize,
+    path::PathBuf,
     str::FromStr,
     sync::Arc,
+    time::SystemTime,
 };
 
+use anemo::PeerId;
+use async_trait::async_trait;
 use axum::{
     Router,
     extract::{Query, State},
@@ -15,13 +21,24 @@ use axum::{
     routing::{get, post},
 };
 use base64::Engine;
+use fastcrypto::encoding::{Encoding, Hex};
 use humantime::parse_duration;
+use iota_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
+use iota_core::{
+    live_object_set_audit::{
+        LiveObjectSetAuditConfig, LiveObjectSetReference, MAX_OBJECT_REFS_PER_PAGE, ObjectIdRange,
+        ObjectRefsPage, RangeDigest,
+    },
+    traffic_controller::policies::{Reputation, ReputationTable},
+};
+use iota_snapshot::live_object_set::SnapshotLiveObjectSet;
+use iota_transaction_checks::deny::DenyListEntry;
 use iota_types::{
-    base_types::AuthorityName,
+    base_types::{AuthorityName, IotaAddress, ObjectID, ObjectRef},
     crypto::{RandomnessPartialSignature, RandomnessRound, RandomnessSignature},
     error::IotaError,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use telemetry_subscribers::TracingHandle;
 use tokio::sync::oneshot;
 use tracing::info;
@@ -73,6 +90,82 @@ use crate::IotaNode;
 // Inject a full signature from another node, bypassing validity checks.
 //
 //  $ curl 'http://127.0.0.1:1337/randomness-inject-full-sig?round=123&sigs=base64encodedsig'
+//
+// View the deny list entries added at runtime (entries of the node config are
+// not included):
+//
+//   $ curl 'http://127.0.0.1:1337/deny-list'
+//
+// Deny an object, package, package upgrade family or address. The entry is
+// persisted and applied to transactions signed from now on. The kind is one of
+// `object`, `package`, `package-family` (takes the original package ID) and
+// `address`:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/deny-list/add?kind=package-family&id=0x123'
+//
+// Remove an entry from the deny list:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/deny-list/remove?kind=package-family&id=0x123'
+//
+// View the peers known to this node, ordered by reputation, with their last
+// seen time, latency, state sync outcomes and bans:
+//
+//   $ curl 'http://127.0.0.1:1337/peers'
+//
+// Lift the ban of a peer and reset its negative reputation:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/peers/unban?peer_id=hexencodedpeerid'
+//
+// Audit the live object set of this node in the background against the live
+// object set of a trusted peer, whose admin interface must be reachable (e.g.
+// through an SSH tunnel). Divergences are only meaningful if both nodes are
+// at the same checkpoint, so rerun the audit to confirm them:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/live-object-set-audit/start?peer=http://127.0.0.1:1338'
+//
+// Audit the live object set against the formal snapshot of epoch 5 in a local
+// directory. Objects modified after the end of the epoch are not reported.
+// `max_objects_per_range` and `throttle` tune how far diverging ranges are
+// bisected and the pause between two ranges:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/live-object-set-audit/start?snapshot_path=/opt/iota/snapshots&epoch=5&throttle=50ms'
+//
+// View the progress and the diverging ranges and objects of the audit:
+//
+//   $ curl 'http://127.0.0.1:1337/live-object-set-audit'
+//
+// Stop the running audit:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/live-object-set-audit/stop'
+//
+// The number and accumulated digest of the live objects in a range of object
+// IDs, and their references. These are read by nodes auditing against this one.
+// At most `limit` (and at most 10000) references are returned at once, along
+// with a `next_cursor` to pass as `start` to read the rest of the range:
+//
+//   $ curl 'http://127.0.0.1:1337/live-object-set/digest?start=0x0&end=0xffff'
+//   $ curl 'http://127.0.0.1:1337/live-object-set/refs?start=0x0&end=0xffff&limit=1000'
+//
+// Take a backup of the databases of the node to the path configured in
+// `db-backup-config`. Restore it with `iota-tool restore-backup`:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/db-backup'
+//
+// List the complete backups, from the oldest to the most recent:
+//
+//   $ curl 'http://127.0.0.1:1337/db-backups'
+//
+// View the senders and gas coins of failed transactions tracked by the
+// reputation policies of the traffic controller, by decreasing score, with
+// their throttling or blocking:
+//
+//   $ curl 'http://127.0.0.1:1337/traffic-control/reputation'
+//
+// Reset the reputation of a sender or gas coin, lifting its restriction, or of
+// all senders and gas coins if neither is given:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/traffic-control/reputation/reset?sender=0x123'
+//   $ curl -X POST 'http://127.0.0.1:1337/traffic-control/reputation/reset?gas_coin=0x456'
 
 const LOGGING_ROUTE: &str = "/logging";
 const TRACING_ROUTE: &str = "/enable-tracing";
@@ -85,6 +178,24 @@ const NODE_CONFIG: &str = "/node-config";
 const RANDOMNESS_PARTIAL_SIGS_ROUTE: &str = "/randomness-partial-sigs";
 const RANDOMNESS_INJECT_PARTIAL_SIGS_ROUTE: &str = "/randomness-inject-partial-sigs";
 const RANDOMNESS_INJECT_FULL_SIG_ROUTE: &str = "/randomness-inject-full-sig";
+const DENY_LIST_ROUTE: &str = "/deny-list";
+const DENY_LIST_ADD_ROUTE: &str = "/deny-list/add";
+const DENY_LIST_REMOVE_ROUTE: &str = "/deny-list/remove";
+const PEERS_ROUTE: &str = "/peers";
+const PEERS_UNBAN_ROUTE: &str = "/peers/unban";
+const LIVE_OBJECT_SET_AUDIT_ROUTE: &str = "/live-object-set-audit";
+const LIVE_OBJECT_SET_AUDIT_START_ROUTE: &str = "/live-object-set-audit/start";
+const LIVE_OBJECT_SET_AUDIT_STOP_ROUTE: &str = "/live-object-set-audit/stop";
+const LIVE_OBJECT_SET_DIGEST_ROUTE: &str = "/live-object-set/digest";
+const LIVE_OBJECT_SET_REFS_ROUTE: &str = "/live-object-set/refs";
+const DB_BACKUP_ROUTE: &str = "/db-backup";
+const DB_BACKUPS_ROUTE: &str = "/db-backups";
+const TRAFFIC_CONTROL_REPUTATION_ROUTE: &str = "/traffic-control/reputation";
+const TRAFFIC_CONTROL_REPUTATION_RESET_ROUTE: &str = "/traffic-control/reputation/reset";
+
+/// Number of concurrent downloads of the reference files of a snapshot that
+/// the live object set is audited against.
+const SNAPSHOT_DOWNLOAD_CONCURRENCY: usize = 8;
 
 struct AppState {
     node: Arc<IotaNode>,
@@ -124,6 +235,32 @@ pub async fn run_admin_server(node: Arc<IotaNode>, port: u16, tracing_handle: Tr
             RANDOMNESS_INJECT_FULL_SIG_ROUTE,
             post(randomness_inject_full_sig),
         )
+        .route(DENY_LIST_ROUTE, get(deny_list))
+        .route(DENY_LIST_ADD_ROUTE, post(deny_list_add))
+        .route(DENY_LIST_REMOVE_ROUTE, post(deny_list_remove))
+        .route(PEERS_ROUTE, get(peers))
+        .route(PEERS_UNBAN_ROUTE, post(peers_unban))
+        .route(LIVE_OBJECT_SET_AUDIT_ROUTE, get(live_object_set_audit))
+        .route(
+            LIVE_OBJECT_SET_AUDIT_START_ROUTE,
+            post(live_object_set_audit_start),
+        )
+        .route(
+            LIVE_OBJECT_SET_AUDIT_STOP_ROUTE,
+            post(live_object_set_audit_stop),
+        )
+        .route(LIVE_OBJECT_SET_DIGEST_ROUTE, get(live_object_set_digest))
+        .route(LIVE_OBJECT_SET_REFS_ROUTE, get(live_object_set_refs))
+        .route(DB_BACKUP_ROUTE, post(db_backup))
+        .route(DB_BACKUPS_ROUTE, get(db_backups))
+        .route(
+            TRAFFIC_CONTROL_REPUTATION_ROUTE,
+            get(traffic_control_reputation),
+        )
+        .route(
+            TRAFFIC_CONTROL_REPUTATION_RESET_ROUTE,
+            post(traffic_control_reputation_reset),
+        )
         .with_state(Arc::new(app_state));
 
     let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
@@ -442,3 +579,433 @@ async fn randomness_inject_full_sig(
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
     }
 }
+
+async fn deny_list(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    let authority_state = state.node.state();
+    let deny_list = authority_state.dynamic_deny_list();
+    (
+        StatusCode::OK,
+        format!(
+            "path: {}\n{:#?}\n",
+            deny_list.path().display(),
+            deny_list.entries()
+        ),
+    )
+}
+
+#[derive(Deserialize)]
+struct DenyListUpdate {
+    kind: String,
+    id: String,
+}
+
+async fn deny_list_add(
+    State(state): State<Arc<AppState>>,
+    args: Query<DenyListUpdate>,
+) -> (StatusCode, String) {
+    let Query(DenyListUpdate { kind, id }) = args;
+
+    let entry = match DenyListEntry::parse(&kind, &id) {
+        Ok(entry) => entry,
+        Err(err) => return (StatusCode::BAD_REQUEST, err.to_string()),
+    };
+
+    match state.node.state().dynamic_deny_list().add(entry) {
+        Ok(true) => (StatusCode::OK, format!("{entry} added to deny list\n")),
+        Ok(false) => (StatusCode::OK, format!("{entry} is already denied\n")),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, format!("{err:#}")),
+    }
+}
+
+async fn deny_list_remove(
+    State(state): State<Arc<AppState>>,
+    args: Query<DenyListUpdate>,
+) -> (StatusCode, String) {
+    let Query(DenyListUpdate { kind, id }) = args;
+
+    let entry = match DenyListEntry::parse(&kind, &id) {
+        Ok(entry) => entry,
+        Err(err) => return (StatusCode::BAD_REQUEST, err.to_string()),
+    };
+
+    match state.node.state().dynamic_deny_list().remove(entry) {
+        Ok(true) => (StatusCode::OK, format!("{entry} removed from deny list\n")),
+        Ok(false) => (StatusCode::OK, format!("{entry} is not denied\n")),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, format!("{err:#}")),
+    }
+}
+
+async fn peers(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    (
+        StatusCode::OK,
+        format!("{:#?}\n", state.node.peer_table().peers()),
+    )
+}
+
+#[derive(Deserialize)]
+struct Peer {
+    peer_id: String,
+}
+
+async fn peers_unban(
+    State(state): State<Arc<AppState>>,
+    args: Query<Peer>,
+) -> (StatusCode, String) {
+    let Query(Peer { peer_id }) = args;
+
+    let peer_id = match Hex::decode(peer_id.trim_start_matches("0x"))
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| {
+            <[u8; 32]>::try_from(bytes).map_err(|_| "peer ID must be 32 bytes".to_string())
+        }) {
+        Ok(bytes) => PeerId(bytes),
+        Err(err) => return (StatusCode::BAD_REQUEST, err),
+    };
+
+    if state.node.peer_table().unban(&peer_id) {
+        (StatusCode::OK, format!("peer {peer_id} unbanned\n"))
+    } else {
+        (
+            StatusCode::NOT_FOUND,
+            format!("peer {peer_id} is not known\n"),
+        )
+    }
+}
+
+#[derive(Deserialize)]
+struct LiveObjectSetAuditStart {
+    peer: Option<String>,
+    snapshot_path: Option<PathBuf>,
+    epoch: Option<u64>,
+    max_objects_per_range: Option<u64>,
+    throttle: Option<String>,
+}
+
+async fn live_object_set_audit_start(
+    State(state): State<Arc<AppState>>,
+    args: Query<LiveObjectSetAuditStart>,
+) -> (StatusCode, String) {
+    let Query(LiveObjectSetAuditStart {
+        peer,
+        snapshot_path,
+        epoch,
+        max_objects_per_range,
+        throttle,
+    }) = args;
+
+    let mut config = LiveObjectSetAuditConfig::default();
+    if let Some(max_objects_per_range) = max_objects_per_range {
+        config.max_objects_per_range = max_objects_per_range;
+    }
+    if let Some(throttle) = throttle {
+        match parse_duration(&throttle) {
+            Ok(throttle) => config.throttle = throttle,
+            Err(err) => return (StatusCode::BAD_REQUEST, err.to_string()),
+        }
+    }
+
+    let audit = state.node.live_object_set_audit();
+    let result = match (peer, snapshot_path, epoch) {
+        (Some(peer), None, None) => {
+            let reference = PeerLiveObjectSet::new(peer.clone());
+            audit.start(format!("peer {peer}"), None, config, async move {
+                Ok(Arc::new(reference) as Arc<dyn LiveObjectSetReference>)
+            })
+        }
+        (None, Some(snapshot_path), Some(epoch)) => {
+            let checkpoint = match audit.local().epoch_last_checkpoint(epoch) {
+                Ok(Some(checkpoint)) => checkpoint,
+                Ok(None) => {
+                    return (
+                        StatusCode::BAD_REQUEST,
+                        format!("the live object set at the end of epoch {epoch} is not known\n"),
+                    );
+                }
+                Err(err) => return (StatusCode::INTERNAL_SERVER_ERROR, format!("{err:#}")),
+            };
+
+            let description = format!("snapshot of epoch {epoch} at {}", snapshot_path.display());
+            let remote_store_config = ObjectStoreConfig {
+                object_store: Some(ObjectStoreType::File),
+                directory: Some(snapshot_path),
+                ..Default::default()
+            };
+            let local_store_config = ObjectStoreConfig {
+                object_store: Some(ObjectStoreType::File),
+                directory: Some(state.node.config.db_path().join("live_object_set_audit")),
+                ..Default::default()
+            };
+            audit.start(description, Some(checkpoint), config, async move {
+                let reference = SnapshotLiveObjectSet::load(
+                    epoch,
+                    &remote_store_config,
+                    &local_store_config,
+                    NonZeroUsize::new(SNAPSHOT_DOWNLOAD_CONCURRENCY).unwrap(),
+                )
+                .await?;
+                Ok(Arc::new(reference) as Arc<dyn LiveObjectSetReference>)
+            })
+        }
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                "either `peer`, or `snapshot_path` and `epoch` must be given\n".to_string(),
+            );
+        }
+    };
+
+    match result {
+        Ok(()) => (
+            StatusCode::OK,
+            "live object set audit started\n".to_string(),
+        ),
+        Err(err) => (StatusCode::CONFLICT, format!("{err}\n")),
+    }
+}
+
+async fn live_object_set_audit_stop(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    if state.node.live_object_set_audit().stop() {
+        (
+            StatusCode::OK,
+            "live object set audit stopping\n".to_string(),
+        )
+    } else {
+        (
+            StatusCode::OK,
+            "no live object set audit is running\n".to_string(),
+        )
+    }
+}
+
+async fn live_object_set_audit(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    json_response(&state.node.live_object_set_audit().report())
+}
+
+async fn live_object_set_digest(
+    State(state): State<Arc<AppState>>,
+    range: Query<ObjectIdRange>,
+) -> (StatusCode, String) {
+    let Query(range) = range;
+    let local = state.node.live_object_set_audit().local();
+    match tokio::task::spawn_blocking(move || local.range_digest(range)).await {
+        Ok(digest) => json_response(&digest),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+struct LiveObjectSetRefs {
+    start: ObjectID,
+    end: ObjectID,
+    limit: Option<usize>,
+}
+
+async fn live_object_set_refs(
+    State(state): State<Arc<AppState>>,
+    args: Query<LiveObjectSetRefs>,
+) -> (StatusCode, String) {
+    let Query(LiveObjectSetRefs { start, end, limit }) = args;
+    let range = ObjectIdRange::new(start, end);
+    let limit = limit
+        .unwrap_or(MAX_OBJECT_REFS_PER_PAGE)
+        .clamp(1, MAX_OBJECT_REFS_PER_PAGE);
+    let local = state.node.live_object_set_audit().local();
+    match tokio::task::spawn_blocking(move || local.range_object_refs(range, limit)).await {
+        Ok(refs) => json_response(&refs),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+async fn db_backup(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    match state.node.backup_dbs().await {
+        Ok(path) => (
+            StatusCode::OK,
+            format!("db backup written to {}\n", path.display()),
+        ),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, format!("{err:#}\n")),
+    }
+}
+
+async fn db_backups(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    match state.node.list_db_backups() {
+        Ok(backups) => (
+            StatusCode::OK,
+            backups
+                .iter()
+                .map(|path| format!("{}\n", path.display()))
+                .collect(),
+        ),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, format!("{err:#}\n")),
+    }
+}
+
+fn reputations_by_score<K: Copy + Eq + Hash>(
+    table: Option<&ReputationTable<K>>,
+) -> Option<Vec<(K, Reputation)>> {
+    table.map(|table| {
+        let mut entries = table.entries(SystemTime::now());
+        entries.sort_by(|(_, a), (_, b)| b.score.total_cmp(&a.score));
+        entries
+    })
+}
+
+async fn traffic_control_reputation(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    let Some(traffic_controller) = state.node.traffic_controller().await else {
+        return (
+            StatusCode::NOT_FOUND,
+            "traffic control is not enabled on this node\n".to_string(),
+        );
+    };
+    let reputation = traffic_controller.transaction_reputation();
+    (
+        StatusCode::OK,
+        format!(
+            "senders: {:#?}\ngas coins: {:#?}\n",
+            reputations_by_score(reputation.senders.as_ref()),
+            reputations_by_score(reputation.gas_coins.as_ref()),
+        ),
+    )
+}
+
+#[derive(Deserialize)]
+struct ReputationReset {
+    sender: Option<String>,
+    gas_coin: Option<String>,
+}
+
+async fn traffic_control_reputation_reset(
+    State(state): State<Arc<AppState>>,
+    args: Query<ReputationReset>,
+) -> (StatusCode, String) {
+    let Query(ReputationReset { sender, gas_coin }) = args;
+
+    let Some(traffic_controller) = state.node.traffic_controller().await else {
+        return (
+            StatusCode::NOT_FOUND,
+            "traffic control is not enabled on this node\n".to_string(),
+        );
+    };
+    let reputation = traffic_controller.transaction_reputation();
+
+    match (sender, gas_coin) {
+        (Some(sender), None) => {
+            let sender = match IotaAddress::from_str(&sender) {
+                Ok(sender) => sender,
+                Err(err) => return (StatusCode::BAD_REQUEST, err.to_string()),
+            };
+            if reputation
+                .senders
+                .as_ref()
+                .is_some_and(|senders| senders.reset(&sender))
+            {
+                (
+                    StatusCode::OK,
+                    format!("reputation of sender {sender} reset\n"),
+                )
+            } else {
+                (
+                    StatusCode::NOT_FOUND,
+                    format!("sender {sender} has no reputation\n"),
+                )
+            }
+        }
+        (None, Some(gas_coin)) => {
+            let gas_coin = match ObjectID::from_str(&gas_coin) {
+                Ok(gas_coin) => gas_coin,
+                Err(err) => return (StatusCode::BAD_REQUEST, err.to_string()),
+            };
+            if reputation
+                .gas_coins
+                .as_ref()
+                .is_some_and(|gas_coins| gas_coins.reset(&gas_coin))
+            {
+                (
+                    StatusCode::OK,
+                    format!("reputation of gas coin {gas_coin} reset\n"),
+                )
+            } else {
+                (
+                    StatusCode::NOT_FOUND,
+                    format!("gas coin {gas_coin} has no reputation\n"),
+                )
+            }
+        }
+        (None, None) => {
+            if let Some(senders) = &reputation.senders {
+                senders.clear();
+            }
+            if let Some(gas_coins) = &reputation.gas_coins {
+                gas_coins.clear();
+            }
+            (
+                StatusCode::OK,
+                "reputation of all senders and gas coins reset\n".to_string(),
+            )
+        }
+        (Some(_), Some(_)) => (
+            StatusCode::BAD_REQUEST,
+            "only one of sender and gas_coin may be given\n".to_string(),
+        ),
+    }
+}
+
+fn json_response(value: &impl Serialize) -> (StatusCode, String) {
+    match serde_json::to_string_pretty(value) {
+        Ok(json) => (StatusCode::OK, json + "\n"),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+/// The live object set of a trusted peer, read through the live object set
+/// routes of its admin interface.
+struct PeerLiveObjectSet {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl PeerLiveObjectSet {
+    fn new(url: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    async fn get<T: DeserializeOwned>(
+        &self,
+        route: &str,
+        range: ObjectIdRange,
+    ) -> anyhow::Result<T> {
+        Ok(self
+            .client
+            .get(format!("{}{route}", self.url))
+            .query(&range)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+}
+
+#[async_trait]
+impl LiveObjectSetReference for PeerLiveObjectSet {
+    async fn range_digest(&self, range: ObjectIdRange) -> anyhow::Result<RangeDigest> {
+        self.get(LIVE_OBJECT_SET_DIGEST_ROUTE, range).await
+    }
+
+    async fn range_object_refs(&self, mut range: ObjectIdRange) -> anyhow::Result<Vec<ObjectRef>> {
+        let mut object_refs = vec![];
+        loop {
+            let page: ObjectRefsPage = self.get(LIVE_OBJECT_SET_REFS_ROUTE, range).await?;
+            object_refs.extend(page.object_refs);
+            match page.next_cursor {
+                Some(cursor) if cursor > range.start => range.start = cursor,
+                Some(cursor) => {
+                    anyhow::bail!("Peer returned cursor {cursor} not past the start of {range}")
+                }
+                None => return Ok(object_refs),
+            }
+        }
+    }
+}
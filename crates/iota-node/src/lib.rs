@@ -54,6 +54,7 @@ use iota_core::{
     consensus_handler::ConsensusHandlerInitializer,
     consensus_manager::{ConsensusClient, ConsensusManager, ConsensusManagerTrait},
     consensus_validator::{IotaTxValidator, IotaTxValidatorMetrics},
+    db_backup::DBBackupHandler,
     db_checkpoint_handler::DBCheckpointHandler,
     epoch::{
         committee_store::CommitteeStore, consensus_store_pruner::ConsensusStorePruner,
@@ -61,6 +62,7 @@ use iota_core::{
         reconfiguration::ReconfigurationInitiator,
     },
     execution_cache::build_execution_cache,
+    live_object_set_audit::LiveObjectSetAudit,
     module_cache_metrics::ResolverMetrics,
     overload_monitor::overload_monitor,
     rest_index::RestIndexStore,
@@ -68,7 +70,7 @@ use iota_core::{
     signature_verifier::SignatureVerifierMetrics,
     state_accumulator::{StateAccumulator, StateAccumulatorMetrics},
     storage::{RestReadStore, RocksDbStore},
-    traffic_controller::metrics::TrafficControllerMetrics,
+    traffic_controller::{TrafficController, metrics::TrafficControllerMetrics},
     transaction_orchestrator::TransactionOrchestrator,
     validator_tx_finalizer::ValidatorTxFinalizer,
 };
@@ -86,10 +88,11 @@ use iota_metrics::{
     server_timing_middleware, spawn_monitored_task,
 };
 use iota_network::{
-    api::ValidatorServer, discovery, discovery::TrustedPeerChangeEvent, randomness, state_sync,
+    api::ValidatorServer, discovery, discovery::TrustedPeerChangeEvent, peer_table::PeerTable,
+    randomness, state_sync,
 };
 use iota_network_stack::server::ServerBuilder;
-use iota_protocol_config::{Chain, ProtocolConfig};
+use iota_protocol_config::{Chain, PerObjectCongestionControlMode, ProtocolConfig};
 use iota_rest_api::RestMetrics;
 use iota_snapshot::uploader::StateSnapshotUploader;
 use iota_storage::{
@@ -137,6 +140,7 @@ pub mod metrics;
 
 pub struct ValidatorComponents {
     validator_server_handle: JoinHandle<Result<()>>,
+    traffic_controller: Option<Arc<TrafficController>>,
     validator_overload_monitor_handle: Option<JoinHandle<()>>,
     consensus_manager: ConsensusManager,
     consensus_store_pruner: ConsensusStorePruner,
@@ -216,8 +220,10 @@ pub struct IotaNode {
     _discovery: discovery::Handle,
     state_sync_handle: state_sync::Handle,
     randomness_handle: randomness::Handle,
+    peer_table: Arc<PeerTable>,
     checkpoint_store: Arc<CheckpointStore>,
     accumulator: Mutex<Option<Arc<StateAccumulator>>>,
+    live_object_set_audit: Arc<LiveObjectSetAudit>,
     connection_monitor_status: Arc<ConnectionMonitorStatus>,
 
     /// Broadcast channel to send the starting system state for the next epoch.
@@ -229,6 +235,8 @@ pub struct IotaNode {
 
     _db_checkpoint_handle: Option<tokio::sync::broadcast::Sender<()>>,
 
+    db_backup_handler: Option<Arc<DBBackupHandler>>,
+
     #[cfg(msim)]
     sim_state: SimState,
 
@@ -256,6 +264,10 @@ impl fmt::Debug for IotaNode {
 
 static MAX_JWK_KEYS_PER_FETCH: usize = 100;
 
+// How often locally measured execution time estimates are shared through
+// consensus.
+const EXECUTION_TIME_OBSERVATION_INTERVAL: Duration = Duration::from_secs(5);
+
 impl IotaNode {
     pub async fn start(
         config: NodeConfig,
@@ -404,6 +416,39 @@ impl IotaNode {
         }
     }
 
+    /// Starts the task that periodically submits the execution time estimates
+    /// measured by this validator to consensus, where they are combined into
+    /// the estimates used for shared object congestion control.
+    fn start_execution_time_observer(
+        epoch_store: Arc<AuthorityPerEpochStore>,
+        consensus_adapter: Arc<ConsensusAdapter>,
+    ) {
+        let epoch = epoch_store.epoch();
+        info!("Starting execution time observer task");
+        spawn_monitored_task!(epoch_store.clone().within_alive_epoch(
+            async move {
+                loop {
+                    tokio::time::sleep(EXECUTION_TIME_OBSERVATION_INTERVAL).await;
+                    while let Some(transaction) =
+                        epoch_store.take_pending_execution_time_observation()
+                    {
+                        debug!("Submitting execution time observation to consensus");
+                        consensus_adapter
+                            .submit(transaction, None, &epoch_store)
+                            .tap_err(|e| {
+                                warn!(
+                                    "Error when submitting execution time observation to consensus {:?}",
+                                    e
+                                )
+                            })
+                            .ok();
+                    }
+                }
+            }
+            .instrument(error_span!("execution_time_observer_task", epoch)),
+        ));
+    }
+
     pub async fn start_async(
         config: NodeConfig,
         registry_service: RegistryService,
@@ -467,6 +512,7 @@ impl IotaNode {
         let is_genesis = perpetual_tables
             .database_is_empty()
             .expect("Database read should not fail at init.");
+        let live_object_set_audit = Arc::new(LiveObjectSetAudit::new(perpetual_tables.clone()));
         let store = AuthorityStore::open(
             perpetual_tables,
             &genesis,
@@ -614,6 +660,10 @@ impl IotaNode {
                 .unwrap_or_default()
                 .mailbox_capacity(),
         );
+        let peer_table = Arc::new(PeerTable::open(
+            config.peer_table_path(),
+            &config.p2p_config.state_sync.clone().unwrap_or_default(),
+        ));
         let (p2p_network, discovery_handle, state_sync_handle, randomness_handle) =
             Self::create_p2p_network(
                 &config,
@@ -622,6 +672,7 @@ impl IotaNode {
                 trusted_peer_change_rx,
                 archive_readers.clone(),
                 randomness_tx,
+                peer_table.clone(),
                 &prometheus_registry,
             )?;
 
@@ -653,6 +704,12 @@ impl IotaNode {
             state_snapshot_handle.is_some(),
         )?;
 
+        let db_backup_handler = config
+            .db_backup_config
+            .as_ref()
+            .map(|db_backup_config| DBBackupHandler::new(db_backup_config, &prometheus_registry))
+            .transpose()?;
+
         let mut genesis_objects = genesis.objects().to_vec();
         if let Some(migration_tx_data) = migration_tx_data.as_ref() {
             genesis_objects.extend(migration_tx_data.get_objects());
@@ -833,14 +890,18 @@ impl IotaNode {
             _discovery: discovery_handle,
             state_sync_handle,
             randomness_handle,
+            peer_table,
             checkpoint_store,
             accumulator: Mutex::new(Some(accumulator)),
+            live_object_set_audit,
             end_of_epoch_channel,
             connection_monitor_status,
             trusted_peer_change_tx,
 
             _db_checkpoint_handle: db_checkpoint_handle,
 
+            db_backup_handler,
+
             #[cfg(msim)]
             sim_state: Default::default(),
 
@@ -861,9 +922,55 @@ impl IotaNode {
             }
         });
 
+        if let Some(interval) = node
+            .db_backup_handler
+            .as_ref()
+            .and_then(|handler| handler.interval())
+        {
+            let node_copy = node.clone();
+            spawn_monitored_task!(Self::run_db_backup_loop(node_copy, interval));
+        }
+
         Ok(node)
     }
 
+    /// Takes a backup of the databases of the node, including the consensus
+    /// store if the node is a validator. Returns the path of the backup.
+    pub async fn backup_dbs(&self) -> Result<PathBuf> {
+        let Some(handler) = &self.db_backup_handler else {
+            return Err(anyhow!("DB backups are not configured for this node"));
+        };
+
+        let mut backup = handler.begin(self.state.clone()).await?;
+        if let Some(components) = &*self.validator_components.lock().await {
+            backup
+                .checkpoint_consensus(&components.consensus_manager)
+                .await?;
+        }
+        handler.finish(backup).await
+    }
+
+    /// The complete backups of the databases of the node, ordered from the
+    /// oldest to the most recent.
+    pub fn list_db_backups(&self) -> Result<Vec<PathBuf>> {
+        match &self.db_backup_handler {
+            Some(handler) => handler.list_backups(),
+            None => Err(anyhow!("DB backups are not configured for this node")),
+        }
+    }
+
+    async fn run_db_backup_loop(self: Arc<Self>, interval: Duration) {
+        let mut interval = tokio::time::interval(interval);
+        // The first tick completes immediately, do not back up at startup.
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            if let Err(err) = self.backup_dbs().await {
+                error!("Failed to take scheduled db backup: {err:?}");
+            }
+        }
+    }
+
     pub fn subscribe_to_epoch_change(&self) -> broadcast::Receiver<IotaSystemState> {
         self.end_of_epoch_channel.subscribe()
     }
@@ -1031,6 +1138,7 @@ impl IotaNode {
         trusted_peer_change_rx: watch::Receiver<TrustedPeerChangeEvent>,
         archive_readers: ArchiveReaderBalancer,
         randomness_tx: mpsc::Sender<(EpochId, RandomnessRound, Vec<u8>)>,
+        peer_table: Arc<PeerTable>,
         prometheus_registry: &Registry,
     ) -> Result<(
         Network,
@@ -1042,11 +1150,13 @@ impl IotaNode {
             .config(config.p2p_config.state_sync.clone().unwrap_or_default())
             .store(state_sync_store)
             .archive_readers(archive_readers)
+            .peer_table(peer_table.clone())
             .with_metrics(prometheus_registry)
             .build();
 
         let (discovery, discovery_server) = discovery::Builder::new(trusted_peer_change_rx)
             .config(config.p2p_config.clone())
+            .peer_table(peer_table)
             .build();
 
         let (randomness, randomness_router) =
@@ -1208,7 +1318,7 @@ impl IotaNode {
         let iota_tx_validator_metrics =
             IotaTxValidatorMetrics::new(&registry_service.default_registry());
 
-        let validator_server_handle = Self::start_grpc_validator_service(
+        let (validator_server_handle, traffic_controller) = Self::start_grpc_validator_service(
             &config,
             state.clone(),
             consensus_adapter.clone(),
@@ -1246,6 +1356,7 @@ impl IotaNode {
             consensus_store_pruner,
             accumulator,
             validator_server_handle,
+            traffic_controller,
             validator_overload_monitor_handle,
             checkpoint_metrics,
             iota_node_metrics,
@@ -1268,6 +1379,7 @@ impl IotaNode {
         consensus_store_pruner: ConsensusStorePruner,
         accumulator: Weak<StateAccumulator>,
         validator_server_handle: JoinHandle<Result<()>>,
+        traffic_controller: Option<Arc<TrafficController>>,
         validator_overload_monitor_handle: Option<JoinHandle<()>>,
         checkpoint_metrics: Arc<CheckpointMetrics>,
         iota_node_metrics: Arc<IotaNodeMetrics>,
@@ -1336,8 +1448,17 @@ impl IotaNode {
             );
         }
 
+        if epoch_store
+            .protocol_config()
+            .per_object_congestion_control_mode()
+            == PerObjectCongestionControlMode::ExecutionTimeEstimate
+        {
+            Self::start_execution_time_observer(epoch_store.clone(), consensus_adapter.clone());
+        }
+
         Ok(ValidatorComponents {
             validator_server_handle,
+            traffic_controller,
             validator_overload_monitor_handle,
             consensus_manager,
             consensus_store_pruner,
@@ -1431,7 +1552,10 @@ impl IotaNode {
         state: Arc<AuthorityState>,
         consensus_adapter: Arc<ConsensusAdapter>,
         prometheus_registry: &Registry,
-    ) -> Result<tokio::task::JoinHandle<Result<()>>> {
+    ) -> Result<(
+        tokio::task::JoinHandle<Result<()>>,
+        Option<Arc<TrafficController>>,
+    )> {
         let validator_service = ValidatorService::new(
             state.clone(),
             consensus_adapter,
@@ -1447,6 +1571,7 @@ impl IotaNode {
         let mut server_builder =
             ServerBuilder::from_config(&server_conf, GrpcMetrics::new(prometheus_registry));
 
+        let traffic_controller = validator_service.traffic_controller();
         server_builder = server_builder.add_service(ValidatorServer::new(validator_service));
 
         let server = server_builder
@@ -1457,7 +1582,7 @@ impl IotaNode {
         info!("Listening to traffic on {local_addr}");
         let grpc_server = spawn_monitored_task!(server.serve().map_err(Into::into));
 
-        Ok(grpc_server)
+        Ok((grpc_server, traffic_controller))
     }
 
     pub fn state(&self) -> Arc<AuthorityState> {
@@ -1641,6 +1766,7 @@ impl IotaNode {
 
             let new_validator_components = if let Some(ValidatorComponents {
                 validator_server_handle,
+                traffic_controller,
                 validator_overload_monitor_handle,
                 consensus_manager,
                 consensus_store_pruner,
@@ -1695,6 +1821,7 @@ impl IotaNode {
                             consensus_store_pruner,
                             weak_accumulator,
                             validator_server_handle,
+                            traffic_controller,
                             validator_overload_monitor_handle,
                             checkpoint_metrics,
                             self.metrics.clone(),
@@ -1867,6 +1994,26 @@ impl IotaNode {
     pub fn randomness_handle(&self) -> randomness::Handle {
         self.randomness_handle.clone()
     }
+
+    /// Returns the table of discovered peers and their reputation.
+    pub fn peer_table(&self) -> Arc<PeerTable> {
+        self.peer_table.clone()
+    }
+
+    /// Returns the audit of the live object set against a trusted reference.
+    pub fn live_object_set_audit(&self) -> Arc<LiveObjectSetAudit> {
+        self.live_object_set_audit.clone()
+    }
+
+    /// Returns the traffic controller of the validator service, if the node
+    /// is a validator and traffic control is configured.
+    pub async fn traffic_controller(&self) -> Option<Arc<TrafficController>> {
+        self.validator_components
+            .lock()
+            .await
+            .as_ref()
+            .and_then(|components| components.traffic_controller.clone())
+    }
 }
 
 #[cfg(not(msim))]
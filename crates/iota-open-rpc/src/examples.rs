@@ -265,6 +265,8 @@ impl RpcExampleProvider {
             error: None,
             raw_txn_data: vec![],
             raw_effects: vec![],
+            gas_breakdown: None,
+            trace: None,
         };
 
         Examples::new("iota_devInspectTransactionBlock", vec![
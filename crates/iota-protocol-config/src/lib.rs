@@ -16,13 +16,15 @@ use tracing::{info, warn};
 
 /// The minimum and maximum protocol versions supported by this build.
 const MIN_PROTOCOL_VERSION: u64 = 1;
-pub const MAX_PROTOCOL_VERSION: u64 = 2;
+pub const MAX_PROTOCOL_VERSION: u64 = 3;
 
 // Record history of protocol version allocations here:
 //
 // Version 1: Original version.
 // Version 2: Don't redistribute slashed staking rewards, fix computation of
 // SystemEpochInfoEventV1.
+// Version 3: Enable nested multisig, Schnorr BIP-340 and batched Ed25519
+// verification on devnet.
 #[derive(Copy, Clone, Debug, Hash, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ProtocolVersion(u64);
 
@@ -178,6 +180,18 @@ struct FeatureFlags {
     #[serde(skip_serializing_if = "is_false")]
     passkey_auth: bool,
 
+    // Enable multisig members which are multisigs themselves
+    #[serde(skip_serializing_if = "is_false")]
+    nested_multisig: bool,
+
+    // Enable BIP-340 Schnorr signature verification
+    #[serde(skip_serializing_if = "is_false")]
+    enable_schnorr_bip340: bool,
+
+    // Enable batch Ed25519 signature verification
+    #[serde(skip_serializing_if = "is_false")]
+    enable_ed25519_batch_verify: bool,
+
     // Rethrow type layout errors during serialization instead of trying to convert them.
     // This flag is used to provide the correct MoveVM configuration for clients.
     #[serde(skip_serializing_if = "is_true")]
@@ -216,6 +230,9 @@ pub enum PerObjectCongestionControlMode {
     None, // No congestion control.
     TotalGasBudget, // Use txn gas budget as execution cost.
     TotalTxCount,   // Use total txn count as execution cost.
+    // Use the committee-agreed execution time estimates of the transaction's
+    // Move calls, in microseconds, as execution cost.
+    ExecutionTimeEstimate,
 }
 
 impl PerObjectCongestionControlMode {
@@ -830,6 +847,17 @@ pub struct ProtocolConfig {
     vdf_verify_vdf_cost: Option<u64>,
     vdf_hash_to_input_cost: Option<u64>,
 
+    // schnorr::bip340_verify
+    schnorr_bip340_verify_cost_base: Option<u64>,
+    schnorr_bip340_verify_msg_cost_per_byte: Option<u64>,
+    schnorr_bip340_verify_msg_cost_per_block: Option<u64>,
+
+    // ed25519::ed25519_batch_verify
+    ed25519_batch_verify_cost_base: Option<u64>,
+    ed25519_batch_verify_cost_per_signature: Option<u64>,
+    ed25519_batch_verify_msg_cost_per_byte: Option<u64>,
+    ed25519_batch_verify_msg_cost_per_block: Option<u64>,
+
     // Stdlib costs
     bcs_per_byte_serialized_cost: Option<u64>,
     bcs_legacy_min_output_size_cost: Option<u64>,
@@ -932,7 +960,9 @@ pub struct ProtocolConfig {
 
     /// The max accumulated txn execution cost per object in a mysticeti commit.
     /// Transactions in a commit will be deferred once their touch shared
-    /// objects hit this limit.    
+    /// objects hit this limit. When using
+    /// `PerObjectCongestionControlMode::ExecutionTimeEstimate`, the cost is
+    /// measured in microseconds of estimated execution time.
     max_accumulated_txn_cost_per_object_in_mysticeti_commit: Option<u64>,
 }
 
@@ -1033,6 +1063,18 @@ impl ProtocolConfig {
         self.feature_flags.passkey_auth
     }
 
+    pub fn nested_multisig(&self) -> bool {
+        self.feature_flags.nested_multisig
+    }
+
+    pub fn enable_schnorr_bip340(&self) -> bool {
+        self.feature_flags.enable_schnorr_bip340
+    }
+
+    pub fn enable_ed25519_batch_verify(&self) -> bool {
+        self.feature_flags.enable_ed25519_batch_verify
+    }
+
     pub fn max_transaction_size_bytes(&self) -> u64 {
         // Provide a default value if protocol config version is too low.
         self.consensus_max_transaction_size_bytes
@@ -1489,6 +1531,15 @@ impl ProtocolConfig {
             vdf_verify_vdf_cost: None,
             vdf_hash_to_input_cost: None,
 
+            schnorr_bip340_verify_cost_base: None,
+            schnorr_bip340_verify_msg_cost_per_byte: None,
+            schnorr_bip340_verify_msg_cost_per_block: None,
+
+            ed25519_batch_verify_cost_base: None,
+            ed25519_batch_verify_cost_per_signature: None,
+            ed25519_batch_verify_msg_cost_per_byte: None,
+            ed25519_batch_verify_msg_cost_per_block: None,
+
             bcs_per_byte_serialized_cost: Some(2),
             bcs_legacy_min_output_size_cost: Some(1),
             bcs_failure_cost: Some(52),
@@ -1639,6 +1690,25 @@ impl ProtocolConfig {
                 1 => unreachable!(),
                 // version 2 is a new framework version but with no config changes
                 2 => {}
+                3 => {
+                    if chain != Chain::Mainnet && chain != Chain::Testnet {
+                        cfg.feature_flags.nested_multisig = true;
+
+                        // Priced like the secp256k1 ECDSA verification.
+                        cfg.feature_flags.enable_schnorr_bip340 = true;
+                        cfg.schnorr_bip340_verify_cost_base = Some(52);
+                        cfg.schnorr_bip340_verify_msg_cost_per_byte = Some(2);
+                        cfg.schnorr_bip340_verify_msg_cost_per_block = Some(2);
+
+                        // Batch verification shares the multiscalar multiplication across
+                        // the batch, so each signature costs half of an `ed25519_verify` call.
+                        cfg.feature_flags.enable_ed25519_batch_verify = true;
+                        cfg.ed25519_batch_verify_cost_base = Some(52);
+                        cfg.ed25519_batch_verify_cost_per_signature = Some(26);
+                        cfg.ed25519_batch_verify_msg_cost_per_byte = Some(2);
+                        cfg.ed25519_batch_verify_msg_cost_per_block = Some(2);
+                    }
+                }
                 // Use this template when making changes:
                 //
                 //     // modify an existing constant.
@@ -1759,6 +1829,18 @@ impl ProtocolConfig {
     pub fn set_passkey_auth_for_testing(&mut self, val: bool) {
         self.feature_flags.passkey_auth = val
     }
+
+    pub fn set_nested_multisig_for_testing(&mut self, val: bool) {
+        self.feature_flags.nested_multisig = val
+    }
+
+    pub fn set_enable_schnorr_bip340_for_testing(&mut self, val: bool) {
+        self.feature_flags.enable_schnorr_bip340 = val
+    }
+
+    pub fn set_enable_ed25519_batch_verify_for_testing(&mut self, val: bool) {
+        self.feature_flags.enable_ed25519_batch_verify = val
+    }
 }
 
 type OverrideFn = dyn Fn(ProtocolVersion, ProtocolConfig) -> ProtocolConfig + Send + Sync;
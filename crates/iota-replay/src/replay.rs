@@ -25,6 +25,7 @@ use iota_types::{
     digests::{ObjectDigest, TransactionDigest},
     error::{ExecutionError, IotaError, IotaResult},
     executable_transaction::VerifiedExecutableTransaction,
+    execution::ExecutionInstrumentation,
     gas::IotaGasStatus,
     in_memory_storage::InMemoryStorage,
     inner_temporary_store::InnerTemporaryStore,
@@ -853,6 +854,7 @@ impl LocalExec {
                             tx_info.sender,
                             tx_info.sender_signed_data.digest(),
                             skip_checks,
+                            &mut ExecutionInstrumentation::default(),
                         )
                         .3
                         .unwrap_or_default(),
@@ -14,9 +14,10 @@ use iota_json_rpc_types::{
     Checkpoint, CheckpointId, CheckpointPage, DevInspectArgs, DevInspectResults,
     DryRunTransactionBlockResponse, DynamicFieldPage, IotaData, IotaGetPastObjectRequest,
     IotaMoveNormalizedModule, IotaObjectDataOptions, IotaObjectResponse, IotaObjectResponseQuery,
-    IotaPastObjectResponse, IotaTransactionBlockEffects, IotaTransactionBlockResponse,
-    IotaTransactionBlockResponseOptions, IotaTransactionBlockResponseQuery, ObjectsPage,
-    ProtocolConfigResponse, TransactionBlocksPage, TransactionFilter,
+    IotaPastObjectResponse, IotaStateOverrides, IotaTransactionBlockEffects,
+    IotaTransactionBlockResponse, IotaTransactionBlockResponseOptions,
+    IotaTransactionBlockResponseQuery, ObjectsPage, ProtocolConfigResponse, TransactionBlocksPage,
+    TransactionFilter,
 };
 use iota_types::{
     base_types::{IotaAddress, ObjectID, SequenceNumber, TransactionDigest},
@@ -605,7 +606,30 @@ impl ReadApi {
         Ok(self
             .api
             .http
-            .dry_run_transaction_block(Base64::from_bytes(&bcs::to_bytes(&tx)?))
+            .dry_run_transaction_block(Base64::from_bytes(&bcs::to_bytes(&tx)?), None)
+            .await?)
+    }
+
+    /// Dry run a transaction block against the on-chain state with the given
+    /// overrides applied.
+    ///
+    /// Objects can be replaced with other contents or versions, packages with
+    /// locally compiled bytecode, and the clock and epoch can be set. The
+    /// response reports effects and events as if the overridden state were
+    /// real, e.g. to analyze what-if scenarios or test package upgrades before
+    /// publishing them.
+    pub async fn dry_run_transaction_block_with_overrides(
+        &self,
+        tx: TransactionData,
+        state_overrides: IotaStateOverrides,
+    ) -> IotaRpcResult<DryRunTransactionBlockResponse> {
+        Ok(self
+            .api
+            .http
+            .dry_run_transaction_block(
+                Base64::from_bytes(&bcs::to_bytes(&tx)?),
+                Some(state_overrides),
+            )
             .await?)
     }
 
@@ -0,0 +1,263 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Decoding of the BCS contents of events and objects, e.g. from
+//! [`CheckpointData`], into Move values and JSON, using the layouts of their
+//! types from the packages that define them.
+//!
+//! A [Decoder] reads packages from any [PackageStore]. [LocalPackageStore]
+//! keeps packages in memory, so that a consumer of checkpoints can decode
+//! their contents without a connection to a fullnode, by adding the packages
+//! published in each checkpoint to it before decoding the checkpoint.
+//!
+//! ```rust,no_run
+//! use iota_sdk::decoder::{Decoder, LocalPackageStore};
+//! # use iota_types::full_checkpoint_content::CheckpointData;
+//!
+//! # async fn process(checkpoints: Vec<CheckpointData>) -> Result<(), anyhow::Error> {
+//! let decoder = Decoder::new(LocalPackageStore::new());
+//! for checkpoint in checkpoints {
+//!     decoder
+//!         .package_store()
+//!         .update_from_checkpoint(&checkpoint)?;
+//!     for event in decoder.decode_checkpoint_events(&checkpoint).await? {
+//!         println!("{}: {}", event.type_, event.parsed_json);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use async_trait::async_trait;
+use iota_json_rpc_types::{IotaEvent, IotaMoveValue};
+use iota_package_resolver::{
+    Package, PackageStore, Resolver, Result as ResolverResult, error::Error as ResolverError,
+};
+use iota_types::{
+    base_types::SequenceNumber, event::Event, full_checkpoint_content::CheckpointData,
+    move_package::MovePackage, object::Object,
+};
+use lru::LruCache;
+use move_core_types::{
+    account_address::AccountAddress,
+    annotated_value::{MoveDatatypeLayout, MoveTypeLayout, MoveValue},
+    language_storage::{StructTag, TypeTag},
+};
+use serde_json::Value;
+
+use crate::error::{Error, IotaRpcResult};
+
+/// The number of type layouts that a [Decoder] keeps.
+const LAYOUT_CACHE_SIZE: NonZeroUsize = unsafe { NonZeroUsize::new_unchecked(1024) };
+
+/// Decodes BCS encoded Move values, given their types.
+///
+/// Layouts of types are cached, so packages are only read from the store the
+/// first time a type is decoded. To also avoid reading the same package
+/// repeatedly for different types from a remote store, wrap it in a
+/// [`PackageStoreWithLruCache`](iota_package_resolver::PackageStoreWithLruCache).
+pub struct Decoder<S> {
+    resolver: Resolver<S>,
+    layouts: Mutex<LruCache<StructTag, MoveTypeLayout>>,
+}
+
+impl<S> Decoder<S> {
+    pub fn new(package_store: S) -> Self {
+        Self {
+            resolver: Resolver::new(package_store),
+            layouts: Mutex::new(LruCache::new(LAYOUT_CACHE_SIZE)),
+        }
+    }
+
+    pub fn package_store(&self) -> &S {
+        self.resolver.package_store()
+    }
+}
+
+impl<S: PackageStore> Decoder<S> {
+    /// Return the layout of the type `type_`. The types in the layout refer to
+    /// the packages that define them.
+    pub async fn type_layout(&self, type_: &StructTag) -> IotaRpcResult<MoveTypeLayout> {
+        if let Some(layout) = self.layouts.lock().unwrap().get(type_) {
+            return Ok(layout.clone());
+        }
+
+        let layout = self
+            .resolver
+            .type_layout(TypeTag::Struct(Box::new(type_.clone())))
+            .await?;
+
+        // A type's layout never changes, even if its package is upgraded, so it can
+        // be cached for as long as there is room for it.
+        self.layouts
+            .lock()
+            .unwrap()
+            .put(type_.clone(), layout.clone());
+        Ok(layout)
+    }
+
+    /// Decode the BCS encoded value `bytes` of type `type_`.
+    pub async fn decode(&self, type_: &StructTag, bytes: &[u8]) -> IotaRpcResult<MoveValue> {
+        let layout = self.type_layout(type_).await?;
+        MoveValue::simple_deserialize(bytes, &layout)
+            .map_err(|e| Error::Data(format!("Failed to deserialize value of type {type_}: {e}")))
+    }
+
+    /// Decode the BCS encoded value `bytes` of type `type_` to JSON, in the
+    /// same format that a fullnode uses for the parsed contents of events and
+    /// objects.
+    pub async fn decode_json(&self, type_: &StructTag, bytes: &[u8]) -> IotaRpcResult<Value> {
+        let value = self.decode(type_, bytes).await?;
+        Ok(IotaMoveValue::from(value).to_json_value())
+    }
+
+    /// Decode the contents of `event` to JSON.
+    pub async fn decode_event(&self, event: &Event) -> IotaRpcResult<Value> {
+        self.decode_json(&event.type_, &event.contents).await
+    }
+
+    /// Decode the contents of `object` to JSON. Fails if the object is a
+    /// package.
+    pub async fn decode_object(&self, object: &Object) -> IotaRpcResult<Value> {
+        let Some(move_object) = object.data.try_as_move() else {
+            return Err(Error::Data(format!(
+                "Object {} is a package, not a Move object",
+                object.id()
+            )));
+        };
+        self.decode_json(&move_object.type_().clone().into(), move_object.contents())
+            .await
+    }
+
+    /// Decode all events emitted in `checkpoint`, as a fullnode would return
+    /// them.
+    ///
+    /// The packages that are published in the checkpoint must already be in
+    /// the store, if the checkpoint emits events of types they define.
+    pub async fn decode_checkpoint_events(
+        &self,
+        checkpoint: &CheckpointData,
+    ) -> IotaRpcResult<Vec<IotaEvent>> {
+        let timestamp_ms = checkpoint.checkpoint_summary.timestamp_ms;
+        let mut events = vec![];
+
+        for transaction in &checkpoint.transactions {
+            let Some(tx_events) = &transaction.events else {
+                continue;
+            };
+
+            let tx_digest = *transaction.transaction.digest();
+            for (event_seq, event) in tx_events.data.iter().enumerate() {
+                let layout = match self.type_layout(&event.type_).await? {
+                    MoveTypeLayout::Struct(s) => MoveDatatypeLayout::Struct(s),
+                    MoveTypeLayout::Enum(e) => MoveDatatypeLayout::Enum(e),
+                    _ => {
+                        return Err(Error::Data(format!(
+                            "Event type {} is not a datatype",
+                            event.type_
+                        )));
+                    }
+                };
+
+                events.push(
+                    IotaEvent::try_from(
+                        event.clone(),
+                        tx_digest,
+                        event_seq as u64,
+                        Some(timestamp_ms),
+                        layout,
+                    )
+                    .map_err(|e| Error::Data(e.to_string()))?,
+                );
+            }
+        }
+
+        Ok(events)
+    }
+}
+
+/// A [PackageStore] that keeps packages in memory.
+///
+/// Packages are fetched by their storage ID, except that fetching a package by
+/// its original ID returns the latest version of it in the store. That way, a
+/// type can also be decoded when it is referred to through the original ID of
+/// its package, even if it was only introduced by an upgrade.
+#[derive(Default)]
+pub struct LocalPackageStore {
+    inner: RwLock<LocalPackages>,
+}
+
+#[derive(Default)]
+struct LocalPackages {
+    /// Packages by storage ID.
+    packages: HashMap<AccountAddress, Arc<Package>>,
+    /// The storage ID and version of the latest version of each package, by
+    /// original ID.
+    latest: HashMap<AccountAddress, (AccountAddress, SequenceNumber)>,
+}
+
+impl LocalPackageStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `package` to the store.
+    pub fn insert_package(&self, package: &MovePackage) -> IotaRpcResult<()> {
+        let storage_id = AccountAddress::from(package.id());
+        let original_id = AccountAddress::from(package.original_package_id());
+        let version = package.version();
+        let resolved = Arc::new(Package::read_from_package(package)?);
+
+        let mut inner = self.inner.write().unwrap();
+        inner.packages.insert(storage_id, resolved);
+        match inner.latest.get(&original_id) {
+            Some((_, latest)) if *latest >= version => {}
+            _ => {
+                inner.latest.insert(original_id, (storage_id, version));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Add `object` to the store, if it is a package.
+    pub fn insert_object(&self, object: &Object) -> IotaRpcResult<()> {
+        match object.data.try_as_package() {
+            Some(package) => self.insert_package(package),
+            None => Ok(()),
+        }
+    }
+
+    /// Add all packages that are published or upgraded in `checkpoint` to the
+    /// store.
+    pub fn update_from_checkpoint(&self, checkpoint: &CheckpointData) -> IotaRpcResult<()> {
+        for transaction in &checkpoint.transactions {
+            for object in &transaction.output_objects {
+                self.insert_object(object)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PackageStore for LocalPackageStore {
+    async fn fetch(&self, id: AccountAddress) -> ResolverResult<Arc<Package>> {
+        let inner = self.inner.read().unwrap();
+        let storage_id = inner
+            .latest
+            .get(&id)
+            .map_or(id, |(storage_id, _)| *storage_id);
+        inner
+            .packages
+            .get(&storage_id)
+            .cloned()
+            .ok_or(ResolverError::PackageNotFound(id))
+    }
+}
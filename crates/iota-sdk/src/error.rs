@@ -32,4 +32,6 @@ pub enum Error {
     InsufficientFunds { address: IotaAddress, amount: u128 },
     #[error(transparent)]
     Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    PackageResolver(#[from] iota_package_resolver::error::Error),
 }
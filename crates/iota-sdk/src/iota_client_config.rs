@@ -4,7 +4,7 @@
 
 use std::fmt::{Display, Formatter, Write};
 
-use anyhow::anyhow;
+use anyhow::{anyhow, ensure};
 use getset::{Getters, MutGetters};
 use iota_config::Config;
 use iota_keys::keystore::{AccountKeystore, Keystore};
@@ -25,6 +25,9 @@ pub struct IotaClientConfig {
     pub(crate) envs: Vec<IotaEnv>,
     pub(crate) active_env: Option<String>,
     pub(crate) active_address: Option<IotaAddress>,
+    /// Addresses known by their alias without a key in the keystore.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) watch_only_addresses: Vec<WatchOnlyAddress>,
 }
 
 impl IotaClientConfig {
@@ -34,6 +37,7 @@ impl IotaClientConfig {
             envs: Default::default(),
             active_env: None,
             active_address: None,
+            watch_only_addresses: Default::default(),
         }
     }
 
@@ -90,6 +94,77 @@ impl IotaClientConfig {
             self.envs.push(env)
         }
     }
+
+    /// Add an address without its key, known by the given alias. The alias
+    /// must not be used by another address, with or without key.
+    pub fn add_watch_only_address(
+        &mut self,
+        alias: String,
+        address: IotaAddress,
+    ) -> Result<(), anyhow::Error> {
+        ensure!(
+            !self.keystore.alias_exists(&alias) && self.get_watch_only_address(&alias).is_none(),
+            "Alias {alias} is already used"
+        );
+        ensure!(
+            !self.keystore.addresses().contains(&address),
+            "Address {address} is already managed with its key"
+        );
+        ensure!(
+            !self.is_watch_only(&address),
+            "Address {address} is already watched"
+        );
+        self.watch_only_addresses
+            .push(WatchOnlyAddress { alias, address });
+        Ok(())
+    }
+
+    /// Remove the watch-only address with the given alias. If it was the
+    /// active address, the first address of the keystore becomes active.
+    pub fn remove_watch_only_address(&mut self, alias: &str) -> Result<IotaAddress, anyhow::Error> {
+        let index = self
+            .watch_only_addresses
+            .iter()
+            .position(|watched| watched.alias == alias)
+            .ok_or_else(|| anyhow!("No watch-only address with alias {alias}"))?;
+        let removed = self.watch_only_addresses.remove(index);
+        if self.active_address == Some(removed.address) {
+            self.active_address = None;
+        }
+        Ok(removed.address)
+    }
+
+    pub fn get_watch_only_address(&self, alias: &str) -> Option<IotaAddress> {
+        self.watch_only_addresses
+            .iter()
+            .find(|watched| watched.alias == alias)
+            .map(|watched| watched.address)
+    }
+
+    pub fn is_watch_only(&self, address: &IotaAddress) -> bool {
+        self.watch_only_addresses
+            .iter()
+            .any(|watched| &watched.address == address)
+    }
+
+    /// Get the address with the given alias, looking up the keystore first
+    /// and then the watch-only addresses.
+    pub fn get_address_by_alias(&self, alias: String) -> Result<IotaAddress, anyhow::Error> {
+        match self.keystore.get_address_by_alias(alias.clone()) {
+            Ok(address) => Ok(*address),
+            Err(err) => self.get_watch_only_address(&alias).ok_or(err),
+        }
+    }
+}
+
+/// An address known by an alias without a key in the keystore, e.g. a
+/// multisig address or an address whose key is kept offline. Transactions
+/// sent by it can be built, but have to be signed elsewhere.
+#[derive(Debug, Clone, Serialize, Deserialize, Getters)]
+#[getset(get = "pub")]
+pub struct WatchOnlyAddress {
+    pub(crate) alias: String,
+    pub(crate) address: IotaAddress,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Getters, MutGetters)]
@@ -235,6 +310,13 @@ impl Display for IotaClientConfig {
             "Managed addresses: {}",
             self.keystore.addresses().len()
         )?;
+        if !self.watch_only_addresses.is_empty() {
+            writeln!(
+                writer,
+                "Watch-only addresses: {}",
+                self.watch_only_addresses.len()
+            )?;
+        }
         write!(writer, "Active address: ")?;
         match self.active_address {
             Some(r) => writeln!(writer, "{}", r)?,
@@ -19,6 +19,8 @@
 //!   and submit it to the fullnode(s)
 //! * [ReadApi] - provides functions for retrieving data about different objects
 //!   and transactions
+//! * [Decoder](decoder::Decoder) - decodes the BCS contents of events and
+//!   objects, e.g. from checkpoints, using the packages that define their types
 //! * <a href="../iota_transaction_builder/struct.TransactionBuilder.html"
 //!   title="struct
 //!   iota_transaction_builder::TransactionBuilder">TransactionBuilder</a> -
@@ -74,6 +76,7 @@
 //! in the [repository](https://github.com/iotaledger/iota/tree/main/crates/iota-sdk/examples).
 
 pub mod apis;
+pub mod decoder;
 pub mod error;
 pub mod iota_client_config;
 pub mod json_rpc_error;
@@ -93,12 +96,15 @@ use iota_json_rpc_api::{
 };
 pub use iota_json_rpc_types as rpc_types;
 use iota_json_rpc_types::{
-    IotaObjectDataFilter, IotaObjectDataOptions, IotaObjectResponse, IotaObjectResponseQuery,
-    ObjectsPage,
+    DynamicFieldPage, IotaObjectDataFilter, IotaObjectDataOptions, IotaObjectResponse,
+    IotaObjectResponseQuery, ObjectsPage,
 };
 use iota_transaction_builder::{DataReader, TransactionBuilder};
 pub use iota_types as types;
-use iota_types::base_types::{IotaAddress, ObjectID, ObjectInfo};
+use iota_types::{
+    base_types::{IotaAddress, ObjectID, ObjectInfo},
+    dynamic_field::DynamicFieldInfo,
+};
 use jsonrpsee::{
     core::client::ClientT,
     http_client::{HeaderMap, HeaderValue, HttpClient, HttpClientBuilder},
@@ -600,4 +606,25 @@ impl DataReader for ReadApi {
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error> {
         Ok(self.get_reference_gas_price().await?)
     }
+
+    async fn get_dynamic_fields(
+        &self,
+        object_id: ObjectID,
+    ) -> Result<Vec<DynamicFieldInfo>, anyhow::Error> {
+        let mut result = vec![];
+        let mut has_next = true;
+        let mut cursor = None;
+
+        while has_next {
+            let DynamicFieldPage {
+                data,
+                next_cursor,
+                has_next_page,
+            } = self.get_dynamic_fields(object_id, cursor, None).await?;
+            result.extend(data);
+            cursor = next_cursor;
+            has_next = has_next_page;
+        }
+        Ok(result)
+    }
 }
@@ -83,21 +83,19 @@ impl WalletContext {
 
     // TODO: Ger rid of mut
     pub fn active_address(&mut self) -> Result<IotaAddress, anyhow::Error> {
-        if self.config.keystore.addresses().is_empty() {
+        // The active address may be a watch-only address, which is not in the
+        // keystore.
+        if let Some(active_address) = self.config.active_address {
+            return Ok(active_address);
+        }
+        let Some(address) = self.config.keystore.addresses().first().copied() else {
             return Err(anyhow!(
                 "No managed addresses. Create new address with `new-address` command."
             ));
-        }
-
-        // Ok to unwrap because we checked that config addresses not empty
+        };
         // Set it if not exists
-        self.config.active_address = Some(
-            self.config
-                .active_address
-                .unwrap_or(*self.config.keystore.addresses().first().unwrap()),
-        );
-
-        Ok(self.config.active_address.unwrap())
+        self.config.active_address = Some(address);
+        Ok(address)
     }
 
     /// Get the latest object reference given a object id.
@@ -0,0 +1,10 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+#[allow(unused_field)]
+module decoder::events {
+    public struct Ping has copy, drop {
+        value: u64,
+        flag: bool,
+    }
+}
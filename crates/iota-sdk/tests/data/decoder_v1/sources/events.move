@@ -0,0 +1,15 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+#[allow(unused_field)]
+module decoder::events {
+    public struct Ping has copy, drop {
+        value: u64,
+        flag: bool,
+    }
+
+    public struct Pong has copy, drop {
+        ping: Ping,
+        count: u16,
+    }
+}
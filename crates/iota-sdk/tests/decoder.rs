@@ -0,0 +1,106 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{path::PathBuf, str::FromStr};
+
+use iota_move_build::{BuildConfig, CompiledPackage};
+use iota_protocol_config::ProtocolConfig;
+use iota_sdk::decoder::{Decoder, LocalPackageStore};
+use iota_types::{base_types::ObjectID, move_package::MovePackage};
+use move_core_types::{
+    account_address::AccountAddress, annotated_value::MoveValue, language_storage::StructTag,
+};
+use serde_json::json;
+
+fn build_test_package(dir: &str) -> CompiledPackage {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.extend(["tests", "data", dir]);
+    BuildConfig::new_for_testing().build(&path).unwrap()
+}
+
+fn tag(s: &str) -> StructTag {
+    StructTag::from_str(s).unwrap()
+}
+
+/// Publish `decoder_v0` at 0xd0 and upgrade it to `decoder_v1` at 0xd1.
+fn test_packages() -> (MovePackage, MovePackage) {
+    let config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let v0 = MovePackage::new_initial(
+        &build_test_package("decoder_v0").into_modules(),
+        u64::MAX,
+        config.move_binary_format_version(),
+        [],
+    )
+    .unwrap();
+    let v1 = v0
+        .new_upgraded(
+            ObjectID::from_single_byte(0xd1),
+            &build_test_package("decoder_v1").into_modules(),
+            &config,
+            [],
+        )
+        .unwrap();
+    (v0, v1)
+}
+
+#[tokio::test]
+async fn decode_json() {
+    let (v0, _) = test_packages();
+    let store = LocalPackageStore::new();
+    store.insert_package(&v0).unwrap();
+    let decoder = Decoder::new(store);
+
+    let bytes = bcs::to_bytes(&(42u64, true)).unwrap();
+    let value = decoder
+        .decode_json(&tag("0xd0::events::Ping"), &bytes)
+        .await
+        .unwrap();
+    assert_eq!(value, json!({ "value": "42", "flag": true }));
+
+    // The type was never published.
+    assert!(
+        decoder
+            .decode_json(&tag("0xd0::events::Pong"), &bytes)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn decode_upgraded_type() {
+    let (v0, v1) = test_packages();
+    let store = LocalPackageStore::new();
+    store.insert_package(&v0).unwrap();
+    store.insert_package(&v1).unwrap();
+    let decoder = Decoder::new(store);
+
+    let bytes = bcs::to_bytes(&((7u64, false), 3u16)).unwrap();
+    let expected = json!({ "ping": { "value": "7", "flag": false }, "count": 3 });
+
+    // The type can be referred to by its defining ID, or the original ID of its
+    // package.
+    for type_ in ["0xd1::events::Pong", "0xd0::events::Pong"] {
+        let value = decoder.decode_json(&tag(type_), &bytes).await.unwrap();
+        assert_eq!(value, expected);
+    }
+
+    // Decoded types refer to the packages that define them.
+    let MoveValue::Struct(pong) = decoder
+        .decode(&tag("0xd0::events::Pong"), &bytes)
+        .await
+        .unwrap()
+    else {
+        panic!("Expected a struct");
+    };
+    assert_eq!(
+        pong.type_.address,
+        AccountAddress::from_str("0xd1").unwrap()
+    );
+    let MoveValue::Struct(ping) = &pong.fields[0].1 else {
+        panic!("Expected a struct");
+    };
+    assert_eq!(
+        ping.type_.address,
+        AccountAddress::from_str("0xd0").unwrap()
+    );
+}
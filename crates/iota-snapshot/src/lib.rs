@@ -7,6 +7,7 @@
 #[cfg(test)]
 mod tests;
 
+pub mod live_object_set;
 pub mod reader;
 pub mod uploader;
 mod writer;
@@ -0,0 +1,77 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::num::NonZeroUsize;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use indicatif::{MultiProgress, ProgressDrawTarget};
+use iota_config::object_storage_config::ObjectStoreConfig;
+use iota_core::live_object_set_audit::{LiveObjectSetReference, ObjectIdRange, RangeDigest};
+use iota_types::base_types::ObjectRef;
+
+use crate::reader::StateSnapshotReaderV1;
+
+/// The live object set at the end of an epoch, as recorded by the references
+/// of a formal snapshot, to audit the live object set of a node against.
+///
+/// All references of the snapshot are held in memory, ordered by object ID.
+pub struct SnapshotLiveObjectSet {
+    epoch: u64,
+    refs: Vec<ObjectRef>,
+}
+
+impl SnapshotLiveObjectSet {
+    /// Downloads the references of the snapshot of `epoch` from the remote
+    /// store to the local staging directory, and loads them.
+    pub async fn load(
+        epoch: u64,
+        remote_store_config: &ObjectStoreConfig,
+        local_store_config: &ObjectStoreConfig,
+        download_concurrency: NonZeroUsize,
+    ) -> Result<Self> {
+        let reader = StateSnapshotReaderV1::new(
+            epoch,
+            remote_store_config,
+            local_store_config,
+            usize::MAX,
+            download_concurrency,
+            MultiProgress::with_draw_target(ProgressDrawTarget::hidden()),
+        )
+        .await?;
+        Self::from_reader(epoch, &reader)
+    }
+
+    fn from_reader(epoch: u64, reader: &StateSnapshotReaderV1) -> Result<Self> {
+        let mut refs = vec![];
+        for ref_iter in reader.ref_iters()? {
+            refs.extend(ref_iter);
+        }
+        refs.sort_by_key(|object_ref| object_ref.0);
+
+        Ok(Self { epoch, refs })
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    fn range(&self, range: ObjectIdRange) -> &[ObjectRef] {
+        let start = self.refs.partition_point(|r| r.0 < range.start);
+        let end = self.refs.partition_point(|r| r.0 <= range.end);
+        &self.refs[start..end]
+    }
+}
+
+#[async_trait]
+impl LiveObjectSetReference for SnapshotLiveObjectSet {
+    async fn range_digest(&self, range: ObjectIdRange) -> Result<RangeDigest> {
+        Ok(RangeDigest::from_object_refs(
+            self.range(range).iter().copied(),
+        ))
+    }
+
+    async fn range_object_refs(&self, range: ObjectIdRange) -> Result<Vec<ObjectRef>> {
+        Ok(self.range(range).to_vec())
+    }
+}
@@ -533,6 +533,21 @@ impl StateSnapshotReaderV1 {
         )
     }
 
+    /// Returns iterators over the references in all .ref files.
+    pub fn ref_iters(&self) -> Result<Vec<ObjectRefIter>> {
+        self.ref_files
+            .values()
+            .flat_map(|part_files| part_files.values())
+            .map(|file_metadata| {
+                ObjectRefIter::new(
+                    file_metadata,
+                    self.local_staging_dir_root.clone(),
+                    self.epoch_dir(),
+                )
+            })
+            .collect()
+    }
+
     /// Returns a list of all buckets.
     fn buckets(&self) -> Result<Vec<u32>> {
         Ok(self.ref_files.keys().copied().collect())
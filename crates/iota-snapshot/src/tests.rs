@@ -2,7 +2,7 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::HashSet, num::NonZeroUsize, sync::Arc};
+use std::{collections::HashSet, num::NonZeroUsize, sync::Arc, time::Duration};
 
 use fastcrypto::hash::MultisetHash;
 use futures::future::AbortHandle;
@@ -10,6 +10,10 @@ use indicatif::MultiProgress;
 use iota_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
 use iota_core::{
     authority::authority_store_tables::AuthorityPerpetualTables,
+    live_object_set_audit::{
+        LiveObjectSetAudit, LiveObjectSetAuditConfig, LiveObjectSetAuditStatus,
+        LiveObjectSetReference, ObjectDivergence,
+    },
     state_accumulator::StateAccumulator,
 };
 use iota_types::{
@@ -18,7 +22,10 @@ use iota_types::{
 };
 use tempfile::tempdir;
 
-use crate::{FileCompression, reader::StateSnapshotReaderV1, writer::StateSnapshotWriterV1};
+use crate::{
+    FileCompression, live_object_set::SnapshotLiveObjectSet, reader::StateSnapshotReaderV1,
+    writer::StateSnapshotWriterV1,
+};
 
 fn temp_dir() -> std::path::PathBuf {
     tempdir()
@@ -169,3 +176,82 @@ async fn test_snapshot_empty_db() -> Result<(), anyhow::Error> {
     compare_live_objects(&perpetual_db, &restored_perpetual_db)?;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_audit_live_object_set_against_snapshot() -> Result<(), anyhow::Error> {
+    let db_path = temp_dir();
+    let local = temp_dir().join("local_dir");
+    let remote = temp_dir().join("remote_dir");
+    let restored_local = temp_dir().join("local_dir_restore");
+    let local_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(local),
+        ..Default::default()
+    };
+    let remote_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(remote),
+        ..Default::default()
+    };
+    let snapshot_writer = StateSnapshotWriterV1::new(
+        &local_store_config,
+        &remote_store_config,
+        FileCompression::Zstd,
+        NonZeroUsize::new(1).unwrap(),
+    )
+    .await?;
+    let perpetual_db = Arc::new(AuthorityPerpetualTables::open(&db_path, None));
+    insert_keys(&perpetual_db, 1000)?;
+    let root_accumulator =
+        ECMHLiveObjectSetDigest::from(accumulate_live_object_set(&perpetual_db).digest());
+    snapshot_writer
+        .write_internal(0, perpetual_db.clone(), root_accumulator)
+        .await?;
+    let local_store_restore_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(restored_local),
+        ..Default::default()
+    };
+    let reference: Arc<dyn LiveObjectSetReference> = Arc::new(
+        SnapshotLiveObjectSet::load(
+            0,
+            &remote_store_config,
+            &local_store_restore_config,
+            NonZeroUsize::new(1).unwrap(),
+        )
+        .await?,
+    );
+
+    // An object that is not part of the snapshot.
+    let unexpected = Object::immutable_with_id_for_testing(ObjectID::MAX);
+    perpetual_db.insert_object_test_only(unexpected.clone())?;
+
+    let audit = LiveObjectSetAudit::new(perpetual_db.clone());
+    let config = LiveObjectSetAuditConfig {
+        max_objects_per_range: 10,
+        throttle: Duration::ZERO,
+    };
+    audit.start("snapshot".to_string(), None, config, async move {
+        Ok(reference)
+    })?;
+
+    let report = loop {
+        let report = audit.report();
+        if !matches!(
+            report.status,
+            LiveObjectSetAuditStatus::LoadingReference | LiveObjectSetAuditStatus::Running
+        ) {
+            break report;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+
+    assert_eq!(report.status, LiveObjectSetAuditStatus::Completed);
+    assert_eq!(report.diverging_ranges.len(), 1);
+    assert_eq!(report.diverging_ranges[0].objects, vec![
+        ObjectDivergence::Unexpected {
+            local: unexpected.compute_object_reference()
+        }
+    ]);
+    Ok(())
+}
@@ -194,6 +194,7 @@ impl ValidatorConfigBuilder {
             checkpoint_executor_config,
             supported_protocol_versions: self.supported_protocol_versions,
             db_checkpoint_config: Default::default(),
+            db_backup_config: None,
             indirect_objects_threshold: usize::MAX,
             // By default, expensive checks will be enabled in debug build, but not in release
             // build.
@@ -484,6 +485,7 @@ impl FullnodeConfigBuilder {
             checkpoint_executor_config,
             supported_protocol_versions: self.supported_protocol_versions,
             db_checkpoint_config: self.db_checkpoint_config.unwrap_or_default(),
+            db_backup_config: None,
             indirect_objects_threshold: usize::MAX,
             expensive_safety_check_config: self
                 .expensive_safety_check_config
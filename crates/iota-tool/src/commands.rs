@@ -33,7 +33,7 @@ use crate::{
     db_tool::{DbToolCommand, execute_db_tool_command, print_db_all_tables},
     download_db_snapshot, download_formal_snapshot, dump_checkpoints_from_archive,
     get_latest_available_epoch, get_object, get_transaction_block, make_clients,
-    restore_from_db_checkpoint, verify_archive, verify_archive_by_checksum,
+    restore_from_backup, restore_from_db_checkpoint, verify_archive, verify_archive_by_checksum,
 };
 
 #[derive(Parser, Clone, ValueEnum)]
@@ -253,6 +253,27 @@ pub enum ToolCommand {
         db_checkpoint_path: PathBuf,
     },
 
+    /// Verifies a backup taken by a node with `db-backup-config` against
+    /// its manifest, and installs it into the database directory of the node,
+    /// including the consensus store for validators. The node must be
+    /// stopped. A restored validator no longer knows about the blocks and
+    /// transactions it signed after the backup was taken, so enable amnesia
+    /// recovery in its consensus parameters before starting it.
+    #[command(name = "restore-backup")]
+    RestoreFromBackup {
+        #[arg(long = "config-path")]
+        config_path: PathBuf,
+        #[arg(long = "backup-path")]
+        backup_path: PathBuf,
+        /// Only verify the backup, without installing it.
+        #[arg(long = "verify-only")]
+        verify_only: bool,
+        /// Replace the existing databases of the node, including all of its
+        /// consensus stores.
+        #[arg(long = "force")]
+        force: bool,
+    },
+
     #[clap(
         name = "download-db-snapshot",
         about = "Downloads the legacy database snapshot via cloud object store, outputs to local disk"
@@ -662,6 +683,15 @@ impl ToolCommand {
                 let config = iota_config::NodeConfig::load(config_path)?;
                 restore_from_db_checkpoint(&config, &db_checkpoint_path).await?;
             }
+            ToolCommand::RestoreFromBackup {
+                config_path,
+                backup_path,
+                verify_only,
+                force,
+            } => {
+                let config = iota_config::NodeConfig::load(config_path)?;
+                restore_from_backup(&config, &backup_path, verify_only, force)?;
+            }
             ToolCommand::DownloadFormalSnapshot {
                 epoch,
                 genesis,
@@ -40,6 +40,7 @@ use iota_core::{
     authority::{AuthorityStore, authority_store_tables::AuthorityPerpetualTables},
     authority_client::{AuthorityAPI, NetworkAuthorityClient},
     checkpoints::CheckpointStore,
+    db_backup::{restore_backup, verify_backup},
     epoch::committee_store::CommitteeStore,
     execution_cache::build_execution_cache_from_env,
     storage::RocksDbStore,
@@ -576,6 +577,36 @@ pub async fn restore_from_db_checkpoint(
     Ok(())
 }
 
+pub fn restore_from_backup(
+    config: &NodeConfig,
+    backup_path: &Path,
+    verify_only: bool,
+    force: bool,
+) -> Result<(), anyhow::Error> {
+    let manifest = if verify_only {
+        verify_backup(backup_path)?
+    } else {
+        restore_backup(
+            backup_path,
+            &config.db_path(),
+            config.consensus_config().map(|config| config.db_path()),
+            force,
+        )?
+    };
+
+    let total_size: u64 = manifest.files.iter().map(|file| file.size).sum();
+    println!(
+        "Backup of epoch {} ({} files, {total_size} bytes) {}",
+        manifest.epoch,
+        manifest.files.len(),
+        if verify_only { "verified" } else { "restored" }
+    );
+    if let Some(consensus_epoch) = manifest.consensus_epoch {
+        println!("Includes the consensus store of epoch {consensus_epoch}");
+    }
+    Ok(())
+}
+
 fn start_summary_sync(
     perpetual_db: Arc<AuthorityPerpetualTables>,
     committee_store: Arc<CommitteeStore>,
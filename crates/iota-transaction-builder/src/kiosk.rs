@@ -0,0 +1,793 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Builders to trade objects through the `0x2::kiosk` module.
+
+use anyhow::{anyhow, bail, ensure};
+use iota_json_rpc_types::{IotaObjectDataOptions, IotaRawData};
+use iota_types::{
+    IOTA_FRAMEWORK_PACKAGE_ID, TypeTag,
+    base_types::{IotaAddress, ObjectID, ObjectRef, ObjectType},
+    dynamic_field::{Field, derive_dynamic_field_id},
+    id::ID,
+    kiosk::{
+        KIOSK_DELIST_FUNCTION_NAME, KIOSK_LIST_FUNCTION_NAME, KIOSK_MODULE_NAME,
+        KIOSK_NEW_FUNCTION_NAME, KIOSK_PLACE_AND_LIST_FUNCTION_NAME, KIOSK_PLACE_FUNCTION_NAME,
+        KIOSK_PURCHASE_FUNCTION_NAME, KIOSK_WITHDRAW_FUNCTION_NAME, Kiosk, KioskListing,
+        KioskOwnerCap, TRANSFER_POLICY_CONFIRM_REQUEST_FUNCTION_NAME, TRANSFER_POLICY_MODULE_NAME,
+        TransferPolicy,
+    },
+    object::Owner,
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    transaction::{Argument, Command, ObjectArg, TransactionKind},
+};
+use move_core_types::{ident_str, language_storage::StructTag};
+
+use crate::TransactionBuilder;
+
+/// A Move object read to be used as input of a kiosk transaction.
+struct KioskInput {
+    object_ref: ObjectRef,
+    owner: Owner,
+    type_: StructTag,
+    bcs_bytes: Vec<u8>,
+}
+
+impl KioskInput {
+    fn object_arg(&self, mutable: bool) -> ObjectArg {
+        match self.owner {
+            Owner::Shared {
+                initial_shared_version,
+            } => ObjectArg::SharedObject {
+                id: self.object_ref.0,
+                initial_shared_version,
+                mutable,
+            },
+            Owner::AddressOwner(_) | Owner::ObjectOwner(_) | Owner::Immutable => {
+                ObjectArg::ImmOrOwnedObject(self.object_ref)
+            }
+        }
+    }
+}
+
+impl TransactionBuilder {
+    async fn kiosk_input(&self, object_id: ObjectID) -> anyhow::Result<KioskInput> {
+        let object = self
+            .0
+            .get_object_with_options(object_id, IotaObjectDataOptions::bcs_lossless())
+            .await?
+            .into_object()?;
+        let owner = object
+            .owner
+            .ok_or_else(|| anyhow!("Missing owner of object {object_id}"))?;
+        let Some(IotaRawData::MoveObject(raw)) = object.bcs else {
+            bail!("Object {object_id} is not a Move object");
+        };
+        Ok(KioskInput {
+            object_ref: object.object_ref(),
+            owner,
+            type_: raw.type_,
+            bcs_bytes: raw.bcs_bytes,
+        })
+    }
+
+    async fn kiosk(&self, kiosk_id: ObjectID) -> anyhow::Result<KioskInput> {
+        let kiosk = self.kiosk_input(kiosk_id).await?;
+        ensure!(
+            Kiosk::is_kiosk(&kiosk.type_),
+            "Object {kiosk_id} is not a kiosk, but a {}",
+            kiosk.type_
+        );
+        Ok(kiosk)
+    }
+
+    /// Returns the `KioskOwnerCap` of the kiosk `kiosk_id` owned by `owner`.
+    pub async fn kiosk_owner_cap(
+        &self,
+        owner: IotaAddress,
+        kiosk_id: ObjectID,
+    ) -> anyhow::Result<ObjectRef> {
+        for info in self
+            .0
+            .get_owned_objects(owner, KioskOwnerCap::type_())
+            .await?
+        {
+            let cap = self.kiosk_input(info.object_id).await?;
+            let contents: KioskOwnerCap = bcs::from_bytes(&cap.bcs_bytes)?;
+            if contents.kiosk_id() == kiosk_id {
+                return Ok(cap.object_ref);
+            }
+        }
+        bail!("{owner} does not own the KioskOwnerCap of kiosk {kiosk_id}")
+    }
+
+    /// Returns the price of an item listed in a kiosk. Items listed
+    /// exclusively with a `PurchaseCap` are not included.
+    pub async fn kiosk_listing_price(
+        &self,
+        kiosk_id: ObjectID,
+        item_id: ObjectID,
+    ) -> anyhow::Result<u64> {
+        let listing = KioskListing {
+            id: ID::new(item_id),
+            is_exclusive: false,
+        };
+        let field_id = derive_dynamic_field_id(
+            kiosk_id,
+            &TypeTag::Struct(Box::new(KioskListing::type_())),
+            &bcs::to_bytes(&listing)?,
+        )?;
+        let response = self
+            .0
+            .get_object_with_options(field_id, IotaObjectDataOptions::new().with_bcs())
+            .await?;
+        let Some(IotaRawData::MoveObject(raw)) = response.data.and_then(|object| object.bcs) else {
+            bail!("Item {item_id} is not listed in kiosk {kiosk_id}");
+        };
+        let field: Field<KioskListing, u64> = bcs::from_bytes(&raw.bcs_bytes)?;
+        Ok(field.value)
+    }
+
+    /// Adds the kiosk as a mutable input, and the `KioskOwnerCap` of `owner`
+    /// for it.
+    async fn kiosk_owner_args(
+        &self,
+        builder: &mut ProgrammableTransactionBuilder,
+        owner: IotaAddress,
+        kiosk_id: ObjectID,
+    ) -> anyhow::Result<(Argument, Argument)> {
+        let kiosk = self.kiosk(kiosk_id).await?;
+        let cap = self.kiosk_owner_cap(owner, kiosk_id).await?;
+        Ok((
+            builder.obj(kiosk.object_arg(true))?,
+            builder.obj(ObjectArg::ImmOrOwnedObject(cap))?,
+        ))
+    }
+
+    async fn item_ref_and_type(&self, item_id: ObjectID) -> anyhow::Result<(ObjectRef, TypeTag)> {
+        let (item_ref, item_type) = self.get_object_ref_and_type(item_id).await?;
+        let ObjectType::Struct(item_type) = item_type else {
+            bail!("Object {item_id} is not a Move object");
+        };
+        Ok((item_ref, TypeTag::Struct(Box::new(item_type.into()))))
+    }
+
+    /// Build a [`TransactionKind::ProgrammableTransaction`] that creates a
+    /// shared kiosk and sends its `KioskOwnerCap` to `owner`.
+    pub fn new_kiosk_tx_kind(&self, owner: IotaAddress) -> anyhow::Result<TransactionKind> {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let Argument::Result(kiosk) = builder.programmable_move_call(
+            IOTA_FRAMEWORK_PACKAGE_ID,
+            KIOSK_MODULE_NAME.to_owned(),
+            KIOSK_NEW_FUNCTION_NAME.to_owned(),
+            vec![],
+            vec![],
+        ) else {
+            bail!("Move call should return a result");
+        };
+        builder.programmable_move_call(
+            IOTA_FRAMEWORK_PACKAGE_ID,
+            ident_str!("transfer").to_owned(),
+            ident_str!("public_share_object").to_owned(),
+            vec![TypeTag::Struct(Box::new(Kiosk::type_()))],
+            vec![Argument::NestedResult(kiosk, 0)],
+        );
+        builder.transfer_arg(owner, Argument::NestedResult(kiosk, 1));
+        Ok(TransactionKind::programmable(builder.finish()))
+    }
+
+    /// Build a [`TransactionKind::ProgrammableTransaction`] that places an
+    /// item owned by `owner` in their kiosk, and lists it for `price` if
+    /// provided.
+    pub async fn place_in_kiosk_tx_kind(
+        &self,
+        owner: IotaAddress,
+        kiosk_id: ObjectID,
+        item_id: ObjectID,
+        price: Option<u64>,
+    ) -> anyhow::Result<TransactionKind> {
+        let (item_ref, item_type) = self.item_ref_and_type(item_id).await?;
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let (kiosk, cap) = self.kiosk_owner_args(&mut builder, owner, kiosk_id).await?;
+        let mut arguments = vec![
+            kiosk,
+            cap,
+            builder.obj(ObjectArg::ImmOrOwnedObject(item_ref))?,
+        ];
+        let function = match price {
+            Some(price) => {
+                arguments.push(builder.pure(price)?);
+                KIOSK_PLACE_AND_LIST_FUNCTION_NAME
+            }
+            None => KIOSK_PLACE_FUNCTION_NAME,
+        };
+        builder.programmable_move_call(
+            IOTA_FRAMEWORK_PACKAGE_ID,
+            KIOSK_MODULE_NAME.to_owned(),
+            function.to_owned(),
+            vec![item_type],
+            arguments,
+        );
+        Ok(TransactionKind::programmable(builder.finish()))
+    }
+
+    /// Build a [`TransactionKind::ProgrammableTransaction`] that lists an
+    /// item placed in the kiosk of `owner` for `price`.
+    pub async fn list_in_kiosk_tx_kind(
+        &self,
+        owner: IotaAddress,
+        kiosk_id: ObjectID,
+        item_id: ObjectID,
+        price: u64,
+    ) -> anyhow::Result<TransactionKind> {
+        let (_, item_type) = self.item_ref_and_type(item_id).await?;
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let (kiosk, cap) = self.kiosk_owner_args(&mut builder, owner, kiosk_id).await?;
+        let arguments = vec![kiosk, cap, builder.pure(item_id)?, builder.pure(price)?];
+        builder.programmable_move_call(
+            IOTA_FRAMEWORK_PACKAGE_ID,
+            KIOSK_MODULE_NAME.to_owned(),
+            KIOSK_LIST_FUNCTION_NAME.to_owned(),
+            vec![item_type],
+            arguments,
+        );
+        Ok(TransactionKind::programmable(builder.finish()))
+    }
+
+    /// Build a [`TransactionKind::ProgrammableTransaction`] that removes the
+    /// listing of an item in the kiosk of `owner`, keeping the item in the
+    /// kiosk.
+    pub async fn delist_from_kiosk_tx_kind(
+        &self,
+        owner: IotaAddress,
+        kiosk_id: ObjectID,
+        item_id: ObjectID,
+    ) -> anyhow::Result<TransactionKind> {
+        let (_, item_type) = self.item_ref_and_type(item_id).await?;
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let (kiosk, cap) = self.kiosk_owner_args(&mut builder, owner, kiosk_id).await?;
+        let arguments = vec![kiosk, cap, builder.pure(item_id)?];
+        builder.programmable_move_call(
+            IOTA_FRAMEWORK_PACKAGE_ID,
+            KIOSK_MODULE_NAME.to_owned(),
+            KIOSK_DELIST_FUNCTION_NAME.to_owned(),
+            vec![item_type],
+            arguments,
+        );
+        Ok(TransactionKind::programmable(builder.finish()))
+    }
+
+    /// Build a [`TransactionKind::ProgrammableTransaction`] that purchases a
+    /// listed item, paying its price from the gas coin, confirms the transfer
+    /// with the `TransferPolicy` `policy_id` of the item type and sends the
+    /// item to `buyer`.
+    ///
+    /// Only policies without rules are supported, as satisfying a rule needs
+    /// calls to the module defining it.
+    pub async fn purchase_from_kiosk_tx_kind(
+        &self,
+        buyer: IotaAddress,
+        kiosk_id: ObjectID,
+        item_id: ObjectID,
+        policy_id: ObjectID,
+    ) -> anyhow::Result<TransactionKind> {
+        let kiosk = self.kiosk(kiosk_id).await?;
+        let (_, item_type) = self.item_ref_and_type(item_id).await?;
+        let price = self.kiosk_listing_price(kiosk_id, item_id).await?;
+
+        let policy = self.kiosk_input(policy_id).await?;
+        ensure!(
+            TransferPolicy::item_type(&policy.type_) == Some(&item_type),
+            "Object {policy_id} is not a TransferPolicy<{item_type}>"
+        );
+        let rules = bcs::from_bytes::<TransferPolicy>(&policy.bcs_bytes)?
+            .rules
+            .contents;
+        ensure!(
+            rules.is_empty(),
+            "Transfer policy {policy_id} has rules which must be satisfied in the purchase: {}",
+            rules
+                .iter()
+                .map(|rule| rule.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let kiosk = builder.obj(kiosk.object_arg(true))?;
+        let item = builder.pure(item_id)?;
+        let price = builder.pure(price)?;
+        let Argument::Result(payment) =
+            builder.command(Command::SplitCoins(Argument::GasCoin, vec![price]))
+        else {
+            bail!("Split coins should return a result");
+        };
+        let Argument::Result(purchase) = builder.programmable_move_call(
+            IOTA_FRAMEWORK_PACKAGE_ID,
+            KIOSK_MODULE_NAME.to_owned(),
+            KIOSK_PURCHASE_FUNCTION_NAME.to_owned(),
+            vec![item_type.clone()],
+            vec![kiosk, item, Argument::NestedResult(payment, 0)],
+        ) else {
+            bail!("Move call should return a result");
+        };
+        let policy = builder.obj(policy.object_arg(false))?;
+        builder.programmable_move_call(
+            IOTA_FRAMEWORK_PACKAGE_ID,
+            TRANSFER_POLICY_MODULE_NAME.to_owned(),
+            TRANSFER_POLICY_CONFIRM_REQUEST_FUNCTION_NAME.to_owned(),
+            vec![item_type],
+            vec![policy, Argument::NestedResult(purchase, 1)],
+        );
+        builder.transfer_arg(buyer, Argument::NestedResult(purchase, 0));
+        Ok(TransactionKind::programmable(builder.finish()))
+    }
+
+    /// Build a [`TransactionKind::ProgrammableTransaction`] that withdraws
+    /// `amount` of the profits of the kiosk of `owner`, or all profits if not
+    /// provided, and sends them to `owner`.
+    pub async fn withdraw_from_kiosk_tx_kind(
+        &self,
+        owner: IotaAddress,
+        kiosk_id: ObjectID,
+        amount: Option<u64>,
+    ) -> anyhow::Result<TransactionKind> {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let (kiosk, cap) = self.kiosk_owner_args(&mut builder, owner, kiosk_id).await?;
+        let arguments = vec![kiosk, cap, builder.pure(amount)?];
+        let coin = builder.programmable_move_call(
+            IOTA_FRAMEWORK_PACKAGE_ID,
+            KIOSK_MODULE_NAME.to_owned(),
+            KIOSK_WITHDRAW_FUNCTION_NAME.to_owned(),
+            vec![],
+            arguments,
+        );
+        builder.transfer_arg(owner, coin);
+        Ok(TransactionKind::programmable(builder.finish()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, str::FromStr, sync::Arc};
+
+    use async_trait::async_trait;
+    use iota_json_rpc_types::{IotaObjectData, IotaObjectResponse};
+    use iota_types::{
+        balance::Balance,
+        base_types::{ObjectInfo, SequenceNumber},
+        collection_types::VecSet,
+        digests::TransactionDigest,
+        dynamic_field::DynamicFieldInfo,
+        error::IotaObjectResponseError,
+        id::UID,
+        kiosk::TypeName,
+        object::{MoveObject, Object},
+        transaction::{CallArg, ProgrammableTransaction},
+    };
+    use move_core_types::annotated_value::MoveStructLayout;
+    use serde::Serialize;
+
+    use super::*;
+    use crate::DataReader;
+
+    const PRICE: u64 = 1_000;
+
+    /// Serves the objects of a test kiosk from memory.
+    #[derive(Default)]
+    struct MockDataReader {
+        objects: BTreeMap<ObjectID, Object>,
+    }
+
+    #[async_trait]
+    impl DataReader for MockDataReader {
+        async fn get_owned_objects(
+            &self,
+            address: IotaAddress,
+            object_type: StructTag,
+        ) -> anyhow::Result<Vec<ObjectInfo>> {
+            Ok(self
+                .objects
+                .values()
+                .filter(|object| {
+                    object.owner == Owner::AddressOwner(address)
+                        && object.struct_tag().as_ref() == Some(&object_type)
+                })
+                .map(ObjectInfo::from_object)
+                .collect())
+        }
+
+        async fn get_object_with_options(
+            &self,
+            object_id: ObjectID,
+            options: IotaObjectDataOptions,
+        ) -> anyhow::Result<IotaObjectResponse> {
+            let Some(object) = self.objects.get(&object_id) else {
+                return Ok(IotaObjectResponse::new_with_error(
+                    IotaObjectResponseError::NotExists { object_id },
+                ));
+            };
+            // The raw BCS of an object does not depend on its layout.
+            let layout = MoveStructLayout::new(object.struct_tag().unwrap(), vec![]);
+            Ok(IotaObjectResponse::new_with_data(IotaObjectData::new(
+                object.compute_object_reference(),
+                object.clone(),
+                layout,
+                options,
+                None,
+            )?))
+        }
+
+        async fn get_reference_gas_price(&self) -> anyhow::Result<u64> {
+            Ok(1_000)
+        }
+
+        async fn get_dynamic_fields(
+            &self,
+            _object_id: ObjectID,
+        ) -> anyhow::Result<Vec<DynamicFieldInfo>> {
+            Ok(vec![])
+        }
+    }
+
+    impl MockDataReader {
+        fn insert(&mut self, type_: StructTag, owner: Owner, contents: &impl Serialize) {
+            let contents = bcs::to_bytes(contents).unwrap();
+            let object = MoveObject::new_from_execution_with_limit(
+                type_.into(),
+                SequenceNumber::from_u64(1),
+                contents,
+                u64::MAX,
+            )
+            .unwrap();
+            let object = Object::new_move(object, owner, TransactionDigest::genesis_marker());
+            self.objects.insert(object.id(), object);
+        }
+    }
+
+    /// A shared kiosk of `owner` holding an item listed for [`PRICE`].
+    struct TestKiosk {
+        owner: IotaAddress,
+        kiosk_id: ObjectID,
+        cap_ref: ObjectRef,
+        item_id: ObjectID,
+        policy_id: ObjectID,
+        builder: TransactionBuilder,
+    }
+
+    fn item_type() -> StructTag {
+        StructTag::from_str(&format!("0x{}::nft::Nft", "a".repeat(64))).unwrap()
+    }
+
+    fn shared() -> Owner {
+        Owner::Shared {
+            initial_shared_version: SequenceNumber::from_u64(1),
+        }
+    }
+
+    fn transfer_policy(rules: Vec<TypeName>) -> TransferPolicy {
+        TransferPolicy {
+            id: UID::new(ObjectID::random()),
+            balance: Balance::new(0),
+            rules: VecSet { contents: rules },
+        }
+    }
+
+    fn test_kiosk(extra_objects: impl FnOnce(&mut MockDataReader, &TestKiosk)) -> TestKiosk {
+        let owner = IotaAddress::random_for_testing_only();
+        let mut reader = MockDataReader::default();
+
+        let kiosk = Kiosk {
+            id: UID::new(ObjectID::random()),
+            profits: Balance::new(PRICE),
+            owner,
+            item_count: 1,
+        };
+        let kiosk_id = kiosk.id.object_id().to_owned();
+        reader.insert(Kiosk::type_(), shared(), &kiosk);
+
+        let cap = KioskOwnerCap {
+            id: UID::new(ObjectID::random()),
+            for_: ID::new(kiosk_id),
+        };
+        let cap_id = cap.id.object_id().to_owned();
+        reader.insert(KioskOwnerCap::type_(), Owner::AddressOwner(owner), &cap);
+
+        let item = UID::new(ObjectID::random());
+        let item_id = item.object_id().to_owned();
+        reader.insert(item_type(), Owner::ObjectOwner(kiosk_id.into()), &item);
+
+        let listing = KioskListing {
+            id: ID::new(item_id),
+            is_exclusive: false,
+        };
+        let listing_type = TypeTag::Struct(Box::new(KioskListing::type_()));
+        let field = Field {
+            id: UID::new(
+                derive_dynamic_field_id(kiosk_id, &listing_type, &bcs::to_bytes(&listing).unwrap())
+                    .unwrap(),
+            ),
+            name: listing,
+            value: PRICE,
+        };
+        reader.insert(
+            DynamicFieldInfo::dynamic_field_type(listing_type, TypeTag::U64),
+            Owner::ObjectOwner(kiosk_id.into()),
+            &field,
+        );
+
+        let policy = transfer_policy(vec![]);
+        let policy_id = policy.id.object_id().to_owned();
+        reader.insert(
+            TransferPolicy::type_(TypeTag::Struct(Box::new(item_type()))),
+            shared(),
+            &policy,
+        );
+
+        let mut kiosk = TestKiosk {
+            owner,
+            kiosk_id,
+            cap_ref: reader.objects[&cap_id].compute_object_reference(),
+            item_id,
+            policy_id,
+            builder: TransactionBuilder::new(Arc::new(MockDataReader::default())),
+        };
+        extra_objects(&mut reader, &kiosk);
+        kiosk.builder = TransactionBuilder::new(Arc::new(reader));
+        kiosk
+    }
+
+    fn programmable(kind: TransactionKind) -> ProgrammableTransaction {
+        let TransactionKind::ProgrammableTransaction(pt) = kind else {
+            panic!("Expected a programmable transaction");
+        };
+        pt
+    }
+
+    /// Summarizes the commands as `module::function<type args>` for Move
+    /// calls, `split(coin, amounts)` for coin splits and `transfer(objects)`
+    /// for transfers.
+    fn commands(pt: &ProgrammableTransaction) -> Vec<String> {
+        pt.commands
+            .iter()
+            .map(|command| match command {
+                Command::MoveCall(call) => format!(
+                    "{}::{}<{}>",
+                    call.module,
+                    call.function,
+                    call.type_arguments
+                        .iter()
+                        .map(|tag| tag.to_canonical_string(true))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                Command::SplitCoins(coin, amounts) => format!("split({coin:?}, {amounts:?})"),
+                Command::TransferObjects(objects, _) => format!("transfer({objects:?})"),
+                command => format!("{command}"),
+            })
+            .collect()
+    }
+
+    fn item() -> String {
+        item_type().to_canonical_string(true)
+    }
+
+    fn kiosk_arg(kiosk: &TestKiosk) -> CallArg {
+        CallArg::Object(ObjectArg::SharedObject {
+            id: kiosk.kiosk_id,
+            initial_shared_version: SequenceNumber::from_u64(1),
+            mutable: true,
+        })
+    }
+
+    fn pure(value: &impl Serialize) -> CallArg {
+        CallArg::Pure(bcs::to_bytes(value).unwrap())
+    }
+
+    #[test]
+    fn test_new_kiosk() {
+        let kiosk = test_kiosk(|_, _| {});
+        let pt = programmable(kiosk.builder.new_kiosk_tx_kind(kiosk.owner).unwrap());
+        assert_eq!(commands(&pt), vec![
+            "kiosk::new<>".to_owned(),
+            format!(
+                "transfer::public_share_object<{}>",
+                Kiosk::type_().to_canonical_string(true)
+            ),
+            "transfer([NestedResult(0, 1)])".to_owned(),
+        ]);
+        assert_eq!(pt.inputs, vec![pure(&kiosk.owner)]);
+    }
+
+    #[tokio::test]
+    async fn test_place_in_kiosk() {
+        let kiosk = test_kiosk(|_, _| {});
+        let item_ref = kiosk
+            .builder
+            .get_object_ref_and_type(kiosk.item_id)
+            .await
+            .unwrap()
+            .0;
+
+        let pt = programmable(
+            kiosk
+                .builder
+                .place_in_kiosk_tx_kind(kiosk.owner, kiosk.kiosk_id, kiosk.item_id, None)
+                .await
+                .unwrap(),
+        );
+        assert_eq!(commands(&pt), vec![format!("kiosk::place<{}>", item())]);
+        assert_eq!(pt.inputs, vec![
+            kiosk_arg(&kiosk),
+            CallArg::Object(ObjectArg::ImmOrOwnedObject(kiosk.cap_ref)),
+            CallArg::Object(ObjectArg::ImmOrOwnedObject(item_ref)),
+        ]);
+
+        let pt = programmable(
+            kiosk
+                .builder
+                .place_in_kiosk_tx_kind(kiosk.owner, kiosk.kiosk_id, kiosk.item_id, Some(PRICE))
+                .await
+                .unwrap(),
+        );
+        assert_eq!(commands(&pt), vec![format!(
+            "kiosk::place_and_list<{}>",
+            item()
+        )]);
+        assert_eq!(pt.inputs.last(), Some(&pure(&PRICE)));
+    }
+
+    #[tokio::test]
+    async fn test_list_and_delist_in_kiosk() {
+        let kiosk = test_kiosk(|_, _| {});
+        let owner_inputs = vec![
+            kiosk_arg(&kiosk),
+            CallArg::Object(ObjectArg::ImmOrOwnedObject(kiosk.cap_ref)),
+            pure(&kiosk.item_id),
+        ];
+
+        let pt = programmable(
+            kiosk
+                .builder
+                .list_in_kiosk_tx_kind(kiosk.owner, kiosk.kiosk_id, kiosk.item_id, PRICE)
+                .await
+                .unwrap(),
+        );
+        assert_eq!(commands(&pt), vec![format!("kiosk::list<{}>", item())]);
+        assert_eq!(pt.inputs[..3], owner_inputs[..]);
+        assert_eq!(pt.inputs[3], pure(&PRICE));
+
+        let pt = programmable(
+            kiosk
+                .builder
+                .delist_from_kiosk_tx_kind(kiosk.owner, kiosk.kiosk_id, kiosk.item_id)
+                .await
+                .unwrap(),
+        );
+        assert_eq!(commands(&pt), vec![format!("kiosk::delist<{}>", item())]);
+        assert_eq!(pt.inputs, owner_inputs);
+    }
+
+    #[tokio::test]
+    async fn test_kiosk_owner_cap_is_required() {
+        let kiosk = test_kiosk(|_, _| {});
+        let other = IotaAddress::random_for_testing_only();
+        let err = kiosk
+            .builder
+            .list_in_kiosk_tx_kind(other, kiosk.kiosk_id, kiosk.item_id, PRICE)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("does not own the KioskOwnerCap"),
+            "{err}"
+        );
+
+        let err = kiosk
+            .builder
+            .withdraw_from_kiosk_tx_kind(kiosk.owner, kiosk.item_id, None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("is not a kiosk"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_purchase_from_kiosk() {
+        let kiosk = test_kiosk(|_, _| {});
+        let buyer = IotaAddress::random_for_testing_only();
+        let pt = programmable(
+            kiosk
+                .builder
+                .purchase_from_kiosk_tx_kind(buyer, kiosk.kiosk_id, kiosk.item_id, kiosk.policy_id)
+                .await
+                .unwrap(),
+        );
+        assert_eq!(commands(&pt), vec![
+            "split(GasCoin, [Input(2)])".to_owned(),
+            format!("kiosk::purchase<{}>", item()),
+            format!("transfer_policy::confirm_request<{}>", item()),
+            "transfer([NestedResult(1, 0)])".to_owned(),
+        ]);
+        assert_eq!(pt.inputs, vec![
+            kiosk_arg(&kiosk),
+            pure(&kiosk.item_id),
+            pure(&PRICE),
+            CallArg::Object(ObjectArg::SharedObject {
+                id: kiosk.policy_id,
+                initial_shared_version: SequenceNumber::from_u64(1),
+                mutable: false,
+            }),
+            pure(&buyer),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_purchase_from_kiosk_checks_listing_and_policy() {
+        let mut policy_with_rules = ObjectID::ZERO;
+        let mut other_policy = ObjectID::ZERO;
+        let mut unlisted_item = ObjectID::ZERO;
+        let kiosk = test_kiosk(|reader, kiosk| {
+            let policy = transfer_policy(vec![TypeName {
+                name: format!("{}::royalty_rule::Rule", "b".repeat(64)),
+            }]);
+            policy_with_rules = policy.id.object_id().to_owned();
+            reader.insert(
+                TransferPolicy::type_(TypeTag::Struct(Box::new(item_type()))),
+                shared(),
+                &policy,
+            );
+
+            let policy = transfer_policy(vec![]);
+            other_policy = policy.id.object_id().to_owned();
+            reader.insert(TransferPolicy::type_(TypeTag::U64), shared(), &policy);
+
+            let item = UID::new(ObjectID::random());
+            unlisted_item = item.object_id().to_owned();
+            reader.insert(
+                item_type(),
+                Owner::ObjectOwner(kiosk.kiosk_id.into()),
+                &item,
+            );
+        });
+        let buyer = IotaAddress::random_for_testing_only();
+        let purchase = |item_id, policy_id| {
+            kiosk
+                .builder
+                .purchase_from_kiosk_tx_kind(buyer, kiosk.kiosk_id, item_id, policy_id)
+        };
+
+        let err = purchase(kiosk.item_id, policy_with_rules)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("royalty_rule::Rule"), "{err}");
+
+        let err = purchase(kiosk.item_id, other_policy).await.unwrap_err();
+        assert!(err.to_string().contains("is not a TransferPolicy"), "{err}");
+
+        let err = purchase(unlisted_item, kiosk.policy_id).await.unwrap_err();
+        assert!(err.to_string().contains("is not listed"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_from_kiosk() {
+        let kiosk = test_kiosk(|_, _| {});
+        for amount in [None, Some(PRICE / 2)] {
+            let pt = programmable(
+                kiosk
+                    .builder
+                    .withdraw_from_kiosk_tx_kind(kiosk.owner, kiosk.kiosk_id, amount)
+                    .await
+                    .unwrap(),
+            );
+            assert_eq!(commands(&pt), vec![
+                "kiosk::withdraw<>".to_owned(),
+                "transfer([Result(0)])".to_owned(),
+            ]);
+            assert_eq!(pt.inputs, vec![
+                kiosk_arg(&kiosk),
+                CallArg::Object(ObjectArg::ImmOrOwnedObject(kiosk.cap_ref)),
+                pure(&amount),
+                pure(&kiosk.owner),
+            ]);
+        }
+    }
+}
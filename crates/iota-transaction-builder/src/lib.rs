@@ -2,8 +2,10 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod kiosk;
 pub mod package;
 pub mod stake;
+pub mod stardust;
 pub mod utils;
 
 use std::{result::Result, str::FromStr, sync::Arc};
@@ -18,6 +20,7 @@ use iota_types::{
     IOTA_FRAMEWORK_PACKAGE_ID,
     base_types::{IotaAddress, ObjectID, ObjectInfo},
     coin,
+    dynamic_field::DynamicFieldInfo,
     error::UserInputError,
     fp_ensure,
     object::Object,
@@ -41,6 +44,11 @@ pub trait DataReader {
     ) -> Result<IotaObjectResponse, anyhow::Error>;
 
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error>;
+
+    async fn get_dynamic_fields(
+        &self,
+        object_id: ObjectID,
+    ) -> Result<Vec<DynamicFieldInfo>, anyhow::Error>;
 }
 
 #[derive(Clone)]
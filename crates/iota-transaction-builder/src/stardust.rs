@@ -0,0 +1,723 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Builders to claim the assets held by the Stardust outputs migrated at
+//! genesis.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail};
+use iota_json_rpc_types::{IotaObjectDataOptions, IotaRawData};
+use iota_types::{
+    IOTA_FRAMEWORK_PACKAGE_ID, STARDUST_PACKAGE_ID, TypeTag,
+    balance::BALANCE_MODULE_NAME,
+    base_types::{IotaAddress, ObjectID, ObjectRef},
+    coin::COIN_MODULE_NAME,
+    collection_types::Bag,
+    object::Owner,
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    stardust::output::{
+        ALIAS_OUTPUT_MODULE_NAME, ALIAS_OUTPUT_STRUCT_NAME, AliasOutput, BASIC_OUTPUT_MODULE_NAME,
+        BASIC_OUTPUT_STRUCT_NAME, BasicOutput, NFT_OUTPUT_MODULE_NAME, NFT_OUTPUT_STRUCT_NAME,
+        NftOutput,
+        unlock_conditions::{
+            ExpirationUnlockCondition, StorageDepositReturnUnlockCondition, TimelockUnlockCondition,
+        },
+    },
+    transaction::{Argument, ObjectArg, TransactionData, TransactionKind},
+};
+use move_core_types::{ident_str, identifier::IdentStr, language_storage::StructTag};
+
+use crate::TransactionBuilder;
+
+pub const EXTRACT_ASSETS_FUNCTION_NAME: &IdentStr = ident_str!("extract_assets");
+pub const UTILITIES_MODULE_NAME: &IdentStr = ident_str!("utilities");
+pub const EXTRACT_AND_SEND_TO_FUNCTION_NAME: &IdentStr = ident_str!("extract_and_send_to");
+
+/// The kinds of Stardust outputs which hold assets after the migration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StardustOutputKind {
+    Basic,
+    Nft,
+    Alias,
+}
+
+impl StardustOutputKind {
+    pub const ALL: [StardustOutputKind; 3] = [Self::Basic, Self::Nft, Self::Alias];
+
+    fn module_name(&self) -> &'static IdentStr {
+        match self {
+            Self::Basic => BASIC_OUTPUT_MODULE_NAME,
+            Self::Nft => NFT_OUTPUT_MODULE_NAME,
+            Self::Alias => ALIAS_OUTPUT_MODULE_NAME,
+        }
+    }
+
+    fn struct_name(&self) -> &'static IdentStr {
+        match self {
+            Self::Basic => BASIC_OUTPUT_STRUCT_NAME,
+            Self::Nft => NFT_OUTPUT_STRUCT_NAME,
+            Self::Alias => ALIAS_OUTPUT_STRUCT_NAME,
+        }
+    }
+
+    /// The struct tag of the output type, matching any coin type.
+    pub fn struct_tag(&self) -> StructTag {
+        StructTag {
+            address: STARDUST_PACKAGE_ID.into(),
+            module: self.module_name().to_owned(),
+            name: self.struct_name().to_owned(),
+            type_params: vec![],
+        }
+    }
+
+    /// Returns the kind of output and its coin type if `tag` is the type of a
+    /// Stardust output.
+    pub fn from_struct_tag(tag: &StructTag) -> Option<(Self, TypeTag)> {
+        let kind = Self::ALL.into_iter().find(|kind| {
+            tag.address == STARDUST_PACKAGE_ID.into()
+                && tag.module.as_ident_str() == kind.module_name()
+                && tag.name.as_ident_str() == kind.struct_name()
+        })?;
+        match tag.type_params.as_slice() {
+            [coin_type] => Some((kind, coin_type.clone())),
+            _ => None,
+        }
+    }
+}
+
+/// Whether, and how, an address can claim the assets of a Stardust output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StardustClaimStatus {
+    /// All assets of the output can be claimed now.
+    ClaimableNow,
+    /// The output can be claimed from this unix time (in seconds) on.
+    ClaimableLater { unix_time: u32 },
+    /// The output can be claimed now, but claiming it sends `return_amount`
+    /// of its coins back to `return_address`.
+    NeedsReturn {
+        return_address: IotaAddress,
+        return_amount: u64,
+    },
+    /// The address can not claim the output, e.g. because the output expired
+    /// and now belongs to its return address.
+    Unclaimable,
+}
+
+/// A migrated Stardust output and the assets it holds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StardustOutput {
+    pub object_ref: ObjectRef,
+    pub owner: Owner,
+    pub kind: StardustOutputKind,
+    /// The type of the coins held by the output.
+    pub coin_type: TypeTag,
+    /// The amount of coins held by the output.
+    pub balance: u64,
+    /// The types of the native tokens held by the output.
+    pub native_tokens: Vec<TypeTag>,
+    pub storage_deposit_return: Option<StorageDepositReturnUnlockCondition>,
+    pub timelock: Option<TimelockUnlockCondition>,
+    pub expiration: Option<ExpirationUnlockCondition>,
+}
+
+impl StardustOutput {
+    /// Whether `claimer` can claim the output in the epoch which started at
+    /// `epoch_start_timestamp_ms`. Unlock conditions are checked against the
+    /// epoch start timestamp on chain, not against the current time.
+    pub fn claim_status(
+        &self,
+        claimer: IotaAddress,
+        epoch_start_timestamp_ms: u64,
+    ) -> StardustClaimStatus {
+        let now = (epoch_start_timestamp_ms / 1000) as u32;
+        let mut unlock_time = self
+            .timelock
+            .as_ref()
+            .map(|timelock| timelock.unix_time)
+            .filter(|unix_time| *unix_time > now);
+        // Before the expiration only the owner can unlock the output, and only
+        // the return address afterwards.
+        if let Some(expiration) = &self.expiration {
+            if expiration.unix_time <= now {
+                if claimer != expiration.return_address {
+                    return StardustClaimStatus::Unclaimable;
+                }
+            } else if claimer == expiration.return_address && claimer != expiration.owner {
+                unlock_time = unlock_time.max(Some(expiration.unix_time));
+            } else if claimer != expiration.owner {
+                return StardustClaimStatus::Unclaimable;
+            }
+        } else if self.owner != Owner::AddressOwner(claimer) {
+            return StardustClaimStatus::Unclaimable;
+        }
+        if let Some(unix_time) = unlock_time {
+            return StardustClaimStatus::ClaimableLater { unix_time };
+        }
+        match &self.storage_deposit_return {
+            Some(storage_deposit_return) => StardustClaimStatus::NeedsReturn {
+                return_address: storage_deposit_return.return_address,
+                return_amount: storage_deposit_return.return_amount,
+            },
+            None => StardustClaimStatus::ClaimableNow,
+        }
+    }
+
+    /// The amount of coins the claimer receives, after returning the storage
+    /// deposit.
+    pub fn claimable_amount(&self) -> u64 {
+        self.balance.saturating_sub(
+            self.storage_deposit_return
+                .as_ref()
+                .map_or(0, |storage_deposit_return| {
+                    storage_deposit_return.return_amount
+                }),
+        )
+    }
+
+    fn object_arg(&self) -> anyhow::Result<ObjectArg> {
+        match self.owner {
+            Owner::AddressOwner(_) => Ok(ObjectArg::ImmOrOwnedObject(self.object_ref)),
+            Owner::Shared {
+                initial_shared_version,
+            } => Ok(ObjectArg::SharedObject {
+                id: self.object_ref.0,
+                initial_shared_version,
+                mutable: true,
+            }),
+            Owner::ObjectOwner(owner) => bail!(
+                "Output {} is owned by the alias or NFT {owner}, which must unlock it first",
+                self.object_ref.0
+            ),
+            Owner::Immutable => bail!("Output {} is immutable", self.object_ref.0),
+        }
+    }
+
+    /// Adds the commands claiming the output to `builder`: all assets are
+    /// extracted, the storage deposit is returned, native tokens are sent to
+    /// `claimer` as `Coin<T>` objects, and the remaining coins and the `Nft`
+    /// or `Alias` object are transferred to `claimer`.
+    pub fn claim(
+        &self,
+        builder: &mut ProgrammableTransactionBuilder,
+        claimer: IotaAddress,
+    ) -> anyhow::Result<()> {
+        let output = builder.obj(self.object_arg()?)?;
+        let Argument::Result(assets) = builder.programmable_move_call(
+            STARDUST_PACKAGE_ID,
+            self.kind.module_name().to_owned(),
+            EXTRACT_ASSETS_FUNCTION_NAME.to_owned(),
+            vec![self.coin_type.clone()],
+            vec![output],
+        ) else {
+            bail!("Move call should return a result");
+        };
+        let balance = Argument::NestedResult(assets, 0);
+        let mut native_tokens = Argument::NestedResult(assets, 1);
+
+        for token_type in &self.native_tokens {
+            let recipient = builder.pure(claimer)?;
+            native_tokens = builder.programmable_move_call(
+                STARDUST_PACKAGE_ID,
+                UTILITIES_MODULE_NAME.to_owned(),
+                EXTRACT_AND_SEND_TO_FUNCTION_NAME.to_owned(),
+                vec![token_type.clone()],
+                vec![native_tokens, recipient],
+            );
+        }
+        builder.programmable_move_call(
+            IOTA_FRAMEWORK_PACKAGE_ID,
+            ident_str!("bag").to_owned(),
+            ident_str!("destroy_empty").to_owned(),
+            vec![],
+            vec![native_tokens],
+        );
+
+        if self.claimable_amount() == 0 {
+            builder.programmable_move_call(
+                IOTA_FRAMEWORK_PACKAGE_ID,
+                BALANCE_MODULE_NAME.to_owned(),
+                ident_str!("destroy_zero").to_owned(),
+                vec![self.coin_type.clone()],
+                vec![balance],
+            );
+        } else {
+            let coin = builder.programmable_move_call(
+                IOTA_FRAMEWORK_PACKAGE_ID,
+                COIN_MODULE_NAME.to_owned(),
+                ident_str!("from_balance").to_owned(),
+                vec![self.coin_type.clone()],
+                vec![balance],
+            );
+            builder.transfer_arg(claimer, coin);
+        }
+
+        if self.kind != StardustOutputKind::Basic {
+            builder.transfer_arg(claimer, Argument::NestedResult(assets, 2));
+        }
+        Ok(())
+    }
+}
+
+impl TransactionBuilder {
+    /// Returns all Stardust outputs owned by `address`. Outputs with an
+    /// expiration unlock condition are shared objects, which cannot be
+    /// queried by owner, so they are not included even if they name
+    /// `address`. They can be read with [`Self::stardust_output`].
+    pub async fn stardust_outputs(
+        &self,
+        address: IotaAddress,
+    ) -> anyhow::Result<Vec<StardustOutput>> {
+        let mut outputs = vec![];
+        for kind in StardustOutputKind::ALL {
+            for info in self.0.get_owned_objects(address, kind.struct_tag()).await? {
+                outputs.push(self.stardust_output(info.object_id).await?);
+            }
+        }
+        Ok(outputs)
+    }
+
+    /// Reads the Stardust output with the given ID and the types of the
+    /// native tokens it holds.
+    pub async fn stardust_output(&self, object_id: ObjectID) -> anyhow::Result<StardustOutput> {
+        let object = self
+            .0
+            .get_object_with_options(object_id, IotaObjectDataOptions::bcs_lossless())
+            .await?
+            .into_object()?;
+        let owner = object
+            .owner
+            .ok_or_else(|| anyhow!("Missing owner of object {object_id}"))?;
+        let Some(IotaRawData::MoveObject(raw)) = &object.bcs else {
+            bail!("Object {object_id} is not a Move object");
+        };
+        let (kind, coin_type) = StardustOutputKind::from_struct_tag(&raw.type_)
+            .ok_or_else(|| anyhow!("Object {object_id} is not a Stardust output"))?;
+
+        let (balance, bag, storage_deposit_return, timelock, expiration) = match kind {
+            StardustOutputKind::Basic => {
+                let output: BasicOutput = bcs::from_bytes(&raw.bcs_bytes)?;
+                (
+                    output.balance.value(),
+                    output.native_tokens,
+                    output.storage_deposit_return,
+                    output.timelock,
+                    output.expiration,
+                )
+            }
+            StardustOutputKind::Nft => {
+                let output: NftOutput = bcs::from_bytes(&raw.bcs_bytes)?;
+                (
+                    output.balance.value(),
+                    output.native_tokens,
+                    output.storage_deposit_return,
+                    output.timelock,
+                    output.expiration,
+                )
+            }
+            StardustOutputKind::Alias => {
+                let output: AliasOutput = bcs::from_bytes(&raw.bcs_bytes)?;
+                (
+                    output.balance.value(),
+                    output.native_tokens,
+                    None,
+                    None,
+                    None,
+                )
+            }
+        };
+
+        Ok(StardustOutput {
+            object_ref: object.object_ref(),
+            owner,
+            kind,
+            coin_type,
+            balance,
+            native_tokens: self.native_token_types(&bag).await?,
+            storage_deposit_return,
+            timelock,
+            expiration,
+        })
+    }
+
+    /// Returns the types of the native token balances in the `Bag` of an
+    /// output, which are keyed by the type name without `0x` prefix.
+    async fn native_token_types(&self, bag: &Bag) -> anyhow::Result<Vec<TypeTag>> {
+        if bag.size == 0 {
+            return Ok(vec![]);
+        }
+        self.0
+            .get_dynamic_fields(*bag.id.object_id())
+            .await?
+            .into_iter()
+            .map(|field| -> anyhow::Result<TypeTag> {
+                let key =
+                    field.name.value.as_str().ok_or_else(|| {
+                        anyhow!("Unexpected native token key {}", field.name.value)
+                    })?;
+                Ok(TypeTag::from_str(&format!("0x{key}"))?)
+            })
+            .collect()
+    }
+
+    /// Build a [`TransactionKind::ProgrammableTransaction`] that claims the
+    /// assets of all `outputs` for `claimer`.
+    pub fn claim_stardust_outputs_tx_kind(
+        &self,
+        claimer: IotaAddress,
+        outputs: &[StardustOutput],
+    ) -> anyhow::Result<TransactionKind> {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        for output in outputs {
+            output.claim(&mut builder, claimer)?;
+        }
+        Ok(TransactionKind::programmable(builder.finish()))
+    }
+
+    /// Claim the assets of the Stardust outputs with the given IDs.
+    pub async fn claim_stardust_outputs(
+        &self,
+        signer: IotaAddress,
+        output_ids: Vec<ObjectID>,
+        gas: impl Into<Option<ObjectID>>,
+        gas_budget: u64,
+    ) -> anyhow::Result<TransactionData> {
+        let mut outputs = Vec::with_capacity(output_ids.len());
+        for output_id in output_ids {
+            outputs.push(self.stardust_output(output_id).await?);
+        }
+        let tx_kind = self.claim_stardust_outputs_tx_kind(signer, &outputs)?;
+        let gas_price = self.0.get_reference_gas_price().await?;
+        let input_objects = outputs.iter().map(|output| output.object_ref.0).collect();
+        let gas = self
+            .select_gas(signer, gas, gas_budget, input_objects, gas_price)
+            .await?;
+        Ok(TransactionData::new(
+            tx_kind, signer, gas, gas_budget, gas_price,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iota_types::{
+        base_types::{SequenceNumber, random_object_ref},
+        gas_coin::GAS,
+        transaction::{CallArg, Command, ProgrammableTransaction},
+    };
+
+    use super::*;
+
+    const NOW_MS: u64 = 1_700_000_000_000;
+    const NOW: u32 = (NOW_MS / 1000) as u32;
+
+    fn output(kind: StardustOutputKind, owner: IotaAddress) -> StardustOutput {
+        StardustOutput {
+            object_ref: random_object_ref(),
+            owner: Owner::AddressOwner(owner),
+            kind,
+            coin_type: GAS::type_tag(),
+            balance: 1_000,
+            native_tokens: vec![],
+            storage_deposit_return: None,
+            timelock: None,
+            expiration: None,
+        }
+    }
+
+    fn expiring_output(
+        kind: StardustOutputKind,
+        owner: IotaAddress,
+        return_address: IotaAddress,
+        unix_time: u32,
+    ) -> StardustOutput {
+        StardustOutput {
+            owner: Owner::Shared {
+                initial_shared_version: SequenceNumber::from_u64(1),
+            },
+            expiration: Some(ExpirationUnlockCondition {
+                owner,
+                return_address,
+                unix_time,
+            }),
+            ..output(kind, owner)
+        }
+    }
+
+    fn native_token(name: &str) -> TypeTag {
+        TypeTag::from_str(&format!(
+            "0x{}::{name}::{}",
+            "a".repeat(64),
+            name.to_uppercase()
+        ))
+        .unwrap()
+    }
+
+    fn build_claim(output: &StardustOutput, claimer: IotaAddress) -> ProgrammableTransaction {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        output.claim(&mut builder, claimer).unwrap();
+        builder.finish()
+    }
+
+    /// Summarizes the commands as `module::function<type args>` for Move
+    /// calls and `transfer(objects)` for transfers.
+    fn commands(pt: &ProgrammableTransaction) -> Vec<String> {
+        pt.commands
+            .iter()
+            .map(|command| match command {
+                Command::MoveCall(call) => format!(
+                    "{}::{}<{}>",
+                    call.module,
+                    call.function,
+                    call.type_arguments
+                        .iter()
+                        .map(|tag| tag.to_canonical_string(true))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                Command::TransferObjects(objects, _) => format!("transfer({objects:?})"),
+                command => format!("{command}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_from_struct_tag() {
+        for kind in StardustOutputKind::ALL {
+            let mut tag = kind.struct_tag();
+            assert_eq!(StardustOutputKind::from_struct_tag(&tag), None);
+            tag.type_params = vec![GAS::type_tag()];
+            assert_eq!(
+                StardustOutputKind::from_struct_tag(&tag),
+                Some((kind, GAS::type_tag()))
+            );
+        }
+        assert_eq!(StardustOutputKind::from_struct_tag(&GAS::type_()), None);
+    }
+
+    #[test]
+    fn test_claim_status_timelock() {
+        let owner = IotaAddress::random_for_testing_only();
+        for kind in [StardustOutputKind::Basic, StardustOutputKind::Nft] {
+            let mut output = output(kind, owner);
+            assert_eq!(
+                output.claim_status(owner, NOW_MS),
+                StardustClaimStatus::ClaimableNow
+            );
+            assert_eq!(
+                output.claim_status(IotaAddress::random_for_testing_only(), NOW_MS),
+                StardustClaimStatus::Unclaimable
+            );
+
+            output.timelock = Some(TimelockUnlockCondition {
+                unix_time: NOW + 60,
+            });
+            assert_eq!(
+                output.claim_status(owner, NOW_MS),
+                StardustClaimStatus::ClaimableLater {
+                    unix_time: NOW + 60
+                }
+            );
+            // The timelock is checked against the epoch start timestamp.
+            assert_eq!(
+                output.claim_status(owner, NOW_MS + 60_000),
+                StardustClaimStatus::ClaimableNow
+            );
+        }
+    }
+
+    #[test]
+    fn test_claim_status_expiration() {
+        let owner = IotaAddress::random_for_testing_only();
+        let return_address = IotaAddress::random_for_testing_only();
+        let stranger = IotaAddress::random_for_testing_only();
+        for kind in [StardustOutputKind::Basic, StardustOutputKind::Nft] {
+            let mut output = expiring_output(kind, owner, return_address, NOW + 60);
+
+            // Before the expiration the owner can claim the output, and the
+            // return address has to wait for the expiration.
+            assert_eq!(
+                output.claim_status(owner, NOW_MS),
+                StardustClaimStatus::ClaimableNow
+            );
+            assert_eq!(
+                output.claim_status(return_address, NOW_MS),
+                StardustClaimStatus::ClaimableLater {
+                    unix_time: NOW + 60
+                }
+            );
+            assert_eq!(
+                output.claim_status(stranger, NOW_MS),
+                StardustClaimStatus::Unclaimable
+            );
+
+            // The return address waits for the later of timelock and
+            // expiration.
+            output.timelock = Some(TimelockUnlockCondition {
+                unix_time: NOW + 120,
+            });
+            assert_eq!(
+                output.claim_status(return_address, NOW_MS),
+                StardustClaimStatus::ClaimableLater {
+                    unix_time: NOW + 120
+                }
+            );
+            output.timelock = None;
+
+            // After the expiration only the return address can claim it.
+            assert_eq!(
+                output.claim_status(owner, NOW_MS + 60_000),
+                StardustClaimStatus::Unclaimable
+            );
+            assert_eq!(
+                output.claim_status(return_address, NOW_MS + 60_000),
+                StardustClaimStatus::ClaimableNow
+            );
+            assert_eq!(
+                output.claim_status(stranger, NOW_MS + 60_000),
+                StardustClaimStatus::Unclaimable
+            );
+        }
+    }
+
+    #[test]
+    fn test_claim_status_storage_deposit_return() {
+        let owner = IotaAddress::random_for_testing_only();
+        let return_address = IotaAddress::random_for_testing_only();
+        for kind in [StardustOutputKind::Basic, StardustOutputKind::Nft] {
+            let mut output = output(kind, owner);
+            output.storage_deposit_return = Some(StorageDepositReturnUnlockCondition {
+                return_address,
+                return_amount: 400,
+            });
+            assert_eq!(
+                output.claim_status(owner, NOW_MS),
+                StardustClaimStatus::NeedsReturn {
+                    return_address,
+                    return_amount: 400
+                }
+            );
+            assert_eq!(output.claimable_amount(), 600);
+
+            // A pending timelock takes precedence over the return.
+            output.timelock = Some(TimelockUnlockCondition {
+                unix_time: NOW + 60,
+            });
+            assert_eq!(
+                output.claim_status(owner, NOW_MS),
+                StardustClaimStatus::ClaimableLater {
+                    unix_time: NOW + 60
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_claim_basic_output_with_native_tokens() {
+        let owner = IotaAddress::random_for_testing_only();
+        let mut output = output(StardustOutputKind::Basic, owner);
+        output.native_tokens = vec![native_token("foo"), native_token("bar")];
+
+        let pt = build_claim(&output, owner);
+        assert_eq!(
+            pt.inputs[0],
+            CallArg::Object(ObjectArg::ImmOrOwnedObject(output.object_ref))
+        );
+        let gas = GAS::type_tag().to_canonical_string(true);
+        let foo = native_token("foo").to_canonical_string(true);
+        let bar = native_token("bar").to_canonical_string(true);
+        assert_eq!(commands(&pt), vec![
+            format!("basic_output::extract_assets<{gas}>"),
+            format!("utilities::extract_and_send_to<{foo}>"),
+            format!("utilities::extract_and_send_to<{bar}>"),
+            "bag::destroy_empty<>".to_owned(),
+            format!("coin::from_balance<{gas}>"),
+            "transfer([Result(4)])".to_owned(),
+        ]);
+
+        // Each native token is taken out of the bag returned by the previous
+        // call, starting with the bag of the extracted assets.
+        let Command::MoveCall(first) = &pt.commands[1] else {
+            panic!("Expected a Move call");
+        };
+        assert_eq!(first.arguments[0], Argument::NestedResult(0, 1));
+        let Command::MoveCall(second) = &pt.commands[2] else {
+            panic!("Expected a Move call");
+        };
+        assert_eq!(second.arguments[0], Argument::Result(1));
+        let Command::MoveCall(destroy) = &pt.commands[3] else {
+            panic!("Expected a Move call");
+        };
+        assert_eq!(destroy.arguments, vec![Argument::Result(2)]);
+    }
+
+    #[test]
+    fn test_claim_nft_output() {
+        let owner = IotaAddress::random_for_testing_only();
+        let return_address = IotaAddress::random_for_testing_only();
+        let output = expiring_output(StardustOutputKind::Nft, owner, return_address, NOW);
+
+        let pt = build_claim(&output, return_address);
+        assert_eq!(
+            pt.inputs[0],
+            CallArg::Object(ObjectArg::SharedObject {
+                id: output.object_ref.0,
+                initial_shared_version: SequenceNumber::from_u64(1),
+                mutable: true,
+            })
+        );
+        let gas = GAS::type_tag().to_canonical_string(true);
+        assert_eq!(commands(&pt), vec![
+            format!("nft_output::extract_assets<{gas}>"),
+            "bag::destroy_empty<>".to_owned(),
+            format!("coin::from_balance<{gas}>"),
+            "transfer([Result(2)])".to_owned(),
+            "transfer([NestedResult(0, 2)])".to_owned(),
+        ]);
+    }
+
+    #[test]
+    fn test_claim_output_returning_the_whole_balance() {
+        let owner = IotaAddress::random_for_testing_only();
+        let mut output = output(StardustOutputKind::Nft, owner);
+        output.storage_deposit_return = Some(StorageDepositReturnUnlockCondition {
+            return_address: IotaAddress::random_for_testing_only(),
+            return_amount: output.balance,
+        });
+
+        let gas = GAS::type_tag().to_canonical_string(true);
+        assert_eq!(commands(&build_claim(&output, owner)), vec![
+            format!("nft_output::extract_assets<{gas}>"),
+            "bag::destroy_empty<>".to_owned(),
+            format!("balance::destroy_zero<{gas}>"),
+            "transfer([NestedResult(0, 2)])".to_owned(),
+        ]);
+    }
+
+    #[test]
+    fn test_claim_alias_output() {
+        let owner = IotaAddress::random_for_testing_only();
+        let mut output = output(StardustOutputKind::Alias, owner);
+        output.native_tokens = vec![native_token("foo")];
+
+        let gas = GAS::type_tag().to_canonical_string(true);
+        let foo = native_token("foo").to_canonical_string(true);
+        assert_eq!(commands(&build_claim(&output, owner)), vec![
+            format!("alias_output::extract_assets<{gas}>"),
+            format!("utilities::extract_and_send_to<{foo}>"),
+            "bag::destroy_empty<>".to_owned(),
+            format!("coin::from_balance<{gas}>"),
+            "transfer([Result(3)])".to_owned(),
+            "transfer([NestedResult(0, 2)])".to_owned(),
+        ]);
+    }
+
+    #[test]
+    fn test_claim_object_owned_output_fails() {
+        let owner = IotaAddress::random_for_testing_only();
+        let mut output = output(StardustOutputKind::Basic, owner);
+        output.owner = Owner::ObjectOwner(IotaAddress::random_for_testing_only());
+        let mut builder = ProgrammableTransactionBuilder::new();
+        assert!(output.claim(&mut builder, owner).is_err());
+    }
+}
@@ -2,15 +2,28 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::{
+    collections::BTreeSet,
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{Context, anyhow};
+use arc_swap::ArcSwap;
 use fastcrypto_zkp::bn254::zk_login::OIDCProvider;
 use iota_config::transaction_deny_config::TransactionDenyConfig;
 use iota_types::{
-    base_types::ObjectRef,
+    base_types::{IotaAddress, ObjectID, ObjectRef},
     error::{IotaError, IotaResult, UserInputError},
     signature::GenericSignature,
     storage::BackingPackageStore,
     transaction::{Command, InputObjectKind, TransactionData, TransactionDataAPI},
 };
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
 macro_rules! deny_if_true {
     ($cond:expr, $msg:expr) => {
         if ($cond) {
@@ -23,31 +36,186 @@ macro_rules! deny_if_true {
     };
 }
 
+/// Entries of the deny list that can be changed while the node is running.
+/// They are checked in addition to the deny lists of the
+/// `TransactionDenyConfig`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DenyListEntries {
+    /// Object IDs that are not allowed to be used as input or receiving
+    /// objects.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub objects: BTreeSet<ObjectID>,
+    /// Package IDs that are not allowed to be called into, either directly or
+    /// through transitive dependencies.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub packages: BTreeSet<ObjectID>,
+    /// Original package IDs whose entire upgrade family is denied.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub package_families: BTreeSet<ObjectID>,
+    /// Addresses that are not allowed to be used as sender or sponsor.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub addresses: BTreeSet<IotaAddress>,
+}
+
+impl DenyListEntries {
+    /// Adds an entry, returning whether it was not present before.
+    pub fn insert(&mut self, entry: DenyListEntry) -> bool {
+        match entry {
+            DenyListEntry::Object(id) => self.objects.insert(id),
+            DenyListEntry::Package(id) => self.packages.insert(id),
+            DenyListEntry::PackageFamily(id) => self.package_families.insert(id),
+            DenyListEntry::Address(address) => self.addresses.insert(address),
+        }
+    }
+
+    /// Removes an entry, returning whether it was present.
+    pub fn remove(&mut self, entry: &DenyListEntry) -> bool {
+        match entry {
+            DenyListEntry::Object(id) => self.objects.remove(id),
+            DenyListEntry::Package(id) => self.packages.remove(id),
+            DenyListEntry::PackageFamily(id) => self.package_families.remove(id),
+            DenyListEntry::Address(address) => self.addresses.remove(address),
+        }
+    }
+}
+
+/// A single entry of the deny list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DenyListEntry {
+    Object(ObjectID),
+    Package(ObjectID),
+    /// The upgrade family of the package with the given original ID.
+    PackageFamily(ObjectID),
+    Address(IotaAddress),
+}
+
+impl DenyListEntry {
+    /// Parses an entry from its kind (`object`, `package`, `package-family`
+    /// or `address`) and ID.
+    pub fn parse(kind: &str, id: &str) -> anyhow::Result<Self> {
+        Ok(match kind {
+            "object" => Self::Object(ObjectID::from_str(id)?),
+            "package" => Self::Package(ObjectID::from_str(id)?),
+            "package-family" => Self::PackageFamily(ObjectID::from_str(id)?),
+            "address" => Self::Address(IotaAddress::from_str(id)?),
+            _ => return Err(anyhow!("unknown deny list entry kind: {kind}")),
+        })
+    }
+}
+
+impl fmt::Display for DenyListEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Object(id) => write!(f, "object {id}"),
+            Self::Package(id) => write!(f, "package {id}"),
+            Self::PackageFamily(id) => write!(f, "package family {id}"),
+            Self::Address(address) => write!(f, "address {address}"),
+        }
+    }
+}
+
+/// Deny list which can be updated at runtime, e.g. through the admin
+/// interface of the node.
+///
+/// Every update is written to disk before it is applied, so that it survives
+/// restarts, and readers always see either the old or the new entries as a
+/// whole.
+pub struct DynamicDenyList {
+    path: PathBuf,
+    entries: ArcSwap<DenyListEntries>,
+    // Serializes updates, so that concurrent updates are not lost.
+    update_lock: Mutex<()>,
+}
+
+impl DynamicDenyList {
+    /// Loads the deny list persisted at `path`, which is created on the first
+    /// update if it does not exist.
+    pub fn load(path: PathBuf) -> anyhow::Result<Self> {
+        let entries = if path.exists() {
+            let contents = std::fs::read_to_string(&path)
+                .with_context(|| format!("failed to read deny list {}", path.display()))?;
+            serde_yaml::from_str(&contents)
+                .with_context(|| format!("failed to parse deny list {}", path.display()))?
+        } else {
+            DenyListEntries::default()
+        };
+        Ok(Self {
+            path,
+            entries: ArcSwap::from_pointee(entries),
+            update_lock: Mutex::new(()),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns a consistent snapshot of the current entries.
+    pub fn entries(&self) -> Arc<DenyListEntries> {
+        self.entries.load_full()
+    }
+
+    /// Adds an entry, returning whether it was not present before.
+    pub fn add(&self, entry: DenyListEntry) -> anyhow::Result<bool> {
+        self.update(|entries| entries.insert(entry))
+            .inspect(|added| info!(%entry, added, "Added entry to deny list"))
+    }
+
+    /// Removes an entry, returning whether it was present.
+    pub fn remove(&self, entry: DenyListEntry) -> anyhow::Result<bool> {
+        self.update(|entries| entries.remove(&entry))
+            .inspect(|removed| info!(%entry, removed, "Removed entry from deny list"))
+    }
+
+    fn update(&self, f: impl FnOnce(&mut DenyListEntries) -> bool) -> anyhow::Result<bool> {
+        let _guard = self.update_lock.lock().unwrap();
+        let mut entries = DenyListEntries::clone(&self.entries.load());
+        if !f(&mut entries) {
+            return Ok(false);
+        }
+        persist(&self.path, &entries)?;
+        self.entries.store(Arc::new(entries));
+        Ok(true)
+    }
+}
+
+fn persist(path: &Path, entries: &DenyListEntries) -> anyhow::Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, serde_yaml::to_string(entries)?)
+        .with_context(|| format!("failed to write deny list {}", tmp_path.display()))?;
+    std::fs::rename(&tmp_path, path)
+        .with_context(|| format!("failed to write deny list {}", path.display()))?;
+    Ok(())
+}
+
 /// Check that the provided transaction is allowed to be signed according to the
-/// deny config.
+/// deny config and the dynamic deny list.
 pub fn check_transaction_for_signing(
     tx_data: &TransactionData,
     tx_signatures: &[GenericSignature],
     input_object_kinds: &[InputObjectKind],
     receiving_objects: &[ObjectRef],
     filter_config: &TransactionDenyConfig,
+    dynamic_deny_list: &DenyListEntries,
     package_store: &dyn BackingPackageStore,
 ) -> IotaResult {
     check_disabled_features(filter_config, tx_data, tx_signatures)?;
 
-    check_signers(filter_config, tx_data)?;
+    check_signers(filter_config, dynamic_deny_list, tx_data)?;
 
-    check_input_objects(filter_config, input_object_kinds)?;
+    check_input_objects(filter_config, dynamic_deny_list, input_object_kinds)?;
 
-    check_package_dependencies(filter_config, tx_data, package_store)?;
+    check_package_dependencies(filter_config, dynamic_deny_list, tx_data, package_store)?;
 
-    check_receiving_objects(filter_config, receiving_objects)?;
+    check_receiving_objects(filter_config, dynamic_deny_list, receiving_objects)?;
 
     Ok(())
 }
 
 fn check_receiving_objects(
     filter_config: &TransactionDenyConfig,
+    dynamic_deny_list: &DenyListEntries,
     receiving_objects: &[ObjectRef],
 ) -> IotaResult {
     deny_if_true!(
@@ -56,7 +224,8 @@ fn check_receiving_objects(
     );
     for (id, _, _) in receiving_objects {
         deny_if_true!(
-            filter_config.get_object_deny_set().contains(id),
+            filter_config.get_object_deny_set().contains(id)
+                || dynamic_deny_list.objects.contains(id),
             format!("Access to object {:?} is temporarily disabled", id)
         );
     }
@@ -108,14 +277,19 @@ fn check_disabled_features(
     Ok(())
 }
 
-fn check_signers(filter_config: &TransactionDenyConfig, tx_data: &TransactionData) -> IotaResult {
+fn check_signers(
+    filter_config: &TransactionDenyConfig,
+    dynamic_deny_list: &DenyListEntries,
+    tx_data: &TransactionData,
+) -> IotaResult {
     let deny_map = filter_config.get_address_deny_set();
-    if deny_map.is_empty() {
+    let dynamic_deny_map = &dynamic_deny_list.addresses;
+    if deny_map.is_empty() && dynamic_deny_map.is_empty() {
         return Ok(());
     }
     for signer in tx_data.signers() {
         deny_if_true!(
-            deny_map.contains(&signer),
+            deny_map.contains(&signer) || dynamic_deny_map.contains(&signer),
             format!(
                 "Access to account address {:?} is temporarily disabled",
                 signer
@@ -127,18 +301,20 @@ fn check_signers(filter_config: &TransactionDenyConfig, tx_data: &TransactionDat
 
 fn check_input_objects(
     filter_config: &TransactionDenyConfig,
+    dynamic_deny_list: &DenyListEntries,
     input_object_kinds: &[InputObjectKind],
 ) -> IotaResult {
     let deny_map = filter_config.get_object_deny_set();
+    let dynamic_deny_map = &dynamic_deny_list.objects;
     let shared_object_disabled = filter_config.shared_object_disabled();
-    if deny_map.is_empty() && !shared_object_disabled {
+    if deny_map.is_empty() && dynamic_deny_map.is_empty() && !shared_object_disabled {
         // No need to iterate through the input objects if no relevant policy is set.
         return Ok(());
     }
     for input_object_kind in input_object_kinds {
         let id = input_object_kind.object_id();
         deny_if_true!(
-            deny_map.contains(&id),
+            deny_map.contains(&id) || dynamic_deny_map.contains(&id),
             format!("Access to input object {:?} is temporarily disabled", id)
         );
         deny_if_true!(
@@ -151,14 +327,22 @@ fn check_input_objects(
 
 fn check_package_dependencies(
     filter_config: &TransactionDenyConfig,
+    dynamic_deny_list: &DenyListEntries,
     tx_data: &TransactionData,
     package_store: &dyn BackingPackageStore,
 ) -> IotaResult {
     let deny_map = filter_config.get_package_deny_set();
-    if deny_map.is_empty() {
+    let dynamic_deny_map = &dynamic_deny_list.packages;
+    let family_deny_map = filter_config.get_package_family_deny_set();
+    let dynamic_family_deny_map = &dynamic_deny_list.package_families;
+    let check_families = !family_deny_map.is_empty() || !dynamic_family_deny_map.is_empty();
+    if deny_map.is_empty() && dynamic_deny_map.is_empty() && !check_families {
         return Ok(());
     }
     let mut dependencies = vec![];
+    // Original IDs of the upgrade families of all used packages, only collected
+    // if a package family is denied.
+    let mut families = vec![];
     for command in tx_data.kind().iter_commands() {
         match command {
             Command::Publish(_, deps) => {
@@ -166,12 +350,19 @@ fn check_package_dependencies(
                 // by the user. But that's OK because this publish transaction will fail
                 // to execute in the end. Similar reasoning for Upgrade.
                 dependencies.extend(deps.iter().copied());
+                if check_families {
+                    families.extend(original_package_ids(package_store, deps)?);
+                }
             }
             Command::Upgrade(_, deps, package_id, _) => {
                 dependencies.extend(deps.iter().copied());
                 // It's crucial that we don't allow upgrading a package in the deny list,
                 // otherwise one can bypass the deny list by upgrading a package.
                 dependencies.push(*package_id);
+                if check_families {
+                    families.extend(original_package_ids(package_store, deps)?);
+                    families.extend(original_package_ids(package_store, &[*package_id])?);
+                }
             }
             Command::MoveCall(call) => {
                 let package = package_store.get_package_object(&call.package)?.ok_or(
@@ -195,6 +386,12 @@ fn check_package_dependencies(
                         .map(|upgrade_info| upgrade_info.upgraded_id),
                 );
                 dependencies.push(package.move_package().id());
+                if check_families {
+                    // The keys of the linkage table are the original IDs of the dependencies,
+                    // so any version of a denied package family is caught here.
+                    families.extend(package.move_package().linkage_table().keys().copied());
+                    families.push(package.move_package().original_package_id());
+                }
             }
             Command::TransferObjects(..)
             | &Command::SplitCoins(..)
@@ -204,9 +401,33 @@ fn check_package_dependencies(
     }
     for dep in dependencies {
         deny_if_true!(
-            deny_map.contains(&dep),
+            deny_map.contains(&dep) || dynamic_deny_map.contains(&dep),
             format!("Access to package {:?} is temporarily disabled", dep)
         );
     }
+    for family in families {
+        deny_if_true!(
+            family_deny_map.contains(&family) || dynamic_family_deny_map.contains(&family),
+            format!(
+                "Access to the upgrade family of package {:?} is temporarily disabled",
+                family
+            )
+        );
+    }
     Ok(())
 }
+
+/// Looks up the original package IDs of the given packages. Packages that do
+/// not exist are skipped, as transactions using them fail to execute anyway.
+fn original_package_ids(
+    package_store: &dyn BackingPackageStore,
+    package_ids: &[ObjectID],
+) -> IotaResult<Vec<ObjectID>> {
+    let mut original_ids = vec![];
+    for package_id in package_ids {
+        if let Some(package) = package_store.get_package_object(package_id)? {
+            original_ids.push(package.move_package().original_package_id());
+        }
+    }
+    Ok(original_ids)
+}
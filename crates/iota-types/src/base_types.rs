@@ -52,6 +52,7 @@ use crate::{
     iota_serde::{HexAccountAddress, Readable, to_iota_struct_tag_string},
     messages_checkpoint::CheckpointTimestamp,
     multisig::MultiSigPublicKey,
+    nested_multisig::{MultiSigMember, NestedMultiSigPublicKey},
     object::{Object, Owner},
     parse_iota_struct_tag,
     signature::GenericSignature,
@@ -775,6 +776,40 @@ impl From<&MultiSigPublicKey> for IotaAddress {
     }
 }
 
+impl From<&NestedMultiSigPublicKey> for IotaAddress {
+    /// Derive a IotaAddress from [struct NestedMultiSigPublicKey]. If no member
+    /// is a multisig, this is the address of the equivalent [struct
+    /// MultiSigPublicKey]. Otherwise it is the 32-byte Blake2b hash of
+    /// `flag_NestedMultiSig || threshold || flag_1 || id_1 || weight_1 || ...
+    /// || flag_n || id_n || weight_n`.
+    ///
+    /// For a single public key member, flag_i is the flag of its scheme and
+    /// id_i the public key. For a multisig member, flag_i is the MultiSig flag
+    /// and id_i the address of that multisig.
+    fn from(multisig_pk: &NestedMultiSigPublicKey) -> Self {
+        if let Some(flat) = multisig_pk.as_flat() {
+            return (&flat).into();
+        }
+        let mut hasher = DefaultHash::default();
+        hasher.update([SignatureScheme::NestedMultiSig.flag()]);
+        hasher.update(multisig_pk.threshold().to_le_bytes());
+        multisig_pk.members().iter().for_each(|(member, w)| {
+            match member {
+                MultiSigMember::PublicKey(pk) => {
+                    hasher.update([pk.flag()]);
+                    hasher.update(pk.as_ref());
+                }
+                MultiSigMember::MultiSig(member_pk) => {
+                    hasher.update([SignatureScheme::MultiSig.flag()]);
+                    hasher.update(IotaAddress::from(member_pk));
+                }
+            }
+            hasher.update(w.to_le_bytes());
+        });
+        IotaAddress(hasher.finalize().digest)
+    }
+}
+
 /// Iota address for [struct ZkLoginAuthenticator] is defined as the black2b
 /// hash of [zklogin_flag || iss_bytes_length || iss_bytes ||
 /// unpadded_address_seed_in_bytes].
@@ -806,6 +841,7 @@ impl TryFrom<&GenericSignature> for IotaAddress {
                 IotaAddress::try_from_unpadded(&zklogin.inputs)
             }
             GenericSignature::PasskeyAuthenticator(s) => Ok(IotaAddress::from(&s.get_pk()?)),
+            GenericSignature::NestedMultiSig(ms) => Ok(ms.get_pk().into()),
         }
     }
 }
@@ -1680,6 +1680,7 @@ pub enum SignatureScheme {
     MultiSig,
     ZkLoginAuthenticator,
     PasskeyAuthenticator,
+    NestedMultiSig,
 }
 
 impl SignatureScheme {
@@ -1693,6 +1694,7 @@ impl SignatureScheme {
             // Address.
             SignatureScheme::ZkLoginAuthenticator => 0x05,
             SignatureScheme::PasskeyAuthenticator => 0x06,
+            SignatureScheme::NestedMultiSig => 0x07,
         }
     }
 
@@ -1721,6 +1723,7 @@ impl SignatureScheme {
             0x04 => Ok(SignatureScheme::BLS12381),
             0x05 => Ok(SignatureScheme::ZkLoginAuthenticator),
             0x06 => Ok(SignatureScheme::PasskeyAuthenticator),
+            0x07 => Ok(SignatureScheme::NestedMultiSig),
             _ => Err(IotaError::KeyConversion("Invalid key scheme".to_string())),
         }
     }
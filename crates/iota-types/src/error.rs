@@ -300,6 +300,9 @@ pub enum UserInputError {
 
     #[error("Coin type is globally paused for use: {coin_type}")]
     CoinTypeGlobalPause { coin_type: String },
+
+    #[error("Invalid state override for object {object_id}: {error}")]
+    InvalidStateOverride { object_id: ObjectID, error: String },
 }
 
 #[derive(
@@ -6,12 +6,14 @@ use std::collections::{BTreeMap, BTreeSet, HashSet};
 
 use move_core_types::language_storage::TypeTag;
 use once_cell::sync::Lazy;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     base_types::{ObjectID, ObjectRef, SequenceNumber},
     digests::{ObjectDigest, TransactionDigest},
     event::Event,
+    gas::{CommandGasUsage, GasBreakdown},
     is_system_package,
     object::{Data, Object, Owner},
     storage::BackingPackageStore,
@@ -87,6 +89,127 @@ pub type ExecutionResult = (
     Vec<(Vec<u8>, TypeTag)>,
 );
 
+/// Diagnostics recorded while executing a programmable transaction for a dry
+/// run or dev-inspect. Each of them is only recorded when requested, by
+/// creating it empty, and none of them affects the effects of the transaction.
+#[derive(Debug, Default)]
+pub struct ExecutionInstrumentation {
+    /// Computation charged for each command that was run.
+    pub command_gas_usage: Option<Vec<CommandGasUsage>>,
+    /// Trace of the Move execution of each command that was run. Only recorded
+    /// in dev-inspect.
+    pub execution_trace: Option<ExecutionTrace>,
+}
+
+impl ExecutionInstrumentation {
+    pub fn new(track_command_gas: bool, trace_execution: bool) -> Self {
+        Self {
+            command_gas_usage: track_command_gas.then(Vec::new),
+            execution_trace: trace_execution.then(ExecutionTrace::default),
+        }
+    }
+
+    /// The per-command gas breakdown, if it was tracked.
+    pub fn gas_breakdown(&self) -> Option<GasBreakdown> {
+        self.command_gas_usage.as_deref().map(GasBreakdown::new)
+    }
+}
+
+/// Step-by-step trace of a programmable transaction executed in dev-inspect
+/// mode, with one entry per command that was run. Commands after a failing
+/// command are not executed and therefore have no entry.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionTrace {
+    pub commands: Vec<CommandTrace>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandTrace {
+    /// Index of the command in the programmable transaction.
+    pub command_index: u16,
+    /// The kind of the command, e.g. `MoveCall` or `SplitCoins`.
+    pub command: String,
+    /// The Move execution steps of the command, in execution order. Only
+    /// `MoveCall` and `Publish` commands (for module initializers) run Move
+    /// code.
+    pub steps: Vec<TraceStep>,
+    /// Input objects, including the gas coin, changed by the command.
+    pub object_changes: Vec<TracedObjectChange>,
+    /// The error the command failed with, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A single step of Move execution. `depth` is the depth of the call stack,
+/// with `0` for the function called by the command.
+///
+/// Values are rendered in a Move-like syntax: structs as `{ .. }` with their
+/// fields in declaration order, vectors as `[ .. ]`, byte vectors as
+/// `x"..."`, addresses as `@0x..` and references with a leading `&`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TraceStep {
+    /// A Move function was called. The arguments of the function called by
+    /// the command are its BCS encoded inputs, since they are only decoded
+    /// inside the VM.
+    #[serde(rename_all = "camelCase")]
+    Call {
+        depth: u16,
+        function: String,
+        type_arguments: Vec<String>,
+        arguments: Vec<String>,
+    },
+    /// A Move function returned. Return values are only known for native
+    /// functions; the values returned by the function called by the command
+    /// are part of the dev-inspect results.
+    #[serde(rename_all = "camelCase")]
+    Return {
+        depth: u16,
+        function: String,
+        return_values: Vec<String>,
+    },
+    /// A Move function aborted.
+    Abort { depth: u16, function: String },
+    /// An event was emitted.
+    #[serde(rename_all = "camelCase")]
+    Event {
+        depth: u16,
+        event_type: String,
+        contents: String,
+    },
+    /// A dynamic field or dynamic object field was borrowed immutably or
+    /// checked for existence.
+    DynamicFieldRead {
+        depth: u16,
+        parent: ObjectID,
+        child: ObjectID,
+    },
+    /// A dynamic field or dynamic object field was added, removed or borrowed
+    /// mutably.
+    DynamicFieldWrite {
+        depth: u16,
+        parent: ObjectID,
+        child: ObjectID,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TracedObjectChange {
+    pub object_id: ObjectID,
+    pub kind: TracedObjectChangeKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum TracedObjectChangeKind {
+    /// The contents of the object changed.
+    Mutated,
+    /// The object was taken by value, to be transferred, wrapped or deleted.
+    Taken,
+}
+
 impl ExecutionResultsV1 {
     pub fn drop_writes(&mut self) {
         self.written_objects.clear();
@@ -8,6 +8,8 @@ pub use checked::*;
 #[iota_macros::with_checked_arithmetic]
 pub mod checked {
 
+    use std::collections::BTreeMap;
+
     use enum_dispatch::enum_dispatch;
     use iota_protocol_config::ProtocolConfig;
     use itertools::MultiUnzip;
@@ -263,6 +265,84 @@ pub mod checked {
         }
     }
 
+    /// Computation charged for a single command of a programmable transaction.
+    #[serde_as]
+    #[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CommandGasUsage {
+        /// Index of the command in the programmable transaction.
+        pub command_index: u16,
+        /// The kind of the command, e.g. `MoveCall` or `SplitCoins`.
+        pub command: String,
+        /// The Move function called by a `MoveCall` command, as
+        /// `package::module::function`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub function: Option<String>,
+        /// Computation cost of the command, before the computation cost of the
+        /// whole transaction is rounded.
+        #[schemars(with = "BigInt<u64>")]
+        #[serde_as(as = "Readable<BigInt<u64>, _>")]
+        pub computation_cost: u64,
+    }
+
+    /// Computation charged for all calls of a top-level Move function in a
+    /// programmable transaction.
+    #[serde_as]
+    #[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+    #[serde(rename_all = "camelCase")]
+    pub struct MoveFunctionGasUsage {
+        /// The Move function, as `package::module::function`.
+        pub function: String,
+        /// Number of `MoveCall` commands calling the function.
+        #[schemars(with = "BigInt<u64>")]
+        #[serde_as(as = "Readable<BigInt<u64>, _>")]
+        pub calls: u64,
+        /// Computation cost of all calls of the function.
+        #[schemars(with = "BigInt<u64>")]
+        #[serde_as(as = "Readable<BigInt<u64>, _>")]
+        pub computation_cost: u64,
+    }
+
+    /// Breakdown of the computation cost of a programmable transaction by
+    /// command and by top-level Move function. Computation charged outside of
+    /// commands, like for loading input objects, and storage costs are not
+    /// attributed to any command.
+    #[derive(Eq, PartialEq, Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+    #[serde(rename_all = "camelCase")]
+    pub struct GasBreakdown {
+        pub commands: Vec<CommandGasUsage>,
+        /// Sorted by decreasing computation cost.
+        pub move_functions: Vec<MoveFunctionGasUsage>,
+    }
+
+    impl GasBreakdown {
+        pub fn new(commands: &[CommandGasUsage]) -> Self {
+            let mut functions = BTreeMap::<&str, (u64, u64)>::new();
+            for command in commands {
+                if let Some(function) = &command.function {
+                    let (calls, computation_cost) = functions.entry(function).or_default();
+                    *calls += 1;
+                    *computation_cost += command.computation_cost;
+                }
+            }
+            let mut move_functions = functions
+                .into_iter()
+                .map(
+                    |(function, (calls, computation_cost))| MoveFunctionGasUsage {
+                        function: function.to_string(),
+                        calls,
+                        computation_cost,
+                    },
+                )
+                .collect::<Vec<_>>();
+            move_functions.sort_by(|a, b| b.computation_cost.cmp(&a.computation_cost));
+            Self {
+                commands: commands.to_vec(),
+                move_functions,
+            }
+        }
+    }
+
     // Helper functions to deal with gas coins operations.
     //
 
@@ -0,0 +1,209 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::{
+    ident_str,
+    identifier::IdentStr,
+    language_storage::{StructTag, TypeTag},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    IOTA_FRAMEWORK_ADDRESS,
+    balance::Balance,
+    base_types::{IotaAddress, ObjectID},
+    collection_types::VecSet,
+    id::{ID, UID},
+};
+
+pub const KIOSK_MODULE_NAME: &IdentStr = ident_str!("kiosk");
+pub const KIOSK_STRUCT_NAME: &IdentStr = ident_str!("Kiosk");
+pub const KIOSK_OWNER_CAP_STRUCT_NAME: &IdentStr = ident_str!("KioskOwnerCap");
+pub const KIOSK_ITEM_STRUCT_NAME: &IdentStr = ident_str!("Item");
+pub const KIOSK_LISTING_STRUCT_NAME: &IdentStr = ident_str!("Listing");
+pub const KIOSK_LOCK_STRUCT_NAME: &IdentStr = ident_str!("Lock");
+
+pub const KIOSK_NEW_FUNCTION_NAME: &IdentStr = ident_str!("new");
+pub const KIOSK_PLACE_FUNCTION_NAME: &IdentStr = ident_str!("place");
+pub const KIOSK_PLACE_AND_LIST_FUNCTION_NAME: &IdentStr = ident_str!("place_and_list");
+pub const KIOSK_LIST_FUNCTION_NAME: &IdentStr = ident_str!("list");
+pub const KIOSK_DELIST_FUNCTION_NAME: &IdentStr = ident_str!("delist");
+pub const KIOSK_PURCHASE_FUNCTION_NAME: &IdentStr = ident_str!("purchase");
+pub const KIOSK_WITHDRAW_FUNCTION_NAME: &IdentStr = ident_str!("withdraw");
+
+pub const TRANSFER_POLICY_MODULE_NAME: &IdentStr = ident_str!("transfer_policy");
+pub const TRANSFER_POLICY_STRUCT_NAME: &IdentStr = ident_str!("TransferPolicy");
+pub const TRANSFER_POLICY_CAP_STRUCT_NAME: &IdentStr = ident_str!("TransferPolicyCap");
+pub const TRANSFER_POLICY_RULE_KEY_STRUCT_NAME: &IdentStr = ident_str!("RuleKey");
+pub const TRANSFER_POLICY_CONFIRM_REQUEST_FUNCTION_NAME: &IdentStr = ident_str!("confirm_request");
+
+fn kiosk_struct_tag(name: &IdentStr) -> StructTag {
+    StructTag {
+        address: IOTA_FRAMEWORK_ADDRESS,
+        module: KIOSK_MODULE_NAME.to_owned(),
+        name: name.to_owned(),
+        type_params: vec![],
+    }
+}
+
+/// Rust version of the Move iota::kiosk::Kiosk type
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct Kiosk {
+    pub id: UID,
+    /// Balance of the Kiosk - all profits from sales go here.
+    pub profits: Balance,
+    /// The owner of the kiosk, which is not necessarily the owner of its
+    /// `KioskOwnerCap`.
+    pub owner: IotaAddress,
+    /// Number of items stored in the kiosk.
+    pub item_count: u32,
+}
+
+impl Kiosk {
+    pub fn type_() -> StructTag {
+        kiosk_struct_tag(KIOSK_STRUCT_NAME)
+    }
+
+    pub fn is_kiosk(tag: &StructTag) -> bool {
+        tag.address == IOTA_FRAMEWORK_ADDRESS
+            && tag.module.as_ident_str() == KIOSK_MODULE_NAME
+            && tag.name.as_ident_str() == KIOSK_STRUCT_NAME
+    }
+}
+
+/// Rust version of the Move iota::kiosk::KioskOwnerCap type
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct KioskOwnerCap {
+    pub id: UID,
+    /// The ID of the kiosk the cap gives access to.
+    pub for_: ID,
+}
+
+impl KioskOwnerCap {
+    pub fn type_() -> StructTag {
+        kiosk_struct_tag(KIOSK_OWNER_CAP_STRUCT_NAME)
+    }
+
+    pub fn is_kiosk_owner_cap(tag: &StructTag) -> bool {
+        tag.address == IOTA_FRAMEWORK_ADDRESS
+            && tag.module.as_ident_str() == KIOSK_MODULE_NAME
+            && tag.name.as_ident_str() == KIOSK_OWNER_CAP_STRUCT_NAME
+    }
+
+    pub fn kiosk_id(&self) -> ObjectID {
+        self.for_.bytes
+    }
+}
+
+/// Rust version of the Move iota::kiosk::Item type, the name of the dynamic
+/// object field holding an item placed in a kiosk.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq)]
+pub struct KioskItem {
+    pub id: ID,
+}
+
+impl KioskItem {
+    pub fn type_() -> StructTag {
+        kiosk_struct_tag(KIOSK_ITEM_STRUCT_NAME)
+    }
+}
+
+/// Rust version of the Move iota::kiosk::Listing type, the name of the
+/// dynamic field holding the price of a listed item.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq)]
+pub struct KioskListing {
+    pub id: ID,
+    /// Whether the item is listed with a `PurchaseCap`.
+    pub is_exclusive: bool,
+}
+
+impl KioskListing {
+    pub fn type_() -> StructTag {
+        kiosk_struct_tag(KIOSK_LISTING_STRUCT_NAME)
+    }
+}
+
+/// Rust version of the Move iota::kiosk::Lock type, the name of the dynamic
+/// field marking an item as locked in a kiosk.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq)]
+pub struct KioskLock {
+    pub id: ID,
+}
+
+impl KioskLock {
+    pub fn type_() -> StructTag {
+        kiosk_struct_tag(KIOSK_LOCK_STRUCT_NAME)
+    }
+}
+
+/// Rust version of the Move std::type_name::TypeName type
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct TypeName {
+    /// The type, with addresses written without `0x` prefix.
+    pub name: String,
+}
+
+/// Rust version of the Move iota::transfer_policy::TransferPolicy type
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct TransferPolicy {
+    pub id: UID,
+    /// The balance collected by the rules of the policy.
+    pub balance: Balance,
+    /// The types of the rules which must be satisfied to confirm a transfer.
+    pub rules: VecSet<TypeName>,
+}
+
+impl TransferPolicy {
+    pub fn type_(item_type: TypeTag) -> StructTag {
+        StructTag {
+            address: IOTA_FRAMEWORK_ADDRESS,
+            module: TRANSFER_POLICY_MODULE_NAME.to_owned(),
+            name: TRANSFER_POLICY_STRUCT_NAME.to_owned(),
+            type_params: vec![item_type],
+        }
+    }
+
+    pub fn is_transfer_policy(tag: &StructTag) -> bool {
+        tag.address == IOTA_FRAMEWORK_ADDRESS
+            && tag.module.as_ident_str() == TRANSFER_POLICY_MODULE_NAME
+            && tag.name.as_ident_str() == TRANSFER_POLICY_STRUCT_NAME
+    }
+
+    /// Returns the type of the items the policy applies to if `tag` is a
+    /// `TransferPolicy<T>`.
+    pub fn item_type(tag: &StructTag) -> Option<&TypeTag> {
+        if !Self::is_transfer_policy(tag) {
+            return None;
+        }
+        match &tag.type_params[..] {
+            [item_type] => Some(item_type),
+            _ => None,
+        }
+    }
+}
+
+/// Rust version of the Move iota::transfer_policy::TransferPolicyCap type
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct TransferPolicyCap {
+    pub id: UID,
+    pub policy_id: ID,
+}
+
+/// Rust version of the Move iota::transfer_policy::RuleKey type, the name of
+/// the dynamic field holding the configuration of a rule.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, Eq, PartialEq)]
+pub struct TransferPolicyRuleKey {
+    // Move structs without fields are compiled with a single boolean field.
+    dummy_field: bool,
+}
+
+impl TransferPolicyRuleKey {
+    pub fn type_(rule_type: TypeTag) -> StructTag {
+        StructTag {
+            address: IOTA_FRAMEWORK_ADDRESS,
+            module: TRANSFER_POLICY_MODULE_NAME.to_owned(),
+            name: TRANSFER_POLICY_RULE_KEY_STRUCT_NAME.to_owned(),
+            type_params: vec![rule_type],
+        }
+    }
+}
@@ -65,6 +65,7 @@ pub mod inner_temporary_store;
 pub mod iota_sdk2_conversions;
 pub mod iota_serde;
 pub mod iota_system_state;
+pub mod kiosk;
 pub mod layout_resolver;
 pub mod message_envelope;
 pub mod messages_checkpoint;
@@ -75,6 +76,7 @@ pub mod metrics;
 pub mod mock_checkpoint_builder;
 pub mod move_package;
 pub mod multisig;
+pub mod nested_multisig;
 pub mod object;
 pub mod passkey_authenticator;
 pub mod programmable_transaction_builder;
@@ -83,6 +85,7 @@ pub mod randomness_state;
 pub mod signature;
 pub mod signature_verification;
 pub mod stardust;
+pub mod state_override;
 pub mod storage;
 pub mod supported_protocol_versions;
 pub mod system_admin_cap;
@@ -7,7 +7,7 @@ use std::{
     fmt::{Debug, Formatter},
     hash::{Hash, Hasher},
     sync::Arc,
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use byteorder::{BigEndian, ReadBytesExt};
@@ -86,6 +86,7 @@ pub enum ConsensusTransactionKey {
     NewJWKFetched(Box<(AuthorityName, JwkId, JWK)>),
     RandomnessDkgMessage(AuthorityName),
     RandomnessDkgConfirmation(AuthorityName),
+    ExecutionTimeObservation(AuthorityName, u64 /* generation */),
 }
 
 impl Debug for ConsensusTransactionKey {
@@ -118,6 +119,12 @@ impl Debug for ConsensusTransactionKey {
             Self::RandomnessDkgConfirmation(name) => {
                 write!(f, "RandomnessDkgConfirmation({:?})", name.concise())
             }
+            Self::ExecutionTimeObservation(name, generation) => write!(
+                f,
+                "ExecutionTimeObservation({:?}, {:?})",
+                name.concise(),
+                generation
+            ),
         }
     }
 }
@@ -187,6 +194,65 @@ impl AuthorityCapabilitiesV1 {
     }
 }
 
+/// The maximum number of estimates a single `ExecutionTimeObservation` may
+/// carry.
+pub const MAX_EXECUTION_TIME_OBSERVATIONS_PER_MESSAGE: usize = 128;
+
+/// Identifies a Move entry function whose execution time is observed by
+/// validators.
+#[derive(Serialize, Deserialize, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ExecutionTimeObservationKey {
+    pub package: ObjectID,
+    pub module: String,
+    pub function: String,
+}
+
+/// Used to share the execution time estimates measured locally by an authority
+/// via consensus. Estimates of all authorities are combined into the execution
+/// time estimates used by shared object congestion control.
+#[derive(Serialize, Deserialize, Clone, Hash)]
+pub struct ExecutionTimeObservation {
+    /// Originating authority - must match transaction source authority from
+    /// consensus.
+    pub authority: AuthorityName,
+    /// Generation number set by sending authority. Observations with a
+    /// generation lower than or equal to the last one received from the same
+    /// authority are ignored.
+    pub generation: u64,
+    /// Smoothed local execution time estimates for the listed entry functions.
+    /// Only estimates that changed since the last observation are included.
+    pub estimates: Vec<(ExecutionTimeObservationKey, Duration)>,
+}
+
+impl Debug for ExecutionTimeObservation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExecutionTimeObservation")
+            .field("authority", &self.authority.concise())
+            .field("generation", &self.generation)
+            .field("estimates", &self.estimates)
+            .finish()
+    }
+}
+
+impl ExecutionTimeObservation {
+    pub fn new(
+        authority: AuthorityName,
+        estimates: Vec<(ExecutionTimeObservationKey, Duration)>,
+    ) -> Self {
+        let generation = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Iota did not exist prior to 1970")
+            .as_micros()
+            .try_into()
+            .expect("This build of iota is not supported in the year 500,000");
+        Self {
+            authority,
+            generation,
+            estimates,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub enum ConsensusTransactionKind {
     UserTransaction(Box<CertifiedTransaction>),
@@ -204,6 +270,8 @@ pub enum ConsensusTransactionKind {
     // of `RandomnessDkgMessages` have been received locally, to complete the key generation
     // process. Contents are a serialized `fastcrypto_tbls::dkg::Confirmation`.
     RandomnessDkgConfirmation(AuthorityName, Vec<u8>),
+
+    ExecutionTimeObservation(ExecutionTimeObservation),
 }
 
 impl ConsensusTransactionKind {
@@ -393,6 +461,16 @@ impl ConsensusTransaction {
         }
     }
 
+    pub fn new_execution_time_observation(observation: ExecutionTimeObservation) -> Self {
+        let mut hasher = DefaultHasher::new();
+        observation.hash(&mut hasher);
+        let tracking_id = hasher.finish().to_le_bytes();
+        Self {
+            tracking_id,
+            kind: ConsensusTransactionKind::ExecutionTimeObservation(observation),
+        }
+    }
+
     pub fn get_tracking_id(&self) -> u64 {
         (&self.tracking_id[..])
             .read_u64::<BigEndian>()
@@ -429,6 +507,12 @@ impl ConsensusTransaction {
             ConsensusTransactionKind::RandomnessDkgConfirmation(authority, _) => {
                 ConsensusTransactionKey::RandomnessDkgConfirmation(*authority)
             }
+            ConsensusTransactionKind::ExecutionTimeObservation(observation) => {
+                ConsensusTransactionKey::ExecutionTimeObservation(
+                    observation.authority,
+                    observation.generation,
+                )
+            }
         }
     }
 
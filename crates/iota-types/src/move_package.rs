@@ -8,7 +8,10 @@ use derive_more::Display;
 use fastcrypto::hash::HashFunction;
 use iota_protocol_config::ProtocolConfig;
 use move_binary_format::{
-    binary_config::BinaryConfig, file_format::CompiledModule, file_format_common::VERSION_6,
+    binary_config::BinaryConfig,
+    compatibility::{Compatibility, InclusionCheck},
+    file_format::{AbilitySet, CompiledModule},
+    file_format_common::VERSION_6,
     normalized,
 };
 use move_core_types::{
@@ -138,6 +141,44 @@ impl UpgradePolicy {
     pub fn is_valid_policy(policy: &u8) -> bool {
         Self::try_from(*policy).is_ok()
     }
+
+    /// Verifies the compatibility of two normalized Move modules based on the
+    /// upgrade policy. Depending on the policy, it checks if the
+    /// new module is a subset, equal, or compatible with the
+    /// current module. The compatibility check may include aspects like struct
+    /// layout, public function linking, and struct type parameters.
+    pub fn check_compatibility(
+        &self,
+        cur_module: &normalized::Module,
+        new_module: &normalized::Module,
+    ) -> Result<(), ExecutionError> {
+        match self {
+            UpgradePolicy::Additive => InclusionCheck::Subset.check(cur_module, new_module),
+            UpgradePolicy::DepOnly => InclusionCheck::Equal.check(cur_module, new_module),
+            UpgradePolicy::Compatible => {
+                let compatibility = Compatibility {
+                    check_datatype_and_pub_function_linking: true,
+                    check_datatype_layout: true,
+                    check_friend_linking: false,
+                    check_private_entry_linking: false,
+                    disallowed_new_abilities: AbilitySet::ALL,
+                    disallow_change_datatype_type_params: true,
+                    // We disallow adding new variants to enums for now
+                    disallow_new_variants: true,
+                };
+
+                compatibility.check(cur_module, new_module)
+            }
+        }
+        .map_err(|e| {
+            ExecutionError::new_with_source(
+                ExecutionErrorKind::PackageUpgradeError {
+                    upgrade_error: PackageUpgradeError::IncompatibleUpgrade,
+                },
+                e,
+            )
+        })
+    }
 }
 
 impl TryFrom<u8> for UpgradePolicy {
@@ -491,6 +532,31 @@ impl MovePackage {
         (*module.address()).into()
     }
 
+    /// The original IDs of the packages that the modules of this package refer
+    /// to directly, as opposed to the transitive dependencies recorded in its
+    /// linkage table.
+    pub fn immediate_dependencies(&self) -> IotaResult<BTreeSet<ObjectID>> {
+        let mut dependencies = BTreeSet::new();
+        for bytes in self.module_map.values() {
+            // Only the module handles are read, so it is OK to deserialize with the max
+            // Move binary version
+            let module = CompiledModule::deserialize_with_defaults(bytes).map_err(|error| {
+                IotaError::ModuleDeserializationFailure {
+                    error: error.to_string(),
+                }
+            })?;
+            dependencies.extend(
+                module
+                    .immediate_dependencies()
+                    .into_iter()
+                    .map(|dep| ObjectID::from(*dep.address())),
+            );
+        }
+
+        dependencies.remove(&self.original_package_id());
+        Ok(dependencies)
+    }
+
     pub fn deserialize_module(
         &self,
         module: &Identifier,
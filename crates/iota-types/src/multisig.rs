@@ -138,77 +138,15 @@ impl AuthenticatorTrait for MultiSig {
                     .ok_or(IotaError::InvalidSignature {
                         error: "Invalid public keys index".to_string(),
                     })?;
-            let res = match sig {
-                CompressedSignature::Ed25519(s) => {
-                    let pk =
-                        Ed25519PublicKey::from_bytes(subsig_pubkey.as_ref()).map_err(|_| {
-                            IotaError::InvalidSignature {
-                                error: "Invalid ed25519 pk bytes".to_string(),
-                            }
-                        })?;
-                    pk.verify(
-                        &digest,
-                        &s.try_into().map_err(|_| IotaError::InvalidSignature {
-                            error: "Invalid ed25519 signature bytes".to_string(),
-                        })?,
-                    )
-                }
-                CompressedSignature::Secp256k1(s) => {
-                    let pk =
-                        Secp256k1PublicKey::from_bytes(subsig_pubkey.as_ref()).map_err(|_| {
-                            IotaError::InvalidSignature {
-                                error: "Invalid k1 pk bytes".to_string(),
-                            }
-                        })?;
-                    pk.verify(
-                        &digest,
-                        &s.try_into().map_err(|_| IotaError::InvalidSignature {
-                            error: "Invalid k1 signature bytes".to_string(),
-                        })?,
-                    )
-                }
-                CompressedSignature::Secp256r1(s) => {
-                    let pk =
-                        Secp256r1PublicKey::from_bytes(subsig_pubkey.as_ref()).map_err(|_| {
-                            IotaError::InvalidSignature {
-                                error: "Invalid r1 pk bytes".to_string(),
-                            }
-                        })?;
-                    pk.verify(
-                        &digest,
-                        &s.try_into().map_err(|_| IotaError::InvalidSignature {
-                            error: "Invalid r1 signature bytes".to_string(),
-                        })?,
-                    )
-                }
-                CompressedSignature::ZkLogin(z) => {
-                    let authenticator = ZkLoginAuthenticator::from_bytes(&z.0).map_err(|_| {
-                        IotaError::InvalidSignature {
-                            error: "Invalid zklogin authenticator bytes".to_string(),
-                        }
-                    })?;
-                    authenticator
-                        .verify_claims(
-                            value,
-                            IotaAddress::from(subsig_pubkey),
-                            verify_params,
-                            zklogin_inputs_cache.clone(),
-                        )
-                        .map_err(|e| FastCryptoError::GeneralError(e.to_string()))
-                }
-            };
-            if res.is_ok() {
-                weight_sum += *weight as u16;
-            } else {
-                return res.map_err(|e| IotaError::InvalidSignature {
-                    error: format!(
-                        "Invalid sig for pk={} address={:?} error={:?}",
-                        subsig_pubkey.encode_base64(),
-                        IotaAddress::from(subsig_pubkey),
-                        e.to_string()
-                    ),
-                });
-            }
+            verify_compressed_signature(
+                sig,
+                subsig_pubkey,
+                value,
+                &digest,
+                verify_params,
+                &zklogin_inputs_cache,
+            )?;
+            weight_sum += *weight as u16;
         }
         if weight_sum >= self.multisig_pk.threshold {
             Ok(())
@@ -223,6 +161,85 @@ impl AuthenticatorTrait for MultiSig {
     }
 }
 
+/// Verifies the signature of a single public key of a multisig against the
+/// digest of the intent message it commits to.
+pub(crate) fn verify_compressed_signature<T>(
+    sig: &CompressedSignature,
+    subsig_pubkey: &PublicKey,
+    value: &IntentMessage<T>,
+    digest: &[u8],
+    verify_params: &VerifyParams,
+    zklogin_inputs_cache: &Arc<VerifiedDigestCache<ZKLoginInputsDigest>>,
+) -> Result<(), IotaError>
+where
+    T: Serialize,
+{
+    let res = match sig {
+        CompressedSignature::Ed25519(s) => {
+            let pk = Ed25519PublicKey::from_bytes(subsig_pubkey.as_ref()).map_err(|_| {
+                IotaError::InvalidSignature {
+                    error: "Invalid ed25519 pk bytes".to_string(),
+                }
+            })?;
+            pk.verify(
+                digest,
+                &s.try_into().map_err(|_| IotaError::InvalidSignature {
+                    error: "Invalid ed25519 signature bytes".to_string(),
+                })?,
+            )
+        }
+        CompressedSignature::Secp256k1(s) => {
+            let pk = Secp256k1PublicKey::from_bytes(subsig_pubkey.as_ref()).map_err(|_| {
+                IotaError::InvalidSignature {
+                    error: "Invalid k1 pk bytes".to_string(),
+                }
+            })?;
+            pk.verify(
+                digest,
+                &s.try_into().map_err(|_| IotaError::InvalidSignature {
+                    error: "Invalid k1 signature bytes".to_string(),
+                })?,
+            )
+        }
+        CompressedSignature::Secp256r1(s) => {
+            let pk = Secp256r1PublicKey::from_bytes(subsig_pubkey.as_ref()).map_err(|_| {
+                IotaError::InvalidSignature {
+                    error: "Invalid r1 pk bytes".to_string(),
+                }
+            })?;
+            pk.verify(
+                digest,
+                &s.try_into().map_err(|_| IotaError::InvalidSignature {
+                    error: "Invalid r1 signature bytes".to_string(),
+                })?,
+            )
+        }
+        CompressedSignature::ZkLogin(z) => {
+            let authenticator = ZkLoginAuthenticator::from_bytes(&z.0).map_err(|_| {
+                IotaError::InvalidSignature {
+                    error: "Invalid zklogin authenticator bytes".to_string(),
+                }
+            })?;
+            authenticator
+                .verify_claims(
+                    value,
+                    IotaAddress::from(subsig_pubkey),
+                    verify_params,
+                    zklogin_inputs_cache.clone(),
+                )
+                .map_err(|e| FastCryptoError::GeneralError(e.to_string()))
+        }
+    };
+    res.map_err(|e| IotaError::InvalidSignature {
+        error: format!(
+            "Invalid sig for pk={} address={:?} error={:?}",
+            subsig_pubkey.encode_base64(),
+            IotaAddress::from(subsig_pubkey),
+            e.to_string()
+        ),
+    })
+}
+
 /// Interpret a bitmap of 01s as a list of indices that is set to 1s.
 /// e.g. 22 = 0b10110, then the result is [1, 2, 4].
 pub fn as_indices(bitmap: u16) -> Result<Vec<u8>, IotaError> {
@@ -319,6 +336,10 @@ impl MultiSig {
         &self.sigs
     }
 
+    pub fn get_bitmap(&self) -> BitmapUnit {
+        self.bitmap
+    }
+
     pub fn get_zklogin_sigs(&self) -> Result<Vec<ZkLoginAuthenticator>, IotaError> {
         let authenticator_as_bytes: Vec<_> = self
             .sigs
@@ -0,0 +1,661 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    hash::{Hash, Hasher},
+    str::FromStr,
+    sync::Arc,
+};
+
+use fastcrypto::{
+    encoding::{Base64, Encoding},
+    error::{FastCryptoError, FastCryptoResult},
+    hash::HashFunction,
+    traits::{EncodeDecodeBase64, ToFromBytes},
+};
+use once_cell::sync::OnceCell;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use shared_crypto::intent::IntentMessage;
+
+use crate::{
+    base_types::{EpochId, IotaAddress},
+    crypto::{CompressedSignature, DefaultHash, PublicKey, SignatureScheme},
+    digests::ZKLoginInputsDigest,
+    error::IotaError,
+    multisig::{
+        BitmapUnit, MAX_BITMAP_VALUE, MAX_SIGNER_IN_MULTISIG, MultiSigPublicKey, ThresholdUnit,
+        WeightUnit, as_indices, verify_compressed_signature,
+    },
+    signature::{AuthenticatorTrait, GenericSignature, VerifyParams},
+    signature_verification::VerifiedDigestCache,
+    zk_login_authenticator::ZkLoginAuthenticator,
+};
+
+#[cfg(test)]
+#[path = "unit_tests/nested_multisig_tests.rs"]
+mod nested_multisig_tests;
+
+/// The maximum nesting depth of a [struct NestedMultiSigPublicKey]. A multisig
+/// whose members are all single public keys has depth 1.
+pub const MAX_MULTISIG_DEPTH: usize = 3;
+/// The maximum number of single public keys in a nested multisig, counted
+/// across all levels.
+pub const MAX_SIGNER_IN_NESTED_MULTISIG: usize = 32;
+
+/// A member of a [struct NestedMultiSigPublicKey], either a single public key
+/// or a multisig public key itself.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum MultiSigMember {
+    PublicKey(PublicKey),
+    MultiSig(NestedMultiSigPublicKey),
+}
+
+impl MultiSigMember {
+    /// The address controlled by this member alone.
+    pub fn address(&self) -> IotaAddress {
+        match self {
+            MultiSigMember::PublicKey(pk) => pk.into(),
+            MultiSigMember::MultiSig(multisig_pk) => multisig_pk.into(),
+        }
+    }
+}
+
+/// A single public key is encoded as `flag || pk` and a multisig public key as
+/// `flag_MultiSig || bcs_bytes(NestedMultiSigPublicKey)`, both in Base64.
+impl EncodeDecodeBase64 for MultiSigMember {
+    fn encode_base64(&self) -> String {
+        match self {
+            MultiSigMember::PublicKey(pk) => pk.encode_base64(),
+            MultiSigMember::MultiSig(multisig_pk) => multisig_pk.encode_base64(),
+        }
+    }
+
+    fn decode_base64(value: &str) -> FastCryptoResult<Self> {
+        let bytes = Base64::decode(value)?;
+        if bytes.first() == Some(&SignatureScheme::MultiSig.flag()) {
+            NestedMultiSigPublicKey::decode_base64(value).map(MultiSigMember::MultiSig)
+        } else {
+            PublicKey::decode_base64(value).map(MultiSigMember::PublicKey)
+        }
+    }
+}
+
+impl FromStr for MultiSigMember {
+    type Err = eyre::Report;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::decode_base64(s).map_err(|e| eyre::eyre!("Fail to decode base64 {}", e))
+    }
+}
+
+impl From<PublicKey> for MultiSigMember {
+    fn from(pk: PublicKey) -> Self {
+        MultiSigMember::PublicKey(pk)
+    }
+}
+
+impl From<NestedMultiSigPublicKey> for MultiSigMember {
+    fn from(multisig_pk: NestedMultiSigPublicKey) -> Self {
+        MultiSigMember::MultiSig(multisig_pk)
+    }
+}
+
+/// The public key of a multisig whose members may be multisigs themselves, up
+/// to [const MAX_MULTISIG_DEPTH] levels. Every level follows the same rules as
+/// [struct MultiSigPublicKey].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct NestedMultiSigPublicKey {
+    /// A list of members and their corresponding weight.
+    members: Vec<(MultiSigMember, WeightUnit)>,
+    /// If the total weight of the members with a verified signature is larger
+    /// than threshold, the multisig is verified.
+    threshold: ThresholdUnit,
+}
+
+impl NestedMultiSigPublicKey {
+    /// Construct NestedMultiSigPublicKey without validation.
+    pub fn insecure_new(
+        members: Vec<(MultiSigMember, WeightUnit)>,
+        threshold: ThresholdUnit,
+    ) -> Self {
+        Self { members, threshold }
+    }
+
+    pub fn new(
+        members: Vec<MultiSigMember>,
+        weights: Vec<WeightUnit>,
+        threshold: ThresholdUnit,
+    ) -> Result<Self, IotaError> {
+        if members.len() != weights.len() {
+            return Err(IotaError::InvalidSignature {
+                error: "Invalid multisig public key construction".to_string(),
+            });
+        }
+        Self::insecure_new(members.into_iter().zip(weights).collect(), threshold)
+            .validate()
+            .map_err(|_| IotaError::InvalidSignature {
+                error: "Invalid multisig public key construction".to_string(),
+            })
+    }
+
+    pub fn get_index(&self, member: &MultiSigMember) -> Option<u8> {
+        self.members
+            .iter()
+            .position(|x| &x.0 == member)
+            .map(|x| x as u8)
+    }
+
+    pub fn threshold(&self) -> &ThresholdUnit {
+        &self.threshold
+    }
+
+    pub fn members(&self) -> &Vec<(MultiSigMember, WeightUnit)> {
+        &self.members
+    }
+
+    /// Number of multisig levels, 1 if no member is a multisig.
+    pub fn depth(&self) -> usize {
+        1 + self
+            .members
+            .iter()
+            .filter_map(|(member, _)| match member {
+                MultiSigMember::MultiSig(multisig_pk) => Some(multisig_pk.depth()),
+                MultiSigMember::PublicKey(_) => None,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Number of single public keys across all levels.
+    pub fn num_public_keys(&self) -> usize {
+        self.members
+            .iter()
+            .map(|(member, _)| match member {
+                MultiSigMember::MultiSig(multisig_pk) => multisig_pk.num_public_keys(),
+                MultiSigMember::PublicKey(_) => 1,
+            })
+            .sum()
+    }
+
+    /// Returns the equivalent [struct MultiSigPublicKey] if no member is a
+    /// multisig.
+    pub fn as_flat(&self) -> Option<MultiSigPublicKey> {
+        let pk_map = self
+            .members
+            .iter()
+            .map(|(member, weight)| match member {
+                MultiSigMember::PublicKey(pk) => Some((pk.clone(), *weight)),
+                MultiSigMember::MultiSig(_) => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(MultiSigPublicKey::insecure_new(pk_map, self.threshold))
+    }
+
+    pub fn validate(&self) -> Result<NestedMultiSigPublicKey, FastCryptoError> {
+        if self.depth() > MAX_MULTISIG_DEPTH
+            || self.num_public_keys() > MAX_SIGNER_IN_NESTED_MULTISIG
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        self.validate_level()?;
+        Ok(self.to_owned())
+    }
+
+    fn validate_level(&self) -> Result<(), FastCryptoError> {
+        let members = self.members();
+        if self.threshold == 0
+            || members.is_empty()
+            || members.len() > MAX_SIGNER_IN_MULTISIG
+            || members.iter().any(|(_member, weight)| *weight == 0)
+            || members
+                .iter()
+                .map(|(_member, weight)| *weight as ThresholdUnit)
+                .sum::<ThresholdUnit>()
+                < self.threshold
+            || members.iter().enumerate().any(|(i, (member, _weight))| {
+                members
+                    .iter()
+                    .skip(i + 1)
+                    .any(|(other_member, _weight)| *member == *other_member)
+            })
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        members
+            .iter()
+            .try_for_each(|(member, _weight)| match member {
+                MultiSigMember::MultiSig(multisig_pk) => multisig_pk.validate_level(),
+                MultiSigMember::PublicKey(_) => Ok(()),
+            })
+    }
+}
+
+impl From<MultiSigPublicKey> for NestedMultiSigPublicKey {
+    fn from(multisig_pk: MultiSigPublicKey) -> Self {
+        Self {
+            members: multisig_pk
+                .pubkeys()
+                .iter()
+                .map(|(pk, weight)| (MultiSigMember::PublicKey(pk.clone()), *weight))
+                .collect(),
+            threshold: *multisig_pk.threshold(),
+        }
+    }
+}
+
+/// Encoded as the MultiSig flag (0x03) concat with the bcs bytes of [struct
+/// NestedMultiSigPublicKey] in Base64, so that it can be passed wherever a
+/// member public key is expected.
+impl EncodeDecodeBase64 for NestedMultiSigPublicKey {
+    fn encode_base64(&self) -> String {
+        let mut bytes = vec![SignatureScheme::MultiSig.flag()];
+        bytes.extend(bcs::to_bytes(self).expect("BCS serialization should not fail"));
+        Base64::encode(bytes)
+    }
+
+    fn decode_base64(value: &str) -> FastCryptoResult<Self> {
+        let bytes = Base64::decode(value)?;
+        if bytes.first() != Some(&SignatureScheme::MultiSig.flag()) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let multisig_pk: NestedMultiSigPublicKey =
+            bcs::from_bytes(&bytes[1..]).map_err(|_| FastCryptoError::InvalidInput)?;
+        multisig_pk.validate()
+    }
+}
+
+/// The signature of a member of a [struct NestedMultiSigPublicKey].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum NestedCompressedSignature {
+    /// The signature of a single public key member.
+    Signature(CompressedSignature),
+    /// The signatures of a multisig member, with the bitmap of the members of
+    /// that multisig they belong to.
+    MultiSig {
+        sigs: Vec<NestedCompressedSignature>,
+        bitmap: BitmapUnit,
+    },
+}
+
+/// The struct that contains signatures and public keys necessary for
+/// authenticating a nested multisig.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct NestedMultiSig {
+    /// The signatures of the signing members.
+    sigs: Vec<NestedCompressedSignature>,
+    /// A bitmap that indicates the position of the member each signature
+    /// should be authenticated with.
+    bitmap: BitmapUnit,
+    /// The members of the multisig and their weights.
+    multisig_pk: NestedMultiSigPublicKey,
+    /// A bytes representation of [struct NestedMultiSig]. This helps with
+    /// implementing [trait AsRef<[u8]>].
+    #[serde(skip)]
+    bytes: OnceCell<Vec<u8>>,
+}
+
+/// Necessary trait for [struct SenderSignedData].
+impl PartialEq for NestedMultiSig {
+    fn eq(&self, other: &Self) -> bool {
+        self.sigs == other.sigs
+            && self.bitmap == other.bitmap
+            && self.multisig_pk == other.multisig_pk
+    }
+}
+
+/// Necessary trait for [struct SenderSignedData].
+impl Eq for NestedMultiSig {}
+
+/// Necessary trait for [struct SenderSignedData].
+impl Hash for NestedMultiSig {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_ref().hash(state);
+    }
+}
+
+impl AuthenticatorTrait for NestedMultiSig {
+    fn verify_user_authenticator_epoch(
+        &self,
+        epoch_id: EpochId,
+        max_epoch_upper_bound_delta: Option<u64>,
+    ) -> Result<(), IotaError> {
+        self.get_zklogin_sigs()?.iter().try_for_each(|s| {
+            s.verify_user_authenticator_epoch(epoch_id, max_epoch_upper_bound_delta)
+        })
+    }
+
+    fn verify_claims<T>(
+        &self,
+        value: &IntentMessage<T>,
+        multisig_address: IotaAddress,
+        verify_params: &VerifyParams,
+        zklogin_inputs_cache: Arc<VerifiedDigestCache<ZKLoginInputsDigest>>,
+    ) -> Result<(), IotaError>
+    where
+        T: Serialize,
+    {
+        self.multisig_pk
+            .validate()
+            .map_err(|_| IotaError::InvalidSignature {
+                error: "Invalid multisig pubkey".to_string(),
+            })?;
+
+        if IotaAddress::from(&self.multisig_pk) != multisig_address {
+            return Err(IotaError::InvalidSignature {
+                error: "Invalid address derived from pks".to_string(),
+            });
+        }
+
+        if !self.get_zklogin_sigs()?.is_empty() && !verify_params.accept_zklogin_in_multisig {
+            return Err(IotaError::InvalidSignature {
+                error: "zkLogin sig not supported inside multisig".to_string(),
+            });
+        }
+
+        let message = bcs::to_bytes(&value).expect("Message serialization should not fail");
+        let mut hasher = DefaultHash::default();
+        hasher.update(message);
+        let digest = hasher.finalize().digest;
+        verify_member_signatures(
+            &self.multisig_pk,
+            &self.sigs,
+            self.bitmap,
+            value,
+            &digest,
+            verify_params,
+            &zklogin_inputs_cache,
+        )
+    }
+}
+
+/// Verifies the signatures of the members of one level of a nested multisig
+/// and that their total weight reaches the threshold of that level.
+fn verify_member_signatures<T>(
+    multisig_pk: &NestedMultiSigPublicKey,
+    sigs: &[NestedCompressedSignature],
+    bitmap: BitmapUnit,
+    value: &IntentMessage<T>,
+    digest: &[u8],
+    verify_params: &VerifyParams,
+    zklogin_inputs_cache: &Arc<VerifiedDigestCache<ZKLoginInputsDigest>>,
+) -> Result<(), IotaError>
+where
+    T: Serialize,
+{
+    let indices = as_indices(bitmap)?;
+    if sigs.len() != indices.len() {
+        return Err(IotaError::InvalidSignature {
+            error: "Invalid number of signatures".to_string(),
+        });
+    }
+    let mut weight_sum: u16 = 0;
+    for (sig, i) in sigs.iter().zip(indices) {
+        let (member, weight) =
+            multisig_pk
+                .members
+                .get(i as usize)
+                .ok_or(IotaError::InvalidSignature {
+                    error: "Invalid public keys index".to_string(),
+                })?;
+        match (sig, member) {
+            (NestedCompressedSignature::Signature(sig), MultiSigMember::PublicKey(pk)) => {
+                verify_compressed_signature(
+                    sig,
+                    pk,
+                    value,
+                    digest,
+                    verify_params,
+                    zklogin_inputs_cache,
+                )?
+            }
+            (
+                NestedCompressedSignature::MultiSig { sigs, bitmap },
+                MultiSigMember::MultiSig(multisig_pk),
+            ) => verify_member_signatures(
+                multisig_pk,
+                sigs,
+                *bitmap,
+                value,
+                digest,
+                verify_params,
+                zklogin_inputs_cache,
+            )
+            .map_err(|e| IotaError::InvalidSignature {
+                error: format!(
+                    "Invalid sig for multisig member address={:?} error={:?}",
+                    IotaAddress::from(multisig_pk),
+                    e.to_string()
+                ),
+            })?,
+            _ => {
+                return Err(IotaError::InvalidSignature {
+                    error: format!("Signature does not match the kind of member {i}"),
+                });
+            }
+        }
+        weight_sum += *weight as u16;
+    }
+    if weight_sum >= multisig_pk.threshold {
+        Ok(())
+    } else {
+        Err(IotaError::InvalidSignature {
+            error: format!(
+                "Insufficient weight={:?} threshold={:?}",
+                weight_sum, multisig_pk.threshold
+            ),
+        })
+    }
+}
+
+/// Checks that the signatures of one level of a nested multisig match the
+/// bitmap and the kind of the members they belong to.
+fn validate_member_signatures(
+    multisig_pk: &NestedMultiSigPublicKey,
+    sigs: &[NestedCompressedSignature],
+    bitmap: BitmapUnit,
+) -> Result<(), FastCryptoError> {
+    let indices = as_indices(bitmap).map_err(|_| FastCryptoError::InvalidInput)?;
+    if sigs.is_empty() || sigs.len() != indices.len() {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    sigs.iter().zip(indices).try_for_each(|(sig, i)| {
+        match (sig, multisig_pk.members.get(i as usize)) {
+            (NestedCompressedSignature::Signature(_), Some((MultiSigMember::PublicKey(_), _))) => {
+                Ok(())
+            }
+            (
+                NestedCompressedSignature::MultiSig { sigs, bitmap },
+                Some((MultiSigMember::MultiSig(multisig_pk), _)),
+            ) => validate_member_signatures(multisig_pk, sigs, *bitmap),
+            _ => Err(FastCryptoError::InvalidInput),
+        }
+    })
+}
+
+impl NestedMultiSig {
+    /// Create NestedMultiSig from its fields without validation
+    pub fn insecure_new(
+        sigs: Vec<NestedCompressedSignature>,
+        bitmap: BitmapUnit,
+        multisig_pk: NestedMultiSigPublicKey,
+    ) -> Self {
+        Self {
+            sigs,
+            bitmap,
+            multisig_pk,
+            bytes: OnceCell::new(),
+        }
+    }
+
+    /// This combines the signatures of some members of `multisig_pk` to a
+    /// NestedMultiSig. A single public key member signs with a single
+    /// signature [enum GenericSignature::Signature] or zkLogin signature,
+    /// and a multisig member with the [struct MultiSig] or [struct
+    /// NestedMultiSig] built from the signatures of its own members. The
+    /// signatures can be passed in any order.
+    pub fn combine(
+        full_sigs: Vec<GenericSignature>,
+        multisig_pk: NestedMultiSigPublicKey,
+    ) -> Result<Self, IotaError> {
+        multisig_pk
+            .validate()
+            .map_err(|_| IotaError::InvalidSignature {
+                error: "Invalid multisig public key".to_string(),
+            })?;
+
+        if full_sigs.len() > multisig_pk.members.len() || full_sigs.is_empty() {
+            return Err(IotaError::InvalidSignature {
+                error: "Invalid number of signatures".to_string(),
+            });
+        }
+        let mut bitmap = 0;
+        let mut indexed_sigs = Vec::with_capacity(full_sigs.len());
+        for s in full_sigs {
+            let (member, sig) = match s {
+                GenericSignature::MultiSig(multisig) => (
+                    MultiSigMember::MultiSig(multisig.get_pk().clone().into()),
+                    NestedCompressedSignature::MultiSig {
+                        sigs: multisig
+                            .get_sigs()
+                            .iter()
+                            .cloned()
+                            .map(NestedCompressedSignature::Signature)
+                            .collect(),
+                        bitmap: multisig.get_bitmap(),
+                    },
+                ),
+                GenericSignature::NestedMultiSig(multisig) => (
+                    MultiSigMember::MultiSig(multisig.multisig_pk),
+                    NestedCompressedSignature::MultiSig {
+                        sigs: multisig.sigs,
+                        bitmap: multisig.bitmap,
+                    },
+                ),
+                s => (
+                    MultiSigMember::PublicKey(s.to_public_key()?),
+                    NestedCompressedSignature::Signature(s.to_compressed()?),
+                ),
+            };
+            let index = multisig_pk
+                .get_index(&member)
+                .ok_or(IotaError::IncorrectSigner {
+                    error: format!("member does not exist: {}", member.encode_base64()),
+                })?;
+            if bitmap & (1 << index) != 0 {
+                return Err(IotaError::InvalidSignature {
+                    error: "Duplicate member".to_string(),
+                });
+            }
+            bitmap |= 1 << index;
+            indexed_sigs.push((index, sig));
+        }
+        indexed_sigs.sort_by_key(|(index, _)| *index);
+
+        NestedMultiSig {
+            sigs: indexed_sigs.into_iter().map(|(_, sig)| sig).collect(),
+            bitmap,
+            multisig_pk,
+            bytes: OnceCell::new(),
+        }
+        .init_and_validate()
+        .map_err(|_| IotaError::InvalidSignature {
+            error: "Invalid signatures of multisig members".to_string(),
+        })
+    }
+
+    pub fn init_and_validate(&mut self) -> Result<Self, FastCryptoError> {
+        if self.bitmap > MAX_BITMAP_VALUE {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        self.multisig_pk.validate()?;
+        validate_member_signatures(&self.multisig_pk, &self.sigs, self.bitmap)?;
+        Ok(self.to_owned())
+    }
+
+    pub fn get_pk(&self) -> &NestedMultiSigPublicKey {
+        &self.multisig_pk
+    }
+
+    pub fn get_sigs(&self) -> &[NestedCompressedSignature] {
+        &self.sigs
+    }
+
+    pub fn get_bitmap(&self) -> BitmapUnit {
+        self.bitmap
+    }
+
+    pub fn get_indices(&self) -> Result<Vec<u8>, IotaError> {
+        as_indices(self.bitmap)
+    }
+
+    /// Returns the zkLogin signatures of all levels.
+    pub fn get_zklogin_sigs(&self) -> Result<Vec<ZkLoginAuthenticator>, IotaError> {
+        fn collect<'a>(sigs: &'a [NestedCompressedSignature], out: &mut Vec<&'a [u8]>) {
+            for sig in sigs {
+                match sig {
+                    NestedCompressedSignature::Signature(CompressedSignature::ZkLogin(z)) => {
+                        out.push(&z.0)
+                    }
+                    NestedCompressedSignature::Signature(_) => (),
+                    NestedCompressedSignature::MultiSig { sigs, .. } => collect(sigs, out),
+                }
+            }
+        }
+        let mut authenticator_as_bytes = Vec::new();
+        collect(&self.sigs, &mut authenticator_as_bytes);
+        authenticator_as_bytes
+            .iter()
+            .map(|z| {
+                ZkLoginAuthenticator::from_bytes(z).map_err(|_| IotaError::InvalidSignature {
+                    error: "Invalid zklogin authenticator bytes".to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+impl ToFromBytes for NestedMultiSig {
+    fn from_bytes(bytes: &[u8]) -> Result<NestedMultiSig, FastCryptoError> {
+        // The first byte matches the flag of NestedMultiSig.
+        if bytes.first().ok_or(FastCryptoError::InvalidInput)?
+            != &SignatureScheme::NestedMultiSig.flag()
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let mut multisig: NestedMultiSig =
+            bcs::from_bytes(&bytes[1..]).map_err(|_| FastCryptoError::InvalidSignature)?;
+        multisig.init_and_validate()
+    }
+}
+
+impl FromStr for NestedMultiSig {
+    type Err = IotaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = Base64::decode(s).map_err(|_| IotaError::InvalidSignature {
+            error: "Invalid base64 string".to_string(),
+        })?;
+        let sig = NestedMultiSig::from_bytes(&bytes).map_err(|_| IotaError::InvalidSignature {
+            error: "Invalid multisig bytes".to_string(),
+        })?;
+        Ok(sig)
+    }
+}
+
+/// This initialize the underlying bytes representation of NestedMultiSig. It
+/// encodes [struct NestedMultiSig] as the NestedMultiSig flag (0x07) concat
+/// with the bcs bytes of [struct NestedMultiSig] i.e. `flag ||
+/// bcs_bytes(NestedMultiSig)`.
+impl AsRef<[u8]> for NestedMultiSig {
+    fn as_ref(&self) -> &[u8] {
+        self.bytes
+            .get_or_try_init::<_, eyre::Report>(|| {
+                let as_bytes = bcs::to_bytes(self).expect("BCS serialization should not fail");
+                let mut bytes = Vec::with_capacity(1 + as_bytes.len());
+                bytes.push(SignatureScheme::NestedMultiSig.flag());
+                bytes.extend_from_slice(as_bytes.as_slice());
+                Ok(bytes)
+            })
+            .expect("OnceCell invariant violated")
+    }
+}
@@ -31,6 +31,7 @@ use crate::{
     digests::ZKLoginInputsDigest,
     error::{IotaError, IotaResult},
     multisig::MultiSig,
+    nested_multisig::NestedMultiSig,
     passkey_authenticator::PasskeyAuthenticator,
     signature_verification::VerifiedDigestCache,
     zk_login_authenticator::ZkLoginAuthenticator,
@@ -92,6 +93,7 @@ pub enum GenericSignature {
     Signature,
     ZkLoginAuthenticator,
     PasskeyAuthenticator,
+    NestedMultiSig,
 }
 
 impl GenericSignature {
@@ -103,7 +105,10 @@ impl GenericSignature {
     }
 
     pub fn is_upgraded_multisig(&self) -> bool {
-        matches!(self, GenericSignature::MultiSig(_))
+        matches!(
+            self,
+            GenericSignature::MultiSig(_) | GenericSignature::NestedMultiSig(_)
+        )
     }
 
     pub fn verify_authenticator<T>(
@@ -214,7 +219,9 @@ impl GenericSignature {
 /// flag (0x03) concat with the bcs serializedbytes of [struct MultiSigLegacy]
 /// i.e. `flag || bcs_bytes(MultiSigLegacy)`. [struct Multisig] is encodede as
 /// the MultiSig flag (0x03) concat with the bcs serializedbytes of [struct
-/// Multisig] i.e. `flag || bcs_bytes(Multisig)`.
+/// Multisig] i.e. `flag || bcs_bytes(Multisig)`. [struct NestedMultiSig] is
+/// encoded as the NestedMultiSig flag (0x07) concat with the bcs serialized
+/// bytes of [struct NestedMultiSig].
 impl ToFromBytes for GenericSignature {
     fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
         match SignatureScheme::from_flag_byte(
@@ -237,6 +244,9 @@ impl ToFromBytes for GenericSignature {
                     let passkey = PasskeyAuthenticator::from_bytes(bytes)?;
                     Ok(GenericSignature::PasskeyAuthenticator(passkey))
                 }
+                SignatureScheme::NestedMultiSig => Ok(GenericSignature::NestedMultiSig(
+                    NestedMultiSig::from_bytes(bytes)?,
+                )),
                 _ => Err(FastCryptoError::InvalidInput),
             },
             Err(_) => Err(FastCryptoError::InvalidInput),
@@ -252,6 +262,7 @@ impl AsRef<[u8]> for GenericSignature {
             GenericSignature::Signature(s) => s.as_ref(),
             GenericSignature::ZkLoginAuthenticator(s) => s.as_ref(),
             GenericSignature::PasskeyAuthenticator(s) => s.as_ref(),
+            GenericSignature::NestedMultiSig(s) => s.as_ref(),
         }
     }
 }
@@ -0,0 +1,329 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Overrides of on-chain state used to simulate transactions, e.g. in dry-run
+//! and dev-inspect, as if the overridden objects, packages, clock and epoch
+//! were real.
+
+use std::collections::BTreeMap;
+
+use move_binary_format::CompiledModule;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    IOTA_CLOCK_OBJECT_ID,
+    base_types::{EpochId, ObjectID, SequenceNumber, VersionNumber},
+    clock::Clock,
+    error::{IotaError, IotaResult, UserInputError},
+    execution_config_utils::to_binary_config,
+    id::UID,
+    object::{Object, Owner},
+    storage::{
+        BackingPackageStore, BackingStore, ChildObjectResolver, ObjectStore, PackageObject,
+        error::Result as StorageResult,
+    },
+    transaction::InputObjects,
+};
+
+/// A single object or package to use instead of its on-chain counterpart.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ObjectOverride {
+    /// Replaces the object with the given one, including its version, owner
+    /// and contents.
+    Object(Object),
+    /// Replaces the BCS contents of a Move object, keeping its type, owner and
+    /// version.
+    Contents {
+        object_id: ObjectID,
+        contents: Vec<u8>,
+    },
+    /// Uses a past version of the object instead of the latest one.
+    Version {
+        object_id: ObjectID,
+        version: SequenceNumber,
+    },
+    /// Replaces a package with locally compiled modules, as if it had been
+    /// upgraded in place. `dependencies` are the IDs of all transitive
+    /// dependencies of the modules.
+    Package {
+        package_id: ObjectID,
+        modules: Vec<Vec<u8>>,
+        dependencies: Vec<ObjectID>,
+    },
+}
+
+impl ObjectOverride {
+    pub fn object_id(&self) -> ObjectID {
+        match self {
+            Self::Object(object) => object.id(),
+            Self::Contents { object_id, .. } | Self::Version { object_id, .. } => *object_id,
+            Self::Package { package_id, .. } => *package_id,
+        }
+    }
+}
+
+/// Overrides of on-chain state to simulate a transaction against.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateOverrides {
+    pub objects: Vec<ObjectOverride>,
+    /// Timestamp of the `Clock` object seen by the transaction.
+    pub clock_timestamp_ms: Option<u64>,
+    /// Epoch the transaction is executed in.
+    pub epoch: Option<EpochId>,
+}
+
+impl StateOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty() && self.clock_timestamp_ms.is_none() && self.epoch.is_none()
+    }
+
+    /// Resolves the overrides against the given store into the objects that
+    /// replace the on-chain ones.
+    pub fn resolve(
+        self,
+        store: &dyn BackingStore,
+        protocol_config: &iota_protocol_config::ProtocolConfig,
+    ) -> IotaResult<ResolvedStateOverrides> {
+        let mut objects = BTreeMap::new();
+        for object_override in self.objects {
+            let object_id = object_override.object_id();
+            let object = match object_override {
+                ObjectOverride::Object(object) => object,
+                ObjectOverride::Contents { contents, .. } => {
+                    let mut object = latest_object(store, &object_id)?;
+                    let move_object = object
+                        .data
+                        .try_as_move_mut()
+                        .ok_or(UserInputError::MovePackageAsObject { object_id })?;
+                    if contents.get(..ObjectID::LENGTH) != Some(object_id.as_ref()) {
+                        return Err(invalid_override(
+                            object_id,
+                            "contents must start with the object ID",
+                        ));
+                    }
+                    move_object
+                        .update_contents(contents, protocol_config)
+                        .map_err(|e| invalid_override(object_id, e))?;
+                    object
+                }
+                ObjectOverride::Version { version, .. } => store
+                    .get_object_by_key(&object_id, version)?
+                    .ok_or(UserInputError::ObjectNotFound {
+                        object_id,
+                        version: Some(version),
+                    })?,
+                ObjectOverride::Package {
+                    modules,
+                    dependencies,
+                    ..
+                } => resolve_package(store, protocol_config, object_id, modules, dependencies)?,
+            };
+            if object.id() != object_id {
+                return Err(invalid_override(object_id, "object ID mismatch"));
+            }
+            objects.insert(object_id, object);
+        }
+
+        if let Some(timestamp_ms) = self.clock_timestamp_ms {
+            let mut clock = latest_object(store, &IOTA_CLOCK_OBJECT_ID)?;
+            let contents = bcs::to_bytes(&Clock {
+                id: UID::new(IOTA_CLOCK_OBJECT_ID),
+                timestamp_ms,
+            })
+            .expect("clock serialization should not fail");
+            clock
+                .data
+                .try_as_move_mut()
+                .expect("clock is a Move object")
+                .update_contents(contents, protocol_config)
+                .map_err(|e| invalid_override(IOTA_CLOCK_OBJECT_ID, e))?;
+            objects.insert(IOTA_CLOCK_OBJECT_ID, clock);
+        }
+
+        Ok(ResolvedStateOverrides {
+            objects,
+            epoch: self.epoch,
+        })
+    }
+}
+
+fn invalid_override(object_id: ObjectID, error: impl ToString) -> IotaError {
+    UserInputError::InvalidStateOverride {
+        object_id,
+        error: error.to_string(),
+    }
+    .into()
+}
+
+fn latest_object(store: &dyn BackingStore, object_id: &ObjectID) -> IotaResult<Object> {
+    Ok(store
+        .get_object(object_id)?
+        .ok_or(UserInputError::ObjectNotFound {
+            object_id: *object_id,
+            version: None,
+        })?)
+}
+
+fn resolve_package(
+    store: &dyn BackingStore,
+    protocol_config: &iota_protocol_config::ProtocolConfig,
+    package_id: ObjectID,
+    modules: Vec<Vec<u8>>,
+    dependencies: Vec<ObjectID>,
+) -> IotaResult<Object> {
+    let previous =
+        store
+            .get_package_object(&package_id)?
+            .ok_or(UserInputError::ObjectNotFound {
+                object_id: package_id,
+                version: None,
+            })?;
+    if modules.is_empty() {
+        return Err(invalid_override(package_id, "package has no modules"));
+    }
+    let binary_config = to_binary_config(protocol_config);
+    let modules = modules
+        .iter()
+        .map(|bytes| CompiledModule::deserialize_with_config(bytes, &binary_config))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| invalid_override(package_id, e))?;
+    let dependencies = dependencies
+        .iter()
+        .map(|id| {
+            store
+                .get_package_object(id)?
+                .ok_or(UserInputError::DependentPackageNotFound { package_id: *id }.into())
+        })
+        .collect::<IotaResult<Vec<PackageObject>>>()?;
+    let package = previous
+        .move_package()
+        .new_upgraded(
+            package_id,
+            &modules,
+            protocol_config,
+            dependencies.iter().map(|p| p.move_package()),
+        )
+        .map_err(|e| invalid_override(package_id, e))?;
+    Ok(Object::new_from_package(
+        package,
+        previous.object().previous_transaction,
+    ))
+}
+
+/// State overrides resolved into concrete objects.
+#[derive(Debug, Clone, Default)]
+pub struct ResolvedStateOverrides {
+    objects: BTreeMap<ObjectID, Object>,
+    epoch: Option<EpochId>,
+}
+
+impl ResolvedStateOverrides {
+    pub fn objects(&self) -> &BTreeMap<ObjectID, Object> {
+        &self.objects
+    }
+
+    /// The epoch to execute in, falling back to the given current epoch.
+    pub fn epoch_or(&self, current_epoch: EpochId) -> EpochId {
+        self.epoch.unwrap_or(current_epoch)
+    }
+
+    /// Replaces overridden objects among the transaction inputs.
+    pub fn apply_to_inputs(&self, input_objects: &mut InputObjects) {
+        input_objects.replace_objects(&self.objects);
+    }
+
+    /// Wraps a store so that overridden objects shadow the stored ones.
+    pub fn overlay<'a>(&'a self, store: &'a dyn BackingStore) -> StateOverrideStore<'a> {
+        StateOverrideStore {
+            store,
+            objects: &self.objects,
+        }
+    }
+}
+
+/// A store which returns overridden objects instead of the ones of the
+/// underlying store.
+pub struct StateOverrideStore<'a> {
+    store: &'a dyn BackingStore,
+    objects: &'a BTreeMap<ObjectID, Object>,
+}
+
+impl BackingPackageStore for StateOverrideStore<'_> {
+    fn get_package_object(&self, package_id: &ObjectID) -> IotaResult<Option<PackageObject>> {
+        match self.objects.get(package_id) {
+            Some(object) if object.is_package() => Ok(Some(PackageObject::new(object.clone()))),
+            Some(_) => Err(UserInputError::MoveObjectAsPackage {
+                object_id: *package_id,
+            }
+            .into()),
+            None => self.store.get_package_object(package_id),
+        }
+    }
+}
+
+impl ChildObjectResolver for StateOverrideStore<'_> {
+    fn read_child_object(
+        &self,
+        parent: &ObjectID,
+        child: &ObjectID,
+        child_version_upper_bound: SequenceNumber,
+    ) -> IotaResult<Option<Object>> {
+        let Some(object) = self.objects.get(child) else {
+            return self
+                .store
+                .read_child_object(parent, child, child_version_upper_bound);
+        };
+        // An overridden child must belong to the parent, as checked by the base
+        // store for on-chain children
+        if object.owner != Owner::ObjectOwner((*parent).into()) {
+            return Err(IotaError::InvalidChildObjectAccess {
+                object: *child,
+                given_parent: *parent,
+                actual_owner: object.owner,
+            });
+        }
+        Ok(Some(object.clone()))
+    }
+
+    fn get_object_received_at_version(
+        &self,
+        owner: &ObjectID,
+        receiving_object_id: &ObjectID,
+        receive_object_at_version: SequenceNumber,
+        epoch_id: EpochId,
+    ) -> IotaResult<Option<Object>> {
+        match self.objects.get(receiving_object_id) {
+            Some(object) if object.version() == receive_object_at_version => {
+                // as for the base store, an object of another owner is treated as
+                // though it does not exist
+                Ok((object.owner == Owner::AddressOwner((*owner).into())).then(|| object.clone()))
+            }
+            _ => self.store.get_object_received_at_version(
+                owner,
+                receiving_object_id,
+                receive_object_at_version,
+                epoch_id,
+            ),
+        }
+    }
+}
+
+impl ObjectStore for StateOverrideStore<'_> {
+    fn get_object(&self, object_id: &ObjectID) -> StorageResult<Option<Object>> {
+        match self.objects.get(object_id) {
+            Some(object) => Ok(Some(object.clone())),
+            None => self.store.get_object(object_id),
+        }
+    }
+
+    fn get_object_by_key(
+        &self,
+        object_id: &ObjectID,
+        version: VersionNumber,
+    ) -> StorageResult<Option<Object>> {
+        match self.objects.get(object_id) {
+            Some(object) if object.version() == version => Ok(Some(object.clone())),
+            _ => self.store.get_object_by_key(object_id, version),
+        }
+    }
+}
@@ -18,6 +18,10 @@ use rand::distributions::Distribution;
 
 const TRAFFIC_SINK_TIMEOUT_SEC: u64 = 300;
 
+/// The longest half-life of reputation scores and blocklist TTL, which keeps
+/// the expirations of restrictions representable.
+pub const MAX_REPUTATION_DURATION_SECS: u64 = 7 * 24 * 60 * 60;
+
 /// The source that should be used to identify the client's
 /// IP address. To be used to configure cases where a node has
 /// infra running in front of the node that is separate from the
@@ -206,6 +210,90 @@ fn default_sketch_tolerance() -> f64 {
     DEFAULT_SKETCH_TOLERANCE
 }
 
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ReputationConfig {
+    /// Time in secs after which half of a score has decayed
+    #[serde(default = "default_score_half_life_secs")]
+    pub score_half_life_secs: u64,
+    /// Score from which at most one transaction is signed every
+    /// `throttle_interval_ms`, until the score has decayed to half of it
+    #[serde(default = "default_throttle_threshold")]
+    pub throttle_threshold: f64,
+    #[serde(default = "default_throttle_interval_ms")]
+    pub throttle_interval_ms: u64,
+    /// Score from which no transactions are signed for
+    /// `blocklist_ttl_secs`
+    #[serde(default = "default_blocklist_threshold")]
+    pub blocklist_threshold: f64,
+    #[serde(default = "default_blocklist_ttl_secs")]
+    pub blocklist_ttl_secs: u64,
+}
+
+impl Default for ReputationConfig {
+    fn default() -> Self {
+        Self {
+            score_half_life_secs: default_score_half_life_secs(),
+            throttle_threshold: default_throttle_threshold(),
+            throttle_interval_ms: default_throttle_interval_ms(),
+            blocklist_threshold: default_blocklist_threshold(),
+            blocklist_ttl_secs: default_blocklist_ttl_secs(),
+        }
+    }
+}
+
+impl ReputationConfig {
+    /// Checks that the thresholds are positive and that the durations are at
+    /// most `MAX_REPUTATION_DURATION_SECS`, so that restrictions can be
+    /// computed from any score.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.throttle_threshold.is_finite() && self.throttle_threshold > 0.0,
+            "throttle-threshold must be positive, got {}",
+            self.throttle_threshold
+        );
+        anyhow::ensure!(
+            self.blocklist_threshold.is_finite() && self.blocklist_threshold > 0.0,
+            "blocklist-threshold must be positive, got {}",
+            self.blocklist_threshold
+        );
+        anyhow::ensure!(
+            self.score_half_life_secs <= MAX_REPUTATION_DURATION_SECS,
+            "score-half-life-secs must be at most {MAX_REPUTATION_DURATION_SECS}, got {}",
+            self.score_half_life_secs
+        );
+        anyhow::ensure!(
+            self.blocklist_ttl_secs <= MAX_REPUTATION_DURATION_SECS,
+            "blocklist-ttl-secs must be at most {MAX_REPUTATION_DURATION_SECS}, got {}",
+            self.blocklist_ttl_secs
+        );
+        Ok(())
+    }
+}
+
+fn default_score_half_life_secs() -> u64 {
+    60
+}
+
+fn default_throttle_threshold() -> f64 {
+    // a few failures in short succession can happen to well-behaved
+    // clients, e.g. when retrying with stale object versions
+    5.0
+}
+
+fn default_throttle_interval_ms() -> u64 {
+    1_000
+}
+
+fn default_blocklist_threshold() -> f64 {
+    20.0
+}
+
+fn default_blocklist_ttl_secs() -> u64 {
+    300
+}
+
 // Serializable representation of policy types, used in config
 // in order to easily change in tests or to killswitch
 #[derive(Clone, Serialize, Deserialize, Debug, Default)]
@@ -219,6 +307,19 @@ pub enum PolicyType {
     /// `window_size_secs` with granularity of `update_interval_secs`
     FreqThreshold(FreqThresholdConfig),
 
+    /// Keeps a score per transaction sender that increases with every
+    /// transaction of the sender that fails to be signed or executed, e.g.
+    /// because it conflicts with the locks of other transactions on its owned
+    /// objects, and that decays over time. Transactions of senders with a high
+    /// score are throttled, and those of senders with an even higher score are
+    /// blocked for a while. A transaction counts at most once per
+    /// `score_half_life_secs`. Only effective in `reputation_policy_types`
+    SenderReputation(ReputationConfig),
+
+    /// Same as `SenderReputation`, but keeps a score per gas coin. The gas
+    /// coins of sponsored transactions are not scored
+    GasCoinReputation(ReputationConfig),
+
     // Below this point are test policies, and thus should not be used in production
     /// Simple policy that adds connection_ip to blocklist when the same
     /// connection_ip is encountered in tally N times. If used in an error
@@ -244,6 +345,10 @@ pub struct PolicyConfig {
     pub spam_policy_type: PolicyType,
     #[serde(default)]
     pub error_policy_type: PolicyType,
+    /// Policies applied to the senders and gas coins of transactions that
+    /// fail, rather than to client IPs
+    #[serde(default)]
+    pub reputation_policy_types: Vec<PolicyType>,
     #[serde(default = "default_channel_capacity")]
     pub channel_capacity: usize,
     #[serde(default = "default_spam_sample_rate")]
@@ -265,6 +370,7 @@ impl Default for PolicyConfig {
             proxy_blocklist_ttl_sec: 0,
             spam_policy_type: PolicyType::NoOp,
             error_policy_type: PolicyType::NoOp,
+            reputation_policy_types: vec![],
             channel_capacity: 100,
             spam_sample_rate: default_spam_sample_rate(),
             dry_run: default_dry_run(),
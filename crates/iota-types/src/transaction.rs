@@ -2194,6 +2194,15 @@ impl SenderSignedData {
                         });
                     }
                 }
+                GenericSignature::NestedMultiSig(_) => {
+                    if !config.nested_multisig() {
+                        return Err(IotaError::UserInput {
+                            error: UserInputError::Unsupported(
+                                "nested multisig is not enabled on this network".to_string(),
+                            ),
+                        });
+                    }
+                }
                 GenericSignature::Signature(_) | GenericSignature::MultiSig(_) => (),
             }
         }
@@ -3023,6 +3032,22 @@ impl InputObjects {
         self.objects.push(object);
     }
 
+    /// Replaces the read objects with the given ones, keeping owned object
+    /// references in sync with the replacements.
+    pub fn replace_objects(&mut self, replacements: &BTreeMap<ObjectID, Object>) {
+        for read_result in &mut self.objects {
+            let Some(replacement) = replacements.get(&read_result.id()) else {
+                continue;
+            };
+            if let InputObjectKind::ImmOrOwnedMoveObject(object_ref) =
+                &mut read_result.input_object_kind
+            {
+                *object_ref = replacement.compute_object_reference();
+            }
+            read_result.object = ObjectReadResultKind::Object(replacement.clone());
+        }
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &ObjectReadResult> {
         self.objects.iter()
     }
@@ -0,0 +1,198 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{str::FromStr, sync::Arc};
+
+use fastcrypto::traits::{EncodeDecodeBase64, ToFromBytes};
+use shared_crypto::intent::{Intent, IntentMessage, PersonalMessage};
+
+use super::{MultiSigMember, NestedCompressedSignature, NestedMultiSig, NestedMultiSigPublicKey};
+use crate::{
+    base_types::IotaAddress,
+    crypto::{IotaKeyPair, Signature, get_key_pair},
+    multisig::{MultiSig, MultiSigPublicKey},
+    signature::{AuthenticatorTrait, GenericSignature, VerifyParams},
+    signature_verification::VerifiedDigestCache,
+    utils::keys,
+};
+
+fn message() -> IntentMessage<PersonalMessage> {
+    IntentMessage::new(Intent::iota_transaction(), PersonalMessage {
+        message: "Hello".as_bytes().to_vec(),
+    })
+}
+
+fn verify(multisig: &NestedMultiSig, address: IotaAddress) -> Result<(), crate::error::IotaError> {
+    multisig.verify_claims(
+        &message(),
+        address,
+        &VerifyParams::default(),
+        Arc::new(VerifiedDigestCache::new_empty()),
+    )
+}
+
+/// A 2-of-2 policy of a single key and a 2-of-3 board multisig.
+fn treasury() -> (
+    IotaKeyPair,
+    Vec<IotaKeyPair>,
+    MultiSigPublicKey,
+    NestedMultiSigPublicKey,
+) {
+    let cfo = IotaKeyPair::Ed25519(get_key_pair().1);
+    let board = keys();
+    let board_pk = MultiSigPublicKey::new(
+        board.iter().map(|kp| kp.public()).collect(),
+        vec![1, 1, 1],
+        2,
+    )
+    .unwrap();
+    let treasury_pk = NestedMultiSigPublicKey::new(
+        vec![
+            cfo.public().into(),
+            NestedMultiSigPublicKey::from(board_pk.clone()).into(),
+        ],
+        vec![1, 1],
+        2,
+    )
+    .unwrap();
+    (cfo, board, board_pk, treasury_pk)
+}
+
+#[test]
+fn test_nested_multisig_verify() {
+    let (cfo, board, board_pk, treasury_pk) = treasury();
+    let address = IotaAddress::from(&treasury_pk);
+    let sign =
+        |kp: &IotaKeyPair| -> GenericSignature { Signature::new_secure(&message(), kp).into() };
+
+    let board_sig = MultiSig::combine(vec![sign(&board[0]), sign(&board[2])], board_pk.clone())
+        .unwrap()
+        .into();
+    // Signatures can be combined in any order.
+    let multisig =
+        NestedMultiSig::combine(vec![board_sig, sign(&cfo)], treasury_pk.clone()).unwrap();
+    assert_eq!(multisig.get_indices().unwrap(), vec![0, 1]);
+    assert!(verify(&multisig, address).is_ok());
+
+    // The address of a single member is not authorized.
+    assert!(verify(&multisig, IotaAddress::from(&board_pk)).is_err());
+
+    // The weight of the CFO alone does not reach the threshold.
+    let multisig = NestedMultiSig::combine(vec![sign(&cfo)], treasury_pk.clone()).unwrap();
+    assert!(verify(&multisig, address).is_err());
+
+    // A single board member does not reach the threshold of the board.
+    let board_sig = MultiSig::combine(vec![sign(&board[1])], board_pk)
+        .unwrap()
+        .into();
+    let multisig = NestedMultiSig::combine(vec![sign(&cfo), board_sig], treasury_pk).unwrap();
+    assert!(
+        verify(&multisig, address)
+            .unwrap_err()
+            .to_string()
+            .contains("Insufficient weight=1 threshold=2")
+    );
+}
+
+#[test]
+fn test_nested_multisig_address() {
+    let (cfo, _board, board_pk, treasury_pk) = treasury();
+
+    // Without multisig members, the address is the one of the flat multisig.
+    let flat_pk = NestedMultiSigPublicKey::from(board_pk.clone());
+    assert_eq!(flat_pk.depth(), 1);
+    assert_eq!(IotaAddress::from(&flat_pk), IotaAddress::from(&board_pk));
+
+    assert_eq!(treasury_pk.depth(), 2);
+    assert_eq!(treasury_pk.num_public_keys(), 4);
+    assert_ne!(IotaAddress::from(&treasury_pk), IotaAddress::from(&flat_pk));
+
+    // The address commits to the weights of the members.
+    let other_pk =
+        NestedMultiSigPublicKey::new(vec![cfo.public().into(), flat_pk.into()], vec![1, 2], 2)
+            .unwrap();
+    assert_ne!(
+        IotaAddress::from(&treasury_pk),
+        IotaAddress::from(&other_pk)
+    );
+}
+
+#[test]
+fn test_nested_multisig_public_key_validation() {
+    let (cfo, _board, board_pk, treasury_pk) = treasury();
+
+    // Duplicate members and insufficient weights are rejected.
+    let member: MultiSigMember = cfo.public().into();
+    assert!(NestedMultiSigPublicKey::new(vec![member.clone(), member], vec![1, 1], 1).is_err());
+    assert!(NestedMultiSigPublicKey::new(vec![cfo.public().into()], vec![1], 2).is_err());
+
+    // Up to three levels of multisigs are accepted.
+    let level_3 =
+        NestedMultiSigPublicKey::new(vec![treasury_pk.into(), cfo.public().into()], vec![1, 1], 1)
+            .unwrap();
+    assert_eq!(level_3.depth(), 3);
+    assert!(
+        NestedMultiSigPublicKey::new(vec![level_3.into(), cfo.public().into()], vec![1, 1], 1)
+            .is_err()
+    );
+
+    // Members are encoded so that they can be parsed from the command line.
+    let member = MultiSigMember::MultiSig(board_pk.into());
+    assert_eq!(
+        MultiSigMember::from_str(&member.encode_base64()).unwrap(),
+        member
+    );
+    let member = MultiSigMember::PublicKey(cfo.public());
+    assert_eq!(
+        MultiSigMember::from_str(&member.encode_base64()).unwrap(),
+        member
+    );
+}
+
+#[test]
+fn test_nested_multisig_serde_roundtrip() {
+    let (cfo, board, board_pk, treasury_pk) = treasury();
+    let sign =
+        |kp: &IotaKeyPair| -> GenericSignature { Signature::new_secure(&message(), kp).into() };
+    let board_sig = MultiSig::combine(vec![sign(&board[0]), sign(&board[1])], board_pk)
+        .unwrap()
+        .into();
+    let multisig =
+        NestedMultiSig::combine(vec![sign(&cfo), board_sig], treasury_pk.clone()).unwrap();
+
+    let generic_sig = GenericSignature::NestedMultiSig(multisig.clone());
+    let generic_sig_bytes = generic_sig.as_bytes();
+    assert_eq!(generic_sig_bytes.first().unwrap(), &0x07);
+    assert_eq!(
+        GenericSignature::from_bytes(generic_sig_bytes).unwrap(),
+        generic_sig
+    );
+    assert_eq!(
+        IotaAddress::try_from(&generic_sig).unwrap(),
+        IotaAddress::from(&treasury_pk)
+    );
+
+    // A nested multisig whose signatures do not match the kind of the members
+    // cannot be deserialized.
+    let mut sigs = multisig.get_sigs().to_vec();
+    sigs.swap(0, 1);
+    let malformed = GenericSignature::NestedMultiSig(NestedMultiSig::insecure_new(
+        sigs,
+        multisig.get_bitmap(),
+        treasury_pk.clone(),
+    ));
+    assert!(GenericSignature::from_bytes(malformed.as_bytes()).is_err());
+
+    // Neither can a nested multisig with more signatures than bits in the bitmap.
+    let mut sigs = multisig.get_sigs().to_vec();
+    let NestedCompressedSignature::Signature(cfo_sig) = sigs[0].clone() else {
+        panic!("expected the signature of the CFO first");
+    };
+    sigs.push(NestedCompressedSignature::Signature(cfo_sig));
+    let malformed = GenericSignature::NestedMultiSig(NestedMultiSig::insecure_new(
+        sigs,
+        multisig.get_bitmap(),
+        treasury_pk,
+    ));
+    assert!(GenericSignature::from_bytes(malformed.as_bytes()).is_err());
+}
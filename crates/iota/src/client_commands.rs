@@ -20,13 +20,15 @@ use fastcrypto::{
     encoding::{Base64, Encoding},
     traits::ToFromBytes,
 };
+use futures::StreamExt;
 use iota_json::IotaJsonValue;
 use iota_json_rpc_types::{
-    Coin, DryRunTransactionBlockResponse, DynamicFieldPage, IotaCoinMetadata, IotaData,
-    IotaExecutionStatus, IotaObjectData, IotaObjectDataOptions, IotaObjectResponse,
-    IotaObjectResponseQuery, IotaParsedData, IotaProtocolConfigValue, IotaRawData,
+    Coin, DryRunTransactionBlockResponse, DynamicFieldPage, EventFilter, EventPage, Filter,
+    IotaCoinMetadata, IotaData, IotaEvent, IotaExecutionStatus, IotaObjectData,
+    IotaObjectDataOptions, IotaObjectResponse, IotaObjectResponseQuery, IotaParsedData,
+    IotaProgrammableMoveCall, IotaProtocolConfigValue, IotaRawData, IotaTransactionBlockDataAPI,
     IotaTransactionBlockEffects, IotaTransactionBlockEffectsAPI, IotaTransactionBlockResponse,
-    IotaTransactionBlockResponseOptions,
+    IotaTransactionBlockResponseOptions, IotaTransactionBlockResponseQuery, TransactionFilter,
 };
 use iota_keys::keystore::AccountKeystore;
 use iota_move::manage_package::resolve_lock_file_path;
@@ -46,19 +48,24 @@ use iota_sdk::{
     wallet_context::WalletContext,
 };
 use iota_source_validation::{BytecodeSourceVerifier, ValidationMode};
+use iota_transaction_builder::{
+    TransactionBuilder,
+    stardust::{StardustClaimStatus, StardustOutput},
+};
 use iota_types::{
     base_types::{IotaAddress, ObjectID, SequenceNumber},
     crypto::{EmptySignInfo, SignatureScheme},
     digests::TransactionDigest,
     dynamic_field::DynamicFieldInfo,
     error::IotaError,
+    event::EventID,
     gas::GasCostSummary,
     gas_coin::GasCoin,
     message_envelope::Envelope,
     metrics::BytecodeVerifierMetrics,
     move_package::UpgradeCap,
     object::Owner,
-    parse_iota_type_tag,
+    parse_iota_struct_tag, parse_iota_type_tag,
     quorum_driver_types::ExecuteTransactionRequestType,
     signature::GenericSignature,
     transaction::{
@@ -68,7 +75,11 @@ use iota_types::{
 use json_to_table::json_to_table;
 use move_binary_format::CompiledModule;
 use move_bytecode_verifier_meter::Scope;
-use move_core_types::{account_address::AccountAddress, language_storage::TypeTag};
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{StructTag, TypeTag},
+};
 use move_package::BuildConfig as MoveBuildConfig;
 use prometheus::Registry;
 use reqwest::StatusCode;
@@ -103,6 +114,10 @@ mod profiler_tests;
 /// Only to be used within CLI
 pub const GAS_SAFE_OVERHEAD: u64 = 1000;
 
+/// The maximum number of pages of events queried from the node to fill a page
+/// of events matching all filters.
+const MAX_SCANNED_EVENT_PAGES: usize = 50;
+
 #[derive(Parser)]
 #[clap(rename_all = "kebab-case")]
 pub enum IotaClientCommands {
@@ -187,6 +202,32 @@ pub enum IotaClientCommands {
     /// List all IOTA environments
     Envs,
 
+    /// List the events emitted by transactions, newest first. When several
+    /// filters are given, only the events matching all of them are listed,
+    /// and a listing that scans many events without filling up stops early
+    /// with a cursor to continue from.
+    #[clap(name = "events")]
+    Events {
+        #[clap(flatten)]
+        filter: EventFilterArgs,
+        /// Continue the listing after this event, given as
+        /// `<TX_DIGEST>:<EVENT_SEQ>` as printed as the next cursor of a
+        /// previous listing.
+        #[clap(long, value_parser = parse_event_id, conflicts_with = "follow")]
+        cursor: Option<EventID>,
+        /// Maximum number of events to list.
+        #[clap(long, default_value_t = 20)]
+        limit: usize,
+        /// List the oldest events first.
+        #[clap(long, conflicts_with = "follow")]
+        ascending: bool,
+        /// Instead of listing past events, print the events matching the
+        /// filters as they are emitted. This needs a WebSocket URL for the
+        /// active environment.
+        #[clap(long)]
+        follow: bool,
+    },
+
     /// Execute a Signed Transaction. This is useful when the user prefers to
     /// sign elsewhere and use this command to execute.
     ExecuteSignedTx {
@@ -232,6 +273,53 @@ pub enum IotaClientCommands {
         address: Option<KeyIdentity>,
     },
 
+    /// List the transactions sent or received by an address, newest first.
+    /// A transaction is received by an address if it transferred objects to
+    /// it.
+    #[clap(name = "history")]
+    History {
+        /// Address (or its alias) whose transactions are listed. If not
+        /// provided, the active address is used.
+        #[clap(name = "address")]
+        #[arg(value_parser)]
+        address: Option<KeyIdentity>,
+        /// Whether to list the transactions sent, received or both.
+        #[clap(long, value_enum, default_value_t = HistoryDirection::Both)]
+        direction: HistoryDirection,
+        /// Only list the transactions calling a Move function, given as
+        /// `<PACKAGE>[::<MODULE>[::<FUNCTION>]]` to match any function of a
+        /// package or module.
+        #[clap(long)]
+        function: Option<MoveFunctionFilter>,
+        /// Only list the transactions included in this checkpoint or later.
+        #[clap(long)]
+        from_checkpoint: Option<u64>,
+        /// Only list the transactions included in this checkpoint or earlier.
+        #[clap(long)]
+        to_checkpoint: Option<u64>,
+        /// Only list the transactions executed at or after this time, in
+        /// milliseconds since the Unix epoch.
+        #[clap(long)]
+        start_time: Option<u64>,
+        /// Only list the transactions executed before this time, in
+        /// milliseconds since the Unix epoch.
+        #[clap(long)]
+        end_time: Option<u64>,
+        /// Maximum number of transactions to list.
+        #[clap(long, default_value_t = 20)]
+        limit: usize,
+        /// List the oldest transactions first.
+        #[clap(long)]
+        ascending: bool,
+    },
+
+    /// Create kiosks, and place, list and purchase items in them.
+    #[clap(name = "kiosk")]
+    Kiosk {
+        #[clap(subcommand)]
+        cmd: KioskCommand,
+    },
+
     /// Merge two coin objects into one coin
     MergeCoin {
         /// The address of the coin to merge into.
@@ -407,6 +495,13 @@ pub enum IotaClientCommands {
         opts: OptsWithGas,
     },
 
+    /// List and claim the Stardust outputs migrated at genesis.
+    #[clap(name = "stardust")]
+    Stardust {
+        #[clap(subcommand)]
+        cmd: StardustCommand,
+    },
+
     /// Switch active address and network(e.g., devnet, local rpc server).
     #[clap(name = "switch")]
     Switch {
@@ -548,6 +643,16 @@ pub enum IotaClientCommands {
         address_override: Option<ObjectID>,
     },
 
+    /// Manage the watch-only addresses, which are known by an alias without
+    /// having their key in the keystore. They can be used wherever an address
+    /// is expected, and transactions sent by them can be serialized with
+    /// `--serialize-unsigned-transaction` to be signed elsewhere.
+    #[clap(name = "watch-address")]
+    WatchAddress {
+        #[clap(subcommand)]
+        cmd: WatchAddressCommand,
+    },
+
     /// Profile the gas usage of a transaction. Unless an output filepath is not
     /// specified, outputs a file
     /// `gas_profile_{tx_digest}_{unix_timestamp}.json` which can be opened in a
@@ -629,6 +734,155 @@ pub enum IotaClientCommands {
     },
 }
 
+#[derive(Subcommand)]
+pub enum WatchAddressCommand {
+    /// Watch an address, e.g. a multisig address or an address whose key is
+    /// kept offline.
+    #[clap(name = "add")]
+    Add {
+        /// The address to watch
+        address: IotaAddress,
+        /// The alias of the address, which must not be used by another address
+        #[clap(long)]
+        alias: String,
+    },
+
+    /// Stop watching an address.
+    #[clap(name = "remove")]
+    Remove {
+        /// The alias of the address
+        alias: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum KioskCommand {
+    /// Create a shared kiosk, and send its `KioskOwnerCap` to the active
+    /// address.
+    #[clap(name = "create")]
+    Create {
+        #[clap(flatten)]
+        opts: OptsWithGas,
+    },
+
+    /// Place an item owned by the active address in its kiosk, and list it
+    /// if a price is given.
+    #[clap(name = "place")]
+    Place {
+        /// ID of the kiosk
+        #[clap(long)]
+        kiosk: ObjectID,
+        /// ID of the item to place
+        #[clap(long)]
+        item: ObjectID,
+        /// Price (in NANOS) to list the item for
+        #[clap(long)]
+        price: Option<u64>,
+        #[clap(flatten)]
+        opts: OptsWithGas,
+    },
+
+    /// List an item placed in a kiosk of the active address for sale.
+    #[clap(name = "list")]
+    List {
+        /// ID of the kiosk
+        #[clap(long)]
+        kiosk: ObjectID,
+        /// ID of the item to list
+        #[clap(long)]
+        item: ObjectID,
+        /// Price (in NANOS) to list the item for
+        #[clap(long)]
+        price: u64,
+        #[clap(flatten)]
+        opts: OptsWithGas,
+    },
+
+    /// Remove the listing of an item in a kiosk of the active address. The
+    /// item stays in the kiosk.
+    #[clap(name = "delist")]
+    Delist {
+        /// ID of the kiosk
+        #[clap(long)]
+        kiosk: ObjectID,
+        /// ID of the listed item
+        #[clap(long)]
+        item: ObjectID,
+        #[clap(flatten)]
+        opts: OptsWithGas,
+    },
+
+    /// Purchase a listed item for the active address, paying its price from
+    /// the gas coin. Only transfer policies without rules are supported.
+    #[clap(name = "purchase")]
+    Purchase {
+        /// ID of the kiosk
+        #[clap(long)]
+        kiosk: ObjectID,
+        /// ID of the listed item
+        #[clap(long)]
+        item: ObjectID,
+        /// ID of the `TransferPolicy` confirming the transfer of the item
+        #[clap(long)]
+        policy: ObjectID,
+        #[clap(flatten)]
+        opts: OptsWithGas,
+    },
+
+    /// Withdraw the profits of a kiosk of the active address.
+    #[clap(name = "withdraw")]
+    Withdraw {
+        /// ID of the kiosk
+        #[clap(long)]
+        kiosk: ObjectID,
+        /// Amount (in NANOS) to withdraw. If not provided, all profits are
+        /// withdrawn.
+        #[clap(long)]
+        amount: Option<u64>,
+        #[clap(flatten)]
+        opts: OptsWithGas,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum StardustCommand {
+    /// List the Stardust outputs owned by the address and whether they can be
+    /// claimed now, later, or only by returning their storage deposit.
+    /// Outputs with an expiration are shared objects, which can only be
+    /// looked up by address through GraphQL, so they are not listed unless
+    /// `--graphql` is set.
+    #[clap(name = "list")]
+    List {
+        /// Address (or its alias) owning the outputs
+        #[clap(name = "owner_address")]
+        #[arg(value_parser)]
+        address: Option<KeyIdentity>,
+        /// The URL of a GraphQL service to look up the outputs from, which
+        /// also finds the outputs with an expiration naming the address.
+        #[clap(long)]
+        graphql: Option<String>,
+    },
+
+    /// Claim the assets of Stardust outputs for the active address. The
+    /// storage deposits are returned, native tokens are sent as `Coin<T>`
+    /// objects and NFTs and aliases are transferred to the active address.
+    #[clap(name = "claim")]
+    Claim {
+        /// IDs of the outputs to claim. If not provided, all outputs owned by
+        /// the active address which can be claimed now are claimed. The
+        /// shared outputs with an expiration are only included if
+        /// `--graphql` is set.
+        #[clap(long, num_args(1..))]
+        object_ids: Vec<ObjectID>,
+        /// The URL of a GraphQL service to look up the outputs to claim from
+        /// if no IDs are given.
+        #[clap(long, conflicts_with = "object_ids")]
+        graphql: Option<String>,
+        #[clap(flatten)]
+        opts: OptsWithGas,
+    },
+}
+
 /// Global options for most transaction execution related commands
 #[derive(Args, Debug)]
 pub struct Opts {
@@ -750,6 +1004,318 @@ impl OptsWithGas {
     }
 }
 
+/// Filters of the events listed by `iota client events`.
+#[derive(Args, Debug)]
+pub struct EventFilterArgs {
+    /// Only events emitted by transactions sent by this address (or alias).
+    #[clap(long)]
+    #[arg(value_parser)]
+    pub sender: Option<KeyIdentity>,
+    /// Only events emitted by calls to a Move module, given as
+    /// `<PACKAGE>::<MODULE>`.
+    #[clap(long, value_parser = parse_move_module)]
+    pub module: Option<(ObjectID, Identifier)>,
+    /// Only events of this type, for example
+    /// `0x3::validator::StakingRequestEvent`.
+    #[clap(long, value_parser = parse_iota_struct_tag)]
+    pub event_type: Option<StructTag>,
+    /// Only events emitted at or after this time, in milliseconds since the
+    /// Unix epoch.
+    #[clap(long)]
+    pub start_time: Option<u64>,
+    /// Only events emitted before this time, in milliseconds since the Unix
+    /// epoch.
+    #[clap(long)]
+    pub end_time: Option<u64>,
+}
+
+impl EventFilterArgs {
+    /// The filters given, from the most to the least selective one.
+    fn into_filters(self, context: &mut WalletContext) -> Result<Vec<EventFilter>, anyhow::Error> {
+        let mut filters = Vec::new();
+        if let Some(event_type) = self.event_type {
+            filters.push(EventFilter::MoveEventType(event_type));
+        }
+        if let Some((package, module)) = self.module {
+            filters.push(EventFilter::MoveModule { package, module });
+        }
+        if let Some(sender) = self.sender {
+            filters.push(EventFilter::Sender(get_identity_address(
+                Some(sender),
+                context,
+            )?));
+        }
+        if self.start_time.is_some() || self.end_time.is_some() {
+            filters.push(EventFilter::TimeRange {
+                start_time: self.start_time.unwrap_or_default(),
+                end_time: self.end_time.unwrap_or(u64::MAX),
+            });
+        }
+        Ok(filters)
+    }
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum HistoryDirection {
+    Sent,
+    Received,
+    Both,
+}
+
+/// A Move function, or any function of a package or module, called by a
+/// transaction.
+#[derive(Clone, Debug)]
+pub struct MoveFunctionFilter {
+    package: ObjectID,
+    module: Option<String>,
+    function: Option<String>,
+}
+
+impl FromStr for MoveFunctionFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split("::");
+        let package = ObjectID::from_str(parts.next().unwrap_or_default())?;
+        let module = parts.next().map(str::to_owned);
+        let function = parts.next().map(str::to_owned);
+        ensure!(
+            parts.next().is_none(),
+            "Expected a Move function as <PACKAGE>[::<MODULE>[::<FUNCTION>]], got {s}"
+        );
+        Ok(Self {
+            package,
+            module,
+            function,
+        })
+    }
+}
+
+impl MoveFunctionFilter {
+    fn matches(&self, call: &IotaProgrammableMoveCall) -> bool {
+        call.package == self.package
+            && self
+                .module
+                .as_ref()
+                .is_none_or(|module| *module == call.module)
+            && self
+                .function
+                .as_ref()
+                .is_none_or(|function| *function == call.function)
+    }
+}
+
+/// Filters applied to the transactions sent or received by an address listed
+/// by `iota client history`.
+struct HistoryFilter {
+    function: Option<MoveFunctionFilter>,
+    from_checkpoint: Option<u64>,
+    to_checkpoint: Option<u64>,
+    start_time: Option<u64>,
+    end_time: Option<u64>,
+}
+
+impl HistoryFilter {
+    fn matches(&self, transaction: &IotaTransactionBlockResponse) -> bool {
+        let in_range = |value: Option<u64>, from: Option<u64>, to_exclusive: Option<u64>| {
+            (from.is_none() && to_exclusive.is_none())
+                || value.is_some_and(|value| {
+                    from.is_none_or(|from| value >= from)
+                        && to_exclusive.is_none_or(|to| value < to)
+                })
+        };
+        let calls_function = || {
+            self.function.as_ref().is_none_or(|function| {
+                transaction.transaction.as_ref().is_some_and(|tx| {
+                    tx.data
+                        .move_calls()
+                        .into_iter()
+                        .any(|call| function.matches(call))
+                })
+            })
+        };
+        in_range(
+            transaction.checkpoint,
+            self.from_checkpoint,
+            self.to_checkpoint.map(|to| to.saturating_add(1)),
+        ) && in_range(transaction.timestamp_ms, self.start_time, self.end_time)
+            && calls_function()
+    }
+
+    /// Whether the transaction, and so all the transactions listed after it,
+    /// is past the checkpoint and time ranges.
+    fn is_past(&self, transaction: &IotaTransactionBlockResponse, descending: bool) -> bool {
+        let past = |value: Option<u64>, from: Option<u64>, to_exclusive: Option<u64>| {
+            value.is_some_and(|value| {
+                if descending {
+                    from.is_some_and(|from| value < from)
+                } else {
+                    to_exclusive.is_some_and(|to| value >= to)
+                }
+            })
+        };
+        past(
+            transaction.checkpoint,
+            self.from_checkpoint,
+            self.to_checkpoint.map(|to| to.saturating_add(1)),
+        ) || past(transaction.timestamp_ms, self.start_time, self.end_time)
+    }
+}
+
+/// Query the events matching all the filters, which are sorted from the most to
+/// the least selective one. Nodes only support querying a single filter, so
+/// the first filter is queried and the others are applied to its results.
+/// The query stops at the first event past the time range, if any, and after
+/// `MAX_SCANNED_EVENT_PAGES` pages, returning a cursor to continue from.
+async fn query_events(
+    client: &IotaClient,
+    mut filters: Vec<EventFilter>,
+    mut cursor: Option<EventID>,
+    limit: usize,
+    descending: bool,
+) -> Result<EventPage, anyhow::Error> {
+    let query = if filters.is_empty() {
+        EventFilter::All(vec![])
+    } else {
+        filters.remove(0)
+    };
+    let time_range = filters.iter().find_map(|filter| match filter {
+        EventFilter::TimeRange {
+            start_time,
+            end_time,
+        } => Some((*start_time, *end_time)),
+        _ => None,
+    });
+    let is_past = |event: &IotaEvent| {
+        time_range
+            .zip(event.timestamp_ms)
+            .is_some_and(|((start_time, end_time), timestamp)| {
+                if descending {
+                    timestamp < start_time
+                } else {
+                    timestamp >= end_time
+                }
+            })
+    };
+    let others = EventFilter::All(filters);
+    let mut data = Vec::new();
+    for _ in 0..MAX_SCANNED_EVENT_PAGES {
+        let page = client
+            .event_api()
+            .query_events(query.clone(), cursor, limit, descending)
+            .await?;
+        for event in page.data {
+            if is_past(&event) {
+                return Ok(EventPage {
+                    data,
+                    next_cursor: cursor,
+                    has_next_page: false,
+                });
+            }
+            cursor = Some(event.id);
+            if others.matches(&event) {
+                data.push(event);
+                if data.len() == limit {
+                    return Ok(EventPage {
+                        data,
+                        next_cursor: cursor,
+                        has_next_page: true,
+                    });
+                }
+            }
+        }
+        if !page.has_next_page {
+            return Ok(EventPage {
+                data,
+                next_cursor: cursor,
+                has_next_page: false,
+            });
+        }
+        cursor = page.next_cursor;
+    }
+    Ok(EventPage {
+        data,
+        next_cursor: cursor,
+        has_next_page: true,
+    })
+}
+
+/// Query the transactions matching the filter of the node and `filter`, up to
+/// `limit` of them.
+async fn query_history(
+    client: &IotaClient,
+    query: TransactionFilter,
+    filter: &HistoryFilter,
+    limit: usize,
+    descending: bool,
+) -> Result<Vec<IotaTransactionBlockResponse>, anyhow::Error> {
+    let query = IotaTransactionBlockResponseQuery::new(
+        Some(query),
+        Some(
+            IotaTransactionBlockResponseOptions::new()
+                .with_input()
+                .with_effects(),
+        ),
+    );
+    let mut transactions = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = client
+            .read_api()
+            .query_transaction_blocks(query.clone(), cursor, limit, descending)
+            .await?;
+        for transaction in page.data {
+            if filter.is_past(&transaction, descending) {
+                return Ok(transactions);
+            }
+            if filter.matches(&transaction) {
+                transactions.push(transaction);
+                if transactions.len() == limit {
+                    return Ok(transactions);
+                }
+            }
+        }
+        if !page.has_next_page {
+            return Ok(transactions);
+        }
+        cursor = page.next_cursor;
+    }
+}
+
+/// Print the events matching the filters as they are emitted, until the
+/// subscription is closed by the node.
+pub async fn follow_events(
+    filter: EventFilterArgs,
+    context: &mut WalletContext,
+    pretty: bool,
+) -> Result<(), anyhow::Error> {
+    let filter = EventFilter::All(filter.into_filters(context)?);
+    let client = context.get_client().await?;
+    let mut events = client.event_api().subscribe_event(filter).await.context(
+        "Cannot subscribe to events, make sure the active environment has a WebSocket URL",
+    )?;
+    while let Some(event) = events.next().await {
+        let event = event?;
+        if pretty {
+            println!("{event}");
+        } else {
+            println!("{}", serde_json::to_string(&event)?);
+        }
+    }
+    Ok(())
+}
+
+fn parse_event_id(s: &str) -> Result<EventID, anyhow::Error> {
+    EventID::try_from(s.to_owned())
+}
+
+fn parse_move_module(s: &str) -> Result<(ObjectID, Identifier), anyhow::Error> {
+    let (package, module) = s
+        .split_once("::")
+        .ok_or_else(|| anyhow!("Expected a Move module as <PACKAGE>::<MODULE>, got {s}"))?;
+    Ok((ObjectID::from_str(package)?, Identifier::new(module)?))
+}
+
 #[derive(Clone, Debug, EnumString, Hash, Eq, PartialEq)]
 #[strum(serialize_all = "snake_case")]
 pub enum EmitOption {
@@ -862,13 +1428,21 @@ impl IotaClientCommands {
                     .into_iter()
                     .map(|(address, alias)| (alias.alias.to_string(), *address))
                     .collect();
+                let mut watch_only_addresses: Vec<(String, IotaAddress)> = context
+                    .config()
+                    .watch_only_addresses()
+                    .iter()
+                    .map(|watched| (watched.alias().clone(), *watched.address()))
+                    .collect();
                 if sort_by_alias {
                     addresses.sort();
+                    watch_only_addresses.sort();
                 }
 
                 let output = AddressesOutput {
                     active_address,
                     addresses,
+                    watch_only_addresses,
                 };
                 IotaClientCommandResult::Addresses(output)
             }
@@ -1525,6 +2099,84 @@ impl IotaClientCommands {
                     recovery_phrase: phrase,
                 })
             }
+            IotaClientCommands::Events { follow: true, .. } => {
+                bail!("Following events is only supported by `iota client events --follow`")
+            }
+            IotaClientCommands::Events {
+                filter,
+                cursor,
+                limit,
+                ascending,
+                follow: false,
+            } => {
+                ensure!(limit > 0, "The limit must be greater than 0");
+                let filters = filter.into_filters(context)?;
+                let client = context.get_client().await?;
+                let page = query_events(&client, filters, cursor, limit, !ascending).await?;
+                IotaClientCommandResult::Events(page)
+            }
+            IotaClientCommands::History {
+                address,
+                direction,
+                function,
+                from_checkpoint,
+                to_checkpoint,
+                start_time,
+                end_time,
+                limit,
+                ascending,
+            } => {
+                ensure!(limit > 0, "The limit must be greater than 0");
+                let address = get_identity_address(address, context)?;
+                let client = context.get_client().await?;
+                let filter = HistoryFilter {
+                    function,
+                    from_checkpoint,
+                    to_checkpoint,
+                    start_time,
+                    end_time,
+                };
+                let descending = !ascending;
+                let transactions = match direction {
+                    HistoryDirection::Sent => {
+                        let query = TransactionFilter::FromAddress(address);
+                        query_history(&client, query, &filter, limit, descending).await?
+                    }
+                    HistoryDirection::Received => {
+                        let query = TransactionFilter::ToAddress(address);
+                        query_history(&client, query, &filter, limit, descending).await?
+                    }
+                    HistoryDirection::Both => {
+                        // Not every RPC supports `FromOrToAddress`, so the sent and received
+                        // transactions are queried separately and merged.
+                        let sent = TransactionFilter::FromAddress(address);
+                        let received = TransactionFilter::ToAddress(address);
+                        let mut seen = HashSet::new();
+                        let mut transactions: Vec<_> =
+                            query_history(&client, sent, &filter, limit, descending)
+                                .await?
+                                .into_iter()
+                                .chain(
+                                    query_history(&client, received, &filter, limit, descending)
+                                        .await?,
+                                )
+                                .filter(|transaction| seen.insert(transaction.digest))
+                                .collect();
+                        transactions.sort_by_key(|transaction| {
+                            (transaction.checkpoint, transaction.timestamp_ms)
+                        });
+                        if descending {
+                            transactions.reverse();
+                        }
+                        transactions.truncate(limit);
+                        transactions
+                    }
+                };
+                IotaClientCommandResult::TransactionHistory(TransactionHistoryOutput {
+                    address,
+                    transactions,
+                })
+            }
             IotaClientCommands::Gas { address } => {
                 let address = get_identity_address(address, context)?;
                 let coins = context
@@ -1615,6 +2267,22 @@ impl IotaClientCommands {
                 )
                 .await?
             }
+            IotaClientCommands::Kiosk { cmd } => cmd.execute(context).await?,
+            IotaClientCommands::Stardust { cmd } => cmd.execute(context).await?,
+            IotaClientCommands::WatchAddress { cmd } => {
+                match cmd {
+                    WatchAddressCommand::Add { address, alias } => {
+                        context
+                            .config_mut()
+                            .add_watch_only_address(alias, address)?;
+                    }
+                    WatchAddressCommand::Remove { alias } => {
+                        context.config_mut().remove_watch_only_address(&alias)?;
+                    }
+                }
+                context.config().save()?;
+                IotaClientCommandResult::NoOutput
+            }
             IotaClientCommands::Switch { address, env } => {
                 let mut addr = None;
 
@@ -1626,7 +2294,9 @@ impl IotaClientCommands {
 
                 if let Some(address) = address {
                     let address = get_identity_address(Some(address), context)?;
-                    if !context.config().keystore().addresses().contains(&address) {
+                    if !context.config().keystore().addresses().contains(&address)
+                        && !context.config().is_watch_only(&address)
+                    {
                         return Err(anyhow!("Address {} not managed by wallet", address));
                     }
                     context.config_mut().set_active_address(address);
@@ -1817,31 +2487,7 @@ pub(crate) async fn upgrade_package(
     )
     .await?;
 
-    let package_id = package_id.map_err(|e| match e {
-        PublishedAtError::NotPresent => {
-            anyhow!("No 'published-at' field in Move.toml or 'published-id' in Move.lock for package to be upgraded.")
-        }
-        PublishedAtError::Invalid(v) => anyhow!(
-            "Invalid 'published-at' field in Move.toml or 'published-id' in Move.lock of package to be upgraded. \
-                         Expected an on-chain address, but found: {v:?}"
-        ),
-        PublishedAtError::Conflict {
-            id_lock,
-            id_manifest,
-        } => {
-            let env_alias = format!("(currently {})", env_alias.unwrap_or_default());
-            anyhow!(
-                "Conflicting published package address: `Move.toml` contains published-at address \
-                 {id_manifest} but `Move.lock` file contains published-at address {id_lock}. \
-                 You may want to:
-
-                 - delete the published-at address in the `Move.toml` if the `Move.lock` address is correct; OR
-                 - update the `Move.lock` address using the `iota manage-package` command to be the same as the `Move.toml`; OR
-                 - check that your `iota active-env` {env_alias} corresponds to the chain on which the package is published (i.e., devnet, testnet, mainnet); OR
-                 - contact the maintainer if this package is a dependency and request resolving the conflict."
-            )
-        }
-    })?;
+    let package_id = package_id.map_err(|e| published_at_error(e, env_alias))?;
 
     let resp = read_api
         .get_object_with_options(
@@ -1877,6 +2523,41 @@ pub(crate) async fn upgrade_package(
     ))
 }
 
+/// Explain why the `published-at` address of a package to be upgraded could
+/// not be determined.
+pub(crate) fn published_at_error(
+    error: PublishedAtError,
+    env_alias: Option<String>,
+) -> anyhow::Error {
+    match error {
+        PublishedAtError::NotPresent => {
+            anyhow!(
+                "No 'published-at' field in Move.toml or 'published-id' in Move.lock for package to be upgraded."
+            )
+        }
+        PublishedAtError::Invalid(v) => anyhow!(
+            "Invalid 'published-at' field in Move.toml or 'published-id' in Move.lock of package to be upgraded. \
+                         Expected an on-chain address, but found: {v:?}"
+        ),
+        PublishedAtError::Conflict {
+            id_lock,
+            id_manifest,
+        } => {
+            let env_alias = format!("(currently {})", env_alias.unwrap_or_default());
+            anyhow!(
+                "Conflicting published package address: `Move.toml` contains published-at address \
+                 {id_manifest} but `Move.lock` file contains published-at address {id_lock}. \
+                 You may want to:
+
+                 - delete the published-at address in the `Move.toml` if the `Move.lock` address is correct; OR
+                 - update the `Move.lock` address using the `iota manage-package` command to be the same as the `Move.toml`; OR
+                 - check that your `iota active-env` {env_alias} corresponds to the chain on which the package is published (i.e., devnet, testnet, mainnet); OR
+                 - contact the maintainer if this package is a dependency and request resolving the conflict."
+            )
+        }
+    }
+}
+
 pub(crate) async fn compile_package(
     read_api: &ReadApi,
     build_config: MoveBuildConfig,
@@ -2027,14 +2708,32 @@ impl Display for IotaClientCommandResult {
         match self {
             IotaClientCommandResult::Addresses(addresses) => {
                 let mut builder = TableBuilder::default();
-                builder.set_header(vec!["alias", "address", "active address"]);
-                for (alias, address) in &addresses.addresses {
+                let with_watch_only = !addresses.watch_only_addresses.is_empty();
+                let mut header = vec!["alias", "address", "active address"];
+                if with_watch_only {
+                    header.push("watch-only");
+                }
+                builder.set_header(header);
+                let watch_only = addresses
+                    .watch_only_addresses
+                    .iter()
+                    .map(|entry| (entry, true));
+                for ((alias, address), is_watch_only) in addresses
+                    .addresses
+                    .iter()
+                    .map(|entry| (entry, false))
+                    .chain(watch_only)
+                {
                     let active_address = if address == &addresses.active_address {
                         "*".to_string()
                     } else {
                         "".to_string()
                     };
-                    builder.push_record([alias.to_string(), address.to_string(), active_address]);
+                    let mut record = vec![alias.to_string(), address.to_string(), active_address];
+                    if with_watch_only {
+                        record.push(if is_watch_only { "*" } else { "" }.to_string());
+                    }
+                    builder.push_record(record);
                 }
                 let mut table = builder.build();
                 let style = TableStyle::rounded();
@@ -2338,6 +3037,110 @@ impl Display for IotaClientCommandResult {
 
                 writeln!(f, "{}", table)?;
             }
+            IotaClientCommandResult::Events(page) => {
+                if page.data.is_empty() {
+                    return write!(f, "No events found");
+                }
+                for event in &page.data {
+                    writeln!(f, "{event}")?;
+                }
+                if let Some(cursor) = page.next_cursor.filter(|_| page.has_next_page) {
+                    write!(f, "Next cursor: {}:{}", cursor.tx_digest, cursor.event_seq)?;
+                }
+            }
+            IotaClientCommandResult::TransactionHistory(history) => {
+                if history.transactions.is_empty() {
+                    return write!(f, "No transactions found for this address");
+                }
+                let mut builder = TableBuilder::default();
+                builder.set_header(vec![
+                    "digest",
+                    "checkpoint",
+                    "timestampMs",
+                    "direction",
+                    "moveCalls",
+                    "status",
+                ]);
+                for transaction in &history.transactions {
+                    let (direction, move_calls) = match &transaction.transaction {
+                        Some(tx) => (
+                            if tx.data.sender() == &history.address {
+                                "sent"
+                            } else {
+                                "received"
+                            },
+                            tx.data
+                                .move_calls()
+                                .iter()
+                                .map(|call| format!("{}::{}", call.module, call.function))
+                                .collect::<Vec<_>>()
+                                .join("\n"),
+                        ),
+                        None => ("", String::new()),
+                    };
+                    let status = transaction
+                        .effects
+                        .as_ref()
+                        .map(|effects| match effects.status() {
+                            IotaExecutionStatus::Success => "success",
+                            IotaExecutionStatus::Failure { .. } => "failure",
+                        })
+                        .unwrap_or_default();
+                    builder.push_record(vec![
+                        transaction.digest.to_string(),
+                        transaction
+                            .checkpoint
+                            .map(|checkpoint| checkpoint.to_string())
+                            .unwrap_or_default(),
+                        transaction
+                            .timestamp_ms
+                            .map(|timestamp| timestamp.to_string())
+                            .unwrap_or_default(),
+                        direction.to_string(),
+                        move_calls,
+                        status.to_string(),
+                    ]);
+                }
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                write!(f, "{}", table)?
+            }
+            IotaClientCommandResult::StardustOutputs(outputs) => {
+                if outputs.is_empty() {
+                    return write!(f, "No Stardust outputs are owned by this address");
+                }
+                let mut builder = TableBuilder::default();
+                builder.set_header(vec![
+                    "objectId",
+                    "kind",
+                    "balance",
+                    "nativeTokens",
+                    "status",
+                ]);
+                for output in outputs {
+                    let status = match &output.status {
+                        StardustClaimStatusOutput::ClaimableNow => "claimable now".to_string(),
+                        StardustClaimStatusOutput::ClaimableLater { unix_time } => {
+                            format!("claimable from unix time {unix_time}")
+                        }
+                        StardustClaimStatusOutput::NeedsReturn {
+                            return_address,
+                            return_amount,
+                        } => format!("needs return of {return_amount} to {return_address}"),
+                        StardustClaimStatusOutput::Unclaimable => "unclaimable".to_string(),
+                    };
+                    builder.push_record(vec![
+                        output.object_id.to_string(),
+                        output.kind.clone(),
+                        output.balance.to_string(),
+                        output.native_tokens.len().to_string(),
+                        status,
+                    ]);
+                }
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                write!(f, "{}", table)?
+            }
             IotaClientCommandResult::NoOutput => {}
             IotaClientCommandResult::DryRun(response) => {
                 writeln!(f, "{}", Pretty(response))?;
@@ -2443,6 +3246,7 @@ impl IotaClientCommandResult {
             | IotaClientCommandResult::ChainIdentifier(_)
             | IotaClientCommandResult::DynamicFieldQuery(_)
             | IotaClientCommandResult::Envs(_, _)
+            | IotaClientCommandResult::Events(_)
             | IotaClientCommandResult::Gas(_)
             | IotaClientCommandResult::NewAddress(_)
             | IotaClientCommandResult::NewEnv(_)
@@ -2452,8 +3256,10 @@ impl IotaClientCommandResult {
             | IotaClientCommandResult::RawObject(_)
             | IotaClientCommandResult::SerializedSignedTransaction(_)
             | IotaClientCommandResult::SerializedUnsignedTransaction(_)
+            | IotaClientCommandResult::StardustOutputs(_)
             | IotaClientCommandResult::Switch(_)
             | IotaClientCommandResult::SyncClientState
+            | IotaClientCommandResult::TransactionHistory(_)
             | IotaClientCommandResult::VerifyBytecodeMeter { .. }
             | IotaClientCommandResult::VerifySource => (),
         }
@@ -2466,6 +3272,8 @@ impl IotaClientCommandResult {
 pub struct AddressesOutput {
     pub active_address: IotaAddress,
     pub addresses: Vec<(String, IotaAddress)>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub watch_only_addresses: Vec<(String, IotaAddress)>,
 }
 
 #[derive(Serialize)]
@@ -2476,6 +3284,13 @@ pub struct DynamicFieldOutput {
     pub data: Vec<DynamicFieldInfo>,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionHistoryOutput {
+    pub address: IotaAddress,
+    pub transactions: Vec<IotaTransactionBlockResponse>,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NewAddressOutput {
@@ -2539,6 +3354,288 @@ impl From<&GasCoin> for GasCoinOutput {
     }
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StardustOutputEntry {
+    pub object_id: ObjectID,
+    pub kind: String,
+    pub coin_type: String,
+    pub balance: u64,
+    pub claimable_amount: u64,
+    pub native_tokens: Vec<String>,
+    pub status: StardustClaimStatusOutput,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum StardustClaimStatusOutput {
+    ClaimableNow,
+    ClaimableLater {
+        unix_time: u32,
+    },
+    NeedsReturn {
+        return_address: IotaAddress,
+        return_amount: u64,
+    },
+    Unclaimable,
+}
+
+impl StardustOutputEntry {
+    fn new(output: &StardustOutput, status: StardustClaimStatus) -> Self {
+        Self {
+            object_id: output.object_ref.0,
+            kind: format!("{:?}", output.kind),
+            coin_type: output.coin_type.to_canonical_string(true),
+            balance: output.balance,
+            claimable_amount: output.claimable_amount(),
+            native_tokens: output
+                .native_tokens
+                .iter()
+                .map(|token| token.to_canonical_string(true))
+                .collect(),
+            status: match status {
+                StardustClaimStatus::ClaimableNow => StardustClaimStatusOutput::ClaimableNow,
+                StardustClaimStatus::ClaimableLater { unix_time } => {
+                    StardustClaimStatusOutput::ClaimableLater { unix_time }
+                }
+                StardustClaimStatus::NeedsReturn {
+                    return_address,
+                    return_amount,
+                } => StardustClaimStatusOutput::NeedsReturn {
+                    return_address,
+                    return_amount,
+                },
+                StardustClaimStatus::Unclaimable => StardustClaimStatusOutput::Unclaimable,
+            },
+        }
+    }
+}
+
+impl StardustCommand {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<IotaClientCommandResult, anyhow::Error> {
+        let client = context.get_client().await?;
+        // Unlock conditions are checked against the start of the current epoch.
+        let epoch_start_timestamp_ms = client
+            .governance_api()
+            .get_latest_iota_system_state()
+            .await?
+            .epoch_start_timestamp_ms;
+        Ok(match self {
+            StardustCommand::List { address, graphql } => {
+                let address = get_identity_address(address, context)?;
+                let outputs =
+                    stardust_outputs(client.transaction_builder(), address, graphql.as_deref())
+                        .await?
+                        .iter()
+                        .map(|output| {
+                            StardustOutputEntry::new(
+                                output,
+                                output.claim_status(address, epoch_start_timestamp_ms),
+                            )
+                        })
+                        .collect();
+                IotaClientCommandResult::StardustOutputs(outputs)
+            }
+            StardustCommand::Claim {
+                object_ids,
+                graphql,
+                opts,
+            } => {
+                let signer = context.active_address()?;
+                let builder = client.transaction_builder();
+                let outputs = if object_ids.is_empty() {
+                    let outputs: Vec<_> = stardust_outputs(builder, signer, graphql.as_deref())
+                        .await?
+                        .into_iter()
+                        .filter(|output| {
+                            matches!(
+                                output.claim_status(signer, epoch_start_timestamp_ms),
+                                StardustClaimStatus::ClaimableNow
+                                    | StardustClaimStatus::NeedsReturn { .. }
+                            )
+                        })
+                        .collect();
+                    ensure!(
+                        !outputs.is_empty(),
+                        "No Stardust outputs owned by {signer} can be claimed now"
+                    );
+                    outputs
+                } else {
+                    let mut outputs = Vec::with_capacity(object_ids.len());
+                    for object_id in object_ids {
+                        let output = builder.stardust_output(object_id).await?;
+                        match output.claim_status(signer, epoch_start_timestamp_ms) {
+                            StardustClaimStatus::ClaimableNow
+                            | StardustClaimStatus::NeedsReturn { .. } => outputs.push(output),
+                            StardustClaimStatus::ClaimableLater { unix_time } => bail!(
+                                "Output {object_id} can only be claimed from unix time {unix_time}"
+                            ),
+                            StardustClaimStatus::Unclaimable => {
+                                bail!("Output {object_id} cannot be claimed by {signer}")
+                            }
+                        }
+                    }
+                    outputs
+                };
+                let tx_kind = builder.claim_stardust_outputs_tx_kind(signer, &outputs)?;
+                dry_run_or_execute_or_serialize(
+                    signer, tx_kind, context, None, None, opts.gas, opts.rest,
+                )
+                .await?
+            }
+        })
+    }
+}
+
+const STARDUST_OUTPUTS_QUERY: &str = r#"
+query ($address: IotaAddress!, $after: String) {
+  address(address: $address) {
+    stardustOutputs(after: $after) {
+      nodes {
+        ... on BasicOutput { address }
+        ... on NftOutput { address }
+        ... on AliasOutput { address }
+      }
+      pageInfo { hasNextPage endCursor }
+    }
+  }
+}
+"#;
+
+/// Reads the Stardust outputs of `address`. Outputs with an expiration unlock
+/// condition are shared objects, which the RPC cannot list by address, so they
+/// are only found through the GraphQL service at `graphql`, if set. Otherwise
+/// the user is told they may be missing.
+async fn stardust_outputs(
+    builder: &TransactionBuilder,
+    address: IotaAddress,
+    graphql: Option<&str>,
+) -> anyhow::Result<Vec<StardustOutput>> {
+    let Some(url) = graphql else {
+        eprintln!(
+            "{}",
+            "Warning: Stardust outputs with an expiration are shared objects and are not \
+             included. Look them up with `--graphql` or claim them with `--object-ids`."
+                .bold()
+                .yellow()
+        );
+        return builder.stardust_outputs(address).await;
+    };
+
+    let client = reqwest::Client::new();
+    let mut outputs = vec![];
+    let mut after: Option<String> = None;
+    loop {
+        let response: Value = client
+            .post(url)
+            .json(&json!({
+                "query": STARDUST_OUTPUTS_QUERY,
+                "variables": { "address": address, "after": after },
+            }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if let Some(errors) = response.get("errors") {
+            bail!("Failed to query Stardust outputs from {url}: {errors}");
+        }
+
+        let connection = &response["data"]["address"]["stardustOutputs"];
+        for node in connection["nodes"].as_array().into_iter().flatten() {
+            let Some(object_id) = node["address"].as_str() else {
+                bail!("Missing Stardust output ID in the response from {url}");
+            };
+            outputs.push(
+                builder
+                    .stardust_output(ObjectID::from_str(object_id)?)
+                    .await?,
+            );
+        }
+
+        // Pages may be short or empty, since outputs are filtered as the
+        // service scans them, so only `hasNextPage` ends the lookup.
+        let page_info = &connection["pageInfo"];
+        match (
+            page_info["hasNextPage"].as_bool(),
+            page_info["endCursor"].as_str(),
+        ) {
+            (Some(true), Some(cursor)) => after = Some(cursor.to_string()),
+            _ => break,
+        }
+    }
+    Ok(outputs)
+}
+
+impl KioskCommand {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<IotaClientCommandResult, anyhow::Error> {
+        let signer = context.active_address()?;
+        let client = context.get_client().await?;
+        let builder = client.transaction_builder();
+        let (tx_kind, opts) = match self {
+            KioskCommand::Create { opts } => (builder.new_kiosk_tx_kind(signer)?, opts),
+            KioskCommand::Place {
+                kiosk,
+                item,
+                price,
+                opts,
+            } => (
+                builder
+                    .place_in_kiosk_tx_kind(signer, kiosk, item, price)
+                    .await?,
+                opts,
+            ),
+            KioskCommand::List {
+                kiosk,
+                item,
+                price,
+                opts,
+            } => (
+                builder
+                    .list_in_kiosk_tx_kind(signer, kiosk, item, price)
+                    .await?,
+                opts,
+            ),
+            KioskCommand::Delist { kiosk, item, opts } => (
+                builder
+                    .delist_from_kiosk_tx_kind(signer, kiosk, item)
+                    .await?,
+                opts,
+            ),
+            KioskCommand::Purchase {
+                kiosk,
+                item,
+                policy,
+                opts,
+            } => (
+                builder
+                    .purchase_from_kiosk_tx_kind(signer, kiosk, item, policy)
+                    .await?,
+                opts,
+            ),
+            KioskCommand::Withdraw {
+                kiosk,
+                amount,
+                opts,
+            } => (
+                builder
+                    .withdraw_from_kiosk_tx_kind(signer, kiosk, amount)
+                    .await?,
+                opts,
+            ),
+        };
+        dry_run_or_execute_or_serialize(signer, tx_kind, context, None, None, opts.gas, opts.rest)
+            .await
+    }
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ObjectsOutput {
@@ -2592,6 +3689,7 @@ pub enum IotaClientCommandResult {
     DynamicFieldQuery(DynamicFieldPage),
     DryRun(DryRunTransactionBlockResponse),
     Envs(Vec<IotaEnv>, Option<String>),
+    Events(EventPage),
     Gas(Vec<GasCoin>),
     NewAddress(NewAddressOutput),
     NewEnv(IotaEnv),
@@ -2601,9 +3699,11 @@ pub enum IotaClientCommandResult {
     RawObject(IotaObjectResponse),
     SerializedSignedTransaction(SenderSignedData),
     SerializedUnsignedTransaction(TransactionData),
+    StardustOutputs(Vec<StardustOutputEntry>),
     Switch(SwitchResponse),
     SyncClientState,
     TransactionBlock(IotaTransactionBlockResponse),
+    TransactionHistory(TransactionHistoryOutput),
     VerifyBytecodeMeter {
         success: bool,
         max_package_ticks: Option<u128>,
@@ -2984,6 +4084,11 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
             tx_data,
         ))
     } else {
+        ensure!(
+            !context.config().is_watch_only(&signer),
+            "{signer} is a watch-only address, use --serialize-unsigned-transaction to get the \
+            transaction to sign elsewhere"
+        );
         let signature = context.config().keystore().sign_secure(
             &tx_data.sender(),
             &tx_data,
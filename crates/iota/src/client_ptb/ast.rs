@@ -35,6 +35,8 @@ pub const SUMMARY: &str = "summary";
 pub const GAS_COIN: &str = "gas-coin";
 pub const JSON: &str = "json";
 pub const DRY_RUN: &str = "dry-run";
+pub const DEV_INSPECT: &str = "dev-inspect";
+pub const TRACE: &str = "trace";
 pub const SERIALIZE_UNSIGNED: &str = "serialize-unsigned-transaction";
 pub const SERIALIZE_SIGNED: &str = "serialize-signed-transaction";
 
@@ -74,6 +76,8 @@ pub const COMMANDS: &[&str] = &[
     GAS_COIN,
     JSON,
     DRY_RUN,
+    DEV_INSPECT,
+    TRACE,
     SERIALIZE_UNSIGNED,
     SERIALIZE_SIGNED,
 ];
@@ -111,6 +115,8 @@ pub struct ProgramMetadata {
     pub gas_object_id: Option<Spanned<ObjectID>>,
     pub json_set: bool,
     pub dry_run_set: bool,
+    pub dev_inspect_set: bool,
+    pub trace_set: bool,
     pub gas_budget: Option<Spanned<u64>>,
 }
 
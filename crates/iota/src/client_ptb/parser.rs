@@ -41,6 +41,8 @@ struct ProgramParsingState {
     serialize_signed_set: bool,
     json_set: bool,
     dry_run_set: bool,
+    dev_inspect_set: bool,
+    trace_set: bool,
     gas_object_id: Option<Spanned<ObjectID>>,
     gas_budget: Option<Spanned<u64>>,
 }
@@ -63,6 +65,8 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 serialize_signed_set: false,
                 json_set: false,
                 dry_run_set: false,
+                dev_inspect_set: false,
+                trace_set: false,
                 gas_object_id: None,
                 gas_budget: None,
             },
@@ -111,6 +115,8 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 L(T::Command, A::SUMMARY) => flag!(summary_set),
                 L(T::Command, A::JSON) => flag!(json_set),
                 L(T::Command, A::DRY_RUN) => flag!(dry_run_set),
+                L(T::Command, A::DEV_INSPECT) => flag!(dev_inspect_set),
+                L(T::Command, A::TRACE) => flag!(trace_set),
                 L(T::Command, A::PREVIEW) => flag!(preview_set),
                 L(T::Command, A::WARN_SHADOWS) => flag!(warn_shadows_set),
                 L(T::Command, A::GAS_COIN) => {
@@ -208,6 +214,8 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                     gas_object_id: self.state.gas_object_id,
                     json_set: self.state.json_set,
                     dry_run_set: self.state.dry_run_set,
+                    dev_inspect_set: self.state.dev_inspect_set,
+                    trace_set: self.state.trace_set,
                     gas_budget: self.state.gas_budget,
                 },
             ))
@@ -4,12 +4,13 @@
 
 use std::collections::HashSet;
 
-use anyhow::{Error, anyhow, ensure};
+use anyhow::{Error, anyhow, bail, ensure};
 use clap::{Args, ValueHint, arg};
-use iota_json_rpc_types::{IotaExecutionStatus, IotaTransactionBlockEffectsAPI};
+use iota_json_rpc_types::{DevInspectArgs, IotaExecutionStatus, IotaTransactionBlockEffectsAPI};
 use iota_keys::keystore::AccountKeystore;
 use iota_sdk::{IotaClient, wallet_context::WalletContext};
 use iota_types::{
+    base_types::{IotaAddress, ObjectID},
     digests::TransactionDigest,
     gas::GasCostSummary,
     transaction::{ProgrammableTransaction, TransactionKind},
@@ -21,6 +22,7 @@ use super::{ast::ProgramMetadata, lexer::Lexer, parser::ProgramParser};
 use crate::{
     client_commands::{
         IotaClientCommandResult, Opts, OptsWithGas, dry_run_or_execute_or_serialize,
+        execute_dry_run,
     },
     client_ptb::{
         ast::{ParsedProgram, Program},
@@ -93,12 +95,24 @@ impl PTB {
             !program_metadata.serialize_unsigned_set || !program_metadata.serialize_signed_set,
             "Cannot specify both flags: --serialize-unsigned-transaction and --serialize-signed-transaction."
         );
+        ensure!(
+            !program_metadata.trace_set || program_metadata.dev_inspect_set,
+            "The --trace flag can only be used together with --dev-inspect."
+        );
 
         if program_metadata.preview_set {
             println!("{}", PTBPreview {
                 program: &program,
                 program_metadata: &program_metadata
             });
+            // The preview is still useful without the gas breakdown, e.g. when
+            // offline, so failing to dry run the PTB is not an error.
+            if let Err(e) =
+                Self::preview_gas_breakdown(program, &program_metadata, &source_string, context)
+                    .await
+            {
+                eprintln!("Could not dry run the PTB to break down its gas cost: {e}");
+            }
             return Ok(());
         }
 
@@ -128,20 +142,13 @@ impl PTB {
             Ok(x) => x,
         };
 
+        if program_metadata.dev_inspect_set {
+            return Self::dev_inspect(ptb, &program_metadata, context).await;
+        }
+
         // get all the metadata needed for executing the PTB: sender, gas, signing tx
         let gas = program_metadata.gas_object_id.map(|x| x.value);
-
-        // the sender is the gas object if gas is provided, otherwise the active address
-        let sender = match gas {
-            Some(gas) => context
-                .get_object_owner(&gas)
-                .await
-                .map_err(|_| anyhow!("Could not find owner for gas object ID"))?,
-            None => context
-                .config()
-                .active_address()
-                .ok_or_else(|| anyhow!("No active address, cannot execute PTB"))?,
-        };
+        let sender = Self::sender(gas, context).await?;
 
         // build the tx kind
         let tx_kind = TransactionKind::ProgrammableTransaction(ProgrammableTransaction {
@@ -218,6 +225,104 @@ impl PTB {
         Ok(())
     }
 
+    /// The sender is the owner of the gas object if gas is provided, otherwise
+    /// the active address.
+    async fn sender(gas: Option<ObjectID>, context: &WalletContext) -> Result<IotaAddress, Error> {
+        match gas {
+            Some(gas) => context
+                .get_object_owner(&gas)
+                .await
+                .map_err(|_| anyhow!("Could not find owner for gas object ID")),
+            None => context
+                .config()
+                .active_address()
+                .ok_or_else(|| anyhow!("No active address, cannot execute PTB")),
+        }
+    }
+
+    /// Dry runs the PTB and prints the computation cost of each command.
+    async fn preview_gas_breakdown(
+        program: Program,
+        program_metadata: &ProgramMetadata,
+        source_string: &str,
+        context: &WalletContext,
+    ) -> Result<(), Error> {
+        let client = context.get_client().await?;
+        let (res, _) = Self::build_ptb(program, context, client.clone()).await;
+        let ptb = res.map_err(|errors| {
+            let rendered = build_error_reports(source_string, errors);
+            anyhow!(
+                "{}",
+                rendered
+                    .iter()
+                    .map(|e| format!("{e:?}"))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            )
+        })?;
+        let gas = program_metadata.gas_object_id.map(|x| x.value);
+        let sender = Self::sender(gas, context).await?;
+        let gas_price = context.get_reference_gas_price().await?;
+        let tx_kind = TransactionKind::ProgrammableTransaction(ptb);
+
+        let IotaClientCommandResult::DryRun(response) = execute_dry_run(
+            &client,
+            sender,
+            tx_kind,
+            program_metadata.gas_budget.map(|x| x.value),
+            gas_price,
+            gas.map(|gas| vec![gas]),
+            None,
+        )
+        .await?
+        else {
+            bail!("Internal error, unexpected response from PTB dry run.");
+        };
+        if let IotaExecutionStatus::Failure { error } = response.effects.status() {
+            println!("Dry run failed: {error}");
+        }
+        match &response.gas_breakdown {
+            Some(gas_breakdown) => println!("{}", Pretty(gas_breakdown)),
+            None => println!("The node did not return a gas breakdown for the PTB."),
+        }
+        Ok(())
+    }
+
+    /// Runs the PTB in dev-inspect mode and prints the results, including a
+    /// trace of the Move execution of each command if requested.
+    async fn dev_inspect(
+        ptb: ProgrammableTransaction,
+        program_metadata: &ProgramMetadata,
+        context: &WalletContext,
+    ) -> Result<(), Error> {
+        let client = context.get_client().await?;
+        let gas = program_metadata.gas_object_id.map(|x| x.value);
+        let sender = Self::sender(gas, context).await?;
+        let results = client
+            .read_api()
+            .dev_inspect_transaction_block(
+                sender,
+                TransactionKind::ProgrammableTransaction(ptb),
+                None,
+                None,
+                DevInspectArgs {
+                    gas_budget: program_metadata.gas_budget.map(|x| x.value.into()),
+                    trace: Some(program_metadata.trace_set),
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        if program_metadata.json_set {
+            let json_string = serde_json::to_string_pretty(&results)
+                .map_err(|_| anyhow!("Cannot serialize dev-inspect results to json"))?;
+            println!("{json_string}");
+        } else {
+            println!("{}", Pretty(&results));
+        }
+        Ok(())
+    }
+
     /// Exposed for testing
     pub async fn build_ptb(
         program: Program,
@@ -227,12 +332,24 @@ impl PTB {
         Result<ProgrammableTransaction, Vec<PTBError>>,
         Vec<PTBError>,
     ) {
-        let starting_addresses = context
-            .config()
-            .keystore()
-            .addresses_with_alias()
-            .into_iter()
-            .map(|(sa, alias)| (alias.alias.clone(), AccountAddress::from(*sa)))
+        let config = context.config();
+        // Aliases of the keystore take precedence over the watch-only ones.
+        let starting_addresses = config
+            .watch_only_addresses()
+            .iter()
+            .map(|watched| {
+                (
+                    watched.alias().clone(),
+                    AccountAddress::from(*watched.address()),
+                )
+            })
+            .chain(
+                config
+                    .keystore()
+                    .addresses_with_alias()
+                    .into_iter()
+                    .map(|(sa, alias)| (alias.alias.clone(), AccountAddress::from(*sa))),
+            )
             .collect();
         let builder = PTBBuilder::new(starting_addresses, client.read_api());
         builder.build(program).await
@@ -299,6 +416,18 @@ pub fn ptb_description() -> clap::Command {
             --"dry-run"
             "Perform a dry run of the PTB instead of executing it."
         ))
+        .arg(arg!(
+            --"dev-inspect"
+            "Run the PTB in dev-inspect mode instead of executing it. Any Move function can be \
+            called and no gas coin is needed. Use --json to also get the return values of \
+            each command."
+        ))
+        .arg(arg!(
+            --"trace"
+            "With --dev-inspect, also show a trace of the Move execution of each command: the \
+            functions called with their arguments, the events emitted, the dynamic fields \
+            accessed and the input objects changed."
+        ))
         .arg(arg!(
             --"gas-coin" <ID> ...
             "The object ID of the gas coin to use. If not specified, it will try to use the first \
@@ -390,7 +519,9 @@ pub fn ptb_description() -> clap::Command {
         ).value_hint(ValueHint::DirPath))
         .arg(arg!(
             --"preview"
-            "Preview the list of PTB transactions instead of executing them."
+            "Preview the list of PTB transactions instead of executing them. If the \
+            network can be reached, the PTB is also dry run to show the computation cost \
+            of each command."
         ))
         .arg(arg!(
             --"serialize-unsigned-transaction"
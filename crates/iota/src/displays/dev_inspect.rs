@@ -0,0 +1,141 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt::{Display, Formatter, Write};
+
+use iota_json_rpc_types::{DevInspectResults, IotaTransactionBlockEffectsAPI};
+use iota_types::execution::{CommandTrace, ExecutionTrace, TraceStep, TracedObjectChangeKind};
+use tabled::{
+    builder::Builder as TableBuilder,
+    settings::{Panel as TablePanel, Style as TableStyle, style::HorizontalLine},
+};
+
+use crate::displays::Pretty;
+
+impl Display for Pretty<'_, DevInspectResults> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Pretty(results) = self;
+
+        writeln!(
+            f,
+            "Dev inspect completed, execution status: {}",
+            results.effects.status()
+        )?;
+        writeln!(f, "{}", results.effects)?;
+        write!(f, "{}", results.events)?;
+        if let Some(error) = &results.error {
+            writeln!(f, "Execution error: {error}")?;
+        }
+        if let Some(gas_breakdown) = &results.gas_breakdown {
+            writeln!(f, "{}", Pretty(gas_breakdown))?;
+        }
+        if let Some(trace) = &results.trace {
+            writeln!(f, "{}", Pretty(trace))?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Pretty<'_, ExecutionTrace> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Pretty(trace) = self;
+
+        let mut builder = TableBuilder::default();
+        builder.set_header(["#", "command", "trace"]);
+        for command in &trace.commands {
+            builder.push_record([
+                command.command_index.to_string(),
+                command.command.clone(),
+                render_command_trace(command)?,
+            ]);
+        }
+        let mut table = builder.build();
+        table.with(TablePanel::header("Execution Trace"));
+        table.with(TableStyle::rounded().horizontals([
+            HorizontalLine::new(1, TableStyle::modern().get_horizontal()),
+            HorizontalLine::new(2, TableStyle::modern().get_horizontal()),
+        ]));
+        table.with(tabled::settings::style::BorderSpanCorrection);
+        write!(f, "{table}")
+    }
+}
+
+/// Renders the steps of a command as a call tree, indented by call depth,
+/// followed by the input objects it changed and the error it failed with.
+fn render_command_trace(command: &CommandTrace) -> Result<String, std::fmt::Error> {
+    let mut rendered = String::new();
+    for step in &command.steps {
+        match step {
+            TraceStep::Call {
+                depth,
+                function,
+                type_arguments,
+                arguments,
+            } => {
+                indent(&mut rendered, *depth);
+                write!(rendered, "call {function}")?;
+                if !type_arguments.is_empty() {
+                    write!(rendered, "<{}>", type_arguments.join(", "))?;
+                }
+                writeln!(rendered, "({})", arguments.join(", "))?;
+            }
+            TraceStep::Return {
+                depth,
+                function,
+                return_values,
+            } => {
+                indent(&mut rendered, *depth);
+                write!(rendered, "return {function}")?;
+                if !return_values.is_empty() {
+                    write!(rendered, " -> ({})", return_values.join(", "))?;
+                }
+                writeln!(rendered)?;
+            }
+            TraceStep::Abort { depth, function } => {
+                indent(&mut rendered, *depth);
+                writeln!(rendered, "abort {function}")?;
+            }
+            TraceStep::Event {
+                depth,
+                event_type,
+                contents,
+            } => {
+                indent(&mut rendered, *depth);
+                writeln!(rendered, "event {event_type} {contents}")?;
+            }
+            TraceStep::DynamicFieldRead {
+                depth,
+                parent,
+                child,
+            } => {
+                indent(&mut rendered, *depth);
+                writeln!(rendered, "read dynamic field {child} of {parent}")?;
+            }
+            TraceStep::DynamicFieldWrite {
+                depth,
+                parent,
+                child,
+            } => {
+                indent(&mut rendered, *depth);
+                writeln!(rendered, "write dynamic field {child} of {parent}")?;
+            }
+        }
+    }
+    for change in &command.object_changes {
+        let kind = match change.kind {
+            TracedObjectChangeKind::Mutated => "mutated",
+            TracedObjectChangeKind::Taken => "taken",
+        };
+        writeln!(rendered, "object {} {kind}", change.object_id)?;
+    }
+    if let Some(error) = &command.error {
+        writeln!(rendered, "error: {error}")?;
+    }
+    Ok(rendered.trim_end().to_string())
+}
+
+fn indent(rendered: &mut String, depth: u16) {
+    for _ in 0..depth {
+        rendered.push_str("  ");
+    }
+}
@@ -93,6 +93,9 @@ impl Display for Pretty<'_, DryRunTransactionBlockResponse> {
             )]));
             writeln!(f, "{}", table)?;
         }
+        if let Some(gas_breakdown) = &response.gas_breakdown {
+            writeln!(f, "{}", Pretty(gas_breakdown))?;
+        }
         writeln!(
             f,
             "Dry run completed, execution status: {}",
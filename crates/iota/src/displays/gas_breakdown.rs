@@ -0,0 +1,58 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt::{Display, Formatter};
+
+use iota_types::gas::GasBreakdown;
+use tabled::{
+    builder::Builder as TableBuilder,
+    settings::{Panel as TablePanel, Style as TableStyle, style::HorizontalLine},
+};
+
+use crate::displays::Pretty;
+
+impl Display for Pretty<'_, GasBreakdown> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Pretty(breakdown) = self;
+
+        let mut builder = TableBuilder::default();
+        builder.set_header(["#", "command", "function", "computation cost (NANOS)"]);
+        for command in &breakdown.commands {
+            builder.push_record([
+                command.command_index.to_string(),
+                command.command.clone(),
+                command.function.clone().unwrap_or_default(),
+                command.computation_cost.to_string(),
+            ]);
+        }
+        let mut table = builder.build();
+        table.with(TablePanel::header("Computation Cost per Command"));
+        table.with(TableStyle::rounded().horizontals([
+            HorizontalLine::new(1, TableStyle::modern().get_horizontal()),
+            HorizontalLine::new(2, TableStyle::modern().get_horizontal()),
+        ]));
+        table.with(tabled::settings::style::BorderSpanCorrection);
+        write!(f, "{table}")?;
+
+        if breakdown.move_functions.is_empty() {
+            return Ok(());
+        }
+        let mut builder = TableBuilder::default();
+        builder.set_header(["function", "calls", "computation cost (NANOS)"]);
+        for function in &breakdown.move_functions {
+            builder.push_record([
+                function.function.clone(),
+                function.calls.to_string(),
+                function.computation_cost.to_string(),
+            ]);
+        }
+        let mut table = builder.build();
+        table.with(TablePanel::header("Computation Cost per Move Function"));
+        table.with(TableStyle::rounded().horizontals([
+            HorizontalLine::new(1, TableStyle::modern().get_horizontal()),
+            HorizontalLine::new(2, TableStyle::modern().get_horizontal()),
+        ]));
+        table.with(tabled::settings::style::BorderSpanCorrection);
+        write!(f, "\n{table}")
+    }
+}
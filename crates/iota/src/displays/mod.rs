@@ -2,7 +2,9 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+mod dev_inspect;
 mod dry_run_tx_block;
+mod gas_breakdown;
 mod gas_cost_summary;
 mod ptb_preview;
 mod status;
@@ -59,11 +59,13 @@ use tempfile::tempdir;
 use tracing::{self, info};
 
 use crate::{
-    client_commands::IotaClientCommands,
+    client_commands::{IotaClientCommands, follow_events},
     console::start_console,
     fire_drill::{FireDrill, run_fire_drill},
     genesis_ceremony::{Ceremony, run},
     keytool::KeyToolCommand,
+    package_graph::PackageGraph,
+    upgrade_compatibility::check_upgrade,
     validator_commands::IotaValidatorCommand,
 };
 
@@ -452,13 +454,21 @@ impl IotaCommand {
                 let config_path = config.unwrap_or(iota_config_dir()?.join(IOTA_CLIENT_CONFIG));
                 prompt_if_no_config(&config_path, accept_defaults).await?;
                 let mut context = WalletContext::new(&config_path, None, None)?;
-                if let Some(cmd) = cmd {
-                    cmd.execute(&mut context).await?.print(!json);
-                } else {
-                    // Print help
-                    let mut app: Command = IotaCommand::command();
-                    app.build();
-                    app.find_subcommand_mut("client").unwrap().print_help()?;
+                match cmd {
+                    // Following events prints them as they are emitted instead of returning
+                    // a result.
+                    Some(IotaClientCommands::Events {
+                        filter,
+                        follow: true,
+                        ..
+                    }) => follow_events(filter, &mut context, !json).await?,
+                    Some(cmd) => cmd.execute(&mut context).await?.print(!json),
+                    None => {
+                        // Print help
+                        let mut app: Command = IotaCommand::command();
+                        app.build();
+                        app.find_subcommand_mut("client").unwrap().print_help()?;
+                    }
                 }
                 Ok(())
             }
@@ -494,14 +504,43 @@ impl IotaCommand {
                         // case, we should resolve the correct addresses for the respective chain
                         // (e.g., testnet, mainnet) from the Move.lock under automated address
                         // management.
-                        let config =
-                            client_config.unwrap_or(iota_config_dir()?.join(IOTA_CLIENT_CONFIG));
-                        prompt_if_no_config(&config, false).await?;
-                        let context = WalletContext::new(&config, None, None)?;
+                        let context = move_wallet_context(client_config).await?;
                         let client = context.get_client().await?;
                         let chain_id = client.read_api().get_chain_identifier().await.ok();
                         build.chain_id = chain_id.clone();
                     }
+                    // Checking upgrades and showing dependency graphs needs the packages on
+                    // chain, so these commands are executed here rather than by `iota-move`.
+                    iota_move::Command::CheckUpgrade(check) => {
+                        let context = move_wallet_context(client_config).await?;
+                        let package_path = match package_path {
+                            Some(path) => path,
+                            None => std::env::current_dir()?,
+                        };
+                        let report =
+                            check_upgrade(check, &package_path, build_config, &context).await?;
+                        if check.json {
+                            println!("{}", serde_json::to_string_pretty(&report)?);
+                        } else {
+                            print!("{report}");
+                        }
+                        return Ok(());
+                    }
+                    iota_move::Command::DependencyGraph(graph_cmd) => {
+                        let context = move_wallet_context(client_config).await?;
+                        let client = context.get_client().await?;
+                        let mut graph =
+                            PackageGraph::build(client.read_api(), graph_cmd.package_id).await?;
+                        if let Some(url) = &graph_cmd.graphql {
+                            graph.fetch_dependents(url).await?;
+                        }
+                        if graph_cmd.json {
+                            println!("{}", serde_json::to_string_pretty(&graph)?);
+                        } else {
+                            print!("{graph}");
+                        }
+                        return Ok(());
+                    }
                     _ => (),
                 };
                 execute_move_command(package_path.as_deref(), build_config, cmd)
@@ -1145,6 +1184,16 @@ async fn genesis(
     Ok(())
 }
 
+/// The wallet context for `iota move` commands that need a network
+/// connection, prompting to create the client config if it does not exist.
+async fn move_wallet_context(
+    client_config: Option<PathBuf>,
+) -> Result<WalletContext, anyhow::Error> {
+    let config = client_config.unwrap_or(iota_config_dir()?.join(IOTA_CLIENT_CONFIG));
+    prompt_if_no_config(&config, false).await?;
+    WalletContext::new(&config, None, None)
+}
+
 async fn prompt_if_no_config(
     wallet_conf_path: &Path,
     accept_defaults: bool,
@@ -10,8 +10,8 @@ use iota_sdk::wallet_context::WalletContext;
 use iota_types::base_types::IotaAddress;
 use serde::Serialize;
 
-/// An address or an alias associated with a key in the wallet
-/// This is used to distinguish between an address or an alias,
+/// An address or an alias associated with a key or a watch-only address in the
+/// wallet. This is used to distinguish between an address or an alias,
 /// enabling a user to use an alias for any command that requires an address.
 #[derive(Serialize, Clone)]
 pub enum KeyIdentity {
@@ -40,16 +40,17 @@ impl Display for KeyIdentity {
     }
 }
 
-/// Get the IotaAddress corresponding to this key identity.
-/// If no string is provided, then the current active address is returned.
+/// Get the IotaAddress corresponding to this key identity, which may be a
+/// watch-only address. If no string is provided, then the current active
+/// address is returned.
 pub fn get_identity_address(
     input: Option<KeyIdentity>,
     ctx: &mut WalletContext,
 ) -> Result<IotaAddress, Error> {
-    if let Some(addr) = input {
-        get_identity_address_from_keystore(addr, ctx.config().keystore())
-    } else {
-        Ok(ctx.active_address()?)
+    match input {
+        Some(KeyIdentity::Address(x)) => Ok(x),
+        Some(KeyIdentity::Alias(x)) => ctx.config().get_address_by_alias(x),
+        None => Ok(ctx.active_address()?),
     }
 }
 
@@ -39,7 +39,8 @@ use iota_types::{
         get_authority_key_pair,
     },
     error::IotaResult,
-    multisig::{MultiSig, MultiSigPublicKey, ThresholdUnit, WeightUnit},
+    multisig::{MultiSig, ThresholdUnit, WeightUnit},
+    nested_multisig::{MultiSigMember, NestedMultiSig, NestedMultiSigPublicKey},
     signature::{GenericSignature, VerifyParams},
     signature_verification::VerifiedDigestCache,
     transaction::{TransactionData, TransactionDataAPI},
@@ -151,11 +152,15 @@ pub enum KeyToolCommand {
     },
     /// To MultiSig Iota Address. Pass in a list of all public keys `flag || pk`
     /// in Base64. See `keytool list` for example public keys.
+    ///
+    /// A member can also be a multisig itself, passed as the
+    /// `multisigPublicKey` output of a previous `multi-sig-address` call, to
+    /// build a nested MultiSig address.
     MultiSigAddress {
         #[clap(long)]
         threshold: ThresholdUnit,
         #[clap(long, num_args(1..))]
-        pks: Vec<PublicKey>,
+        pks: Vec<MultiSigMember>,
         #[clap(long, num_args(1..))]
         weights: Vec<WeightUnit>,
     },
@@ -169,11 +174,16 @@ pub enum KeyToolCommand {
     /// The order of `sigs` must be the same as the order of `pks`.
     /// e.g. for [pk1, pk2, pk3, pk4, pk5], [sig1, sig2, sig5] is valid, but
     /// [sig2, sig1, sig5] is invalid.
+    ///
+    /// If some of the `pks` are multisigs, a nested MultiSig is built. The
+    /// signature of a multisig member is the `multisigSerialized` output of
+    /// combining the partial signatures of its own members, and `sigs` can
+    /// then be passed in any order.
     MultiSigCombinePartialSig {
         #[clap(long, num_args(1..))]
         sigs: Vec<GenericSignature>,
         #[clap(long, num_args(1..))]
-        pks: Vec<PublicKey>,
+        pks: Vec<MultiSigMember>,
         #[clap(long, num_args(1..))]
         weights: Vec<WeightUnit>,
         #[clap(long)]
@@ -348,6 +358,9 @@ pub struct ExportedKey {
 #[serde(rename_all = "camelCase")]
 pub struct MultiSigAddress {
     multisig_address: String,
+    /// Encoded public key of the multisig, to use it as a member of another
+    /// multisig.
+    multisig_public_key: String,
     multisig: Vec<MultiSigOutput>,
 }
 
@@ -652,16 +665,18 @@ impl KeyToolCommand {
                 pks,
                 weights,
             } => {
-                let multisig_pk = MultiSigPublicKey::new(pks.clone(), weights.clone(), threshold)?;
+                let multisig_pk =
+                    NestedMultiSigPublicKey::new(pks.clone(), weights.clone(), threshold)?;
                 let address: IotaAddress = (&multisig_pk).into();
                 let mut output = MultiSigAddress {
                     multisig_address: address.to_string(),
+                    multisig_public_key: multisig_pk.encode_base64(),
                     multisig: vec![],
                 };
 
                 for (pk, w) in pks.into_iter().zip(weights.into_iter()) {
                     output.multisig.push(MultiSigOutput {
-                        address: Into::<IotaAddress>::into(&pk),
+                        address: pk.address(),
                         public_base64_key: pk.encode_base64(),
                         weight: w,
                     });
@@ -674,10 +689,12 @@ impl KeyToolCommand {
                 weights,
                 threshold,
             } => {
-                let multisig_pk = MultiSigPublicKey::new(pks, weights, threshold)?;
+                let multisig_pk = NestedMultiSigPublicKey::new(pks, weights, threshold)?;
                 let address: IotaAddress = (&multisig_pk).into();
-                let multisig = MultiSig::combine(sigs, multisig_pk)?;
-                let generic_sig: GenericSignature = multisig.into();
+                let generic_sig: GenericSignature = match multisig_pk.as_flat() {
+                    Some(multisig_pk) => MultiSig::combine(sigs, multisig_pk)?.into(),
+                    None => NestedMultiSig::combine(sigs, multisig_pk)?.into(),
+                };
                 let multisig_serialized = generic_sig.encode_base64();
                 CommandOutput::MultiSigCombinePartialSig(MultiSigCombinePartialSig {
                     multisig_address: address,
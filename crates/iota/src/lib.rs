@@ -15,7 +15,9 @@ pub mod genesis_inspector;
 pub mod iota_commands;
 pub mod key_identity;
 pub mod keytool;
+pub mod package_graph;
 pub mod shell;
+pub mod upgrade_compatibility;
 pub mod validator_commands;
 mod verifier_meter;
 // Commented: https://github.com/iotaledger/iota/issues/1777
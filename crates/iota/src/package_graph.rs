@@ -0,0 +1,250 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::BTreeSet,
+    fmt::{Display, Formatter},
+};
+
+use anyhow::{anyhow, bail};
+use iota_json_rpc_types::{IotaObjectDataOptions, IotaRawData};
+use iota_sdk::apis::ReadApi;
+use iota_types::{
+    base_types::{ObjectID, SequenceNumber},
+    move_package::MovePackage,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tabled::{builder::Builder as TableBuilder, settings::Style as TableStyle};
+
+/// The maximum number of objects to fetch in a single request.
+const MULTI_GET_CHUNK_SIZE: usize = 50;
+
+/// Query for a page of the packages that depend on a package.
+const DEPENDENTS_QUERY: &str = r#"
+query ($address: IotaAddress!, $after: String) {
+  package(address: $address) {
+    dependents(after: $after) {
+      pageInfo { hasNextPage endCursor }
+      nodes { address version }
+    }
+  }
+}
+"#;
+
+/// The dependency graph of a package published on chain.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageGraph {
+    /// The package the graph is for.
+    pub root: PackageNode,
+    /// The transitive dependencies of the root package, at the versions that
+    /// it links to according to its linkage table, ordered by original ID.
+    pub dependencies: Vec<PackageNode>,
+    /// The packages that depend on any version of the root package, if they
+    /// were looked up.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependents: Option<Vec<PackageDependent>>,
+}
+
+/// A package in a dependency graph.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageNode {
+    pub original_id: ObjectID,
+    pub package_id: ObjectID,
+    pub version: SequenceNumber,
+    /// The original IDs of the packages that this package refers to directly.
+    pub dependencies: BTreeSet<ObjectID>,
+}
+
+/// A package that depends on a version of another package.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageDependent {
+    #[serde(alias = "address")]
+    pub package_id: ObjectID,
+    pub version: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+impl PackageGraph {
+    /// Build the dependency graph of the package at `package_id` from its
+    /// linkage table and the packages that it links to.
+    pub async fn build(read_api: &ReadApi, package_id: ObjectID) -> anyhow::Result<Self> {
+        let root = fetch_package(read_api, package_id).await?;
+        let linkage = root.linkage_table();
+        let linked = fetch_packages(
+            read_api,
+            linkage.values().map(|info| info.upgraded_id).collect(),
+        )
+        .await?;
+
+        let mut dependencies = Vec::with_capacity(linkage.len());
+        for ((original_id, info), package) in linkage.iter().zip(linked) {
+            dependencies.push(PackageNode {
+                original_id: *original_id,
+                package_id: info.upgraded_id,
+                version: info.upgraded_version,
+                dependencies: package.immediate_dependencies()?,
+            });
+        }
+
+        Ok(Self {
+            root: PackageNode {
+                original_id: root.original_package_id(),
+                package_id: root.id(),
+                version: root.version(),
+                dependencies: root.immediate_dependencies()?,
+            },
+            dependencies,
+            dependents: None,
+        })
+    }
+
+    /// Look up the packages that depend on any version of the root package,
+    /// from the GraphQL service at `url`.
+    pub async fn fetch_dependents(&mut self, url: &str) -> anyhow::Result<()> {
+        let client = reqwest::Client::new();
+        let mut dependents = vec![];
+        let mut after: Option<String> = None;
+
+        loop {
+            let response: Value = client
+                .post(url)
+                .json(&json!({
+                    "query": DEPENDENTS_QUERY,
+                    "variables": { "address": self.root.package_id, "after": after },
+                }))
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            if let Some(errors) = response.get("errors") {
+                bail!("Failed to query dependents from {url}: {errors}");
+            }
+
+            let Some(connection) = response.pointer("/data/package/dependents") else {
+                bail!("Package {} not found at {url}", self.root.package_id);
+            };
+
+            let nodes: Vec<PackageDependent> = serde_json::from_value(connection["nodes"].clone())?;
+            let page_info: PageInfo = serde_json::from_value(connection["pageInfo"].clone())?;
+            dependents.extend(nodes);
+
+            match page_info.end_cursor {
+                Some(cursor) if page_info.has_next_page => after = Some(cursor),
+                _ => break,
+            }
+        }
+
+        self.dependents = Some(dependents);
+        Ok(())
+    }
+}
+
+impl Display for PackageGraph {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Package {} (original ID {}, version {})",
+            self.root.package_id, self.root.original_id, self.root.version,
+        )?;
+
+        if self.dependencies.is_empty() {
+            writeln!(f, "No dependencies")?;
+        } else {
+            let mut builder = TableBuilder::default();
+            builder.set_header([
+                "original id",
+                "linked id",
+                "version",
+                "direct",
+                "depends on",
+            ]);
+            for node in &self.dependencies {
+                builder.push_record([
+                    node.original_id.to_string(),
+                    node.package_id.to_string(),
+                    node.version.value().to_string(),
+                    if self.root.dependencies.contains(&node.original_id) {
+                        "*".to_string()
+                    } else {
+                        "".to_string()
+                    },
+                    node.dependencies
+                        .iter()
+                        .map(ObjectID::to_string)
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                ]);
+            }
+            let mut table = builder.build();
+            table.with(TableStyle::rounded());
+            writeln!(f, "{table}")?;
+        }
+
+        match &self.dependents {
+            None => {}
+            Some(dependents) if dependents.is_empty() => writeln!(f, "No dependents")?,
+            Some(dependents) => {
+                let mut builder = TableBuilder::default();
+                builder.set_header(["dependent id", "version"]);
+                for dependent in dependents {
+                    builder.push_record([
+                        dependent.package_id.to_string(),
+                        dependent.version.to_string(),
+                    ]);
+                }
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                writeln!(f, "{table}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Fetch the package at `package_id` from chain.
+pub(crate) async fn fetch_package(
+    read_api: &ReadApi,
+    package_id: ObjectID,
+) -> anyhow::Result<MovePackage> {
+    fetch_packages(read_api, vec![package_id])
+        .await?
+        .pop()
+        .ok_or_else(|| anyhow!("Package {package_id} not found"))
+}
+
+/// Fetch the packages at `package_ids` from chain, in the same order.
+pub(crate) async fn fetch_packages(
+    read_api: &ReadApi,
+    package_ids: Vec<ObjectID>,
+) -> anyhow::Result<Vec<MovePackage>> {
+    let mut packages = Vec::with_capacity(package_ids.len());
+    for chunk in package_ids.chunks(MULTI_GET_CHUNK_SIZE) {
+        let responses = read_api
+            .multi_get_object_with_options(chunk.to_vec(), IotaObjectDataOptions::bcs_lossless())
+            .await?;
+
+        for (package_id, response) in chunk.iter().zip(responses) {
+            let Some(IotaRawData::Package(package)) = response.into_object()?.bcs else {
+                bail!("Object {package_id} is not a package");
+            };
+            // This package came from on-chain and the tool runs locally, so don't worry
+            // about trying to enforce the package size limit.
+            packages.push(package.to_move_package(u64::MAX)?);
+        }
+    }
+
+    Ok(packages)
+}
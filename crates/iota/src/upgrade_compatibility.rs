@@ -0,0 +1,441 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{Display, Formatter},
+    path::Path,
+};
+
+use anyhow::bail;
+use iota_move::check_upgrade::CheckUpgrade;
+use iota_protocol_config::{Chain, ProtocolConfig};
+use iota_sdk::wallet_context::WalletContext;
+use iota_types::{
+    base_types::{ObjectID, SequenceNumber},
+    execution_config_utils::to_binary_config,
+    move_package::{MovePackage, UpgradeInfo, UpgradePolicy, normalize_deserialized_modules},
+};
+use move_binary_format::{CompiledModule, normalized};
+use move_core_types::account_address::AccountAddress;
+use move_package::BuildConfig as MoveBuildConfig;
+use serde::Serialize;
+use tabled::{builder::Builder as TableBuilder, settings::Style as TableStyle};
+
+use crate::{
+    client_commands::{compile_package, published_at_error},
+    package_graph::{fetch_package, fetch_packages},
+};
+
+/// The policies a package can be upgraded under, from the most to the least
+/// permissive.
+const UPGRADE_POLICIES: [UpgradePolicy; 3] = [
+    UpgradePolicy::Compatible,
+    UpgradePolicy::Additive,
+    UpgradePolicy::DepOnly,
+];
+
+/// Whether a local build of a package can be published as an upgrade of a
+/// package on chain, and how it differs from it.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradeCompatibilityReport {
+    /// The package on chain that the upgrade was checked against.
+    pub package_id: ObjectID,
+    pub version: SequenceNumber,
+    /// Why the upgraded package could not be created, e.g. because it
+    /// cannot be linked to its dependencies, in which case the upgrade fails
+    /// under any policy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package_error: Option<String>,
+    pub policies: Vec<PolicyCompatibility>,
+    /// The modules that were added, removed, or changed.
+    pub modules: Vec<ModuleChange>,
+    /// The dependencies whose linked version changed.
+    pub dependencies: Vec<DependencyChange>,
+}
+
+/// Whether the upgrade is allowed under an upgrade policy.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyCompatibility {
+    pub policy: String,
+    pub compatible: bool,
+    /// The existing modules that are not upgraded in a way that the policy
+    /// allows.
+    pub incompatible_modules: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleChange {
+    pub module: String,
+    pub change: Change,
+    /// The structs, enums and functions of the module that were added,
+    /// removed or changed, if the module was changed.
+    pub items: Vec<ItemChange>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemChange {
+    pub kind: ItemKind,
+    pub name: String,
+    pub change: Change,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ItemKind {
+    Struct,
+    Enum,
+    Function,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Change {
+    Added,
+    Removed,
+    /// The definition changed: The layout of a datatype, or the signature of a
+    /// function.
+    Changed,
+    /// Only the code of a function changed.
+    CodeChanged,
+}
+
+/// A dependency that the upgrade links to a different version of, that it
+/// starts depending on, or that it no longer depends on.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyChange {
+    pub original_id: ObjectID,
+    pub current: Option<SequenceNumber>,
+    pub upgraded: Option<SequenceNumber>,
+}
+
+/// Build the package at `package_path` and check it as an upgrade of the
+/// package on chain that `cmd` refers to.
+pub async fn check_upgrade(
+    cmd: &CheckUpgrade,
+    package_path: &Path,
+    build_config: MoveBuildConfig,
+    context: &WalletContext,
+) -> anyhow::Result<UpgradeCompatibilityReport> {
+    let client = context.get_client().await?;
+    let read_api = client.read_api();
+
+    let (dependencies, compiled_modules, _, published_at) = compile_package(
+        read_api,
+        build_config,
+        package_path,
+        cmd.with_unpublished_dependencies,
+        cmd.skip_dependency_verification,
+    )
+    .await?;
+
+    let package_id = match cmd.package_id {
+        Some(package_id) => package_id,
+        None => {
+            let env_alias = context
+                .config()
+                .get_active_env()
+                .map(|e| e.alias().clone())
+                .ok();
+            published_at.map_err(|e| published_at_error(e, env_alias))?
+        }
+    };
+
+    let protocol_version = read_api.get_protocol_config(None).await?.protocol_version;
+    let Some(protocol_config) =
+        ProtocolConfig::get_for_version_if_supported(protocol_version, Chain::Unknown)
+    else {
+        bail!(
+            "The network runs protocol version {}, which this binary does not support",
+            protocol_version.as_u64()
+        );
+    };
+
+    let binary_config = to_binary_config(&protocol_config);
+    let modules = compiled_modules
+        .iter()
+        .map(|bytes| CompiledModule::deserialize_with_config(bytes, &binary_config))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let existing = fetch_package(read_api, package_id).await?;
+    let dependencies =
+        fetch_packages(read_api, dependencies.published.into_values().collect()).await?;
+
+    check_upgrade_compatibility(&existing, modules, &dependencies, &protocol_config)
+}
+
+/// Check `modules`, linked to `dependencies`, as an upgrade of the `existing`
+/// package, following the same rules as the execution of an upgrade.
+pub fn check_upgrade_compatibility(
+    existing: &MovePackage,
+    mut modules: Vec<CompiledModule>,
+    dependencies: &[MovePackage],
+    protocol_config: &ProtocolConfig,
+) -> anyhow::Result<UpgradeCompatibilityReport> {
+    if modules.is_empty() {
+        bail!("The package to check has no modules");
+    }
+
+    substitute_package_id(&mut modules, existing.original_package_id())?;
+
+    let binary_config = to_binary_config(protocol_config);
+    let current = existing.normalize(&binary_config)?;
+    let upgraded = normalize_deserialized_modules(modules.iter());
+
+    let policies = UPGRADE_POLICIES
+        .iter()
+        .map(|policy| {
+            let incompatible_modules: Vec<_> = current
+                .iter()
+                .filter(|(name, module)| match upgraded.get(*name) {
+                    Some(upgraded) => policy.check_compatibility(module, upgraded).is_err(),
+                    None => true,
+                })
+                .map(|(name, _)| name.clone())
+                .collect();
+
+            PolicyCompatibility {
+                policy: policy.to_string(),
+                compatible: incompatible_modules.is_empty(),
+                incompatible_modules,
+            }
+        })
+        .collect();
+
+    // Create the package as the upgrade would, to check its linkage.
+    let (package_error, linkage_table) =
+        match existing.new_upgraded(ObjectID::ZERO, &modules, protocol_config, dependencies) {
+            Ok(package) => (None, package.linkage_table().clone()),
+            Err(e) => (Some(e.to_string()), BTreeMap::new()),
+        };
+
+    let dependencies = if package_error.is_some() {
+        vec![]
+    } else {
+        diff_linkage(existing, &linkage_table)
+    };
+
+    Ok(UpgradeCompatibilityReport {
+        package_id: existing.id(),
+        version: existing.version(),
+        package_error,
+        policies,
+        modules: diff_modules(&current, &upgraded),
+        dependencies,
+    })
+}
+
+/// Set the self address of `modules`, which must be 0x0 before publishing, to
+/// `package_id`.
+fn substitute_package_id(
+    modules: &mut [CompiledModule],
+    package_id: ObjectID,
+) -> anyhow::Result<()> {
+    for module in modules {
+        let index = module.self_handle().address.0 as usize;
+        let Some(address) = module.address_identifiers.get_mut(index) else {
+            bail!("Module {} has an invalid address index", module.self_id());
+        };
+
+        *address = AccountAddress::from(package_id);
+    }
+
+    Ok(())
+}
+
+fn diff_modules(
+    current: &BTreeMap<String, normalized::Module>,
+    upgraded: &BTreeMap<String, normalized::Module>,
+) -> Vec<ModuleChange> {
+    let names: BTreeSet<_> = current.keys().chain(upgraded.keys()).collect();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let change = match (current.get(name), upgraded.get(name)) {
+                (Some(_), None) => (Change::Removed, vec![]),
+                (None, Some(_)) => (Change::Added, vec![]),
+                (Some(current), Some(upgraded)) => {
+                    let items = diff_items(current, upgraded);
+                    if items.is_empty() {
+                        return None;
+                    }
+                    (Change::Changed, items)
+                }
+                (None, None) => return None,
+            };
+
+            Some(ModuleChange {
+                module: name.clone(),
+                change: change.0,
+                items: change.1,
+            })
+        })
+        .collect()
+}
+
+fn diff_items(current: &normalized::Module, upgraded: &normalized::Module) -> Vec<ItemChange> {
+    let mut items = vec![];
+    diff_definitions(
+        ItemKind::Struct,
+        &current.structs,
+        &upgraded.structs,
+        |_, _| Change::Changed,
+        &mut items,
+    );
+    diff_definitions(
+        ItemKind::Enum,
+        &current.enums,
+        &upgraded.enums,
+        |_, _| Change::Changed,
+        &mut items,
+    );
+    diff_definitions(
+        ItemKind::Function,
+        &current.functions,
+        &upgraded.functions,
+        |current, upgraded| {
+            let signature = |f: &normalized::Function| {
+                (
+                    f.visibility,
+                    f.is_entry,
+                    f.type_parameters.clone(),
+                    f.parameters.clone(),
+                    f.return_.clone(),
+                )
+            };
+            if signature(current) == signature(upgraded) {
+                Change::CodeChanged
+            } else {
+                Change::Changed
+            }
+        },
+        &mut items,
+    );
+    items
+}
+
+/// Record the definitions that were added, removed, or changed between
+/// `current` and `upgraded`, classifying changed definitions with `changed`.
+fn diff_definitions<K: Display + Ord, V: PartialEq>(
+    kind: ItemKind,
+    current: &BTreeMap<K, V>,
+    upgraded: &BTreeMap<K, V>,
+    changed: impl Fn(&V, &V) -> Change,
+    items: &mut Vec<ItemChange>,
+) {
+    let names: BTreeSet<_> = current.keys().chain(upgraded.keys()).collect();
+    for name in names {
+        let change = match (current.get(name), upgraded.get(name)) {
+            (Some(_), None) => Change::Removed,
+            (None, Some(_)) => Change::Added,
+            (Some(current), Some(upgraded)) if current != upgraded => changed(current, upgraded),
+            _ => continue,
+        };
+
+        items.push(ItemChange {
+            kind,
+            name: name.to_string(),
+            change,
+        });
+    }
+}
+
+fn diff_linkage(
+    existing: &MovePackage,
+    linkage_table: &BTreeMap<ObjectID, UpgradeInfo>,
+) -> Vec<DependencyChange> {
+    let current = existing.linkage_table();
+    let original_ids: BTreeSet<_> = current.keys().chain(linkage_table.keys()).collect();
+    original_ids
+        .into_iter()
+        .filter_map(|original_id| {
+            let current = current.get(original_id).map(|info| info.upgraded_version);
+            let upgraded = linkage_table
+                .get(original_id)
+                .map(|info| info.upgraded_version);
+            (current != upgraded).then_some(DependencyChange {
+                original_id: *original_id,
+                current,
+                upgraded,
+            })
+        })
+        .collect()
+}
+
+impl Display for UpgradeCompatibilityReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Upgrade of package {} (version {})",
+            self.package_id,
+            self.version.value()
+        )?;
+
+        if let Some(error) = &self.package_error {
+            writeln!(f, "The upgraded package cannot be created: {error}")?;
+        }
+
+        let mut builder = TableBuilder::default();
+        builder.set_header(["policy", "compatible", "incompatible modules"]);
+        for policy in &self.policies {
+            builder.push_record([
+                policy.policy.clone(),
+                policy.compatible.to_string(),
+                policy.incompatible_modules.join("\n"),
+            ]);
+        }
+        let mut table = builder.build();
+        table.with(TableStyle::rounded());
+        writeln!(f, "{table}")?;
+
+        if self.modules.is_empty() {
+            writeln!(f, "No modules changed")?;
+        } else {
+            let mut builder = TableBuilder::default();
+            builder.set_header(["module", "change", "item", "item change"]);
+            for module in &self.modules {
+                builder.push_record([
+                    module.module.clone(),
+                    format!("{:?}", module.change),
+                    String::new(),
+                    String::new(),
+                ]);
+                for item in &module.items {
+                    builder.push_record([
+                        String::new(),
+                        String::new(),
+                        format!("{:?} {}", item.kind, item.name),
+                        format!("{:?}", item.change),
+                    ]);
+                }
+            }
+            let mut table = builder.build();
+            table.with(TableStyle::rounded());
+            writeln!(f, "{table}")?;
+        }
+
+        if !self.dependencies.is_empty() {
+            let mut builder = TableBuilder::default();
+            builder.set_header(["dependency", "current version", "upgraded version"]);
+            let version =
+                |v: Option<SequenceNumber>| v.map_or("-".to_string(), |v| v.value().to_string());
+            for dependency in &self.dependencies {
+                builder.push_record([
+                    dependency.original_id.to_string(),
+                    version(dependency.current),
+                    version(dependency.upgraded),
+                ]);
+            }
+            let mut table = builder.build();
+            table.with(TableStyle::rounded());
+            writeln!(f, "{table}")?;
+        }
+
+        Ok(())
+    }
+}
@@ -25,12 +25,15 @@ use expect_test::expect;
 use iota::iota_commands::IndexerFeatureArgs;
 use iota::{
     client_commands::{
-        EmitOption, IotaClientCommandResult, IotaClientCommands, Opts, OptsWithGas, SwitchResponse,
-        estimate_gas_budget,
+        EmitOption, EventFilterArgs, HistoryDirection, IotaClientCommandResult, IotaClientCommands,
+        Opts, OptsWithGas, StardustClaimStatusOutput, StardustCommand, SwitchResponse,
+        WatchAddressCommand, estimate_gas_budget,
     },
     client_ptb::ptb::PTB,
     iota_commands::{IotaCommand, parse_host_port},
     key_identity::{KeyIdentity, get_identity_address},
+    package_graph::PackageGraph,
+    upgrade_compatibility::{Change, check_upgrade},
 };
 use iota_config::{
     IOTA_CLIENT_CONFIG, IOTA_FULLNODE_CONFIG, IOTA_GENESIS_FILENAME,
@@ -45,7 +48,9 @@ use iota_json_rpc_types::{
 };
 use iota_keys::keystore::AccountKeystore;
 use iota_macros::sim_test;
+use iota_move::check_upgrade::CheckUpgrade;
 use iota_move_build::{BuildConfig, IotaPackageHooks};
+use iota_protocol_config::ProtocolConfig;
 use iota_sdk::{IotaClient, iota_client_config::IotaClientConfig, wallet_context::WalletContext};
 use iota_swarm_config::{
     genesis_config::{AccountConfig, DEFAULT_NUMBER_OF_AUTHORITIES, GenesisConfig},
@@ -53,18 +58,29 @@ use iota_swarm_config::{
 };
 use iota_test_transaction_builder::batch_make_transfer_transactions;
 use iota_types::{
-    base_types::{IotaAddress, ObjectID},
+    balance::Balance,
+    base_types::{IotaAddress, ObjectID, TxContext},
+    collection_types::Bag,
     crypto::{
         AccountKeyPair, Ed25519IotaSignature, IotaKeyPair, IotaSignatureInner,
         Secp256k1IotaSignature, SignatureScheme, get_key_pair,
     },
     error::IotaObjectResponseError,
     gas_coin::GasCoin,
-    object::Owner,
+    id::UID,
+    object::{OBJECT_START_VERSION, Object, Owner},
+    parse_iota_struct_tag,
+    stardust::{
+        coin_type::CoinType,
+        output::{
+            BasicOutput,
+            unlock_conditions::{StorageDepositReturnUnlockCondition, TimelockUnlockCondition},
+        },
+    },
     transaction::{
         TEST_ONLY_GAS_UNIT_FOR_GENERIC, TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS,
         TEST_ONLY_GAS_UNIT_FOR_PUBLISH, TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN,
-        TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        TEST_ONLY_GAS_UNIT_FOR_TRANSFER, TransactionDataAPI,
     },
 };
 use move_package::{BuildConfig as MoveBuildConfig, lock_file::schema::ManagedPackage};
@@ -1902,6 +1918,108 @@ async fn test_package_upgrade_command() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_move_check_upgrade_and_dependency_graph() -> Result<(), anyhow::Error> {
+    move_package::package_hooks::register_package_hooks(Box::new(IotaPackageHooks));
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let context = &mut test_cluster.wallet;
+
+    // Work on a copy of the package, so that it can be modified.
+    let tmp_dir = tempfile::tempdir().unwrap();
+    fs_extra::dir::copy(
+        PathBuf::from(TEST_DATA_DIR).join("dummy_modules_upgrade"),
+        tmp_dir.path(),
+        &fs_extra::dir::CopyOptions::default(),
+    )
+    .unwrap();
+    let package_path = tmp_dir.path().join("dummy_modules_upgrade");
+
+    let resp = IotaClientCommands::Publish {
+        package_path: package_path.clone(),
+        build_config: BuildConfig::new_for_testing().config,
+        opts: OptsWithGas::for_testing(None, rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
+        skip_dependency_verification: false,
+        with_unpublished_dependencies: false,
+    }
+    .execute(context)
+    .await?;
+
+    let IotaClientCommandResult::TransactionBlock(response) = resp else {
+        unreachable!("Invalid response");
+    };
+    let IotaTransactionBlockEffects::V1(effects) = response.effects.unwrap();
+    assert!(effects.status.is_ok());
+    let package_id = effects
+        .created()
+        .iter()
+        .find(|refe| matches!(refe.owner, Owner::Immutable))
+        .unwrap()
+        .reference
+        .object_id;
+
+    let cmd = CheckUpgrade {
+        package_id: Some(package_id),
+        with_unpublished_dependencies: false,
+        skip_dependency_verification: false,
+        json: false,
+    };
+
+    // The unmodified package is a compatible upgrade under every policy.
+    let report = check_upgrade(
+        &cmd,
+        &package_path,
+        BuildConfig::new_for_testing().config,
+        context,
+    )
+    .await?;
+    assert_eq!(report.package_id, package_id);
+    assert!(report.package_error.is_none());
+    assert!(report.policies.iter().all(|p| p.compatible));
+    assert!(report.modules.is_empty());
+    assert!(report.dependencies.is_empty());
+
+    // Adding a function is allowed by the additive policy, but not by the
+    // dependency-only policy.
+    std::fs::write(
+        package_path.join("sources").join("trusted_coin.move"),
+        "module examples::trusted_coin {\n    public fun f() { }\n    public fun g() { }\n}\n",
+    )?;
+    let report = check_upgrade(
+        &cmd,
+        &package_path,
+        BuildConfig::new_for_testing().config,
+        context,
+    )
+    .await?;
+    let compatible: Vec<_> = report
+        .policies
+        .iter()
+        .map(|p| (p.policy.as_str(), p.compatible))
+        .collect();
+    assert_eq!(compatible, vec![
+        ("COMPATIBLE", true),
+        ("ADDITIVE", true),
+        ("DEP_ONLY", false)
+    ]);
+    assert_eq!(report.modules.len(), 1);
+    assert_eq!(report.modules[0].module, "trusted_coin");
+    assert_eq!(report.modules[0].change, Change::Changed);
+    assert_eq!(report.modules[0].items.len(), 1);
+    assert_eq!(report.modules[0].items[0].name, "g");
+    assert_eq!(report.modules[0].items[0].change, Change::Added);
+
+    // The package links to the framework packages.
+    let client = context.get_client().await?;
+    let graph = PackageGraph::build(client.read_api(), package_id).await?;
+    assert_eq!(graph.root.package_id, package_id);
+    let linked: BTreeSet<_> = graph.dependencies.iter().map(|d| d.original_id).collect();
+    assert!(linked.contains(&ObjectID::from_single_byte(1)));
+    assert!(linked.contains(&ObjectID::from_single_byte(2)));
+
+    Ok(())
+}
+
 #[sim_test]
 async fn test_package_management_on_upgrade_command() -> Result<(), anyhow::Error> {
     move_package::package_hooks::register_package_hooks(Box::new(IotaPackageHooks));
@@ -3043,6 +3161,103 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_watch_only_address() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let address = test_cluster.get_address_0();
+    let watched = IotaAddress::random_for_testing_only();
+    let context = &mut test_cluster.wallet;
+    let opts = |serialize_unsigned_transaction| Opts {
+        gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+        dry_run: false,
+        serialize_unsigned_transaction,
+        serialize_signed_transaction: false,
+        emit: HashSet::new(),
+    };
+
+    IotaClientCommands::WatchAddress {
+        cmd: WatchAddressCommand::Add {
+            address: watched,
+            alias: "cold".to_string(),
+        },
+    }
+    .execute(context)
+    .await?;
+    let alias = KeyIdentity::Alias("cold".to_string());
+
+    // The alias can be used as a recipient
+    let client = context.get_client().await?;
+    let coins = client
+        .coin_read_api()
+        .get_coins(address, None, None, None)
+        .await?
+        .data;
+    IotaClientCommands::TransferIota {
+        to: alias.clone(),
+        iota_coin_object_id: coins[0].coin_object_id,
+        amount: None,
+        opts: opts(false),
+    }
+    .execute(context)
+    .await?;
+
+    let gas = IotaClientCommands::Gas {
+        address: Some(alias.clone()),
+    }
+    .execute(context)
+    .await?;
+    let IotaClientCommandResult::Gas(gas) = gas else {
+        panic!("Expected gas coins, got {gas:?}");
+    };
+    assert_eq!(gas.len(), 1);
+    assert_eq!(*gas[0].id(), coins[0].coin_object_id);
+
+    // The watched address can be the active address
+    IotaClientCommands::Switch {
+        address: Some(alias),
+        env: None,
+    }
+    .execute(context)
+    .await?;
+    assert_eq!(context.active_address()?, watched);
+
+    // Its transactions can be serialized, but not signed
+    let transfer = IotaClientCommands::TransferIota {
+        to: KeyIdentity::Address(address),
+        iota_coin_object_id: coins[0].coin_object_id,
+        amount: Some(1),
+        opts: opts(true),
+    }
+    .execute(context)
+    .await?;
+    let IotaClientCommandResult::SerializedUnsignedTransaction(tx_data) = transfer else {
+        panic!("Expected an unsigned transaction, got {transfer:?}");
+    };
+    assert_eq!(tx_data.sender(), watched);
+    let err = IotaClientCommands::TransferIota {
+        to: KeyIdentity::Address(address),
+        iota_coin_object_id: coins[0].coin_object_id,
+        amount: Some(1),
+        opts: opts(false),
+    }
+    .execute(context)
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("watch-only"), "{err}");
+
+    IotaClientCommands::WatchAddress {
+        cmd: WatchAddressCommand::Remove {
+            alias: "cold".to_string(),
+        },
+    }
+    .execute(context)
+    .await?;
+    assert!(!context.config().is_watch_only(&watched));
+    assert_ne!(context.active_address()?, watched);
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_stake_with_none_amount() -> Result<(), anyhow::Error> {
     let mut test_cluster = TestClusterBuilder::new().build().await;
@@ -3147,6 +3362,121 @@ async fn test_stake_with_u64_amount() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_history_and_events() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let address = test_cluster.get_address_0();
+    let context = &mut test_cluster.wallet;
+
+    let client = context.get_client().await?;
+    let coins = client
+        .coin_read_api()
+        .get_coins(address, None, None, None)
+        .await?
+        .data;
+
+    let config_path = test_cluster.swarm.dir().join(IOTA_CLIENT_CONFIG);
+    let validator_addr = client
+        .governance_api()
+        .get_latest_iota_system_state()
+        .await?
+        .active_validators[0]
+        .iota_address;
+
+    test_with_iota_binary(&[
+        "client",
+        "--client.config",
+        config_path.to_str().unwrap(),
+        "call",
+        "--package",
+        "0x3",
+        "--module",
+        "iota_system",
+        "--function",
+        "request_add_stake_mul_coin",
+        "--args",
+        "0x5",
+        &format!("[{}]", coins.first().unwrap().coin_object_id),
+        "[]",
+        &validator_addr.to_string(),
+        "--gas-budget",
+        "1000000000",
+    ])
+    .await?;
+
+    // The staking transaction is the only one of the address calling
+    // `request_add_stake_mul_coin`
+    let history = IotaClientCommands::History {
+        address: Some(KeyIdentity::Address(address)),
+        direction: HistoryDirection::Sent,
+        function: Some("0x3::iota_system::request_add_stake_mul_coin".parse()?),
+        from_checkpoint: None,
+        to_checkpoint: None,
+        start_time: None,
+        end_time: None,
+        limit: 10,
+        ascending: false,
+    }
+    .execute(context)
+    .await?;
+    let IotaClientCommandResult::TransactionHistory(history) = history else {
+        panic!("Expected the transaction history, got {history:?}");
+    };
+    assert_eq!(history.transactions.len(), 1);
+    let digest = history.transactions[0].digest;
+
+    // It emitted a staking request event
+    let events = IotaClientCommands::Events {
+        filter: EventFilterArgs {
+            sender: Some(KeyIdentity::Address(address)),
+            module: None,
+            event_type: Some(parse_iota_struct_tag(
+                "0x3::validator::StakingRequestEvent",
+            )?),
+            start_time: None,
+            end_time: None,
+        },
+        cursor: None,
+        limit: 10,
+        ascending: false,
+        follow: false,
+    }
+    .execute(context)
+    .await?;
+    let IotaClientCommandResult::Events(page) = events else {
+        panic!("Expected events, got {events:?}");
+    };
+    assert_eq!(page.data.len(), 1);
+    assert_eq!(page.data[0].id.tx_digest, digest);
+
+    test_with_iota_binary(&[
+        "client",
+        "--client.config",
+        config_path.to_str().unwrap(),
+        "history",
+        "--direction",
+        "received",
+        "--from-checkpoint",
+        "0",
+        "--json",
+    ])
+    .await?;
+    test_with_iota_binary(&[
+        "client",
+        "--client.config",
+        config_path.to_str().unwrap(),
+        "events",
+        "--module",
+        "0x3::iota_system",
+        "--start-time",
+        "0",
+        "--limit",
+        "5",
+    ])
+    .await?;
+    Ok(())
+}
+
 async fn test_with_iota_binary(args: &[&str]) -> Result<(), anyhow::Error> {
     let mut cmd = assert_cmd::Command::cargo_bin("iota").unwrap();
     let args = args.iter().map(|s| s.to_string()).collect::<Vec<_>>();
@@ -4673,3 +5003,107 @@ async fn test_call_command_emit_args() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[sim_test]
+async fn test_stardust_list_command() -> Result<(), anyhow::Error> {
+    let owner = IotaAddress::random_for_testing_only();
+    let return_address = IotaAddress::random_for_testing_only();
+    let protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let tx_context = TxContext::random_for_testing_only();
+    let basic_output = |storage_deposit_return, timelock| -> Object {
+        BasicOutput {
+            id: UID::new(ObjectID::random()),
+            balance: Balance::new(1_000_000),
+            native_tokens: Bag::default(),
+            storage_deposit_return,
+            timelock,
+            expiration: None,
+            metadata: None,
+            tag: None,
+            sender: None,
+        }
+        .to_genesis_object(
+            owner,
+            &protocol_config,
+            &tx_context,
+            OBJECT_START_VERSION,
+            &CoinType::Iota,
+        )
+        .unwrap()
+    };
+    let claimable = basic_output(None, Some(TimelockUnlockCondition { unix_time: 1 }));
+    let timelocked = basic_output(
+        None,
+        Some(TimelockUnlockCondition {
+            unix_time: u32::MAX,
+        }),
+    );
+    let needs_return = basic_output(
+        Some(StorageDepositReturnUnlockCondition {
+            return_address,
+            return_amount: 400_000,
+        }),
+        None,
+    );
+
+    let mut test_cluster = TestClusterBuilder::new()
+        .with_objects([claimable.clone(), timelocked.clone(), needs_return.clone()])
+        .build()
+        .await;
+    let context = &mut test_cluster.wallet;
+
+    let IotaClientCommandResult::StardustOutputs(outputs) = StardustCommand::List {
+        address: Some(KeyIdentity::Address(owner)),
+        graphql: None,
+    }
+    .execute(context)
+    .await?
+    else {
+        panic!("Expected the Stardust outputs");
+    };
+    assert_eq!(outputs.len(), 3);
+    for output in &outputs {
+        assert_eq!(output.kind, "Basic");
+        assert_eq!(output.balance, 1_000_000);
+        assert!(output.native_tokens.is_empty());
+        if output.object_id == claimable.id() {
+            assert!(matches!(
+                output.status,
+                StardustClaimStatusOutput::ClaimableNow
+            ));
+            assert_eq!(output.claimable_amount, 1_000_000);
+        } else if output.object_id == timelocked.id() {
+            assert!(matches!(
+                output.status,
+                StardustClaimStatusOutput::ClaimableLater {
+                    unix_time: u32::MAX
+                }
+            ));
+        } else if output.object_id == needs_return.id() {
+            assert!(matches!(
+                output.status,
+                StardustClaimStatusOutput::NeedsReturn {
+                    return_address: address,
+                    return_amount: 400_000,
+                } if address == return_address
+            ));
+            assert_eq!(output.claimable_amount, 600_000);
+        } else {
+            panic!("Unexpected output {}", output.object_id);
+        }
+    }
+
+    // Outputs are only listed for their owner.
+    let IotaClientCommandResult::StardustOutputs(outputs) = StardustCommand::List {
+        address: Some(KeyIdentity::Address(return_address)),
+        graphql: None,
+    }
+    .execute(context)
+    .await?
+    else {
+        panic!("Expected the Stardust outputs");
+    };
+    assert!(outputs.is_empty());
+
+    Ok(())
+}
@@ -8,6 +8,7 @@ use anyhow::Result;
 use iota_config::transaction_deny_config::TransactionDenyConfig;
 use iota_execution::Executor;
 use iota_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
+use iota_transaction_checks::deny::DenyListEntries;
 use iota_types::{
     committee::{Committee, EpochId},
     effects::TransactionEffects,
@@ -110,6 +111,7 @@ impl EpochState {
             &input_object_kinds,
             &receiving_object_refs,
             deny_config,
+            &DenyListEntries::default(),
             &store,
         )?;
 
@@ -38,7 +38,9 @@ mod checked {
         digests::{ChainIdentifier, get_mainnet_chain_identifier, get_testnet_chain_identifier},
         effects::TransactionEffects,
         error::{ExecutionError, ExecutionErrorKind},
-        execution::{ExecutionResults, ExecutionResultsV1, is_certificate_denied},
+        execution::{
+            ExecutionInstrumentation, ExecutionResults, ExecutionResultsV1, is_certificate_denied,
+        },
         execution_config_utils::to_binary_config,
         execution_status::{CongestedObjects, ExecutionStatus},
         gas::{GasCostSummary, IotaGasStatus},
@@ -84,7 +86,8 @@ mod checked {
     /// `TransactionKind`. It checks for any expensive operations, manages
     /// shared object references, and ensures transaction dependencies are
     /// met. The returned objects are not committed to the store until the
-    /// resulting effects are applied by the caller.
+    /// resulting effects are applied by the caller. The diagnostics requested
+    /// in `instrumentation` are recorded for programmable transactions.
     #[instrument(name = "tx_execute_to_effects", level = "debug", skip_all)]
     pub fn execute_transaction_to_effects<Mode: ExecutionMode>(
         store: &dyn BackingStore,
@@ -101,6 +104,7 @@ mod checked {
         metrics: Arc<LimitsMetrics>,
         enable_expensive_checks: bool,
         certificate_deny_set: &HashSet<TransactionDigest>,
+        instrumentation: &mut ExecutionInstrumentation,
     ) -> (
         InnerTemporaryStore,
         IotaGasStatus,
@@ -153,6 +157,7 @@ mod checked {
             deny_cert,
             contains_deleted_input,
             cancelled_objects,
+            instrumentation,
         );
 
         let status = if let Err(error) = &execution_result {
@@ -301,6 +306,7 @@ mod checked {
         deny_cert: bool,
         contains_deleted_input: bool,
         cancelled_objects: Option<(Vec<ObjectID>, SequenceNumber)>,
+        instrumentation: &mut ExecutionInstrumentation,
     ) -> (
         GasCostSummary,
         Result<Mode::ExecutionResults, ExecutionError>,
@@ -356,6 +362,7 @@ mod checked {
                     gas_charger,
                     protocol_config,
                     metrics.clone(),
+                    instrumentation,
                 )
             };
 
@@ -603,6 +610,7 @@ mod checked {
         gas_charger: &mut GasCharger,
         protocol_config: &ProtocolConfig,
         metrics: Arc<LimitsMetrics>,
+        instrumentation: &mut ExecutionInstrumentation,
     ) -> Result<Mode::ExecutionResults, ExecutionError> {
         let result = match transaction_kind {
             TransactionKind::Genesis(GenesisTransaction { objects, events }) => {
@@ -647,7 +655,7 @@ mod checked {
                 Ok(Mode::empty_results())
             }
             TransactionKind::ProgrammableTransaction(pt) => {
-                programmable_transactions::execution::execute::<Mode>(
+                programmable_transactions::execution::execute_instrumented::<Mode>(
                     protocol_config,
                     metrics,
                     move_vm,
@@ -655,6 +663,7 @@ mod checked {
                     tx_ctx,
                     gas_charger,
                     pt,
+                    instrumentation,
                 )
             }
             TransactionKind::EndOfEpochTransaction(txns) => {
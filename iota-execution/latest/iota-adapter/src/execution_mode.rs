@@ -35,6 +35,10 @@ pub trait ExecutionMode {
     /// UpgradeCap is produced
     fn packages_are_predefined() -> bool;
 
+    /// Allow recording a trace of the Move execution of each command, when
+    /// requested through `ExecutionInstrumentation::execution_trace`
+    fn allow_execution_trace() -> bool;
+
     fn empty_arguments() -> Self::ArgumentUpdates;
 
     fn empty_results() -> Self::ExecutionResults;
@@ -77,6 +81,10 @@ impl ExecutionMode for Normal {
         false
     }
 
+    fn allow_execution_trace() -> bool {
+        false
+    }
+
     fn empty_arguments() -> Self::ArgumentUpdates {}
 
     fn empty_results() -> Self::ExecutionResults {}
@@ -119,6 +127,10 @@ impl ExecutionMode for Genesis {
         true
     }
 
+    fn allow_execution_trace() -> bool {
+        false
+    }
+
     fn skip_conservation_checks() -> bool {
         false
     }
@@ -175,6 +187,10 @@ impl ExecutionMode for System {
         true
     }
 
+    fn allow_execution_trace() -> bool {
+        false
+    }
+
     fn empty_arguments() -> Self::ArgumentUpdates {}
 
     fn empty_results() -> Self::ExecutionResults {}
@@ -223,6 +239,10 @@ impl<const SKIP_ALL_CHECKS: bool> ExecutionMode for DevInspect<SKIP_ALL_CHECKS>
         false
     }
 
+    fn allow_execution_trace() -> bool {
+        true
+    }
+
     fn empty_arguments() -> Self::ArgumentUpdates {
         vec![]
     }
@@ -22,7 +22,7 @@ mod checked {
         coin::Coin,
         error::{ExecutionError, ExecutionErrorKind, command_argument_error},
         event::Event,
-        execution::{ExecutionResults, ExecutionResultsV1},
+        execution::{ExecutionResults, ExecutionResultsV1, TraceStep},
         execution_status::CommandArgumentError,
         metrics::LimitsMetrics,
         move_package::MovePackage,
@@ -59,7 +59,10 @@ mod checked {
             ObjectValue, RawValueType, ResultValue, TryFromValue, UsageKind, Value,
         },
         gas_charger::GasCharger,
-        programmable_transactions::linkage_view::LinkageView,
+        programmable_transactions::{
+            linkage_view::LinkageView,
+            trace::{ExecutionTracer, TracingGasMeter},
+        },
         type_resolver::TypeTagResolver,
     };
 
@@ -102,6 +105,8 @@ mod checked {
         /// if the borrow is mutable This gets cleared out when new
         /// results are pushed, i.e. the end of a command
         borrowed: HashMap<Argument, /* mut */ bool>,
+        /// Records the Move execution of each command when tracing is enabled
+        tracer: Option<ExecutionTracer>,
     }
 
     /// A write for an object that was generated outside of the Move
@@ -233,9 +238,48 @@ mod checked {
                 new_packages: vec![],
                 user_events: vec![],
                 borrowed: HashMap::new(),
+                tracer: None,
             })
         }
 
+        /// Start tracing the Move execution of the commands.
+        pub fn enable_tracing(&mut self) {
+            self.tracer.get_or_insert_with(ExecutionTracer::default);
+        }
+
+        pub fn is_tracing(&self) -> bool {
+            self.tracer.is_some()
+        }
+
+        /// The trace steps recorded since the last call, if tracing is
+        /// enabled.
+        pub fn take_trace_steps(&mut self) -> Vec<TraceStep> {
+            self.tracer
+                .as_mut()
+                .map(ExecutionTracer::take_steps)
+                .unwrap_or_default()
+        }
+
+        /// The contents of the input objects, including the gas coin, or
+        /// `None` for the ones that have been taken.
+        pub fn input_object_contents(&self) -> Vec<(ObjectID, Option<Vec<u8>>)> {
+            std::iter::once(&self.gas)
+                .chain(&self.inputs)
+                .filter_map(|input| {
+                    let Some(InputObjectMetadata::InputObject { id, .. }) = &input.object_metadata
+                    else {
+                        return None;
+                    };
+                    let contents = input.inner.value.as_ref().map(|value| {
+                        let mut bytes = vec![];
+                        value.write_bcs_bytes(&mut bytes);
+                        bytes
+                    });
+                    Some((*id, contents))
+                })
+                .collect()
+        }
+
         pub fn object_runtime(&mut self) -> &ObjectRuntime {
             self.native_extensions.get()
         }
@@ -946,15 +990,45 @@ mod checked {
             ty_args: Vec<Type>,
             args: Vec<impl Borrow<[u8]>>,
         ) -> VMResult<SerializedReturnValues> {
+            let type_arguments = if self.tracer.is_some() {
+                ty_args
+                    .iter()
+                    .map(|ty| {
+                        let tag = self.vm.get_runtime().get_type_tag(ty)?;
+                        Ok(tag.to_canonical_string(/* with_prefix */ true))
+                    })
+                    .collect::<VMResult<Vec<_>>>()?
+            } else {
+                vec![]
+            };
             let gas_status = self.gas_charger.move_gas_status_mut();
             let mut data_store = IotaDataStore::new(&self.linkage_view, &self.new_packages);
+            let Some(tracer) = &mut self.tracer else {
+                return self.vm.get_runtime().execute_function_bypass_visibility(
+                    module,
+                    function_name,
+                    ty_args,
+                    args,
+                    &mut data_store,
+                    gas_status,
+                    &mut self.native_extensions,
+                );
+            };
+            let serialized_arguments: Vec<&[u8]> = args.iter().map(|arg| arg.borrow()).collect();
+            tracer.enter_entry_function(
+                module,
+                function_name.as_str(),
+                type_arguments,
+                &serialized_arguments,
+            );
+            let mut gas_meter = TracingGasMeter::new(gas_status, tracer);
             self.vm.get_runtime().execute_function_bypass_visibility(
                 module,
                 function_name,
                 ty_args,
                 args,
                 &mut data_store,
-                gas_status,
+                &mut gas_meter,
                 &mut self.native_extensions,
             )
         }
@@ -23,8 +23,12 @@ mod checked {
         },
         coin::Coin,
         error::{ExecutionError, ExecutionErrorKind, command_argument_error},
+        execution::{
+            CommandTrace, ExecutionInstrumentation, TracedObjectChange, TracedObjectChangeKind,
+        },
         execution_config_utils::to_binary_config,
         execution_status::{CommandArgumentError, PackageUpgradeError},
+        gas::CommandGasUsage,
         id::{RESOLVED_IOTA_ID, UID},
         metrics::LimitsMetrics,
         move_package::{
@@ -41,7 +45,6 @@ mod checked {
     };
     use move_binary_format::{
         CompiledModule,
-        compatibility::{Compatibility, InclusionCheck},
         errors::{Location, PartialVMResult, VMResult},
         file_format::{AbilitySet, CodeOffset, FunctionDefinitionIndex, LocalIndex, Visibility},
         file_format_common::VERSION_6,
@@ -86,6 +89,30 @@ mod checked {
         tx_context: &mut TxContext,
         gas_charger: &mut GasCharger,
         pt: ProgrammableTransaction,
+    ) -> Result<Mode::ExecutionResults, ExecutionError> {
+        execute_instrumented::<Mode>(
+            protocol_config,
+            metrics,
+            vm,
+            state_view,
+            tx_context,
+            gas_charger,
+            pt,
+            &mut ExecutionInstrumentation::default(),
+        )
+    }
+
+    /// Like `execute`, additionally recording the diagnostics requested in
+    /// `instrumentation` for each command that is run.
+    pub fn execute_instrumented<Mode: ExecutionMode>(
+        protocol_config: &ProtocolConfig,
+        metrics: Arc<LimitsMetrics>,
+        vm: &MoveVM,
+        state_view: &mut dyn ExecutionState,
+        tx_context: &mut TxContext,
+        gas_charger: &mut GasCharger,
+        pt: ProgrammableTransaction,
+        instrumentation: &mut ExecutionInstrumentation,
     ) -> Result<Mode::ExecutionResults, ExecutionError> {
         let ProgrammableTransaction { inputs, commands } = pt;
         let mut context = ExecutionContext::new(
@@ -97,10 +124,54 @@ mod checked {
             gas_charger,
             inputs,
         )?;
+        if Mode::allow_execution_trace() && instrumentation.execution_trace.is_some() {
+            context.enable_tracing();
+        }
         // execute commands
         let mut mode_results = Mode::empty_results();
         for (idx, command) in commands.into_iter().enumerate() {
-            if let Err(err) = execute_command::<Mode>(&mut context, &mut mode_results, command) {
+            let command_gas = instrumentation.command_gas_usage.is_some().then(|| {
+                let gas_used = context
+                    .gas_charger
+                    .move_gas_status()
+                    .gas_used_pre_gas_price();
+                (command_label(&command), gas_used)
+            });
+            let command_trace = context
+                .is_tracing()
+                .then(|| (command_label(&command).0, context.input_object_contents()));
+            let result = execute_command::<Mode>(&mut context, &mut mode_results, command);
+            if let (Some(command_gas_usage), Some(((command, function), gas_used_before))) =
+                (&mut instrumentation.command_gas_usage, command_gas)
+            {
+                let move_gas_status = context.gas_charger.move_gas_status();
+                let gas_units = move_gas_status.gas_used_pre_gas_price() - gas_used_before;
+                command_gas_usage.push(CommandGasUsage {
+                    command_index: idx as u16,
+                    command,
+                    function,
+                    computation_cost: gas_units * move_gas_status.gas_price(),
+                });
+            }
+            if let Some((command, contents_before)) = command_trace {
+                let object_changes =
+                    traced_object_changes(contents_before, context.input_object_contents());
+                let steps = context.take_trace_steps();
+                let error = result.as_ref().err().map(|err| match err.source() {
+                    Some(source) => format!("{:?}: {source}", err.kind()),
+                    None => format!("{:?}", err.kind()),
+                });
+                if let Some(execution_trace) = &mut instrumentation.execution_trace {
+                    execution_trace.commands.push(CommandTrace {
+                        command_index: idx as u16,
+                        command,
+                        steps,
+                        object_changes,
+                        error,
+                    });
+                }
+            }
+            if let Err(err) = result {
                 let object_runtime: &ObjectRuntime = context.object_runtime();
                 // We still need to record the loaded child objects for replay
                 let loaded_runtime_objects = object_runtime.loaded_runtime_objects();
@@ -131,6 +202,54 @@ mod checked {
         Ok(mode_results)
     }
 
+    /// The kind of a command and, for `MoveCall` commands, the function called,
+    /// used to label the gas it was charged and its trace.
+    fn command_label(command: &Command) -> (String, Option<String>) {
+        let kind = match command {
+            Command::MoveCall(call) => {
+                let ProgrammableMoveCall {
+                    package,
+                    module,
+                    function,
+                    ..
+                } = &**call;
+                return (
+                    "MoveCall".to_string(),
+                    Some(format!("{package}::{module}::{function}")),
+                );
+            }
+            Command::TransferObjects(..) => "TransferObjects",
+            Command::SplitCoins(..) => "SplitCoins",
+            Command::MergeCoins(..) => "MergeCoins",
+            Command::Publish(..) => "Publish",
+            Command::MakeMoveVec(..) => "MakeMoveVec",
+            Command::Upgrade(..) => "Upgrade",
+        };
+        (kind.to_string(), None)
+    }
+
+    /// The input objects mutated or taken by a command, given their contents
+    /// before and after it was executed.
+    fn traced_object_changes(
+        contents_before: Vec<(ObjectID, Option<Vec<u8>>)>,
+        contents_after: Vec<(ObjectID, Option<Vec<u8>>)>,
+    ) -> Vec<TracedObjectChange> {
+        contents_before
+            .into_iter()
+            .zip(contents_after)
+            .filter_map(|((object_id, before), (_, after))| {
+                let kind = match (before, after) {
+                    (Some(_), None) => TracedObjectChangeKind::Taken,
+                    (Some(before), Some(after)) if before != after => {
+                        TracedObjectChangeKind::Mutated
+                    }
+                    _ => return None,
+                };
+                Some(TracedObjectChange { object_id, kind })
+            })
+            .collect()
+    }
+
     /// Execute a single command
     #[instrument(level = "trace", skip_all)]
     fn execute_command<Mode: ExecutionMode>(
@@ -689,50 +808,12 @@ mod checked {
                 ));
             };
 
-            check_module_compatibility(&policy, &cur_module, &new_module)?;
+            policy.check_compatibility(&cur_module, &new_module)?;
         }
 
         Ok(())
     }
 
-    /// Verifies the compatibility of two normalized Move modules based on the
-    /// specified upgrade policy. Depending on the policy, it checks if the
-    /// new module is a subset, equal, or compatible with the
-    /// current module. The compatibility check may include aspects like struct
-    /// layout, public function linking, and struct type parameters.
-    fn check_module_compatibility(
-        policy: &UpgradePolicy,
-        cur_module: &normalized::Module,
-        new_module: &normalized::Module,
-    ) -> Result<(), ExecutionError> {
-        match policy {
-            UpgradePolicy::Additive => InclusionCheck::Subset.check(cur_module, new_module),
-            UpgradePolicy::DepOnly => InclusionCheck::Equal.check(cur_module, new_module),
-            UpgradePolicy::Compatible => {
-                let compatibility = Compatibility {
-                    check_datatype_and_pub_function_linking: true,
-                    check_datatype_layout: true,
-                    check_friend_linking: false,
-                    check_private_entry_linking: false,
-                    disallowed_new_abilities: AbilitySet::ALL,
-                    disallow_change_datatype_type_params: true,
-                    // We disallow adding new variants to enums for now
-                    disallow_new_variants: true,
-                };
-
-                compatibility.check(cur_module, new_module)
-            }
-        }
-        .map_err(|e| {
-            ExecutionError::new_with_source(
-                ExecutionErrorKind::PackageUpgradeError {
-                    upgrade_error: PackageUpgradeError::IncompatibleUpgrade,
-                },
-                e,
-            )
-        })
-    }
-
     /// Retrieves a `PackageObject` from the storage based on the provided
     /// `package_id`. It ensures that exactly one package is fetched,
     /// returning an invariant violation if the number of fetched packages
@@ -0,0 +1,639 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tracing of Move execution for dev-inspect. The Move VM reports every call,
+//! native call, return and abort to its gas meter, so tracing is done by
+//! wrapping the gas meter of the transaction in a [`TracingGasMeter`], which
+//! records a [`TraceStep`] for each of them before charging gas as usual.
+
+use std::fmt::Write;
+
+use iota_types::{IOTA_FRAMEWORK_ADDRESS, base_types::ObjectID, execution::TraceStep};
+use move_binary_format::errors::PartialVMResult;
+use move_core_types::{
+    account_address::AccountAddress,
+    gas_algebra::{InternalGas, NumArgs, NumBytes},
+    language_storage::ModuleId,
+    u256::U256,
+};
+use move_vm_profiler::GasProfiler;
+use move_vm_types::{
+    gas::{GasMeter, SimpleInstruction},
+    views::{TypeView, ValueView, ValueVisitor},
+};
+
+/// Rendered values longer than this are truncated. Rendering stops once the
+/// limit is reached, so large values are never rendered in full.
+const MAX_RENDERED_VALUE_LENGTH: usize = 1024;
+
+const EVENT_MODULE_NAME: &str = "event";
+const EVENT_EMIT_FUNCTION_NAME: &str = "emit";
+const DYNAMIC_FIELD_MODULE_NAME: &str = "dynamic_field";
+
+/// The steps recorded for the command being executed.
+#[derive(Default)]
+pub struct ExecutionTracer {
+    /// The functions with a frame on the call stack, and whether their call
+    /// was recorded as a `Call` step rather than as an event or dynamic field
+    /// access.
+    call_stack: Vec<(String, bool)>,
+    /// The native function being executed, if any.
+    native_call: Option<(String, bool)>,
+    steps: Vec<TraceStep>,
+}
+
+impl ExecutionTracer {
+    /// Record the call of the function called by a command, which is not
+    /// reported to the gas meter by the VM.
+    pub fn enter_entry_function(
+        &mut self,
+        module_id: &ModuleId,
+        function_name: &str,
+        type_arguments: Vec<String>,
+        serialized_arguments: &[&[u8]],
+    ) {
+        self.record_call(
+            module_id,
+            function_name,
+            type_arguments,
+            serialized_arguments
+                .iter()
+                .map(|bytes| render_bytes(bytes))
+                .collect(),
+        );
+    }
+
+    /// Take the steps recorded since the last call, resetting the call stack
+    /// left behind by a failed execution.
+    pub fn take_steps(&mut self) -> Vec<TraceStep> {
+        self.call_stack.clear();
+        self.native_call = None;
+        std::mem::take(&mut self.steps)
+    }
+
+    fn depth(&self) -> u16 {
+        self.call_stack.len() as u16
+    }
+
+    fn record_call(
+        &mut self,
+        module_id: &ModuleId,
+        function_name: &str,
+        type_arguments: Vec<String>,
+        arguments: Vec<String>,
+    ) {
+        self.steps.push(TraceStep::Call {
+            depth: self.depth(),
+            function: function_label(module_id, function_name),
+            type_arguments,
+            arguments,
+        });
+        self.call_stack
+            .push((function_label(module_id, function_name), true));
+    }
+
+    /// Record a call reported by the VM. Calls to the natives emitting events
+    /// and accessing dynamic fields are recorded as the corresponding step.
+    fn on_call(
+        &mut self,
+        module_id: &ModuleId,
+        function_name: &str,
+        type_arguments: Vec<String>,
+        arguments: &[impl ValueView],
+    ) {
+        if module_id.address() != &IOTA_FRAMEWORK_ADDRESS {
+            let arguments = arguments.iter().map(render_value).collect();
+            self.record_call(module_id, function_name, type_arguments, arguments);
+            return;
+        }
+        let depth = self.depth();
+        let step = match (module_id.name().as_str(), function_name) {
+            (EVENT_MODULE_NAME, EVENT_EMIT_FUNCTION_NAME) => TraceStep::Event {
+                depth,
+                event_type: type_arguments.first().cloned().unwrap_or_default(),
+                contents: arguments.first().map(render_value).unwrap_or_default(),
+            },
+            (
+                DYNAMIC_FIELD_MODULE_NAME,
+                "borrow_child_object" | "has_child_object" | "has_child_object_with_ty",
+            ) => TraceStep::DynamicFieldRead {
+                depth,
+                parent: first_object_id(arguments.first()),
+                child: first_object_id(arguments.get(1)),
+            },
+            (
+                DYNAMIC_FIELD_MODULE_NAME,
+                "add_child_object" | "borrow_child_object_mut" | "remove_child_object",
+            ) => TraceStep::DynamicFieldWrite {
+                depth,
+                parent: first_object_id(arguments.first()),
+                child: first_object_id(arguments.get(1)),
+            },
+            _ => {
+                let arguments = arguments.iter().map(render_value).collect();
+                self.record_call(module_id, function_name, type_arguments, arguments);
+                return;
+            }
+        };
+        self.steps.push(step);
+        self.call_stack
+            .push((function_label(module_id, function_name), false));
+    }
+
+    /// A native function is executed right after its call was reported, so it
+    /// has no frame of its own.
+    fn on_native_call(&mut self) {
+        self.native_call = self.call_stack.pop();
+    }
+
+    fn on_native_return(&mut self, return_values: Option<Vec<String>>) {
+        let Some((function, recorded)) = self.native_call.take() else {
+            return;
+        };
+        let depth = self.depth();
+        match return_values {
+            Some(return_values) if recorded => self.steps.push(TraceStep::Return {
+                depth,
+                function,
+                return_values,
+            }),
+            Some(_) => (),
+            None => self.steps.push(TraceStep::Abort { depth, function }),
+        }
+    }
+
+    fn on_return(&mut self) {
+        if let Some((function, _)) = self.call_stack.pop() {
+            self.steps.push(TraceStep::Return {
+                depth: self.depth(),
+                function,
+                return_values: vec![],
+            });
+        }
+    }
+
+    fn on_abort(&mut self) {
+        if let Some((function, _)) = self.call_stack.last() {
+            self.steps.push(TraceStep::Abort {
+                depth: self.depth() - 1,
+                function: function.clone(),
+            });
+        }
+    }
+}
+
+/// A gas meter charging gas exactly like the wrapped one while reporting the
+/// execution to an [`ExecutionTracer`].
+pub struct TracingGasMeter<'g, G> {
+    gas_meter: &'g mut G,
+    tracer: &'g mut ExecutionTracer,
+}
+
+impl<'g, G: GasMeter> TracingGasMeter<'g, G> {
+    pub fn new(gas_meter: &'g mut G, tracer: &'g mut ExecutionTracer) -> Self {
+        Self { gas_meter, tracer }
+    }
+}
+
+impl<G: GasMeter> GasMeter for TracingGasMeter<'_, G> {
+    fn charge_simple_instr(&mut self, instr: SimpleInstruction) -> PartialVMResult<()> {
+        if instr == SimpleInstruction::Abort {
+            self.tracer.on_abort();
+        }
+        self.gas_meter.charge_simple_instr(instr)
+    }
+
+    fn charge_pop(&mut self, popped_val: impl ValueView) -> PartialVMResult<()> {
+        self.gas_meter.charge_pop(popped_val)
+    }
+
+    fn charge_call(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+        num_locals: NumArgs,
+    ) -> PartialVMResult<()> {
+        let args = args.collect::<Vec<_>>();
+        self.tracer.on_call(module_id, func_name, vec![], &args);
+        self.gas_meter
+            .charge_call(module_id, func_name, args.into_iter(), num_locals)
+    }
+
+    fn charge_call_generic(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        ty_args: impl ExactSizeIterator<Item = impl TypeView>,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+        num_locals: NumArgs,
+    ) -> PartialVMResult<()> {
+        let ty_args = ty_args.collect::<Vec<_>>();
+        let args = args.collect::<Vec<_>>();
+        let type_arguments = ty_args
+            .iter()
+            .map(|ty| ty.to_type_tag().to_canonical_string(/* with_prefix */ true))
+            .collect();
+        self.tracer
+            .on_call(module_id, func_name, type_arguments, &args);
+        self.gas_meter.charge_call_generic(
+            module_id,
+            func_name,
+            ty_args.into_iter(),
+            args.into_iter(),
+            num_locals,
+        )
+    }
+
+    fn charge_ld_const(&mut self, size: NumBytes) -> PartialVMResult<()> {
+        self.gas_meter.charge_ld_const(size)
+    }
+
+    fn charge_ld_const_after_deserialization(
+        &mut self,
+        val: impl ValueView,
+    ) -> PartialVMResult<()> {
+        self.gas_meter.charge_ld_const_after_deserialization(val)
+    }
+
+    fn charge_copy_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.gas_meter.charge_copy_loc(val)
+    }
+
+    fn charge_move_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.gas_meter.charge_move_loc(val)
+    }
+
+    fn charge_store_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.gas_meter.charge_store_loc(val)
+    }
+
+    fn charge_pack(
+        &mut self,
+        is_generic: bool,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.gas_meter.charge_pack(is_generic, args)
+    }
+
+    fn charge_unpack(
+        &mut self,
+        is_generic: bool,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.gas_meter.charge_unpack(is_generic, args)
+    }
+
+    fn charge_variant_switch(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.gas_meter.charge_variant_switch(val)
+    }
+
+    fn charge_read_ref(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.gas_meter.charge_read_ref(val)
+    }
+
+    fn charge_write_ref(
+        &mut self,
+        new_val: impl ValueView,
+        old_val: impl ValueView,
+    ) -> PartialVMResult<()> {
+        self.gas_meter.charge_write_ref(new_val, old_val)
+    }
+
+    fn charge_eq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()> {
+        self.gas_meter.charge_eq(lhs, rhs)
+    }
+
+    fn charge_neq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()> {
+        self.gas_meter.charge_neq(lhs, rhs)
+    }
+
+    fn charge_vec_pack<'a>(
+        &mut self,
+        ty: impl TypeView + 'a,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.gas_meter.charge_vec_pack(ty, args)
+    }
+
+    fn charge_vec_len(&mut self, ty: impl TypeView) -> PartialVMResult<()> {
+        self.gas_meter.charge_vec_len(ty)
+    }
+
+    fn charge_vec_borrow(
+        &mut self,
+        is_mut: bool,
+        ty: impl TypeView,
+        is_success: bool,
+    ) -> PartialVMResult<()> {
+        self.gas_meter.charge_vec_borrow(is_mut, ty, is_success)
+    }
+
+    fn charge_vec_push_back(
+        &mut self,
+        ty: impl TypeView,
+        val: impl ValueView,
+    ) -> PartialVMResult<()> {
+        self.gas_meter.charge_vec_push_back(ty, val)
+    }
+
+    fn charge_vec_pop_back(
+        &mut self,
+        ty: impl TypeView,
+        val: Option<impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.gas_meter.charge_vec_pop_back(ty, val)
+    }
+
+    fn charge_vec_unpack(
+        &mut self,
+        ty: impl TypeView,
+        expect_num_elements: NumArgs,
+        elems: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.gas_meter
+            .charge_vec_unpack(ty, expect_num_elements, elems)
+    }
+
+    fn charge_vec_swap(&mut self, ty: impl TypeView) -> PartialVMResult<()> {
+        self.gas_meter.charge_vec_swap(ty)
+    }
+
+    fn charge_native_function(
+        &mut self,
+        amount: InternalGas,
+        ret_vals: Option<impl ExactSizeIterator<Item = impl ValueView>>,
+    ) -> PartialVMResult<()> {
+        let ret_vals = ret_vals.map(|ret_vals| ret_vals.collect::<Vec<_>>());
+        self.tracer.on_native_return(
+            ret_vals
+                .as_ref()
+                .map(|ret_vals| ret_vals.iter().map(render_value).collect()),
+        );
+        self.gas_meter
+            .charge_native_function(amount, ret_vals.map(|ret_vals| ret_vals.into_iter()))
+    }
+
+    fn charge_native_function_before_execution(
+        &mut self,
+        ty_args: impl ExactSizeIterator<Item = impl TypeView>,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.tracer.on_native_call();
+        self.gas_meter
+            .charge_native_function_before_execution(ty_args, args)
+    }
+
+    fn charge_drop_frame(
+        &mut self,
+        locals: impl Iterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.tracer.on_return();
+        self.gas_meter.charge_drop_frame(locals)
+    }
+
+    fn remaining_gas(&self) -> InternalGas {
+        self.gas_meter.remaining_gas()
+    }
+
+    fn get_profiler_mut(&mut self) -> Option<&mut GasProfiler> {
+        self.gas_meter.get_profiler_mut()
+    }
+
+    fn set_profiler(&mut self, profiler: GasProfiler) {
+        self.gas_meter.set_profiler(profiler)
+    }
+}
+
+fn function_label(module_id: &ModuleId, function_name: &str) -> String {
+    format!(
+        "{}::{}::{function_name}",
+        module_id
+            .address()
+            .to_canonical_string(/* with_prefix */ true),
+        module_id.name()
+    )
+}
+
+fn render_bytes(bytes: &[u8]) -> String {
+    // Only the bytes that fit in the length limit are rendered.
+    let rendered_bytes = bytes.len().min(MAX_RENDERED_VALUE_LENGTH / 2);
+    let mut rendered = String::with_capacity(2 * rendered_bytes + 5);
+    rendered.push_str("x\"");
+    for byte in &bytes[..rendered_bytes] {
+        let _ = write!(rendered, "{byte:02x}");
+    }
+    rendered.push('"');
+    if rendered_bytes < bytes.len() {
+        rendered.push_str("..");
+    }
+    rendered
+}
+
+fn render_value(value: &impl ValueView) -> String {
+    let mut renderer = ValueRenderer::default();
+    value.visit(&mut renderer);
+    renderer.finish()
+}
+
+/// The ID of a dynamic field parent or child is the first address found in
+/// the argument, which is either an address, a `UID` or an object.
+fn first_object_id(value: Option<&impl ValueView>) -> ObjectID {
+    let mut finder = FirstAddress::default();
+    if let Some(value) = value {
+        value.visit(&mut finder);
+    }
+    finder.0.unwrap_or(AccountAddress::ZERO).into()
+}
+
+/// A container being rendered, with the number of its elements still to be
+/// visited.
+struct OpenContainer {
+    close: &'static str,
+    remaining: usize,
+    first: bool,
+}
+
+/// Renders a value in a Move-like syntax. Visitors are called in pre-order,
+/// so containers are closed once all their elements have been visited.
+///
+/// Once `MAX_RENDERED_VALUE_LENGTH` is exceeded, the remaining values are
+/// skipped: containers are not entered and leaves are ignored.
+#[derive(Default)]
+struct ValueRenderer {
+    rendered: String,
+    open: Vec<OpenContainer>,
+}
+
+impl ValueRenderer {
+    fn has_budget(&self) -> bool {
+        self.rendered.len() <= MAX_RENDERED_VALUE_LENGTH
+    }
+
+    fn finish(mut self) -> String {
+        if !self.has_budget() {
+            // Rendered values are ASCII, so any index is a char boundary.
+            self.rendered.truncate(MAX_RENDERED_VALUE_LENGTH);
+            self.rendered.push_str("..");
+        }
+        self.rendered
+    }
+
+    fn begin_value(&mut self) {
+        if let Some(container) = self.open.last_mut() {
+            if !container.first {
+                self.rendered.push_str(", ");
+            }
+            container.first = false;
+        }
+    }
+
+    fn end_value(&mut self) {
+        while let Some(container) = self.open.last_mut() {
+            container.remaining -= 1;
+            if container.remaining > 0 {
+                break;
+            }
+            self.rendered.push_str(container.close);
+            self.open.pop();
+        }
+    }
+
+    fn leaf(&mut self, rendered: impl std::fmt::Display) {
+        if !self.has_budget() {
+            return;
+        }
+        self.begin_value();
+        let _ = write!(self.rendered, "{rendered}");
+        self.end_value();
+    }
+
+    fn container(&mut self, open: &str, close: &'static str, empty: &str, len: usize) -> bool {
+        if !self.has_budget() {
+            return false;
+        }
+        self.begin_value();
+        if len == 0 {
+            self.rendered.push_str(empty);
+            self.end_value();
+        } else {
+            self.rendered.push_str(open);
+            self.open.push(OpenContainer {
+                close,
+                remaining: len,
+                first: true,
+            });
+        }
+        true
+    }
+}
+
+impl ValueVisitor for ValueRenderer {
+    fn visit_u8(&mut self, _depth: usize, val: u8) {
+        self.leaf(val)
+    }
+
+    fn visit_u16(&mut self, _depth: usize, val: u16) {
+        self.leaf(val)
+    }
+
+    fn visit_u32(&mut self, _depth: usize, val: u32) {
+        self.leaf(val)
+    }
+
+    fn visit_u64(&mut self, _depth: usize, val: u64) {
+        self.leaf(val)
+    }
+
+    fn visit_u128(&mut self, _depth: usize, val: u128) {
+        self.leaf(val)
+    }
+
+    fn visit_u256(&mut self, _depth: usize, val: U256) {
+        self.leaf(val)
+    }
+
+    fn visit_bool(&mut self, _depth: usize, val: bool) {
+        self.leaf(val)
+    }
+
+    fn visit_address(&mut self, _depth: usize, val: AccountAddress) {
+        self.leaf(format_args!(
+            "@{}",
+            val.to_canonical_string(/* with_prefix */ true)
+        ))
+    }
+
+    fn visit_struct(&mut self, _depth: usize, len: usize) -> bool {
+        self.container("{ ", " }", "{}", len)
+    }
+
+    fn visit_variant(&mut self, depth: usize, len: usize) -> bool {
+        self.visit_struct(depth, len)
+    }
+
+    fn visit_vec(&mut self, _depth: usize, len: usize) -> bool {
+        self.container("[", "]", "[]", len)
+    }
+
+    fn visit_ref(&mut self, _depth: usize, _is_global: bool) -> bool {
+        if !self.has_budget() {
+            return false;
+        }
+        self.begin_value();
+        self.rendered.push('&');
+        self.open.push(OpenContainer {
+            close: "",
+            remaining: 1,
+            first: true,
+        });
+        true
+    }
+
+    fn visit_vec_u8(&mut self, _depth: usize, vals: &[u8]) {
+        if self.has_budget() {
+            self.leaf(render_bytes(vals))
+        }
+    }
+}
+
+#[derive(Default)]
+struct FirstAddress(Option<AccountAddress>);
+
+impl ValueVisitor for FirstAddress {
+    fn visit_u8(&mut self, _depth: usize, _val: u8) {}
+
+    fn visit_u16(&mut self, _depth: usize, _val: u16) {}
+
+    fn visit_u32(&mut self, _depth: usize, _val: u32) {}
+
+    fn visit_u64(&mut self, _depth: usize, _val: u64) {}
+
+    fn visit_u128(&mut self, _depth: usize, _val: u128) {}
+
+    fn visit_u256(&mut self, _depth: usize, _val: U256) {}
+
+    fn visit_bool(&mut self, _depth: usize, _val: bool) {}
+
+    fn visit_address(&mut self, _depth: usize, val: AccountAddress) {
+        self.0.get_or_insert(val);
+    }
+
+    fn visit_struct(&mut self, _depth: usize, _len: usize) -> bool {
+        self.0.is_none()
+    }
+
+    fn visit_variant(&mut self, _depth: usize, _len: usize) -> bool {
+        self.0.is_none()
+    }
+
+    fn visit_vec(&mut self, _depth: usize, _len: usize) -> bool {
+        self.0.is_none()
+    }
+
+    fn visit_ref(&mut self, _depth: usize, _is_global: bool) -> bool {
+        self.0.is_none()
+    }
+
+    fn visit_vec_u8(&mut self, _depth: usize, _vals: &[u8]) {}
+}
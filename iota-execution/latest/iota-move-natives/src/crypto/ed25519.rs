@@ -9,20 +9,30 @@ use fastcrypto::{
     traits::{ToFromBytes, VerifyingKey},
 };
 use move_binary_format::errors::PartialVMResult;
-use move_core_types::gas_algebra::InternalGas;
+use move_core_types::{gas_algebra::InternalGas, vm_status::StatusCode};
 use move_vm_runtime::{native_charge_gas_early_exit, native_functions::NativeContext};
 use move_vm_types::{
     loaded_data::runtime_types::Type,
-    natives::function::NativeResult,
+    natives::function::{NativeResult, PartialVMError},
     pop_arg,
     values::{Value, VectorRef},
 };
 use smallvec::smallvec;
 
-use crate::NativesCostTable;
+use crate::{NativesCostTable, object_runtime::ObjectRuntime};
 
 const ED25519_BLOCK_SIZE: usize = 128;
 
+pub const NOT_SUPPORTED_ERROR: u64 = 1;
+
+fn is_batch_verify_supported(context: &NativeContext) -> bool {
+    context
+        .extensions()
+        .get::<ObjectRuntime>()
+        .protocol_config
+        .enable_ed25519_batch_verify()
+}
+
 #[derive(Clone)]
 pub struct Ed25519VerifyCostParams {
     /// Base cost for invoking the `ed25519_verify` function
@@ -96,3 +106,134 @@ pub fn ed25519_verify(
         public_key.verify(&msg_ref, &signature).is_ok()
     )]))
 }
+
+#[derive(Clone)]
+pub struct Ed25519BatchVerifyCostParams {
+    /// Base cost for invoking the `ed25519_batch_verify_internal` function
+    pub ed25519_batch_verify_cost_base: Option<InternalGas>,
+    /// Cost per signature in the batch
+    pub ed25519_batch_verify_cost_per_signature: Option<InternalGas>,
+    /// Cost per byte of all `msgs`
+    pub ed25519_batch_verify_msg_cost_per_byte: Option<InternalGas>,
+    /// Cost per block of each of the `msgs`, where a block is 128 bytes
+    pub ed25519_batch_verify_msg_cost_per_block: Option<InternalGas>,
+}
+
+fn batch_verify_cost(cost: Option<InternalGas>) -> PartialVMResult<InternalGas> {
+    cost.ok_or_else(|| {
+        PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+            .with_message("Gas cost for ed25519_batch_verify not available".to_string())
+    })
+}
+
+/// ****************************************************************************
+/// ********************* native fun ed25519_batch_verify_internal
+/// Implementation of the Move native function
+/// `ed25519::ed25519_batch_verify_internal(signatures: &vector<vector<u8>>,
+/// public_keys: &vector<vector<u8>>, msgs: &vector<vector<u8>>): bool;`
+///   gas cost: ed25519_batch_verify_cost_base
+/// | base cost for function call and fixed opers
+///              + ed25519_batch_verify_cost_per_signature * signatures.len()
+///              + ed25519_batch_verify_msg_cost_per_byte * sum(msg.len())
+///              + ed25519_batch_verify_msg_cost_per_block *
+///                sum(num_blocks(msg))
+/// Note: each block is of size `ED25519_BLOCK_SIZE` bytes, and we round up.
+/// The signatures are checked together with a single batch verification, which
+/// accepts a batch if and only if every signature would be accepted by
+/// `ed25519_verify`. The batch is rejected if it is empty or the vector lengths
+/// differ.
+/// ****************************************************************************
+/// *******************
+pub fn ed25519_batch_verify_internal(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    let cost = context.gas_used();
+    if !is_batch_verify_supported(context) {
+        return Ok(NativeResult::err(cost, NOT_SUPPORTED_ERROR));
+    }
+
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 3);
+
+    // Load the cost parameters from the protocol config
+    let cost_params = &context
+        .extensions()
+        .get::<NativesCostTable>()
+        .ed25519_batch_verify_cost_params
+        .clone();
+    // Charge the base cost for this oper
+    native_charge_gas_early_exit!(
+        context,
+        batch_verify_cost(cost_params.ed25519_batch_verify_cost_base)?
+    );
+
+    // Only borrow the elements, their bytes are read after the size dependent
+    // costs have been charged.
+    let element_type = Type::Vector(Box::new(Type::U8));
+    let borrow_elements = |vector: VectorRef| -> PartialVMResult<Vec<VectorRef>> {
+        let length = vector.len(&element_type)?.value_as::<u64>()?;
+        (0..length)
+            .map(|i| {
+                vector
+                    .borrow_elem(i as usize, &element_type)?
+                    .value_as::<VectorRef>()
+            })
+            .collect()
+    };
+    let msgs = borrow_elements(pop_arg!(args, VectorRef))?;
+    let public_keys = borrow_elements(pop_arg!(args, VectorRef))?;
+    let signatures = borrow_elements(pop_arg!(args, VectorRef))?;
+
+    // Charge the arg size dependent costs
+    native_charge_gas_early_exit!(
+        context,
+        batch_verify_cost(cost_params.ed25519_batch_verify_cost_per_signature)?
+            * (signatures.len() as u64).into()
+    );
+    let msg_cost_per_byte = batch_verify_cost(cost_params.ed25519_batch_verify_msg_cost_per_byte)?;
+    let msg_cost_per_block =
+        batch_verify_cost(cost_params.ed25519_batch_verify_msg_cost_per_block)?;
+    for msg in &msgs {
+        let msg_len = msg.as_bytes_ref().len();
+        native_charge_gas_early_exit!(
+            context,
+            msg_cost_per_byte * (msg_len as u64).into()
+                + msg_cost_per_block
+                    * (((msg_len + ED25519_BLOCK_SIZE - 1) / ED25519_BLOCK_SIZE) as u64).into()
+        );
+    }
+    let cost = context.gas_used();
+
+    if signatures.is_empty()
+        || signatures.len() != public_keys.len()
+        || signatures.len() != msgs.len()
+    {
+        return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
+    }
+
+    let Ok(signatures) = signatures
+        .iter()
+        .map(|signature| <Ed25519Signature as ToFromBytes>::from_bytes(&signature.as_bytes_ref()))
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
+    };
+
+    let Ok(public_keys) = public_keys
+        .iter()
+        .map(|public_key| <Ed25519PublicKey as ToFromBytes>::from_bytes(&public_key.as_bytes_ref()))
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
+    };
+
+    let msgs = msgs.iter().map(VectorRef::as_bytes_ref).collect::<Vec<_>>();
+    let msgs = msgs.iter().map(|msg| msg.as_slice()).collect::<Vec<_>>();
+
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(
+        Ed25519PublicKey::verify_batch_empty_fail_different_msg(&msgs, &public_keys, &signatures)
+            .is_ok()
+    )]))
+}
@@ -12,5 +12,6 @@ pub mod group_ops;
 pub mod hash;
 pub mod hmac;
 pub mod poseidon;
+pub mod schnorr;
 pub mod vdf;
 pub mod zklogin;
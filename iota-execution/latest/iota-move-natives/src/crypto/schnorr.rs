@@ -0,0 +1,117 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::VecDeque;
+
+use k256::schnorr::{Signature, VerifyingKey};
+use move_binary_format::errors::PartialVMResult;
+use move_core_types::{gas_algebra::InternalGas, vm_status::StatusCode};
+use move_vm_runtime::{native_charge_gas_early_exit, native_functions::NativeContext};
+use move_vm_types::{
+    loaded_data::runtime_types::Type,
+    natives::function::{NativeResult, PartialVMError},
+    pop_arg,
+    values::{Value, VectorRef},
+};
+use smallvec::smallvec;
+
+use crate::{NativesCostTable, object_runtime::ObjectRuntime};
+
+pub const NOT_SUPPORTED_ERROR: u64 = 0;
+
+const BIP340_BLOCK_SIZE: usize = 64;
+
+fn is_supported(context: &NativeContext) -> bool {
+    context
+        .extensions()
+        .get::<ObjectRuntime>()
+        .protocol_config
+        .enable_schnorr_bip340()
+}
+
+#[derive(Clone)]
+pub struct SchnorrBip340VerifyCostParams {
+    /// Base cost for invoking the `bip340_verify` function
+    pub schnorr_bip340_verify_cost_base: Option<InternalGas>,
+    /// Cost per byte of `msg`
+    pub schnorr_bip340_verify_msg_cost_per_byte: Option<InternalGas>,
+    /// Cost per block of `msg`, where a block is 64 bytes
+    pub schnorr_bip340_verify_msg_cost_per_block: Option<InternalGas>,
+}
+
+fn verify_cost(cost: Option<InternalGas>) -> PartialVMResult<InternalGas> {
+    cost.ok_or_else(|| {
+        PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+            .with_message("Gas cost for bip340_verify not available".to_string())
+    })
+}
+
+/// ****************************************************************************
+/// ********************* native fun bip340_verify
+/// Implementation of the Move native function
+/// `schnorr::bip340_verify(signature: &vector<u8>, public_key: &vector<u8>,
+/// msg: &vector<u8>): bool;`   gas cost: schnorr_bip340_verify_cost_base
+/// | base cost for function call and fixed opers
+///              + schnorr_bip340_verify_msg_cost_per_byte * msg.len()   | cost
+///                depends on length of message
+///              + schnorr_bip340_verify_msg_cost_per_block * num_blocks(msg) |
+///                cost depends on number of blocks in message
+/// Note: each block is of size `BIP340_BLOCK_SIZE` bytes, and we round up.
+///       `signature` (64 bytes) and `public_key` (32 bytes, x-only) are fixed
+/// size, so their costs are included in the base cost.
+/// ****************************************************************************
+/// *******************
+pub fn bip340_verify(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    let cost = context.gas_used();
+    if !is_supported(context) {
+        return Ok(NativeResult::err(cost, NOT_SUPPORTED_ERROR));
+    }
+
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 3);
+
+    // Load the cost parameters from the protocol config
+    let cost_params = &context
+        .extensions()
+        .get::<NativesCostTable>()
+        .schnorr_bip340_verify_cost_params
+        .clone();
+    // Charge the base cost for this oper
+    native_charge_gas_early_exit!(
+        context,
+        verify_cost(cost_params.schnorr_bip340_verify_cost_base)?
+    );
+
+    let msg = pop_arg!(args, VectorRef);
+    let msg_ref = msg.as_bytes_ref();
+    let public_key_bytes = pop_arg!(args, VectorRef);
+    let public_key_bytes_ref = public_key_bytes.as_bytes_ref();
+    let signature_bytes = pop_arg!(args, VectorRef);
+    let signature_bytes_ref = signature_bytes.as_bytes_ref();
+
+    // Charge the arg size dependent costs
+    native_charge_gas_early_exit!(
+        context,
+        verify_cost(cost_params.schnorr_bip340_verify_msg_cost_per_byte)?
+            * (msg_ref.len() as u64).into()
+            + verify_cost(cost_params.schnorr_bip340_verify_msg_cost_per_block)?
+                * (((msg_ref.len() + BIP340_BLOCK_SIZE - 1) / BIP340_BLOCK_SIZE) as u64).into()
+    );
+    let cost = context.gas_used();
+
+    let Ok(signature) = Signature::try_from(signature_bytes_ref.as_slice()) else {
+        return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
+    };
+
+    let Ok(public_key) = VerifyingKey::from_bytes(&public_key_bytes_ref) else {
+        return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
+    };
+
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(
+        public_key.verify_raw(&msg_ref, &signature).is_ok()
+    )]))
+}
@@ -42,7 +42,7 @@ use self::{
         ecvrf,
         ecvrf::EcvrfEcvrfVerifyCostParams,
         ed25519,
-        ed25519::Ed25519VerifyCostParams,
+        ed25519::{Ed25519BatchVerifyCostParams, Ed25519VerifyCostParams},
         groth16,
         groth16::{
             Groth16PrepareVerifyingKeyCostParams, Groth16VerifyGroth16ProofInternalCostParams,
@@ -51,7 +51,8 @@ use self::{
         hash::{HashBlake2b256CostParams, HashKeccak256CostParams},
         hmac,
         hmac::HmacHmacSha3256CostParams,
-        poseidon,
+        poseidon, schnorr,
+        schnorr::SchnorrBip340VerifyCostParams,
     },
     dynamic_field::{
         DynamicFieldAddChildObjectCostParams, DynamicFieldBorrowChildObjectCostParams,
@@ -151,6 +152,7 @@ pub struct NativesCostTable {
 
     // ed25519
     pub ed25519_verify_cost_params: Ed25519VerifyCostParams,
+    pub ed25519_batch_verify_cost_params: Ed25519BatchVerifyCostParams,
 
     // groth16
     pub groth16_prepare_verifying_key_cost_params: Groth16PrepareVerifyingKeyCostParams,
@@ -173,6 +175,9 @@ pub struct NativesCostTable {
     // vdf
     pub vdf_cost_params: VDFCostParams,
 
+    // schnorr
+    pub schnorr_bip340_verify_cost_params: SchnorrBip340VerifyCostParams,
+
     // zklogin
     pub check_zklogin_id_cost_params: CheckZkloginIdCostParams,
     pub check_zklogin_issuer_cost_params: CheckZkloginIssuerCostParams,
@@ -312,6 +317,20 @@ impl NativesCostTable {
                     .ed25519_ed25519_verify_msg_cost_per_block()
                     .into(),
             },
+            ed25519_batch_verify_cost_params: Ed25519BatchVerifyCostParams {
+                ed25519_batch_verify_cost_base: protocol_config
+                    .ed25519_batch_verify_cost_base_as_option()
+                    .map(Into::into),
+                ed25519_batch_verify_cost_per_signature: protocol_config
+                    .ed25519_batch_verify_cost_per_signature_as_option()
+                    .map(Into::into),
+                ed25519_batch_verify_msg_cost_per_byte: protocol_config
+                    .ed25519_batch_verify_msg_cost_per_byte_as_option()
+                    .map(Into::into),
+                ed25519_batch_verify_msg_cost_per_block: protocol_config
+                    .ed25519_batch_verify_msg_cost_per_block_as_option()
+                    .map(Into::into),
+            },
             // hash
             hash_blake2b256_cost_params: HashBlake2b256CostParams {
                 hash_blake2b256_cost_base: protocol_config.hash_blake2b256_cost_base().into(),
@@ -648,6 +667,17 @@ impl NativesCostTable {
                     .vdf_hash_to_input_cost_as_option()
                     .map(Into::into),
             },
+            schnorr_bip340_verify_cost_params: SchnorrBip340VerifyCostParams {
+                schnorr_bip340_verify_cost_base: protocol_config
+                    .schnorr_bip340_verify_cost_base_as_option()
+                    .map(Into::into),
+                schnorr_bip340_verify_msg_cost_per_byte: protocol_config
+                    .schnorr_bip340_verify_msg_cost_per_byte_as_option()
+                    .map(Into::into),
+                schnorr_bip340_verify_msg_cost_per_block: protocol_config
+                    .schnorr_bip340_verify_msg_cost_per_block_as_option()
+                    .map(Into::into),
+            },
         }
     }
 }
@@ -843,6 +873,11 @@ pub fn all_natives(silent: bool, protocol_config: &ProtocolConfig) -> NativeFunc
             "ed25519_verify",
             make_native!(ed25519::ed25519_verify),
         ),
+        (
+            "ed25519",
+            "ed25519_batch_verify_internal",
+            make_native!(ed25519::ed25519_batch_verify_internal),
+        ),
         ("event", "emit", make_native!(event::emit)),
         (
             "event",
@@ -1040,6 +1075,11 @@ pub fn all_natives(silent: bool, protocol_config: &ProtocolConfig) -> NativeFunc
             "hash_to_input_internal",
             make_native!(vdf::hash_to_input_internal),
         ),
+        (
+            "schnorr",
+            "bip340_verify",
+            make_native!(schnorr::bip340_verify),
+        ),
         (
             "ecdsa_k1",
             "secp256k1_sign",
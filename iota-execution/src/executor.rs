@@ -11,7 +11,7 @@ use iota_types::{
     digests::TransactionDigest,
     effects::TransactionEffects,
     error::ExecutionError,
-    execution::{ExecutionResult, TypeLayoutStore},
+    execution::{ExecutionInstrumentation, ExecutionResult, TypeLayoutStore},
     gas::IotaGasStatus,
     inner_temporary_store::InnerTemporaryStore,
     layout_resolver::LayoutResolver,
@@ -49,6 +49,37 @@ pub trait Executor {
         Result<(), ExecutionError>,
     );
 
+    /// Like `execute_transaction_to_effects`, additionally recording the
+    /// diagnostics requested in `instrumentation`.
+    fn dry_run_transaction(
+        &self,
+        store: &dyn BackingStore,
+        // Configuration
+        protocol_config: &ProtocolConfig,
+        metrics: Arc<LimitsMetrics>,
+        enable_expensive_checks: bool,
+        certificate_deny_set: &HashSet<TransactionDigest>,
+        // Epoch
+        epoch_id: &EpochId,
+        epoch_timestamp_ms: u64,
+        // Transaction Inputs
+        input_objects: CheckedInputObjects,
+        // Gas related
+        gas_coins: Vec<ObjectRef>,
+        gas_status: IotaGasStatus,
+        // Transaction
+        transaction_kind: TransactionKind,
+        transaction_signer: IotaAddress,
+        transaction_digest: TransactionDigest,
+        // Diagnostics
+        instrumentation: &mut ExecutionInstrumentation,
+    ) -> (
+        InnerTemporaryStore,
+        IotaGasStatus,
+        TransactionEffects,
+        Result<(), ExecutionError>,
+    );
+
     fn dev_inspect_transaction(
         &self,
         store: &dyn BackingStore,
@@ -70,6 +101,8 @@ pub trait Executor {
         transaction_signer: IotaAddress,
         transaction_digest: TransactionDigest,
         skip_all_checks: bool,
+        // Diagnostics
+        instrumentation: &mut ExecutionInstrumentation,
     ) -> (
         InnerTemporaryStore,
         IotaGasStatus,
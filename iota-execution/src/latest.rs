@@ -18,7 +18,7 @@ use iota_types::{
     digests::TransactionDigest,
     effects::TransactionEffects,
     error::{ExecutionError, IotaError, IotaResult},
-    execution::{ExecutionResult, TypeLayoutStore},
+    execution::{ExecutionInstrumentation, ExecutionResult, TypeLayoutStore},
     gas::IotaGasStatus,
     inner_temporary_store::InnerTemporaryStore,
     layout_resolver::LayoutResolver,
@@ -98,6 +98,48 @@ impl executor::Executor for Executor {
             metrics,
             enable_expensive_checks,
             certificate_deny_set,
+            &mut ExecutionInstrumentation::default(),
+        )
+    }
+
+    fn dry_run_transaction(
+        &self,
+        store: &dyn BackingStore,
+        protocol_config: &ProtocolConfig,
+        metrics: Arc<LimitsMetrics>,
+        enable_expensive_checks: bool,
+        certificate_deny_set: &HashSet<TransactionDigest>,
+        epoch_id: &EpochId,
+        epoch_timestamp_ms: u64,
+        input_objects: CheckedInputObjects,
+        gas_coins: Vec<ObjectRef>,
+        gas_status: IotaGasStatus,
+        transaction_kind: TransactionKind,
+        transaction_signer: IotaAddress,
+        transaction_digest: TransactionDigest,
+        instrumentation: &mut ExecutionInstrumentation,
+    ) -> (
+        InnerTemporaryStore,
+        IotaGasStatus,
+        TransactionEffects,
+        Result<(), ExecutionError>,
+    ) {
+        execute_transaction_to_effects::<execution_mode::Normal>(
+            store,
+            input_objects,
+            gas_coins,
+            gas_status,
+            transaction_kind,
+            transaction_signer,
+            transaction_digest,
+            &self.0,
+            epoch_id,
+            epoch_timestamp_ms,
+            protocol_config,
+            metrics,
+            enable_expensive_checks,
+            certificate_deny_set,
+            instrumentation,
         )
     }
 
@@ -117,6 +159,7 @@ impl executor::Executor for Executor {
         transaction_signer: IotaAddress,
         transaction_digest: TransactionDigest,
         skip_all_checks: bool,
+        instrumentation: &mut ExecutionInstrumentation,
     ) -> (
         InnerTemporaryStore,
         IotaGasStatus,
@@ -139,6 +182,7 @@ impl executor::Executor for Executor {
                 metrics,
                 enable_expensive_checks,
                 certificate_deny_set,
+                instrumentation,
             )
         } else {
             execute_transaction_to_effects::<execution_mode::DevInspect<false>>(
@@ -156,6 +200,7 @@ impl executor::Executor for Executor {
                 metrics,
                 enable_expensive_checks,
                 certificate_deny_set,
+                instrumentation,
             )
         }
     }